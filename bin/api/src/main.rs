@@ -0,0 +1,72 @@
+use async_std::{
+    net::TcpListener,
+    task,
+};
+use clap::Clap;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use sunshine_bounty_ffi::ffi_utils::async_std::sync::RwLock;
+use sunshine_client_utils::{
+    Client as _,
+    Result,
+};
+use test_client::{
+    health::{
+        self,
+        Health,
+    },
+    Client,
+};
+
+mod server;
+
+#[derive(Clone, Debug, Clap)]
+pub struct Opts {
+    #[clap(short = 'p', long = "path")]
+    pub path: Option<PathBuf>,
+    #[clap(short = 'c', long = "chain-spec-path")]
+    pub chain_spec_path: PathBuf,
+    /// The address to serve the api on
+    #[clap(short = 'l', long = "listen", default_value = "127.0.0.1:8080")]
+    pub listen: String,
+    /// Serve `/health` and `/metrics` on this port, off when not set
+    #[clap(long = "health-port")]
+    pub health_port: Option<u16>,
+    /// Seconds between polls of the node for `/health`
+    #[clap(long = "health-interval", default_value = "6")]
+    pub health_interval: u64,
+}
+
+#[async_std::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+    let opts: Opts = Opts::parse();
+    let root = if let Some(root) = opts.path {
+        root
+    } else {
+        dirs::config_dir().unwrap().join("sunshine-bounty-api")
+    };
+    let client = Arc::new(RwLock::new(
+        Client::new(&root, &opts.chain_spec_path).await?,
+    ));
+    let health = Health::new();
+    if let Some(port) = opts.health_port {
+        let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+        task::spawn(health::serve(health.clone(), listener));
+        let (client, health) = (client.clone(), health.clone());
+        let interval = Duration::from_secs(opts.health_interval.max(1));
+        task::spawn(async move {
+            loop {
+                // a failed poll is reported by `/health`
+                let _ = health.poll(&*client.read().await).await;
+                task::sleep(interval).await;
+            }
+        });
+    }
+    let app = server::app(client, health);
+    app.listen(opts.listen).await?;
+    Ok(())
+}
@@ -0,0 +1,311 @@
+//! Read-only HTTP api for web frontends
+//!
+//! The endpoints answer with the same JSON as the ffi calls they wrap, so
+//! apps and web frontends share the dtos of `sunshine_bounty_ffi::dto`:
+//!
+//! - `GET /bounties?min=..&tag=..` lists open bounties, see
+//!   `client_bounty_open_bounties`
+//! - `GET /bounties/:id` gets a bounty, see `client_bounty_get`
+//! - `GET /votes/:id` gets the progress of a vote, see `client_vote_status`
+//! - `GET /orgs/:id/members` lists the members of an org, see
+//!   `client_org_members`
+//! - `GET /events` upgrades to a websocket pushing every event of the chain
+//!   as an `EventInformation`
+//!
+//! Events waiting to be pushed count towards the queue depth of the
+//! `Health` served on `--health-port`.
+//!
+//! Nothing is signed, the client does not need a key.
+use serde::Deserialize;
+use std::sync::Arc;
+use substrate_subxt::{
+    Error,
+    EventSubscription,
+};
+use sunshine_bounty_ffi::{
+    dto::{
+        EventInformation,
+        SCHEMA_VERSION,
+    },
+    error::{
+        ErrorCode,
+        FfiError,
+    },
+    ffi::{
+        Bounty,
+        Org,
+        Vote,
+    },
+    ffi_utils::async_std::sync::RwLock,
+};
+use sunshine_client_utils::{
+    Client as _,
+    Result,
+};
+use test_client::{
+    health::Health,
+    Client,
+    Node,
+};
+use tide::{
+    http::mime,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+use tide_websockets::{
+    WebSocket,
+    WebSocketConnection,
+};
+
+#[derive(Clone)]
+pub struct State {
+    client: Arc<RwLock<Client>>,
+    health: Health,
+}
+
+pub fn app(client: Arc<RwLock<Client>>, health: Health) -> Server<State> {
+    let mut app = tide::with_state(State { client, health });
+    app.at("/bounties").get(open_bounties);
+    app.at("/bounties/:id").get(bounty);
+    app.at("/votes/:id").get(vote);
+    app.at("/orgs/:id/members").get(org_members);
+    app.at("/events")
+        .get(WebSocket::new(|req: Request<State>, stream| {
+            async move { push_events(req.state(), stream).await }
+        }));
+    app
+}
+
+#[derive(Deserialize)]
+struct BountiesQuery {
+    min: Option<String>,
+    tag: Option<String>,
+}
+
+async fn open_bounties(req: Request<State>) -> tide::Result {
+    let query: BountiesQuery = req.query()?;
+    let reply = Bounty::<Client, Node>::new(&req.state().client)
+        .open_bounties(
+            query.min.as_deref().unwrap_or("0"),
+            query.tag.as_deref(),
+        )
+        .await;
+    Ok(respond(reply))
+}
+
+async fn bounty(req: Request<State>) -> tide::Result {
+    let reply = Bounty::<Client, Node>::new(&req.state().client)
+        .get(req.param("id")?)
+        .await;
+    Ok(respond(reply))
+}
+
+async fn vote(req: Request<State>) -> tide::Result {
+    let reply = Vote::<Client, Node>::new(&req.state().client)
+        .status(req.param("id")?)
+        .await;
+    Ok(respond(reply))
+}
+
+async fn org_members(req: Request<State>) -> tide::Result {
+    let reply = Org::<Client, Node>::new(&req.state().client)
+        .members(req.param("id")?)
+        .await;
+    Ok(respond(reply))
+}
+
+async fn push_events(
+    state: &State,
+    stream: WebSocketConnection,
+) -> tide::Result<()> {
+    let client = state.client.read().await;
+    let chain = client.chain_client();
+    let sub = chain.subscribe_events().await?;
+    let mut events = EventSubscription::new(sub, chain.events_decoder());
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            // the dispatch errors of failed extrinsics are not events
+            Err(Error::Runtime(_)) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        state.health.enqueued(1);
+        let sent = stream
+            .send_json(&EventInformation {
+                schema_version: SCHEMA_VERSION,
+                module: event.module,
+                variant: event.variant,
+                data: format!("0x{}", hex::encode(&event.data)),
+            })
+            .await;
+        state.health.handled(1);
+        sent?;
+    }
+    Ok(())
+}
+
+/// Answers with the JSON envelope of the ffi call, choosing the status of
+/// an error by its code
+fn respond(reply: Result<String>) -> Response {
+    let (status, body) = match reply {
+        Ok(json) => (StatusCode::Ok, json),
+        Err(err) => {
+            let err = FfiError::from(err);
+            (status_of(err.code), err.to_string())
+        }
+    };
+    Response::builder(status)
+        .body(body)
+        .content_type(mime::JSON)
+        .build()
+}
+
+fn status_of(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::InvalidNumber
+        | ErrorCode::InvalidAccountId
+        | ErrorCode::InvalidBountyTag
+        | ErrorCode::InvalidOrgHandle
+        | ErrorCode::InvalidCid
+        | ErrorCode::InvalidAirdrop => StatusCode::BadRequest,
+        ErrorCode::MetadataNotFound | ErrorCode::BountyNotFound => {
+            StatusCode::NotFound
+        }
+        ErrorCode::ConnectionFailed => StatusCode::BadGateway,
+        _ => StatusCode::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use test_client::{
+        bounty::BountyClient,
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        org::OrgClient,
+        utils::{
+            bounty::{
+                bounty_tag,
+                SubmissionKind,
+            },
+            organization::OrgRep,
+            vote::{
+                Threshold,
+                VoterView,
+            },
+        },
+        vote::VoteClient,
+        GithubIssue,
+        TextBlock,
+    };
+    use tide::http::{
+        self,
+        Method,
+        Url,
+    };
+
+    async fn get(app: &Server<State>, path: &str) -> (StatusCode, Value) {
+        let url = Url::parse("http://localhost").unwrap().join(path).unwrap();
+        let mut res: http::Response = app
+            .respond(http::Request::new(Method::Get, url))
+            .await
+            .unwrap();
+        let body = res.body_string().await.unwrap();
+        (res.status(), serde_json::from_str(&body).unwrap())
+    }
+
+    #[async_std::test]
+    async fn endpoints_answer_with_the_ffi_dtos() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 124,
+        };
+        client
+            .post_bounty(
+                bounty,
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![bounty_tag("docs").unwrap()],
+            )
+            .await
+            .unwrap();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice, bob],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(1, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        client
+            .submit_vote(vote, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        let app = app(Arc::new(RwLock::new(client)), Health::new());
+
+        let (status, json) = get(&app, "/bounties/1").await;
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(json["ok"]["issue_number"], 124);
+        assert_eq!(json["ok"]["total"], "10");
+        assert_eq!(json["ok"]["tags"], serde_json::json!(["docs"]));
+
+        let (status, json) = get(&app, "/bounties?min=5&tag=docs").await;
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(json["ok"].as_array().unwrap().len(), 1);
+        let (_, json) = get(&app, "/bounties?tag=rust").await;
+        assert!(json["ok"].as_array().unwrap().is_empty());
+
+        let (status, json) = get(&app, &format!("/votes/{}", vote)).await;
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(json["ok"]["outcome"], "Approved");
+
+        let (status, json) = get(&app, &format!("/orgs/{}/members", org)).await;
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(json["ok"].as_array().unwrap().len(), 2);
+    }
+
+    #[async_std::test]
+    async fn invalid_arguments_are_bad_requests() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let app = app(Arc::new(RwLock::new(client)), Health::new());
+
+        let (status, json) = get(&app, "/bounties/one").await;
+        assert_eq!(status, StatusCode::BadRequest);
+        assert_eq!(json["code"], "INVALID_NUMBER");
+
+        let (status, json) = get(&app, "/bounties?tag=Docs!").await;
+        assert_eq!(status, StatusCode::BadRequest);
+        assert_eq!(json["code"], "INVALID_BOUNTY_TAG");
+    }
+}
@@ -1,19 +1,34 @@
+use crate::error::EXIT_CODES;
 use clap::Clap;
 use std::path::PathBuf;
+#[cfg(feature = "tui")]
+use sunshine_bounty_cli::dashboard;
 use sunshine_bounty_cli::{
+    address,
+    backup,
     bank,
     bounty,
     donate,
     org,
+    recovery,
+    run,
+    scheme::KeyScheme,
+    script,
     shares,
     vote,
+    watch,
 };
 use sunshine_cli_utils::{
     key,
     wallet,
 };
+use test_client::{
+    era::TransactionEra,
+    submit::WaitFor,
+};
 
 #[derive(Clone, Debug, Clap)]
+#[clap(after_help = EXIT_CODES)]
 pub struct Opts {
     #[clap(subcommand)]
     pub cmd: SubCommand,
@@ -21,6 +36,42 @@ pub struct Opts {
     pub path: Option<PathBuf>,
     #[clap(short = 'c', long = "chain-spec-path")]
     pub chain_spec_path: Option<PathBuf>,
+    /// Submit without confirming how arguments were resolved or what
+    /// irreversible commands will do, for scripts
+    #[clap(short = 'y', long = "yes")]
+    pub yes: bool,
+    /// Print the client's RPC, extrinsic and offchain spans to stderr
+    #[clap(long = "trace")]
+    pub trace: bool,
+    /// How long to wait for extrinsics: none, in-block or finalized
+    #[clap(long = "wait-for", default_value = "in-block")]
+    pub wait_for: WaitFor,
+    /// How many seconds to wait for the node before giving up
+    #[clap(long = "timeout", default_value = "60")]
+    pub timeout: u64,
+    /// Era of extrinsics: immortal or the number of blocks they are valid
+    #[clap(long = "era", default_value = "immortal")]
+    pub era: TransactionEra,
+    /// How often to sign and submit an extrinsic again if the node drops it
+    #[clap(long = "resubmissions", default_value = "2")]
+    pub resubmissions: u32,
+    /// Print the hash of each extrinsic and every status the node reports
+    #[clap(short = 'v', long = "verbose")]
+    pub verbose: bool,
+    /// Dry run extrinsics and print what they would do without submitting
+    #[clap(long = "dry-run")]
+    pub dry_run: bool,
+    /// Dry run every extrinsic first and abort before paying fees if it
+    /// would fail
+    #[clap(long = "validate-before-submit")]
+    pub validate_before_submit: bool,
+    /// Print lists in full, separated by tabs, even on a terminal
+    #[clap(long = "no-truncate")]
+    pub no_truncate: bool,
+    /// Submit extrinsics even to runtimes the client was not checked
+    /// against, which may decode them as something else
+    #[clap(long = "force-incompatible")]
+    pub force_incompatible: bool,
 }
 
 #[derive(Clone, Debug, Clap)]
@@ -32,6 +83,15 @@ pub enum SubCommand {
     Donate(DonateCommand),
     Bank(BankCommand),
     Bounty(BountyCommand),
+    Recovery(RecoveryCommand),
+    Backup(BackupCommand),
+    Script(ScriptCommand),
+    Address(AddressCommand),
+    /// Stay connected, print every event and optionally serve `/health`
+    Run(run::RunCommand),
+    /// Live view of your orgs, open votes and open bounties
+    #[cfg(feature = "tui")]
+    Dashboard(dashboard::DashboardCommand),
 }
 
 #[derive(Clone, Debug, Clap)]
@@ -42,9 +102,25 @@ pub struct KeyCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub enum KeySubCommand {
-    Set(key::KeySetCommand),
+    Set(KeySetCommand),
     Unlock(key::KeyUnlockCommand),
     Lock(key::KeyLockCommand),
+    /// Follow an account without its key, read commands take its label
+    /// with `--account`
+    Watch(watch::KeyWatchCommand),
+    /// List the watch-only accounts
+    Watched(watch::KeyWatchedCommand),
+    /// Stop following a watch-only account
+    Unwatch(watch::KeyUnwatchCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct KeySetCommand {
+    /// Signature scheme of the key: sr25519, ed25519 or ecdsa
+    #[clap(long = "scheme", default_value = "sr25519")]
+    pub scheme: KeyScheme,
+    #[clap(flatten)]
+    pub key: key::KeySetCommand,
 }
 
 #[derive(Clone, Debug, Clap)]
@@ -72,9 +148,25 @@ pub enum OrgSubCommand {
     BurnShares(shares::SharesBurnCommand),
     BatchIssueShares(shares::SharesBatchIssueCommand),
     BatchBurnShares(shares::SharesBatchBurnCommand),
+    ConvertShares(shares::SharesConvertCommand),
+    /// Issue voting shares to the accounts of a csv file in batches,
+    /// resuming where an interrupted run stopped
+    Airdrop(shares::SharesAirdropCommand),
+    /// Require recipients to accept the shares issued by an org
+    ShareConsent(shares::SharesConsentCommand),
+    /// Accept or decline the shares offered to you
+    AcceptShares(shares::SharesAcceptCommand),
+    /// List the shares offered to an account
+    ShareGrants(shares::SharesGrantsCommand),
     // full org stuff
     RegisterFlatOrg(org::NewFlatOrgCommand),
     RegisterWeightedOrg(org::NewWeightedOrgCommand),
+    SetOrgHandle(org::SetOrgHandleCommand),
+    /// Register a child org with a copy of the members of an org
+    Fork(org::ForkOrgCommand),
+    /// Print the proof of a member's voting shares against the membership
+    /// root of the org
+    Proof(org::MembershipProofCommand),
 }
 
 #[derive(Clone, Debug, Clap)]
@@ -88,6 +180,32 @@ pub enum VoteSubCommand {
     CreateSignalThresholdVote(vote::VoteCreateSignalThresholdCommand),
     CreatePercentThresholdVote(vote::VoteCreatePercentThresholdCommand),
     SubmitVote(vote::VoteSubmitCommand),
+    /// End a vote as cancelled whatever its tally
+    Cancel(vote::VoteCancelCommand),
+    /// Print a receipt proving your latest ballot in a vote
+    Receipt(vote::VoteReceiptCommand),
+    /// Check a receipt printed by `vote receipt` against the chain
+    VerifyReceipt(vote::VoteVerifyReceiptCommand),
+    /// List who voted which way, optionally as CSV
+    Ballots(vote::VoteBallotsCommand),
+    /// List the open votes you were minted signal in but did not cast
+    Pending(vote::VotePendingCommand),
+    /// Export or import the thresholds registered for orgs
+    Threshold(VoteThresholdCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteThresholdCommand {
+    #[clap(subcommand)]
+    pub cmd: VoteThresholdSubCommand,
+}
+
+#[derive(Clone, Debug, Clap)]
+pub enum VoteThresholdSubCommand {
+    Export(vote::VoteThresholdExportCommand),
+    /// Print the thresholds missing from the chain, registering them with
+    /// `--yes`
+    Import(vote::VoteThresholdImportCommand),
 }
 
 #[derive(Clone, Debug, Clap)]
@@ -134,4 +252,59 @@ pub enum BountySubCommand {
     GetSubmission(bounty::GetSubmissionCommand),
     GetOpenBounties(bounty::GetOpenBountiesCommand),
     GetOpenSubmissions(bounty::GetOpenSubmissionsCommand),
+    GetContributions(bounty::GetContributionsCommand),
+    /// Pin a snapshot of the bounty to the offchain store
+    Archive(bounty::BountyArchiveCommand),
+    /// Check a pinned snapshot against the chain state
+    VerifyArchive(bounty::BountyVerifyArchiveCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct RecoveryCommand {
+    #[clap(subcommand)]
+    pub cmd: RecoverySubCommand,
+}
+
+#[derive(Clone, Debug, Clap)]
+pub enum RecoverySubCommand {
+    AddFriends(recovery::AddFriendsCommand),
+    Initiate(recovery::InitiateCommand),
+    Vouch(recovery::VouchCommand),
+    Claim(recovery::ClaimCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct BackupCommand {
+    #[clap(subcommand)]
+    pub cmd: BackupSubCommand,
+}
+
+#[derive(Clone, Debug, Clap)]
+pub enum BackupSubCommand {
+    Export(backup::ExportCommand),
+    Import(backup::ImportCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct ScriptCommand {
+    #[clap(subcommand)]
+    pub cmd: ScriptSubCommand,
+}
+
+#[derive(Clone, Debug, Clap)]
+pub enum ScriptSubCommand {
+    Run(script::ScriptRunCommand),
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct AddressCommand {
+    #[clap(subcommand)]
+    pub cmd: AddressSubCommand,
+}
+
+#[derive(Clone, Debug, Clap)]
+pub enum AddressSubCommand {
+    Add(address::AddressAddCommand),
+    List(address::AddressListCommand),
+    Remove(address::AddressRemoveCommand),
 }
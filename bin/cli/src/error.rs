@@ -0,0 +1,212 @@
+use substrate_subxt::Error as SubxtError;
+use sunshine_bounty_cli::{
+    AliasAlreadyExists,
+    AssetNotAcceptedForBounties,
+    ConflictingForkFilters,
+    InvalidAmount,
+    InvalidBountyTag,
+    InvalidCid,
+    InvalidDirection,
+    InvalidDuration,
+    InvalidGithubIssueUrl,
+    InvalidOrgHandle,
+    InvalidSplit,
+    InvalidStateRoot,
+    MissingChainSpec,
+    NotAnOrgMember,
+    PostBountyInputError,
+    SubmissionKindMismatch,
+    UnknownAlias,
+    UnknownBlockTime,
+    UnknownDevAccount,
+    UnknownKeyScheme,
+    UnknownOrgHandle,
+};
+use test_client::{
+    submit,
+    Error as ClientError,
+};
+
+/// The exit codes documented in `--help`, scripts may tell what to do about
+/// a failure by its code alone
+pub const EXIT_CODES: &str = "EXIT CODES:
+    0    success
+    1    any other failure
+    2    bad arguments, fix the invocation
+    3    the node could not be reached, retry
+    4    the keystore is locked or has no key
+    5    the runtime rejected the extrinsic, do not retry
+    6    the node did not confirm the extrinsic in time, it may be included
+    7    what was asked for does not exist";
+
+/// The class of a failure, the process exits with its value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExitCode {
+    Failure = 1,
+    Usage = 2,
+    Connection = 3,
+    Keystore = 4,
+    Dispatch = 5,
+    Timeout = 6,
+    NotFound = 7,
+}
+
+/// The exit code of the first error in the chain with a class, `Failure`
+/// if none has one
+pub fn exit_code(err: &anyhow::Error) -> ExitCode {
+    err.chain().find_map(classify).unwrap_or(ExitCode::Failure)
+}
+
+fn classify(err: &(dyn std::error::Error + 'static)) -> Option<ExitCode> {
+    if let Some(err) = err.downcast_ref::<ClientError>() {
+        return Some(client_exit_code(err))
+    }
+    if submit::timed_out(err).is_some() {
+        return Some(ExitCode::Timeout)
+    }
+    if submit::dry_run_failed(err).is_some() {
+        return Some(ExitCode::Dispatch)
+    }
+    if let Some(err) = err.downcast_ref::<SubxtError>() {
+        return match err {
+            SubxtError::Rpc(_) | SubxtError::Io(_) => {
+                Some(ExitCode::Connection)
+            }
+            SubxtError::Runtime(_) => Some(ExitCode::Dispatch),
+            _ => None,
+        }
+    }
+    if err.is::<std::num::ParseIntError>()
+        || err.is::<MissingChainSpec>()
+        || err.is::<PostBountyInputError>()
+        || err.is::<InvalidGithubIssueUrl>()
+        || err.is::<SubmissionKindMismatch>()
+        || err.is::<AssetNotAcceptedForBounties>()
+        || err.is::<InvalidBountyTag>()
+        || err.is::<InvalidOrgHandle>()
+        || err.is::<UnknownKeyScheme>()
+        || err.is::<UnknownDevAccount>()
+        || err.is::<InvalidAmount>()
+        || err.is::<InvalidDirection>()
+        || err.is::<InvalidSplit>()
+        || err.is::<InvalidDuration>()
+        || err.is::<UnknownBlockTime>()
+        || err.is::<AliasAlreadyExists>()
+        || err.is::<InvalidCid>()
+        || err.is::<NotAnOrgMember>()
+        || err.is::<InvalidStateRoot>()
+        || err.is::<ConflictingForkFilters>()
+    {
+        return Some(ExitCode::Usage)
+    }
+    if err.is::<UnknownOrgHandle>() || err.is::<UnknownAlias>() {
+        return Some(ExitCode::NotFound)
+    }
+    None
+}
+
+fn client_exit_code(err: &ClientError) -> ExitCode {
+    match err {
+        ClientError::ParseIntError
+        | ClientError::InvalidOrgHandle
+        | ClientError::InvalidPercentThreshold
+        | ClientError::ZeroSignalThreshold
+        | ClientError::AgainstExceedsInFavorThreshold
+        | ClientError::VotesCastThresholdNotRegistered
+        | ClientError::SubmissionMustDifferFromBountyInfo
+        | ClientError::InvalidBackup
+        | ClientError::BackupChainMismatch
+        | ClientError::InvalidVoteReceipt
+        | ClientError::VoteReceiptChainMismatch
+        | ClientError::WatchOnlyLabelTaken { .. }
+        | ClientError::EvidenceTooLarge { .. }
+        | ClientError::InvalidMimeType { .. }
+        | ClientError::InvalidAirdropChunk { .. }
+        | ClientError::DuplicateAirdropAccount => ExitCode::Usage,
+        ClientError::KeystoreLocked
+        | ClientError::BackupDecryptionFailed
+        | ClientError::WatchOnlyCannotSign { .. } => ExitCode::Keystore,
+        ClientError::DryRunFailed { .. } => ExitCode::Dispatch,
+        ClientError::Timeout { .. } => ExitCode::Timeout,
+        ClientError::BountyNotFound
+        | ClientError::NoBallotCast
+        | ClientError::UnknownWatchOnly { .. }
+        | ClientError::EvidenceUnavailable { .. } => ExitCode::NotFound,
+        ClientError::EventNotFound
+        | ClientError::EvidenceCorrupt
+        | ClientError::VoteReceiptNotProven
+        | ClientError::UnsupportedRuntime { .. }
+        | ClientError::CallVectorMismatch { .. }
+        | ClientError::CallVectorUnavailable { .. } => ExitCode::Failure,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_client::{
+        bounty::BountyClient,
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        vote::VoteClient,
+        Client,
+        Node,
+    };
+
+    #[test]
+    fn arguments_exit_with_usage() {
+        let err: anyhow::Error = "x".parse::<u64>().unwrap_err().into();
+        assert_eq!(exit_code(&err), ExitCode::Usage);
+        let err: anyhow::Error = InvalidBountyTag("Rust".into()).into();
+        assert_eq!(exit_code(&err), ExitCode::Usage);
+        let err = anyhow::anyhow!("something else");
+        assert_eq!(exit_code(&err), ExitCode::Failure);
+    }
+
+    #[test]
+    fn evidence_errors_have_exit_codes() {
+        let err: anyhow::Error =
+            ClientError::EvidenceTooLarge { size: 2, max: 1 }.into();
+        assert_eq!(exit_code(&err), ExitCode::Usage);
+        let err: anyhow::Error =
+            ClientError::EvidenceUnavailable { cid: "bafy".into() }.into();
+        assert_eq!(exit_code(&err), ExitCode::NotFound);
+        let err: anyhow::Error = ClientError::EvidenceCorrupt.into();
+        assert_eq!(exit_code(&err), ExitCode::Failure);
+    }
+
+    #[test]
+    fn airdrop_errors_exit_with_usage() {
+        let err: anyhow::Error =
+            ClientError::InvalidAirdropChunk { max: 100 }.into();
+        assert_eq!(exit_code(&err), ExitCode::Usage);
+        let err: anyhow::Error = ClientError::DuplicateAirdropAccount.into();
+        assert_eq!(exit_code(&err), ExitCode::Usage);
+    }
+
+    #[test]
+    fn unreachable_nodes_exit_with_connection() {
+        let refused = std::io::ErrorKind::ConnectionRefused.into();
+        let err: anyhow::Error = SubxtError::Io(refused).into();
+        assert_eq!(exit_code(&err), ExitCode::Connection);
+    }
+
+    #[async_std::test]
+    async fn rejected_extrinsics_exit_with_dispatch() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let err = client.approve_bounty_submission(99).await.unwrap_err();
+        assert_eq!(exit_code(&err), ExitCode::Dispatch);
+    }
+
+    #[async_std::test]
+    async fn missing_ballots_exit_with_not_found() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let err = client.vote_receipt(99).await.unwrap_err();
+        assert_eq!(exit_code(&err), ExitCode::NotFound);
+    }
+}
@@ -1,33 +1,176 @@
-use crate::command::*;
+use crate::{
+    command::*,
+    error::{
+        exit_code,
+        ExitCode,
+    },
+};
 use clap::Clap;
+use std::{
+    path::Path,
+    time::Duration,
+};
+use sunshine_bounty_cli::{
+    args::Resolver,
+    scheme::KeyScheme,
+    table,
+    MissingChainSpec,
+};
 use sunshine_cli_utils::Result;
-use test_client::Client;
+use test_client::{
+    chain::{
+        ChainPropertiesExt,
+        PropertiesOverrides,
+    },
+    client::{
+        crypto::keychain::KeyType,
+        Client,
+    },
+    compat::{
+        self,
+        CompatibilityExt,
+        Report,
+    },
+    era,
+    submit::{
+        self,
+        ExtrinsicProgress,
+        WaitFor,
+    },
+    ClientWith,
+    EcdsaDevice,
+    Ed25519Device,
+    Node,
+    UserDevice,
+};
+use tracing_subscriber::fmt::format::FmtSpan;
 
 mod command;
+mod error;
 
 #[async_std::main]
-async fn main() -> Result<()> {
+async fn main() {
     env_logger::init();
-    let opts: Opts = Opts::parse();
-    let root = if let Some(root) = opts.path {
+    let opts = match Opts::try_parse() {
+        Ok(opts) => opts,
+        // help and version are printed to stdout and exit with 0
+        Err(err) if !err.use_stderr() => err.exit(),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(ExitCode::Usage as i32)
+        }
+    };
+    if let Err(err) = try_main(opts).await {
+        eprintln!("Error: {:?}", err);
+        std::process::exit(exit_code(&err) as i32)
+    }
+}
+
+async fn try_main(opts: Opts) -> Result<()> {
+    if opts.trace {
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
+            .with_max_level(tracing::Level::DEBUG)
+            .with_span_events(FmtSpan::CLOSE)
+            .finish();
+        // set directly so the `log` records keep going to env_logger
+        tracing::subscriber::set_global_default(subscriber)?;
+    }
+    let root = if let Some(root) = opts.path.clone() {
         root
     } else {
         dirs::config_dir().unwrap().join("sunshine-bounty")
     };
-    let chain_spec = if let Some(chain_spec) = opts.chain_spec_path {
+    let chain_spec = if let Some(chain_spec) = opts.chain_spec_path.clone() {
         chain_spec
     } else {
-        unimplemented!()
+        return Err(MissingChainSpec.into())
+    };
+
+    // a new key is opened with its own scheme, the keystore otherwise with
+    // the scheme of the key set last
+    let scheme = match &opts.cmd {
+        SubCommand::Key(KeyCommand {
+            cmd: KeySubCommand::Set(cmd),
+        }) => cmd.scheme,
+        _ => KeyScheme::load(&root)?,
+    };
+    let wait_for = opts.wait_for;
+    let verbose = opts.verbose;
+    let dry_run = opts.dry_run;
+    submit::set_wait_for(wait_for);
+    submit::set_dry_run(dry_run);
+    submit::set_validate_before_submit(opts.validate_before_submit);
+    submit::set_timeout(Duration::from_secs(opts.timeout));
+    submit::set_resubmissions(opts.resubmissions);
+    era::set_transaction_era(opts.era);
+    table::set_no_truncate(opts.no_truncate);
+    compat::set_force_incompatible(opts.force_incompatible);
+    submit::observe_progress(move |progress| {
+        // the hash is all there is to report without waiting
+        let submitted = matches!(progress, ExtrinsicProgress::Submitted(_));
+        let dry_run_outcome = matches!(progress, ExtrinsicProgress::DryRun(_));
+        if verbose
+            || (wait_for == WaitFor::None && submitted)
+            || (dry_run && dry_run_outcome)
+        {
+            eprintln!("{}", progress);
+        }
+    });
+    let result = match scheme {
+        KeyScheme::Sr25519 => run::<UserDevice>(opts, &root, &chain_spec).await,
+        KeyScheme::Ed25519 => {
+            run::<Ed25519Device>(opts, &root, &chain_spec).await
+        }
+        KeyScheme::Ecdsa => run::<EcdsaDevice>(opts, &root, &chain_spec).await,
     };
+    match result {
+        Err(err) if err.chain().any(submit::is_not_waited) => Ok(()),
+        Err(err) if err.chain().any(submit::is_dry_run) => Ok(()),
+        Err(err) => {
+            if let Some(name) = err.chain().find_map(submit::module_error) {
+                eprintln!("Extrinsic failed with {}", name);
+            }
+            if err.chain().any(|err| submit::timed_out(err).is_some()) {
+                eprintln!("The extrinsic may still be included");
+            }
+            Err(err)
+        }
+        Ok(()) => Ok(()),
+    }
+}
 
-    let mut client = Client::new(&root, &chain_spec).await?;
+async fn run<K: KeyType>(
+    opts: Opts,
+    root: &Path,
+    chain_spec: &Path,
+) -> Result<()>
+where
+    ClientWith<K>: Client<Node>,
+{
+    let mut client = ClientWith::<K>::new(root, chain_spec).await?;
+    client.override_chain_properties(PropertiesOverrides::load(root)?);
+    // commands which do not need the node still run if it is unreachable
+    if let Ok(report) = client.compatibility().await {
+        if !report.is_safe() {
+            let force = opts.force_incompatible;
+            eprint!("{}", incompatible_banner(&report, force));
+        }
+    }
+    let resolver = Resolver::new(&client, root, opts.yes)?;
 
     match opts.cmd {
         SubCommand::Key(KeyCommand { cmd }) => {
             match cmd {
-                KeySubCommand::Set(cmd) => cmd.exec(&mut client).await?,
+                KeySubCommand::Set(cmd) => {
+                    cmd.key.exec(&mut client).await?;
+                    cmd.scheme.save(root)?;
+                }
                 KeySubCommand::Unlock(cmd) => cmd.exec(&mut client).await?,
                 KeySubCommand::Lock(cmd) => cmd.exec(&mut client).await?,
+                KeySubCommand::Watch(cmd) => cmd.exec(&client, root)?,
+                KeySubCommand::Watched(cmd) => cmd.exec(root)?,
+                KeySubCommand::Unwatch(cmd) => cmd.exec(root)?,
             }
         }
         SubCommand::Wallet(WalletCommand { cmd }) => {
@@ -42,43 +185,98 @@ async fn main() -> Result<()> {
         }
         SubCommand::Org(OrgCommand { cmd }) => {
             match cmd {
-                OrgSubCommand::IssueShares(cmd) => cmd.exec(&client).await?,
-                OrgSubCommand::BurnShares(cmd) => cmd.exec(&client).await?,
+                OrgSubCommand::IssueShares(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::BurnShares(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
                 OrgSubCommand::BatchIssueShares(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 OrgSubCommand::BatchBurnShares(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::ConvertShares(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::Airdrop(cmd) => {
+                    cmd.exec(&client, &resolver, root).await?
+                }
+                OrgSubCommand::ShareConsent(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::AcceptShares(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::ShareGrants(cmd) => {
+                    cmd.exec(&client, &resolver).await?
                 }
                 OrgSubCommand::RegisterFlatOrg(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 OrgSubCommand::RegisterWeightedOrg(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::SetOrgHandle(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::Fork(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                OrgSubCommand::Proof(cmd) => {
+                    cmd.exec(&client, &resolver).await?
                 }
             }
         }
         SubCommand::Vote(VoteCommand { cmd }) => {
             match cmd {
                 VoteSubCommand::CreateSignalThresholdVote(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 VoteSubCommand::CreatePercentThresholdVote(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 VoteSubCommand::SubmitVote(cmd) => cmd.exec(&client).await?,
+                VoteSubCommand::Receipt(cmd) => cmd.exec(&client).await?,
+                VoteSubCommand::VerifyReceipt(cmd) => cmd.exec(&client).await?,
+                VoteSubCommand::Cancel(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                VoteSubCommand::Ballots(cmd) => cmd.exec(&client).await?,
+                VoteSubCommand::Pending(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                VoteSubCommand::Threshold(VoteThresholdCommand { cmd }) => {
+                    match cmd {
+                        VoteThresholdSubCommand::Export(cmd) => {
+                            cmd.exec(&client, &resolver).await?
+                        }
+                        VoteThresholdSubCommand::Import(cmd) => {
+                            cmd.exec(&client, &resolver).await?
+                        }
+                    }
+                }
             }
         }
         SubCommand::Donate(DonateCommand { cmd }) => {
             match cmd {
-                DonateSubCommand::PropDonate(cmd) => cmd.exec(&client).await?,
-                DonateSubCommand::EqualDonate(cmd) => cmd.exec(&client).await?,
+                DonateSubCommand::PropDonate(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                DonateSubCommand::EqualDonate(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
             }
         }
         SubCommand::Bank(BankCommand { cmd }) => {
             match cmd {
-                BankSubCommand::Open(cmd) => cmd.exec(&client).await?,
-                BankSubCommand::ProposeSpend(cmd) => cmd.exec(&client).await?,
+                BankSubCommand::Open(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                BankSubCommand::ProposeSpend(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
                 BankSubCommand::TriggerVote(cmd) => cmd.exec(&client).await?,
                 BankSubCommand::SudoApprove(cmd) => cmd.exec(&client).await?,
                 BankSubCommand::Close(cmd) => cmd.exec(&client).await?,
@@ -86,28 +284,105 @@ async fn main() -> Result<()> {
         }
         SubCommand::Bounty(BountyCommand { cmd }) => {
             match cmd {
-                BountySubCommand::PostBounty(cmd) => cmd.exec(&client).await?,
+                BountySubCommand::PostBounty(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
                 BountySubCommand::ContributeToBounty(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 BountySubCommand::SubmitForBounty(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 BountySubCommand::ApproveApplication(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 BountySubCommand::GetBounty(cmd) => cmd.exec(&client).await?,
                 BountySubCommand::GetSubmission(cmd) => {
                     cmd.exec(&client).await?
                 }
                 BountySubCommand::GetOpenBounties(cmd) => {
-                    cmd.exec(&client).await?
+                    cmd.exec(&client, &resolver).await?
                 }
                 BountySubCommand::GetOpenSubmissions(cmd) => {
                     cmd.exec(&client).await?
                 }
+                BountySubCommand::GetContributions(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                BountySubCommand::Archive(cmd) => cmd.exec(&client).await?,
+                BountySubCommand::VerifyArchive(cmd) => {
+                    cmd.exec(&client).await?
+                }
             }
         }
+        SubCommand::Recovery(RecoveryCommand { cmd }) => {
+            match cmd {
+                RecoverySubCommand::AddFriends(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                RecoverySubCommand::Initiate(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                RecoverySubCommand::Vouch(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+                RecoverySubCommand::Claim(cmd) => {
+                    cmd.exec(&client, &resolver).await?
+                }
+            }
+        }
+        SubCommand::Backup(BackupCommand { cmd }) => {
+            match cmd {
+                BackupSubCommand::Export(cmd) => {
+                    cmd.exec(&client, root).await?
+                }
+                BackupSubCommand::Import(cmd) => {
+                    cmd.exec(&client, root).await?
+                }
+            }
+        }
+        SubCommand::Script(ScriptCommand { cmd }) => {
+            match cmd {
+                ScriptSubCommand::Run(cmd) => cmd.exec(&client).await?,
+            }
+        }
+        SubCommand::Address(AddressCommand { cmd }) => {
+            match cmd {
+                AddressSubCommand::Add(cmd) => cmd.exec(root)?,
+                AddressSubCommand::List(cmd) => cmd.exec(root)?,
+                AddressSubCommand::Remove(cmd) => cmd.exec(root)?,
+            }
+        }
+        SubCommand::Run(cmd) => cmd.exec(&client).await?,
+        #[cfg(feature = "tui")]
+        SubCommand::Dashboard(cmd) => cmd.exec(&client, &resolver).await?,
     }
     Ok(())
 }
+
+/// The warning printed before a command on a runtime the client was not
+/// checked against
+fn incompatible_banner(report: &Report, force: bool) -> String {
+    let mut banner = format!(
+        "Warning: the runtime (spec version {}, transaction version {}) \
+         is not fully supported by this client\n",
+        report.spec_version, report.transaction_version,
+    );
+    for feature in report.mismatches() {
+        banner.push_str(&format!(
+            "  {}: {}, {}\n",
+            feature.feature,
+            feature.support,
+            feature.reason.as_deref().unwrap_or_default(),
+        ));
+    }
+    banner.push_str(
+        if force {
+            "Extrinsics are submitted anyway with --force-incompatible\n"
+        } else {
+            "Extrinsics to these features are refused, pass \
+         --force-incompatible to submit them anyway\n"
+        },
+    );
+    banner
+}
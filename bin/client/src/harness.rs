@@ -0,0 +1,202 @@
+//! Runs a light dev node in its own process for tests which talk to a node
+//! over rpc.
+//!
+//! Every `SpawnedNode` listens on free ports and keeps its base path and
+//! log in its own temporary directory, so tests using it run in parallel.
+//! The node binary is taken from `SUNSHINE_TEST_NODE` or the workspace's
+//! debug build, it needs the `light-dev` feature:
+//! `cargo build -p test-node --features light-dev`. Tests which only need
+//! the typed clients use an in-process mock node instead, which needs no
+//! binary.
+use std::{
+    fs::File,
+    io,
+    net::{
+        Ipv4Addr,
+        TcpListener,
+        TcpStream,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::{
+        Child,
+        Command,
+        ExitStatus,
+        Stdio,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tempdir::TempDir;
+use thiserror::Error;
+
+/// The environment variable naming the node binary to spawn
+pub const NODE_BIN_ENV: &str = "SUNSHINE_TEST_NODE";
+
+/// How long a node may take until its websocket rpc accepts connections
+pub const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Error)]
+pub enum HarnessError {
+    #[error("failed to spawn {bin}: {source}")]
+    Spawn { bin: PathBuf, source: io::Error },
+    #[error("node exited with {status} before it started, its log:\n{log}")]
+    Exited { status: ExitStatus, log: String },
+    #[error("node did not start within {timeout:?}, its log:\n{log}")]
+    Timeout { timeout: Duration, log: String },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// A node process, killed when dropped
+pub struct SpawnedNode {
+    child: Child,
+    dir: TempDir,
+    ws_port: u16,
+}
+
+impl SpawnedNode {
+    /// Spawns the node binary from `SUNSHINE_TEST_NODE` or the workspace
+    pub fn spawn() -> Result<Self, HarnessError> {
+        Self::spawn_with(&node_bin(), STARTUP_TIMEOUT)
+    }
+
+    /// Spawns `bin` as a light dev node and waits `timeout` at most for its
+    /// websocket rpc
+    pub fn spawn_with(
+        bin: &Path,
+        timeout: Duration,
+    ) -> Result<Self, HarnessError> {
+        let dir = TempDir::new("sunshine-test-node")?;
+        let log = File::create(dir.path().join("node.log"))?;
+        let ws_port = free_port()?;
+        let child = Command::new(bin)
+            .arg("--light-dev")
+            .arg("--base-path")
+            .arg(dir.path().join("base"))
+            .args(&["--port", &free_port()?.to_string()])
+            .args(&["--rpc-port", &free_port()?.to_string()])
+            .args(&["--ws-port", &ws_port.to_string()])
+            .args(&["--no-mdns", "--no-prometheus", "--no-telemetry"])
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .stdin(Stdio::null())
+            .spawn()
+            .map_err(|source| {
+                HarnessError::Spawn {
+                    bin: bin.to_path_buf(),
+                    source,
+                }
+            })?;
+        let mut node = Self {
+            child,
+            dir,
+            ws_port,
+        };
+        node.wait_for_rpc(timeout)?;
+        Ok(node)
+    }
+
+    fn wait_for_rpc(&mut self, timeout: Duration) -> Result<(), HarnessError> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(HarnessError::Exited {
+                    status,
+                    log: self.log(),
+                })
+            }
+            if TcpStream::connect((Ipv4Addr::LOCALHOST, self.ws_port)).is_ok() {
+                return Ok(())
+            }
+            if started.elapsed() >= timeout {
+                return Err(HarnessError::Timeout {
+                    timeout,
+                    log: self.log(),
+                })
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// The port the websocket rpc listens on at localhost
+    pub fn ws_port(&self) -> u16 {
+        self.ws_port
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.ws_port)
+    }
+
+    /// Everything the node logged so far
+    pub fn log(&self) -> String {
+        std::fs::read_to_string(self.dir.path().join("node.log"))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for SpawnedNode {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+fn node_bin() -> PathBuf {
+    std::env::var_os(NODE_BIN_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../target/debug/test-node")
+        })
+}
+
+/// A port nothing listens on, the os does not hand it out again right away
+fn free_port() -> io::Result<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Runtime;
+    use substrate_subxt::ClientBuilder;
+
+    #[test]
+    fn nodes_exiting_early_report_their_log() {
+        let bin = Path::new("/bin/sh");
+        match SpawnedNode::spawn_with(bin, Duration::from_secs(5)) {
+            Err(HarnessError::Exited { status, log }) => {
+                assert!(!status.success());
+                assert!(!log.is_empty());
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+            Ok(_) => panic!("sh is no node"),
+        }
+    }
+
+    #[async_std::test]
+    #[ignore = "needs the test-node binary built with light-dev"]
+    async fn spawned_nodes_serve_rpc_in_parallel() {
+        let first = SpawnedNode::spawn().unwrap();
+        let second = SpawnedNode::spawn().unwrap();
+        assert_ne!(first.ws_port(), second.ws_port());
+        for node in &[first, second] {
+            let client = ClientBuilder::<Runtime>::new()
+                .set_url(node.ws_url())
+                .skip_type_sizes_check()
+                .build()
+                .await
+                .unwrap();
+            assert!(client.block_hash(None).await.unwrap().is_some());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod harness;
+
 use libipld::{
     cache::IpldCache,
     cbor::DagCborCodec,
@@ -10,8 +12,11 @@ use substrate_subxt::{
         AccountData,
         Balances,
     },
-    extrinsic,
-    sp_core,
+    sp_core::{
+        self,
+        ecdsa,
+        ed25519,
+    },
     sp_runtime,
     sp_runtime::traits::{
         IdentifyAccount,
@@ -23,7 +28,9 @@ use sunshine_bounty_client::{
     bank::Bank,
     bounty::Bounty,
     donate::Donate,
+    era::EraExtra,
     org::Org,
+    recovery::Recovery,
     vote::Vote,
 };
 use sunshine_client_utils::{
@@ -76,6 +83,8 @@ impl Org for Runtime {
     type OrgId = u64;
     type Shares = u64;
     type Constitution = TextBlock;
+    type SpendThresholdId = u64;
+    type SpendVoteId = u64;
 }
 
 impl Vote for Runtime {
@@ -83,13 +92,15 @@ impl Vote for Runtime {
     type Signal = u64;
     type ThresholdId = u64;
     type Percent = sp_runtime::Permill;
-    type VoteTopic = TextBlock;
+    type VoteTopic = VoteText;
     type VoterView = utils::vote::VoterView;
-    type VoteJustification = TextBlock;
+    type VoteJustification = VoteText;
 }
 
 impl Donate for Runtime {}
 
+impl Recovery for Runtime {}
+
 impl Bank for Runtime {
     type BankId = u64;
     type SpendId = u64;
@@ -100,7 +111,8 @@ impl Bounty for Runtime {
     type BountyId = u64;
     type BountyPost = GithubIssue;
     type SubmissionId = u64;
-    type BountySubmission = GithubIssue;
+    type BountySubmission = GithubSubmission;
+    type AssetId = u32;
 }
 
 impl sunshine_identity_client::Identity for Runtime {
@@ -115,13 +127,19 @@ impl sunshine_faucet_client::Faucet for Runtime {}
 
 impl substrate_subxt::Runtime for Runtime {
     type Signature = sp_runtime::MultiSignature;
-    type Extra = extrinsic::DefaultExtra<Self>;
+    type Extra = EraExtra<Self>;
 }
 
 pub struct OffchainClient<S> {
     store: S,
     bounties: IpldCache<S, DagCborCodec, GithubIssue>,
+    submissions: IpldCache<S, DagCborCodec, GithubSubmission>,
+    evidence: IpldCache<S, DagCborCodec, Evidence>,
+    evidence_chunks: IpldCache<S, DagCborCodec, EvidenceChunk>,
     constitutions: IpldCache<S, DagCborCodec, TextBlock>,
+    texts: IpldCache<S, DagCborCodec, VoteText>,
+    profiles: IpldCache<S, DagCborCodec, MemberProfile>,
+    archives: IpldCache<S, DagCborCodec, BountyArchive>,
 }
 
 impl<S: Store> OffchainClient<S> {
@@ -133,19 +151,57 @@ impl<S: Store> OffchainClient<S> {
                 BLAKE2B_256,
                 64,
             ),
+            submissions: IpldCache::new(
+                store.clone(),
+                DagCborCodec,
+                BLAKE2B_256,
+                64,
+            ),
+            evidence: IpldCache::new(
+                store.clone(),
+                DagCborCodec,
+                BLAKE2B_256,
+                64,
+            ),
+            // chunks are up to `EVIDENCE_CHUNK_SIZE`, few are kept decoded
+            evidence_chunks: IpldCache::new(
+                store.clone(),
+                DagCborCodec,
+                BLAKE2B_256,
+                8,
+            ),
             constitutions: IpldCache::new(
                 store.clone(),
                 DagCborCodec,
                 BLAKE2B_256,
                 64,
             ),
+            texts: IpldCache::new(store.clone(), DagCborCodec, BLAKE2B_256, 64),
+            profiles: IpldCache::new(
+                store.clone(),
+                DagCborCodec,
+                BLAKE2B_256,
+                64,
+            ),
+            archives: IpldCache::new(
+                store.clone(),
+                DagCborCodec,
+                BLAKE2B_256,
+                64,
+            ),
             store,
         }
     }
 }
 
 derive_cache!(OffchainClient, bounties, DagCborCodec, GithubIssue);
+derive_cache!(OffchainClient, submissions, DagCborCodec, GithubSubmission);
+derive_cache!(OffchainClient, evidence, DagCborCodec, Evidence);
+derive_cache!(OffchainClient, evidence_chunks, DagCborCodec, EvidenceChunk);
 derive_cache!(OffchainClient, constitutions, DagCborCodec, TextBlock);
+derive_cache!(OffchainClient, texts, DagCborCodec, VoteText);
+derive_cache!(OffchainClient, profiles, DagCborCodec, MemberProfile);
+derive_cache!(OffchainClient, archives, DagCborCodec, BountyArchive);
 
 impl<S: Store> From<S> for OffchainClient<S> {
     fn from(store: S) -> Self {
@@ -163,61 +219,107 @@ impl<S: Store> Deref for OffchainClient<S> {
 
 impl<S: Store> sunshine_client_utils::OffchainClient<S> for OffchainClient<S> {}
 
+macro_rules! impl_node {
+    ($node:ident, $new_full:path) => {
+        impl NodeT for $node {
+            type ChainSpec = test_node::ChainSpec;
+            type Runtime = Runtime;
+            type Block = test_node::OpaqueBlock;
+
+            fn impl_name() -> &'static str {
+                test_node::IMPL_NAME
+            }
+
+            fn impl_version() -> &'static str {
+                test_node::IMPL_VERSION
+            }
+
+            fn author() -> &'static str {
+                test_node::AUTHOR
+            }
+
+            fn copyright_start_year() -> i32 {
+                test_node::COPYRIGHT_START_YEAR
+            }
+
+            fn chain_spec_dev() -> Self::ChainSpec {
+                test_node::development_config()
+            }
+
+            fn chain_spec_from_json_bytes(
+                json: Vec<u8>,
+            ) -> Result<Self::ChainSpec, ChainSpecError> {
+                Self::ChainSpec::from_json_bytes(json).map_err(ChainSpecError)
+            }
+
+            fn new_light(
+                config: Configuration,
+            ) -> Result<
+                (TaskManager, RpcHandlers, Network<Self>),
+                sc_service::Error,
+            > {
+                test_node::new_light(config)
+            }
+
+            fn new_full(
+                config: Configuration,
+            ) -> Result<
+                (TaskManager, RpcHandlers, Network<Self>),
+                sc_service::Error,
+            > {
+                $new_full(config)
+            }
+        }
+    };
+}
+
 #[derive(Clone, Copy)]
 pub struct Node;
 
-impl NodeT for Node {
-    type ChainSpec = test_node::ChainSpec;
-    type Runtime = Runtime;
-    type Block = test_node::OpaqueBlock;
+impl_node!(Node, test_node::new_full);
 
-    fn impl_name() -> &'static str {
-        test_node::IMPL_NAME
-    }
+/// A node sealing a block as soon as an extrinsic enters its pool, which
+/// the client tests run on so that they never wait for a slot
+#[cfg(feature = "light-dev")]
+#[derive(Clone, Copy)]
+pub struct DevNode;
 
-    fn impl_version() -> &'static str {
-        test_node::IMPL_VERSION
-    }
+#[cfg(feature = "light-dev")]
+impl_node!(DevNode, test_node::test_service::new_instant_seal);
 
-    fn author() -> &'static str {
-        test_node::AUTHOR
-    }
+pub struct UserDevice;
 
-    fn copyright_start_year() -> i32 {
-        test_node::COPYRIGHT_START_YEAR
-    }
+impl KeyType for UserDevice {
+    const KEY_TYPE: u8 = 0;
+    type Pair = sr25519::Pair;
+}
 
-    fn chain_spec_dev() -> Self::ChainSpec {
-        test_node::development_config()
-    }
+/// A device holding an ed25519 key, e.g. one imported from other tooling
+pub struct Ed25519Device;
 
-    fn chain_spec_from_json_bytes(
-        json: Vec<u8>,
-    ) -> Result<Self::ChainSpec, ChainSpecError> {
-        Self::ChainSpec::from_json_bytes(json).map_err(ChainSpecError)
-    }
+impl KeyType for Ed25519Device {
+    const KEY_TYPE: u8 = 1;
+    type Pair = ed25519::Pair;
+}
 
-    fn new_light(
-        config: Configuration,
-    ) -> Result<(TaskManager, RpcHandlers, Network<Self>), sc_service::Error>
-    {
-        test_node::new_light(config)
-    }
+/// A device holding an ecdsa key, whose account id is the hash of the
+/// public key
+pub struct EcdsaDevice;
 
-    fn new_full(
-        config: Configuration,
-    ) -> Result<(TaskManager, RpcHandlers, Network<Self>), sc_service::Error>
-    {
-        test_node::new_full(config)
-    }
+impl KeyType for EcdsaDevice {
+    const KEY_TYPE: u8 = 2;
+    type Pair = ecdsa::Pair;
 }
 
-pub struct UserDevice;
+/// A client of the node `N` whose keystore holds a key of the device type
+/// `K`
+pub type ClientWith<K, N = Node> =
+    GenericClient<N, K, OffchainClient<OffchainStore<N>>>;
 
-impl KeyType for UserDevice {
-    const KEY_TYPE: u8 = 0;
-    type Pair = sr25519::Pair;
-}
+pub type Client = ClientWith<UserDevice>;
+pub type Ed25519Client = ClientWith<Ed25519Device>;
+pub type EcdsaClient = ClientWith<EcdsaDevice>;
 
-pub type Client =
-    GenericClient<Node, UserDevice, OffchainClient<OffchainStore<Node>>>;
+/// A client of the instant seal `DevNode`
+#[cfg(feature = "light-dev")]
+pub type DevClient = ClientWith<UserDevice, DevNode>;
@@ -0,0 +1,71 @@
+use sc_cli::{
+    RunCmd,
+    RuntimeVersion,
+    SubstrateCli,
+};
+use sc_service::ChainSpec;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Cli {
+    #[structopt(subcommand)]
+    pub subcommand: Option<Subcommand>,
+
+    #[structopt(flatten)]
+    pub run: RunCmd,
+
+    /// Run the dev chain in memory, sealing a finalized block for every
+    /// extrinsic instead of authoring with aura and grandpa
+    #[cfg(feature = "light-dev")]
+    #[structopt(long)]
+    pub light_dev: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Subcommand {
+    PurgeChain(sc_cli::PurgeChainCmd),
+}
+
+impl SubstrateCli for Cli {
+    fn impl_name() -> String {
+        crate::IMPL_NAME.into()
+    }
+
+    fn impl_version() -> String {
+        crate::IMPL_VERSION.into()
+    }
+
+    fn description() -> String {
+        crate::DESCRIPTION.into()
+    }
+
+    fn author() -> String {
+        crate::AUTHOR.into()
+    }
+
+    fn support_url() -> String {
+        crate::SUPPORT_URL.into()
+    }
+
+    fn copyright_start_year() -> i32 {
+        crate::COPYRIGHT_START_YEAR
+    }
+
+    fn executable_name() -> String {
+        crate::EXECUTABLE_NAME.into()
+    }
+
+    fn load_spec(&self, id: &str) -> Result<Box<dyn ChainSpec>, String> {
+        Ok(match id {
+            "dev" => Box::new(crate::development_config()),
+            "" | "local" => Box::new(crate::local_testnet_config()),
+            path => Box::new(crate::ChainSpec::from_json_file(path.into())?),
+        })
+    }
+
+    fn native_runtime_version(
+        _: &Box<dyn ChainSpec>,
+    ) -> &'static RuntimeVersion {
+        &test_runtime::VERSION
+    }
+}
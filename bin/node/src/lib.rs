@@ -1,3 +1,7 @@
+pub mod cli;
+#[cfg(feature = "light-dev")]
+pub mod test_service;
+
 use sc_executor::native_executor_instance;
 use sc_service::ChainType;
 use sp_core::{
@@ -17,6 +21,7 @@ use test_runtime::{
     Balance,
     BalancesConfig,
     BlockNumber,
+    BountyConfig,
     GenesisConfig,
     GrandpaConfig,
     GrantConfig,
@@ -24,6 +29,7 @@ use test_runtime::{
     Signature,
     SystemConfig,
     TreasuryConfig,
+    VoteConfig,
     WASM_BINARY,
 };
 
@@ -213,5 +219,7 @@ pub fn testnet_genesis(
             minting_interval: treasury_mint_rate.0,
             mint_amount: treasury_mint_rate.1,
         }),
+        vote: Some(VoteConfig {}),
+        bounty: Some(BountyConfig {}),
     }
 }
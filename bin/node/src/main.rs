@@ -1,82 +1,34 @@
 use sc_cli::{
-    RunCmd,
     Runner,
-    RuntimeVersion,
     SubstrateCli,
 };
 use sc_service::{
-    ChainSpec,
+    config::RpcMethods,
+    ChainType,
     DatabaseConfig,
     Role,
 };
-use structopt::StructOpt;
+use test_node::cli::{
+    Cli,
+    Subcommand,
+};
 use tiny_multihash::Multihash;
 
-#[derive(Debug, StructOpt)]
-pub struct Cli {
-    #[structopt(subcommand)]
-    pub subcommand: Option<Subcommand>,
-
-    #[structopt(flatten)]
-    pub run: RunCmd,
-}
-
-#[derive(Debug, StructOpt)]
-pub enum Subcommand {
-    PurgeChain(sc_cli::PurgeChainCmd),
-}
-
-impl SubstrateCli for Cli {
-    fn impl_name() -> String {
-        test_node::IMPL_NAME.into()
-    }
-
-    fn impl_version() -> String {
-        test_node::IMPL_VERSION.into()
-    }
-
-    fn description() -> String {
-        test_node::DESCRIPTION.into()
-    }
-
-    fn author() -> String {
-        test_node::AUTHOR.into()
-    }
-
-    fn support_url() -> String {
-        test_node::SUPPORT_URL.into()
-    }
-
-    fn copyright_start_year() -> i32 {
-        test_node::COPYRIGHT_START_YEAR
-    }
-
-    fn executable_name() -> String {
-        test_node::EXECUTABLE_NAME.into()
-    }
-
-    fn load_spec(
-        &self,
-        id: &str,
-    ) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-        Ok(match id {
-            "dev" => Box::new(test_node::development_config()),
-            "" | "local" => Box::new(test_node::local_testnet_config()),
-            path => {
-                Box::new(test_node::ChainSpec::from_json_file(path.into())?)
-            }
+fn main() -> sc_cli::Result<()> {
+    #[allow(unused_mut)]
+    let mut cli = <Cli as SubstrateCli>::from_args();
+    #[cfg(feature = "light-dev")]
+    if cli.light_dev && cli.subcommand.is_none() {
+        use test_node::test_service;
+        // the light dev profile only runs the dev chain
+        cli.run.shared_params.dev = true;
+        let mut runner = cli.create_runner(&cli.run)?;
+        test_service::in_memory(runner.config_mut());
+        allow_dev_dry_runs(&mut runner);
+        return runner.run_node_until_exit(|config| {
+            test_service::new_instant_seal(config).map(|service| service.0)
         })
     }
-
-    fn native_runtime_version(
-        _: &Box<dyn ChainSpec>,
-    ) -> &'static RuntimeVersion {
-        &test_runtime::VERSION
-    }
-}
-
-fn main() -> sc_cli::Result<()> {
-    let cli = <Cli as SubstrateCli>::from_args();
     match &cli.subcommand {
         Some(Subcommand::PurgeChain(cmd)) => {
             let mut runner = cli.create_runner(cmd)?;
@@ -86,6 +38,7 @@ fn main() -> sc_cli::Result<()> {
         None => {
             let mut runner = cli.create_runner(&cli.run)?;
             force_parity_db(&mut runner);
+            allow_dev_dry_runs(&mut runner);
             runner.run_node_until_exit(|config| {
                 match config.role {
                     Role::Light => test_node::new_light::<Multihash>(config),
@@ -102,3 +55,14 @@ fn force_parity_db(runner: &mut Runner<Cli>) {
     let path = config.database.path().unwrap().to_path_buf();
     config.database = DatabaseConfig::ParityDb { path };
 }
+
+/// Exposes the unsafe rpc methods like `system_dryRun` on development
+/// chains even when the rpc listens on external interfaces
+fn allow_dev_dry_runs(runner: &mut Runner<Cli>) {
+    let config = runner.config_mut();
+    if config.chain_spec.chain_type() == ChainType::Development
+        && matches!(config.rpc_methods, RpcMethods::Auto)
+    {
+        config.rpc_methods = RpcMethods::Unsafe;
+    }
+}
@@ -0,0 +1,201 @@
+//! A node for tests and local sandboxes which seals a block as soon as an
+//! extrinsic enters its pool.
+//!
+//! The light dev profile keeps the runtime of the full node but replaces
+//! aura and grandpa with instant seal, every block finalized as it is
+//! sealed, and keeps the chain in memory. It needs no session keys, starts
+//! in well under a second and never sleeps for a slot, so a client waits
+//! for an extrinsic only as long as it takes to execute. `start` runs one
+//! on a free port of localhost for the client test harness, and the client
+//! tests run their mock nodes on it through test-client's `DevNode`.
+use crate::{
+    cli::Cli,
+    Executor,
+};
+use futures::{
+    Future,
+    StreamExt,
+};
+use sc_cli::CliConfiguration;
+use sc_consensus_manual_seal::{
+    rpc::EngineCommand,
+    run_manual_seal,
+    ManualSealParams,
+};
+use sc_network::NetworkService;
+use sc_service::{
+    config::{
+        Configuration,
+        DatabaseConfig,
+    },
+    error::Error as ServiceError,
+    RpcHandlers,
+    TaskManager,
+    TaskType,
+};
+use sp_database::MemDb;
+use sp_inherents::InherentDataProviders;
+use sp_runtime::traits::Block as BlockT;
+use std::{
+    net::{
+        Ipv4Addr,
+        TcpListener,
+    },
+    pin::Pin,
+    sync::Arc,
+};
+use structopt::StructOpt;
+use test_runtime::{
+    opaque::Block,
+    RuntimeApi,
+};
+
+/// Keeps the chain in memory, the light dev profile starts from genesis
+/// every time
+pub fn in_memory(config: &mut Configuration) {
+    config.database = DatabaseConfig::Custom(Arc::new(MemDb::default()));
+}
+
+/// The network service of a light dev node
+pub type Network = Arc<NetworkService<Block, <Block as BlockT>::Hash>>;
+
+/// Starts the services of a node sealing a finalized block for every
+/// extrinsic imported into its pool
+pub fn new_instant_seal(
+    config: Configuration,
+) -> Result<(TaskManager, RpcHandlers, Network), ServiceError> {
+    let (client, backend, keystore, mut task_manager) =
+        sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
+    let client = Arc::new(client);
+    let select_chain = sc_consensus::LongestChain::new(backend.clone());
+    let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+        config.transaction_pool.clone(),
+        config.prometheus_registry(),
+        task_manager.spawn_handle(),
+        client.clone(),
+    );
+    let import_queue = sc_consensus_manual_seal::import_queue(
+        Box::new(client.clone()),
+        &task_manager.spawn_handle(),
+        config.prometheus_registry(),
+    );
+    let (network, network_status_sinks, system_rpc_tx, network_starter) =
+        sc_service::build_network(sc_service::BuildNetworkParams {
+            config: &config,
+            client: client.clone(),
+            transaction_pool: transaction_pool.clone(),
+            spawn_handle: task_manager.spawn_handle(),
+            import_queue,
+            on_demand: None,
+            block_announce_validator_builder: None,
+            finality_proof_request_builder: None,
+            finality_proof_provider: None,
+        })?;
+    let proposer = sc_basic_authorship::ProposerFactory::new(
+        client.clone(),
+        transaction_pool.clone(),
+        config.prometheus_registry(),
+    );
+    let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+        network: network.clone(),
+        client: client.clone(),
+        keystore,
+        task_manager: &mut task_manager,
+        transaction_pool: transaction_pool.clone(),
+        telemetry_connection_sinks: Default::default(),
+        rpc_extensions_builder: Box::new(|_| ()),
+        on_demand: None,
+        remote_blockchain: None,
+        backend,
+        network_status_sinks,
+        system_rpc_tx,
+        config,
+    })?;
+    let inherent_data_providers = InherentDataProviders::new();
+    inherent_data_providers
+        .register_provider(sp_timestamp::InherentDataProvider)
+        .map_err(|e| ServiceError::Other(e.into_string()))?;
+    // instant seal leaves its blocks unfinalized, clients wait for finality
+    let commands_stream = transaction_pool
+        .pool()
+        .validated_pool()
+        .import_notification_stream()
+        .map(|_| {
+            EngineCommand::SealNewBlock {
+                create_empty: false,
+                finalize: true,
+                parent_hash: None,
+                sender: None,
+            }
+        });
+    let authorship = run_manual_seal(ManualSealParams {
+        block_import: client.clone(),
+        env: proposer,
+        client,
+        pool: transaction_pool.pool().clone(),
+        commands_stream,
+        select_chain,
+        consensus_data_provider: None,
+        inherent_data_providers,
+    });
+    task_manager
+        .spawn_essential_handle()
+        .spawn_blocking("instant-seal", authorship);
+    network_starter.start_network();
+    Ok((task_manager, rpc_handlers, network))
+}
+
+/// A running light dev node, stopped when dropped
+pub struct TestNode {
+    _task_manager: TaskManager,
+    ws_port: u16,
+}
+
+impl TestNode {
+    /// The port the websocket rpc listens on at localhost
+    pub fn ws_port(&self) -> u16 {
+        self.ws_port
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.ws_port)
+    }
+}
+
+/// Starts a light dev node of the dev chain with the websocket rpc on a
+/// free port of localhost
+pub fn start() -> Result<TestNode, ServiceError> {
+    let ws_port = free_port()?;
+    let cli = Cli::from_iter(&[
+        crate::EXECUTABLE_NAME,
+        "--dev",
+        "--tmp",
+        "--no-mdns",
+        "--no-prometheus",
+        "--no-telemetry",
+        "--ws-port",
+        &ws_port.to_string(),
+    ]);
+    let task_executor = |fut: Pin<Box<dyn Future<Output = ()> + Send>>,
+                         _: TaskType| {
+        async_std::task::spawn(fut)
+    };
+    let mut config = cli
+        .run
+        .create_configuration(&cli, task_executor.into())
+        .map_err(|e| ServiceError::Other(e.to_string()))?;
+    in_memory(&mut config);
+    // a sandbox talks to its own client only
+    config.network.listen_addresses = vec![];
+    let (task_manager, _, _) = new_instant_seal(config)?;
+    Ok(TestNode {
+        _task_manager: task_manager,
+        ws_port,
+    })
+}
+
+/// A port nothing listens on, the os does not hand it out again right away
+fn free_port() -> Result<u16, ServiceError> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
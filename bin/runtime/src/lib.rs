@@ -176,6 +176,10 @@ parameter_types! {
     /// Assume 10% of weight for average on_initialize calls.
     pub MaximumExtrinsicWeight: Weight = AvailableBlockRatio::get()
         .saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
+    /// Half of that is shared by the pallets' housekeeping, like sweeping
+    /// expired votes.
+    pub HousekeepingBudget: Weight =
+        Perbill::from_percent(5) * MaximumBlockWeight::get();
     pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
     pub const Version: RuntimeVersion = VERSION;
 }
@@ -303,17 +307,96 @@ impl pallet_transaction_payment::Trait for Runtime {
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
 }
+
+parameter_types! {
+    pub const ConfigDepositBase: Balance = 1000;
+    pub const FriendDepositFactor: Balance = 100;
+    pub const MaxFriends: u16 = 9;
+    pub const RecoveryDeposit: Balance = 1000;
+}
+
+impl pallet_recovery::Trait for Runtime {
+    type Event = Event;
+    type Call = Call;
+    type Currency = Balances;
+    type ConfigDepositBase = ConfigDepositBase;
+    type FriendDepositFactor = FriendDepositFactor;
+    type MaxFriends = MaxFriends;
+    type RecoveryDeposit = RecoveryDeposit;
+}
+parameter_types! {
+    pub const OrgTreasury: ModuleId = ModuleId(*b"org/trsy");
+    pub const SpendVoteDuration: BlockNumber = 7 * DAYS;
+    pub const MaxMembers: u32 = 10_000;
+    pub const HandleCooldown: BlockNumber = 30 * DAYS;
+    pub const MaxApplicationDeposit: Balance = 1000;
+    pub const MaxApplicationCooldown: BlockNumber = 30 * DAYS;
+    pub const MaxPendingApplications: u32 = 1000;
+    pub const ShareGrantExpiry: BlockNumber = 7 * DAYS;
+    pub const MaxPendingShareGrants: u32 = 16;
+}
 impl org::Trait for Runtime {
     type Event = Event;
     type Cid = sunshine_codec::Cid; // Serialize and Deserialize
+    type CidFormat = util::cid::ScaleBytesCid;
     type OrgId = u64;
     type Shares = u64;
+    type OrgCurrency = Balances;
+    type OrgTreasury = OrgTreasury;
+    type SpendVote = Vote;
+    type SpendVoteDuration = SpendVoteDuration;
+    type MaxMembers = MaxMembers;
+    type HandleCooldown = HandleCooldown;
+    type MaxApplicationDeposit = MaxApplicationDeposit;
+    type MaxApplicationCooldown = MaxApplicationCooldown;
+    type MaxPendingApplications = MaxPendingApplications;
+    type ShareGrantExpiry = ShareGrantExpiry;
+    type MaxPendingShareGrants = MaxPendingShareGrants;
+    type ShareChangeHandler = Vote;
+    type LifecycleHandler = Vote;
 }
+parameter_types! {
+    pub const MaxCustomWeights: u32 = 10;
+    pub const LazyMintThreshold: u32 = 500;
+    pub const MaxJustificationHistory: u32 = 8;
+    pub const FreeBallots: u32 = 2;
+    pub const MaxCompositeLegs: u32 = 4;
+    pub const MaxThresholdTweak: Permill = Permill::from_percent(10);
+    pub const MaxStandingBallots: u32 = 16;
+    // two members would each hold half of any percent vote
+    pub const MinTurnoutForPercentVotes: u64 = 3;
+    pub const MaxPercentVoteFloor: u64 = 1_000_000;
+}
+/// The block the housekeeping budget is metered for
+pub struct CurrentBlock;
+impl frame_support::traits::Get<BlockNumber> for CurrentBlock {
+    fn get() -> BlockNumber {
+        System::block_number()
+    }
+}
+/// Meters the housekeeping of all pallets against one budget per block
+pub type Housekeeping = util::housekeeping::BlockBudget<
+    HousekeepingBudget,
+    CurrentBlock,
+    BlockNumber,
+>;
 impl vote::Trait for Runtime {
     type Event = Event;
     type VoteId = u64;
     type Signal = u64;
     type ThresholdId = u64;
+    type BondCurrency = Balances;
+    type MaxCustomWeights = MaxCustomWeights;
+    type OutcomeHandler = Org;
+    type LazyMintThreshold = LazyMintThreshold;
+    type MaxJustificationHistory = MaxJustificationHistory;
+    type FreeBallots = FreeBallots;
+    type MaxCompositeLegs = MaxCompositeLegs;
+    type MaxThresholdTweak = MaxThresholdTweak;
+    type MaxStandingBallots = MaxStandingBallots;
+    type Housekeeping = Housekeeping;
+    type MinTurnoutForPercentVotes = MinTurnoutForPercentVotes;
+    type MaxPercentVoteFloor = MaxPercentVoteFloor;
 }
 impl drip::Trait for Runtime {
     type Event = Event;
@@ -346,20 +429,81 @@ impl bank::Trait for Runtime {
     type MaxTreasuryPerOrg = MaxTreasuryPerOrg;
     type MinDeposit = MinimumDeposit;
 }
+impl pallet_assets::Trait for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+}
+/// Moves `Balances` for the native currency and `Assets` for the rest
+pub struct NativeOrAssets;
+impl util::traits::MultiCurrency<AccountId> for NativeOrAssets {
+    type AssetId = u32;
+    type Balance = Balance;
+
+    fn free_balance(asset: Option<u32>, who: &AccountId) -> Balance {
+        match asset {
+            None => Balances::free_balance(who),
+            Some(id) => Assets::balance(id, who.clone()),
+        }
+    }
+    fn transfer(
+        asset: Option<u32>,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+        existence: frame_support::traits::ExistenceRequirement,
+    ) -> sp_runtime::DispatchResult {
+        use frame_support::traits::{
+            Currency,
+            UnfilteredDispatchable,
+        };
+        match asset {
+            None => {
+                <Balances as Currency<AccountId>>::transfer(
+                    from, to, amount, existence,
+                )
+            }
+            Some(id) => {
+                // assets have no existential deposit
+                pallet_assets::Call::<Runtime>::transfer(id, to.clone(), amount)
+                    .dispatch_bypass_filter(Origin::signed(from.clone()))
+                    .map(|_| ())
+                    .map_err(|e| e.error)
+            }
+        }
+    }
+}
 parameter_types! {
     pub const Foundation: ModuleId = ModuleId(*b"fundacon");
     pub const MinDeposit: u128 = 10;
     pub const MinContribution: u128 = 5;
+    pub const SubmissionDeposit: u128 = 2;
+    pub const MaxPendingSubmissions: u32 = 1;
+    pub const MaxBountyAdmins: u32 = 5;
+    pub const MaxBountyTags: u32 = 8;
+    pub const MaxPayoutSplits: u32 = 8;
+    pub const DisputeWindow: BlockNumber = 7 * DAYS;
+    pub const DisputeDeposit: u128 = 10;
+    pub const ReviewTimeout: BlockNumber = 14 * DAYS;
 }
 impl bounty::Trait for Runtime {
     type Event = Event;
     type IpfsReference = sunshine_codec::Cid;
     type Currency = Balances;
+    type Assets = NativeOrAssets;
     type BountyId = u64;
     type SubmissionId = u64;
     type Foundation = Foundation;
     type MinDeposit = MinDeposit;
     type MinContribution = MinContribution;
+    type SubmissionDeposit = SubmissionDeposit;
+    type MaxPendingSubmissions = MaxPendingSubmissions;
+    type MaxBountyAdmins = MaxBountyAdmins;
+    type MaxBountyTags = MaxBountyTags;
+    type MaxPayoutSplits = MaxPayoutSplits;
+    type DisputeWindow = DisputeWindow;
+    type DisputeDeposit = DisputeDeposit;
+    type ReviewTimeout = ReviewTimeout;
 }
 parameter_types! {
     pub const BigFoundation: ModuleId = ModuleId(*b"big/fund");
@@ -388,15 +532,17 @@ construct_runtime!(
         Aura: pallet_aura::{Module, Config<T>, Inherent},
         Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
         Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Module, Call, Storage, Event<T>},
         TransactionPayment: pallet_transaction_payment::{Module, Storage},
+        Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
         // sunshine-bounty modules
         Org: org::{Module, Call, Config<T>, Storage, Event<T>},
-        Vote: vote::{Module, Call, Storage, Event<T>},
+        Vote: vote::{Module, Call, Config, Storage, Event<T>},
         Drip: drip::{Module, Call, Storage, Event<T>},
         Treasury: treasury::{Module, Call, Config<T>, Storage, Event<T>},
         Donate: donate::{Module, Call, Event<T>},
         Bank: bank::{Module, Call, Storage, Event<T>},
-        Bounty: bounty::{Module, Call, Storage, Event<T>},
+        Bounty: bounty::{Module, Call, Config, Storage, Event<T>},
         Grant: grant::{Module, Call, Config<T>, Storage, Event<T>},
     }
 );
@@ -518,6 +664,40 @@ impl_runtime_apis! {
         }
     }
 
+    impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
+        fn account_nonce(account: AccountId) -> Index {
+            System::account_nonce(account)
+        }
+    }
+
+    impl org_runtime_api::OrgApi<Block, u64> for Runtime {
+        fn membership_root(org: u64) -> util::merkle::H256 {
+            Org::membership_root(org)
+        }
+    }
+
+    impl vote_runtime_api::VoteApi<Block, u64, u64, AccountId, BlockNumber, sunshine_codec::Cid> for Runtime {
+        fn vote_progress(vote_id: u64) -> Option<util::vote::VoteProgress<u64>> {
+            Vote::vote_progress(vote_id)
+        }
+
+        fn ballot_status(
+            vote_id: u64,
+            who: AccountId,
+        ) -> Option<util::vote::BallotStatus<BlockNumber>> {
+            Vote::ballot_status(vote_id, &who)
+        }
+
+        fn ballots(
+            vote_id: u64,
+            direction: Option<util::vote::VoterView>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, u64, Option<sunshine_codec::Cid>, BlockNumber, BlockNumber)> {
+            Vote::ballots(vote_id, direction, offset, limit)
+        }
+    }
+
     impl fg_primitives::GrandpaApi<Block> for Runtime {
         fn grandpa_authorities() -> GrandpaAuthorityList {
             Grandpa::grandpa_authorities()
@@ -0,0 +1,127 @@
+use crate::{
+    error::{
+        AliasAlreadyExists,
+        UnknownAlias,
+    },
+    table::{
+        Column,
+        Table,
+    },
+};
+use clap::Clap;
+use std::{
+    collections::BTreeMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+use sunshine_client_utils::Result;
+
+const ADDRESS_BOOK_FILE: &str = "address_book.yml";
+
+/// Local aliases for accounts, usable as `@alias` wherever an account is
+/// expected
+#[derive(Clone, Debug, Default)]
+pub struct AddressBook {
+    path: PathBuf,
+    entries: BTreeMap<String, String>,
+}
+
+impl AddressBook {
+    /// Loads the address book stored under the client root, starting an
+    /// empty one if none was saved yet
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(ADDRESS_BOOK_FILE);
+        let entries = if path.exists() {
+            serde_yaml::from_slice(&std::fs::read(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_yaml::to_string(&self.entries)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, alias: &str) -> Option<&str> {
+        self.entries.get(alias).map(|s| s.as_str())
+    }
+
+    pub fn add(&mut self, alias: &str, account: &str) -> Result<()> {
+        if self.entries.contains_key(alias) {
+            return Err(AliasAlreadyExists(alias.to_string()).into())
+        }
+        self.entries.insert(alias.to_string(), account.to_string());
+        Ok(())
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Result<String> {
+        Ok(self
+            .entries
+            .remove(alias)
+            .ok_or_else(|| UnknownAlias(alias.to_string()))?)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter()
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct AddressAddCommand {
+    /// Name to refer to the account by, without the leading `@`
+    pub alias: String,
+    /// SS58 address of the account
+    pub account: String,
+}
+
+impl AddressAddCommand {
+    pub fn exec(&self, root: &Path) -> Result<()> {
+        let alias = self.alias.trim_start_matches('@');
+        let mut book = AddressBook::load(root)?;
+        book.add(alias, &self.account)?;
+        book.save()?;
+        println!("Added @{} for {}", alias, self.account);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct AddressListCommand {}
+
+impl AddressListCommand {
+    pub fn exec(&self, root: &Path) -> Result<()> {
+        let book = AddressBook::load(root)?;
+        let mut table = Table::new(&[
+            ("alias", Column::Text),
+            ("account", Column::Address),
+        ]);
+        for (alias, account) in book.iter() {
+            table.push(vec![format!("@{}", alias), account.clone()]);
+        }
+        table.print();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct AddressRemoveCommand {
+    pub alias: String,
+}
+
+impl AddressRemoveCommand {
+    pub fn exec(&self, root: &Path) -> Result<()> {
+        let alias = self.alias.trim_start_matches('@');
+        let mut book = AddressBook::load(root)?;
+        let account = book.remove(alias)?;
+        book.save()?;
+        println!("Removed @{} ({})", alias, account);
+        Ok(())
+    }
+}
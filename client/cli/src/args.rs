@@ -0,0 +1,553 @@
+use crate::{
+    address::AddressBook,
+    error::{
+        ConfirmationDeclined,
+        InvalidAmount,
+        InvalidDirection,
+        InvalidDuration,
+        InvalidOrgHandle,
+        InvalidSplit,
+        UnknownAlias,
+        UnknownBlockTime,
+        UnknownOrgHandle,
+    },
+    script::dev_account,
+};
+use core::{
+    fmt::Display,
+    str::FromStr,
+};
+use std::{
+    io::Write,
+    path::Path,
+    sync::Mutex,
+};
+use substrate_subxt::{
+    sp_core::crypto::Ss58Codec,
+    sp_runtime::Permill,
+    system::System,
+};
+use sunshine_bounty_client::{
+    chain::{
+        ChainProperties,
+        ChainPropertiesExt,
+    },
+    org::{
+        HandleToOrgStoreExt,
+        Org,
+    },
+    watch::WatchList,
+};
+use sunshine_bounty_utils::{
+    organization::{
+        handle_name,
+        org_handle,
+        OrgHandle,
+    },
+    vote::VoterView,
+};
+use sunshine_client_utils::{
+    crypto::ss58::Ss58,
+    Client,
+    Node,
+    Result,
+};
+
+/// A token amount such as `10`, `10.5` or `10.5k`, in whole tokens
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AmountArg {
+    mantissa: u128,
+    exponent: i32,
+}
+
+impl AmountArg {
+    /// Converts to base units, also returning whether digits beyond the
+    /// token's precision were dropped
+    pub fn to_base_units(&self, decimals: u8) -> Option<(u128, bool)> {
+        let exponent = self.exponent + decimals as i32;
+        if exponent >= 0 {
+            let scale = 10u128.checked_pow(exponent as u32)?;
+            Some((self.mantissa.checked_mul(scale)?, false))
+        } else {
+            match 10u128.checked_pow(-exponent as u32) {
+                Some(scale) => {
+                    Some((self.mantissa / scale, self.mantissa % scale != 0))
+                }
+                None => Some((0, self.mantissa != 0)),
+            }
+        }
+    }
+}
+
+impl FromStr for AmountArg {
+    type Err = InvalidAmount;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let err = || InvalidAmount(s.to_string());
+        let raw = s.trim().to_lowercase().replace('_', "");
+        let (number, suffix) = match raw.chars().last() {
+            Some('k') => (&raw[..raw.len() - 1], 3),
+            Some('m') => (&raw[..raw.len() - 1], 6),
+            Some('b') => (&raw[..raw.len() - 1], 9),
+            _ => (raw.as_str(), 0),
+        };
+        let mut parts = number.splitn(2, '.');
+        let int = parts.next().unwrap_or_default();
+        let frac = parts.next().unwrap_or_default();
+        let digits = format!("{}{}", int, frac);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(err())
+        }
+        Ok(Self {
+            mantissa: digits.parse().map_err(|_| err())?,
+            exponent: suffix - frac.len() as i32,
+        })
+    }
+}
+
+/// A duration given either in blocks (`100`, `100blocks`) or in wall-clock
+/// time (`90s`, `30m`, `2h`, `3d`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DurationArg {
+    Blocks(u32),
+    Millis(u64),
+}
+
+impl DurationArg {
+    /// Converts to a number of blocks, rounding up so the duration is never
+    /// shorter than asked for
+    pub fn to_blocks(&self, block_time: u64) -> Option<u32> {
+        match self {
+            Self::Blocks(blocks) => Some(*blocks),
+            Self::Millis(millis) => {
+                let blocks = (millis + block_time - 1) / block_time;
+                if blocks > u32::MAX as u64 {
+                    None
+                } else {
+                    Some(blocks as u32)
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for DurationArg {
+    type Err = InvalidDuration;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let err = || InvalidDuration(s.to_string());
+        let raw = s.trim().to_lowercase();
+        let split = raw
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| raw.len());
+        let (number, unit) = raw.split_at(split);
+        if number.is_empty() {
+            return Err(err())
+        }
+        let unit_millis: u64 = match unit.trim() {
+            "" | "b" | "block" | "blocks" => {
+                return Ok(Self::Blocks(number.parse().map_err(|_| err())?))
+            }
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            _ => return Err(err()),
+        };
+        let number: u64 = number.parse().map_err(|_| err())?;
+        Ok(Self::Millis(
+            number.checked_mul(unit_millis).ok_or_else(err)?,
+        ))
+    }
+}
+
+/// An org given either by its id (`3`) or by its handle (`@sunshine-devs`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrgArg {
+    Id(u64),
+    Handle(OrgHandle),
+}
+
+impl FromStr for OrgArg {
+    type Err = InvalidOrgHandle;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        if let Some(name) = s.strip_prefix('@') {
+            org_handle(name)
+                .map(Self::Handle)
+                .ok_or_else(|| InvalidOrgHandle(s.to_string()))
+        } else {
+            s.parse()
+                .map(Self::Id)
+                .map_err(|_| InvalidOrgHandle(s.to_string()))
+        }
+    }
+}
+
+/// A ballot direction, `in-favor`, `against`, `abstain` or `not-voted` for
+/// members who were minted signal but did not vote
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DirectionArg(pub VoterView);
+
+impl FromStr for DirectionArg {
+    type Err = InvalidDirection;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let direction = match s.trim().to_lowercase().as_str() {
+            "in-favor" | "yes" => VoterView::InFavor,
+            "against" | "no" => VoterView::Against,
+            "abstain" => VoterView::Abstain,
+            "not-voted" => VoterView::Uninitialized,
+            _ => return Err(InvalidDirection(s.to_string())),
+        };
+        Ok(Self(direction))
+    }
+}
+
+/// A co-author's share of a submission payout like `bob=25` or
+/// `@carol=12.5%`, in percent with at most four decimals
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitArg {
+    /// An `@alias`, dev account or ss58 address, resolved by the `Resolver`
+    pub account: String,
+    pub share: Permill,
+}
+
+impl FromStr for SplitArg {
+    type Err = InvalidSplit;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let err = || InvalidSplit(s.to_string());
+        let mut parts = s.trim().rsplitn(2, '=');
+        let percent = parts.next().unwrap_or_default();
+        let account = parts.next().filter(|a| !a.is_empty()).ok_or_else(err)?;
+        let percent = percent.strip_suffix('%').unwrap_or(percent);
+        let mut digits = percent.splitn(2, '.');
+        let int = digits.next().unwrap_or_default();
+        let frac = digits.next().unwrap_or_default();
+        if int.is_empty()
+            || frac.len() > 4
+            || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(err())
+        }
+        // a permill is ten thousand parts per percent
+        let parts = format!("{}{:0<4}", int, frac)
+            .parse::<u32>()
+            .map_err(|_| err())?;
+        if parts == 0 || parts > 1_000_000 {
+            return Err(err())
+        }
+        Ok(Self {
+            account: account.to_string(),
+            share: Permill::from_parts(parts),
+        })
+    }
+}
+
+/// Turns human-friendly arguments into chain values, collecting the
+/// conversions the user should double check before submitting
+pub struct Resolver {
+    properties: ChainProperties,
+    block_time: Option<u64>,
+    book: AddressBook,
+    watch: WatchList,
+    yes: bool,
+    notes: Mutex<Vec<String>>,
+}
+
+impl Resolver {
+    pub fn new<N: Node, C: Client<N>>(
+        client: &C,
+        root: &Path,
+        yes: bool,
+    ) -> Result<Self> {
+        let chain = client.chain_client();
+        // blocks are produced every two minimum timestamp periods
+        let block_time = chain
+            .metadata()
+            .module("Timestamp")
+            .and_then(|m| m.constant("MinimumPeriod"))
+            .and_then(|c| c.value::<u64>())
+            .ok()
+            .map(|period| period * 2);
+        Ok(Self {
+            properties: client.chain_properties(),
+            block_time,
+            book: AddressBook::load(root)?,
+            watch: WatchList::load(root)?,
+            yes,
+            notes: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn amount(&self, amount: &AmountArg) -> Result<u128> {
+        self.amount_with_decimals(amount, self.properties.token_decimals)
+    }
+
+    /// Like `amount` for an asset with other decimals than the native one
+    pub fn amount_with_decimals(
+        &self,
+        amount: &AmountArg,
+        decimals: u8,
+    ) -> Result<u128> {
+        let invalid = || InvalidAmount(format!("{:?}", amount));
+        let (units, lossy) =
+            amount.to_base_units(decimals).ok_or_else(invalid)?;
+        if lossy {
+            self.note(format!(
+                "amount rounded down to {} base units ({} decimals)",
+                units, decimals
+            ));
+        }
+        Ok(units)
+    }
+
+    pub fn blocks(&self, duration: &DurationArg) -> Result<u32> {
+        match duration {
+            DurationArg::Blocks(blocks) => Ok(*blocks),
+            DurationArg::Millis(millis) => {
+                let block_time = self.block_time.ok_or(UnknownBlockTime)?;
+                let invalid = || InvalidDuration(format!("{:?}", duration));
+                let blocks =
+                    duration.to_blocks(block_time).ok_or_else(invalid)?;
+                self.note(format!(
+                    "{}s resolves to {} blocks of {}ms",
+                    millis / 1_000,
+                    blocks,
+                    block_time
+                ));
+                Ok(blocks)
+            }
+        }
+    }
+
+    /// Accepts an SS58 address, a dev keyring name or an `@alias` from the
+    /// address book
+    pub fn account<R: System>(&self, account: &str) -> Result<R::AccountId>
+    where
+        R::AccountId: Ss58Codec,
+    {
+        let ss58 = if let Some(alias) = account.strip_prefix('@') {
+            self.book
+                .get(alias)
+                .ok_or_else(|| UnknownAlias(alias.to_string()))?
+                .to_string()
+        } else if let Ok(key) = dev_account(account) {
+            let account_id = key.to_account_id();
+            self.note(format!(
+                "dev account {} resolves to {}",
+                account,
+                self.properties.ss58(&account_id)
+            ));
+            account_id.to_ss58check()
+        } else {
+            account.to_string()
+        };
+        let account: Ss58<R> = ss58.parse()?;
+        Ok(account.0)
+    }
+
+    /// The account a read command is run as: a watch-only label, anything
+    /// `account` accepts, or the signer if none was passed
+    pub fn read_account<N: Node, C: Client<N>>(
+        &self,
+        client: &C,
+        account: Option<&str>,
+    ) -> Result<<N::Runtime as System>::AccountId>
+    where
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        match account {
+            Some(account) => {
+                self.account::<N::Runtime>(self.watch.resolve(account))
+            }
+            None => Ok(client.signer()?.account_id().clone()),
+        }
+    }
+
+    /// Fails with `WatchOnlyCannotSign` if `account` is watch-only
+    pub fn ensure_can_sign<R: System>(
+        &self,
+        account: &R::AccountId,
+    ) -> Result<()>
+    where
+        R::AccountId: Ss58Codec,
+    {
+        self.watch.ensure_can_sign(&self.properties.ss58(account))
+    }
+
+    /// Looks up the org claiming a handle, ids are passed through
+    pub async fn org<N: Node, C: Client<N>>(
+        &self,
+        client: &C,
+        org: &OrgArg,
+    ) -> Result<<N::Runtime as Org>::OrgId>
+    where
+        N::Runtime: Org,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+    {
+        match org {
+            OrgArg::Id(id) => Ok((*id).into()),
+            OrgArg::Handle(handle) => {
+                let name = handle_name(handle);
+                let id = client
+                    .chain_client()
+                    .handle_to_org(*handle, None)
+                    .await?
+                    .ok_or_else(|| UnknownOrgHandle(name.to_string()))?;
+                self.note(format!("@{} resolves to org {}", name, id));
+                Ok(id)
+            }
+        }
+    }
+
+    /// Whether `--yes` was passed
+    pub fn yes(&self) -> bool {
+        self.yes
+    }
+
+    /// Prints the conversions made so far and asks before going on, unless
+    /// `--yes` was passed
+    pub fn confirm(&self) -> Result<()> {
+        let notes = std::mem::take(&mut *self.notes.lock().unwrap());
+        if self.yes || notes.is_empty() {
+            return Ok(())
+        }
+        for note in notes {
+            println!("{}", note);
+        }
+        if ask("Submit? [y/N] ")?.eq_ignore_ascii_case("y") {
+            Ok(())
+        } else {
+            Err(ConfirmationDeclined.into())
+        }
+    }
+
+    /// Prints what an irreversible command is about to do and the account
+    /// signing it, and goes on only once `yes` is typed, unless `--yes`
+    /// was passed
+    pub fn confirm_irreversible<N: Node, C: Client<N>>(
+        &self,
+        client: &C,
+        summary: &[String],
+    ) -> Result<()>
+    where
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let notes = std::mem::take(&mut *self.notes.lock().unwrap());
+        if self.yes {
+            return Ok(())
+        }
+        let signer = client.signer()?.account_id().clone();
+        for note in notes {
+            println!("{}", note);
+        }
+        println!("This cannot be undone:");
+        for line in summary {
+            println!("  {}", line);
+        }
+        println!("  signed by {}", self.properties.ss58(&signer));
+        // a stray `y` must not approve by mistake
+        if ask("Type yes to submit: ")? == "yes" {
+            Ok(())
+        } else {
+            Err(ConfirmationDeclined.into())
+        }
+    }
+
+    fn note(&self, note: String) {
+        self.notes.lock().unwrap().push(note);
+    }
+}
+
+/// Prints `prompt` and reads the answer from stdin, trimmed
+fn ask(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amounts() {
+        let amount: AmountArg = "10".parse().unwrap();
+        assert_eq!(amount.to_base_units(0), Some((10, false)));
+        assert_eq!(amount.to_base_units(12), Some((10_000_000_000_000, false)));
+        let amount: AmountArg = "10.5k".parse().unwrap();
+        assert_eq!(amount.to_base_units(0), Some((10_500, false)));
+        let amount: AmountArg = "0.15".parse().unwrap();
+        assert_eq!(amount.to_base_units(1), Some((1, true)));
+        assert!("1.2.3".parse::<AmountArg>().is_err());
+        assert!("k".parse::<AmountArg>().is_err());
+        assert!("-1".parse::<AmountArg>().is_err());
+    }
+
+    #[test]
+    fn test_amount_overflow() {
+        let amount: AmountArg = "1000000b".parse().unwrap();
+        assert_eq!(amount.to_base_units(30), None);
+    }
+
+    #[test]
+    fn test_parse_durations() {
+        let parse = |s: &str| s.parse::<DurationArg>().unwrap();
+        assert_eq!(parse("100"), DurationArg::Blocks(100));
+        assert_eq!(parse("100blocks"), DurationArg::Blocks(100));
+        assert_eq!(parse("2h"), DurationArg::Millis(7_200_000));
+        assert!("2y".parse::<DurationArg>().is_err());
+        assert!("h".parse::<DurationArg>().is_err());
+    }
+
+    #[test]
+    fn test_parse_orgs() {
+        assert_eq!("3".parse::<OrgArg>().unwrap(), OrgArg::Id(3));
+        assert_eq!(
+            "@sunshine-devs".parse::<OrgArg>().unwrap(),
+            OrgArg::Handle(org_handle("sunshine-devs").unwrap())
+        );
+        assert!("@Sunshine".parse::<OrgArg>().is_err());
+        assert!("sunshine-devs".parse::<OrgArg>().is_err());
+    }
+
+    #[test]
+    fn test_parse_directions() {
+        let parse = |s: &str| s.parse::<DirectionArg>().unwrap().0;
+        assert_eq!(parse("in-favor"), VoterView::InFavor);
+        assert_eq!(parse("Against"), VoterView::Against);
+        assert_eq!(parse("not-voted"), VoterView::Uninitialized);
+        assert!("sideways".parse::<DirectionArg>().is_err());
+    }
+
+    #[test]
+    fn test_parse_splits() {
+        let parse = |s: &str| s.parse::<SplitArg>().unwrap();
+        assert_eq!(
+            parse("bob=25"),
+            SplitArg {
+                account: "bob".to_string(),
+                share: Permill::from_percent(25),
+            }
+        );
+        assert_eq!(parse("bob=12.5%").share, Permill::from_parts(125_000));
+        assert_eq!(parse("bob=0.0001").share, Permill::from_parts(1));
+        assert!("bob".parse::<SplitArg>().is_err());
+        assert!("=25".parse::<SplitArg>().is_err());
+        assert!("bob=0".parse::<SplitArg>().is_err());
+        assert!("bob=100.5".parse::<SplitArg>().is_err());
+        assert!("bob=0.00001".parse::<SplitArg>().is_err());
+    }
+
+    #[test]
+    fn test_duration_rounds_up_to_blocks() {
+        let two_hours = DurationArg::Millis(7_200_000);
+        assert_eq!(two_hours.to_blocks(6_000), Some(1200));
+        assert_eq!(DurationArg::Millis(7_000).to_blocks(6_000), Some(2));
+        assert_eq!(DurationArg::Blocks(7).to_blocks(6_000), Some(7));
+    }
+}
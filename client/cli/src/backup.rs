@@ -0,0 +1,77 @@
+use clap::Clap;
+use std::{
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+use sunshine_bounty_client::backup::{
+    BackupClient,
+    Progress,
+};
+use sunshine_client_utils::{
+    crypto::secrecy::SecretString,
+    Node,
+    Result,
+};
+
+#[derive(Clone, Debug, Clap)]
+pub struct ExportCommand {
+    /// Where to write the encrypted archive
+    pub path: PathBuf,
+}
+
+impl ExportCommand {
+    pub async fn exec<N: Node, C: BackupClient<N>>(
+        &self,
+        client: &C,
+        root: &Path,
+    ) -> Result<()> {
+        let password = ask_for_password("Password to encrypt the backup: ")?;
+        client
+            .export_state(root, &self.path, &password, &mut print_progress)
+            .await?;
+        println!("\nExported the client state to {}", self.path.display());
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct ImportCommand {
+    /// The archive written by `backup export`
+    pub path: PathBuf,
+}
+
+impl ImportCommand {
+    pub async fn exec<N: Node, C: BackupClient<N>>(
+        &self,
+        client: &C,
+        root: &Path,
+    ) -> Result<()> {
+        let password = ask_for_password("Password of the backup: ")?;
+        client
+            .import_state(root, &self.path, &password, &mut print_progress)
+            .await?;
+        println!("\nRestored the client state from {}", self.path.display());
+        Ok(())
+    }
+}
+
+fn ask_for_password(prompt: &str) -> Result<SecretString> {
+    Ok(SecretString::new(rpassword::read_password_from_tty(Some(
+        prompt,
+    ))?))
+}
+
+fn print_progress(progress: Progress) {
+    let percent = match progress.total {
+        0 => 100,
+        total => progress.done * 100 / total,
+    };
+    print!(
+        "\r{}% ({}/{} bytes)",
+        percent, progress.done, progress.total
+    );
+    std::io::stdout().flush().ok();
+}
@@ -1,7 +1,8 @@
 #![allow(clippy::type_complexity)]
-use crate::{
-    error::VotePercentThresholdInputBoundError,
-    vote::*,
+use crate::args::{
+    AmountArg,
+    OrgArg,
+    Resolver,
 };
 use clap::Clap;
 use core::fmt::{
@@ -11,7 +12,6 @@ use core::fmt::{
 use substrate_subxt::{
     balances::Balances,
     sp_core::crypto::Ss58Codec,
-    sp_runtime::Permill,
     system::System,
 };
 use sunshine_bounty_client::{
@@ -19,27 +19,22 @@ use sunshine_bounty_client::{
         Bank,
         BankClient,
     },
+    chain::ChainPropertiesExt,
     org::Org,
-    vote::Vote,
-};
-use sunshine_bounty_utils::{
-    organization::OrgRep,
     vote::{
-        Threshold,
-        ThresholdInput,
-        XorThreshold,
+        Vote,
+        VoteThresholdBuilder,
     },
 };
 use sunshine_client_utils::{
-    crypto::ss58::Ss58,
     Node,
     Result,
 };
 
 #[derive(Clone, Debug, Clap)]
 pub struct OpenCommand {
-    pub seed: u128,
-    pub hosting_org: u64,
+    pub seed: AmountArg,
+    pub hosting_org: OrgArg,
     pub bank_operator: Option<String>,
     pub percent_threshold: u8,
 }
@@ -48,47 +43,32 @@ impl OpenCommand {
     pub async fn exec<N: Node, C: BankClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bank,
         <N::Runtime as System>::AccountId: Ss58Codec,
         <N::Runtime as Org>::OrgId: From<u64> + Display,
-        <N::Runtime as Vote>::Percent: From<Permill>,
         <N::Runtime as Balances>::Balance: From<u128> + Display,
     {
         let bank_operator = if let Some(acc) = &self.bank_operator {
-            let new_acc: Ss58<N::Runtime> = acc.parse()?;
-            Some(new_acc.0)
+            Some(resolver.account::<N::Runtime>(acc)?)
         } else {
             None
         };
-        let support: <N::Runtime as Vote>::Percent =
-            u8_to_permill(self.percent_threshold)
-                .map_err(|_| VotePercentThresholdInputBoundError)?
-                .into();
-        let threshold: Threshold<<N::Runtime as Vote>::Percent> =
-            Threshold::new(support, None);
-        let threshold_config: ThresholdInput<
-            OrgRep<<N::Runtime as Org>::OrgId>,
-            XorThreshold<
-                <N::Runtime as Vote>::Signal,
-                <N::Runtime as Vote>::Percent,
-            >,
-        > = ThresholdInput::new(
-            OrgRep::Equal(self.hosting_org.into()),
-            XorThreshold::Percent(threshold),
-        );
+        let hosting_org = resolver.org(client, &self.hosting_org).await?;
+        let seed = resolver.amount(&self.seed)?;
+        let threshold_config =
+            VoteThresholdBuilder::<N::Runtime>::flat(hosting_org)
+                .percent(self.percent_threshold, None)?;
+        resolver.confirm()?;
         let event = client
-            .open(
-                self.seed.into(),
-                self.hosting_org.into(),
-                bank_operator,
-                threshold_config,
-            )
+            .open(seed.into(), hosting_org, bank_operator, threshold_config)
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} initialized new bank account {:?} with balance {} for Org {} with bank operator {:?}",
-            event.seeder, event.new_bank_id, event.seed, event.hosting_org, event.bank_operator
+            properties.ss58(&event.seeder), event.new_bank_id, event.seed, event.hosting_org, event.bank_operator.as_ref().map(|operator| properties.ss58(operator))
         );
         Ok(())
     }
@@ -97,7 +77,7 @@ impl OpenCommand {
 #[derive(Clone, Debug, Clap)]
 pub struct ProposeSpendCommand {
     pub bank_id: u64,
-    pub amount: u128,
+    pub amount: AmountArg,
     pub dest: String,
 }
 
@@ -105,6 +85,7 @@ impl ProposeSpendCommand {
     pub async fn exec<N: Node, C: BankClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bank,
@@ -112,13 +93,16 @@ impl ProposeSpendCommand {
         <N::Runtime as Bank>::BankId: From<u64> + Display,
         <N::Runtime as Balances>::Balance: From<u128> + Display,
     {
-        let raw_dest: Ss58<N::Runtime> = self.dest.parse()?;
+        let amount = resolver.amount(&self.amount)?;
+        let dest = resolver.account::<N::Runtime>(&self.dest)?;
+        resolver.confirm()?;
         let event = client
-            .propose_spend(self.bank_id.into(), self.amount.into(), raw_dest.0)
+            .propose_spend(self.bank_id.into(), amount.into(), dest)
             .await?;
+        let properties = client.chain_properties();
         println!(
-            "Account {} proposed new spend from Bank {:?} with Spend Proposal ID {:?} of Amount {} to Destination {:?}",
-            event.caller, event.bank_id, event.spend_id, event.amount, event.dest
+            "Account {} proposed new spend from Bank {:?} with Spend Proposal ID {:?} of Amount {} to Destination {}",
+            properties.ss58(&event.caller), event.bank_id, event.spend_id, event.amount, properties.ss58(&event.dest)
         );
         Ok(())
     }
@@ -145,9 +129,13 @@ impl TriggerVoteCommand {
         let event = client
             .trigger_vote(self.bank_id.into(), self.spend_id.into())
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} triggered VoteID {} for Bank {:?} Spend Proposal {:?}",
-            event.caller, event.vote_id, event.bank_id, event.spend_id
+            properties.ss58(&event.caller),
+            event.vote_id,
+            event.bank_id,
+            event.spend_id
         );
         Ok(())
     }
@@ -174,9 +162,12 @@ impl SudoApproveCommand {
         let event = client
             .sudo_approve(self.bank_id.into(), self.spend_id.into())
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} sudo approved Bank {:?} Spend Proposal {:?}",
-            event.caller, event.bank_id, event.spend_id
+            properties.ss58(&event.caller),
+            event.bank_id,
+            event.spend_id
         );
         Ok(())
     }
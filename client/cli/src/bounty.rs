@@ -1,4 +1,21 @@
-use crate::utils::GithubIssueMetadata;
+use crate::{
+    args::{
+        AmountArg,
+        Resolver,
+        SplitArg,
+    },
+    error::{
+        AssetNotAcceptedForBounties,
+        InvalidBountyTag,
+        InvalidCid,
+        SubmissionKindMismatch,
+    },
+    table::{
+        Column,
+        Table,
+    },
+    utils::GithubIssueMetadata,
+};
 use clap::Clap;
 use core::fmt::{
     Debug,
@@ -7,17 +24,33 @@ use core::fmt::{
 use libipld::{
     cache::Cache,
     cbor::DagCborCodec,
+    cid::Cid,
+};
+use std::convert::{
+    TryFrom,
+    TryInto,
 };
-use std::convert::TryInto;
 use substrate_subxt::{
     balances::Balances,
     sp_core::crypto::Ss58Codec,
+    sp_runtime::PerThing,
     system::System,
 };
 use sunshine_bounty_client::{
     bounty::{
         Bounty,
         BountyClient,
+        EvidenceClient,
+    },
+    chain::{
+        ChainProperties,
+        ChainPropertiesExt,
+    },
+    org::Org,
+    utils::bounty::{
+        bounty_tag,
+        BountyTag,
+        SubmissionKind,
     },
     GithubIssue,
 };
@@ -30,13 +63,33 @@ use sunshine_client_utils::{
 #[derive(Clone, Debug, Clap)]
 pub struct BountyPostCommand {
     pub issue_url: String,
-    pub amount: u128,
+    pub amount: AmountArg,
+    /// Require submissions to reference a pull request
+    #[clap(long)]
+    pub pull_request: bool,
+    /// Co-depositers who may review submissions alongside the poster
+    #[clap(long)]
+    pub admin: Vec<String>,
+    /// Fund the bounty in this asset instead of the native currency
+    #[clap(long)]
+    pub asset: Option<u32>,
+    /// Tags to find the bounty by, e.g. `rust` or `good-first-issue`
+    #[clap(long)]
+    pub tag: Vec<String>,
+    /// Post to the board of this org, funded by its treasury
+    #[clap(long)]
+    pub org: Option<u64>,
+}
+
+fn parse_tag(tag: &str) -> Result<BountyTag> {
+    Ok(bounty_tag(tag).ok_or_else(|| InvalidBountyTag(tag.to_string()))?)
 }
 
 impl BountyPostCommand {
     pub async fn exec<N: Node, C: BountyClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bounty,
@@ -44,6 +97,8 @@ impl BountyPostCommand {
         <N::Runtime as Balances>::Balance: From<u128> + Display,
         <N::Runtime as Bounty>::BountyId: Display,
         <N::Runtime as Bounty>::BountyPost: From<GithubIssue>,
+        <N::Runtime as Bounty>::AssetId: From<u32> + Display,
+        <N::Runtime as Org>::OrgId: From<u64>,
     {
         let metadata: GithubIssueMetadata =
             self.issue_url.as_str().try_into()?;
@@ -53,10 +108,57 @@ impl BountyPostCommand {
             issue_number: metadata.issue,
         }
         .into();
-        let event = client.post_bounty(bounty, self.amount.into()).await?;
+        let submission_kind = if self.pull_request {
+            SubmissionKind::PullRequest
+        } else {
+            SubmissionKind::Any
+        };
+        let mut admins = Vec::with_capacity(self.admin.len());
+        for admin in &self.admin {
+            admins.push(resolver.account::<N::Runtime>(admin)?);
+        }
+        let tags = self
+            .tag
+            .iter()
+            .map(|tag| parse_tag(tag))
+            .collect::<Result<Vec<_>>>()?;
+        let asset = self.asset.map(Into::into);
+        let amount =
+            asset_amount(client, resolver, &self.amount, asset).await?;
+        resolver.confirm()?;
+        let event = match self.org {
+            Some(org) => {
+                client
+                    .post_org_bounty(
+                        org.into(),
+                        bounty,
+                        amount.into(),
+                        asset,
+                        submission_kind,
+                        admins,
+                        tags,
+                    )
+                    .await?
+            }
+            None => {
+                client
+                    .post_bounty(
+                        bounty,
+                        amount.into(),
+                        asset,
+                        submission_kind,
+                        admins,
+                        tags,
+                    )
+                    .await?
+            }
+        };
+        let properties = client.chain_properties();
         println!(
             "Depositer with AccountId {} posted new BountyId {}, Balance {}",
-            event.depositer, event.id, event.amount,
+            properties.ss58(&event.depositer),
+            event.id,
+            event.amount,
         );
         Ok(())
     }
@@ -65,26 +167,34 @@ impl BountyPostCommand {
 #[derive(Clone, Debug, Clap)]
 pub struct BountyContributeCommand {
     pub bounty_id: u64,
-    pub amount: u128,
+    pub amount: AmountArg,
 }
 
 impl BountyContributeCommand {
     pub async fn exec<N: Node, C: BountyClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bounty,
         <N::Runtime as System>::AccountId: Ss58Codec,
         <N::Runtime as Balances>::Balance: From<u128> + Display,
         <N::Runtime as Bounty>::BountyId: From<u64> + Display,
+        <N::Runtime as Bounty>::AssetId: Display,
     {
+        // contributions are made in the asset the bounty was posted in
+        let asset = client.bounty(self.bounty_id.into()).await?.asset();
+        let amount =
+            asset_amount(client, resolver, &self.amount, asset).await?;
+        resolver.confirm()?;
         let event = client
-            .contribute_to_bounty(self.bounty_id.into(), self.amount.into())
+            .contribute_to_bounty(self.bounty_id.into(), amount.into(), asset)
             .await?;
+        let properties = client.chain_properties();
         println!(
             "AccountId {} contributed ${} to BountyId {} and the Total Balance for the Bounty is now {}",
-            event.contributor, event.amount, event.bounty_id, event.total
+            properties.ss58(&event.contributor), event.amount, event.bounty_id, event.total
         );
         Ok(())
     }
@@ -94,13 +204,18 @@ impl BountyContributeCommand {
 pub struct BountySubmitCommand {
     pub issue_url: String,
     pub bounty_id: u64,
-    pub amount: u128,
+    pub amount: AmountArg,
+    /// Co-authors paid a share of the amount like `bob=25`, the submitter
+    /// is paid the rest
+    #[clap(long)]
+    pub split: Vec<SplitArg>,
 }
 
 impl BountySubmitCommand {
     pub async fn exec<N: Node, C: BountyClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bounty,
@@ -112,22 +227,41 @@ impl BountySubmitCommand {
     {
         let metadata: GithubIssueMetadata =
             self.issue_url.as_str().try_into()?;
+        let kind = client.submission_kind(self.bounty_id.into()).await?;
+        match (kind, metadata.pull_request) {
+            (SubmissionKind::PullRequest, false)
+            | (SubmissionKind::Issue, true) => {
+                return Err(SubmissionKindMismatch(kind).into())
+            }
+            _ => (),
+        }
         let bounty: <N::Runtime as Bounty>::BountySubmission = GithubIssue {
             repo_owner: metadata.owner,
             repo_name: metadata.repo,
             issue_number: metadata.issue,
         }
         .into();
+        let amount = resolver.amount(&self.amount)?;
+        let mut split = Vec::with_capacity(self.split.len());
+        for slice in &self.split {
+            split.push((
+                resolver.account::<N::Runtime>(&slice.account)?,
+                slice.share,
+            ));
+        }
+        resolver.confirm()?;
         let event = client
             .submit_for_bounty(
                 self.bounty_id.into(),
                 bounty,
-                self.amount.into(),
+                amount.into(),
+                split,
             )
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Submitter with AccountId {} submitted for BountyId {}, requesting Balance {} with SubmissionId {:?}",
-            event.submitter, event.bounty_id, event.amount, event.id,
+            properties.ss58(&event.submitter), event.bounty_id, event.amount, event.id,
         );
         Ok(())
     }
@@ -142,25 +276,116 @@ impl BountyApproveCommand {
     pub async fn exec<N: Node, C: BountyClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bounty,
         <N::Runtime as System>::AccountId: Ss58Codec,
-        <N::Runtime as Balances>::Balance: From<u128> + Display,
+        <N::Runtime as Balances>::Balance: From<u128> + Into<u128> + Display,
         <N::Runtime as Bounty>::SubmissionId: From<u64> + Display,
         <N::Runtime as Bounty>::BountyId: Display,
+        <N::Runtime as Bounty>::AssetId: Display,
     {
+        let submission = client.submission(self.submission_id.into()).await?;
+        let bounty = client.bounty(submission.bounty_id()).await?;
+        let split = client.submission_split(self.submission_id.into()).await?;
+        let properties = client.chain_properties();
+        let asset = bounty.asset();
+        let amount: u128 = submission.amount().into();
+        let total: u128 = bounty.total().into();
+        let mut summary = vec![
+            format!(
+                "approve SubmissionId {} to BountyId {}",
+                self.submission_id,
+                bounty.id()
+            ),
+            format!(
+                "pay {} to {}",
+                format_asset_amount(client, amount, asset).await?,
+                properties.ss58(&submission.submitter())
+            ),
+        ];
+        for (coauthor, share) in split {
+            // a permill is ten thousand parts per percent
+            summary.push(format!(
+                "  of which {}% to co-author {}",
+                share.deconstruct() as f64 / 10_000.0,
+                properties.ss58(&coauthor)
+            ));
+        }
+        summary.push(format!(
+            "leave {} in the bounty",
+            format_asset_amount(client, total.saturating_sub(amount), asset)
+                .await?
+        ));
+        resolver.confirm_irreversible(client, &summary)?;
         let event = client
             .approve_bounty_submission(self.submission_id.into())
             .await?;
         println!(
             "Approved SubmissionId {} to transfer Balance {} to AccountId {}. Remaining Balance {} for BountyId {} ",
-            event.submission_id, event.amount, event.submitter, event.new_total, event.bounty_id
+            event.submission_id, event.amount, properties.ss58(&event.submitter), event.new_total, event.bounty_id
         );
         Ok(())
     }
 }
 
+#[derive(Clone, Debug, Clap)]
+pub struct GetContributionsCommand {
+    /// A watch-only label or another account to list the contributions of
+    /// instead of the signer
+    #[clap(long = "account")]
+    pub account: Option<String>,
+}
+
+impl GetContributionsCommand {
+    pub async fn exec<N: Node, C: BountyClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Bounty,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Balances>::Balance: Into<u128>,
+        <N::Runtime as Bounty>::BountyId: Display,
+        <N::Runtime as Bounty>::AssetId: Display,
+    {
+        let account = resolver.read_account(client, self.account.as_deref())?;
+        let contributions = client
+            .account_contributions(account.clone())
+            .await?
+            .unwrap_or_default();
+        if contributions.is_empty() {
+            println!(
+                "{} has no open contributions",
+                client.chain_properties().ss58(&account)
+            );
+            return Ok(())
+        }
+        let mut table = Table::new(&[
+            ("bounty", Column::Id),
+            ("contributed", Column::Amount),
+        ]);
+        for contribution in contributions {
+            // contributions are in the asset of their bounty, the native
+            // currency for bounties closed since
+            let asset = client
+                .bounty(contribution.id())
+                .await
+                .ok()
+                .and_then(|bounty| bounty.asset());
+            table.push(vec![
+                contribution.id().to_string(),
+                format_asset_amount(client, contribution.total().into(), asset)
+                    .await?,
+            ]);
+        }
+        table.print();
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Clap)]
 pub struct GetBountyCommand {
     pub bounty_id: u64,
@@ -177,12 +402,29 @@ impl GetBountyCommand {
         <N::Runtime as Balances>::Balance: Display,
         <N::Runtime as Bounty>::BountyId: Display + From<u64>,
         <N::Runtime as Bounty>::IpfsReference: Debug,
+        <N::Runtime as Org>::OrgId: Display,
     {
         let bounty_state = client.bounty(self.bounty_id.into()).await?;
+        let properties = client.chain_properties();
+        let admins: Vec<String> = bounty_state
+            .admins()
+            .iter()
+            .map(|admin| properties.ss58(admin))
+            .collect();
         println!(
-            "BOUNTY {} INFORMATION: CID: {:?} | Depositor: {} | Total Balance: {} ",
-            self.bounty_id, bounty_state.info(), bounty_state.depositer(), bounty_state.total(),
+            "BOUNTY {} INFORMATION: CID: {:?} | Depositor: {} | Admins: {} | Total Balance: {} ",
+            self.bounty_id, bounty_state.info(), properties.ss58(&bounty_state.depositer()), admins.join(", "), bounty_state.total(),
         );
+        if let Some(matching) =
+            client.bounty_matching(self.bounty_id.into()).await?
+        {
+            println!(
+                "Org {} matches {:?} of each contribution, {} left to match",
+                matching.org(),
+                matching.ratio(),
+                matching.remaining()
+            );
+        }
         Ok(())
     }
 }
@@ -207,9 +449,10 @@ impl GetSubmissionCommand {
     {
         let submission_state =
             client.submission(self.submission_id.into()).await?;
+        let properties = client.chain_properties();
         println!(
             "SUBMISSION {} INFORMATION: Bounty ID: {} | CID : {:?} | Submitter: {} | Total Balance: {} ",
-            self.submission_id, submission_state.bounty_id(), submission_state.submission(), submission_state.submitter(), submission_state.amount(),
+            self.submission_id, submission_state.bounty_id(), submission_state.submission(), properties.ss58(&submission_state.submitter()), submission_state.amount(),
         );
         Ok(())
     }
@@ -217,52 +460,76 @@ impl GetSubmissionCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub struct GetOpenBountiesCommand {
-    pub min: u128,
+    pub min: AmountArg,
+    /// Only list bounties with this tag
+    #[clap(long)]
+    pub tag: Option<String>,
 }
 
 impl GetOpenBountiesCommand {
     pub async fn exec<N: Node, C: BountyClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Bounty<IpfsReference = sunshine_codec::Cid>,
         C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
-        <N::Runtime as Balances>::Balance: From<u128> + Display,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Balances>::Balance: From<u128> + Into<u128>,
         <N::Runtime as Bounty>::BountyId: Display + From<u64>,
         <N::Runtime as Bounty>::SubmissionId: Display + From<u64>,
+        <N::Runtime as Bounty>::AssetId: Display,
     {
-        let open_bounties = client.open_bounties(self.min.into()).await?;
-        if let Some(b) = open_bounties {
-            for (id, bounty) in b.into_iter() {
-                let event_cid = bounty.info();
-                match client.offchain_client().get(&event_cid).await {
-                    Ok(bounty_body) => {
-                        println!(
-                            "Live BountyID {} has total available balance {} at {} added by {}",
-                            id,
-                            bounty.total(),
-                            format!(
-                                "https://github.com/{}/{}/issues/{}",
-                                bounty_body.repo_owner,
-                                bounty_body.repo_name,
-                                bounty_body.issue_number
-                            ),
-                            bounty.depositer().to_string()
-                        );
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Error while getting bounty {}. skipping..",
-                            id
-                        );
-                        eprintln!("{}", e);
-                        continue
-                    }
+        let min = resolver.amount(&self.min)?;
+        let tagged = match &self.tag {
+            Some(tag) => Some(client.bounties_by_tag(parse_tag(tag)?).await?),
+            None => None,
+        };
+        let open_bounties = client.open_bounties(min.into()).await?;
+        let properties = client.chain_properties();
+        let mut table = Table::new(&[
+            ("id", Column::Id),
+            ("state", Column::State),
+            ("total", Column::Amount),
+            ("issue", Column::Text),
+            ("depositor", Column::Address),
+            ("posted", Column::Id),
+        ]);
+        for (id, bounty) in open_bounties.unwrap_or_default() {
+            if let Some(tagged) = &tagged {
+                if !tagged.contains(&id) {
+                    continue
                 }
             }
-        } else {
+            let event_cid = bounty.info();
+            let bounty_body = match client
+                .offchain_client()
+                .get(&event_cid)
+                .await
+            {
+                Ok(bounty_body) => bounty_body,
+                Err(e) => {
+                    eprintln!("Error while getting bounty {}. skipping..", id);
+                    eprintln!("{}", e);
+                    continue
+                }
+            };
+            let total = bounty.total().into();
+            table.push(vec![
+                id.to_string(),
+                "open".to_string(),
+                format_asset_amount(client, total, bounty.asset()).await?,
+                issue_url(&bounty_body),
+                properties.ss58(&bounty.depositer()),
+                // bounties posted before blocks were tracked have none
+                block(bounty.timestamped(), bounty.posted_at()),
+            ]);
+        }
+        if table.is_empty() {
             println!("No open bounties above the passed input minimum balance");
+        } else {
+            table.print();
         }
         Ok(())
     }
@@ -274,36 +541,43 @@ pub struct GetOpenSubmissionsCommand {
 }
 
 impl GetOpenSubmissionsCommand {
-    pub async fn exec<N: Node, C: BountyClient<N>>(
+    pub async fn exec<N: Node, C: BountyClient<N> + EvidenceClient<N>>(
         &self,
         client: &C,
     ) -> Result<()>
     where
         N::Runtime: Bounty<IpfsReference = sunshine_codec::Cid>,
-        C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
-        <N::Runtime as Balances>::Balance: Display,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Balances>::Balance: Into<u128>,
         <N::Runtime as Bounty>::BountyId: From<u64> + Display,
         <N::Runtime as Bounty>::SubmissionId: Display + From<u64>,
+        <N::Runtime as Bounty>::AssetId: Display,
     {
         let open_submissions =
             client.open_submissions(self.bounty_id.into()).await?;
-        if let Some(s) = open_submissions {
-            for (id, sub) in s.into_iter() {
-                let event_cid = sub.submission();
-                match client.offchain_client().get(&event_cid).await {
-                    Ok(submission_body) => {
-                        println!("Live SubmissionID {} requests total balance {} at {} submitted by {}",
-                            id,
-                            sub.amount(),
-                            format!(
-                                "https://github.com/{}/{}/issues/{}",
-                                submission_body.repo_owner,
-                                submission_body.repo_name,
-                                submission_body.issue_number
-                            ),
-                            sub.submitter().to_string()
-                        );
-                    }
+        let properties = client.chain_properties();
+        let mut table = Table::new(&[
+            ("id", Column::Id),
+            ("state", Column::State),
+            ("amount", Column::Amount),
+            ("issue", Column::Text),
+            ("submitter", Column::Address),
+            ("submitted", Column::Id),
+            ("times out", Column::Id),
+            ("reviewer", Column::Address),
+        ]);
+        let submissions = open_submissions.unwrap_or_default();
+        // submissions are paid in the asset of their bounty
+        let asset = if submissions.is_empty() {
+            None
+        } else {
+            client.bounty(self.bounty_id.into()).await?.asset()
+        };
+        for (id, sub, reviewer, deadline) in submissions {
+            let event_cid = sub.submission();
+            let submission_body =
+                match client.resolve_submission(&event_cid).await {
+                    Ok(submission_body) => submission_body,
                     Err(e) => {
                         eprintln!(
                             "Error while getting submission {}. skipping..",
@@ -313,9 +587,146 @@ impl GetOpenSubmissionsCommand {
                         continue
                     }
                 };
-            }
-        } else {
+            // reviewers are nagged in the last quarter of the review timeout
+            let state = if sub.state().approved() {
+                "approved"
+            } else if deadline.passed {
+                "overdue"
+            } else if deadline.nearing {
+                "nearing"
+            } else {
+                "open"
+            };
+            table.push(vec![
+                id.to_string(),
+                state.to_string(),
+                format_asset_amount(client, sub.amount().into(), asset).await?,
+                issue_url(&submission_body.issue()),
+                properties.ss58(&sub.submitter()),
+                block(sub.timestamped(), sub.submitted_at()),
+                block(sub.timestamped(), deadline.times_out_at),
+                reviewer
+                    .map(|r| properties.ss58(&r))
+                    .unwrap_or_else(|| "unassigned".to_string()),
+            ]);
+        }
+        if table.is_empty() {
             println!("No open submissions for the passed in BountyID");
+        } else {
+            table.print();
+        }
+        Ok(())
+    }
+}
+
+fn issue_url(issue: &GithubIssue) -> String {
+    format!(
+        "https://github.com/{}/{}/issues/{}",
+        issue.repo_owner, issue.repo_name, issue.issue_number
+    )
+}
+
+/// The block of an event, empty if it was not tracked yet
+fn block<B: Display>(timestamped: bool, block: B) -> String {
+    if timestamped {
+        format!("#{}", block)
+    } else {
+        String::new()
+    }
+}
+
+/// Resolves `amount` in the decimals of `asset`, the native currency if None
+async fn asset_amount<N: Node, C: BountyClient<N>>(
+    client: &C,
+    resolver: &Resolver,
+    amount: &AmountArg,
+    asset: Option<<N::Runtime as Bounty>::AssetId>,
+) -> Result<u128>
+where
+    N::Runtime: Bounty,
+    <N::Runtime as Bounty>::AssetId: Display,
+{
+    match asset {
+        Some(id) => {
+            let metadata = client
+                .bounty_asset(id)
+                .await?
+                .ok_or_else(|| AssetNotAcceptedForBounties(id.to_string()))?;
+            resolver.amount_with_decimals(amount, metadata.decimals())
+        }
+        None => resolver.amount(amount),
+    }
+}
+
+/// Renders base units of `asset` with its decimals and symbol, the native
+/// currency if None
+async fn format_asset_amount<N: Node, C: BountyClient<N>>(
+    client: &C,
+    amount: u128,
+    asset: Option<<N::Runtime as Bounty>::AssetId>,
+) -> Result<String>
+where
+    N::Runtime: Bounty,
+    <N::Runtime as Bounty>::AssetId: Display,
+{
+    let properties = client.chain_properties();
+    match asset {
+        Some(id) => {
+            let metadata = client
+                .bounty_asset(id)
+                .await?
+                .ok_or_else(|| AssetNotAcceptedForBounties(id.to_string()))?;
+            let asset = ChainProperties {
+                token_decimals: metadata.decimals(),
+                token_symbol: String::from_utf8_lossy(&metadata.symbol())
+                    .into_owned(),
+                ..properties
+            };
+            Ok(asset.format_amount(amount))
+        }
+        None => Ok(properties.format_amount(amount)),
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct BountyArchiveCommand {
+    pub bounty_id: u64,
+}
+
+impl BountyArchiveCommand {
+    pub async fn exec<N: Node, C: BountyClient<N>>(
+        &self,
+        client: &C,
+    ) -> Result<()>
+    where
+        N::Runtime: Bounty,
+        <N::Runtime as Bounty>::BountyId: From<u64>,
+    {
+        let cid = client.archive_bounty(self.bounty_id.into()).await?;
+        println!("Archived bounty {} as {}", self.bounty_id, cid);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct BountyVerifyArchiveCommand {
+    pub cid: String,
+}
+
+impl BountyVerifyArchiveCommand {
+    pub async fn exec<N: Node, C: BountyClient<N>>(
+        &self,
+        client: &C,
+    ) -> Result<()>
+    where
+        N::Runtime: Bounty,
+    {
+        let cid = Cid::try_from(self.cid.as_str())
+            .map_err(|_| InvalidCid(self.cid.clone()))?;
+        if client.verify_archive(&cid).await? {
+            println!("Archive {} matches the chain state", cid);
+        } else {
+            println!("Archive {} does not match the chain state", cid);
         }
         Ok(())
     }
@@ -0,0 +1,790 @@
+//! A live terminal dashboard of the signer's orgs, open votes and open
+//! bounties
+//!
+//! `--account` shows those of another account, like a watch-only one, which
+//! the dashboard does not vote for.
+//!
+//! The panes refresh whenever an `Org`, `Vote` or `Bounty` event arrives on
+//! the event subscription and otherwise every `--interval` seconds, which
+//! also keeps the time left on votes current. When stdout is not a terminal
+//! the dashboard prints the panes once instead.
+//!
+//! Keys: `Tab` switches panes, the arrows select, `v` votes on the selected
+//! vote, `Enter` lists the submissions of the selected bounty, `Esc` goes
+//! back, `r` refreshes and `q` quits.
+use crate::args::{
+    AmountArg,
+    Resolver,
+};
+use async_std::future::timeout;
+use clap::Clap;
+use core::fmt::Display;
+use crossterm::{
+    event::{
+        self,
+        Event,
+        KeyCode,
+    },
+    execute,
+    terminal::{
+        disable_raw_mode,
+        enable_raw_mode,
+        EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+use std::{
+    io::{
+        self,
+        Stdout,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use substrate_subxt::{
+    balances::Balances,
+    sp_core::crypto::Ss58Codec,
+    sp_runtime::traits::UniqueSaturatedInto,
+    system::System,
+    EventSubscription,
+};
+use sunshine_bounty_client::{
+    bounty::{
+        Bounty,
+        BountyClient,
+    },
+    chain::{
+        BlockClock,
+        BlockClockExt,
+        ChainPropertiesExt,
+    },
+    org::{
+        Org,
+        OrgClient,
+    },
+    utils::vote::VoterView,
+    vote::{
+        Vote,
+        VoteClient,
+    },
+};
+use sunshine_client_utils::{
+    Node,
+    Result,
+};
+use tui_rs::{
+    backend::{
+        Backend,
+        CrosstermBackend,
+    },
+    layout::{
+        Constraint,
+        Direction,
+        Layout,
+    },
+    style::{
+        Modifier,
+        Style,
+    },
+    widgets::{
+        Block,
+        Borders,
+        List,
+        ListItem,
+        ListState,
+        Paragraph,
+    },
+    Frame,
+    Terminal,
+};
+
+/// How long each wait for a key or an event lasts before the other
+const POLL: Duration = Duration::from_millis(50);
+
+const HELP: &str =
+    "Tab: pane | Up/Down: select | v: vote | Enter: submissions | r: refresh | q: quit";
+
+#[derive(Clone, Debug, Clap)]
+pub struct DashboardCommand {
+    /// Only list bounties with at least this much left to pay out
+    #[clap(long = "min", default_value = "0")]
+    pub min: AmountArg,
+    /// Seconds between refreshes while no event arrives
+    #[clap(long = "interval", default_value = "6")]
+    pub interval: u64,
+    /// Show a watch-only label or another account instead of the signer
+    #[clap(long = "account")]
+    pub account: Option<String>,
+}
+
+/// A line of a pane with the id of the vote or bounty it shows
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Row {
+    pub id: u64,
+    pub text: String,
+}
+
+/// The rows of the three panes, read from the chain on each refresh
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Summary {
+    pub orgs: Vec<Row>,
+    pub votes: Vec<Row>,
+    pub bounties: Vec<Row>,
+}
+
+impl Summary {
+    /// Prints the panes one after the other, for output which is not a
+    /// terminal
+    pub fn print(&self) {
+        for (title, rows) in &[
+            ("Orgs", &self.orgs),
+            ("Open votes", &self.votes),
+            ("Open bounties", &self.bounties),
+        ] {
+            println!("{}", title);
+            if rows.is_empty() {
+                println!("  none");
+            }
+            for row in rows.iter() {
+                println!("  {}", row.text);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pane {
+    Orgs,
+    Votes,
+    Bounties,
+}
+
+impl Default for Pane {
+    fn default() -> Self {
+        Self::Orgs
+    }
+}
+
+impl Pane {
+    fn index(self) -> usize {
+        match self {
+            Self::Orgs => 0,
+            Self::Votes => 1,
+            Self::Bounties => 2,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Orgs => Self::Votes,
+            Self::Votes => Self::Bounties,
+            Self::Bounties => Self::Orgs,
+        }
+    }
+
+    fn previous(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// What a key asks the dashboard to do besides redrawing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    Vote(u64, VoterView),
+    Submissions(u64),
+}
+
+/// The state of the dashboard between redraws
+#[derive(Clone, Debug, Default)]
+pub struct Dashboard {
+    summary: Summary,
+    pane: Pane,
+    selected: [usize; 3],
+    /// The vote waiting for a direction
+    prompt: Option<u64>,
+    /// The bounty whose submissions replace the bounty pane
+    submissions: Option<(u64, Vec<Row>)>,
+    status: String,
+}
+
+impl Dashboard {
+    pub fn new(summary: Summary) -> Self {
+        Self {
+            summary,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the rows, keeping the selections in range
+    pub fn update(&mut self, summary: Summary) {
+        self.summary = summary;
+        for pane in &[Pane::Orgs, Pane::Votes, Pane::Bounties] {
+            let len = self.rows(*pane).len();
+            let selected = &mut self.selected[pane.index()];
+            *selected = (*selected).min(len.saturating_sub(1));
+        }
+    }
+
+    pub fn show_submissions(&mut self, bounty: u64, rows: Vec<Row>) {
+        self.submissions = Some((bounty, rows));
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
+    fn rows(&self, pane: Pane) -> &[Row] {
+        match pane {
+            Pane::Orgs => &self.summary.orgs,
+            Pane::Votes => &self.summary.votes,
+            Pane::Bounties => &self.summary.bounties,
+        }
+    }
+
+    fn selected_row(&self) -> Option<&Row> {
+        self.rows(self.pane).get(self.selected[self.pane.index()])
+    }
+
+    pub fn on_key(&mut self, key: KeyCode) -> Action {
+        if let Some(vote) = self.prompt {
+            let view = match key {
+                KeyCode::Char('y') => VoterView::InFavor,
+                KeyCode::Char('n') => VoterView::Against,
+                KeyCode::Char('a') => VoterView::Abstain,
+                KeyCode::Esc => {
+                    self.prompt = None;
+                    self.status.clear();
+                    return Action::None
+                }
+                _ => return Action::None,
+            };
+            self.prompt = None;
+            self.status = format!("Voting {:?} in vote {}...", view, vote);
+            return Action::Vote(vote, view)
+        }
+        match key {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Esc => {
+                self.submissions = None;
+                Action::None
+            }
+            KeyCode::Tab => {
+                self.pane = self.pane.next();
+                Action::None
+            }
+            KeyCode::BackTab => {
+                self.pane = self.pane.previous();
+                Action::None
+            }
+            KeyCode::Up => {
+                let selected = &mut self.selected[self.pane.index()];
+                *selected = selected.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Down => {
+                let len = self.rows(self.pane).len();
+                let selected = &mut self.selected[self.pane.index()];
+                if *selected + 1 < len {
+                    *selected += 1;
+                }
+                Action::None
+            }
+            KeyCode::Char('v') if self.pane == Pane::Votes => {
+                if let Some(vote) = self.selected_row().map(|row| row.id) {
+                    self.prompt = Some(vote);
+                    self.status = format!(
+                        "Vote {}: y in favor, n against, a abstain, Esc cancels",
+                        vote
+                    );
+                }
+                Action::None
+            }
+            KeyCode::Enter if self.pane == Pane::Bounties => {
+                self.selected_row()
+                    .map(|row| Action::Submissions(row.id))
+                    .unwrap_or(Action::None)
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(35),
+                    Constraint::Min(5),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(f.size());
+        let bounties = match &self.submissions {
+            Some((bounty, rows)) => {
+                (
+                    format!("Submissions of bounty {} (Esc: back)", bounty),
+                    rows,
+                )
+            }
+            None => ("Open bounties".to_string(), &self.summary.bounties),
+        };
+        let panes = [
+            (Pane::Orgs, "Orgs".to_string(), &self.summary.orgs),
+            (Pane::Votes, "Open votes".to_string(), &self.summary.votes),
+            (Pane::Bounties, bounties.0, bounties.1),
+        ];
+        for (pane, title, rows) in panes.iter() {
+            let items: Vec<ListItem> = rows
+                .iter()
+                .map(|row| ListItem::new(row.text.as_str()))
+                .collect();
+            let mut borders = Block::default().borders(Borders::ALL);
+            if *pane == self.pane {
+                borders = borders.border_style(
+                    Style::default().add_modifier(Modifier::BOLD),
+                );
+            }
+            let list = List::new(items)
+                .block(borders.title(title.as_str()))
+                .highlight_style(
+                    Style::default().add_modifier(Modifier::REVERSED),
+                );
+            let mut state = ListState::default();
+            if *pane == self.pane && !rows.is_empty() {
+                state.select(Some(self.selected[pane.index()]));
+            }
+            f.render_stateful_widget(list, chunks[pane.index()], &mut state);
+        }
+        let status = if self.status.is_empty() {
+            HELP
+        } else {
+            &self.status
+        };
+        f.render_widget(Paragraph::new(status), chunks[3]);
+    }
+}
+
+/// The time left until `ends`, in blocks and approximately in wall-clock
+/// time if the chain has a block time
+pub fn time_left(ends: Option<u64>, clock: Option<BlockClock>) -> String {
+    match (ends, clock) {
+        (None, _) => "no deadline".to_string(),
+        (Some(ends), None) => format!("ends at block {}", ends),
+        (Some(ends), Some(clock)) if ends <= clock.best => "ending".to_string(),
+        (Some(ends), Some(clock)) => {
+            let blocks = ends - clock.best;
+            format!(
+                "{} blocks (~{}) left",
+                blocks,
+                format_millis(blocks.saturating_mul(clock.block_time))
+            )
+        }
+    }
+}
+
+/// Renders a duration in its two largest units, like `2d 3h` or `5m`
+fn format_millis(millis: u64) -> String {
+    let minutes = millis / 60_000;
+    let (days, hours, minutes) =
+        (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => "<1m".to_string(),
+        (0, 0, m) => format!("{}m", m),
+        (0, h, m) => format!("{}h {}m", h, m),
+        (d, h, _) => format!("{}d {}h", d, h),
+    }
+}
+
+impl DashboardCommand {
+    pub async fn exec<N: Node, C>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        C: OrgClient<N> + VoteClient<N> + BountyClient<N>,
+        N::Runtime: Org + Vote + Bounty,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: Display,
+        <N::Runtime as Org>::Shares: Display,
+        <N::Runtime as Vote>::VoteId: From<u64> + Into<u64>,
+        <N::Runtime as Vote>::Signal: Display,
+        <N::Runtime as Vote>::VoterView: From<VoterView>,
+        <N::Runtime as Bounty>::BountyId: From<u64> + Into<u64>,
+        <N::Runtime as Bounty>::SubmissionId: Display,
+        <N::Runtime as Balances>::Balance: From<u128> + Display,
+    {
+        let min = resolver.amount(&self.min)?.into();
+        let account = resolver.read_account(client, self.account.as_deref())?;
+        // votes are signed by the key, never for the account shown
+        let is_signer = client
+            .signer()
+            .map(|signer| signer.account_id() == &account)
+            .unwrap_or(false);
+        let read_only = match resolver.ensure_can_sign::<N::Runtime>(&account) {
+            Err(e) => Some(e.to_string()),
+            Ok(()) if !is_signer => {
+                Some(format!(
+                    "votes are cast by the key, not by {}",
+                    client.chain_properties().ss58(&account)
+                ))
+            }
+            Ok(()) => None,
+        };
+        let summary = summary(client, &account, min).await?;
+        if !atty::is(atty::Stream::Stdout) {
+            summary.print();
+            return Ok(())
+        }
+        let mut dashboard = Dashboard::new(summary);
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        let result = self
+            .run(
+                client,
+                &account,
+                read_only.as_deref(),
+                min,
+                &mut dashboard,
+                &mut terminal,
+            )
+            .await;
+        // restore the terminal even if the dashboard failed
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        result
+    }
+
+    async fn run<N: Node, C>(
+        &self,
+        client: &C,
+        account: &<N::Runtime as System>::AccountId,
+        read_only: Option<&str>,
+        min: <N::Runtime as Balances>::Balance,
+        dashboard: &mut Dashboard,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<()>
+    where
+        C: OrgClient<N> + VoteClient<N> + BountyClient<N>,
+        N::Runtime: Org + Vote + Bounty,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: Display,
+        <N::Runtime as Org>::Shares: Display,
+        <N::Runtime as Vote>::VoteId: From<u64> + Into<u64>,
+        <N::Runtime as Vote>::Signal: Display,
+        <N::Runtime as Vote>::VoterView: From<VoterView>,
+        <N::Runtime as Bounty>::BountyId: From<u64> + Into<u64>,
+        <N::Runtime as Bounty>::SubmissionId: Display,
+        <N::Runtime as Balances>::Balance: From<u128> + Display,
+    {
+        let chain = client.chain_client();
+        // without a subscription the dashboard still refreshes on interval
+        let mut events = match chain.subscribe_events().await {
+            Ok(sub) => {
+                Some(EventSubscription::new(sub, chain.events_decoder()))
+            }
+            Err(_) => None,
+        };
+        let interval = Duration::from_secs(self.interval.max(1));
+        loop {
+            terminal.draw(|f| dashboard.draw(f))?;
+            let deadline = Instant::now() + interval;
+            let mut refresh = false;
+            let mut redraw = false;
+            while !refresh && !redraw {
+                if Instant::now() >= deadline {
+                    refresh = true;
+                    break
+                }
+                if event::poll(POLL)? {
+                    if let Event::Key(key) = event::read()? {
+                        redraw = true;
+                        match dashboard.on_key(key.code) {
+                            Action::None => {}
+                            Action::Quit => return Ok(()),
+                            Action::Refresh => refresh = true,
+                            Action::Vote(..) if read_only.is_some() => {
+                                let reason = read_only.unwrap_or_default();
+                                dashboard.set_status(format!(
+                                    "Vote failed: {}",
+                                    reason
+                                ));
+                            }
+                            Action::Vote(vote, view) => {
+                                terminal.draw(|f| dashboard.draw(f))?;
+                                let status = match client
+                                    .submit_vote(vote.into(), view.into(), None)
+                                    .await
+                                {
+                                    Ok(_) => {
+                                        format!(
+                                            "Voted {:?} in vote {}",
+                                            view, vote
+                                        )
+                                    }
+                                    Err(e) => format!("Vote failed: {}", e),
+                                };
+                                dashboard.set_status(status);
+                                refresh = true;
+                            }
+                            Action::Submissions(bounty) => {
+                                match submissions(client, bounty).await {
+                                    Ok(rows) => {
+                                        dashboard.show_submissions(bounty, rows)
+                                    }
+                                    Err(e) => {
+                                        dashboard.set_status(format!(
+                                            "Cannot list the submissions: {}",
+                                            e
+                                        ))
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(subscription) = events.as_mut() {
+                    match timeout(POLL, subscription.next()).await {
+                        Ok(Some(Ok(event))) => {
+                            if ["Org", "Vote", "Bounty"]
+                                .contains(&event.module.as_str())
+                            {
+                                refresh = true;
+                            }
+                        }
+                        // the dispatch errors of failed extrinsics
+                        Ok(Some(Err(_))) => {}
+                        Ok(None) => events = None,
+                        Err(_) => {}
+                    }
+                }
+            }
+            if refresh {
+                match summary(client, account, min).await {
+                    Ok(summary) => dashboard.update(summary),
+                    Err(e) => {
+                        dashboard.set_status(format!("Refresh failed: {}", e))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reads the orgs of `account`, the open votes it was minted signal in and
+/// the bounties with at least `min` left
+async fn summary<N: Node, C>(
+    client: &C,
+    account: &<N::Runtime as System>::AccountId,
+    min: <N::Runtime as Balances>::Balance,
+) -> Result<Summary>
+where
+    C: OrgClient<N> + VoteClient<N> + BountyClient<N>,
+    N::Runtime: Org + Vote + Bounty,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    <N::Runtime as Org>::OrgId: Display,
+    <N::Runtime as Org>::Shares: Display,
+    <N::Runtime as Vote>::VoteId: Into<u64>,
+    <N::Runtime as Vote>::Signal: Display,
+    <N::Runtime as Bounty>::BountyId: Into<u64>,
+    <N::Runtime as Balances>::Balance: Display,
+{
+    let properties = client.chain_properties();
+    let clock = client.block_clock().await?;
+    let orgs = client
+        .share_profiles(account.clone())
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(org, profile, state)| {
+            Row {
+                id: 0,
+                text: format!(
+                    "Org {}: {} of {} shares",
+                    org,
+                    profile.total(),
+                    state.total_shares()
+                ),
+            }
+        })
+        .collect();
+    let votes = client
+        .open_votes(account)
+        .await?
+        .into_iter()
+        .map(|vote| {
+            let id = vote.id.into();
+            let progress = &vote.progress;
+            let ends = vote.ends.map(|ends| ends.unique_saturated_into());
+            Row {
+                id,
+                text: format!(
+                    "Vote {}: {} in favor, {} against of {} needed, {}{}",
+                    id,
+                    progress.in_favor(),
+                    progress.against(),
+                    progress.threshold_in_favor(),
+                    time_left(ends, clock),
+                    if vote.voted { ", voted" } else { "" },
+                ),
+            }
+        })
+        .collect();
+    let bounties = client
+        .open_bounties(min)
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(id, bounty)| {
+            let id = id.into();
+            Row {
+                id,
+                text: format!(
+                    "Bounty {}: {} left, posted by {}",
+                    id,
+                    bounty.total(),
+                    properties.ss58(&bounty.depositer())
+                ),
+            }
+        })
+        .collect();
+    Ok(Summary {
+        orgs,
+        votes,
+        bounties,
+    })
+}
+
+async fn submissions<N: Node, C>(client: &C, bounty: u64) -> Result<Vec<Row>>
+where
+    C: BountyClient<N>,
+    N::Runtime: Bounty,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    <N::Runtime as Bounty>::BountyId: From<u64>,
+    <N::Runtime as Bounty>::SubmissionId: Display,
+    <N::Runtime as Balances>::Balance: Display,
+{
+    let properties = client.chain_properties();
+    Ok(client
+        .open_submissions(bounty.into())
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(id, submission, reviewer, deadline)| {
+            let due = if deadline.passed {
+                ", overdue"
+            } else if deadline.nearing {
+                ", nearing timeout"
+            } else {
+                ""
+            };
+            Row {
+                id: 0,
+                text: format!(
+                    "Submission {}: requests {} by {}, reviewer {}{}",
+                    id,
+                    submission.amount(),
+                    properties.ss58(&submission.submitter()),
+                    reviewer
+                        .map(|r| properties.ss58(&r))
+                        .unwrap_or_else(|| "unassigned".to_string()),
+                    due
+                ),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard() -> Dashboard {
+        let row = |id| {
+            Row {
+                id,
+                text: id.to_string(),
+            }
+        };
+        Dashboard::new(Summary {
+            orgs: vec![row(0)],
+            votes: vec![row(4), row(7)],
+            bounties: vec![row(2)],
+        })
+    }
+
+    #[test]
+    fn votes_prompt_for_a_direction() {
+        let mut dashboard = dashboard();
+        // only the vote pane votes
+        assert_eq!(dashboard.on_key(KeyCode::Char('v')), Action::None);
+        assert_eq!(dashboard.prompt, None);
+        dashboard.on_key(KeyCode::Tab);
+        dashboard.on_key(KeyCode::Down);
+        dashboard.on_key(KeyCode::Down);
+        assert_eq!(dashboard.on_key(KeyCode::Char('v')), Action::None);
+        assert_eq!(dashboard.prompt, Some(7));
+        // keys other than a direction are ignored while prompting
+        assert_eq!(dashboard.on_key(KeyCode::Char('q')), Action::None);
+        assert_eq!(
+            dashboard.on_key(KeyCode::Char('n')),
+            Action::Vote(7, VoterView::Against)
+        );
+        dashboard.on_key(KeyCode::Char('v'));
+        dashboard.on_key(KeyCode::Esc);
+        assert_eq!(dashboard.prompt, None);
+        assert_eq!(dashboard.on_key(KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn bounties_drill_into_their_submissions() {
+        let mut dashboard = dashboard();
+        dashboard.on_key(KeyCode::BackTab);
+        assert_eq!(dashboard.pane, Pane::Bounties);
+        assert_eq!(dashboard.on_key(KeyCode::Enter), Action::Submissions(2));
+        dashboard.show_submissions(2, vec![]);
+        dashboard.on_key(KeyCode::Esc);
+        assert_eq!(dashboard.submissions, None);
+    }
+
+    #[test]
+    fn selections_stay_in_range_across_refreshes() {
+        let mut dashboard = dashboard();
+        dashboard.on_key(KeyCode::Tab);
+        dashboard.on_key(KeyCode::Down);
+        assert_eq!(dashboard.selected_row().unwrap().id, 7);
+        dashboard.update(Summary::default());
+        assert_eq!(dashboard.selected, [0, 0, 0]);
+        assert_eq!(dashboard.selected_row(), None);
+    }
+
+    #[test]
+    fn time_left_is_rendered_in_blocks_and_time() {
+        let clock = BlockClock {
+            best: 100,
+            now: 0,
+            block_time: 6000,
+        };
+        assert_eq!(time_left(None, Some(clock)), "no deadline");
+        assert_eq!(time_left(Some(120), None), "ends at block 120");
+        assert_eq!(time_left(Some(90), Some(clock)), "ending");
+        assert_eq!(time_left(Some(110), Some(clock)), "10 blocks (~1m) left");
+        assert_eq!(
+            time_left(Some(1100), Some(clock)),
+            "1000 blocks (~1h 40m) left"
+        );
+        assert_eq!(format_millis(2 * 86_400_000 + 3_600_000), "2d 1h");
+        assert_eq!(format_millis(59_000), "<1m");
+    }
+}
@@ -1,3 +1,8 @@
+use crate::args::{
+    AmountArg,
+    OrgArg,
+    Resolver,
+};
 use clap::Clap;
 use core::fmt::{
     Debug,
@@ -9,6 +14,7 @@ use substrate_subxt::{
     system::System,
 };
 use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
     donate::{
         Donate,
         DonateClient,
@@ -16,22 +22,22 @@ use sunshine_bounty_client::{
     org::Org,
 };
 use sunshine_client_utils::{
-    crypto::ss58::Ss58,
     Node,
     Result,
 };
 
 #[derive(Clone, Debug, Clap)]
 pub struct PropDonateCommand {
-    pub org: u64,
+    pub org: OrgArg,
     pub rem_recipient: String,
-    pub amt: u128,
+    pub amt: AmountArg,
 }
 
 impl PropDonateCommand {
     pub async fn exec<N: Node, C: DonateClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Donate,
@@ -39,18 +45,18 @@ impl PropDonateCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Balances>::Balance: From<u128> + Display,
     {
-        let remainder_recipient: Ss58<N::Runtime> =
-            self.rem_recipient.parse()?;
+        let org = resolver.org(client, &self.org).await?;
+        let remainder_recipient =
+            resolver.account::<N::Runtime>(&self.rem_recipient)?;
+        let amt = resolver.amount(&self.amt)?;
+        resolver.confirm()?;
         let event = client
-            .make_prop_donation(
-                self.org.into(),
-                remainder_recipient.0,
-                self.amt.into(),
-            )
+            .make_prop_donation(org, remainder_recipient, amt.into())
             .await?;
+        let properties = client.chain_properties();
         println!(
-            "AccountId {:?} donated {} to weighted OrgId {} and {} to the Remainder Recipient {}",
-            event.sender, event.amt_to_org, event.org, event.amt_to_recipient, event.rem_recipient,
+            "AccountId {} donated {} to weighted OrgId {} and {} to the Remainder Recipient {}",
+            properties.ss58(&event.sender), event.amt_to_org, event.org, event.amt_to_recipient, properties.ss58(&event.rem_recipient),
         );
         Ok(())
     }
@@ -58,15 +64,16 @@ impl PropDonateCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub struct EqualDonateCommand {
-    pub org: u64,
+    pub org: OrgArg,
     pub rem_recipient: String,
-    pub amt: u128,
+    pub amt: AmountArg,
 }
 
 impl EqualDonateCommand {
     pub async fn exec<N: Node, C: DonateClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Donate,
@@ -74,18 +81,18 @@ impl EqualDonateCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Balances>::Balance: From<u128> + Display,
     {
-        let remainder_recipient: Ss58<N::Runtime> =
-            self.rem_recipient.parse()?;
+        let org = resolver.org(client, &self.org).await?;
+        let remainder_recipient =
+            resolver.account::<N::Runtime>(&self.rem_recipient)?;
+        let amt = resolver.amount(&self.amt)?;
+        resolver.confirm()?;
         let event = client
-            .make_equal_donation(
-                self.org.into(),
-                remainder_recipient.0,
-                self.amt.into(),
-            )
+            .make_equal_donation(org, remainder_recipient, amt.into())
             .await?;
+        let properties = client.chain_properties();
         println!(
-            "AccountId {:?} donated {} to flat OrgId {} and {} to the Remainder Recipient {}",
-            event.sender, event.amt_to_org, event.org, event.amt_to_recipient, event.rem_recipient,
+            "AccountId {} donated {} to flat OrgId {} and {} to the Remainder Recipient {}",
+            properties.ss58(&event.sender), event.amt_to_org, event.org, event.amt_to_recipient, properties.ss58(&event.rem_recipient),
         );
         Ok(())
     }
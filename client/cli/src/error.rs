@@ -1,9 +1,6 @@
+use sunshine_bounty_client::utils::bounty::SubmissionKind;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
-#[error("Vote percent threshold input must be 0 < x < 100")]
-pub struct VotePercentThresholdInputBoundError;
-
 #[derive(Debug, Error)]
 #[error("Input error for posting bounty.")]
 pub struct PostBountyInputError;
@@ -11,3 +8,93 @@ pub struct PostBountyInputError;
 #[derive(Debug, Error)]
 #[error("Invalid Github Issue Url.")]
 pub struct InvalidGithubIssueUrl;
+
+#[derive(Debug, Error)]
+#[error("Bounty expects submissions to reference {0:?}")]
+pub struct SubmissionKindMismatch(pub SubmissionKind);
+
+#[derive(Debug, Error)]
+#[error("Asset {0} is not accepted for bounties")]
+pub struct AssetNotAcceptedForBounties(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid bounty tag {0}, expected up to 16 lowercase letters, digits or dashes")]
+pub struct InvalidBountyTag(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid org {0}, expected an id or a handle of up to 32 lowercase letters, digits or dashes")]
+pub struct InvalidOrgHandle(pub String);
+
+#[derive(Debug, Error)]
+#[error("No org claimed the handle @{0}")]
+pub struct UnknownOrgHandle(pub String);
+
+#[derive(Debug, Error)]
+#[error("Unknown key scheme {0}, expected one of sr25519, ed25519, ecdsa")]
+pub struct UnknownKeyScheme(pub String);
+
+#[derive(Debug, Error)]
+#[error("Unknown dev account {0}, expected one of alice, bob, charlie, dave, eve, ferdie, one, two")]
+pub struct UnknownDevAccount(pub String);
+
+#[derive(Debug, Error)]
+#[error("Scenario assertion failed: {0}")]
+pub struct ScenarioAssertionFailed(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid amount {0}, expected a number like 10, 10.5 or 10.5k")]
+pub struct InvalidAmount(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid split {0}, expected an account and its share in percent like bob=25")]
+pub struct InvalidSplit(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid direction {0}, expected one of in-favor, against, abstain, not-voted")]
+pub struct InvalidDirection(pub String);
+
+#[derive(Debug, Error)]
+#[error("Invalid duration {0}, expected blocks like 100blocks or a time like 2h or 3d")]
+pub struct InvalidDuration(pub String);
+
+#[derive(Debug, Error)]
+#[error(
+    "The chain does not expose its block time, pass the duration in blocks"
+)]
+pub struct UnknownBlockTime;
+
+#[derive(Debug, Error)]
+#[error("No address book entry for @{0}")]
+pub struct UnknownAlias(pub String);
+
+#[derive(Debug, Error)]
+#[error("Address book already has an entry for @{0}")]
+pub struct AliasAlreadyExists(pub String);
+
+#[derive(Debug, Error)]
+#[error("Aborted, nothing was submitted")]
+pub struct ConfirmationDeclined;
+
+#[derive(Debug, Error)]
+#[error("Invalid cid {0}")]
+pub struct InvalidCid(pub String);
+
+#[derive(Debug, Error)]
+#[error("{0} is not a member of org {1}")]
+pub struct NotAnOrgMember(pub String, pub String);
+
+#[derive(Debug, Error)]
+#[error("Fork either the listed members or those above --min-shares")]
+pub struct ConflictingForkFilters;
+
+#[derive(Debug, Error)]
+#[error("Invalid state root {0}, expected the hex of a block hash")]
+pub struct InvalidStateRoot(pub String);
+
+#[derive(Debug, Error)]
+#[error("No chain spec, pass it with --chain-spec-path")]
+pub struct MissingChainSpec;
+
+#[derive(Debug, Error)]
+#[error("Invalid airdrop row {0}: {1}, expected an account and its shares like bob,100")]
+pub struct InvalidAirdropRow(pub usize, pub String);
@@ -1,9 +1,20 @@
+pub mod address;
+pub mod args;
+pub mod backup;
 pub mod bank;
 pub mod bounty;
+#[cfg(feature = "tui")]
+pub mod dashboard;
 pub mod donate;
 mod error;
 pub mod org;
+pub mod recovery;
+pub mod run;
+pub mod scheme;
+pub mod script;
 pub mod shares;
+pub mod table;
 mod utils;
 pub mod vote;
+pub mod watch;
 pub use crate::error::*;
@@ -1,3 +1,14 @@
+use crate::{
+    args::{
+        OrgArg,
+        Resolver,
+    },
+    error::{
+        ConflictingForkFilters,
+        InvalidOrgHandle,
+        NotAnOrgMember,
+    },
+};
 use clap::Clap;
 use core::fmt::{
     Debug,
@@ -8,6 +19,7 @@ use substrate_subxt::{
     system::System,
 };
 use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
     org::{
         AccountShare,
         Org,
@@ -15,22 +27,39 @@ use sunshine_bounty_client::{
     },
     TextBlock,
 };
+use sunshine_bounty_utils::organization::{
+    handle_name,
+    org_handle,
+    MemberFilter,
+    OrgHandle,
+};
 use sunshine_client_utils::{
-    crypto::ss58::Ss58,
     Node,
     Result,
 };
 
+fn parse_handle(handle: &str) -> Result<OrgHandle> {
+    Ok(org_handle(handle)
+        .ok_or_else(|| InvalidOrgHandle(handle.to_string()))?)
+}
+
 #[derive(Clone, Debug, Clap)]
 pub struct NewFlatOrgCommand {
     pub constitution: String,
     pub sudo: Option<String>,
-    pub parent_org: Option<u64>,
+    pub parent_org: Option<OrgArg>,
     pub members: Vec<String>,
+    /// Claims a unique handle for the org
+    #[clap(long)]
+    pub handle: Option<String>,
 }
 
 impl NewFlatOrgCommand {
-    pub async fn exec<N: Node, C: OrgClient<N>>(&self, client: &C) -> Result<()>
+    pub async fn exec<N: Node, C: OrgClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
     where
         N::Runtime: Org,
         <N::Runtime as System>::AccountId: Ss58Codec,
@@ -38,34 +67,39 @@ impl NewFlatOrgCommand {
         <N::Runtime as Org>::Constitution: From<TextBlock>,
     {
         let sudo = if let Some(acc) = &self.sudo {
-            let new_acc: Ss58<N::Runtime> = acc.parse()?;
-            Some(new_acc.0)
+            Some(resolver.account::<N::Runtime>(acc)?)
         } else {
             None
         };
         let parent_org: Option<<N::Runtime as Org>::OrgId> =
-            if let Some(org) = self.parent_org {
-                Some(org.into())
+            if let Some(org) = &self.parent_org {
+                Some(resolver.org(client, org).await?)
             } else {
                 None
             };
+        let handle = self.handle.as_deref().map(parse_handle).transpose()?;
         let constitution = TextBlock {
             text: (*self.constitution).to_string(),
         };
         let members = self
             .members
             .iter()
-            .map(|acc| {
-                let mem: Ss58<N::Runtime> = acc.parse()?;
-                Ok(mem.0)
-            })
+            .map(|acc| resolver.account::<N::Runtime>(acc))
             .collect::<Result<Vec<_>>>()?;
+        resolver.confirm()?;
         let event = client
-            .new_flat_org(sudo, parent_org, constitution.into(), &members)
+            .new_flat_org(
+                sudo,
+                parent_org,
+                constitution.into(),
+                &members,
+                handle,
+            )
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} created a flat organization with OrgId: {}, constitution: {:?} and {} members of equal ownership weight",
-            event.caller, event.new_id, event.constitution, event.total
+            properties.ss58(&event.caller), event.new_id, event.constitution, event.total
         );
         Ok(())
     }
@@ -75,12 +109,19 @@ impl NewFlatOrgCommand {
 pub struct NewWeightedOrgCommand {
     pub constitution: String,
     pub sudo: Option<String>,
-    pub parent_org: Option<u64>,
+    pub parent_org: Option<OrgArg>,
     pub members: Vec<AccountShare>,
+    /// Claims a unique handle for the org
+    #[clap(long)]
+    pub handle: Option<String>,
 }
 
 impl NewWeightedOrgCommand {
-    pub async fn exec<N: Node, C: OrgClient<N>>(&self, client: &C) -> Result<()>
+    pub async fn exec<N: Node, C: OrgClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
     where
         N::Runtime: Org,
         <N::Runtime as System>::AccountId: Ss58Codec,
@@ -90,17 +131,17 @@ impl NewWeightedOrgCommand {
     {
         let sudo: Option<<N::Runtime as System>::AccountId> =
             if let Some(acc) = &self.sudo {
-                let new_acc: Ss58<N::Runtime> = acc.parse()?;
-                Some(new_acc.0)
+                Some(resolver.account::<N::Runtime>(acc)?)
             } else {
                 None
             };
         let parent_org: Option<<N::Runtime as Org>::OrgId> =
-            if let Some(org) = self.parent_org {
-                Some(org.into())
+            if let Some(org) = &self.parent_org {
+                Some(resolver.org(client, org).await?)
             } else {
                 None
             };
+        let handle = self.handle.as_deref().map(parse_handle).transpose()?;
         let constitution = TextBlock {
             text: (*self.constitution).to_string(),
         };
@@ -108,19 +149,162 @@ impl NewWeightedOrgCommand {
             .members
             .iter()
             .map(|acc_share| {
-                let mem: Ss58<N::Runtime> = acc_share.0.parse()?;
+                let mem = resolver.account::<N::Runtime>(&acc_share.0)?;
                 let amt_issued: <N::Runtime as Org>::Shares =
                     (acc_share.1).into();
-                Ok((mem.0, amt_issued))
+                Ok((mem, amt_issued))
             })
             .collect::<Result<Vec<_>>>()?;
+        resolver.confirm()?;
         let event = client
-            .new_weighted_org(sudo, parent_org, constitution.into(), &members)
+            .new_weighted_org(
+                sudo,
+                parent_org,
+                constitution.into(),
+                &members,
+                handle,
+            )
             .await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} created a weighted organization with OrgId: {}, constitution: {:?} and {} total shares minted for new members",
-            event.caller, event.new_id, event.constitution, event.total
+            properties.ss58(&event.caller), event.new_id, event.constitution, event.total
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct ForkOrgCommand {
+    pub organization: OrgArg,
+    pub constitution: String,
+    /// The members to copy, all of them if none are listed
+    pub members: Vec<String>,
+    /// Copies only the members holding at least these voting shares
+    #[clap(long)]
+    pub min_shares: Option<u64>,
+    /// Issues one share to every copied member instead of their shares
+    #[clap(long)]
+    pub equal: bool,
+}
+
+impl ForkOrgCommand {
+    pub async fn exec<N: Node, C: OrgClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Org,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        <N::Runtime as Org>::Shares: From<u64>,
+        <N::Runtime as Org>::Constitution: From<TextBlock>,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let filter = match (self.members.is_empty(), self.min_shares) {
+            (true, None) => MemberFilter::All,
+            (true, Some(min)) => MemberFilter::MinShares(min.into()),
+            (false, None) => {
+                MemberFilter::Accounts(
+                    self.members
+                        .iter()
+                        .map(|acc| resolver.account::<N::Runtime>(acc))
+                        .collect::<Result<Vec<_>>>()?,
+                )
+            }
+            (false, Some(_)) => return Err(ConflictingForkFilters.into()),
+        };
+        let constitution = TextBlock {
+            text: (*self.constitution).to_string(),
+        };
+        resolver.confirm()?;
+        let event = client
+            .fork_org(org, constitution.into(), filter, self.equal)
+            .await?;
+        println!(
+            "Forked org {} into org {} with {} members",
+            event.source_org, event.new_org, event.members
         );
         Ok(())
     }
 }
+
+#[derive(Clone, Debug, Clap)]
+pub struct SetOrgHandleCommand {
+    pub organization: OrgArg,
+    /// The new handle, the current one is released if omitted
+    pub handle: Option<String>,
+}
+
+impl SetOrgHandleCommand {
+    pub async fn exec<N: Node, C: OrgClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Org,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let handle = self.handle.as_deref().map(parse_handle).transpose()?;
+        resolver.confirm()?;
+        let event = client.set_org_handle(org, handle).await?;
+        if let Some(handle) = &event.handle {
+            println!(
+                "Org {} is now known as @{}",
+                event.organization,
+                handle_name(handle)
+            );
+        } else {
+            println!("Org {} released its handle", event.organization);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct MembershipProofCommand {
+    pub organization: OrgArg,
+    pub account: String,
+}
+
+impl MembershipProofCommand {
+    pub async fn exec<N: Node, C: OrgClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Org,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        <N::Runtime as Org>::Shares: Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let account = resolver.account::<N::Runtime>(&self.account)?;
+        let properties = client.chain_properties();
+        let proof = match client.membership_proof(org, &account).await? {
+            Some(proof) => proof,
+            None => {
+                let who = properties.ss58(&account);
+                return Err(NotAnOrgMember(who, org.to_string()).into())
+            }
+        };
+        println!("Membership root of org {}: {:#x}", org, proof.root);
+        println!(
+            "Account {} holds {} voting shares",
+            properties.ss58(&account),
+            proof.shares
+        );
+        // the siblings from the leaf up, 32 bytes each
+        let siblings = proof
+            .proof
+            .iter()
+            .map(|sibling| format!("{:x}", sibling))
+            .collect::<String>();
+        println!("Proof: 0x{}", siblings);
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use crate::args::Resolver;
+use clap::Clap;
+use substrate_subxt::{
+    sp_core::crypto::Ss58Codec,
+    system::System,
+};
+use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
+    recovery::{
+        Recovery,
+        RecoveryClient,
+    },
+};
+use sunshine_client_utils::{
+    Node,
+    Result,
+};
+
+#[derive(Clone, Debug, Clap)]
+pub struct AddFriendsCommand {
+    pub threshold: u16,
+    pub friends: Vec<String>,
+}
+
+impl AddFriendsCommand {
+    pub async fn exec<N: Node, C: RecoveryClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Recovery,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let mut friends = Vec::with_capacity(self.friends.len());
+        for friend in &self.friends {
+            friends.push(resolver.account::<N::Runtime>(friend)?);
+        }
+        resolver.confirm()?;
+        let event =
+            client.add_recovery_friends(friends, self.threshold).await?;
+        let properties = client.chain_properties();
+        println!(
+            "AccountId {} can be recovered by {} of its {} friends",
+            properties.ss58(&event.lost),
+            self.threshold,
+            self.friends.len(),
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct InitiateCommand {
+    pub lost: String,
+}
+
+impl InitiateCommand {
+    pub async fn exec<N: Node, C: RecoveryClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Recovery,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let lost = resolver.account::<N::Runtime>(&self.lost)?;
+        resolver.confirm()?;
+        let event = client.initiate_recovery(lost).await?;
+        let properties = client.chain_properties();
+        println!(
+            "AccountId {} started recovering AccountId {}",
+            properties.ss58(&event.rescuer),
+            properties.ss58(&event.lost),
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VouchCommand {
+    pub lost: String,
+    pub rescuer: String,
+}
+
+impl VouchCommand {
+    pub async fn exec<N: Node, C: RecoveryClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Recovery,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let lost = resolver.account::<N::Runtime>(&self.lost)?;
+        let rescuer = resolver.account::<N::Runtime>(&self.rescuer)?;
+        resolver.confirm()?;
+        let event = client.vouch_recovery(lost, rescuer).await?;
+        let properties = client.chain_properties();
+        println!(
+            "AccountId {} vouched for AccountId {} to recover AccountId {}",
+            properties.ss58(&event.friend),
+            properties.ss58(&event.rescuer),
+            properties.ss58(&event.lost),
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct ClaimCommand {
+    pub lost: String,
+}
+
+impl ClaimCommand {
+    pub async fn exec<N: Node, C: RecoveryClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Recovery,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let lost = resolver.account::<N::Runtime>(&self.lost)?;
+        resolver.confirm()?;
+        let event = client.claim_recovered(lost).await?;
+        let properties = client.chain_properties();
+        println!(
+            "AccountId {} recovered AccountId {}",
+            properties.ss58(&event.rescuer),
+            properties.ss58(&event.lost),
+        );
+        Ok(())
+    }
+}
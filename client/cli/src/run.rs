@@ -0,0 +1,77 @@
+//! Keeps the client connected and prints every event of the chain
+//!
+//! With `--health-port` the client also answers `/health` and `/metrics` on
+//! that port, see `sunshine_bounty_client::health`, so deployments can tell
+//! a hung client from a healthy one. The node is polled every `--interval`
+//! seconds even while no events arrive.
+use async_std::{
+    future::timeout,
+    net::TcpListener,
+    task,
+};
+use clap::Clap;
+use std::time::{
+    Duration,
+    Instant,
+};
+use substrate_subxt::{
+    Error,
+    EventSubscription,
+};
+use sunshine_bounty_client::health::{
+    self,
+    Health,
+};
+use sunshine_client_utils::{
+    Client,
+    Node,
+    Result,
+};
+
+#[derive(Clone, Debug, Clap)]
+pub struct RunCommand {
+    /// Serve `/health` and `/metrics` on this port, off when not set
+    #[clap(long = "health-port")]
+    pub health_port: Option<u16>,
+    /// Seconds between polls of the node
+    #[clap(long = "interval", default_value = "6")]
+    pub interval: u64,
+}
+
+impl RunCommand {
+    pub async fn exec<N: Node, C: Client<N>>(&self, client: &C) -> Result<()> {
+        let health = Health::new();
+        if let Some(port) = self.health_port {
+            let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+            task::spawn(health::serve(health.clone(), listener));
+        }
+        let chain = client.chain_client();
+        let sub = chain.subscribe_events().await?;
+        let mut events = EventSubscription::new(sub, chain.events_decoder());
+        let interval = Duration::from_secs(self.interval.max(1));
+        let mut next_poll = Instant::now();
+        loop {
+            if Instant::now() >= next_poll {
+                if let Err(err) = health.poll(client).await {
+                    eprintln!("Cannot reach the node: {}", err);
+                }
+                next_poll = Instant::now() + interval;
+            }
+            let wait = next_poll.saturating_duration_since(Instant::now());
+            // a subscription to a lost node never ends, the poll notices
+            let event = match timeout(wait, events.next()).await {
+                Ok(Some(event)) => event,
+                Ok(None) => return Ok(()),
+                Err(_) => continue,
+            };
+            health.enqueued(1);
+            match event {
+                Ok(event) => println!("{}::{}", event.module, event.variant),
+                // the dispatch errors of failed extrinsics are not events
+                Err(Error::Runtime(_)) => {}
+                Err(err) => return Err(err.into()),
+            }
+            health.handled(1);
+        }
+    }
+}
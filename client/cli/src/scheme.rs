@@ -0,0 +1,88 @@
+use crate::error::UnknownKeyScheme;
+use core::str::FromStr;
+use std::{
+    fmt,
+    path::Path,
+};
+use sunshine_client_utils::Result;
+
+const KEY_SCHEME_FILE: &str = "key_scheme";
+
+/// The signature scheme of the key in the keystore
+///
+/// It is saved under the client root by `key set`, so later sessions open
+/// the keystore with the same pair type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyScheme {
+    Sr25519,
+    Ed25519,
+    Ecdsa,
+}
+
+impl Default for KeyScheme {
+    fn default() -> Self {
+        Self::Sr25519
+    }
+}
+
+impl KeyScheme {
+    /// Loads the scheme saved under the client root, sr25519 for keystores
+    /// set before the scheme was saved
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(KEY_SCHEME_FILE);
+        if path.exists() {
+            Ok(std::fs::read_to_string(&path)?.parse()?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        std::fs::create_dir_all(root)?;
+        std::fs::write(root.join(KEY_SCHEME_FILE), self.to_string())?;
+        Ok(())
+    }
+}
+
+impl FromStr for KeyScheme {
+    type Err = UnknownKeyScheme;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sr25519" => Ok(Self::Sr25519),
+            "ed25519" => Ok(Self::Ed25519),
+            "ecdsa" => Ok(Self::Ecdsa),
+            _ => Err(UnknownKeyScheme(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for KeyScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sr25519 => "sr25519",
+            Self::Ed25519 => "ed25519",
+            Self::Ecdsa => "ecdsa",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemes_round_trip() {
+        for scheme in
+            &[KeyScheme::Sr25519, KeyScheme::Ed25519, KeyScheme::Ecdsa]
+        {
+            assert_eq!(
+                scheme.to_string().parse::<KeyScheme>().unwrap(),
+                *scheme
+            );
+        }
+        assert_eq!("ECDSA\n".parse::<KeyScheme>().unwrap(), KeyScheme::Ecdsa);
+        assert!("secp256k1".parse::<KeyScheme>().is_err());
+    }
+}
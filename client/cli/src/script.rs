@@ -0,0 +1,739 @@
+use crate::{
+    error::{
+        ScenarioAssertionFailed,
+        UnknownDevAccount,
+    },
+    utils::GithubIssueMetadata,
+};
+use clap::Clap;
+use core::fmt::{
+    Debug,
+    Display,
+};
+use libipld::{
+    cache::Cache,
+    cbor::DagCborCodec,
+};
+use parity_scale_codec::Encode;
+use serde::Deserialize;
+use std::{
+    convert::TryInto,
+    path::PathBuf,
+};
+use substrate_subxt::{
+    balances::{
+        Balances,
+        TransferCallExt,
+    },
+    sp_core::crypto::Ss58Codec,
+    sp_runtime::{
+        traits::{
+            IdentifyAccount,
+            Verify,
+        },
+        Permill,
+    },
+    system::{
+        AccountStoreExt,
+        System,
+    },
+    ExtrinsicSuccess,
+    PairSigner,
+    Runtime,
+    SignedExtension,
+    SignedExtra,
+};
+use sunshine_bounty_client::{
+    bounty::{
+        ApproveBountySubmissionCallExt,
+        Bounty,
+        ContributeToBountyCallExt,
+        PostBountyCallExt,
+        SubmitForBountyCallExt,
+    },
+    org::{
+        IssueSharesCallExt,
+        NewFlatOrgCallExt,
+        NewWeightedOrgCallExt,
+        Org,
+    },
+    utils::{
+        bounty::SubmissionKind,
+        organization::OrgRep,
+        share::ShareClass,
+        vote::{
+            VoteOutcome,
+            VoterView,
+            XorThreshold,
+        },
+    },
+    vote::{
+        CreatePercentVoteCallExt,
+        CreateSignalVoteCallExt,
+        SubmitVoteCallExt,
+        Vote,
+        VoteStatesStoreExt,
+        VoteThreshold,
+        VoteThresholdBuilder,
+    },
+    GithubIssue,
+    TextBlock,
+};
+use sunshine_client_utils::{
+    crypto::sr25519,
+    AccountKeyring,
+    Client,
+    Node,
+    OffchainConfig,
+    Result,
+};
+
+/// A declarative scenario, executed step by step against the configured node
+#[derive(Clone, Debug, Deserialize)]
+pub struct Scenario {
+    pub name: Option<String>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Step {
+    /// Named dev account which signs the extrinsic i.e. `alice`
+    pub signer: String,
+    #[serde(flatten)]
+    pub action: Action,
+    #[serde(default)]
+    pub expect: Vec<Expectation>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    Transfer {
+        to: String,
+        amount: u128,
+    },
+    RegisterFlatOrg {
+        sudo: Option<String>,
+        parent: Option<u64>,
+        constitution: String,
+        members: Vec<String>,
+    },
+    RegisterWeightedOrg {
+        sudo: Option<String>,
+        parent: Option<u64>,
+        constitution: String,
+        members: Vec<(String, u64)>,
+    },
+    IssueShares {
+        org: u64,
+        who: String,
+        shares: u64,
+        #[serde(default)]
+        non_voting: bool,
+    },
+    CreateSignalVote {
+        topic: Option<String>,
+        #[serde(default)]
+        weighted: bool,
+        org: u64,
+        support: u64,
+        reject: Option<u64>,
+        duration: Option<u32>,
+    },
+    CreatePercentVote {
+        topic: Option<String>,
+        #[serde(default)]
+        weighted: bool,
+        org: u64,
+        support: u8,
+        reject: Option<u8>,
+        duration: Option<u32>,
+    },
+    SubmitVote {
+        vote_id: u64,
+        direction: ScriptVoterView,
+        justification: Option<String>,
+    },
+    PostBounty {
+        issue_url: String,
+        amount: u128,
+        #[serde(default)]
+        pull_request: bool,
+    },
+    ContributeToBounty {
+        bounty_id: u64,
+        amount: u128,
+    },
+    SubmitForBounty {
+        bounty_id: u64,
+        issue_url: String,
+        amount: u128,
+    },
+    ApproveSubmission {
+        submission_id: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptVoterView {
+    InFavor,
+    Against,
+    Abstain,
+}
+
+impl From<ScriptVoterView> for VoterView {
+    fn from(v: ScriptVoterView) -> VoterView {
+        match v {
+            ScriptVoterView::InFavor => VoterView::InFavor,
+            ScriptVoterView::Against => VoterView::Against,
+            ScriptVoterView::Abstain => VoterView::Abstain,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptVoteOutcome {
+    Voting,
+    Approved,
+    Rejected,
+}
+
+impl PartialEq<VoteOutcome> for ScriptVoteOutcome {
+    fn eq(&self, other: &VoteOutcome) -> bool {
+        matches!(
+            (self, other),
+            (ScriptVoteOutcome::Voting, VoteOutcome::Voting)
+                | (ScriptVoteOutcome::Approved, VoteOutcome::Approved)
+                | (ScriptVoteOutcome::Rejected, VoteOutcome::Rejected)
+        )
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Expectation {
+    /// The extrinsic emitted an event with this variant name, i.e. `Voted`
+    Event(String),
+    /// The vote state outcome after the step
+    VoteOutcome {
+        vote_id: u64,
+        outcome: ScriptVoteOutcome,
+    },
+    /// The free balance of the account after the step
+    Balance { account: String, free: u128 },
+}
+
+pub fn dev_account(name: &str) -> Result<AccountKeyring> {
+    let key = match name.to_lowercase().as_str() {
+        "alice" => AccountKeyring::Alice,
+        "bob" => AccountKeyring::Bob,
+        "charlie" => AccountKeyring::Charlie,
+        "dave" => AccountKeyring::Dave,
+        "eve" => AccountKeyring::Eve,
+        "ferdie" => AccountKeyring::Ferdie,
+        "one" => AccountKeyring::One,
+        "two" => AccountKeyring::Two,
+        _ => return Err(UnknownDevAccount(name.to_string()).into()),
+    };
+    Ok(key)
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct ScriptRunCommand {
+    pub file: PathBuf,
+}
+
+impl ScriptRunCommand {
+    pub async fn exec<N: Node, C: Client<N>>(&self, client: &C) -> Result<()>
+    where
+        N::Runtime: Vote + Bounty,
+        <<<N::Runtime as Runtime>::Extra as SignedExtra<N::Runtime>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync,
+        <N::Runtime as Runtime>::Signature: From<sr25519::Signature>,
+        <<N::Runtime as Runtime>::Signature as Verify>::Signer:
+            From<sr25519::Public> + IdentifyAccount<AccountId = <N::Runtime as System>::AccountId>,
+        <N::Runtime as System>::AccountId: Ss58Codec + From<sr25519::Public> + Into<<N::Runtime as System>::Address>,
+        <N::Runtime as System>::BlockNumber: From<u32>,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        <N::Runtime as Org>::Shares: From<u64> + Display,
+        <N::Runtime as Org>::Cid: From<libipld::cid::Cid>,
+        <N::Runtime as Org>::Constitution: From<TextBlock>,
+        <N::Runtime as Vote>::Signal: From<u64>,
+        <N::Runtime as Vote>::VoteId: From<u64> + Display,
+        <N::Runtime as Vote>::Percent: From<Permill>,
+        <N::Runtime as Vote>::VoterView: From<VoterView>,
+        <N::Runtime as Vote>::VoteTopic: From<TextBlock>,
+        <N::Runtime as Vote>::VoteJustification: From<TextBlock>,
+        <N::Runtime as Bounty>::IpfsReference: From<libipld::cid::Cid>,
+        <N::Runtime as Bounty>::BountyId: From<u64>,
+        <N::Runtime as Bounty>::SubmissionId: From<u64>,
+        <N::Runtime as Bounty>::BountyPost: From<GithubIssue>,
+        <N::Runtime as Bounty>::BountySubmission: From<GithubIssue>,
+        <N::Runtime as Balances>::Balance: From<u128> + Into<u128> + Display,
+        C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Org>::Constitution>
+            + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Vote>::VoteTopic>
+            + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Vote>::VoteJustification>
+            + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Bounty>::BountyPost>
+            + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Bounty>::BountySubmission>,
+    {
+        let raw = std::fs::read_to_string(&self.file)?;
+        let scenario: Scenario = serde_yaml::from_str(&raw)?;
+        println!(
+            "Running scenario {} ({} steps)",
+            scenario.name.as_deref().unwrap_or("<unnamed>"),
+            scenario.steps.len()
+        );
+        for (i, step) in scenario.steps.iter().enumerate() {
+            let n = i + 1;
+            let result = match run_step(client, step).await {
+                Ok(success) => check_expectations(client, step, &success).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => println!("[PASS] step {}: {:?}", n, step.action),
+                Err(e) => {
+                    println!("[FAIL] step {}: {:?}", n, step.action);
+                    println!("       {}", e);
+                    println!("{} of {} steps passed", i, scenario.steps.len());
+                    return Err(e)
+                }
+            }
+        }
+        println!(
+            "{} of {} steps passed",
+            scenario.steps.len(),
+            scenario.steps.len()
+        );
+        Ok(())
+    }
+}
+
+/// The topic, threshold and duration of a vote creating action, checked
+/// by `VoteThresholdBuilder` before it is submitted
+fn vote_threshold<R: Vote>(
+    action: &Action,
+) -> Result<(Option<String>, VoteThreshold<R>, Option<u32>)>
+where
+    R::OrgId: From<u64>,
+    R::Signal: From<u64>,
+{
+    let builder = |weighted: bool, org: u64| {
+        if weighted {
+            VoteThresholdBuilder::<R>::weighted(org.into())
+        } else {
+            VoteThresholdBuilder::<R>::flat(org.into())
+        }
+    };
+    let vote = match action {
+        Action::CreateSignalVote {
+            topic,
+            weighted,
+            org,
+            support,
+            reject,
+            duration,
+        } => {
+            let threshold = builder(*weighted, *org)
+                .signal((*support).into(), reject.map(Into::into))?;
+            (topic.clone(), threshold, *duration)
+        }
+        Action::CreatePercentVote {
+            topic,
+            weighted,
+            org,
+            support,
+            reject,
+            duration,
+        } => {
+            let threshold =
+                builder(*weighted, *org).percent(*support, *reject)?;
+            (topic.clone(), threshold, *duration)
+        }
+        _ => unreachable!("only called for actions creating votes"),
+    };
+    Ok(vote)
+}
+
+async fn run_step<N: Node, C: Client<N>>(
+    client: &C,
+    step: &Step,
+) -> Result<ExtrinsicSuccess<N::Runtime>>
+where
+    N::Runtime: Vote + Bounty,
+    <<<N::Runtime as Runtime>::Extra as SignedExtra<N::Runtime>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync,
+    <N::Runtime as Runtime>::Signature: From<sr25519::Signature>,
+    <<N::Runtime as Runtime>::Signature as Verify>::Signer:
+        From<sr25519::Public> + IdentifyAccount<AccountId = <N::Runtime as System>::AccountId>,
+    <N::Runtime as System>::AccountId: Ss58Codec + From<sr25519::Public> + Into<<N::Runtime as System>::Address>,
+    <N::Runtime as System>::BlockNumber: From<u32>,
+    <N::Runtime as Org>::OrgId: From<u64>,
+    <N::Runtime as Org>::Shares: From<u64>,
+    <N::Runtime as Org>::Cid: From<libipld::cid::Cid>,
+    <N::Runtime as Org>::Constitution: From<TextBlock>,
+    <N::Runtime as Vote>::Signal: From<u64>,
+    <N::Runtime as Vote>::VoteId: From<u64>,
+    <N::Runtime as Vote>::Percent: From<Permill>,
+    <N::Runtime as Vote>::VoterView: From<VoterView>,
+    <N::Runtime as Vote>::VoteTopic: From<TextBlock>,
+    <N::Runtime as Vote>::VoteJustification: From<TextBlock>,
+    <N::Runtime as Bounty>::IpfsReference: From<libipld::cid::Cid>,
+    <N::Runtime as Bounty>::BountyId: From<u64>,
+    <N::Runtime as Bounty>::SubmissionId: From<u64>,
+    <N::Runtime as Bounty>::BountyPost: From<GithubIssue>,
+    <N::Runtime as Bounty>::BountySubmission: From<GithubIssue>,
+    <N::Runtime as Balances>::Balance: From<u128>,
+    C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Org>::Constitution>
+        + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Vote>::VoteTopic>
+        + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Vote>::VoteJustification>
+        + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Bounty>::BountyPost>
+        + Cache<OffchainConfig<N>, DagCborCodec, <N::Runtime as Bounty>::BountySubmission>,
+{
+    let signer = PairSigner::<N::Runtime, sr25519::Pair>::new(
+        dev_account(&step.signer)?.pair(),
+    );
+    let account = |name: &str| -> Result<<N::Runtime as System>::AccountId> {
+        Ok(dev_account(name)?.public().into())
+    };
+    let chain = client.chain_client();
+    let success = match &step.action {
+        Action::Transfer { to, amount } => {
+            chain
+                .transfer_and_watch(
+                    &signer,
+                    &account(to)?.into(),
+                    (*amount).into(),
+                )
+                .await?
+        }
+        Action::RegisterFlatOrg {
+            sudo,
+            parent,
+            constitution,
+            members,
+        } => {
+            let sudo = sudo.as_deref().map(account).transpose()?;
+            let constitution: <N::Runtime as Org>::Constitution = TextBlock {
+                text: constitution.clone(),
+            }
+            .into();
+            let constitution =
+                client.offchain_client().insert(constitution).await?;
+            let members = members
+                .iter()
+                .map(|m| account(m))
+                .collect::<Result<Vec<_>>>()?;
+            chain
+                .new_flat_org_and_watch(
+                    &signer,
+                    sudo,
+                    parent.map(Into::into),
+                    constitution.into(),
+                    &members,
+                    None,
+                )
+                .await?
+        }
+        Action::RegisterWeightedOrg {
+            sudo,
+            parent,
+            constitution,
+            members,
+        } => {
+            let sudo = sudo.as_deref().map(account).transpose()?;
+            let constitution: <N::Runtime as Org>::Constitution = TextBlock {
+                text: constitution.clone(),
+            }
+            .into();
+            let constitution =
+                client.offchain_client().insert(constitution).await?;
+            let members = members
+                .iter()
+                .map(|(m, s)| Ok((account(m)?, (*s).into())))
+                .collect::<Result<Vec<_>>>()?;
+            chain
+                .new_weighted_org_and_watch(
+                    &signer,
+                    sudo,
+                    parent.map(Into::into),
+                    constitution.into(),
+                    &members,
+                    None,
+                )
+                .await?
+        }
+        Action::IssueShares {
+            org,
+            who,
+            shares,
+            non_voting,
+        } => {
+            let class = if *non_voting {
+                ShareClass::NonVoting
+            } else {
+                ShareClass::Voting
+            };
+            chain
+                .issue_shares_and_watch(
+                    &signer,
+                    (*org).into(),
+                    &account(who)?,
+                    (*shares).into(),
+                    class,
+                )
+                .await?
+        }
+        Action::CreateSignalVote { .. } | Action::CreatePercentVote { .. } => {
+            let (topic, threshold, duration) =
+                vote_threshold::<N::Runtime>(&step.action)?;
+            let topic = if let Some(t) = topic {
+                let t: <N::Runtime as Vote>::VoteTopic =
+                    TextBlock { text: t }.into();
+                Some(client.offchain_client().insert(t).await?.into())
+            } else {
+                None
+            };
+            let duration = duration.map(Into::into);
+            match threshold.threshold() {
+                XorThreshold::Signal(t) => {
+                    chain
+                        .create_signal_vote_and_watch(
+                            &signer,
+                            topic,
+                            threshold.org(),
+                            t,
+                            duration,
+                            None,
+                        )
+                        .await?
+                }
+                XorThreshold::Percent(t) => {
+                    chain
+                        .create_percent_vote_and_watch(
+                            &signer,
+                            topic,
+                            threshold.org(),
+                            t,
+                            duration,
+                            None,
+                        )
+                        .await?
+                }
+                XorThreshold::PercentOf(..) => {
+                    unreachable!("scripts open percents of the total issuance")
+                }
+            }
+        }
+        Action::SubmitVote {
+            vote_id,
+            direction,
+            justification,
+        } => {
+            let justification = if let Some(j) = justification {
+                let j: <N::Runtime as Vote>::VoteJustification =
+                    TextBlock { text: j.clone() }.into();
+                Some(client.offchain_client().insert(j).await?.into())
+            } else {
+                None
+            };
+            let view: VoterView = (*direction).into();
+            chain
+                .submit_vote_and_watch(
+                    &signer,
+                    (*vote_id).into(),
+                    view.into(),
+                    justification,
+                )
+                .await?
+        }
+        Action::PostBounty {
+            issue_url,
+            amount,
+            pull_request,
+        } => {
+            let metadata: GithubIssueMetadata =
+                issue_url.as_str().try_into()?;
+            let bounty: <N::Runtime as Bounty>::BountyPost = GithubIssue {
+                repo_owner: metadata.owner,
+                repo_name: metadata.repo,
+                issue_number: metadata.issue,
+            }
+            .into();
+            let issue = Encode::encode(&bounty);
+            let info = client.offchain_client().insert(bounty).await?;
+            chain
+                .post_bounty_and_watch(
+                    &signer,
+                    issue,
+                    info.into(),
+                    (*amount).into(),
+                    None,
+                    if *pull_request {
+                        SubmissionKind::PullRequest
+                    } else {
+                        SubmissionKind::Any
+                    },
+                    vec![],
+                    vec![],
+                )
+                .await?
+        }
+        Action::ContributeToBounty { bounty_id, amount } => {
+            chain
+                .contribute_to_bounty_and_watch(
+                    &signer,
+                    (*bounty_id).into(),
+                    (*amount).into(),
+                    None,
+                )
+                .await?
+        }
+        Action::SubmitForBounty {
+            bounty_id,
+            issue_url,
+            amount,
+        } => {
+            let metadata: GithubIssueMetadata =
+                issue_url.as_str().try_into()?;
+            let submission: <N::Runtime as Bounty>::BountySubmission =
+                GithubIssue {
+                    repo_owner: metadata.owner,
+                    repo_name: metadata.repo,
+                    issue_number: metadata.issue,
+                }
+                .into();
+            let issue = Encode::encode(&submission);
+            let submission_ref =
+                client.offchain_client().insert(submission).await?;
+            chain
+                .submit_for_bounty_and_watch(
+                    &signer,
+                    (*bounty_id).into(),
+                    issue,
+                    submission_ref.into(),
+                    (*amount).into(),
+                    vec![],
+                )
+                .await?
+        }
+        Action::ApproveSubmission { submission_id } => {
+            chain
+                .approve_bounty_submission_and_watch(
+                    &signer,
+                    (*submission_id).into(),
+                )
+                .await?
+        }
+    };
+    Ok(success)
+}
+
+async fn check_expectations<N: Node, C: Client<N>>(
+    client: &C,
+    step: &Step,
+    success: &ExtrinsicSuccess<N::Runtime>,
+) -> Result<()>
+where
+    N::Runtime: Vote + Bounty,
+    <N::Runtime as System>::AccountId: Ss58Codec + From<sr25519::Public>,
+    <N::Runtime as Vote>::VoteId: From<u64>,
+    <N::Runtime as Balances>::Balance: Into<u128>,
+{
+    for expectation in step.expect.iter() {
+        match expectation {
+            Expectation::Event(name) => {
+                if !success.events.iter().any(|e| &e.variant == name) {
+                    let emitted = success
+                        .events
+                        .iter()
+                        .map(|e| format!("{}::{}", e.module, e.variant))
+                        .collect::<Vec<_>>();
+                    return Err(ScenarioAssertionFailed(format!(
+                        "expected event {}, emitted {:?}",
+                        name, emitted
+                    ))
+                    .into())
+                }
+            }
+            Expectation::VoteOutcome { vote_id, outcome } => {
+                let state = client
+                    .chain_client()
+                    .vote_states((*vote_id).into(), None)
+                    .await?;
+                if outcome != &state.outcome() {
+                    return Err(ScenarioAssertionFailed(format!(
+                        "expected outcome {:?} for VoteId {}, found {:?}",
+                        outcome,
+                        vote_id,
+                        state.outcome()
+                    ))
+                    .into())
+                }
+            }
+            Expectation::Balance { account, free } => {
+                let who: <N::Runtime as System>::AccountId =
+                    dev_account(account)?.public().into();
+                let found: u128 = client
+                    .chain_client()
+                    .account(&who, None)
+                    .await?
+                    .data
+                    .free
+                    .into();
+                if found != *free {
+                    return Err(ScenarioAssertionFailed(format!(
+                        "expected free balance {} for {}, found {}",
+                        free, account, found
+                    ))
+                    .into())
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scenario() {
+        let raw = r#"
+name: demo
+steps:
+  - signer: alice
+    action: register_flat_org
+    sudo: alice
+    constitution: good code lives forever
+    members: [alice, bob, charlie]
+    expect:
+      - event: NewFlatOrg
+  - signer: bob
+    action: submit_vote
+    vote_id: 1
+    direction: in_favor
+    expect:
+      - vote_outcome:
+          vote_id: 1
+          outcome: voting
+      - balance:
+          account: bob
+          free: 1000
+"#;
+        let scenario: Scenario = serde_yaml::from_str(raw).unwrap();
+        assert_eq!(scenario.name.as_deref(), Some("demo"));
+        assert_eq!(scenario.steps.len(), 2);
+        assert!(matches!(
+            scenario.steps[0].action,
+            Action::RegisterFlatOrg { .. }
+        ));
+        assert_eq!(scenario.steps[1].expect.len(), 2);
+    }
+
+    #[test]
+    fn unknown_dev_account_fails() {
+        assert!(dev_account("bob").is_ok());
+        assert!(dev_account("mallory").is_err());
+    }
+}
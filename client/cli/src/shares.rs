@@ -1,53 +1,162 @@
+use crate::{
+    args::{
+        OrgArg,
+        Resolver,
+    },
+    vote::csv_field,
+    InvalidAirdropRow,
+};
 use clap::Clap;
 use core::fmt::{
     Debug,
     Display,
 };
+use std::{
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
 use substrate_subxt::{
     sp_core::crypto::Ss58Codec,
     system::System,
 };
-use sunshine_bounty_client::org::{
-    AccountShare,
-    Org,
-    Org as Shares,
-    OrgClient as SharesClient,
+use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
+    org::{
+        AccountShare,
+        AirdropClient,
+        AirdropProgress,
+        AirdropStatus,
+        Org,
+        Org as Shares,
+        OrgClient as SharesClient,
+        SharesOfferedEvent,
+    },
+    utils::share::ShareClass,
 };
 use sunshine_client_utils::{
-    crypto::ss58::Ss58,
     Node,
     Result,
 };
 
+fn share_class(non_voting: bool) -> ShareClass {
+    if non_voting {
+        ShareClass::NonVoting
+    } else {
+        ShareClass::Voting
+    }
+}
+
+fn print_offer<N: Node, C: SharesClient<N>>(
+    client: &C,
+    event: &SharesOfferedEvent<N::Runtime>,
+) where
+    N::Runtime: Shares,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    <N::Runtime as Org>::OrgId: Display,
+    <N::Runtime as Org>::Shares: Display,
+    <N::Runtime as System>::BlockNumber: Display,
+{
+    println!(
+        "{} shares offered to account {} in the context of Org {} as grant \
+         {}, to be accepted before block {}",
+        event.shares,
+        client.chain_properties().ss58(&event.who),
+        event.organization,
+        event.grant_id,
+        event.expires_at
+    );
+}
+
+/// What burning `shares` of the class of `non_voting` from `account` does,
+/// for the confirmation of burns
+async fn burn_summary<N: Node, C: SharesClient<N>>(
+    client: &C,
+    org: <N::Runtime as Org>::OrgId,
+    account: &<N::Runtime as System>::AccountId,
+    shares: <N::Runtime as Shares>::Shares,
+    non_voting: bool,
+) -> Result<String>
+where
+    N::Runtime: Shares,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    <N::Runtime as Org>::OrgId: Display,
+    <N::Runtime as Org>::Shares: Display,
+{
+    let (class, held) = if non_voting {
+        let held = client.non_voting_shares(org, account.clone()).await?;
+        ("non-voting", held)
+    } else {
+        let held = client.share_profile(org, account.clone()).await?.total();
+        ("voting", held)
+    };
+    Ok(format!(
+        "burn {} of the {} {} shares {} holds in org {}",
+        shares,
+        held,
+        class,
+        client.chain_properties().ss58(account),
+        org
+    ))
+}
+
 #[derive(Clone, Debug, Clap)]
 pub struct SharesIssueCommand {
-    pub organization: u64,
+    pub organization: OrgArg,
     pub dest: String,
     pub shares: u64,
+    /// Issue shares which carry no vote
+    #[clap(long)]
+    pub non_voting: bool,
 }
 
 impl SharesIssueCommand {
     pub async fn exec<N: Node, C: SharesClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Shares,
         <N::Runtime as System>::AccountId: Ss58Codec,
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Org>::Shares: From<u64> + Display,
+        <N::Runtime as System>::BlockNumber: Display,
     {
-        let account: Ss58<N::Runtime> = self.dest.parse()?;
+        let org = resolver.org(client, &self.organization).await?;
+        let account = resolver.account::<N::Runtime>(&self.dest)?;
+        resolver.confirm()?;
+        // the issuer consents to the shares it issues to itself
+        if &account != client.signer()?.account_id()
+            && client.requires_share_consent(org).await?
+        {
+            let event = client
+                .offer_shares(
+                    org,
+                    account,
+                    self.shares.into(),
+                    share_class(self.non_voting),
+                )
+                .await?;
+            print_offer::<N, C>(client, &event);
+            return Ok(())
+        }
         let event = client
             .issue_shares(
-                self.organization.into(),
-                account.0,
+                org,
+                account,
                 self.shares.into(),
+                share_class(self.non_voting),
             )
             .await?;
+        let properties = client.chain_properties();
         println!(
-            "{} new shares minted for account {:?} in the context of Org {}",
-            event.shares, event.who, event.organization
+            "{} new shares minted for account {} in the context of Org {}",
+            event.shares,
+            properties.ss58(&event.who),
+            event.organization
         );
         Ok(())
     }
@@ -55,33 +164,58 @@ impl SharesIssueCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub struct SharesBatchIssueCommand {
-    pub organization: u64,
+    pub organization: OrgArg,
     pub new_accounts: Vec<AccountShare>,
+    /// Issue shares which carry no vote
+    #[clap(long)]
+    pub non_voting: bool,
 }
 
 impl SharesBatchIssueCommand {
     pub async fn exec<N: Node, C: SharesClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Shares,
         <N::Runtime as System>::AccountId: Ss58Codec,
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Org>::Shares: From<u64> + Display,
+        <N::Runtime as System>::BlockNumber: Display,
     {
+        let org = resolver.org(client, &self.organization).await?;
         let accounts = self
             .new_accounts
             .iter()
             .map(|acc_share| -> Result<_> {
-                let account: Ss58<N::Runtime> = acc_share.0.parse()?;
+                let account = resolver.account::<N::Runtime>(&acc_share.0)?;
                 let amount_issued: <N::Runtime as Shares>::Shares =
                     (acc_share.1).into();
-                Ok((account.0, amount_issued))
+                Ok((account, amount_issued))
             })
             .collect::<Result<Vec<_>>>()?;
+        resolver.confirm()?;
+        if client.requires_share_consent(org).await? {
+            for (account, shares) in accounts {
+                let event = client
+                    .offer_shares(
+                        org,
+                        account,
+                        shares,
+                        share_class(self.non_voting),
+                    )
+                    .await?;
+                print_offer::<N, C>(client, &event);
+            }
+            return Ok(())
+        }
         let event = client
-            .batch_issue_shares(self.organization.into(), accounts.as_slice())
+            .batch_issue_shares(
+                org,
+                accounts.as_slice(),
+                share_class(self.non_voting),
+            )
             .await?;
         println!(
             "{} new shares minted in the context of Org {}",
@@ -93,14 +227,18 @@ impl SharesBatchIssueCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub struct SharesBatchBurnCommand {
-    pub organization: u64,
+    pub organization: OrgArg,
     pub old_accounts: Vec<AccountShare>,
+    /// Burn shares which carry no vote
+    #[clap(long)]
+    pub non_voting: bool,
 }
 
 impl SharesBatchBurnCommand {
     pub async fn exec<N: Node, C: SharesClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Shares,
@@ -108,18 +246,31 @@ impl SharesBatchBurnCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Org>::Shares: From<u64> + Display,
     {
+        let org = resolver.org(client, &self.organization).await?;
         let accounts = self
             .old_accounts
             .iter()
             .map(|acc_share| -> Result<_> {
-                let account: Ss58<N::Runtime> = acc_share.0.parse()?;
+                let account = resolver.account::<N::Runtime>(&acc_share.0)?;
                 let amount_burned: <N::Runtime as Shares>::Shares =
                     (acc_share.1).into();
-                Ok((account.0, amount_burned))
+                Ok((account, amount_burned))
             })
             .collect::<Result<Vec<_>>>()?;
+        let mut summary = Vec::with_capacity(accounts.len());
+        for (account, shares) in &accounts {
+            summary.push(
+                burn_summary(client, org, account, *shares, self.non_voting)
+                    .await?,
+            );
+        }
+        resolver.confirm_irreversible(client, &summary)?;
         let event = client
-            .batch_burn_shares(self.organization.into(), accounts.as_slice())
+            .batch_burn_shares(
+                org,
+                accounts.as_slice(),
+                share_class(self.non_voting),
+            )
             .await?;
         println!(
             "{} shares burned in the context of Org {}",
@@ -131,15 +282,19 @@ impl SharesBatchBurnCommand {
 
 #[derive(Clone, Debug, Clap)]
 pub struct SharesBurnCommand {
-    pub organization: u64,
+    pub organization: OrgArg,
     pub burner: String,
     pub shares: u64,
+    /// Burn shares which carry no vote
+    #[clap(long)]
+    pub non_voting: bool,
 }
 
 impl SharesBurnCommand {
     pub async fn exec<N: Node, C: SharesClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Shares,
@@ -147,18 +302,323 @@ impl SharesBurnCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Org>::Shares: From<u64> + Display,
     {
-        let account: Ss58<N::Runtime> = self.burner.parse()?;
+        let org = resolver.org(client, &self.organization).await?;
+        let account = resolver.account::<N::Runtime>(&self.burner)?;
+        let summary = burn_summary(
+            client,
+            org,
+            &account,
+            self.shares.into(),
+            self.non_voting,
+        )
+        .await?;
+        resolver.confirm_irreversible(client, &[summary])?;
         let event = client
             .burn_shares(
-                self.organization.into(),
-                account.0,
+                org,
+                account,
                 self.shares.into(),
+                share_class(self.non_voting),
             )
             .await?;
+        let properties = client.chain_properties();
         println!(
-            "{} shares burned from account {:?} in the context of Org {}",
-            event.shares, event.who, event.organization
+            "{} shares burned from account {} in the context of Org {}",
+            event.shares,
+            properties.ss58(&event.who),
+            event.organization
         );
         Ok(())
     }
 }
+
+#[derive(Clone, Debug, Clap)]
+pub struct SharesConvertCommand {
+    pub organization: OrgArg,
+    pub account: String,
+    pub shares: u64,
+    /// Convert non-voting shares into voting shares instead
+    #[clap(long)]
+    pub to_voting: bool,
+}
+
+impl SharesConvertCommand {
+    pub async fn exec<N: Node, C: SharesClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Shares,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        <N::Runtime as Org>::Shares: From<u64> + Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let account = resolver.account::<N::Runtime>(&self.account)?;
+        resolver.confirm()?;
+        let from = share_class(self.to_voting);
+        let event = client
+            .convert_shares(org, account, from, self.shares.into())
+            .await?;
+        let properties = client.chain_properties();
+        println!(
+            "{} {} shares of account {} converted in the context of Org {}",
+            event.shares,
+            if from == ShareClass::Voting {
+                "voting"
+            } else {
+                "non-voting"
+            },
+            properties.ss58(&event.who),
+            event.organization
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct SharesConsentCommand {
+    pub organization: OrgArg,
+    /// Issue shares at once again instead of offering them
+    #[clap(long)]
+    pub off: bool,
+}
+
+impl SharesConsentCommand {
+    pub async fn exec<N: Node, C: SharesClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Shares,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        resolver.confirm()?;
+        let event = client.set_share_consent(org, !self.off).await?;
+        if event.required {
+            println!(
+                "Org {} now offers the shares it issues until the recipient \
+                 accepts them",
+                event.organization
+            );
+        } else {
+            println!("Org {} now issues shares at once", event.organization);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct SharesAcceptCommand {
+    pub organization: OrgArg,
+    pub grant_id: u32,
+    /// Decline the shares instead
+    #[clap(long)]
+    pub decline: bool,
+}
+
+impl SharesAcceptCommand {
+    pub async fn exec<N: Node, C: SharesClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Shares,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        resolver.confirm()?;
+        if self.decline {
+            let event = client.decline_shares(org, self.grant_id).await?;
+            println!(
+                "Declined grant {} of Org {}",
+                event.grant_id, event.organization
+            );
+        } else {
+            let event = client.accept_shares(org, self.grant_id).await?;
+            println!(
+                "Accepted grant {} of Org {}",
+                event.grant_id, event.organization
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct SharesGrantsCommand {
+    /// The recipient of the grants, the signer if not given
+    pub account: Option<String>,
+}
+
+impl SharesGrantsCommand {
+    pub async fn exec<N: Node, C: SharesClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Shares,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: Display,
+        <N::Runtime as Org>::Shares: Display,
+        <N::Runtime as System>::BlockNumber: Display,
+    {
+        let account = match &self.account {
+            Some(account) => resolver.account::<N::Runtime>(account)?,
+            None => client.signer()?.account_id().clone(),
+        };
+        let grants = client.share_grants(&account).await?;
+        if grants.is_empty() {
+            println!("No shares are offered to this account");
+        }
+        for (grant_id, grant) in grants {
+            println!(
+                "grant {}: {} {} shares of Org {}, to be accepted before \
+                 block {}",
+                grant_id,
+                grant.shares(),
+                if grant.class() == ShareClass::Voting {
+                    "voting"
+                } else {
+                    "non-voting"
+                },
+                grant.org(),
+                grant.expires_at()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct SharesAirdropCommand {
+    pub organization: OrgArg,
+    /// A file of `account,shares` rows, one account per row
+    #[clap(long)]
+    pub csv: PathBuf,
+    /// How many accounts are issued shares per extrinsic
+    #[clap(long, default_value = "50")]
+    pub chunk: usize,
+    /// Where to write the outcome per account, next to the csv by default
+    #[clap(long)]
+    pub report: Option<PathBuf>,
+}
+
+impl SharesAirdropCommand {
+    pub async fn exec<N: Node, C: AirdropClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+        root: &Path,
+    ) -> Result<()>
+    where
+        N::Runtime: Shares,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        <N::Runtime as Org>::Shares: From<u64> + Display,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let raw = std::fs::read_to_string(&self.csv)?;
+        let mut allocations = Vec::new();
+        for (i, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            let invalid = || InvalidAirdropRow(i + 1, line.to_string());
+            let mut fields = line.split(',').map(str::trim);
+            let (account, shares) =
+                match (fields.next(), fields.next(), fields.next()) {
+                    (Some(account), Some(shares), None) => (account, shares),
+                    _ => return Err(invalid().into()),
+                };
+            let shares = match shares.parse::<u64>() {
+                Ok(shares) => shares,
+                // a header row names its columns
+                Err(_) if i == 0 => continue,
+                Err(_) => return Err(invalid().into()),
+            };
+            let account = resolver.account::<N::Runtime>(account)?;
+            allocations.push((account, shares.into()));
+        }
+        println!(
+            "Issue voting shares to {} accounts in org {}, {} per extrinsic",
+            allocations.len(),
+            org,
+            self.chunk
+        );
+        resolver.confirm()?;
+        let report = client
+            .airdrop_shares(
+                root,
+                org,
+                allocations,
+                self.chunk,
+                &mut print_progress,
+            )
+            .await?;
+
+        let properties = client.chain_properties();
+        let mut csv = String::from("account,shares,status,reason\n");
+        for (who, shares, status) in &report.accounts {
+            let (status, reason) = match status {
+                AirdropStatus::Issued => ("issued", ""),
+                AirdropStatus::Failed(reason) => ("failed", reason.as_str()),
+                AirdropStatus::Unconfirmed => ("unconfirmed", ""),
+            };
+            let fields = [
+                properties.ss58(who),
+                shares.to_string(),
+                status.to_string(),
+                reason.to_string(),
+            ];
+            let fields =
+                fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        let path = self
+            .report
+            .clone()
+            .unwrap_or_else(|| self.csv.with_extension("report.csv"));
+        std::fs::write(&path, csv)?;
+        println!(
+            "\n{} issued, {} failed, {} unconfirmed, see {}",
+            report.issued(),
+            report.failed(),
+            report.unconfirmed(),
+            path.display()
+        );
+        if report.unconfirmed() > 0 {
+            println!(
+                "Check the shares of the unconfirmed accounts before \
+                 issuing them again"
+            );
+        }
+        Ok(())
+    }
+}
+
+fn print_progress(progress: AirdropProgress) {
+    const WIDTH: usize = 30;
+    let filled = match progress.total {
+        0 => WIDTH,
+        total => progress.done * WIDTH / total,
+    };
+    print!(
+        "\r[{}{}] {}% ({}/{} accounts)",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        filled * 100 / WIDTH,
+        progress.done,
+        progress.total
+    );
+    std::io::stdout().flush().ok();
+}
@@ -0,0 +1,382 @@
+//! Tables printed by the list commands
+//!
+//! On a terminal, addresses are shortened to their head and tail, cids to
+//! their start and end, amounts are right aligned, states are colored and
+//! the text columns are cut to fit the width of the terminal. When stdout
+//! is piped or `--no-truncate` is passed, every value is printed in full
+//! and separated by tabs, so scripts can cut the columns they need.
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+
+/// Spaces between the columns of a terminal table
+const GAP: usize = 2;
+/// Text columns are not cut below this width to fit the terminal
+const MIN_TEXT_WIDTH: usize = 12;
+const ELLIPSIS: char = '…';
+
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const RED: &str = "\x1b[31m";
+const GREY: &str = "\x1b[90m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+static NO_TRUNCATE: AtomicBool = AtomicBool::new(false);
+
+/// Prints tables in full even on a terminal
+pub fn set_no_truncate(no_truncate: bool) {
+    NO_TRUNCATE.store(no_truncate, Ordering::Relaxed);
+}
+
+/// What a column holds, which decides how a terminal shows it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Column {
+    /// Cut to fit the terminal if the table is too wide
+    Text,
+    /// Right aligned and never cut
+    Id,
+    /// Shortened to its head and tail
+    Address,
+    /// Shortened to its head and tail
+    Cid,
+    /// Right aligned and never cut, formatted by the caller
+    Amount,
+    /// Colored by its value
+    State,
+}
+
+/// How a table is rendered
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// Full values separated by tabs
+    Plain,
+    /// Aligned, shortened values, fit to `width` if it is known
+    Terminal { width: Option<usize>, color: bool },
+}
+
+impl Layout {
+    /// `Terminal` if stdout is one and `--no-truncate` was not passed,
+    /// colored unless `NO_COLOR` is set
+    pub fn detect() -> Self {
+        if NO_TRUNCATE.load(Ordering::Relaxed)
+            || !atty::is(atty::Stream::Stdout)
+        {
+            return Layout::Plain
+        }
+        Layout::Terminal {
+            width: crossterm::terminal::size().ok().map(|(w, _)| w as usize),
+            color: std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Table {
+    columns: Vec<(&'static str, Column)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&'static str, Column)]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row with a value per column
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Prints the table in the layout of stdout
+    pub fn print(&self) {
+        print!("{}", self.render(Layout::detect()));
+    }
+
+    pub fn render(&self, layout: Layout) -> String {
+        match layout {
+            Layout::Plain => self.render_plain(),
+            Layout::Terminal { width, color } => {
+                self.render_terminal(width, color)
+            }
+        }
+    }
+
+    fn render_plain(&self) -> String {
+        let headers = self.columns.iter().map(|(header, _)| *header);
+        let mut out = headers.collect::<Vec<_>>().join("\t");
+        out.push('\n');
+        for row in &self.rows {
+            let fields = row.iter().map(|field| field.replace('\t', " "));
+            out.push_str(&fields.collect::<Vec<_>>().join("\t"));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn render_terminal(&self, width: Option<usize>, color: bool) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.columns)
+                    .map(|(field, (_, column))| shorten(field, *column))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut widths = self
+            .columns
+            .iter()
+            .map(|(header, _)| len(header))
+            .collect::<Vec<_>>();
+        for row in &rows {
+            for (width, field) in widths.iter_mut().zip(row) {
+                *width = (*width).max(len(field));
+            }
+        }
+        if let Some(width) = width {
+            self.fit(&mut widths, width);
+        }
+        let mut out = String::new();
+        let headers = self.columns.iter().map(|(header, _)| *header);
+        self.push_line(&mut out, headers, &widths, |field, _| {
+            if color {
+                format!("{}{}{}", BOLD, field, RESET)
+            } else {
+                field
+            }
+        });
+        for row in &rows {
+            let fields = row.iter().map(|field| field.as_str());
+            self.push_line(&mut out, fields, &widths, |field, column| {
+                match (color, column) {
+                    (true, Column::State) => colored(field),
+                    _ => field,
+                }
+            });
+        }
+        out
+    }
+
+    /// Cuts the widest text columns until the table fits in `width`
+    fn fit(&self, widths: &mut [usize], width: usize) {
+        let total = |widths: &[usize]| {
+            widths.iter().sum::<usize>() + GAP * widths.len().saturating_sub(1)
+        };
+        while total(widths) > width {
+            let widest = self
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(i, (_, column))| {
+                    *column == Column::Text && widths[*i] > MIN_TEXT_WIDTH
+                })
+                .max_by_key(|(i, _)| widths[*i])
+                .map(|(i, _)| i);
+            match widest {
+                Some(i) => {
+                    let excess = total(widths) - width;
+                    widths[i] =
+                        widths[i].saturating_sub(excess).max(MIN_TEXT_WIDTH);
+                }
+                // addresses, amounts and ids are never cut
+                None => break,
+            }
+        }
+    }
+
+    fn push_line<'a>(
+        &self,
+        out: &mut String,
+        fields: impl Iterator<Item = &'a str>,
+        widths: &[usize],
+        style: impl Fn(String, Column) -> String,
+    ) {
+        let last = self.columns.len() - 1;
+        let mut line = Vec::with_capacity(self.columns.len());
+        for (i, field) in fields.enumerate() {
+            let (_, column) = self.columns[i];
+            let field = cut(field, widths[i]);
+            let pad = " ".repeat(widths[i] - len(&field));
+            let field = match column {
+                Column::Id | Column::Amount => format!("{}{}", pad, field),
+                // no trailing spaces at the end of the line
+                _ if i == last => field,
+                _ => format!("{}{}", field, pad),
+            };
+            line.push(style(field, column));
+        }
+        out.push_str(&line.join(&" ".repeat(GAP)));
+        out.push('\n');
+    }
+}
+
+fn len(field: &str) -> usize {
+    field.chars().count()
+}
+
+/// `field` cut to `width` characters, the last one an ellipsis
+fn cut(field: &str, width: usize) -> String {
+    if len(field) <= width {
+        return field.to_string()
+    }
+    let mut cut = field.chars().take(width - 1).collect::<String>();
+    cut.push(ELLIPSIS);
+    cut
+}
+
+/// The first `head` and last `tail` characters of `field` around an
+/// ellipsis, `field` if it is short enough
+fn head_tail(field: &str, head: usize, tail: usize) -> String {
+    let chars = field.chars().collect::<Vec<_>>();
+    if chars.len() <= head + tail + 1 {
+        return field.to_string()
+    }
+    let mut short = chars[..head].iter().collect::<String>();
+    short.push(ELLIPSIS);
+    short.extend(&chars[chars.len() - tail..]);
+    short
+}
+
+fn shorten(field: &str, column: Column) -> String {
+    match column {
+        Column::Address => head_tail(field, 6, 6),
+        Column::Cid => head_tail(field, 8, 6),
+        _ => field.to_string(),
+    }
+}
+
+/// Open states are green, approved blue, rejected red and the ones which
+/// are over without a decision grey
+fn colored(field: String) -> String {
+    let color = match field.trim() {
+        "open" | "voting" => GREEN,
+        "approved" | "executed" => BLUE,
+        "nearing" => YELLOW,
+        "rejected" | "overdue" => RED,
+        "expired" | "cancelled" | "closed" => GREY,
+        _ => return field,
+    };
+    format!("{}{}{}", color, field, RESET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const CID: &str =
+        "bafyreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy";
+
+    fn bounties() -> Table {
+        let mut table = Table::new(&[
+            ("id", Column::Id),
+            ("state", Column::State),
+            ("total", Column::Amount),
+            ("issue", Column::Text),
+            ("depositor", Column::Address),
+            ("info", Column::Cid),
+        ]);
+        table.push(vec![
+            "1".into(),
+            "open".into(),
+            "12.5 UNIT".into(),
+            "sunshine-protocol/sunshine#16".into(),
+            ALICE.into(),
+            CID.into(),
+        ]);
+        table.push(vec![
+            "12".into(),
+            "expired".into(),
+            "100 UNIT".into(),
+            "sunshine-protocol/sunshine#7".into(),
+            ALICE.into(),
+            CID.into(),
+        ]);
+        table
+    }
+
+    #[test]
+    fn piped_tables_are_full_and_tab_separated() {
+        assert_eq!(
+            bounties().render(Layout::Plain),
+            format!(
+                "id\tstate\ttotal\tissue\tdepositor\tinfo\n\
+                 1\topen\t12.5 UNIT\tsunshine-protocol/sunshine#16\t\
+                 {a}\t{c}\n\
+                 12\texpired\t100 UNIT\tsunshine-protocol/sunshine#7\t\
+                 {a}\t{c}\n",
+                a = ALICE,
+                c = CID,
+            )
+        );
+    }
+
+    #[test]
+    fn terminal_tables_are_aligned_and_shortened() {
+        let layout = Layout::Terminal {
+            width: None,
+            color: false,
+        };
+        assert_eq!(
+            bounties().render(layout),
+            concat!(
+                "id  state        total  issue                          ",
+                "depositor      info\n",
+                " 1  open     12.5 UNIT  sunshine-protocol/sunshine#16  ",
+                "5Grwva…GKutQY  bafyreig…4s52zy\n",
+                "12  expired   100 UNIT  sunshine-protocol/sunshine#7   ",
+                "5Grwva…GKutQY  bafyreig…4s52zy\n",
+            )
+        );
+    }
+
+    #[test]
+    fn text_columns_are_cut_to_the_terminal_width() {
+        let layout = Layout::Terminal {
+            width: Some(70),
+            color: false,
+        };
+        assert_eq!(
+            bounties().render(layout),
+            concat!(
+                "id  state        total  issue           ",
+                "depositor      info\n",
+                " 1  open     12.5 UNIT  sunshine-prot…  ",
+                "5Grwva…GKutQY  bafyreig…4s52zy\n",
+                "12  expired   100 UNIT  sunshine-prot…  ",
+                "5Grwva…GKutQY  bafyreig…4s52zy\n",
+            )
+        );
+        // addresses, amounts and cids stay readable on narrow terminals
+        let layout = Layout::Terminal {
+            width: Some(50),
+            color: false,
+        };
+        let rendered = bounties().render(layout);
+        assert!(rendered.contains(" 1  open     12.5 UNIT  sunshine-pr…  "));
+        assert!(rendered.contains("5Grwva…GKutQY  bafyreig…4s52zy\n"));
+    }
+
+    #[test]
+    fn states_are_colored() {
+        let layout = Layout::Terminal {
+            width: None,
+            color: true,
+        };
+        let rendered = bounties().render(layout);
+        assert!(rendered.starts_with("\x1b[1mid\x1b[0m"));
+        assert!(rendered.contains("\x1b[32mopen   \x1b[0m"));
+        assert!(rendered.contains("\x1b[90mexpired\x1b[0m"));
+    }
+}
@@ -1,13 +1,14 @@
 use crate::InvalidGithubIssueUrl;
 use regex::Regex;
 use std::convert::TryFrom;
-const GITHUB_ISSUE_URL_REGEX: &str = r"(?m)^https://github.com/([A-Za-z0-9]+(?:[ _-][A-Za-z0-9]+)*)/([A-Za-z0-9]+(?:[ _-][A-Za-z0-9]+)*)/issues/(\d+)$";
+const GITHUB_ISSUE_URL_REGEX: &str = r"(?m)^https://github.com/([A-Za-z0-9]+(?:[ _-][A-Za-z0-9]+)*)/([A-Za-z0-9]+(?:[ _-][A-Za-z0-9]+)*)/(issues|pull)/(\d+)$";
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GithubIssueMetadata {
     pub owner: String,
     pub repo: String,
     pub issue: u64,
+    pub pull_request: bool,
 }
 
 impl<'a> TryFrom<&'a str> for GithubIssueMetadata {
@@ -32,10 +33,15 @@ fn parse_url(url: &str) -> Result<GithubIssueMetadata, InvalidGithubIssueUrl> {
                     .ok_or(InvalidGithubIssueUrl)?
                     .as_str()
                     .to_owned(),
-                issue: groups
+                pull_request: groups
                     .get(3)
                     .ok_or(InvalidGithubIssueUrl)?
                     .as_str()
+                    == "pull",
+                issue: groups
+                    .get(4)
+                    .ok_or(InvalidGithubIssueUrl)?
+                    .as_str()
                     .parse()
                     .expect("should be a valid issue number!"),
             })
@@ -57,7 +63,23 @@ mod test_utils {
             GithubIssueMetadata {
                 owner: String::from("sunshine-protocol"),
                 repo: String::from("sunshine"),
-                issue: 16
+                issue: 16,
+                pull_request: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_github_pull_request() {
+        let url = "https://github.com/sunshine-protocol/sunshine/pull/17";
+        let issue = parse_url(url).unwrap();
+        assert_eq!(
+            issue,
+            GithubIssueMetadata {
+                owner: String::from("sunshine-protocol"),
+                repo: String::from("sunshine"),
+                issue: 17,
+                pull_request: true,
             }
         );
     }
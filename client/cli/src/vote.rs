@@ -1,28 +1,50 @@
-use crate::error::VotePercentThresholdInputBoundError;
+use crate::{
+    args::{
+        DirectionArg,
+        DurationArg,
+        OrgArg,
+        Resolver,
+    },
+    error::InvalidStateRoot,
+    table::{
+        Column,
+        Table,
+    },
+};
 use clap::Clap;
 use core::fmt::{
     Debug,
     Display,
 };
+use parity_scale_codec::Decode;
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use std::path::PathBuf;
 use substrate_subxt::{
-    sp_core::crypto::Ss58Codec,
-    sp_runtime::Permill,
+    sp_core::{
+        bytes::from_hex,
+        crypto::Ss58Codec,
+    },
     system::System,
 };
 use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
     org::Org,
     vote::{
         Vote,
         VoteClient,
+        VoteTextClient,
+        VoteThreshold,
+        VoteThresholdBuilder,
     },
     TextBlock,
+    VoteText,
 };
-use sunshine_bounty_utils::{
-    organization::OrgRep,
-    vote::{
-        Threshold,
-        VoterView,
-    },
+use sunshine_bounty_utils::vote::{
+    VoteOutcome,
+    VoterView,
 };
 use sunshine_client_utils::{
     Node,
@@ -32,17 +54,22 @@ use sunshine_client_utils::{
 #[derive(Clone, Debug, Clap)]
 pub struct VoteCreateSignalThresholdCommand {
     pub topic: Option<String>,
+    /// Composes the topic from this text instead, its first line the title,
+    /// signed by you and dated
+    #[clap(long = "topic-text")]
+    pub topic_text: Option<String>,
     pub weighted: u8,
-    pub organization: u64,
+    pub organization: OrgArg,
     pub support_requirement: u64,
     pub rejection_requirement: Option<u64>,
-    pub duration: Option<u32>,
+    pub duration: Option<DurationArg>,
 }
 
 impl VoteCreateSignalThresholdCommand {
-    pub async fn exec<N: Node, C: VoteClient<N>>(
+    pub async fn exec<N: Node, C: VoteClient<N> + VoteTextClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Vote,
@@ -51,56 +78,32 @@ impl VoteCreateSignalThresholdCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Vote>::Signal: From<u64> + Display,
         <N::Runtime as Vote>::VoteId: Display,
-        <N::Runtime as Vote>::VoteTopic: From<TextBlock>,
+        <N::Runtime as Vote>::VoteTopic: From<TextBlock> + From<VoteText>,
     {
-        let topic: Option<<N::Runtime as Vote>::VoteTopic> =
-            if let Some(t) = &self.topic {
-                Some(
-                    TextBlock {
-                        text: (*t).to_string(),
-                    }
-                    .into(),
-                )
-            } else {
-                None
-            };
-        let rt: Option<<N::Runtime as Vote>::Signal> =
-            if let Some(r) = self.rejection_requirement {
-                Some(r.into())
-            } else {
-                None
-            };
-        let threshold: Threshold<<N::Runtime as Vote>::Signal> =
-            Threshold::new(self.support_requirement.into(), rt);
+        let topic: Option<<N::Runtime as Vote>::VoteTopic> = vote_text(
+            client,
+            self.topic.as_deref(),
+            self.topic_text.as_deref(),
+        )?;
         let duration: Option<<N::Runtime as System>::BlockNumber> =
-            if let Some(req) = self.duration {
-                Some(req.into())
+            if let Some(req) = &self.duration {
+                Some(resolver.blocks(req)?.into())
             } else {
                 None
             };
-        // 0 is false, every other integer is true
-        let event = if self.weighted != 0 {
-            client
-                .create_signal_vote(
-                    topic,
-                    OrgRep::Weighted(self.organization.into()),
-                    threshold,
-                    duration,
-                )
-                .await?
-        } else {
-            client
-                .create_signal_vote(
-                    topic,
-                    OrgRep::Equal(self.organization.into()),
-                    threshold,
-                    duration,
-                )
-                .await?
-        };
+        let org = resolver.org(client, &self.organization).await?;
+        let threshold = threshold_builder::<N::Runtime>(self.weighted, org)
+            .signal(
+                self.support_requirement.into(),
+                self.rejection_requirement.map(Into::into),
+            )?;
+        resolver.confirm()?;
+        let event = client.create_vote(topic, threshold, duration).await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} created a signal threshold vote with VoteId {}",
-            event.caller, event.new_vote_id
+            properties.ss58(&event.caller),
+            event.new_vote_id
         );
         Ok(())
     }
@@ -109,25 +112,60 @@ impl VoteCreateSignalThresholdCommand {
 #[derive(Clone, Debug, Clap)]
 pub struct VoteCreatePercentThresholdCommand {
     pub topic: Option<String>,
+    /// Composes the topic from this text instead, its first line the title,
+    /// signed by you and dated
+    #[clap(long = "topic-text")]
+    pub topic_text: Option<String>,
     pub weighted: u8,
-    pub organization: u64,
+    pub organization: OrgArg,
     pub support_threshold: u8,
     pub rejection_threshold: Option<u8>,
-    pub duration: Option<u32>,
+    pub duration: Option<DurationArg>,
+}
+
+/// The text composed from `composed` by the signer if given, otherwise the
+/// plain text
+fn vote_text<N: Node, C: VoteTextClient<N>, T>(
+    client: &C,
+    plain: Option<&str>,
+    composed: Option<&str>,
+) -> Result<Option<T>>
+where
+    N::Runtime: Vote,
+    T: From<TextBlock> + From<VoteText>,
+{
+    Ok(match (composed, plain) {
+        (Some(text), _) => Some(client.compose_text(text)?.into()),
+        (None, Some(text)) => {
+            Some(
+                TextBlock {
+                    text: text.to_string(),
+                }
+                .into(),
+            )
+        }
+        (None, None) => None,
+    })
 }
 
-pub fn u8_to_permill(u: u8) -> Result<Permill> {
-    if u > 0u8 && u < 100u8 {
-        Ok(Permill::from_percent(u.into()))
+/// The builder of the threshold of a vote of `org`, weighted unless
+/// `weighted` is 0
+fn threshold_builder<R: Vote>(
+    weighted: u8,
+    org: R::OrgId,
+) -> VoteThresholdBuilder<R> {
+    if weighted != 0 {
+        VoteThresholdBuilder::weighted(org)
     } else {
-        Err(VotePercentThresholdInputBoundError.into())
+        VoteThresholdBuilder::flat(org)
     }
 }
 
 impl VoteCreatePercentThresholdCommand {
-    pub async fn exec<N: Node, C: VoteClient<N>>(
+    pub async fn exec<N: Node, C: VoteClient<N> + VoteTextClient<N>>(
         &self,
         client: &C,
+        resolver: &Resolver,
     ) -> Result<()>
     where
         N::Runtime: Vote,
@@ -135,63 +173,29 @@ impl VoteCreatePercentThresholdCommand {
         <N::Runtime as System>::BlockNumber: From<u32>,
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Vote>::VoteId: Display,
-        <N::Runtime as Vote>::VoteTopic: From<TextBlock>,
-        <N::Runtime as Vote>::Percent: From<Permill>,
+        <N::Runtime as Vote>::VoteTopic: From<TextBlock> + From<VoteText>,
     {
-        let topic: Option<<N::Runtime as Vote>::VoteTopic> =
-            if let Some(t) = &self.topic {
-                Some(
-                    TextBlock {
-                        text: (*t).to_string(),
-                    }
-                    .into(),
-                )
-            } else {
-                None
-            };
+        let topic: Option<<N::Runtime as Vote>::VoteTopic> = vote_text(
+            client,
+            self.topic.as_deref(),
+            self.topic_text.as_deref(),
+        )?;
         let duration: Option<<N::Runtime as System>::BlockNumber> =
-            if let Some(req) = self.duration {
-                Some(req.into())
+            if let Some(req) = &self.duration {
+                Some(resolver.blocks(req)?.into())
             } else {
                 None
             };
-        let rt: Option<<N::Runtime as Vote>::Percent> =
-            if let Some(r) = self.rejection_threshold {
-                let ret = u8_to_permill(r)
-                    .map_err(|_| VotePercentThresholdInputBoundError)?;
-                Some(ret.into())
-            } else {
-                None
-            };
-        let support_t: <N::Runtime as Vote>::Percent =
-            u8_to_permill(self.support_threshold)
-                .map_err(|_| VotePercentThresholdInputBoundError)?
-                .into();
-        let threshold: Threshold<<N::Runtime as Vote>::Percent> =
-            Threshold::new(support_t, rt);
-        // 0 is false and everything else is true
-        let event = if self.weighted != 0 {
-            client
-                .create_percent_vote(
-                    topic,
-                    OrgRep::Weighted(self.organization.into()),
-                    threshold,
-                    duration,
-                )
-                .await?
-        } else {
-            client
-                .create_percent_vote(
-                    topic,
-                    OrgRep::Equal(self.organization.into()),
-                    threshold,
-                    duration,
-                )
-                .await?
-        };
+        let org = resolver.org(client, &self.organization).await?;
+        let threshold = threshold_builder::<N::Runtime>(self.weighted, org)
+            .percent(self.support_threshold, self.rejection_threshold)?;
+        resolver.confirm()?;
+        let event = client.create_vote(topic, threshold, duration).await?;
+        let properties = client.chain_properties();
         println!(
             "Account {} created a percent threshold vote with VoteId {}",
-            event.caller, event.new_vote_id
+            properties.ss58(&event.caller),
+            event.new_vote_id
         );
         Ok(())
     }
@@ -202,10 +206,14 @@ pub struct VoteSubmitCommand {
     pub vote_id: u64,
     pub direction: u8,
     pub justification: Option<String>,
+    /// Composes the justification from this text instead, signed by you and
+    /// dated
+    #[clap(long = "justify")]
+    pub justify: Option<String>,
 }
 
 impl VoteSubmitCommand {
-    pub async fn exec<N: Node, C: VoteClient<N>>(
+    pub async fn exec<N: Node, C: VoteClient<N> + VoteTextClient<N>>(
         &self,
         client: &C,
     ) -> Result<()>
@@ -215,7 +223,8 @@ impl VoteSubmitCommand {
         <N::Runtime as Org>::OrgId: From<u64> + Display,
         <N::Runtime as Vote>::VoteId: From<u64> + Display,
         <N::Runtime as Vote>::VoterView: From<VoterView>,
-        <N::Runtime as Vote>::VoteJustification: From<TextBlock>,
+        <N::Runtime as Vote>::VoteJustification:
+            From<TextBlock> + From<VoteText>,
     {
         let voter_view: <N::Runtime as Vote>::VoterView =
             match self.direction {
@@ -225,23 +234,432 @@ impl VoteSubmitCommand {
             }
             .into();
         let justification: Option<<N::Runtime as Vote>::VoteJustification> =
-            if let Some(j) = &self.justification {
-                Some(
-                    TextBlock {
-                        text: (*j).to_string(),
-                    }
-                    .into(),
-                )
-            } else {
-                None
-            };
+            vote_text(
+                client,
+                self.justification.as_deref(),
+                self.justify.as_deref(),
+            )?;
         let event = client
             .submit_vote(self.vote_id.into(), voter_view, justification)
             .await?;
+        let properties = client.chain_properties();
+        println!(
+            "Account {} voted with view {:?} in VoteId {} (ballot {})",
+            properties.ss58(&event.voter),
+            event.view,
+            event.vote_id,
+            event.sequence
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteCancelCommand {
+    pub vote_id: u64,
+    /// Why the vote is cancelled, published with it
+    pub reason: Option<String>,
+}
+
+impl VoteCancelCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Vote>::VoteId: From<u64> + Display,
+        <N::Runtime as Vote>::Signal: Display,
+        <N::Runtime as Vote>::VoteJustification: From<TextBlock>,
+    {
+        let progress = client.vote_progress(self.vote_id.into()).await?;
+        let mut summary = vec![
+            format!(
+                "cancel VoteId {}, {:?} with {} in favor and {} against",
+                self.vote_id,
+                progress.outcome(),
+                progress.in_favor(),
+                progress.against()
+            ),
+            "the vote can never be approved".to_string(),
+        ];
+        if let Some(reason) = &self.reason {
+            summary.push(format!("reason: {}", reason));
+        }
+        resolver.confirm_irreversible(client, &summary)?;
+        let reason = self
+            .reason
+            .as_ref()
+            .map(|text| TextBlock { text: text.clone() }.into());
+        let event = client.cancel_vote(self.vote_id.into(), reason).await?;
+        println!(
+            "Account {} cancelled VoteId {}",
+            client.chain_properties().ss58(&event.who),
+            event.vote_id
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteReceiptCommand {
+    pub vote_id: u64,
+}
+
+impl VoteReceiptCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as Vote>::VoteId: From<u64>,
+    {
+        let receipt = client.vote_receipt(self.vote_id.into()).await?;
+        println!("{}", receipt.to_base64());
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VotePendingCommand {
+    /// A watch-only label or another account to list the votes of instead
+    /// of the signer
+    #[clap(long = "account")]
+    pub account: Option<String>,
+}
+
+impl VotePendingCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Vote>::VoteId: Display,
+        <N::Runtime as Vote>::Signal: Display,
+    {
+        let account = resolver.read_account(client, self.account.as_deref())?;
+        let pending = client.pending_votes(&account).await?;
+        if pending.is_empty() {
+            println!(
+                "{} has no votes to cast",
+                client.chain_properties().ss58(&account)
+            );
+            return Ok(())
+        }
+        let mut table = Table::new(&[
+            ("vote", Column::Id),
+            ("state", Column::State),
+            ("in favor", Column::Amount),
+            ("needed", Column::Amount),
+        ]);
+        for (vote_id, progress) in pending {
+            table.push(vec![
+                vote_id.to_string(),
+                outcome_name(progress.outcome()).to_string(),
+                progress.in_favor().to_string(),
+                progress.threshold_in_favor().to_string(),
+            ]);
+        }
+        table.print();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteVerifyReceiptCommand {
+    /// The receipt printed by `vote receipt`
+    pub blob: String,
+    /// Check the receipt against this state root of its block instead of
+    /// the one the node reports
+    #[clap(long = "state-root")]
+    pub state_root: Option<String>,
+}
+
+impl VoteVerifyReceiptCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Vote>::VoteId: Display,
+    {
+        let state_root = match &self.state_root {
+            Some(root) => {
+                let invalid = || InvalidStateRoot(root.clone());
+                let bytes = from_hex(root).map_err(|_| invalid())?;
+                Some(Decode::decode(&mut &bytes[..]).map_err(|_| invalid())?)
+            }
+            None => None,
+        };
+        let receipt =
+            client.verify_vote_receipt(&self.blob, state_root).await?;
+        let properties = client.chain_properties();
         println!(
-            "Account {} voted with view {:?} in VoteId {}",
-            event.voter, event.view, event.vote_id
+            "Account {} voted with view {:?} in VoteId {} (ballot {})",
+            properties.ss58(&receipt.voted.voter),
+            receipt.voted.view,
+            receipt.voted.vote_id,
+            receipt.voted.sequence
+        );
+        println!(
+            "Cast by extrinsic {} of block {} ({:?})",
+            receipt.extrinsic_index, receipt.number, receipt.block
         );
         Ok(())
     }
 }
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteBallotsCommand {
+    pub vote_id: u64,
+    /// Only the voters who voted `in-favor`, `against`, `abstain` or who
+    /// did `not-voted`
+    #[clap(long = "direction")]
+    pub direction: Option<DirectionArg>,
+    /// Write the ballots to this file as CSV instead of printing them
+    #[clap(long = "csv")]
+    pub csv: Option<PathBuf>,
+}
+
+impl VoteBallotsCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as System>::AccountId: Ss58Codec,
+        <N::Runtime as Org>::Cid: Debug,
+        <N::Runtime as Vote>::VoteId: From<u64>,
+        <N::Runtime as Vote>::Signal: Display,
+        <N::Runtime as System>::BlockNumber: Display,
+    {
+        let directions = match self.direction {
+            Some(DirectionArg(direction)) => vec![direction],
+            None => {
+                vec![VoterView::InFavor, VoterView::Against, VoterView::Abstain]
+            }
+        };
+        let properties = client.chain_properties();
+        let mut rows = Vec::new();
+        for direction in directions {
+            let ballots = client
+                .ballots(self.vote_id.into(), Some(direction), 0, u32::MAX)
+                .await?;
+            for (voter, signal, justification, cast_at, changed_at) in ballots {
+                rows.push([
+                    properties.ss58(&voter),
+                    direction_name(direction).to_string(),
+                    signal.to_string(),
+                    cast_at.to_string(),
+                    changed_at.to_string(),
+                    justification
+                        .map(|cid| format!("{:?}", cid))
+                        .unwrap_or_default(),
+                ]);
+            }
+        }
+        match &self.csv {
+            Some(path) => {
+                let mut csv = String::from(
+                    "account,direction,signal,cast_at,changed_at,justification\n",
+                );
+                for row in &rows {
+                    let fields = row
+                        .iter()
+                        .map(|field| csv_field(field))
+                        .collect::<Vec<_>>();
+                    csv.push_str(&fields.join(","));
+                    csv.push('\n');
+                }
+                std::fs::write(path, csv)?;
+                println!(
+                    "Wrote {} ballots of VoteId {} to {}",
+                    rows.len(),
+                    self.vote_id,
+                    path.display()
+                );
+            }
+            None => {
+                let mut table = Table::new(&[
+                    ("account", Column::Address),
+                    ("direction", Column::Text),
+                    ("signal", Column::Amount),
+                    ("cast", Column::Id),
+                    ("changed", Column::Id),
+                    ("justification", Column::Cid),
+                ]);
+                for [account, direction, signal, cast_at, changed_at, justification] in
+                    rows
+                {
+                    table.push(vec![
+                        account,
+                        direction,
+                        signal,
+                        format!("#{}", cast_at),
+                        format!("#{}", changed_at),
+                        justification,
+                    ]);
+                }
+                table.print();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The name `DirectionArg` parses
+fn direction_name(direction: VoterView) -> &'static str {
+    match direction {
+        VoterView::InFavor => "in-favor",
+        VoterView::Against => "against",
+        VoterView::Abstain => "abstain",
+        VoterView::Uninitialized => "not-voted",
+    }
+}
+
+fn outcome_name(outcome: VoteOutcome) -> &'static str {
+    match outcome {
+        VoteOutcome::NotStarted => "not-started",
+        VoteOutcome::Voting => "voting",
+        VoteOutcome::Approved => "approved",
+        VoteOutcome::Rejected => "rejected",
+        VoteOutcome::Cancelled => "cancelled",
+    }
+}
+
+/// Quotes fields with separators, quotes or line breaks
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteThresholdExportCommand {
+    #[clap(long = "org")]
+    pub organization: OrgArg,
+    /// Where to write the thresholds as JSON
+    #[clap(long = "file")]
+    pub file: PathBuf,
+}
+
+impl VoteThresholdExportCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as Org>::OrgId: From<u64> + Display,
+        VoteThreshold<N::Runtime>: Serialize,
+    {
+        let org = resolver.org(client, &self.organization).await?;
+        let thresholds = registered_thresholds(client, org).await?;
+        std::fs::write(&self.file, serde_json::to_string_pretty(&thresholds)?)?;
+        println!(
+            "Exported {} thresholds of org {} to {}",
+            thresholds.len(),
+            org,
+            self.file.display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct VoteThresholdImportCommand {
+    /// The thresholds written by `vote threshold export`
+    #[clap(long = "file")]
+    pub file: PathBuf,
+    /// Register the missing thresholds instead of only printing them
+    #[clap(long = "yes")]
+    pub yes: bool,
+}
+
+impl VoteThresholdImportCommand {
+    pub async fn exec<N: Node, C: VoteClient<N>>(
+        &self,
+        client: &C,
+        resolver: &Resolver,
+    ) -> Result<()>
+    where
+        N::Runtime: Vote,
+        <N::Runtime as Vote>::ThresholdId: Display,
+        VoteThreshold<N::Runtime>: Serialize + DeserializeOwned,
+    {
+        let wanted: Vec<VoteThreshold<N::Runtime>> =
+            serde_json::from_slice(&std::fs::read(&self.file)?)?;
+        let mut orgs = Vec::new();
+        for threshold in &wanted {
+            if !orgs.contains(&threshold.org().org()) {
+                orgs.push(threshold.org().org());
+            }
+        }
+        let mut registered = Vec::new();
+        for org in orgs {
+            registered.extend(registered_thresholds(client, org).await?);
+        }
+        let mut missing = Vec::new();
+        for threshold in wanted {
+            if !registered.contains(&threshold) && !missing.contains(&threshold)
+            {
+                missing.push(threshold);
+            }
+        }
+        // there is no extrinsic removing a threshold, so the ones missing
+        // from the file are only reported
+        for threshold in &registered {
+            println!("keep     {}", serde_json::to_string(threshold)?);
+        }
+        for threshold in &missing {
+            println!("register {}", serde_json::to_string(threshold)?);
+        }
+        if missing.is_empty() {
+            println!(
+                "The chain has every threshold in {}",
+                self.file.display()
+            );
+            return Ok(())
+        }
+        if !self.yes && !resolver.yes() {
+            println!("Pass --yes to register {} thresholds", missing.len());
+            return Ok(())
+        }
+        for threshold in missing {
+            let event = client.set_threshold_default(threshold).await?;
+            println!("Registered threshold {}", event.threshold_id);
+        }
+        Ok(())
+    }
+}
+
+/// The distinct thresholds registered for `org`, oldest first
+async fn registered_thresholds<N: Node, C: VoteClient<N>>(
+    client: &C,
+    org: <N::Runtime as Org>::OrgId,
+) -> Result<Vec<VoteThreshold<N::Runtime>>>
+where
+    N::Runtime: Vote,
+{
+    let mut thresholds = Vec::new();
+    for config in client.vote_thresholds_of(org).await? {
+        let threshold =
+            VoteThreshold::<N::Runtime>::new(config.org(), config.threshold());
+        if !thresholds.contains(&threshold) {
+            thresholds.push(threshold);
+        }
+    }
+    Ok(thresholds)
+}
@@ -0,0 +1,82 @@
+use crate::table::{
+    Column,
+    Table,
+};
+use clap::Clap;
+use std::path::Path;
+use substrate_subxt::{
+    sp_core::crypto::Ss58Codec,
+    system::System,
+};
+use sunshine_bounty_client::{
+    chain::ChainPropertiesExt,
+    watch::WatchList,
+};
+use sunshine_client_utils::{
+    crypto::ss58::Ss58,
+    Client,
+    Node,
+    Result,
+};
+
+#[derive(Clone, Debug, Clap)]
+pub struct KeyWatchCommand {
+    /// SS58 address of the account to follow without its key
+    pub account: String,
+    /// Name to pass to `--account` instead of the address
+    #[clap(long = "label")]
+    pub label: String,
+}
+
+impl KeyWatchCommand {
+    pub fn exec<N: Node, C: Client<N>>(
+        &self,
+        client: &C,
+        root: &Path,
+    ) -> Result<()>
+    where
+        <N::Runtime as System>::AccountId: Ss58Codec,
+    {
+        let account: Ss58<N::Runtime> = self.account.parse()?;
+        // saved in the format of the chain so it compares with the signer
+        let account = client.chain_properties().ss58(&account.0);
+        let mut list = WatchList::load(root)?;
+        list.add(&self.label, &account)?;
+        list.save()?;
+        println!("Watching {} as {}", account, self.label);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct KeyWatchedCommand {}
+
+impl KeyWatchedCommand {
+    pub fn exec(&self, root: &Path) -> Result<()> {
+        let list = WatchList::load(root)?;
+        let mut table = Table::new(&[
+            ("label", Column::Text),
+            ("account", Column::Address),
+        ]);
+        for (label, account) in list.iter() {
+            table.push(vec![label.clone(), account.clone()]);
+        }
+        table.print();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Clap)]
+pub struct KeyUnwatchCommand {
+    pub label: String,
+}
+
+impl KeyUnwatchCommand {
+    pub fn exec(&self, root: &Path) -> Result<()> {
+        let mut list = WatchList::load(root)?;
+        let account = list.remove(&self.label)?;
+        list.save()?;
+        println!("Stopped watching {} ({})", self.label, account);
+        Ok(())
+    }
+}
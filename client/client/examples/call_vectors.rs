@@ -0,0 +1,59 @@
+//! Writes the call vectors of a runtime to stdout, in the layout read by
+//! `compat::parse_vectors`.
+//!
+//! The metadata is read from the node at the url given as argument, or from
+//! the file given as argument, SCALE encoded or as the hex returned by
+//! `state_getMetadata`.
+use frame_metadata::RuntimeMetadataPrefixed;
+use jsonrpsee::common::Params;
+use parity_scale_codec::Decode;
+use std::convert::TryFrom;
+use substrate_subxt::{
+    sp_core::{
+        bytes::from_hex,
+        Bytes,
+    },
+    Metadata,
+};
+use test_client::{
+    client::Result,
+    compat,
+    Runtime,
+};
+
+const DEFAULT_URL: &str = "ws://127.0.0.1:9944";
+
+async fn read_metadata(source: &str) -> Result<Vec<u8>> {
+    if source.starts_with("ws://") || source.starts_with("wss://") {
+        let client = jsonrpsee::ws_client(source).await?;
+        let metadata: Bytes =
+            client.request("state_getMetadata", Params::None).await?;
+        return Ok(metadata.0)
+    }
+    let blob = std::fs::read(source)?;
+    match std::str::from_utf8(&blob).map(str::trim) {
+        Ok(hex) if hex.starts_with("0x") => Ok(from_hex(hex)?),
+        _ => Ok(blob),
+    }
+}
+
+#[async_std::main]
+async fn main() -> Result<()> {
+    let source = std::env::args().nth(1);
+    let source = source.as_deref().unwrap_or(DEFAULT_URL);
+    let blob = read_metadata(source).await?;
+    let metadata = RuntimeMetadataPrefixed::decode(&mut &blob[..])?;
+    // the names of the typed calls, the vectors are of the metadata alone
+    let typed = compat::call_vectors::<Runtime>(&Metadata::try_from(
+        RuntimeMetadataPrefixed::decode(&mut &blob[..])?,
+    )?)?;
+    let calls = typed
+        .iter()
+        .map(|vector| vector.call.as_str())
+        .collect::<Vec<_>>();
+    println!("# call vectors of {}", source);
+    for vector in compat::metadata_vectors::<Runtime>(&metadata, &calls)? {
+        println!("{}", vector);
+    }
+    Ok(())
+}
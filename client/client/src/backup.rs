@@ -0,0 +1,595 @@
+//! Export and import of the local client state for moving to a new device.
+//!
+//! The archive holds every file below the client root, i.e. the (already
+//! encrypted) keystore, the block store and the client config. It starts with
+//! a plain header naming the chain it was exported from, followed by the file
+//! entries encrypted in chunks with chacha20poly1305 under a key derived from
+//! the password with argon2. Each chunk is bound to its index, to whether it
+//! is the last one and to the header, so reordered, truncated or tampered
+//! archives fail to decrypt.
+use crate::error::Error;
+use chacha20poly1305::{
+    aead::{
+        generic_array::GenericArray,
+        Aead,
+        NewAead,
+        Payload,
+    },
+    ChaCha20Poly1305,
+};
+use parity_scale_codec::Encode;
+use rand::{
+    rngs::OsRng,
+    RngCore,
+};
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    io::{
+        self,
+        BufReader,
+        BufWriter,
+        Read,
+        Write,
+    },
+    path::{
+        Component,
+        Path,
+        PathBuf,
+    },
+};
+use substrate_subxt::system::System;
+use sunshine_client_utils::{
+    async_trait,
+    crypto::secrecy::{
+        ExposeSecret,
+        SecretString,
+    },
+    Client,
+    Node,
+    Result,
+};
+
+const MAGIC: &[u8; 8] = b"SUNSTATE";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+/// Plaintext bytes per encrypted chunk
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+/// How many bytes of file content were processed so far
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    pub done: u64,
+    pub total: u64,
+}
+
+#[async_trait]
+pub trait BackupClient<N: Node>: Client<N> {
+    /// Packages everything below `root`, the directory the client was
+    /// created in, into an encrypted archive at `path`.
+    async fn export_state(
+        &self,
+        root: &Path,
+        path: &Path,
+        password: &SecretString,
+        progress: &mut (dyn FnMut(Progress) + Send),
+    ) -> Result<()>;
+    /// Restores an archive written by `export_state` into `root`.
+    ///
+    /// Nothing below `root` is touched unless the archive was exported from
+    /// the chain this client is connected to and decrypts completely. The
+    /// client has to be recreated afterwards to pick up the restored state.
+    async fn import_state(
+        &self,
+        root: &Path,
+        path: &Path,
+        password: &SecretString,
+        progress: &mut (dyn FnMut(Progress) + Send),
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl<N, C> BackupClient<N> for C
+where
+    N: Node,
+    C: Client<N>,
+{
+    async fn export_state(
+        &self,
+        root: &Path,
+        path: &Path,
+        password: &SecretString,
+        progress: &mut (dyn FnMut(Progress) + Send),
+    ) -> Result<()> {
+        let genesis = genesis::<N, C>(self);
+        let mut files = Vec::new();
+        collect_files(root, root, &mut files)?;
+        let total = files.iter().map(|(_, len)| len).sum();
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let header = Header {
+            salt,
+            genesis,
+            total,
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+        let header_bytes = header.to_bytes();
+        out.write_all(&header_bytes)?;
+        let cipher = cipher(password, &salt)?;
+        let mut sealer = Sealer::new(out, cipher, header_bytes);
+        let mut done = 0;
+        progress(Progress { done, total });
+        for (relative, len) in files {
+            let name = relative_name(&relative)?;
+            sealer.write_all(&(name.len() as u32).to_le_bytes())?;
+            sealer.write_all(name.as_bytes())?;
+            sealer.write_all(&len.to_le_bytes())?;
+            let mut file = File::open(root.join(&relative))?.take(len);
+            let mut buf = vec![0u8; CHUNK_LEN];
+            let mut remaining = len;
+            while remaining > 0 {
+                let read = file.read(&mut buf)?;
+                if read == 0 {
+                    // the entry already promised `len` bytes
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "file shrank while exporting",
+                    )
+                    .into())
+                }
+                sealer.write_all(&buf[..read])?;
+                remaining -= read as u64;
+                done += read as u64;
+                progress(Progress { done, total });
+            }
+        }
+        sealer.finish()?.flush()?;
+        Ok(())
+    }
+
+    async fn import_state(
+        &self,
+        root: &Path,
+        path: &Path,
+        password: &SecretString,
+        progress: &mut (dyn FnMut(Progress) + Send),
+    ) -> Result<()> {
+        let mut input = BufReader::new(File::open(path)?);
+        let (header, header_bytes) = Header::read(&mut input)?;
+        if header.genesis != genesis::<N, C>(self) {
+            return Err(Error::BackupChainMismatch.into())
+        }
+        let cipher = cipher(password, &header.salt)?;
+        let staging = root.with_extension("import");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+        let mut opener = Opener::new(input, cipher, header_bytes);
+        let restored = restore(&mut opener, &staging, header.total, progress);
+        if let Err(err) = restored {
+            fs::remove_dir_all(&staging).ok();
+            return Err(err)
+        }
+        // the archive is complete and authentic, swap it in
+        let old = root.with_extension("old");
+        if root.exists() {
+            if old.exists() {
+                fs::remove_dir_all(&old)?;
+            }
+            fs::rename(root, &old)?;
+        }
+        fs::rename(&staging, root)?;
+        if old.exists() {
+            fs::remove_dir_all(&old)?;
+        }
+        Ok(())
+    }
+}
+
+fn genesis<N: Node, C: Client<N>>(client: &C) -> Vec<u8> {
+    let genesis: &<N::Runtime as System>::Hash =
+        client.chain_client().genesis();
+    genesis.encode()
+}
+
+fn cipher(password: &SecretString, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let key = argon2::hash_raw(
+        password.expose_secret().as_bytes(),
+        salt,
+        &argon2::Config::default(),
+    )?;
+    Ok(ChaCha20Poly1305::new(GenericArray::from_slice(&key)))
+}
+
+fn nonce(index: u64, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..8].copy_from_slice(&index.to_le_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// Lists every file below `dir` with its path relative to `root`
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(PathBuf, u64)>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            collect_files(root, &entry.path(), files)?;
+        } else if meta.is_file() {
+            let relative = entry.path().strip_prefix(root)?.to_path_buf();
+            files.push((relative, meta.len()));
+        }
+    }
+    Ok(())
+}
+
+/// Relative paths are stored with `/` separators on every platform
+fn relative_name(path: &Path) -> Result<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => {
+                parts.push(part.to_str().ok_or(Error::InvalidBackup)?)
+            }
+            _ => return Err(Error::InvalidBackup.into()),
+        }
+    }
+    Ok(parts.join("/"))
+}
+
+/// Rejects names that would escape the directory they are restored to
+fn relative_path(name: &str) -> Result<PathBuf> {
+    let mut path = PathBuf::new();
+    for part in name.split('/') {
+        if part.is_empty() || part == "." || part == ".." {
+            return Err(Error::InvalidBackup.into())
+        }
+        path.push(part);
+    }
+    match path.components().all(|c| matches!(c, Component::Normal(_))) {
+        true => Ok(path),
+        false => Err(Error::InvalidBackup.into()),
+    }
+}
+
+fn restore<R: Read>(
+    opener: &mut Opener<R>,
+    staging: &Path,
+    total: u64,
+    progress: &mut (dyn FnMut(Progress) + Send),
+) -> Result<()> {
+    let mut done = 0;
+    progress(Progress { done, total });
+    let mut len_bytes = [0u8; 4];
+    // a clean end of the plaintext stream can only follow an entry
+    while read_or_end(opener, &mut len_bytes)? {
+        let mut name = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+        opened(opener.read_exact(&mut name))?;
+        let name = String::from_utf8(name).map_err(|_| Error::InvalidBackup)?;
+        let relative = relative_path(&name)?;
+        let mut size = [0u8; 8];
+        opened(opener.read_exact(&mut size))?;
+        let size = u64::from_le_bytes(size);
+        let dest = staging.join(relative);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = BufWriter::new(File::create(&dest)?);
+        let mut remaining = size;
+        let mut buf = vec![0u8; CHUNK_LEN];
+        while remaining > 0 {
+            let want = remaining.min(CHUNK_LEN as u64) as usize;
+            opened(opener.read_exact(&mut buf[..want]))?;
+            file.write_all(&buf[..want])?;
+            remaining -= want as u64;
+            done += want as u64;
+            progress(Progress { done, total });
+        }
+        file.flush()?;
+    }
+    Ok(())
+}
+
+/// Recovers the error `Opener` failed with, a stream that ends in the
+/// middle of an entry is invalid
+fn opened<T>(result: io::Result<T>) -> Result<T> {
+    result.map_err(|err| {
+        match err.into_inner().map(|inner| inner.downcast::<Error>()) {
+            Some(Ok(err)) => (*err).into(),
+            _ => Error::InvalidBackup.into(),
+        }
+    })
+}
+
+/// Fills `buf` or returns false if the stream ended before its first byte
+fn read_or_end<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match opened(reader.read(&mut buf[read..]))? {
+            0 if read == 0 => return Ok(false),
+            0 => return Err(Error::InvalidBackup.into()),
+            n => read += n,
+        }
+    }
+    Ok(true)
+}
+
+struct Header {
+    salt: [u8; SALT_LEN],
+    genesis: Vec<u8>,
+    total: u64,
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&(self.genesis.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.genesis);
+        bytes.extend_from_slice(&self.total.to_le_bytes());
+        bytes
+    }
+
+    fn read<R: Read>(input: &mut R) -> Result<(Self, Vec<u8>)> {
+        let mut magic = [0u8; 9];
+        input
+            .read_exact(&mut magic)
+            .map_err(|_| Error::InvalidBackup)?;
+        if &magic[..8] != MAGIC || magic[8] != VERSION {
+            return Err(Error::InvalidBackup.into())
+        }
+        let mut salt = [0u8; SALT_LEN];
+        let mut len = [0u8; 4];
+        input
+            .read_exact(&mut salt)
+            .and_then(|_| input.read_exact(&mut len))
+            .map_err(|_| Error::InvalidBackup)?;
+        // genesis hashes are 32 bytes, anything much larger is garbage
+        let len = u32::from_le_bytes(len) as usize;
+        if len > 64 {
+            return Err(Error::InvalidBackup.into())
+        }
+        let mut genesis = vec![0u8; len];
+        let mut total = [0u8; 8];
+        input
+            .read_exact(&mut genesis)
+            .and_then(|_| input.read_exact(&mut total))
+            .map_err(|_| Error::InvalidBackup)?;
+        let header = Self {
+            salt,
+            genesis,
+            total: u64::from_le_bytes(total),
+        };
+        let bytes = header.to_bytes();
+        Ok((header, bytes))
+    }
+}
+
+/// Encrypts everything written to it in chunks of `CHUNK_LEN`
+struct Sealer<W: Write> {
+    inner: W,
+    cipher: ChaCha20Poly1305,
+    header: Vec<u8>,
+    buf: Vec<u8>,
+    index: u64,
+}
+
+impl<W: Write> Sealer<W> {
+    fn new(inner: W, cipher: ChaCha20Poly1305, header: Vec<u8>) -> Self {
+        Self {
+            inner,
+            cipher,
+            header,
+            buf: Vec::with_capacity(CHUNK_LEN),
+            index: 0,
+        }
+    }
+
+    fn seal(&mut self, last: bool) -> io::Result<()> {
+        let nonce = nonce(self.index, last);
+        let sealed = self
+            .cipher
+            .encrypt(
+                GenericArray::from_slice(&nonce),
+                Payload {
+                    msg: &self.buf,
+                    aad: &self.header,
+                },
+            )
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "seal"))?;
+        self.inner.write_all(&(sealed.len() as u32).to_le_bytes())?;
+        self.inner.write_all(&sealed)?;
+        self.buf.clear();
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Writes the last chunk, which may be empty
+    fn finish(mut self) -> io::Result<W> {
+        self.seal(true)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Sealer<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let n = data.len().min(CHUNK_LEN - self.buf.len());
+        self.buf.extend_from_slice(&data[..n]);
+        if self.buf.len() == CHUNK_LEN {
+            self.seal(false)?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decrypts a stream written by `Sealer`, failing unless it ends with the
+/// last chunk
+struct Opener<R: Read> {
+    inner: R,
+    cipher: ChaCha20Poly1305,
+    header: Vec<u8>,
+    buf: Vec<u8>,
+    pos: usize,
+    index: u64,
+    done: bool,
+}
+
+impl<R: Read> Opener<R> {
+    fn new(inner: R, cipher: ChaCha20Poly1305, header: Vec<u8>) -> Self {
+        Self {
+            inner,
+            cipher,
+            header,
+            buf: Vec::new(),
+            pos: 0,
+            index: 0,
+            done: false,
+        }
+    }
+
+    fn open(&mut self) -> std::result::Result<(), Error> {
+        let mut len = [0u8; 4];
+        self.inner
+            .read_exact(&mut len)
+            .map_err(|_| Error::InvalidBackup)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len < TAG_LEN || len > CHUNK_LEN + TAG_LEN {
+            return Err(Error::InvalidBackup)
+        }
+        let mut sealed = vec![0u8; len];
+        self.inner
+            .read_exact(&mut sealed)
+            .map_err(|_| Error::InvalidBackup)?;
+        // the last chunk is the only one that is not full
+        let last = len < CHUNK_LEN + TAG_LEN;
+        let nonce = nonce(self.index, last);
+        self.buf = self
+            .cipher
+            .decrypt(
+                GenericArray::from_slice(&nonce),
+                Payload {
+                    msg: &sealed,
+                    aad: &self.header,
+                },
+            )
+            .map_err(|_| Error::BackupDecryptionFailed)?;
+        self.pos = 0;
+        self.index += 1;
+        self.done = last;
+        // nothing may follow the last chunk
+        if last && !matches!(self.inner.read(&mut [0u8; 1]), Ok(0)) {
+            return Err(Error::InvalidBackup)
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Opener<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.buf.len() {
+            if self.done {
+                return Ok(0)
+            }
+            self.open()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        let n = out.len().min(self.buf.len() - self.pos);
+        out[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Node as _,
+        },
+        DevClient as Client,
+        DevNode as Node,
+    };
+
+    fn password(s: &str) -> SecretString {
+        SecretString::new(s.to_string())
+    }
+
+    #[async_std::test]
+    async fn state_survives_export_and_import() {
+        let node = Node::new_mock();
+        let (client, tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let nested = tmp.path().join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        // spans several chunks
+        let big: Vec<u8> = (0..3 * CHUNK_LEN + 7).map(|i| i as u8).collect();
+        fs::write(nested.join("blocks"), &big).unwrap();
+        let out = TempDir::new("backup").unwrap();
+        let archive = out.path().join("state.backup");
+        let pw = password("correct horse");
+        let mut reports = Vec::new();
+        client
+            .export_state(tmp.path(), &archive, &pw, &mut |p| reports.push(p))
+            .await
+            .unwrap();
+        let last = reports.last().unwrap();
+        assert_eq!(last.done, last.total);
+
+        let root = out.path().join("restored");
+        let err = client
+            .import_state(&root, &archive, &password("wrong"), &mut |_| {})
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::BackupDecryptionFailed)
+        ));
+        assert!(!root.exists());
+
+        client
+            .import_state(&root, &archive, &pw, &mut |_| {})
+            .await
+            .unwrap();
+        assert_eq!(fs::read(root.join("nested").join("blocks")).unwrap(), big);
+    }
+
+    #[async_std::test]
+    async fn import_rejects_tampered_archives() {
+        let node = Node::new_mock();
+        let (client, tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let out = TempDir::new("backup").unwrap();
+        let archive = out.path().join("state.backup");
+        client
+            .export_state(tmp.path(), &archive, &password("pw"), &mut |_| {})
+            .await
+            .unwrap();
+        let mut bytes = fs::read(&archive).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&archive, &bytes).unwrap();
+        let root = out.path().join("restored");
+        assert!(client
+            .import_state(&root, &archive, &password("pw"), &mut |_| {})
+            .await
+            .is_err());
+        assert!(!root.exists());
+        assert!(relative_path("../keystore").is_err());
+        assert!(relative_path("/etc/passwd").is_err());
+    }
+}
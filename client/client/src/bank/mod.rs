@@ -4,7 +4,17 @@ pub use subxt::*;
 
 use crate::{
     error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
     org::Org,
+    submit::SubmitExt,
 };
 use substrate_subxt::{
     system::System,
@@ -82,18 +92,21 @@ where
         bank_operator: Option<<N::Runtime as System>::AccountId>,
         threshold: Threshold<N::Runtime>,
     ) -> Result<AccountOpenedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .open_and_watch(
-                &signer,
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            OpenCall {
                 seed,
                 hosting_org,
                 bank_operator,
                 threshold,
-            )
-            .await?
-            .account_opened()?
-            .ok_or_else(|| Error::EventNotFound.into())
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "open")
+        .await?
+        .decoded("account_opened", |r| r.account_opened())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn propose_spend(
         &self,
@@ -101,50 +114,82 @@ where
         amount: BalanceOf<N::Runtime>,
         dest: <N::Runtime as System>::AccountId,
     ) -> Result<SpendProposedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .propose_spend_and_watch(&signer, bank_id, amount, dest)
-            .await?
-            .spend_proposed()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ProposeSpendCall {
+                bank_id,
+                amount,
+                dest,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "propose_spend")
+        .await?
+        .decoded("spend_proposed", |r| r.spend_proposed())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn trigger_vote(
         &self,
         bank_id: <N::Runtime as Bank>::BankId,
         spend_id: <N::Runtime as Bank>::SpendId,
     ) -> Result<VoteTriggeredEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .trigger_vote_and_watch(&signer, bank_id, spend_id)
-            .await?
-            .vote_triggered()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            TriggerVoteCall {
+                bank_id,
+                spend_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "trigger_vote")
+        .await?
+        .decoded("vote_triggered", |r| r.vote_triggered())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn sudo_approve(
         &self,
         bank_id: <N::Runtime as Bank>::BankId,
         spend_id: <N::Runtime as Bank>::SpendId,
     ) -> Result<SudoApprovedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .sudo_approve_and_watch(&signer, bank_id, spend_id)
-            .await?
-            .sudo_approved()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SudoApproveCall {
+                bank_id,
+                spend_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "sudo_approve")
+        .await?
+        .decoded("sudo_approved", |r| r.sudo_approved())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn close(
         &self,
         bank_id: <N::Runtime as Bank>::BankId,
     ) -> Result<AccountClosedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .close_and_watch(&signer, bank_id)
-            .await?
-            .account_closed()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            CloseCall {
+                bank_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "close")
+        .await?
+        .decoded("account_closed", |r| r.account_closed())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn bank(&self, bank_id: <N::Runtime as Bank>::BankId) -> Result<BankSt<N::Runtime>> {
-        Ok(self.chain_client().banks(bank_id, None).await?)
+        Ok(self
+            .chain_client()
+            .banks(bank_id, None)
+            .traced(Op::Rpc, "banks")
+            .await?)
     }
     async fn spend_proposal(
         &self,
@@ -154,13 +199,18 @@ where
         Ok(self
             .chain_client()
             .spend_proposals(bank_id, spend_id, None)
+            .traced(Op::Rpc, "spend_proposals")
             .await?)
     }
     async fn banks_for_org(
         &self,
         org: <N::Runtime as Org>::OrgId,
     ) -> Result<Option<Vec<(<N::Runtime as Bank>::BankId, BankSt<N::Runtime>)>>> {
-        let mut banks = self.chain_client().banks_iter(None).await?;
+        let mut banks = self
+            .chain_client()
+            .banks_iter(None)
+            .traced(Op::Rpc, "banks_iter")
+            .await?;
         let mut banks_for_org = Vec::new();
         while let Some((_, bank)) = banks.next().await? {
             if bank.org() == org {
@@ -0,0 +1,288 @@
+//! Screenshots and other files attached to submissions as evidence
+//!
+//! `EvidenceClient::pin_evidence` splits a file of up to
+//! `MAX_EVIDENCE_SIZE` bytes in `EvidenceChunk`s and pins them with an
+//! `Evidence` linking them, returning the `Attachment` to list in the
+//! `GithubSubmission` it is evidence for. `fetch_evidence` reads the file
+//! back from the local store, which fetches blocks it does not have from
+//! its peers. Files no peer serves are read from the http gateway in
+//! `GATEWAY_ENV` if it is set, block by block, every block checked against
+//! its cid. The size limit is checked again before anything is fetched, so
+//! a submitter cannot make reviewers download more than they would pin.
+use crate::{
+    error::Error,
+    metrics::{
+        Op,
+        TracedExt,
+    },
+    Attachment,
+    Evidence,
+    EvidenceChunk,
+    GithubIssue,
+    GithubSubmission,
+};
+#[cfg(feature = "native")]
+use async_std::{
+    net::TcpStream,
+    prelude::*,
+};
+use libipld::{
+    cache::Cache,
+    cbor::DagCborCodec,
+    cid::Cid,
+    codec::{
+        Codec,
+        Decode,
+    },
+    multihash::{
+        Code,
+        MultihashDigest,
+    },
+};
+use std::convert::TryFrom;
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    OffchainConfig,
+    Result,
+};
+
+/// The largest evidence file pinned or fetched, 5 MiB
+pub const MAX_EVIDENCE_SIZE: u64 = 5 * 1024 * 1024;
+
+/// The bytes of an evidence file in each `EvidenceChunk`
+pub const EVIDENCE_CHUNK_SIZE: usize = 256 * 1024;
+
+/// The environment variable with the `host:port` of the http gateway
+/// evidence is read from when no peer serves it
+pub const GATEWAY_ENV: &str = "SUNSHINE_IPFS_GATEWAY";
+
+/// Room for the status line and headers of a gateway response
+const MAX_RESPONSE_HEAD: u64 = 8 * 1024;
+
+#[async_trait]
+pub trait EvidenceClient<N: Node>: Client<N> {
+    /// Pins `bytes` of the type `mime`, returning the attachment to list
+    /// in a submission
+    async fn pin_evidence(
+        &self,
+        bytes: &[u8],
+        mime: &str,
+    ) -> Result<Attachment>;
+    /// The bytes of the evidence at `cid`, fetched from the peers or the
+    /// gateway if it is not pinned locally
+    async fn fetch_evidence(&self, cid: &Cid) -> Result<Vec<u8>>;
+    /// The body of a submission, without attachments if it was pinned
+    /// before them
+    async fn resolve_submission(&self, cid: &Cid) -> Result<GithubSubmission>;
+}
+
+#[async_trait]
+impl<N, C> EvidenceClient<N> for C
+where
+    N: Node,
+    C: Client<N>,
+    C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, Evidence>
+        + Cache<OffchainConfig<N>, DagCborCodec, EvidenceChunk>
+        + Cache<OffchainConfig<N>, DagCborCodec, GithubSubmission>
+        + Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
+{
+    async fn pin_evidence(
+        &self,
+        bytes: &[u8],
+        mime: &str,
+    ) -> Result<Attachment> {
+        let size = bytes.len() as u64;
+        check_size(size)?;
+        check_mime(mime)?;
+        let mut chunks = Vec::with_capacity(
+            (bytes.len() + EVIDENCE_CHUNK_SIZE - 1) / EVIDENCE_CHUNK_SIZE,
+        );
+        for data in bytes.chunks(EVIDENCE_CHUNK_SIZE) {
+            let chunk = EvidenceChunk {
+                data: data.to_vec(),
+            };
+            chunks.push(
+                self.offchain_client()
+                    .insert(chunk)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?,
+            );
+        }
+        let evidence = Evidence {
+            mime: mime.to_string(),
+            size,
+            chunks,
+        };
+        let cid = self
+            .offchain_client()
+            .insert(evidence)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        Ok(Attachment {
+            evidence: cid,
+            mime: mime.to_string(),
+            size,
+        })
+    }
+
+    async fn fetch_evidence(&self, cid: &Cid) -> Result<Vec<u8>> {
+        let evidence: Evidence = match self.offchain_client().get(cid).await {
+            Ok(evidence) => evidence,
+            Err(_) => gateway_block(cid).await?,
+        };
+        check_size(evidence.size)?;
+        let mut bytes = Vec::with_capacity(evidence.size as usize);
+        for chunk_cid in &evidence.chunks {
+            let chunk: EvidenceChunk =
+                match self.offchain_client().get(chunk_cid).await {
+                    Ok(chunk) => chunk,
+                    Err(_) => gateway_block(chunk_cid).await?,
+                };
+            if (bytes.len() + chunk.data.len()) as u64 > evidence.size {
+                return Err(Error::EvidenceCorrupt.into())
+            }
+            bytes.extend_from_slice(&chunk.data);
+        }
+        if bytes.len() as u64 != evidence.size {
+            return Err(Error::EvidenceCorrupt.into())
+        }
+        Ok(bytes)
+    }
+
+    async fn resolve_submission(&self, cid: &Cid) -> Result<GithubSubmission> {
+        let submission: std::result::Result<GithubSubmission, _> =
+            self.offchain_client().get(cid).await;
+        let err = match submission {
+            Ok(submission) => return Ok(submission),
+            Err(err) => err,
+        };
+        // bodies pinned before attachments are plain issues
+        let issue: std::result::Result<GithubIssue, _> =
+            self.offchain_client().get(cid).await;
+        match issue {
+            Ok(issue) => Ok(issue.into()),
+            Err(_) => Err(err.into()),
+        }
+    }
+}
+
+fn check_size(size: u64) -> Result<()> {
+    if size > MAX_EVIDENCE_SIZE {
+        return Err(Error::EvidenceTooLarge {
+            size,
+            max: MAX_EVIDENCE_SIZE,
+        }
+        .into())
+    }
+    Ok(())
+}
+
+/// Accepts `type/subtype` with optional parameters, like `image/png` or
+/// `text/plain; charset=utf-8`
+fn check_mime(mime: &str) -> Result<()> {
+    let essence = mime.split(';').next().unwrap_or_default().trim();
+    let token = |part: &str| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&b))
+    };
+    let valid = mime.len() <= 255
+        && match essence.find('/') {
+            Some(slash) => {
+                token(&essence[..slash]) && token(&essence[slash + 1..])
+            }
+            None => false,
+        };
+    if !valid {
+        return Err(Error::InvalidMimeType {
+            mime: mime.to_string(),
+        }
+        .into())
+    }
+    Ok(())
+}
+
+/// Reads the block at `cid` from the gateway in `GATEWAY_ENV`, checking it
+/// hashes to `cid` before decoding it
+async fn gateway_block<T: Decode<DagCborCodec>>(cid: &Cid) -> Result<T> {
+    let unavailable = || {
+        Error::EvidenceUnavailable {
+            cid: cid.to_string(),
+        }
+    };
+    let gateway = std::env::var(GATEWAY_ENV).map_err(|_| unavailable())?;
+    let block = match gateway_get(&gateway, cid).await {
+        Ok(Some(block)) => block,
+        Ok(None) => return Err(unavailable().into()),
+        Err(err) => {
+            tracing::warn!("gateway {} failed for {}: {}", gateway, cid, err);
+            return Err(unavailable().into())
+        }
+    };
+    let code = Code::try_from(cid.hash().code())
+        .map_err(|_| Error::EvidenceCorrupt)?;
+    if code.digest(&block) != *cid.hash() {
+        return Err(Error::EvidenceCorrupt.into())
+    }
+    Ok(DagCborCodec
+        .decode(&block)
+        .map_err(|_| Error::EvidenceCorrupt)?)
+}
+
+/// The body of a `200` answer to a raw block request, `None` for any other
+/// status
+#[cfg(feature = "native")]
+async fn gateway_get(
+    gateway: &str,
+    cid: &Cid,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let mut stream = TcpStream::connect(gateway).await?;
+    let request = format!(
+        "GET /ipfs/{}?format=raw HTTP/1.0\r\nHost: {}\r\n\
+         Accept: application/vnd.ipld.raw\r\n\r\n",
+        cid, gateway
+    );
+    stream.write_all(request.as_bytes()).await?;
+    // a block is at most a chunk and its framing
+    let limit = MAX_RESPONSE_HEAD + EVIDENCE_CHUNK_SIZE as u64 + 64;
+    let mut response = Vec::new();
+    stream.take(limit).read_to_end(&mut response).await?;
+    let head_end = match response.windows(4).position(|w| w == b"\r\n\r\n") {
+        Some(end) => end,
+        None => return Ok(None),
+    };
+    let status = response[..head_end].split(|b| *b == b' ').nth(1);
+    if status != Some(&b"200"[..]) {
+        return Ok(None)
+    }
+    Ok(Some(response.split_off(head_end + 4)))
+}
+
+/// Without sockets no gateway is reached, the block is unavailable
+#[cfg(not(feature = "native"))]
+async fn gateway_get(
+    _gateway: &str,
+    _cid: &Cid,
+) -> std::io::Result<Option<Vec<u8>>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types_need_a_type_and_a_subtype() {
+        assert!(check_mime("image/png").is_ok());
+        assert!(check_mime("text/plain; charset=utf-8").is_ok());
+        assert!(check_mime("application/vnd.ipld.raw").is_ok());
+        assert!(check_mime("png").is_err());
+        assert!(check_mime("image/").is_err());
+        assert!(check_mime("image/p ng").is_err());
+        assert!(check_size(MAX_EVIDENCE_SIZE).is_ok());
+        assert!(check_size(MAX_EVIDENCE_SIZE + 1).is_err());
+    }
+}
@@ -1,18 +1,60 @@
+mod evidence;
 mod subxt;
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    org::Org,
+    page::{
+        KeyHasher,
+        StoragePage,
+        DEFAULT_PAGE_SIZE,
+    },
+    submit::SubmitExt,
+    ArchivedSubmission,
+    BountyArchive,
+    GithubIssue,
+};
+pub use evidence::*;
+use futures::{
+    pin_mut,
+    TryStreamExt,
+};
 use libipld::{
     cache::Cache,
     cbor::DagCborCodec,
+    cid::Cid,
 };
-use parity_scale_codec::Encode;
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use std::marker::PhantomData;
 use substrate_subxt::{
+    sp_runtime::{
+        traits::Header as _,
+        Permill,
+    },
     system::System,
     Runtime,
     SignedExtension,
     SignedExtra,
 };
 pub use subxt::*;
+use sunshine_bounty_utils::bounty::{
+    AssetMetadata,
+    BountyTag,
+    RejectionReason,
+    SubmissionKind,
+};
 use sunshine_client_utils::{
     async_trait,
     Client,
@@ -30,30 +72,271 @@ where
         &self,
         bounty: <N::Runtime as Bounty>::BountyPost,
         amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
+        submission_kind: SubmissionKind,
+        admins: Vec<<N::Runtime as System>::AccountId>,
+        tags: Vec<BountyTag>,
+    ) -> Result<BountyPostedEvent<N::Runtime>>;
+    /// Posts a bounty to the board of `org`, funded by its treasury which is
+    /// the depositer, only allowed for the org supervisor and the posters
+    /// they authorized
+    #[allow(clippy::too_many_arguments)]
+    async fn post_org_bounty(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        bounty: <N::Runtime as Bounty>::BountyPost,
+        amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
+        submission_kind: SubmissionKind,
+        admins: Vec<<N::Runtime as System>::AccountId>,
+        tags: Vec<BountyTag>,
     ) -> Result<BountyPostedEvent<N::Runtime>>;
+    /// Allows or stops `poster` posting bounties for `org`, only allowed for
+    /// the org supervisor
+    async fn set_bounty_poster(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        poster: <N::Runtime as System>::AccountId,
+        authorized: bool,
+    ) -> Result<BountyPosterSetEvent<N::Runtime>>;
+    /// Matches `ratio` of every contribution to the bounty out of the
+    /// treasury of `org` until `cap` is spent, a zero ratio or cap ends the
+    /// program, only allowed for the org supervisor
+    async fn set_matching(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        org: <N::Runtime as Org>::OrgId,
+        ratio: Permill,
+        cap: BalanceOf<N::Runtime>,
+    ) -> Result<BountyMatchingSetEvent<N::Runtime>>;
     async fn contribute_to_bounty(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
         amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
     ) -> Result<BountyRaiseContributionEvent<N::Runtime>>;
+    /// Submits for the bounty, splitting the payout with the co-authors in
+    /// `split` and paying the submitter the rest
     async fn submit_for_bounty(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
         submission: <N::Runtime as Bounty>::BountySubmission,
         amount: BalanceOf<N::Runtime>,
+        split: PayoutSplit<N::Runtime>,
     ) -> Result<BountySubmissionPostedEvent<N::Runtime>>;
+    /// Replaces the co-authors the payout of a submission awaiting review is
+    /// split with, only allowed for its submitter
+    async fn set_submission_split(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        split: PayoutSplit<N::Runtime>,
+    ) -> Result<SubmissionSplitSetEvent<N::Runtime>>;
     async fn approve_bounty_submission(
         &self,
         submission_id: <N::Runtime as Bounty>::SubmissionId,
     ) -> Result<BountyPaymentExecutedEvent<N::Runtime>>;
+    async fn withdraw_bounty_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<BountySubmissionWithdrawnEvent<N::Runtime>>;
+    async fn reject_bounty_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        reason: RejectionReason,
+    ) -> Result<BountySubmissionRejectedEvent<N::Runtime>>;
+    async fn add_bounty_admin(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        new_admin: <N::Runtime as System>::AccountId,
+    ) -> Result<BountyAdminAddedEvent<N::Runtime>>;
+    async fn remove_bounty_admin(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        old_admin: <N::Runtime as System>::AccountId,
+    ) -> Result<BountyAdminRemovedEvent<N::Runtime>>;
+    async fn assign_reviewer(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        reviewer: <N::Runtime as System>::AccountId,
+    ) -> Result<SubmissionReviewerAssignedEvent<N::Runtime>>;
+    async fn set_reviewer_reward(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        reward: Option<Permill>,
+    ) -> Result<ReviewerRewardSetEvent<N::Runtime>>;
+    /// Closes the bounty to submissions after `deadline`, extended by
+    /// `window` blocks for each submission made in its final `window`
+    /// blocks up to `max_extension` blocks, only allowed for its admins
+    async fn set_bounty_deadline(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        deadline: Option<<N::Runtime as System>::BlockNumber>,
+        extension: Option<(
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    ) -> Result<BountyDeadlineSetEvent<N::Runtime>>;
+    /// Only accepts submissions from members of `org`, or from anyone if
+    /// None, only allowed for its admins
+    async fn set_required_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        org: Option<<N::Runtime as Org>::OrgId>,
+    ) -> Result<BountyRequiredOrgSetEvent<N::Runtime>>;
+    /// Replaces the tags of the bounty, only allowed for its admins
+    async fn set_bounty_tags(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        tags: Vec<BountyTag>,
+    ) -> Result<BountyTagsSetEvent<N::Runtime>>;
+    /// Maps the lowercase GitHub `handle` to the signer, with the Cid of a
+    /// gist or commit by the handle holding the signer's signature
+    async fn claim_github_handle(
+        &self,
+        handle: Vec<u8>,
+        attestation: <N::Runtime as Bounty>::IpfsReference,
+    ) -> Result<GithubHandleClaimedEvent<N::Runtime>>;
+    async fn release_github_handle(
+        &self,
+    ) -> Result<GithubHandleReleasedEvent<N::Runtime>>;
+    /// Has the org of `threshold` arbitrate disputes of the bounty's
+    /// rejections instead of the default one, or the default one if None,
+    /// only allowed for its admins
+    async fn set_bounty_arbitration(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        threshold: Option<<N::Runtime as Org>::SpendThresholdId>,
+    ) -> Result<BountyArbitrationSetEvent<N::Runtime>>;
+    /// Disputes the rejection of the signer's submission with the Cid of
+    /// their argument, reserving the dispute deposit and opening a vote of
+    /// the arbitration org
+    async fn dispute_rejection(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        argument: <N::Runtime as Org>::Cid,
+    ) -> Result<RejectionDisputedEvent<N::Runtime>>;
+    /// Pays the submission or slashes the dispute deposit once the
+    /// arbitration vote is decided
+    async fn resolve_dispute(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<DisputeResolution<N::Runtime>>;
+    /// Sets how many blocks submissions await review and the most a timed
+    /// out submission is approved for, None to reject them
+    async fn set_review_policy(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        timeout: <N::Runtime as System>::BlockNumber,
+        optimistic_cap: Option<BalanceOf<N::Runtime>>,
+    ) -> Result<ReviewPolicySetEvent<N::Runtime>>;
+    /// Rejects or, if the bounty is optimistic, approves a submission left
+    /// awaiting review past its timeout
+    async fn timeout_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<SubmissionTimedOutEvent<N::Runtime>>;
+    async fn submission_deposit(&self) -> Result<BalanceOf<N::Runtime>>;
+    /// Metadata of an asset accepted for new bounties, None if not accepted
+    async fn bounty_asset(
+        &self,
+        asset_id: <N::Runtime as Bounty>::AssetId,
+    ) -> Result<Option<AssetMetadata>>;
     async fn bounty(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
     ) -> Result<BountyState<N::Runtime>>;
+    async fn bounty_tags(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Vec<BountyTag>>;
+    /// The bounties with the tag, from the tag index
+    async fn bounties_by_tag(
+        &self,
+        tag: BountyTag,
+    ) -> Result<Vec<<N::Runtime as Bounty>::BountyId>>;
+    async fn submission_kind(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<SubmissionKind>;
+    /// The org whose members alone may submit for the bounty, if any
+    async fn required_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>>;
+    /// The org whose board the bounty was posted to, if any
+    async fn bounty_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>>;
+    /// The org matching the contributions to the bounty and what its
+    /// treasury still adds, if any
+    async fn bounty_matching(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<Matching<N::Runtime>>>;
+    /// The open bounties on the board of the org, from the org index
+    async fn org_bounties(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<<N::Runtime as Bounty>::BountyId>>;
+    /// The account and attestation which claimed the lowercase GitHub
+    /// `handle`, if any
+    async fn github_handle_account(
+        &self,
+        handle: Vec<u8>,
+    ) -> Result<
+        Option<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Bounty>::IpfsReference,
+        )>,
+    >;
+    async fn account_github_handle(
+        &self,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<Option<Vec<u8>>>;
     async fn submission(
         &self,
         submission_id: <N::Runtime as Bounty>::SubmissionId,
     ) -> Result<SubState<N::Runtime>>;
+    async fn submission_reviewer(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<<N::Runtime as System>::AccountId>>;
+    /// The co-authors the payout of the submission is split with, empty if
+    /// the submitter is paid it all
+    async fn submission_split(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<PayoutSplit<N::Runtime>>;
+    /// The threshold of the org arbitrating disputes of the bounty's
+    /// rejections, its own or else the default one, None if it has none
+    async fn arbitration_threshold(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::SpendThresholdId>>;
+    /// The rejection of the submission, if its bounty had an arbitrator and
+    /// no dispute of it was resolved yet
+    async fn rejected_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<Rejection<N::Runtime>>>;
+    /// The dispute of the submission's rejection awaiting the arbitration
+    /// vote, if any
+    async fn dispute(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<Dispute<N::Runtime>>>;
+    /// The review policy of the bounty, the default timeout without
+    /// optimistic approval unless its admins set their own
+    async fn review_policy(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Review<N::Runtime>>;
+    /// When the submission may be timed out if it is left awaiting review
+    async fn review_deadline(
+        &self,
+        submission: &SubState<N::Runtime>,
+    ) -> Result<ReviewDeadline<N::Runtime>>;
     async fn contribution(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
@@ -67,9 +350,37 @@ where
             Vec<(<N::Runtime as Bounty>::BountyId, BountyState<N::Runtime>)>,
         >,
     >;
+    /// Bounties which paid out all they could, for history views
+    async fn closed_bounties(
+        &self,
+    ) -> Result<
+        Option<
+            Vec<(<N::Runtime as Bounty>::BountyId, BountyState<N::Runtime>)>,
+        >,
+    >;
+    /// The amount paid to approved submissions of a bounty and their number
+    async fn bounty_payouts(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<(BalanceOf<N::Runtime>, u32)>;
+    /// Submissions awaiting review with their assigned reviewer, if any,
+    /// and when they may be timed out
     async fn open_submissions(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<
+        Option<
+            Vec<(
+                <N::Runtime as Bounty>::SubmissionId,
+                SubState<N::Runtime>,
+                Option<<N::Runtime as System>::AccountId>,
+                ReviewDeadline<N::Runtime>,
+            )>,
+        >,
+    >;
+    async fn unassigned_submissions(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
     ) -> Result<
         Option<
             Vec<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>)>,
@@ -83,6 +394,33 @@ where
         &self,
         account_id: <N::Runtime as System>::AccountId,
     ) -> Result<Option<Vec<Contrib<N::Runtime>>>>;
+    async fn account_submissions(
+        &self,
+        account_id: <N::Runtime as System>::AccountId,
+    ) -> Result<
+        Option<
+            Vec<(
+                <N::Runtime as Bounty>::SubmissionId,
+                SubState<N::Runtime>,
+                Option<<N::Runtime as System>::AccountId>,
+            )>,
+        >,
+    >;
+    /// Reads the state of the bounty at `block` into its archive document
+    async fn bounty_archive(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        block: <N::Runtime as System>::Hash,
+    ) -> Result<BountyArchive>;
+    /// Pins the archive document of the bounty at the finalized head to the
+    /// offchain store
+    async fn archive_bounty(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Cid>;
+    /// Re-derives the archive from the chain state at its block, true if the
+    /// archive still matches
+    async fn verify_archive(&self, cid: &Cid) -> Result<bool>;
 }
 
 #[async_trait]
@@ -92,7 +430,7 @@ where
     N::Runtime: Bounty,
     <<<N::Runtime as Runtime>::Extra as SignedExtra<N::Runtime>>::Extra as SignedExtension>::AdditionalSigned:
         Send + Sync,
-    <N::Runtime as Bounty>::IpfsReference: From<libipld::cid::Cid>,
+    <N::Runtime as Bounty>::IpfsReference: From<Cid> + Into<Cid>,
     C: Client<N>,
     C::OffchainClient: Cache<
             OffchainConfig<N>,
@@ -102,32 +440,144 @@ where
             OffchainConfig<N>,
             DagCborCodec,
             <N::Runtime as Bounty>::BountySubmission,
-        >,
+        > + Cache<OffchainConfig<N>, DagCborCodec, BountyArchive>
+        + Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
 {
     async fn post_bounty(
         &self,
         bounty: <N::Runtime as Bounty>::BountyPost,
         amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
+        submission_kind: SubmissionKind,
+        admins: Vec<<N::Runtime as System>::AccountId>,
+        tags: Vec<BountyTag>,
     ) -> Result<BountyPostedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
+        let signer = self.nonced_signer().await?;
         let issue = Encode::encode(&bounty);
-        let info = self.offchain_client().insert(bounty).await?;
-        self.chain_client()
-            .post_bounty_and_watch(&signer, issue, info.into(), amount)
-            .await?
-            .bounty_posted()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let info = self
+            .offchain_client()
+            .insert(bounty)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            PostBountyCall {
+                issue,
+                info: info.into(),
+                amount,
+                asset_id,
+                submission_kind,
+                admins,
+                tags,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "post_bounty")
+        .await?
+        .decoded("bounty_posted", |r| r.bounty_posted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn post_org_bounty(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        bounty: <N::Runtime as Bounty>::BountyPost,
+        amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
+        submission_kind: SubmissionKind,
+        admins: Vec<<N::Runtime as System>::AccountId>,
+        tags: Vec<BountyTag>,
+    ) -> Result<BountyPostedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let issue = Encode::encode(&bounty);
+        let info = self
+            .offchain_client()
+            .insert(bounty)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            PostOrgBountyCall {
+                org,
+                issue,
+                info: info.into(),
+                amount,
+                asset_id,
+                submission_kind,
+                admins,
+                tags,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "post_org_bounty")
+        .await?
+        .decoded("bounty_posted", |r| r.bounty_posted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_bounty_poster(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        poster: <N::Runtime as System>::AccountId,
+        authorized: bool,
+    ) -> Result<BountyPosterSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetBountyPosterCall {
+                org,
+                poster,
+                authorized,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_bounty_poster")
+        .await?
+        .decoded("bounty_poster_set", |r| r.bounty_poster_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_matching(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        org: <N::Runtime as Org>::OrgId,
+        ratio: Permill,
+        cap: BalanceOf<N::Runtime>,
+    ) -> Result<BountyMatchingSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetMatchingCall {
+                bounty_id,
+                org,
+                ratio,
+                cap,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_matching")
+        .await?
+        .decoded("bounty_matching_set", |r| r.bounty_matching_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn contribute_to_bounty(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
         amount: BalanceOf<N::Runtime>,
+        asset_id: Option<<N::Runtime as Bounty>::AssetId>,
     ) -> Result<BountyRaiseContributionEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .contribute_to_bounty_and_watch(&signer, bounty_id, amount)
-            .await?
-            .bounty_raise_contribution()?
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ContributeToBountyCall {
+                bounty_id,
+                amount,
+                asset_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "contribute_to_bounty")
+        .await?
+        .decoded("bounty_raise_contribution", |r| {
+                r.bounty_raise_contribution()
+            })?
             .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn submit_for_bounty(
@@ -135,41 +585,620 @@ where
         bounty_id: <N::Runtime as Bounty>::BountyId,
         submission: <N::Runtime as Bounty>::BountySubmission,
         amount: BalanceOf<N::Runtime>,
+        split: PayoutSplit<N::Runtime>,
     ) -> Result<BountySubmissionPostedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
+        let signer = self.nonced_signer().await?;
         let issue = Encode::encode(&submission);
-        let submission_ref = self.offchain_client().insert(submission).await?;
-        self.chain_client()
-            .submit_for_bounty_and_watch(
-                &signer,
+        let submission_ref: <N::Runtime as Bounty>::IpfsReference = self
+            .offchain_client()
+            .insert(submission)
+            .traced(Op::OffchainWrite, "insert")
+            .await?
+            .into();
+        // fail locally instead of paying fees for an extrinsic that will be rejected
+        let bounty = self
+            .chain_client()
+            .bounties(bounty_id, None)
+            .traced(Op::Rpc, "bounties")
+            .await?;
+        if submission_ref == bounty.info() {
+            return Err(Error::SubmissionMustDifferFromBountyInfo.into())
+        }
+        self.watch(
+            SubmitForBountyCall {
                 bounty_id,
                 issue,
-                submission_ref.into(),
+                submission_ref,
                 amount,
-            )
-            .await?
-            .bounty_submission_posted()?
+                split,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "submit_for_bounty")
+        .await?
+        .decoded("bounty_submission_posted", |r| {
+                r.bounty_submission_posted()
+            })?
             .ok_or_else(|| Error::EventNotFound.into())
     }
+    async fn set_submission_split(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        split: PayoutSplit<N::Runtime>,
+    ) -> Result<SubmissionSplitSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetSubmissionSplitCall {
+                submission_id,
+                split,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_submission_split")
+        .await?
+        .decoded("submission_split_set", |r| r.submission_split_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
     async fn approve_bounty_submission(
         &self,
         submission_id: <N::Runtime as Bounty>::SubmissionId,
     ) -> Result<BountyPaymentExecutedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .approve_bounty_submission_and_watch(&signer, submission_id)
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ApproveBountySubmissionCall {
+                submission_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "approve_bounty_submission")
+        .await?
+        .decoded("bounty_payment_executed", |r| {
+                r.bounty_payment_executed()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn withdraw_bounty_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<BountySubmissionWithdrawnEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            WithdrawBountySubmissionCall {
+                submission_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "withdraw_bounty_submission")
+        .await?
+        .decoded("bounty_submission_withdrawn", |r| {
+                r.bounty_submission_withdrawn()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn reject_bounty_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        reason: RejectionReason,
+    ) -> Result<BountySubmissionRejectedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            RejectBountySubmissionCall {
+                submission_id,
+                reason,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "reject_bounty_submission")
+        .await?
+        .decoded("bounty_submission_rejected", |r| {
+                r.bounty_submission_rejected()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn add_bounty_admin(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        new_admin: <N::Runtime as System>::AccountId,
+    ) -> Result<BountyAdminAddedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            AddBountyAdminCall {
+                bounty_id,
+                new_admin,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "add_bounty_admin")
+        .await?
+        .decoded("bounty_admin_added", |r| r.bounty_admin_added())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn remove_bounty_admin(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        old_admin: <N::Runtime as System>::AccountId,
+    ) -> Result<BountyAdminRemovedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            RemoveBountyAdminCall {
+                bounty_id,
+                old_admin,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "remove_bounty_admin")
+        .await?
+        .decoded("bounty_admin_removed", |r| r.bounty_admin_removed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn assign_reviewer(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        reviewer: <N::Runtime as System>::AccountId,
+    ) -> Result<SubmissionReviewerAssignedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            AssignReviewerCall {
+                submission_id,
+                reviewer,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "assign_reviewer")
+        .await?
+        .decoded("submission_reviewer_assigned", |r| {
+                r.submission_reviewer_assigned()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_reviewer_reward(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        reward: Option<Permill>,
+    ) -> Result<ReviewerRewardSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetReviewerRewardCall {
+                bounty_id,
+                reward,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_reviewer_reward")
+        .await?
+        .decoded("reviewer_reward_set", |r| r.reviewer_reward_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_bounty_deadline(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        deadline: Option<<N::Runtime as System>::BlockNumber>,
+        extension: Option<(
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    ) -> Result<BountyDeadlineSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetBountyDeadlineCall {
+                bounty_id,
+                deadline,
+                extension,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_bounty_deadline")
+        .await?
+        .decoded("bounty_deadline_set", |r| r.bounty_deadline_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_required_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        org: Option<<N::Runtime as Org>::OrgId>,
+    ) -> Result<BountyRequiredOrgSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(SetRequiredOrgCall { bounty_id, org }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "set_required_org")
+            .await?
+            .decoded("bounty_required_org_set", |r| r.bounty_required_org_set())?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_bounty_tags(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        tags: Vec<BountyTag>,
+    ) -> Result<BountyTagsSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetBountyTagsCall {
+                bounty_id,
+                tags,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_bounty_tags")
+        .await?
+        .decoded("bounty_tags_set", |r| r.bounty_tags_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn claim_github_handle(
+        &self,
+        handle: Vec<u8>,
+        attestation: <N::Runtime as Bounty>::IpfsReference,
+    ) -> Result<GithubHandleClaimedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ClaimGithubHandleCall {
+                handle,
+                attestation,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "claim_github_handle")
+        .await?
+        .decoded("github_handle_claimed", |r| r.github_handle_claimed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn release_github_handle(
+        &self,
+    ) -> Result<GithubHandleReleasedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ReleaseGithubHandleCall {
+                _runtime: PhantomData,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "release_github_handle")
+        .await?
+        .decoded("github_handle_released", |r| r.github_handle_released())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_bounty_arbitration(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        threshold: Option<<N::Runtime as Org>::SpendThresholdId>,
+    ) -> Result<BountyArbitrationSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetBountyArbitrationCall {
+                bounty_id,
+                threshold,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_bounty_arbitration")
+        .await?
+        .decoded("bounty_arbitration_set", |r| r.bounty_arbitration_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn dispute_rejection(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+        argument: <N::Runtime as Org>::Cid,
+    ) -> Result<RejectionDisputedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            DisputeRejectionCall {
+                submission_id,
+                argument,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "dispute_rejection")
+        .await?
+        .decoded("rejection_disputed", |r| r.rejection_disputed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn resolve_dispute(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<DisputeResolution<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let result = self
+            .watch(ResolveDisputeCall { submission_id }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "resolve_dispute")
+            .await?;
+        if let Some(event) = result
+            .decoded("rejection_overturned", |r| r.rejection_overturned())?
+        {
+            return Ok(DisputeResolution::Overturned(event))
+        }
+        result
+            .decoded("rejection_upheld", |r| r.rejection_upheld())?
+            .map(DisputeResolution::Upheld)
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_review_policy(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        timeout: <N::Runtime as System>::BlockNumber,
+        optimistic_cap: Option<BalanceOf<N::Runtime>>,
+    ) -> Result<ReviewPolicySetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetReviewPolicyCall {
+                bounty_id,
+                timeout,
+                optimistic_cap,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_review_policy")
+        .await?
+        .decoded("review_policy_set", |r| r.review_policy_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn timeout_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<SubmissionTimedOutEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(TimeoutSubmissionCall { submission_id }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "timeout_submission")
             .await?
-            .bounty_payment_executed()?
+            .decoded("submission_timed_out", |r| r.submission_timed_out())?
             .ok_or_else(|| Error::EventNotFound.into())
     }
+    async fn submission_deposit(&self) -> Result<BalanceOf<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .metadata()
+            .module("Bounty")?
+            .constant("SubmissionDeposit")?
+            .value()?)
+    }
+    async fn bounty_asset(
+        &self,
+        asset_id: <N::Runtime as Bounty>::AssetId,
+    ) -> Result<Option<AssetMetadata>> {
+        Ok(self
+            .chain_client()
+            .bounty_assets(asset_id, None)
+            .traced(Op::Rpc, "bounty_assets")
+            .await?)
+    }
     async fn bounty(&self, bounty_id: <N::Runtime as Bounty>::BountyId) -> Result<BountyState<N::Runtime>> {
-        Ok(self.chain_client().bounties(bounty_id, None).await?)
+        Ok(self
+            .chain_client()
+            .bounties(bounty_id, None)
+            .traced(Op::Rpc, "bounties")
+            .await?)
+    }
+    async fn bounty_tags(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Vec<BountyTag>> {
+        Ok(self
+            .chain_client()
+            .bounty_tags(bounty_id, None)
+            .traced(Op::Rpc, "bounty_tags")
+            .await?)
+    }
+    async fn bounties_by_tag(
+        &self,
+        tag: BountyTag,
+    ) -> Result<Vec<<N::Runtime as Bounty>::BountyId>> {
+        let mut entries = self
+            .chain_client()
+            .bounties_by_tag_iter(None)
+            .traced(Op::Rpc, "bounties_by_tag_iter")
+            .await?;
+        let mut bounties = Vec::new();
+        // the keys are the two 16 byte prefixes followed by the blake2_128
+        // hash and encoding of the tag and of the bounty id
+        while let Some((key, _)) = entries.next().await? {
+            if key.0.get(48..64) == Some(&tag[..]) {
+                bounties.push(Decode::decode(&mut &key.0[80..])?);
+            }
+        }
+        Ok(bounties)
+    }
+    async fn submission_kind(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<SubmissionKind> {
+        Ok(self
+            .chain_client()
+            .submission_kinds(bounty_id, None)
+            .traced(Op::Rpc, "submission_kinds")
+            .await?)
+    }
+    async fn required_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>> {
+        Ok(self
+            .chain_client()
+            .required_orgs(bounty_id, None)
+            .traced(Op::Rpc, "required_orgs")
+            .await?)
+    }
+    async fn bounty_org(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>> {
+        Ok(self
+            .chain_client()
+            .bounty_orgs(bounty_id, None)
+            .traced(Op::Rpc, "bounty_orgs")
+            .await?)
+    }
+    async fn bounty_matching(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<Matching<N::Runtime>>> {
+        Ok(self
+            .chain_client()
+            .bounty_matching(bounty_id, None)
+            .traced(Op::Rpc, "bounty_matching")
+            .await?)
+    }
+    async fn org_bounties(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<<N::Runtime as Bounty>::BountyId>> {
+        let mut entries = self
+            .chain_client()
+            .bounties_by_org_iter(None)
+            .traced(Op::Rpc, "bounties_by_org_iter")
+            .await?;
+        let mut bounties = Vec::new();
+        // the keys are the two 16 byte prefixes followed by the blake2_128
+        // hash and encoding of the org and of the bounty id
+        while let Some((key, _)) = entries.next().await? {
+            let mut rest = key.0.get(48..).unwrap_or_default();
+            let owner: <N::Runtime as Org>::OrgId =
+                Decode::decode(&mut rest)?;
+            if owner == org {
+                let mut id = rest.get(16..).unwrap_or_default();
+                bounties.push(Decode::decode(&mut id)?);
+            }
+        }
+        Ok(bounties)
+    }
+    async fn github_handle_account(
+        &self,
+        handle: Vec<u8>,
+    ) -> Result<
+        Option<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Bounty>::IpfsReference,
+        )>,
+    > {
+        Ok(self
+            .chain_client()
+            .github_handle_to_account(handle, None)
+            .traced(Op::Rpc, "github_handle_to_account")
+            .await?)
+    }
+    async fn account_github_handle(
+        &self,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .chain_client()
+            .account_github_handles(account, None)
+            .traced(Op::Rpc, "account_github_handles")
+            .await?)
     }
     async fn submission(
         &self,
         submission_id: <N::Runtime as Bounty>::SubmissionId,
     ) -> Result<SubState<N::Runtime>> {
-        Ok(self.chain_client().submissions(submission_id, None).await?)
+        Ok(self
+            .chain_client()
+            .submissions(submission_id, None)
+            .traced(Op::Rpc, "submissions")
+            .await?)
+    }
+    async fn submission_reviewer(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<<N::Runtime as System>::AccountId>> {
+        Ok(self
+            .chain_client()
+            .submission_reviewers(submission_id, None)
+            .traced(Op::Rpc, "submission_reviewers")
+            .await?)
+    }
+    async fn submission_split(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<PayoutSplit<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .submission_splits(submission_id, None)
+            .traced(Op::Rpc, "submission_splits")
+            .await?)
+    }
+    async fn arbitration_threshold(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<<N::Runtime as Org>::SpendThresholdId>> {
+        let own = self
+            .chain_client()
+            .bounty_arbitration(bounty_id, None)
+            .traced(Op::Rpc, "bounty_arbitration")
+            .await?;
+        if own.is_some() {
+            return Ok(own)
+        }
+        Ok(self
+            .chain_client()
+            .default_arbitration(None)
+            .traced(Op::Rpc, "default_arbitration")
+            .await?)
+    }
+    async fn rejected_submission(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<Rejection<N::Runtime>>> {
+        Ok(self
+            .chain_client()
+            .rejected_submissions(submission_id, None)
+            .traced(Op::Rpc, "rejected_submissions")
+            .await?)
+    }
+    async fn dispute(
+        &self,
+        submission_id: <N::Runtime as Bounty>::SubmissionId,
+    ) -> Result<Option<Dispute<N::Runtime>>> {
+        Ok(self
+            .chain_client()
+            .disputes(submission_id, None)
+            .traced(Op::Rpc, "disputes")
+            .await?)
+    }
+    async fn review_policy(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Review<N::Runtime>> {
+        let chain = self.chain_client();
+        if let Some(policy) = chain
+            .review_policies(bounty_id, None)
+            .traced(Op::Rpc, "review_policies")
+            .await?
+        {
+            return Ok(policy)
+        }
+        let timeout = chain
+            .metadata()
+            .module("Bounty")?
+            .constant("ReviewTimeout")?
+            .value()?;
+        Ok(Review::<N::Runtime>::new(timeout, None))
+    }
+    async fn review_deadline(
+        &self,
+        submission: &SubState<N::Runtime>,
+    ) -> Result<ReviewDeadline<N::Runtime>> {
+        let policy = self.review_policy(submission.bounty_id()).await?;
+        Ok(ReviewDeadline::new(
+            &policy,
+            submission.submitted_at(),
+            best_block(self).await?,
+        ))
     }
     async fn contribution(
         &self,
@@ -179,18 +1208,29 @@ where
         Ok(self
             .chain_client()
             .contributions(bounty_id, account, None)
+            .traced(Op::Rpc, "contributions")
             .await?)
     }
     async fn open_bounties(
         &self,
         min: BalanceOf<N::Runtime>,
     ) -> Result<Option<Vec<(<N::Runtime as Bounty>::BountyId, BountyState<N::Runtime>)>>> {
-        let mut bounties = self.chain_client().bounties_iter(None).await?;
+        let chain = self.chain_client();
+        let pages = StoragePage::<
+            <N::Runtime as Bounty>::BountyId,
+            BountyState<N::Runtime>,
+        >::of::<_, BountiesStore<N::Runtime>>(
+            chain.metadata(),
+            KeyHasher::Blake2_128Concat,
+            DEFAULT_PAGE_SIZE,
+        )?;
+        let bounties = pages.stream(chain, None);
+        pin_mut!(bounties);
         let mut bounties_above_min =
             Vec::<(<N::Runtime as Bounty>::BountyId, BountyState<N::Runtime>)>::new();
-        while let Some((_, bounty)) = bounties.next().await? {
+        while let Some((id, bounty)) = bounties.try_next().await? {
             if bounty.total() >= min {
-                bounties_above_min.push((bounty.id(), bounty));
+                bounties_above_min.push((id, bounty));
             }
         }
         if bounties_above_min.is_empty() {
@@ -199,18 +1239,62 @@ where
             Ok(Some(bounties_above_min))
         }
     }
+    async fn closed_bounties(
+        &self,
+    ) -> Result<Option<Vec<(<N::Runtime as Bounty>::BountyId, BountyState<N::Runtime>)>>> {
+        let mut bounties = self
+            .chain_client()
+            .closed_bounties_iter(None)
+            .traced(Op::Rpc, "closed_bounties_iter")
+            .await?;
+        let mut closed = Vec::new();
+        while let Some((_, bounty)) = bounties.next().await? {
+            closed.push((bounty.id(), bounty));
+        }
+        if closed.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(closed))
+        }
+    }
+    async fn bounty_payouts(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<(BalanceOf<N::Runtime>, u32)> {
+        Ok(self
+            .chain_client()
+            .bounty_payouts(bounty_id, None)
+            .traced(Op::Rpc, "bounty_payouts")
+            .await?)
+    }
     async fn open_submissions(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
-    ) -> Result<Option<Vec<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>)>>> {
-        let mut submissions =
-            self.chain_client().submissions_iter(None).await?;
-        let mut submissions_for_bounty =
-            Vec::<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>)>::new();
-        while let Some((_, submission)) = submissions.next().await? {
+    ) -> Result<Option<Vec<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>, Option<<N::Runtime as System>::AccountId>, ReviewDeadline<N::Runtime>)>>> {
+        let chain = self.chain_client();
+        let policy = self.review_policy(bounty_id).await?;
+        let now = best_block(self).await?;
+        let pages = StoragePage::<
+            <N::Runtime as Bounty>::SubmissionId,
+            SubState<N::Runtime>,
+        >::of::<_, SubmissionsStore<N::Runtime>>(
+            chain.metadata(),
+            KeyHasher::Blake2_128Concat,
+            DEFAULT_PAGE_SIZE,
+        )?;
+        let submissions = pages.stream(chain, None);
+        pin_mut!(submissions);
+        let mut submissions_for_bounty = Vec::new();
+        while let Some((id, submission)) = submissions.try_next().await? {
             if submission.bounty_id() == bounty_id {
+                let reviewer = self.submission_reviewer(id).await?;
+                let deadline = ReviewDeadline::new(
+                    &policy,
+                    submission.submitted_at(),
+                    now,
+                );
                 submissions_for_bounty
-                    .push((submission.submission_id(), submission));
+                    .push((id, submission, reviewer, deadline));
             }
         }
         if submissions_for_bounty.is_empty() {
@@ -219,12 +1303,33 @@ where
             Ok(Some(submissions_for_bounty))
         }
     }
+    async fn unassigned_submissions(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Option<Vec<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>)>>> {
+        let unassigned: Vec<_> = self
+            .open_submissions(bounty_id)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, _, reviewer, _)| reviewer.is_none())
+            .map(|(id, submission, _, _)| (id, submission))
+            .collect();
+        if unassigned.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(unassigned))
+        }
+    }
     async fn bounty_contributions(
         &self,
         bounty_id: <N::Runtime as Bounty>::BountyId,
     ) -> Result<Option<Vec<Contrib<N::Runtime>>>> {
-        let mut contributions =
-            self.chain_client().contributions_iter(None).await?;
+        let mut contributions = self
+            .chain_client()
+            .contributions_iter(None)
+            .traced(Op::Rpc, "contributions_iter")
+            .await?;
         let mut contributions_for_bounty = Vec::<Contrib<N::Runtime>>::new();
         while let Some((_, contrib)) = contributions.next().await? {
             if contrib.id() == bounty_id {
@@ -241,8 +1346,11 @@ where
         &self,
         account_id: <N::Runtime as System>::AccountId,
     ) -> Result<Option<Vec<Contrib<N::Runtime>>>> {
-        let mut contributions =
-            self.chain_client().contributions_iter(None).await?;
+        let mut contributions = self
+            .chain_client()
+            .contributions_iter(None)
+            .traced(Op::Rpc, "contributions_iter")
+            .await?;
         let mut contributions_by_account = Vec::<Contrib<N::Runtime>>::new();
         while let Some((_, contrib)) = contributions.next().await? {
             if contrib.account() == account_id {
@@ -255,6 +1363,164 @@ where
             Ok(Some(contributions_by_account))
         }
     }
+    async fn account_submissions(
+        &self,
+        account_id: <N::Runtime as System>::AccountId,
+    ) -> Result<Option<Vec<(<N::Runtime as Bounty>::SubmissionId, SubState<N::Runtime>, Option<<N::Runtime as System>::AccountId>)>>> {
+        let mut submissions = self
+            .chain_client()
+            .submissions_iter(None)
+            .traced(Op::Rpc, "submissions_iter")
+            .await?;
+        let mut submissions_by_account = Vec::new();
+        while let Some((_, submission)) = submissions.next().await? {
+            if submission.submitter() == account_id {
+                let id = submission.submission_id();
+                let reviewer = self.submission_reviewer(id).await?;
+                submissions_by_account.push((id, submission, reviewer));
+            }
+        }
+        if submissions_by_account.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(submissions_by_account))
+        }
+    }
+    async fn bounty_archive(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+        block: <N::Runtime as System>::Hash,
+    ) -> Result<BountyArchive> {
+        let chain = self.chain_client();
+        let at = Some(block);
+        let (state, closed) = if let Some(state) = chain
+            .fetch(&BountiesStore::<N::Runtime> { id: bounty_id }, at)
+            .traced(Op::Rpc, "bounties")
+            .await?
+        {
+            (state, false)
+        } else if let Some(state) = chain
+            .fetch(&ClosedBountiesStore::<N::Runtime> { bounty_id }, at)
+            .traced(Op::Rpc, "closed_bounties")
+            .await?
+        {
+            (state, true)
+        } else {
+            return Err(Error::BountyNotFound.into())
+        };
+        let post: <N::Runtime as Bounty>::BountyPost = self
+            .offchain_client()
+            .get(&state.info().into())
+            .traced(Op::OffchainRead, "get")
+            .await?;
+        let tags = chain
+            .bounty_tags(bounty_id, at)
+            .traced(Op::Rpc, "bounty_tags")
+            .await?;
+        let payouts = chain
+            .bounty_payouts(bounty_id, at)
+            .traced(Op::Rpc, "bounty_payouts")
+            .await?;
+        let mut contributions = Vec::new();
+        let mut contribs = chain
+            .contributions_iter(at)
+            .traced(Op::Rpc, "contributions_iter")
+            .await?;
+        while let Some((_, contrib)) = contribs.next().await? {
+            if contrib.id() == bounty_id {
+                contributions.push(contrib.encode());
+            }
+        }
+        let mut submissions = Vec::new();
+        let mut subs = chain
+            .submissions_iter(at)
+            .traced(Op::Rpc, "submissions_iter")
+            .await?;
+        while let Some((_, submission)) = subs.next().await? {
+            if submission.bounty_id() == bounty_id {
+                let reviewer = chain
+                    .submission_reviewers(submission.submission_id(), at)
+                    .traced(Op::Rpc, "submission_reviewers")
+                    .await?;
+                let cid = submission.submission().into();
+                let body: std::result::Result<
+                    <N::Runtime as Bounty>::BountySubmission,
+                    _,
+                > = self
+                    .offchain_client()
+                    .get(&cid)
+                    .traced(Op::OffchainRead, "get")
+                    .await;
+                // bodies pinned before attachments are plain issues, which
+                // encode like the issue of a submission
+                let body = match body {
+                    Ok(body) => body.encode(),
+                    Err(err) => {
+                        let issue: std::result::Result<GithubIssue, _> =
+                            self.offchain_client().get(&cid).await;
+                        issue.map_err(|_| err)?.encode()
+                    }
+                };
+                submissions.push(ArchivedSubmission {
+                    state: submission.encode(),
+                    reviewer: reviewer.encode(),
+                    body,
+                });
+            }
+        }
+        Ok(BountyArchive {
+            block: block.encode(),
+            bounty_id: bounty_id.encode(),
+            state: state.encode(),
+            closed,
+            post: post.encode(),
+            tags: tags.encode(),
+            payouts: payouts.encode(),
+            contributions,
+            submissions,
+        })
+    }
+    async fn archive_bounty(
+        &self,
+        bounty_id: <N::Runtime as Bounty>::BountyId,
+    ) -> Result<Cid> {
+        let block = self
+            .chain_client()
+            .finalized_head()
+            .traced(Op::Rpc, "finalized_head")
+            .await?;
+        let archive = self.bounty_archive(bounty_id, block).await?;
+        Ok(self
+            .offchain_client()
+            .insert(archive)
+            .traced(Op::OffchainWrite, "insert")
+            .await?)
+    }
+    async fn verify_archive(&self, cid: &Cid) -> Result<bool> {
+        // the store checks the document against the cid when reading it, so
+        // comparing the documents compares their hashes
+        let archive: BountyArchive = self
+            .offchain_client()
+            .get(cid)
+            .traced(Op::OffchainRead, "get")
+            .await?;
+        let bounty_id = Decode::decode(&mut &archive.bounty_id[..])?;
+        let block = Decode::decode(&mut &archive.block[..])?;
+        Ok(self.bounty_archive(bounty_id, block).await? == archive)
+    }
+}
+
+/// The number of the best block, which review deadlines are measured from
+async fn best_block<N: Node, C: Client<N>>(
+    client: &C,
+) -> Result<<N::Runtime as System>::BlockNumber> {
+    Ok(client
+        .chain_client()
+        .header(None::<<N::Runtime as System>::Hash>)
+        .traced(Op::Rpc, "header")
+        .await?
+        .map(|header| *header.number())
+        .unwrap_or_default())
 }
 
 #[cfg(test)]
@@ -263,21 +1529,29 @@ mod tests {
         rngs::OsRng,
         RngCore,
     };
+    use substrate_subxt::sp_runtime::Permill;
     use test_client::{
         bounty::{
+            BountyAdminAddedEvent,
             BountyClient,
             BountyPostedEvent,
             BountyRaiseContributionEvent,
+            ReviewPolicySetEvent,
+            SubmissionReviewerAssignedEvent,
         },
         client::{
             AccountKeyring,
             Client as _,
             Node as _,
         },
-        utils::bounty::BountyInformation,
-        Client,
+        utils::bounty::{
+            bounty_tag,
+            BountyInformation,
+            SubmissionKind,
+        },
+        DevClient as Client,
+        DevNode as Node,
         GithubIssue,
-        Node,
     };
 
     // For testing purposes only, NEVER use this to generate AccountIds in practice because it's random
@@ -304,6 +1578,13 @@ mod tests {
             .unwrap();
     }
 
+    #[async_std::test]
+    async fn submission_deposit_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        assert_eq!(client.submission_deposit().await.unwrap(), 2);
+    }
+
     #[async_std::test]
     async fn post_bounty_test() {
         let node = Node::new_mock();
@@ -314,7 +1595,17 @@ mod tests {
             repo_name: "sunshine-bounty".to_string(),
             issue_number: 124,
         };
-        let event = client.post_bounty(bounty, 10u128).await.unwrap();
+        let event = client
+            .post_bounty(
+                bounty,
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
         let expected_event = BountyPostedEvent {
             depositer: alice_account_id,
             amount: 10,
@@ -324,6 +1615,47 @@ mod tests {
         assert_eq!(event, expected_event);
     }
 
+    #[async_std::test]
+    async fn bounty_admins_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (bob_client, _tmp_bob) =
+            Client::mock(&node, AccountKeyring::Bob).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 127,
+        };
+        client
+            .post_bounty(
+                bounty,
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![bob.clone()],
+                vec![],
+            )
+            .await
+            .unwrap();
+        // the co-depositer manages admins without the original poster
+        let event = bob_client
+            .add_bounty_admin(1, charlie.clone())
+            .await
+            .unwrap();
+        let expected_event = BountyAdminAddedEvent {
+            bounty_id: 1,
+            admin: bob.clone(),
+            new_admin: charlie.clone(),
+        };
+        assert_eq!(event, expected_event);
+        bob_client.remove_bounty_admin(1, alice).await.unwrap();
+        let admins = client.bounty(1).await.unwrap().admins();
+        assert_eq!(admins, vec![bob, charlie]);
+    }
+
     #[async_std::test]
     async fn get_bounties_test() {
         let node = Node::new_mock();
@@ -334,31 +1666,64 @@ mod tests {
             repo_name: "sunshine-bounty".to_string(),
             issue_number: 125,
         };
-        let event1 = client.post_bounty(bounty1, 10u128).await.unwrap();
+        let event1 = client
+            .post_bounty(
+                bounty1,
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![bounty_tag("rust").unwrap()],
+            )
+            .await
+            .unwrap();
         let bounty2 = GithubIssue {
             repo_owner: "sunshine-protocol".to_string(),
             repo_name: "sunshine-bounty".to_string(),
             issue_number: 126,
         };
-        let event2 = client.post_bounty(bounty2, 10u128).await.unwrap();
+        let event2 = client
+            .post_bounty(
+                bounty2,
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
         let bounties = client.open_bounties(9u128).await.unwrap().unwrap();
         assert_eq!(bounties.len(), 2);
         let expected_bounty1 = BountyInformation::new(
             1u64,
             event1.description,
             alice_account_id.clone(),
+            vec![alice_account_id.clone()],
+            None,
             10,
+            bounties.get(1).unwrap().1.posted_at(),
         );
         let expected_bounty2 = BountyInformation::new(
             2u64,
             event2.description,
-            alice_account_id,
+            alice_account_id.clone(),
+            vec![alice_account_id],
+            None,
             10,
+            bounties.get(0).unwrap().1.posted_at(),
         );
+        assert!(expected_bounty1.posted_at() <= expected_bounty2.posted_at());
         assert_eq!(bounties.get(0).unwrap().1, expected_bounty2);
         assert_eq!(bounties.get(0).unwrap().0, 2u64);
         assert_eq!(bounties.get(1).unwrap().1, expected_bounty1);
         assert_eq!(bounties.get(1).unwrap().0, 1u64);
+        let rust = bounty_tag("rust").unwrap();
+        assert_eq!(client.bounties_by_tag(rust).await.unwrap(), vec![1u64]);
+        client.set_bounty_tags(2, vec![rust]).await.unwrap();
+        client.set_bounty_tags(1, vec![]).await.unwrap();
+        assert_eq!(client.bounties_by_tag(rust).await.unwrap(), vec![2u64]);
+        assert_eq!(client.bounty_tags(2).await.unwrap(), vec![rust]);
     }
 
     #[async_std::test]
@@ -383,7 +1748,17 @@ mod tests {
             .free;
         println!("{}", b);
 
-        let event1 = client.post_bounty(bounty, 1000).await.unwrap();
+        let event1 = client
+            .post_bounty(
+                bounty,
+                1000,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
         let expected_event1 = BountyPostedEvent {
             depositer: alice_account_id.clone(),
             amount: 1000,
@@ -407,7 +1782,7 @@ mod tests {
             .free;
         println!("{}", b);
 
-        let event2 = client.contribute_to_bounty(1, 1000).await.unwrap();
+        let event2 = client.contribute_to_bounty(1, 1000, None).await.unwrap();
         let expected_event2 = BountyRaiseContributionEvent {
             contributor: alice_account_id.clone(),
             amount: 1000,
@@ -443,4 +1818,261 @@ mod tests {
         assert_eq!(account_contributions.len(), 1);
         assert_eq!(account_contributions.get(0).unwrap().total(), 2000);
     }
+
+    #[async_std::test]
+    async fn reviewer_assignment_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (charlie_client, _tmp_charlie) =
+            Client::mock(&node, AccountKeyring::Charlie).await;
+        let bob = AccountKeyring::Bob.to_account_id();
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 128,
+        };
+        client
+            .post_bounty(
+                bounty,
+                100u128,
+                None,
+                SubmissionKind::Any,
+                vec![bob.clone()],
+                vec![],
+            )
+            .await
+            .unwrap();
+        let submission = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 129,
+        };
+        let dave = AccountKeyring::Dave.to_account_id();
+        let split = vec![(dave, Permill::from_percent(25))];
+        charlie_client
+            .submit_for_bounty(1, submission.into(), 10u128, split.clone())
+            .await
+            .unwrap();
+        assert_eq!(client.submission_split(1).await.unwrap(), split);
+        let unassigned =
+            client.unassigned_submissions(1).await.unwrap().unwrap();
+        assert_eq!(unassigned.len(), 1);
+        let event = client.assign_reviewer(1, bob.clone()).await.unwrap();
+        let expected_event = SubmissionReviewerAssignedEvent {
+            bounty_id: 1,
+            submission_id: 1,
+            admin: AccountKeyring::Alice.to_account_id(),
+            reviewer: bob.clone(),
+        };
+        assert_eq!(event, expected_event);
+        assert!(client.unassigned_submissions(1).await.unwrap().is_none());
+        let open = client.open_submissions(1).await.unwrap().unwrap();
+        assert_eq!(open.get(0).unwrap().2, Some(bob.clone()));
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let mine = client.account_submissions(charlie).await.unwrap().unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine.get(0).unwrap().2, Some(bob.clone()));
+        assert!(client.account_submissions(bob).await.unwrap().is_none());
+    }
+    #[async_std::test]
+    async fn review_policy_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (charlie_client, _tmp_charlie) =
+            Client::mock(&node, AccountKeyring::Charlie).await;
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 131,
+        };
+        client
+            .post_bounty(
+                bounty,
+                100u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
+        let event = client
+            .set_review_policy(1, 100, Some(10u128))
+            .await
+            .unwrap();
+        let expected_event = ReviewPolicySetEvent {
+            bounty_id: 1,
+            admin: AccountKeyring::Alice.to_account_id(),
+            timeout: 100,
+            optimistic_cap: Some(10),
+        };
+        assert_eq!(event, expected_event);
+        let policy = client.review_policy(1).await.unwrap();
+        assert_eq!(policy.timeout(), 100);
+        assert_eq!(policy.optimistic_cap(), Some(10));
+        let submission = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 132,
+        };
+        charlie_client
+            .submit_for_bounty(1, submission.into(), 10u128, vec![])
+            .await
+            .unwrap();
+        let open = client.open_submissions(1).await.unwrap().unwrap();
+        let (_, submission, _, deadline) = open.get(0).unwrap();
+        assert_eq!(deadline.times_out_at, submission.submitted_at() + 100);
+        assert!(!deadline.nearing);
+        assert!(!deadline.passed);
+        assert_eq!(
+            &client.review_deadline(submission).await.unwrap(),
+            deadline
+        );
+    }
+    #[async_std::test]
+    async fn closed_bounties_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (charlie_client, _tmp_charlie) =
+            Client::mock(&node, AccountKeyring::Charlie).await;
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 130,
+        };
+        client
+            .post_bounty(
+                bounty,
+                1000u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert!(client.closed_bounties().await.unwrap().is_none());
+        let submission = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 131,
+        };
+        charlie_client
+            .submit_for_bounty(1, submission.into(), 500u128, vec![])
+            .await
+            .unwrap();
+        // only the existential deposit of the bounty account is left
+        client.approve_bounty_submission(1).await.unwrap();
+        assert!(client.open_bounties(0u128).await.unwrap().is_none());
+        let closed = client.closed_bounties().await.unwrap().unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed.get(0).unwrap().0, 1u64);
+        assert_eq!(client.bounty_payouts(1).await.unwrap(), (500, 1));
+    }
+
+    #[async_std::test]
+    async fn bounty_archive_test() {
+        use parity_scale_codec::Decode;
+        use test_client::{
+            bounty::{
+                BountyState,
+                Contrib,
+            },
+            Runtime,
+        };
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 124,
+        };
+        let event = client
+            .post_bounty(
+                bounty,
+                1000,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
+        client
+            .contribute_to_bounty(event.id, 1000, None)
+            .await
+            .unwrap();
+        let block = client
+            .chain_client()
+            .block_hash(None)
+            .await
+            .unwrap()
+            .unwrap();
+        let archive = client.bounty_archive(event.id, block).await.unwrap();
+        let state =
+            BountyState::<Runtime>::decode(&mut &archive.state[..]).unwrap();
+        assert_eq!(state.total(), 2000);
+        assert!(!archive.closed);
+        assert_eq!(archive.contributions.len(), 1);
+        let contrib =
+            Contrib::<Runtime>::decode(&mut &archive.contributions[0][..])
+                .unwrap();
+        assert_eq!(contrib.total(), 2000);
+        // the same block always yields the same document
+        assert_eq!(
+            client.bounty_archive(event.id, block).await.unwrap(),
+            archive
+        );
+        let genesis = client
+            .chain_client()
+            .block_hash(Some(0u32.into()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(client.bounty_archive(event.id, genesis).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn github_handle_test() {
+        use libipld::cache::Cache;
+        use test_client::{
+            bounty::Bounty,
+            Runtime,
+        };
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        // stands in for the gist holding alice's signature
+        let gist = GithubIssue {
+            repo_owner: "alice".to_string(),
+            repo_name: "gist".to_string(),
+            issue_number: 1,
+        };
+        let attestation: <Runtime as Bounty>::IpfsReference =
+            client.offchain_client().insert(gist).await.unwrap().into();
+        let event = client
+            .claim_github_handle(b"alice".to_vec(), attestation.clone())
+            .await
+            .unwrap();
+        assert_eq!(event.claimer, alice);
+        assert_eq!(
+            client
+                .github_handle_account(b"alice".to_vec())
+                .await
+                .unwrap(),
+            Some((alice.clone(), attestation))
+        );
+        assert_eq!(
+            client.account_github_handle(alice.clone()).await.unwrap(),
+            Some(b"alice".to_vec())
+        );
+        client.release_github_handle().await.unwrap();
+        assert_eq!(
+            client
+                .github_handle_account(b"alice".to_vec())
+                .await
+                .unwrap(),
+            None
+        );
+    }
 }
@@ -1,3 +1,7 @@
+use crate::org::{
+    Org,
+    OrgEventsDecoder,
+};
 use frame_support::Parameter;
 use libipld::{
     cbor::DagCborCodec,
@@ -11,13 +15,20 @@ use parity_scale_codec::{
     Decode,
     Encode,
 };
-use sp_runtime::traits::{
-    AtLeast32Bit,
-    MaybeSerializeDeserialize,
-    Member,
-    Zero,
+use sp_runtime::{
+    traits::{
+        AtLeast32Bit,
+        MaybeSerializeDeserialize,
+        Member,
+        Saturating,
+        Zero,
+    },
+    Permill,
+};
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
 };
-use std::fmt::Debug;
 use substrate_subxt::{
     balances::{
         Balances,
@@ -33,11 +44,22 @@ use substrate_subxt::{
     Event,
     Store,
 };
-use sunshine_bounty_utils::bounty::{
-    BountyInformation,
-    BountySubmission,
-    Contribution,
-    SubmissionState,
+use sunshine_bounty_utils::{
+    bounty::{
+        AssetMetadata,
+        BountyInformation,
+        BountySubmission,
+        BountyTag,
+        Contribution,
+        MatchingProgram,
+        RejectionDispute,
+        RejectionReason,
+        ReviewPolicy,
+        SubmissionKind,
+        SubmissionState,
+        TimeoutOutcome,
+    },
+    deadline::DeadlineExtension,
 };
 use sunshine_faucet_client::{
     Faucet,
@@ -51,7 +73,7 @@ use sunshine_identity_client::{
 pub type BalanceOf<T> = <T as Balances>::Balance;
 
 #[module]
-pub trait Bounty: System + Balances + Identity + Faucet {
+pub trait Bounty: System + Balances + Identity + Faucet + Org {
     /// Cid type
     type IpfsReference: Parameter + Member + Default;
 
@@ -98,6 +120,9 @@ pub trait Bounty: System + Balances + Identity + Faucet {
         + DagDecode<DagCborCodec>
         + Send
         + Sync;
+
+    /// Identifies assets other than the native currency
+    type AssetId: Parameter + Member + Copy + Default;
 }
 
 // ~~ Storage ~~
@@ -106,8 +131,13 @@ pub type BountyState<T> = BountyInformation<
     <T as Bounty>::BountyId,
     <T as Bounty>::IpfsReference,
     <T as System>::AccountId,
+    <T as Bounty>::AssetId,
     BalanceOf<T>,
+    <T as System>::BlockNumber,
 >;
+/// The org matching the contributions to a bounty and what its treasury
+/// still adds
+pub type Matching<T> = MatchingProgram<<T as Org>::OrgId, BalanceOf<T>>;
 pub type SubState<T> = BountySubmission<
     <T as Bounty>::BountyId,
     <T as Bounty>::SubmissionId,
@@ -115,7 +145,24 @@ pub type SubState<T> = BountySubmission<
     <T as System>::AccountId,
     BalanceOf<T>,
     SubmissionState,
+    <T as System>::BlockNumber,
 >;
+/// The co-authors a submission payout is split with and their shares, the
+/// submitter is paid the rest
+pub type PayoutSplit<T> = Vec<(<T as System>::AccountId, Permill)>;
+/// A rejected submission, its split, the block it was rejected at and the
+/// threshold of the org arbitrating a dispute of the rejection
+pub type Rejection<T> = (
+    SubState<T>,
+    PayoutSplit<T>,
+    <T as System>::BlockNumber,
+    <T as Org>::SpendThresholdId,
+);
+pub type Dispute<T> =
+    RejectionDispute<<T as Org>::Cid, BalanceOf<T>, <T as Org>::SpendVoteId>;
+/// How long submissions to a bounty await review and whether those awaiting
+/// longer are approved
+pub type Review<T> = ReviewPolicy<<T as System>::BlockNumber, BalanceOf<T>>;
 pub type Contrib<T> = Contribution<
     <T as Bounty>::BountyId,
     <T as System>::AccountId,
@@ -128,12 +175,163 @@ pub struct BountiesStore<T: Bounty> {
     pub id: T::BountyId,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SubmissionKindsStore<T: Bounty> {
+    #[store(returns = SubmissionKind)]
+    pub id: T::BountyId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
 pub struct SubmissionsStore<T: Bounty> {
     #[store(returns = SubState<T>)]
     pub id: T::SubmissionId,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SubmissionDepositsStore<T: Bounty> {
+    #[store(returns = BalanceOf<T>)]
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct PendingSubmissionsStore<T: Bounty> {
+    #[store(returns = u32)]
+    pub bounty_id: T::BountyId,
+    pub submitter: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SubmissionSplitsStore<T: Bounty> {
+    #[store(returns = PayoutSplit<T>)]
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SubmissionReviewersStore<T: Bounty> {
+    #[store(returns = Option<<T as System>::AccountId>)]
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ReviewerRewardsStore<T: Bounty> {
+    #[store(returns = Option<Permill>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyDeadlinesStore<T: Bounty> {
+    #[store(returns = Option<(
+        <T as System>::BlockNumber,
+        Option<DeadlineExtension<<T as System>::BlockNumber>>,
+    )>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct RequiredOrgsStore<T: Bounty> {
+    #[store(returns = Option<<T as Org>::OrgId>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyOrgsStore<T: Bounty> {
+    #[store(returns = Option<<T as Org>::OrgId>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountiesByOrgStore<T: Bounty> {
+    #[store(returns = Option<()>)]
+    pub org: <T as Org>::OrgId,
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyMatchingStore<T: Bounty> {
+    #[store(returns = Option<Matching<T>>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyPostersStore<T: Bounty> {
+    #[store(returns = Option<()>)]
+    pub org: <T as Org>::OrgId,
+    pub poster: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyAssetsStore<T: Bounty> {
+    #[store(returns = Option<AssetMetadata>)]
+    pub asset_id: T::AssetId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyTagsStore<T: Bounty> {
+    #[store(returns = Vec<BountyTag>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountiesByTagStore<T: Bounty> {
+    #[store(returns = Option<()>)]
+    pub tag: BountyTag,
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyPayoutsStore<T: Bounty> {
+    #[store(returns = (BalanceOf<T>, u32))]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ClosedBountiesStore<T: Bounty> {
+    #[store(returns = BountyState<T>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct GithubHandleToAccountStore<T: Bounty> {
+    #[store(returns = Option<(<T as System>::AccountId, T::IpfsReference)>)]
+    pub handle: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct AccountGithubHandlesStore<T: Bounty> {
+    #[store(returns = Option<Vec<u8>>)]
+    pub account: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct DefaultArbitrationStore<T: Bounty> {
+    #[store(returns = Option<<T as Org>::SpendThresholdId>)]
+    pub _runtime: PhantomData<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BountyArbitrationStore<T: Bounty> {
+    #[store(returns = Option<<T as Org>::SpendThresholdId>)]
+    pub bounty_id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct RejectedSubmissionsStore<T: Bounty> {
+    #[store(returns = Option<Rejection<T>>)]
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct DisputesStore<T: Bounty> {
+    #[store(returns = Option<Dispute<T>>)]
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ReviewPoliciesStore<T: Bounty> {
+    #[store(returns = Option<Review<T>>)]
+    pub bounty_id: T::BountyId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
 pub struct ContributionsStore<T: Bounty> {
     #[store(returns = Contrib<T>)]
@@ -148,6 +346,10 @@ pub struct PostBountyCall<T: Bounty> {
     pub issue: Vec<u8>,
     pub info: T::IpfsReference,
     pub amount: BalanceOf<T>,
+    pub asset_id: Option<T::AssetId>,
+    pub submission_kind: SubmissionKind,
+    pub admins: Vec<<T as System>::AccountId>,
+    pub tags: Vec<BountyTag>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -158,10 +360,68 @@ pub struct BountyPostedEvent<T: Bounty> {
     pub description: T::IpfsReference,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct PostOrgBountyCall<T: Bounty> {
+    pub org: <T as Org>::OrgId,
+    pub issue: Vec<u8>,
+    pub info: T::IpfsReference,
+    pub amount: BalanceOf<T>,
+    pub asset_id: Option<T::AssetId>,
+    pub submission_kind: SubmissionKind,
+    pub admins: Vec<<T as System>::AccountId>,
+    pub tags: Vec<BountyTag>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgBountyPostedEvent<T: Bounty> {
+    pub org: <T as Org>::OrgId,
+    pub poster: <T as System>::AccountId,
+    pub id: T::BountyId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetBountyPosterCall<T: Bounty> {
+    pub org: <T as Org>::OrgId,
+    pub poster: <T as System>::AccountId,
+    pub authorized: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyPosterSetEvent<T: Bounty> {
+    pub org: <T as Org>::OrgId,
+    pub poster: <T as System>::AccountId,
+    pub authorized: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetMatchingCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub org: <T as Org>::OrgId,
+    pub ratio: Permill,
+    pub cap: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyMatchingSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub org: <T as Org>::OrgId,
+    pub ratio: Permill,
+    pub cap: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ContributionMatchedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub org: <T as Org>::OrgId,
+    pub amount: BalanceOf<T>,
+    pub remaining_cap: BalanceOf<T>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
 pub struct ContributeToBountyCall<T: Bounty> {
     pub bounty_id: T::BountyId,
     pub amount: BalanceOf<T>,
+    pub asset_id: Option<T::AssetId>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -179,6 +439,7 @@ pub struct SubmitForBountyCall<T: Bounty> {
     pub issue: Vec<u8>,
     pub submission_ref: T::IpfsReference,
     pub amount: BalanceOf<T>,
+    pub split: PayoutSplit<T>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -189,6 +450,7 @@ pub struct BountySubmissionPostedEvent<T: Bounty> {
     pub id: T::SubmissionId,
     pub bounty_ref: T::IpfsReference,
     pub submission_ref: T::IpfsReference,
+    pub shares: Option<<T as Org>::Shares>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -205,4 +467,322 @@ pub struct BountyPaymentExecutedEvent<T: Bounty> {
     pub submitter: <T as System>::AccountId,
     pub bounty_ref: T::IpfsReference,
     pub submission_ref: T::IpfsReference,
+    pub approver: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SubmissionSlicePaidEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub recipient: <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetSubmissionSplitCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+    pub split: PayoutSplit<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SubmissionSplitSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub submitter: <T as System>::AccountId,
+    pub split: PayoutSplit<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct WithdrawBountySubmissionCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountySubmissionWithdrawnEvent<T: Bounty> {
+    pub submitter: <T as System>::AccountId,
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub deposit: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct RejectBountySubmissionCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+    pub reason: RejectionReason,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountySubmissionRejectedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub submitter: <T as System>::AccountId,
+    pub reason: RejectionReason,
+    pub deposit: BalanceOf<T>,
+    pub rejecter: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct AddBountyAdminCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub new_admin: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyAdminAddedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub new_admin: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct RemoveBountyAdminCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub old_admin: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyAdminRemovedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub old_admin: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct AssignReviewerCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+    pub reviewer: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SubmissionReviewerAssignedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub admin: <T as System>::AccountId,
+    pub reviewer: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetReviewerRewardCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub reward: Option<Permill>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ReviewerRewardSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub reward: Option<Permill>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ReviewerRewardedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub reviewer: <T as System>::AccountId,
+    pub reward: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyAssetSetEvent<T: Bounty> {
+    pub asset_id: T::AssetId,
+    pub metadata: Option<AssetMetadata>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetBountyTagsCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub tags: Vec<BountyTag>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyTagsSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub tags: Vec<BountyTag>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetBountyDeadlineCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub deadline: Option<<T as System>::BlockNumber>,
+    pub extension:
+        Option<(<T as System>::BlockNumber, <T as System>::BlockNumber)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyDeadlineSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub deadline: Option<<T as System>::BlockNumber>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyDeadlineExtendedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub deadline: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetRequiredOrgCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub org: Option<<T as Org>::OrgId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyRequiredOrgSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub org: Option<<T as Org>::OrgId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ClaimGithubHandleCall<T: Bounty> {
+    pub handle: Vec<u8>,
+    pub attestation: T::IpfsReference,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct GithubHandleClaimedEvent<T: Bounty> {
+    pub claimer: <T as System>::AccountId,
+    pub handle: Vec<u8>,
+    pub attestation: T::IpfsReference,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ReleaseGithubHandleCall<T: Bounty> {
+    pub _runtime: PhantomData<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct GithubHandleReleasedEvent<T: Bounty> {
+    pub claimer: <T as System>::AccountId,
+    pub handle: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyClosedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub total_paid: BalanceOf<T>,
+    pub submission_count: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct DefaultArbitrationSetEvent<T: Bounty> {
+    pub threshold: Option<<T as Org>::SpendThresholdId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetBountyArbitrationCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub threshold: Option<<T as Org>::SpendThresholdId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BountyArbitrationSetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub threshold: Option<<T as Org>::SpendThresholdId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct DisputeRejectionCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+    pub argument: <T as Org>::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RejectionDisputedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub submitter: <T as System>::AccountId,
+    pub argument: <T as Org>::Cid,
+    pub deposit: BalanceOf<T>,
+    pub vote: <T as Org>::SpendVoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ResolveDisputeCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RejectionOverturnedEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub submitter: <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+    pub deposit: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RejectionUpheldEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub submission_id: T::SubmissionId,
+    pub submitter: <T as System>::AccountId,
+    pub deposit: BalanceOf<T>,
+}
+
+/// How the arbitration vote resolved a disputed rejection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeResolution<T: Bounty> {
+    /// The submission was paid and the dispute deposit refunded
+    Overturned(RejectionOverturnedEvent<T>),
+    /// The dispute deposit was slashed to the bounty
+    Upheld(RejectionUpheldEvent<T>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetReviewPolicyCall<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub timeout: <T as System>::BlockNumber,
+    pub optimistic_cap: Option<BalanceOf<T>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ReviewPolicySetEvent<T: Bounty> {
+    pub bounty_id: T::BountyId,
+    pub admin: <T as System>::AccountId,
+    pub timeout: <T as System>::BlockNumber,
+    pub optimistic_cap: Option<BalanceOf<T>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct TimeoutSubmissionCall<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SubmissionTimedOutEvent<T: Bounty> {
+    pub submission_id: T::SubmissionId,
+    pub outcome: TimeoutOutcome<BalanceOf<T>>,
+}
+
+/// When a submission left awaiting review may be timed out, so apps can
+/// nag its reviewers before it is
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewDeadline<T: Bounty> {
+    /// The first block anyone may time the submission out at
+    pub times_out_at: <T as System>::BlockNumber,
+    /// Whether the last quarter of the review timeout began
+    pub nearing: bool,
+    /// Whether anyone may time the submission out already
+    pub passed: bool,
+}
+
+impl<T: Bounty> ReviewDeadline<T> {
+    /// The deadline of a submission made at `submitted_at` under `policy`,
+    /// as of block `now`
+    pub fn new(
+        policy: &Review<T>,
+        submitted_at: <T as System>::BlockNumber,
+        now: <T as System>::BlockNumber,
+    ) -> Self {
+        let times_out_at = policy.times_out_at(submitted_at);
+        let warning = policy.timeout() / 4u32.into();
+        Self {
+            times_out_at,
+            nearing: now.saturating_add(warning) >= times_out_at,
+            passed: now >= times_out_at,
+        }
+    }
 }
@@ -0,0 +1,533 @@
+//! Properties of the connected chain used to render accounts and amounts
+//!
+//! subxt reads `system_properties` when the client connects. Chains which
+//! publish none are rendered like generic substrate chains, with the ss58
+//! format 42 and no token, unless the client config overrides them. The
+//! overrides are read from `chain_properties.json` in the client root, in
+//! the layout of the rpc response:
+//!
+//! ```json
+//! { "ss58Format": 42, "tokenDecimals": 12, "tokenSymbol": "SUN" }
+//! ```
+//!
+//! Overrides apply to every client of the chain, keyed by its genesis hash.
+//!
+//! `BlockClockExt::block_clock` dates blocks from the timestamp of the best
+//! block and the block time of the chain. It assumes no slot was missed
+//! since, so the times are approximate.
+//!
+//! `ChainConstantsExt::constants` decodes constants by pallet and name from
+//! the metadata of the runtime at the best block, rather than from the
+//! metadata read on connection which is stale after a runtime upgrade. The
+//! constants apps check inputs against are cached by `chain_constants`
+//! until the spec version changes or `invalidate_chain_constants` is called
+//! on a `System::CodeUpdated` event.
+use frame_metadata::RuntimeMetadataPrefixed;
+use jsonrpsee::common::Params;
+use once_cell::sync::Lazy;
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    path::Path,
+    sync::Mutex,
+};
+use substrate_subxt::{
+    balances::Balances,
+    sp_core::{
+        crypto::{
+            Ss58AddressFormat,
+            Ss58Codec,
+        },
+        storage::StorageKey,
+        twox_128,
+        Bytes,
+    },
+    sp_runtime::traits::{
+        Header as _,
+        UniqueSaturatedInto,
+    },
+    system::System,
+    Metadata,
+    SystemProperties,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    Result,
+};
+
+const PROPERTIES_FILE: &str = "chain_properties.json";
+
+/// The ss58 format of generic substrate chains
+pub const SUBSTRATE_SS58_FORMAT: u8 = 42;
+
+static OVERRIDES: Lazy<Mutex<HashMap<Vec<u8>, PropertiesOverrides>>> =
+    Lazy::new(Default::default);
+
+/// The chain constants by genesis, with the spec version they were read at
+static CONSTANTS: Lazy<Mutex<HashMap<Vec<u8>, (u32, ChainConstants)>>> =
+    Lazy::new(Default::default);
+
+/// How the chain renders accounts and amounts
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainProperties {
+    pub ss58_format: u8,
+    pub token_decimals: u8,
+    pub token_symbol: String,
+}
+
+impl Default for ChainProperties {
+    fn default() -> Self {
+        Self {
+            ss58_format: SUBSTRATE_SS58_FORMAT,
+            token_decimals: 0,
+            token_symbol: String::new(),
+        }
+    }
+}
+
+impl From<&SystemProperties> for ChainProperties {
+    fn from(properties: &SystemProperties) -> Self {
+        // subxt falls back to all zeros when the chain publishes nothing
+        if properties.token_symbol.is_empty()
+            && properties.ss58_format == 0
+            && properties.token_decimals == 0
+        {
+            return Self::default()
+        }
+        Self {
+            ss58_format: properties.ss58_format,
+            token_decimals: properties.token_decimals,
+            token_symbol: properties.token_symbol.clone(),
+        }
+    }
+}
+
+impl ChainProperties {
+    /// The ss58 address of `account` with the prefix of the chain
+    pub fn ss58<A: Ss58Codec>(&self, account: &A) -> String {
+        account.to_ss58check_with_version(Ss58AddressFormat::Custom(
+            self.ss58_format,
+        ))
+    }
+
+    /// Renders base units of the native token with its decimals and
+    /// symbol, without trailing zeros
+    pub fn format_amount(&self, amount: u128) -> String {
+        let decimals = self.token_decimals as u32;
+        let units = 10u128.checked_pow(decimals);
+        let mut formatted = match units {
+            Some(units) if decimals > 0 => {
+                let fraction = format!(
+                    "{:0width$}",
+                    amount % units,
+                    width = decimals as usize
+                );
+                let fraction = fraction.trim_end_matches('0');
+                if fraction.is_empty() {
+                    (amount / units).to_string()
+                } else {
+                    format!("{}.{}", amount / units, fraction)
+                }
+            }
+            _ => amount.to_string(),
+        };
+        if !self.token_symbol.is_empty() {
+            formatted.push(' ');
+            formatted.push_str(&self.token_symbol);
+        }
+        formatted
+    }
+}
+
+/// Chain properties set in the client config, for chains which do not
+/// publish them
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertiesOverrides {
+    pub ss58_format: Option<u8>,
+    pub token_decimals: Option<u8>,
+    pub token_symbol: Option<String>,
+}
+
+impl PropertiesOverrides {
+    /// Loads the overrides saved under the client root, none if there is no
+    /// config
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(PROPERTIES_FILE);
+        if path.exists() {
+            Ok(serde_json::from_slice(&std::fs::read(&path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        std::fs::create_dir_all(root)?;
+        std::fs::write(
+            root.join(PROPERTIES_FILE),
+            serde_json::to_vec_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn apply(&self, detected: ChainProperties) -> ChainProperties {
+        ChainProperties {
+            ss58_format: self.ss58_format.unwrap_or(detected.ss58_format),
+            token_decimals: self
+                .token_decimals
+                .unwrap_or(detected.token_decimals),
+            token_symbol: self
+                .token_symbol
+                .clone()
+                .unwrap_or(detected.token_symbol),
+        }
+    }
+}
+
+pub trait ChainPropertiesExt<N: Node>: Client<N> {
+    /// The properties detected on connection with the overrides applied
+    fn chain_properties(&self) -> ChainProperties;
+    /// Overrides the detected properties for every client of the chain
+    fn override_chain_properties(&self, overrides: PropertiesOverrides);
+}
+
+impl<N: Node, C: Client<N>> ChainPropertiesExt<N> for C {
+    fn chain_properties(&self) -> ChainProperties {
+        let chain = self.chain_client();
+        let detected = ChainProperties::from(chain.properties());
+        let overrides = OVERRIDES.lock().unwrap();
+        match overrides.get(&chain.genesis().encode()) {
+            Some(overrides) => overrides.apply(detected),
+            None => detected,
+        }
+    }
+
+    fn override_chain_properties(&self, overrides: PropertiesOverrides) {
+        let genesis = self.chain_client().genesis().encode();
+        OVERRIDES.lock().unwrap().insert(genesis, overrides);
+    }
+}
+
+/// The time of the best block, to date other blocks from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BlockClock {
+    /// The number of the best block
+    pub best: u64,
+    /// Its timestamp in milliseconds since the unix epoch
+    pub now: u64,
+    /// The milliseconds between two blocks
+    pub block_time: u64,
+}
+
+impl BlockClock {
+    /// The approximate time of `block` in milliseconds since the unix epoch
+    pub fn millis_at(&self, block: u64) -> u64 {
+        if block >= self.best {
+            self.now.saturating_add(
+                (block - self.best).saturating_mul(self.block_time),
+            )
+        } else {
+            self.now.saturating_sub(
+                (self.best - block).saturating_mul(self.block_time),
+            )
+        }
+    }
+}
+
+#[async_trait]
+pub trait BlockClockExt<N: Node>: Client<N> {
+    /// The clock of the best block, none if the chain has no timestamp
+    /// pallet
+    async fn block_clock(&self) -> Result<Option<BlockClock>>;
+}
+
+#[async_trait]
+impl<N: Node, C: Client<N>> BlockClockExt<N> for C {
+    async fn block_clock(&self) -> Result<Option<BlockClock>> {
+        let chain = self.chain_client();
+        // blocks are produced every two minimum timestamp periods
+        let block_time = match chain
+            .metadata()
+            .module("Timestamp")
+            .and_then(|m| m.constant("MinimumPeriod"))
+            .and_then(|c| c.value::<u64>())
+        {
+            Ok(period) => period * 2,
+            Err(_) => return Ok(None),
+        };
+        let header =
+            match chain.header(None::<<N::Runtime as System>::Hash>).await? {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+        let key = [twox_128(b"Timestamp"), twox_128(b"Now")].concat();
+        let now = chain
+            .fetch_unhashed::<u64>(StorageKey(key), Some(header.hash()))
+            .await?;
+        Ok(now.map(|now| {
+            BlockClock {
+                best: (*header.number()).unique_saturated_into(),
+                now,
+                block_time,
+            }
+        }))
+    }
+}
+
+/// The version of the runtime at the best block
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeVersion {
+    pub spec_name: String,
+    pub impl_name: String,
+    pub spec_version: u32,
+    pub impl_version: u32,
+    /// Not published by runtimes before substrate 2.0
+    #[serde(default)]
+    pub transaction_version: u32,
+}
+
+/// The constants of a runtime, decoded on demand
+pub struct Constants(Metadata);
+
+impl Constants {
+    /// Decodes the constant `name` of `pallet`
+    pub fn get<V: Decode>(&self, pallet: &str, name: &str) -> Result<V> {
+        Ok(self.0.module(pallet)?.constant(name)?.value::<V>()?)
+    }
+}
+
+/// The runtime constants apps check inputs against
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ChainConstants {
+    /// Accounts with less are reaped
+    pub existential_deposit: u128,
+    /// The milliseconds between two blocks, none without a timestamp pallet
+    pub block_time: Option<u64>,
+    pub max_org_members: u32,
+    pub free_ballots: u32,
+    pub max_composite_legs: u32,
+    pub max_justification_history: u32,
+    pub bounty_min_deposit: u128,
+    pub bounty_min_contribution: u128,
+    pub bounty_submission_deposit: u128,
+    pub max_pending_submissions: u32,
+    pub max_bounty_admins: u32,
+    pub max_bounty_tags: u32,
+    pub max_payout_splits: u32,
+    pub bounty_dispute_deposit: u128,
+    /// The blocks after a rejection during which it may be disputed
+    pub bounty_dispute_window: u32,
+    /// The blocks submissions await review unless their bounty set its own
+    pub bounty_review_timeout: u32,
+}
+
+impl ChainConstants {
+    /// Reads the constants of a runtime with balances of type `B`
+    pub fn read<B: Decode + Into<u128>>(constants: &Constants) -> Result<Self> {
+        let balance = |pallet: &str, name: &str| {
+            constants.get::<B>(pallet, name).map(Into::into)
+        };
+        Ok(Self {
+            existential_deposit: balance("Balances", "ExistentialDeposit")?,
+            // blocks are produced every two minimum timestamp periods
+            block_time: constants
+                .get::<u64>("Timestamp", "MinimumPeriod")
+                .ok()
+                .map(|period| period * 2),
+            max_org_members: constants.get("Org", "MaxMembers")?,
+            free_ballots: constants.get("Vote", "FreeBallots")?,
+            max_composite_legs: constants.get("Vote", "MaxCompositeLegs")?,
+            max_justification_history: constants
+                .get("Vote", "MaxJustificationHistory")?,
+            bounty_min_deposit: balance("Bounty", "MinDeposit")?,
+            bounty_min_contribution: balance("Bounty", "MinContribution")?,
+            bounty_submission_deposit: balance("Bounty", "SubmissionDeposit")?,
+            max_pending_submissions: constants
+                .get("Bounty", "MaxPendingSubmissions")?,
+            max_bounty_admins: constants.get("Bounty", "MaxBountyAdmins")?,
+            max_bounty_tags: constants.get("Bounty", "MaxBountyTags")?,
+            max_payout_splits: constants.get("Bounty", "MaxPayoutSplits")?,
+            bounty_dispute_deposit: balance("Bounty", "DisputeDeposit")?,
+            bounty_dispute_window: constants.get("Bounty", "DisputeWindow")?,
+            bounty_review_timeout: constants.get("Bounty", "ReviewTimeout")?,
+        })
+    }
+}
+
+#[async_trait]
+pub trait ChainConstantsExt<N: Node>: Client<N> {
+    /// The version of the runtime at the best block, drops the cached
+    /// constants of an older version
+    async fn runtime_version(&self) -> Result<RuntimeVersion>;
+    /// The constants of the runtime at the best block
+    async fn constants(&self) -> Result<Constants>;
+    /// The constants apps check inputs against, cached for the chain
+    async fn chain_constants(&self) -> Result<ChainConstants>;
+    /// Drops the cached constants after a runtime upgrade
+    fn invalidate_chain_constants(&self);
+}
+
+#[async_trait]
+impl<N: Node, C: Client<N>> ChainConstantsExt<N> for C
+where
+    N::Runtime: Balances,
+    <N::Runtime as Balances>::Balance: Into<u128>,
+{
+    async fn runtime_version(&self) -> Result<RuntimeVersion> {
+        let chain = self.chain_client();
+        let version: RuntimeVersion = chain
+            .rpc_client()
+            .request("state_getRuntimeVersion", Params::None)
+            .await?;
+        let genesis = chain.genesis().encode();
+        let mut cache = CONSTANTS.lock().unwrap();
+        if let Some((spec_version, _)) = cache.get(&genesis) {
+            if *spec_version != version.spec_version {
+                cache.remove(&genesis);
+            }
+        }
+        Ok(version)
+    }
+
+    async fn constants(&self) -> Result<Constants> {
+        let metadata: Bytes = self
+            .chain_client()
+            .rpc_client()
+            .request("state_getMetadata", Params::None)
+            .await?;
+        let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])?;
+        Ok(Constants(Metadata::try_from(metadata)?))
+    }
+
+    async fn chain_constants(&self) -> Result<ChainConstants> {
+        let genesis = self.chain_client().genesis().encode();
+        let cached = CONSTANTS.lock().unwrap().get(&genesis).cloned();
+        if let Some((_, constants)) = cached {
+            return Ok(constants)
+        }
+        let version = self.runtime_version().await?;
+        let constants = ChainConstants::read::<
+            <N::Runtime as Balances>::Balance,
+        >(&self.constants().await?)?;
+        CONSTANTS
+            .lock()
+            .unwrap()
+            .insert(genesis, (version.spec_version, constants.clone()));
+        Ok(constants)
+    }
+
+    fn invalidate_chain_constants(&self) {
+        let genesis = self.chain_client().genesis().encode();
+        CONSTANTS.lock().unwrap().remove(&genesis);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use test_client::client::AccountKeyring;
+
+    fn response(json: serde_json::Value) -> ChainProperties {
+        let properties: SystemProperties =
+            serde_json::from_value(json).unwrap();
+        ChainProperties::from(&properties)
+    }
+
+    #[test]
+    fn published_properties_are_detected() {
+        let properties = response(json!({
+            "ss58Format": 2,
+            "tokenDecimals": 12,
+            "tokenSymbol": "SUN",
+        }));
+        assert_eq!(properties.ss58_format, 2);
+        assert_eq!(properties.token_decimals, 12);
+        let alice = AccountKeyring::Alice.to_account_id();
+        assert_eq!(
+            properties.ss58(&alice),
+            alice.to_ss58check_with_version(Ss58AddressFormat::KusamaAccount)
+        );
+        assert_ne!(properties.ss58(&alice), alice.to_string());
+        assert_eq!(properties.format_amount(1_500_000_000_000), "1.5 SUN");
+        assert_eq!(properties.format_amount(7), "0.000000000007 SUN");
+    }
+
+    #[test]
+    fn overrides_fill_in_unpublished_properties() {
+        let detected = response(json!({
+            "ss58Format": 0,
+            "tokenDecimals": 0,
+            "tokenSymbol": "",
+        }));
+        assert_eq!(detected, ChainProperties::default());
+        let alice = AccountKeyring::Alice.to_account_id();
+        assert_eq!(detected.ss58(&alice), alice.to_string());
+        assert_eq!(detected.format_amount(1000), "1000");
+        let overrides: PropertiesOverrides = serde_json::from_value(json!({
+            "tokenDecimals": 3,
+            "tokenSymbol": "SUN",
+        }))
+        .unwrap();
+        let properties = overrides.apply(detected);
+        assert_eq!(properties.ss58_format, SUBSTRATE_SS58_FORMAT);
+        assert_eq!(properties.format_amount(1000), "1 SUN");
+    }
+
+    #[test]
+    fn overrides_are_saved_in_the_root() {
+        let tmp = tempdir::TempDir::new("chain").unwrap();
+        assert_eq!(
+            PropertiesOverrides::load(tmp.path()).unwrap(),
+            PropertiesOverrides::default()
+        );
+        let overrides = PropertiesOverrides {
+            ss58_format: Some(7),
+            ..Default::default()
+        };
+        overrides.save(tmp.path()).unwrap();
+        assert_eq!(PropertiesOverrides::load(tmp.path()).unwrap(), overrides);
+    }
+
+    #[test]
+    fn blocks_are_dated_from_the_best_block() {
+        let clock = BlockClock {
+            best: 100,
+            now: 1_000_000,
+            block_time: 6000,
+        };
+        assert_eq!(clock.millis_at(100), 1_000_000);
+        assert_eq!(clock.millis_at(90), 940_000);
+        assert_eq!(clock.millis_at(102), 1_012_000);
+        assert_eq!(clock.millis_at(0), 400_000);
+        let genesis = BlockClock { now: 1000, ..clock };
+        assert_eq!(genesis.millis_at(0), 0);
+    }
+
+    #[test]
+    fn runtime_versions_before_transaction_versions_are_read() {
+        let version: RuntimeVersion = serde_json::from_value(json!({
+            "specName": "sunshine",
+            "implName": "sunshine-node",
+            "authoringVersion": 1,
+            "specVersion": 3,
+            "implVersion": 1,
+            "apis": [],
+        }))
+        .unwrap();
+        assert_eq!(version.spec_version, 3);
+        assert_eq!(version.transaction_version, 0);
+    }
+}
@@ -0,0 +1,820 @@
+//! Compatibility of the typed calls with the runtime of the node
+//!
+//! The typed calls are encoded field by field in the order they are
+//! declared in, the runtime decodes a call by the arguments its pallet
+//! declares. Nothing ties the two together, so a call whose arguments
+//! changed in a runtime upgrade is submitted as an extrinsic the runtime
+//! decodes as something else or not at all.
+//!
+//! `call_vectors` encodes a canonical instance of the typed calls the way
+//! submissions are encoded. `metadata_vectors` encodes the same calls from
+//! the runtime metadata alone, the canonical value of every argument in the
+//! order and with the type the metadata declares. `compare_vectors` fails
+//! with `Error::CallVectorMismatch`, naming the call and the first byte
+//! offset the encodings differ at, when the client no longer encodes a call
+//! like the runtime decodes it.
+//!
+//! The `call_vectors` example writes the metadata vectors of a dev node or
+//! of a metadata blob in the layout read by `parse_vectors`:
+//!
+//! ```text
+//! cargo run --example call_vectors -- ws://127.0.0.1:9944 > call_vectors.txt
+//! cargo run --example call_vectors -- metadata.scale > call_vectors.txt
+//! ```
+//!
+//! The tests compare the typed calls with the runtime of the test node and
+//! with the vectors of the file named by `CALL_VECTORS`, if it is set.
+//!
+//! `SUPPORTED_MODULES` lists the runtime versions each typed module was
+//! checked against. `CompatibilityExt::compatibility` reports for the
+//! runtime at the best block which modules are safe to use, which are
+//! degraded because their storage and events may have changed while their
+//! calls did not, and which are unsupported because their calls changed or
+//! their pallet is gone. Extrinsics are only submitted to nodes whose
+//! runtime is in the supported range of the module called, pallets the
+//! client calls outside the typed modules to `SUPPORTED_SPEC_VERSIONS`.
+//! Others fail with the error recognized by `unsupported_runtime` before
+//! anything is signed, unless `set_force_incompatible` was called.
+use crate::{
+    bounty::{
+        ApproveBountySubmissionCall,
+        Bounty,
+        ContributeToBountyCall,
+        PostBountyCall,
+    },
+    chain::RuntimeVersion,
+    donate::{
+        Donate,
+        MakePropDonationCall,
+    },
+    org::{
+        IssueSharesCall,
+        Org,
+    },
+    utils::{
+        bounty::{
+            bounty_tag,
+            SubmissionKind,
+        },
+        share::ShareClass,
+        vote::VoterView,
+    },
+    vote::{
+        CloseVoteCall,
+        SubmitVoteCall,
+        Vote,
+    },
+};
+use frame_metadata::{
+    DecodeDifferent,
+    ModuleMetadata,
+    RuntimeMetadata,
+    RuntimeMetadataPrefixed,
+};
+use jsonrpsee::common::Params;
+use once_cell::sync::Lazy;
+use parity_scale_codec::{
+    Compact,
+    Decode,
+    Encode,
+};
+use serde::Serialize;
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    sync::RwLock,
+};
+use substrate_subxt::{
+    balances::Balances,
+    sp_core::{
+        bytes::{
+            from_hex,
+            to_hex,
+        },
+        Bytes,
+    },
+    system::System,
+    Call,
+    Client as ChainClient,
+    Error,
+    Metadata,
+    Runtime,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    Result,
+};
+
+/// The runtime spec versions the calls of pallets outside the typed modules
+/// were checked against
+pub const SUPPORTED_SPEC_VERSIONS: RangeInclusive<u32> = 1..=1;
+
+/// The runtime versions a typed module of the client was checked against
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleSupport {
+    /// The client feature, as the report names it
+    pub feature: &'static str,
+    /// The pallet the feature calls and reads
+    pub pallet: &'static str,
+    /// The spec versions whose storage, events and constants are read
+    /// like the module lays them out
+    pub spec_versions: RangeInclusive<u32>,
+    /// The transaction versions which decode the calls like the module
+    /// encodes them
+    pub transaction_versions: RangeInclusive<u32>,
+}
+
+/// The supported runtimes of every typed module, extended when a runtime
+/// upgrade was checked against the client
+pub const SUPPORTED_MODULES: &[ModuleSupport] = &[
+    ModuleSupport {
+        feature: "bounty",
+        pallet: "Bounty",
+        spec_versions: 1..=1,
+        transaction_versions: 1..=1,
+    },
+    ModuleSupport {
+        feature: "vote",
+        pallet: "Vote",
+        spec_versions: 1..=1,
+        transaction_versions: 1..=1,
+    },
+    ModuleSupport {
+        feature: "org",
+        pallet: "Org",
+        spec_versions: 1..=1,
+        transaction_versions: 1..=1,
+    },
+    ModuleSupport {
+        feature: "donate",
+        pallet: "Donate",
+        spec_versions: 1..=1,
+        transaction_versions: 1..=1,
+    },
+];
+
+static FORCE_INCOMPATIBLE: Lazy<RwLock<bool>> = Lazy::new(Default::default);
+
+/// Sets whether every following submission is signed even if the runtime
+/// is outside the supported range of the module called
+pub fn set_force_incompatible(force: bool) {
+    *FORCE_INCOMPATIBLE.write().unwrap() = force;
+}
+
+/// How usable a feature of the client is with a runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Support {
+    Safe,
+    /// The calls encode like the runtime decodes them but the storage and
+    /// events may have changed, reads may fail or be wrong
+    Degraded,
+    /// The calls changed or the pallet is gone
+    Unsupported,
+}
+
+impl fmt::Display for Support {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Support::Safe => "safe",
+            Support::Degraded => "degraded",
+            Support::Unsupported => "unsupported",
+        })
+    }
+}
+
+/// The support of a feature by a runtime
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FeatureReport {
+    pub feature: String,
+    pub pallet: String,
+    pub support: Support,
+    /// Why the feature is not safe to use
+    pub reason: Option<String>,
+}
+
+/// The support of every typed module by a runtime
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Report {
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    pub features: Vec<FeatureReport>,
+}
+
+impl Report {
+    /// The support of `SUPPORTED_MODULES` by the runtime with the versions
+    /// of `version` and the pallets `pallets`
+    pub fn new(version: &RuntimeVersion, pallets: &[String]) -> Self {
+        let features = SUPPORTED_MODULES
+            .iter()
+            .map(|module| {
+                let (support, reason) =
+                    module_support(module, version, pallets);
+                FeatureReport {
+                    feature: module.feature.to_string(),
+                    pallet: module.pallet.to_string(),
+                    support,
+                    reason,
+                }
+            })
+            .collect();
+        Self {
+            spec_version: version.spec_version,
+            transaction_version: version.transaction_version,
+            features,
+        }
+    }
+
+    /// Whether every feature is safe to use
+    pub fn is_safe(&self) -> bool {
+        self.features
+            .iter()
+            .all(|feature| feature.support == Support::Safe)
+    }
+
+    /// The features which are not safe to use
+    pub fn mismatches(&self) -> impl Iterator<Item = &FeatureReport> {
+        self.features
+            .iter()
+            .filter(|feature| feature.support != Support::Safe)
+    }
+}
+
+fn module_support(
+    module: &ModuleSupport,
+    version: &RuntimeVersion,
+    pallets: &[String],
+) -> (Support, Option<String>) {
+    if !pallets.iter().any(|pallet| pallet == module.pallet) {
+        let reason = format!("the runtime has no {} pallet", module.pallet);
+        return (Support::Unsupported, Some(reason))
+    }
+    if !module
+        .transaction_versions
+        .contains(&version.transaction_version)
+    {
+        let reason = format!(
+            "calls were checked against transaction versions {} to {}, \
+             not {}",
+            module.transaction_versions.start(),
+            module.transaction_versions.end(),
+            version.transaction_version,
+        );
+        return (Support::Unsupported, Some(reason))
+    }
+    if !module.spec_versions.contains(&version.spec_version) {
+        let reason = format!(
+            "storage and events were checked against spec versions {} to \
+             {}, not {}",
+            module.spec_versions.start(),
+            module.spec_versions.end(),
+            version.spec_version,
+        );
+        return (Support::Degraded, Some(reason))
+    }
+    (Support::Safe, None)
+}
+
+/// Whether calls to `pallet` are submitted to the runtime of `version`
+fn is_writable(pallet: &str, version: &RuntimeVersion) -> bool {
+    match SUPPORTED_MODULES
+        .iter()
+        .find(|module| module.pallet == pallet)
+    {
+        Some(module) => {
+            module.spec_versions.contains(&version.spec_version)
+                && module
+                    .transaction_versions
+                    .contains(&version.transaction_version)
+        }
+        None => SUPPORTED_SPEC_VERSIONS.contains(&version.spec_version),
+    }
+}
+
+/// The names of the pallets with calls in `metadata`
+pub fn metadata_pallets(metadata: &RuntimeMetadataPrefixed) -> Vec<String> {
+    let modules = match &metadata.1 {
+        RuntimeMetadata::V12(metadata) => decoded(&metadata.modules),
+        _ => None,
+    };
+    modules
+        .into_iter()
+        .flatten()
+        .filter(|module| module.calls.is_some())
+        .filter_map(|module| decoded(&module.name).cloned())
+        .collect()
+}
+
+#[async_trait]
+pub trait CompatibilityExt<N: Node>: Client<N> {
+    /// The support of the typed modules by the runtime at the best block
+    async fn compatibility(&self) -> Result<Report>;
+}
+
+#[async_trait]
+impl<N: Node, C: Client<N>> CompatibilityExt<N> for C {
+    async fn compatibility(&self) -> Result<Report> {
+        let rpc = self.chain_client().rpc_client();
+        let version: RuntimeVersion =
+            rpc.request("state_getRuntimeVersion", Params::None).await?;
+        // the metadata read on connection is stale after an upgrade
+        let metadata: Bytes =
+            rpc.request("state_getMetadata", Params::None).await?;
+        let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])?;
+        Ok(Report::new(&version, &metadata_pallets(&metadata)))
+    }
+}
+
+/// The issue of the canonical bounty
+const CANONICAL_ISSUE: &[u8] = b"sunshine-protocol/sunshine-node#1";
+
+/// The encoding of a call, named `Module::function`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallVector {
+    pub call: String,
+    pub encoded: Vec<u8>,
+}
+
+impl fmt::Display for CallVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.call, to_hex(&self.encoded, false))
+    }
+}
+
+/// Reads vectors written one per line by their `Display`, skipping empty
+/// lines and lines starting with `#`
+pub fn parse_vectors(vectors: &str) -> Result<Vec<CallVector>> {
+    vectors
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let call = parts.next().unwrap_or_default();
+            let invalid = |reason: &str| {
+                crate::Error::CallVectorUnavailable {
+                    call: call.to_string(),
+                    reason: reason.to_string(),
+                }
+            };
+            let hex = parts.next().ok_or_else(|| invalid("no encoding"))?;
+            let encoded =
+                from_hex(hex).map_err(|_| invalid("encoding is not hex"))?;
+            Ok(CallVector {
+                call: call.to_string(),
+                encoded,
+            })
+        })
+        .collect()
+}
+
+/// The offset of the first byte `a` and `b` differ at, if they differ
+pub fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    match a.iter().zip(b).position(|(a, b)| a != b) {
+        Some(offset) => Some(offset),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+/// Fails on the first of `actual` which is not in `expected` or encodes
+/// differently
+pub fn compare_vectors(
+    expected: &[CallVector],
+    actual: &[CallVector],
+) -> Result<()> {
+    for vector in actual {
+        let golden = expected
+            .iter()
+            .find(|golden| golden.call == vector.call)
+            .ok_or_else(|| {
+                crate::Error::CallVectorUnavailable {
+                    call: vector.call.clone(),
+                    reason: "no vector to compare with".to_string(),
+                }
+            })?;
+        if let Some(offset) = first_difference(&golden.encoded, &vector.encoded)
+        {
+            return Err(crate::Error::CallVectorMismatch {
+                call: vector.call.clone(),
+                offset,
+            }
+            .into())
+        }
+    }
+    Ok(())
+}
+
+/// The canonical value of the argument type `ty`, as the metadata names it,
+/// encoded with the types of `T`
+///
+/// Options are `Some` and vectors have one element, so that the value they
+/// wrap is part of the encoding.
+pub fn canonical_arg<T: Bounty + Vote + Donate>(ty: &str) -> Option<Vec<u8>> {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    canonical_type::<T>(&ty)
+}
+
+fn canonical_type<T: Bounty + Vote + Donate>(ty: &str) -> Option<Vec<u8>> {
+    let wrapped = |prefix: &str| {
+        ty.strip_prefix(prefix)
+            .and_then(|ty| ty.strip_suffix('>'))
+            .and_then(canonical_type::<T>)
+    };
+    if ty.starts_with("Option<") {
+        let mut encoded = vec![1];
+        encoded.extend(wrapped("Option<")?);
+        return Some(encoded)
+    }
+    if ty.starts_with("Vec<") {
+        let mut encoded = Compact(1u32).encode();
+        encoded.extend(wrapped("Vec<")?);
+        return Some(encoded)
+    }
+    let encoded = match ty {
+        "EncodedIssue" => CANONICAL_ISSUE.encode(),
+        "T::IpfsReference" => T::IpfsReference::default().encode(),
+        "T::Cid" => <T as Org>::Cid::default().encode(),
+        "T::AccountId" => {
+            <T as System>::AccountId::decode(&mut &[0x11; 32][..])
+                .ok()?
+                .encode()
+        }
+        "BalanceOf<T>" => <T as Balances>::Balance::from(1_000u32).encode(),
+        "AssetIdOf<T>" => T::AssetId::default().encode(),
+        "SubmissionKind" => SubmissionKind::PullRequest.encode(),
+        "BountyTag" => bounty_tag("rust")?.encode(),
+        "T::BountyId" => T::BountyId::from(3u32).encode(),
+        "T::SubmissionId" => T::SubmissionId::from(4u32).encode(),
+        "T::VoteId" => T::VoteId::from(5u32).encode(),
+        "VoterView" => VoterView::InFavor.encode(),
+        "T::OrgId" => T::OrgId::from(6u32).encode(),
+        "T::Shares" => T::Shares::from(100u32).encode(),
+        "ShareClass" => ShareClass::NonVoting.encode(),
+        _ => return None,
+    };
+    Some(encoded)
+}
+
+/// The canonical value of `ty` for a field of a typed call
+fn arg<T: Bounty + Vote + Donate, V: Decode>(ty: &str) -> Result<V> {
+    let encoded = canonical_arg::<T>(ty).ok_or_else(|| {
+        crate::Error::CallVectorUnavailable {
+            call: ty.to_string(),
+            reason: "no canonical value of the type".to_string(),
+        }
+    })?;
+    Ok(V::decode(&mut &encoded[..])?)
+}
+
+fn encode<T: Runtime, C: Call<T>>(
+    metadata: &Metadata,
+    call: C,
+) -> Result<CallVector> {
+    let encoded = metadata
+        .module_with_calls(C::MODULE)?
+        .call(C::FUNCTION, call)?;
+    Ok(CallVector {
+        call: format!("{}::{}", C::MODULE, C::FUNCTION),
+        encoded: encoded.0,
+    })
+}
+
+/// The canonical typed calls, encoded like submissions with `metadata`
+pub fn call_vectors<T: Runtime + Bounty + Vote + Donate>(
+    metadata: &Metadata,
+) -> Result<Vec<CallVector>> {
+    let who: <T as System>::AccountId = arg::<T, _>("T::AccountId")?;
+    Ok(vec![
+        encode(
+            metadata,
+            PostBountyCall::<T> {
+                issue: arg::<T, _>("EncodedIssue")?,
+                info: arg::<T, _>("T::IpfsReference")?,
+                amount: arg::<T, _>("BalanceOf<T>")?,
+                asset_id: arg::<T, _>("Option<AssetIdOf<T>>")?,
+                submission_kind: arg::<T, _>("SubmissionKind")?,
+                admins: arg::<T, _>("Vec<T::AccountId>")?,
+                tags: arg::<T, _>("Vec<BountyTag>")?,
+            },
+        )?,
+        encode(
+            metadata,
+            ContributeToBountyCall::<T> {
+                bounty_id: arg::<T, _>("T::BountyId")?,
+                amount: arg::<T, _>("BalanceOf<T>")?,
+                asset_id: arg::<T, _>("Option<AssetIdOf<T>>")?,
+            },
+        )?,
+        encode(
+            metadata,
+            ApproveBountySubmissionCall::<T> {
+                submission_id: arg::<T, _>("T::SubmissionId")?,
+            },
+        )?,
+        encode(
+            metadata,
+            SubmitVoteCall::<T> {
+                vote_id: arg::<T, _>("T::VoteId")?,
+                direction: arg::<T, _>("VoterView")?,
+                justification: arg::<T, _>("Option<T::Cid>")?,
+            },
+        )?,
+        encode(
+            metadata,
+            CloseVoteCall::<T> {
+                vote_id: arg::<T, _>("T::VoteId")?,
+            },
+        )?,
+        encode(
+            metadata,
+            IssueSharesCall::<T> {
+                organization: arg::<T, _>("T::OrgId")?,
+                who: &who,
+                shares: arg::<T, _>("T::Shares")?,
+                class: arg::<T, _>("ShareClass")?,
+            },
+        )?,
+        encode(
+            metadata,
+            MakePropDonationCall::<T> {
+                org: arg::<T, _>("T::OrgId")?,
+                rem_recipient: who.clone(),
+                amt: arg::<T, _>("BalanceOf<T>")?,
+            },
+        )?,
+    ])
+}
+
+fn decoded<B: 'static, O: 'static>(
+    value: &DecodeDifferent<B, O>,
+) -> Option<&O> {
+    match value {
+        DecodeDifferent::Decoded(value) => Some(value),
+        DecodeDifferent::Encode(_) => None,
+    }
+}
+
+/// The canonical `calls` encoded from the arguments declared in `metadata`
+pub fn metadata_vectors<T: Bounty + Vote + Donate>(
+    metadata: &RuntimeMetadataPrefixed,
+    calls: &[&str],
+) -> Result<Vec<CallVector>> {
+    let modules = match &metadata.1 {
+        RuntimeMetadata::V12(metadata) => decoded(&metadata.modules),
+        _ => None,
+    };
+    calls
+        .iter()
+        .map(|call| {
+            let unavailable = |reason: &str| {
+                crate::Error::CallVectorUnavailable {
+                    call: call.to_string(),
+                    reason: reason.to_string(),
+                }
+            };
+            let modules = modules
+                .ok_or_else(|| unavailable("metadata is not version 12"))?;
+            let encoded = metadata_call::<T>(modules, call)
+                .map_err(|reason| unavailable(&reason))?;
+            Ok(CallVector {
+                call: call.to_string(),
+                encoded,
+            })
+        })
+        .collect()
+}
+
+fn metadata_call<T: Bounty + Vote + Donate>(
+    modules: &[ModuleMetadata],
+    call: &str,
+) -> std::result::Result<Vec<u8>, String> {
+    let mut name = call.splitn(2, "::");
+    let module_name = name.next().unwrap_or_default();
+    let function_name = name.next().unwrap_or_default();
+    let module = modules
+        .iter()
+        .find(|module| {
+            decoded(&module.name).map(String::as_str) == Some(module_name)
+        })
+        .ok_or("module not in the metadata")?;
+    let functions = module
+        .calls
+        .as_ref()
+        .and_then(decoded)
+        .ok_or("module has no calls")?;
+    let index = functions
+        .iter()
+        .position(|function| {
+            decoded(&function.name).map(String::as_str) == Some(function_name)
+        })
+        .ok_or("call not in the metadata")?;
+    let mut encoded = vec![module.index, index as u8];
+    let args = decoded(&functions[index].arguments).ok_or("no arguments")?;
+    for arg in args {
+        let ty = decoded(&arg.ty).ok_or("no argument type")?;
+        let value = canonical_arg::<T>(ty)
+            .ok_or_else(|| format!("no canonical value of {}", ty))?;
+        encoded.extend(value);
+    }
+    Ok(encoded)
+}
+
+/// The error of a submission to an unsupported runtime, the subxt error
+/// carries it as its message
+fn unsupported_runtime_error(spec_version: u32) -> Error {
+    Error::Other(crate::Error::UnsupportedRuntime { spec_version }.to_string())
+}
+
+/// The `Error::UnsupportedRuntime` of a submission which was refused
+/// because the typed calls were not checked against the runtime
+pub fn unsupported_runtime(
+    err: &(dyn std::error::Error + 'static),
+) -> Option<crate::Error> {
+    match err.downcast_ref::<Error>()? {
+        Error::Other(msg) => {
+            let spec_version = msg
+                .strip_prefix(crate::error::UNSUPPORTED_RUNTIME)?
+                .parse()
+                .ok()?;
+            Some(crate::Error::UnsupportedRuntime { spec_version })
+        }
+        _ => None,
+    }
+}
+
+/// Fails unless the runtime at the best block is in the supported range of
+/// `pallet` or `set_force_incompatible` was called
+pub(crate) async fn ensure_supported_runtime<T: Runtime>(
+    chain: &ChainClient<T>,
+    pallet: &str,
+) -> std::result::Result<(), Error> {
+    if *FORCE_INCOMPATIBLE.read().unwrap() {
+        return Ok(())
+    }
+    let version: RuntimeVersion = chain
+        .rpc_client()
+        .request("state_getRuntimeVersion", Params::None)
+        .await?;
+    if is_writable(pallet, &version) {
+        Ok(())
+    } else {
+        Err(unsupported_runtime_error(version.spec_version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        compat,
+        DevClient as TestClient,
+        DevNode as Node,
+        Runtime,
+    };
+
+    #[test]
+    fn vectors_are_read_in_the_layout_they_are_written_in() {
+        let vector = CallVector {
+            call: "Vote::close_vote".to_string(),
+            encoded: vec![9, 12, 5, 0, 0, 0, 0, 0, 0, 0],
+        };
+        let file = format!("# Vote\n\n{}\n", vector);
+        assert_eq!(parse_vectors(&file).unwrap(), vec![vector]);
+        assert!(parse_vectors("Vote::close_vote 0xzz").is_err());
+        assert!(parse_vectors("Vote::close_vote").is_err());
+    }
+
+    #[test]
+    fn mismatches_name_the_call_and_the_offset() {
+        assert_eq!(first_difference(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(first_difference(&[1, 2, 3], &[1, 4, 3]), Some(1));
+        assert_eq!(first_difference(&[1, 2], &[1, 2, 3]), Some(2));
+        let vector = |encoded: Vec<u8>| {
+            CallVector {
+                call: "Bounty::approve_bounty_submission".to_string(),
+                encoded,
+            }
+        };
+        let golden = [vector(vec![10, 4, 4, 0])];
+        assert!(compare_vectors(&golden, &[vector(vec![10, 4, 4, 0])]).is_ok());
+        let err =
+            compare_vectors(&golden, &[vector(vec![10, 5, 4, 0])]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bounty::approve_bounty_submission is not encoded like the \
+             runtime declares it from byte 1"
+        );
+        let missing = CallVector {
+            call: "Vote::close_vote".to_string(),
+            encoded: vec![],
+        };
+        assert!(compare_vectors(&golden, &[missing]).is_err());
+    }
+
+    #[test]
+    fn unsupported_runtimes_are_recognized() {
+        let err = unsupported_runtime_error(7);
+        assert!(matches!(
+            unsupported_runtime(&err),
+            Some(crate::Error::UnsupportedRuntime { spec_version: 7 })
+        ));
+        assert!(
+            unsupported_runtime(&Error::Other("Extrinsic Dropped".into()))
+                .is_none()
+        );
+        assert!(SUPPORTED_SPEC_VERSIONS.contains(&1));
+    }
+
+    fn version(spec_version: u32, transaction_version: u32) -> RuntimeVersion {
+        RuntimeVersion {
+            spec_name: "sun-spec".to_string(),
+            impl_name: "sun-time".to_string(),
+            spec_version,
+            impl_version: 1,
+            transaction_version,
+        }
+    }
+
+    #[test]
+    fn runtimes_are_reported_by_module() {
+        let pallets = ["Bounty", "Vote", "Org", "Donate"]
+            .iter()
+            .map(|pallet| pallet.to_string())
+            .collect::<Vec<_>>();
+        let report = Report::new(&version(1, 1), &pallets);
+        assert!(report.is_safe());
+        assert_eq!(report.features.len(), SUPPORTED_MODULES.len());
+        // storage may have changed while the calls did not
+        let report = Report::new(&version(2, 1), &pallets);
+        assert!(report
+            .features
+            .iter()
+            .all(|feature| feature.support == Support::Degraded));
+        assert!(!is_writable("Bounty", &version(2, 1)));
+        // calls changed
+        let report = Report::new(&version(2, 2), &pallets[..3]);
+        let support = report
+            .features
+            .iter()
+            .map(|feature| (feature.feature.as_str(), feature.support))
+            .collect::<Vec<_>>();
+        assert_eq!(support[0], ("bounty", Support::Unsupported));
+        assert_eq!(support[3], ("donate", Support::Unsupported));
+        assert_eq!(
+            report.mismatches().last().unwrap().reason.as_deref(),
+            Some("the runtime has no Donate pallet")
+        );
+        assert_eq!(
+            serde_json::to_value(&report.features[0]).unwrap()["support"],
+            "unsupported"
+        );
+        assert!(is_writable("Balances", &version(1, 7)));
+    }
+
+    #[async_std::test]
+    async fn the_test_runtime_is_in_the_supported_range() {
+        let node = Node::new_mock();
+        let (client, _tmp) =
+            TestClient::mock(&node, AccountKeyring::Alice).await;
+        let report = client.compatibility().await.unwrap();
+        assert!(report.is_safe(), "{:?}", report);
+    }
+
+    #[async_std::test]
+    async fn typed_calls_encode_like_the_runtime_declares() {
+        let node = Node::new_mock();
+        let (client, _tmp) =
+            TestClient::mock(&node, AccountKeyring::Alice).await;
+        let chain = client.chain_client();
+        let vectors =
+            compat::call_vectors::<Runtime>(chain.metadata()).unwrap();
+        let metadata: Bytes = chain
+            .rpc_client()
+            .request("state_getMetadata", Params::None)
+            .await
+            .unwrap();
+        let metadata =
+            RuntimeMetadataPrefixed::decode(&mut &metadata[..]).unwrap();
+        let calls = vectors
+            .iter()
+            .map(|vector| vector.call.as_str())
+            .collect::<Vec<_>>();
+        let expected =
+            compat::metadata_vectors::<Runtime>(&metadata, &calls).unwrap();
+        compat::compare_vectors(&expected, &vectors).unwrap();
+        // the vectors written by the example in ci
+        if let Ok(path) = std::env::var("CALL_VECTORS") {
+            let golden = std::fs::read_to_string(path).unwrap();
+            let golden = compat::parse_vectors(&golden).unwrap();
+            compat::compare_vectors(&golden, &vectors).unwrap();
+        }
+    }
+}
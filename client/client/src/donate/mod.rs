@@ -4,7 +4,17 @@ pub use subxt::*;
 
 use crate::{
     error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
     org::Org,
+    submit::SubmitExt,
 };
 use substrate_subxt::{
     system::System,
@@ -53,12 +63,20 @@ where
         rem_recipient: <N::Runtime as System>::AccountId,
         amt: BalanceOf<N::Runtime>,
     ) -> Result<PropDonationExecutedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .make_prop_donation_and_watch(&signer, org, rem_recipient, amt)
-            .await?
-            .prop_donation_executed()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            MakePropDonationCall {
+                org,
+                rem_recipient,
+                amt,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "make_prop_donation")
+        .await?
+        .decoded("prop_donation_executed", |r| r.prop_donation_executed())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn make_equal_donation(
         &self,
@@ -66,11 +84,21 @@ where
         rem_recipient: <N::Runtime as System>::AccountId,
         amt: BalanceOf<N::Runtime>,
     ) -> Result<EqualDonationExecutedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .make_equal_donation_and_watch(&signer, org, rem_recipient, amt)
-            .await?
-            .equal_donation_executed()?
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            MakeEqualDonationCall {
+                org,
+                rem_recipient,
+                amt,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "make_equal_donation")
+        .await?
+        .decoded("equal_donation_executed", |r| {
+                r.equal_donation_executed()
+            })?
             .ok_or_else(|| Error::EventNotFound.into())
     }
 }
@@ -0,0 +1,290 @@
+//! The era of the extrinsics the client signs
+//!
+//! subxt's `DefaultExtra` signs every extrinsic as immortal, so one which
+//! timed out stays valid for as long as its nonce is free. `EraExtra` signs
+//! with the era set with `set_transaction_era` instead, a mortal extrinsic
+//! can only be included in the `period` blocks after the block it was
+//! signed at.
+//!
+//! `SignedExtra::new` is only given the genesis hash, so the block a mortal
+//! extrinsic is born at is read by `SubmitExt::watch` right before it
+//! signs. Like the other submission settings the era applies to every
+//! client of the process, the birth block is only used for extrinsics of
+//! the chain it was read from.
+use once_cell::sync::Lazy;
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use sp_runtime::{
+    generic::Era,
+    traits::{
+        Header as _,
+        UniqueSaturatedInto,
+    },
+    transaction_validity::TransactionValidityError,
+};
+use std::{
+    fmt::Debug,
+    num::ParseIntError,
+    str::FromStr,
+    sync::RwLock,
+};
+use substrate_subxt::{
+    balances::Balances,
+    extrinsic::{
+        ChargeTransactionPayment,
+        CheckEra,
+        CheckGenesis,
+        CheckNonce,
+        CheckSpecVersion,
+        CheckTxVersion,
+        CheckWeight,
+    },
+    sp_runtime,
+    system::System,
+    Client,
+    Error,
+    Runtime,
+    SignedExtension,
+    SignedExtra,
+};
+
+/// How far back `transaction_status` looks for immortal extrinsics
+pub const IMMORTAL_LOOKBACK: u64 = 256;
+
+static ERA: Lazy<RwLock<TransactionEra>> = Lazy::new(Default::default);
+
+static CHECKPOINT: Lazy<RwLock<Option<Checkpoint>>> =
+    Lazy::new(Default::default);
+
+/// The era of the next extrinsic, with the encoded genesis hash of its
+/// chain and the encoded hash of its birth block
+struct Checkpoint {
+    genesis: Vec<u8>,
+    era: Era,
+    birth: Vec<u8>,
+}
+
+/// The era extrinsics are signed with
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionEra {
+    /// Valid until the nonce is used
+    Immortal,
+    /// Valid for `period` blocks, rounded up to a power of two between 4
+    /// and 65536
+    Mortal { period: u64 },
+}
+
+impl Default for TransactionEra {
+    fn default() -> Self {
+        Self::Immortal
+    }
+}
+
+impl FromStr for TransactionEra {
+    type Err = ParseIntError;
+
+    /// Parses `immortal` or the period of a mortal era
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "immortal" => Ok(Self::Immortal),
+            period => {
+                Ok(Self::Mortal {
+                    period: period.parse()?,
+                })
+            }
+        }
+    }
+}
+
+impl TransactionEra {
+    /// The era of an extrinsic signed at block `current` and the number of
+    /// the block it is born at
+    pub fn at(self, current: u64) -> (Era, u64) {
+        match self {
+            Self::Immortal => (Era::Immortal, 0),
+            Self::Mortal { period } => {
+                let era = Era::mortal(period, current);
+                (era, era.birth(current))
+            }
+        }
+    }
+
+    /// How many blocks back an extrinsic signed with this era can be in
+    pub fn lookback(self) -> u64 {
+        match self.at(0).0 {
+            Era::Immortal => IMMORTAL_LOOKBACK,
+            Era::Mortal(period, _) => period,
+        }
+    }
+}
+
+/// Sets the era of every following extrinsic
+pub fn set_transaction_era(era: TransactionEra) {
+    *ERA.write().unwrap() = era;
+}
+
+/// The era extrinsics are signed with
+pub fn transaction_era() -> TransactionEra {
+    *ERA.read().unwrap()
+}
+
+/// Reads the birth block of the next extrinsic of `chain`
+pub(crate) async fn checkpoint<T: Runtime>(
+    chain: &Client<T>,
+) -> Result<(), Error> {
+    let era = transaction_era();
+    if era == TransactionEra::Immortal {
+        *CHECKPOINT.write().unwrap() = None;
+        return Ok(())
+    }
+    let current: u32 = chain
+        .header(None::<T::Hash>)
+        .await?
+        .map(|header| (*header.number()).unique_saturated_into())
+        .unwrap_or_default();
+    let (era, birth) = era.at(current.into());
+    let birth = chain
+        .block_hash(Some((birth as u32).into()))
+        .await?
+        .ok_or_else(|| Error::Other(format!("Block {} not found", birth)))?;
+    *CHECKPOINT.write().unwrap() = Some(Checkpoint {
+        genesis: chain.genesis().encode(),
+        era,
+        birth: birth.encode(),
+    });
+    Ok(())
+}
+
+/// The era of the checkpoint and its birth block if it was read from the
+/// chain of `genesis`, immortal otherwise
+fn era_of<H: Clone + Decode + Encode>(
+    checkpoint: Option<&Checkpoint>,
+    genesis: &H,
+) -> (Era, H) {
+    checkpoint
+        .filter(|checkpoint| checkpoint.genesis == genesis.encode())
+        .and_then(|checkpoint| {
+            let birth = H::decode(&mut &checkpoint.birth[..]).ok()?;
+            Some((checkpoint.era, birth))
+        })
+        .unwrap_or_else(|| (Era::Immortal, genesis.clone()))
+}
+
+/// `DefaultExtra` with the era set with `set_transaction_era`
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+pub struct EraExtra<T: System> {
+    spec_version: u32,
+    tx_version: u32,
+    nonce: T::Index,
+    genesis_hash: T::Hash,
+    era: Era,
+    birth_hash: T::Hash,
+}
+
+impl<T: System + Balances + Clone + Debug + Eq + Send + Sync> SignedExtra<T>
+    for EraExtra<T>
+{
+    type Extra = (
+        CheckSpecVersion<T>,
+        CheckTxVersion<T>,
+        CheckGenesis<T>,
+        CheckEra<T>,
+        CheckNonce<T>,
+        CheckWeight<T>,
+        ChargeTransactionPayment<T>,
+    );
+
+    fn new(
+        spec_version: u32,
+        tx_version: u32,
+        nonce: T::Index,
+        genesis_hash: T::Hash,
+    ) -> Self {
+        let (era, birth_hash) =
+            era_of(CHECKPOINT.read().unwrap().as_ref(), &genesis_hash);
+        Self {
+            spec_version,
+            tx_version,
+            nonce,
+            genesis_hash,
+            era,
+            birth_hash,
+        }
+    }
+
+    fn extra(&self) -> Self::Extra {
+        (
+            CheckSpecVersion(Default::default(), self.spec_version),
+            CheckTxVersion(Default::default(), self.tx_version),
+            CheckGenesis(Default::default(), self.genesis_hash),
+            CheckEra((self.era, Default::default()), self.birth_hash),
+            CheckNonce(self.nonce),
+            CheckWeight(Default::default()),
+            ChargeTransactionPayment(Default::default()),
+        )
+    }
+}
+
+impl<T: System + Balances + Clone + Debug + Eq + Send + Sync> SignedExtension
+    for EraExtra<T>
+{
+    const IDENTIFIER: &'static str = "EraExtra";
+    type AccountId = T::AccountId;
+    type Call = ();
+    type AdditionalSigned =
+        <<Self as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned;
+    type Pre = ();
+
+    fn additional_signed(
+        &self,
+    ) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        self.extra().additional_signed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eras_parse_from_the_cli_values() {
+        assert_eq!(
+            "immortal".parse::<TransactionEra>().unwrap(),
+            TransactionEra::Immortal
+        );
+        assert_eq!(
+            "64".parse::<TransactionEra>().unwrap(),
+            TransactionEra::Mortal { period: 64 }
+        );
+        assert!("mortal".parse::<TransactionEra>().is_err());
+    }
+
+    #[test]
+    fn mortal_extrinsics_are_born_at_a_recent_block() {
+        let era = TransactionEra::Mortal { period: 100 };
+        assert_eq!(era.lookback(), 128);
+        let (mortal, birth) = era.at(1000);
+        assert!(birth <= 1000 && birth > 1000 - 128);
+        assert_eq!(mortal.birth(1000), birth);
+        assert_eq!(mortal.death(1000), birth + 128);
+        assert_eq!(TransactionEra::Immortal.at(1000), (Era::Immortal, 0));
+        assert_eq!(TransactionEra::Immortal.lookback(), IMMORTAL_LOOKBACK);
+    }
+
+    #[test]
+    fn checkpoints_of_other_chains_are_not_used() {
+        let checkpoint = Checkpoint {
+            genesis: 1u64.encode(),
+            era: Era::mortal(64, 1000),
+            birth: 990u64.encode(),
+        };
+        assert_eq!(
+            era_of(Some(&checkpoint), &1u64),
+            (Era::mortal(64, 1000), 990)
+        );
+        assert_eq!(era_of(Some(&checkpoint), &2u64), (Era::Immortal, 2));
+        assert_eq!(era_of(None, &1u64), (Era::Immortal, 1));
+    }
+}
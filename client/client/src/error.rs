@@ -1,9 +1,79 @@
 use thiserror::Error;
 
+/// The message of `Error::Timeout` up to the hash
+pub(crate) const TIMED_OUT: &str = "timed out waiting for extrinsic ";
+
+/// The message of `Error::DryRunFailed` up to the reason
+pub(crate) const DRY_RUN_FAILED: &str = "dry run failed, not submitted: ";
+
+/// The message of `Error::UnsupportedRuntime` up to the spec version
+pub(crate) const UNSUPPORTED_RUNTIME: &str =
+    "unsupported runtime spec version ";
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("event not found")]
     EventNotFound,
     #[error("Number cannot be parsed from string")]
     ParseIntError,
+    #[error("submission must reference something other than the bounty")]
+    SubmissionMustDifferFromBountyInfo,
+    #[error("backup was exported from a different chain")]
+    BackupChainMismatch,
+    #[error("backup could not be decrypted, the password may be wrong")]
+    BackupDecryptionFailed,
+    #[error("not a valid backup")]
+    InvalidBackup,
+    #[error("org handles are 1 to 32 lowercase letters, digits or dashes")]
+    InvalidOrgHandle,
+    #[error("bounty not found, neither open nor closed")]
+    BountyNotFound,
+    #[error("percent thresholds must be 1 to 100")]
+    InvalidPercentThreshold,
+    #[error("signal thresholds must not be zero")]
+    ZeroSignalThreshold,
+    #[error("the against threshold must not exceed the in favor threshold")]
+    AgainstExceedsInFavorThreshold,
+    #[error("percents of the votes cast open from a registered threshold")]
+    VotesCastThresholdNotRegistered,
+    #[error("timed out waiting for extrinsic {extrinsic_hash}")]
+    Timeout { extrinsic_hash: String },
+    #[error("dry run failed, not submitted: {reason}")]
+    DryRunFailed { reason: String },
+    #[error("no ballot was cast in the vote")]
+    NoBallotCast,
+    #[error("not a valid vote receipt")]
+    InvalidVoteReceipt,
+    #[error("vote receipt was taken on a different chain")]
+    VoteReceiptChainMismatch,
+    #[error("vote receipt is not proven by the state of its block")]
+    VoteReceiptNotProven,
+    #[error("the keystore is locked or has no key yet")]
+    KeystoreLocked,
+    #[error("unsupported runtime spec version {spec_version}")]
+    UnsupportedRuntime { spec_version: u32 },
+    #[error(
+        "{call} is not encoded like the runtime declares it from byte {offset}"
+    )]
+    CallVectorMismatch { call: String, offset: usize },
+    #[error("no vector of {call}: {reason}")]
+    CallVectorUnavailable { call: String, reason: String },
+    #[error("{account} is watch-only, it has no key to sign with")]
+    WatchOnlyCannotSign { account: String },
+    #[error("an account is already watched as {label}")]
+    WatchOnlyLabelTaken { label: String },
+    #[error("no account is watched as {label}")]
+    UnknownWatchOnly { label: String },
+    #[error("evidence of {size} bytes exceeds the limit of {max} bytes")]
+    EvidenceTooLarge { size: u64, max: u64 },
+    #[error("not a mime type: {mime}")]
+    InvalidMimeType { mime: String },
+    #[error("evidence does not match its cid or size")]
+    EvidenceCorrupt,
+    #[error("evidence {cid} is neither pinned nor served by the gateway")]
+    EvidenceUnavailable { cid: String },
+    #[error("airdrop chunks hold 1 to {max} accounts")]
+    InvalidAirdropChunk { max: usize },
+    #[error("an account is listed twice in the airdrop")]
+    DuplicateAirdropAccount,
 }
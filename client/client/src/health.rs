@@ -0,0 +1,245 @@
+//! Liveness of long-running clients over HTTP
+//!
+//! `Health::poll` reads the best and the finalized block from the node, so
+//! a lost connection or a stalled node shows in the `HealthReport`.
+//! Consumers of events count the events they received but did not handle
+//! yet with `Health::enqueued` and `Health::handled`.
+//!
+//! `serve` answers plain HTTP on a listener:
+//!
+//! - `GET /health` answers the `HealthReport` as JSON, with the status 503
+//!   while the node is unreachable
+//! - `GET /metrics` answers the health gauges and the client metrics in the
+//!   Prometheus text format, the client metrics need the `metrics` feature
+use crate::metrics::metrics_prometheus;
+use async_std::{
+    io::BufReader,
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+    prelude::*,
+    task,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    io,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+use substrate_subxt::{
+    sp_runtime::traits::{
+        Header as _,
+        UniqueSaturatedInto,
+    },
+    system::System,
+    Error,
+};
+use sunshine_client_utils::{
+    Client,
+    Node,
+};
+
+/// The liveness of a client as answered by `/health`
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct HealthReport {
+    /// Whether the last poll of the node succeeded
+    pub connected: bool,
+    /// The last finalized block seen
+    pub last_finalized: Option<u64>,
+    /// The number of blocks the finalized block is behind the best block
+    pub lag: Option<u64>,
+    /// The events received but not handled yet
+    pub queue_depth: u64,
+}
+
+/// Shared handle to the `HealthReport` of a client
+#[derive(Clone, Debug, Default)]
+pub struct Health(Arc<Mutex<HealthReport>>);
+
+impl Health {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&self) -> HealthReport {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Counts `events` as received and waiting to be handled
+    pub fn enqueued(&self, events: u64) {
+        self.0.lock().unwrap().queue_depth += events;
+    }
+
+    /// Counts `events` as handled
+    pub fn handled(&self, events: u64) {
+        let mut report = self.0.lock().unwrap();
+        report.queue_depth = report.queue_depth.saturating_sub(events);
+    }
+
+    /// Reads the best and the finalized block from the node, the client is
+    /// reported disconnected if either cannot be read
+    pub async fn poll<N: Node, C: Client<N>>(
+        &self,
+        client: &C,
+    ) -> Result<(), Error> {
+        let heads = heads::<N>(client.chain_client()).await;
+        let mut report = self.0.lock().unwrap();
+        report.connected = heads.is_ok();
+        let (best, finalized) = heads?;
+        report.last_finalized = Some(finalized);
+        report.lag = Some(best.saturating_sub(finalized));
+        Ok(())
+    }
+
+    /// The health gauges followed by the client metrics in the Prometheus
+    /// text format
+    pub fn prometheus(&self) -> String {
+        let report = self.report();
+        let mut out = String::new();
+        let mut gauge = |name: &str, value: u64| {
+            out.push_str(&format!(
+                "# TYPE sunshine_client_{0} gauge\nsunshine_client_{0} {1}\n",
+                name, value
+            ));
+        };
+        gauge("connected", report.connected as u64);
+        if let Some(finalized) = report.last_finalized {
+            gauge("finalized_block", finalized);
+        }
+        if let Some(lag) = report.lag {
+            gauge("finalized_lag_blocks", lag);
+        }
+        gauge("event_queue_depth", report.queue_depth);
+        out + &metrics_prometheus()
+    }
+}
+
+async fn heads<N: Node>(
+    chain: &substrate_subxt::Client<N::Runtime>,
+) -> Result<(u64, u64), Error> {
+    let number = |header: Option<<N::Runtime as System>::Header>| {
+        header
+            .map(|header| (*header.number()).unique_saturated_into())
+            .unwrap_or_default()
+    };
+    let best =
+        number(chain.header(None::<<N::Runtime as System>::Hash>).await?);
+    let finalized = chain.finalized_head().await?;
+    let finalized = number(chain.header(Some(finalized)).await?);
+    Ok((best, finalized))
+}
+
+/// Answers `/health` and `/metrics` on `listener` until accepting fails
+pub async fn serve(health: Health, listener: TcpListener) -> io::Result<()> {
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = stream?;
+        let health = health.clone();
+        task::spawn(async move {
+            if let Err(err) = respond(&health, stream).await {
+                tracing::debug!(error = %err, "health request failed");
+            }
+        });
+    }
+    Ok(())
+}
+
+async fn respond(health: &Health, stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request).await?;
+    // the headers are read so that closing does not reset the connection
+    let mut header = String::new();
+    while reader.read_line(&mut header).await? > 2 {
+        header.clear();
+    }
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/health")) => {
+            let report = health.report();
+            let status = if report.connected {
+                "200 OK"
+            } else {
+                "503 Service Unavailable"
+            };
+            let body = serde_json::to_string(&report)
+                .expect("the report only contains bools and numbers; qed");
+            (status, "application/json", body)
+        }
+        (Some("GET"), Some("/metrics")) => {
+            ("200 OK", "text/plain; version=0.0.4", health.prometheus())
+        }
+        _ => ("404 Not Found", "text/plain", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    (&stream).write_all(response.as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        DevClient as Client,
+        DevNode as Node,
+    };
+
+    async fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request =
+            format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[async_std::test]
+    async fn health_and_metrics_are_served() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let health = Health::new();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        task::spawn(serve(health.clone(), listener));
+
+        let response = get(port, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        health.poll(&client).await.unwrap();
+        health.enqueued(3);
+        health.handled(1);
+        let response = get(port, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let report: HealthReport = serde_json::from_str(body).unwrap();
+        assert!(report.connected);
+        assert!(report.last_finalized.is_some());
+        assert_eq!(report.queue_depth, 2);
+
+        let response = get(port, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response
+            .contains("\r\n\r\n# TYPE sunshine_client_connected gauge\n"));
+        assert!(response.contains("sunshine_client_event_queue_depth 2\n"));
+
+        let response = get(port, "/unknown").await;
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+}
@@ -2,14 +2,33 @@
 mod error;
 // export client error type for ../cli
 pub use error::Error;
+#[cfg(feature = "native")]
+pub mod backup;
 pub mod bank;
 pub mod bounty;
+pub mod chain;
+pub mod compat;
 pub mod donate;
+pub mod era;
+#[cfg(feature = "native")]
+pub mod health;
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod nonce;
 pub mod org;
+pub mod page;
+pub mod recovery;
+pub mod signer;
+pub mod submit;
 pub mod vote;
+pub mod watch;
 pub use sunshine_bounty_utils as utils;
 
-use libipld::DagCbor;
+use libipld::{
+    cid::Cid,
+    DagCbor,
+};
 use parity_scale_codec::{
     Decode,
     Encode,
@@ -20,9 +39,163 @@ pub struct TextBlock {
     pub text: String,
 }
 
+/// A vote topic or justification composed from plain text by
+/// `VoteTextClient`, with its author and the time it was written.
+///
+/// The schema belongs with the other offchain types in `sunshine-codec`
+/// but lives here until that crate is released with it. Texts pinned
+/// before it are plain `TextBlock`s, which convert into a text with only a
+/// body.
+#[derive(Debug, Default, Clone, Eq, PartialEq, DagCbor, Encode, Decode)]
+pub struct VoteText {
+    pub title: String,
+    pub body: String,
+    /// The ss58 address of the author, empty if unknown
+    pub author: String,
+    /// Unix time in milliseconds, 0 if unknown
+    pub created_at: u64,
+}
+
+impl VoteText {
+    /// Takes the first line of `text` as the title and the rest as the body
+    pub fn compose(text: &str, author: String, created_at: u64) -> Self {
+        let text = text.trim();
+        let (title, body) = match text.find('\n') {
+            Some(end) => (&text[..end], &text[end + 1..]),
+            None => (text, ""),
+        };
+        Self {
+            title: title.trim().to_string(),
+            body: body.trim().to_string(),
+            author,
+            created_at,
+        }
+    }
+
+    /// The title and the body separated by a blank line
+    pub fn text(&self) -> String {
+        match (self.title.is_empty(), self.body.is_empty()) {
+            (_, true) => self.title.clone(),
+            (true, false) => self.body.clone(),
+            (false, false) => format!("{}\n\n{}", self.title, self.body),
+        }
+    }
+}
+
+impl From<TextBlock> for VoteText {
+    fn from(block: TextBlock) -> Self {
+        Self {
+            body: block.text,
+            ..Default::default()
+        }
+    }
+}
+
+/// The profile a member attaches to their membership in an org
+#[derive(Debug, Default, Clone, DagCbor, Encode, Decode)]
+pub struct MemberProfile {
+    pub name: String,
+    pub role: Option<String>,
+    pub contact: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, DagCbor, Encode, Decode)]
 pub struct GithubIssue {
     pub issue_number: u64,
     pub repo_owner: String,
     pub repo_name: String,
 }
+
+/// The body of a submission, the issue resolving the bounty and the
+/// evidence files attached to it.
+///
+/// Only the issue is SCALE encoded, the chain keeps one submission per
+/// issue however many files are attached. Bodies pinned before attachments
+/// are plain `GithubIssue`s, read back by
+/// `EvidenceClient::resolve_submission`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, DagCbor, Encode, Decode)]
+pub struct GithubSubmission {
+    pub issue_number: u64,
+    pub repo_owner: String,
+    pub repo_name: String,
+    #[codec(skip)]
+    pub attachments: Vec<Attachment>,
+}
+
+impl GithubSubmission {
+    pub fn issue(&self) -> GithubIssue {
+        GithubIssue {
+            issue_number: self.issue_number,
+            repo_owner: self.repo_owner.clone(),
+            repo_name: self.repo_name.clone(),
+        }
+    }
+}
+
+impl From<GithubIssue> for GithubSubmission {
+    fn from(issue: GithubIssue) -> Self {
+        Self {
+            issue_number: issue.issue_number,
+            repo_owner: issue.repo_owner,
+            repo_name: issue.repo_name,
+            attachments: vec![],
+        }
+    }
+}
+
+/// An evidence file as listed by the submission it is attached to, with
+/// its type and size so apps can choose to load it
+#[derive(Debug, Clone, Eq, PartialEq, DagCbor)]
+pub struct Attachment {
+    /// The cid of the `Evidence`
+    pub evidence: Cid,
+    pub mime: String,
+    pub size: u64,
+}
+
+/// An evidence file pinned by `EvidenceClient::pin_evidence`, its bytes
+/// split in `EvidenceChunk`s
+#[derive(Debug, Clone, Eq, PartialEq, DagCbor)]
+pub struct Evidence {
+    pub mime: String,
+    pub size: u64,
+    /// The chunks in the order of their bytes
+    pub chunks: Vec<Cid>,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, DagCbor)]
+pub struct EvidenceChunk {
+    pub data: Vec<u8>,
+}
+
+/// A bounty as it stood at `block`, pinned by
+/// `BountyClient::archive_bounty`.
+///
+/// Chain values are SCALE encoded with the types of the runtime, so that
+/// tools which know the runtime can decode them without this crate. The
+/// schema belongs with the other offchain types in `sunshine-codec` but
+/// lives here until that crate is released with it.
+#[derive(Debug, Default, Clone, Eq, PartialEq, DagCbor, Encode, Decode)]
+pub struct BountyArchive {
+    /// The hash of the block the chain state was read at
+    pub block: Vec<u8>,
+    pub bounty_id: Vec<u8>,
+    /// The bounty state, taken from the closed bounties once it is closed
+    pub state: Vec<u8>,
+    pub closed: bool,
+    /// The bounty post the state references
+    pub post: Vec<u8>,
+    pub tags: Vec<u8>,
+    /// The total paid out and the number of approved submissions
+    pub payouts: Vec<u8>,
+    pub contributions: Vec<Vec<u8>>,
+    pub submissions: Vec<ArchivedSubmission>,
+}
+
+/// A submission of a `BountyArchive` with its resolved body
+#[derive(Debug, Default, Clone, Eq, PartialEq, DagCbor, Encode, Decode)]
+pub struct ArchivedSubmission {
+    pub state: Vec<u8>,
+    pub reviewer: Vec<u8>,
+    pub body: Vec<u8>,
+}
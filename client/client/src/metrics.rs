@@ -0,0 +1,367 @@
+//! Tracing spans and in-process metrics for client operations
+//!
+//! Every RPC call, extrinsic, offchain store access and event decode runs in
+//! a `client` span carrying the kind of operation and the call name. With
+//! the `metrics` feature the outcomes and latencies are also collected and
+//! can be read with `metrics_snapshot`, or in the Prometheus text format
+//! with `metrics_prometheus`.
+use std::{
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tracing::Span;
+use tracing_futures::Instrument;
+
+/// The kinds of client operations which are traced
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    /// A storage query or other request to the node
+    Rpc,
+    /// An extrinsic, submitted and watched until it is included
+    Extrinsic,
+    /// A read from the offchain store
+    OffchainRead,
+    /// A write to the offchain store
+    OffchainWrite,
+    /// Decoding an event from the result of an extrinsic
+    Decode,
+}
+
+impl Op {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Op::Rpc => "rpc",
+            Op::Extrinsic => "extrinsic",
+            Op::OffchainRead => "offchain_read",
+            Op::OffchainWrite => "offchain_write",
+            Op::Decode => "decode",
+        }
+    }
+}
+
+pub(crate) trait TracedExt<'a, T, E>:
+    Future<Output = Result<T, E>> + Send + Sized + 'a
+where
+    T: 'a,
+    E: Debug + 'a,
+{
+    /// Runs the future in a span, recording its latency and outcome
+    fn traced(
+        self,
+        op: Op,
+        call: &'static str,
+    ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>> {
+        Box::pin(async move {
+            let span = tracing::debug_span!("client", op = op.as_str(), call);
+            let start = Instant::now();
+            let result = self.instrument(span.clone()).await;
+            finish(&span, op, start.elapsed(), result.as_ref().err());
+            result
+        })
+    }
+}
+
+impl<'a, F, T, E> TracedExt<'a, T, E> for F
+where
+    F: Future<Output = Result<T, E>> + Send + 'a,
+    T: 'a,
+    E: Debug + 'a,
+{
+}
+
+pub(crate) trait DecodedExt: Sized {
+    /// Decodes an event from `self` in a span, recording the outcome
+    fn decoded<T, E: Debug>(
+        &self,
+        call: &'static str,
+        decode: impl FnOnce(&Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let span =
+            tracing::debug_span!("client", op = Op::Decode.as_str(), call);
+        let start = Instant::now();
+        let result = span.in_scope(|| decode(self));
+        finish(&span, Op::Decode, start.elapsed(), result.as_ref().err());
+        result
+    }
+}
+
+impl<T> DecodedExt for substrate_subxt::ExtrinsicSuccess<T> where
+    T: substrate_subxt::system::System
+{
+}
+
+fn finish<E: Debug>(span: &Span, op: Op, elapsed: Duration, err: Option<&E>) {
+    let elapsed_ms = elapsed.as_millis() as u64;
+    span.in_scope(|| {
+        match err {
+            Some(err) => tracing::warn!(elapsed_ms, error = ?err, "failed"),
+            None => tracing::debug!(elapsed_ms, "done"),
+        }
+    });
+    #[cfg(feature = "metrics")]
+    recorder::record(op, elapsed, err.map(error_kind));
+    #[cfg(not(feature = "metrics"))]
+    let _ = op;
+}
+
+/// The variant name of an error, e.g. `Rpc` for `Rpc(..)`
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+fn error_kind<E: Debug>(err: &E) -> String {
+    let debug = format!("{:?}", err);
+    let kind = debug
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default();
+    if kind.is_empty() {
+        "Unknown".to_string()
+    } else {
+        kind.to_string()
+    }
+}
+
+/// Returns the metrics collected so far as JSON, or `{}` when the client was
+/// built without the `metrics` feature
+pub fn metrics_snapshot() -> String {
+    #[cfg(feature = "metrics")]
+    {
+        recorder::snapshot()
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        "{}".to_string()
+    }
+}
+
+/// Returns the metrics collected so far in the Prometheus text format, or
+/// nothing when the client was built without the `metrics` feature
+pub fn metrics_prometheus() -> String {
+    #[cfg(feature = "metrics")]
+    {
+        recorder::prometheus()
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        String::new()
+    }
+}
+
+#[cfg(feature = "metrics")]
+mod recorder {
+    use super::Op;
+    use once_cell::sync::Lazy;
+    use serde::Serialize;
+    use std::{
+        collections::BTreeMap,
+        fmt::Write,
+        sync::Mutex,
+        time::Duration,
+    };
+
+    /// Upper bounds in milliseconds of the latency histogram buckets, the
+    /// last bucket counts everything slower
+    const BUCKETS_MS: [u64; 10] =
+        [5, 10, 25, 50, 100, 250, 500, 1_000, 5_000, 30_000];
+
+    static METRICS: Lazy<Mutex<Metrics>> = Lazy::new(Default::default);
+
+    #[derive(Debug, Default, Serialize)]
+    struct Histogram {
+        bounds_ms: Vec<u64>,
+        counts: Vec<u64>,
+        count: u64,
+        sum_ms: u64,
+    }
+
+    impl Histogram {
+        fn observe(&mut self, elapsed: Duration) {
+            if self.counts.is_empty() {
+                self.bounds_ms = BUCKETS_MS.to_vec();
+                self.counts = vec![0; BUCKETS_MS.len() + 1];
+            }
+            let ms = elapsed.as_millis() as u64;
+            let bucket = BUCKETS_MS
+                .iter()
+                .position(|bound| ms <= *bound)
+                .unwrap_or(BUCKETS_MS.len());
+            self.counts[bucket] += 1;
+            self.count += 1;
+            self.sum_ms += ms;
+        }
+    }
+
+    #[derive(Debug, Default, Serialize)]
+    struct Metrics {
+        extrinsics_submitted: u64,
+        failures: BTreeMap<String, u64>,
+        latency: BTreeMap<&'static str, Histogram>,
+        cache_hits: u64,
+        cache_misses: u64,
+        cache_hit_rate: Option<f64>,
+    }
+
+    pub(super) fn record(op: Op, elapsed: Duration, error: Option<String>) {
+        let mut metrics = METRICS.lock().unwrap();
+        match op {
+            Op::Extrinsic => metrics.extrinsics_submitted += 1,
+            Op::OffchainRead if error.is_some() => metrics.cache_misses += 1,
+            Op::OffchainRead => metrics.cache_hits += 1,
+            _ => {}
+        }
+        if let Some(kind) = error {
+            *metrics.failures.entry(kind).or_default() += 1;
+        }
+        metrics
+            .latency
+            .entry(op.as_str())
+            .or_default()
+            .observe(elapsed);
+        let reads = metrics.cache_hits + metrics.cache_misses;
+        if reads > 0 {
+            metrics.cache_hit_rate =
+                Some(metrics.cache_hits as f64 / reads as f64);
+        }
+    }
+
+    pub(super) fn snapshot() -> String {
+        serde_json::to_string(&*METRICS.lock().unwrap())
+            .expect("metrics only contain strings and numbers; qed")
+    }
+
+    pub(super) fn prometheus() -> String {
+        render(&METRICS.lock().unwrap())
+    }
+
+    fn render(metrics: &Metrics) -> String {
+        let mut out = String::new();
+        // writing to a string never fails
+        let _ = write_metrics(&mut out, metrics);
+        out
+    }
+
+    fn write_metrics(out: &mut String, metrics: &Metrics) -> std::fmt::Result {
+        writeln!(
+            out,
+            "# TYPE sunshine_client_extrinsics_submitted_total counter"
+        )?;
+        writeln!(
+            out,
+            "sunshine_client_extrinsics_submitted_total {}",
+            metrics.extrinsics_submitted
+        )?;
+        writeln!(out, "# TYPE sunshine_client_failures_total counter")?;
+        for (kind, count) in &metrics.failures {
+            writeln!(
+                out,
+                "sunshine_client_failures_total{{kind=\"{}\"}} {}",
+                kind, count
+            )?;
+        }
+        writeln!(out, "# TYPE sunshine_client_cache_hits_total counter")?;
+        writeln!(
+            out,
+            "sunshine_client_cache_hits_total {}",
+            metrics.cache_hits
+        )?;
+        writeln!(out, "# TYPE sunshine_client_cache_misses_total counter")?;
+        writeln!(
+            out,
+            "sunshine_client_cache_misses_total {}",
+            metrics.cache_misses
+        )?;
+        writeln!(out, "# TYPE sunshine_client_latency_milliseconds histogram")?;
+        for (op, histogram) in &metrics.latency {
+            // prometheus buckets are cumulative
+            let mut cumulative = 0;
+            for (bound, count) in
+                histogram.bounds_ms.iter().zip(&histogram.counts)
+            {
+                cumulative += count;
+                writeln!(
+                    out,
+                    "sunshine_client_latency_milliseconds_bucket{{op=\"{}\",le=\"{}\"}} {}",
+                    op, bound, cumulative
+                )?;
+            }
+            writeln!(
+                out,
+                "sunshine_client_latency_milliseconds_bucket{{op=\"{}\",le=\"+Inf\"}} {}",
+                op, histogram.count
+            )?;
+            writeln!(
+                out,
+                "sunshine_client_latency_milliseconds_sum{{op=\"{}\"}} {}",
+                op, histogram.sum_ms
+            )?;
+            writeln!(
+                out,
+                "sunshine_client_latency_milliseconds_count{{op=\"{}\"}} {}",
+                op, histogram.count
+            )?;
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_histogram_buckets() {
+            let mut histogram = Histogram::default();
+            histogram.observe(Duration::from_millis(3));
+            histogram.observe(Duration::from_millis(60));
+            histogram.observe(Duration::from_secs(60));
+            assert_eq!(histogram.counts[0], 1);
+            assert_eq!(histogram.counts[5], 1);
+            assert_eq!(histogram.counts[BUCKETS_MS.len()], 1);
+            assert_eq!(histogram.count, 3);
+            assert_eq!(histogram.sum_ms, 60_063);
+        }
+
+        #[test]
+        fn test_prometheus_buckets_are_cumulative() {
+            let mut metrics = Metrics::default();
+            let rpc = metrics.latency.entry(Op::Rpc.as_str()).or_default();
+            rpc.observe(Duration::from_millis(3));
+            rpc.observe(Duration::from_millis(60));
+            metrics.failures.insert("Rpc".to_string(), 2);
+            let text = render(&metrics);
+            assert!(text
+                .contains("sunshine_client_failures_total{kind=\"Rpc\"} 2\n"));
+            assert!(text.contains(
+                "sunshine_client_latency_milliseconds_bucket{op=\"rpc\",le=\"5\"} 1\n"
+            ));
+            assert!(text.contains(
+                "sunshine_client_latency_milliseconds_bucket{op=\"rpc\",le=\"100\"} 2\n"
+            ));
+            assert!(text.contains(
+                "sunshine_client_latency_milliseconds_bucket{op=\"rpc\",le=\"+Inf\"} 2\n"
+            ));
+            assert!(text.contains(
+                "sunshine_client_latency_milliseconds_sum{op=\"rpc\"} 63\n"
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    enum TestError {
+        Rpc(String),
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let err = TestError::Rpc("connection refused".to_string());
+        assert_eq!(error_kind(&err), "Rpc");
+        assert_eq!(error_kind(&"\"quoted\""), "Unknown");
+    }
+}
@@ -0,0 +1,730 @@
+//! A chain simulated in memory, for developing apps without a node
+//!
+//! `MockChain` keeps balances, orgs, votes and bounties in maps and runs the
+//! calls of the core flows through the checks of their pallets: posting,
+//! funding, submitting for and approving bounties, and opening, casting and
+//! tallying signal votes. A failed check returns the module error the
+//! runtime would, `Bounty::NotAuthorizedToApproveBountySubmissions` say, as a
+//! `substrate_subxt::Error`, so apps handle it like an error of the chain.
+//!
+//! Ids are handed out in order from 1 and every call appends the event its
+//! pallet would emit, so a run is the same every time. Blocks only advance
+//! when asked to. Fees, asset bounties and the existential deposit of
+//! accounts being reaped are not simulated.
+use crate::GithubIssue;
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+use substrate_subxt::{
+    Error,
+    ModuleError,
+    RuntimeError,
+};
+use sunshine_bounty_utils::{
+    traits::Apply,
+    vote::{
+        Threshold,
+        VoteOutcome,
+        VoteProgress,
+        VoteState,
+        VoterView,
+    },
+};
+use sunshine_client_utils::Result;
+
+/// The existential deposit of the runtime, kept by contributions
+pub const EXISTENTIAL_DEPOSIT: u128 = 500;
+/// The bounty constants of the runtime
+pub const MIN_DEPOSIT: u128 = 10;
+pub const MIN_CONTRIBUTION: u128 = 5;
+pub const SUBMISSION_DEPOSIT: u128 = 2;
+/// The block time of the runtime in milliseconds
+pub const BLOCK_TIME_MS: u64 = 6000;
+/// The time of block 0, fixed so the times of blocks are repeatable
+pub const GENESIS_MS: u64 = 1_600_000_000_000;
+
+/// The dev accounts the seed scenario is played by
+pub const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+pub const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+pub const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+/// The balance each account of the seed scenario starts with
+const SEED_BALANCE: u128 = 1_000_000;
+
+/// An event of a simulated call, with the names of the pallet event
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockEvent {
+    pub block: u32,
+    pub module: &'static str,
+    pub variant: &'static str,
+    /// The fields of the event, formatted
+    pub data: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockOrg {
+    pub id: u64,
+    pub name: String,
+    /// The account which may open votes for the org
+    pub supervisor: String,
+    pub members: BTreeMap<String, u64>,
+}
+
+impl MockOrg {
+    pub fn total_shares(&self) -> u64 {
+        self.members.values().sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MockBounty {
+    pub id: u64,
+    pub issue: GithubIssue,
+    pub depositer: String,
+    pub admins: Vec<String>,
+    /// The funds left to pay submissions
+    pub total: u128,
+    pub posted_at: u32,
+    pub first_submission_at: Option<u32>,
+    pub resolved_at: Option<u32>,
+    /// Whether the bounty closed after paying out nearly all of its funds
+    pub closed: bool,
+}
+
+impl MockBounty {
+    pub fn is_admin(&self, account: &str) -> bool {
+        self.admins.iter().any(|admin| admin == account)
+    }
+}
+
+/// A submission awaiting review, approved ones are removed like on chain
+#[derive(Clone, Debug)]
+pub struct MockSubmission {
+    pub id: u64,
+    pub bounty_id: u64,
+    pub issue: GithubIssue,
+    pub submitter: String,
+    pub amount: u128,
+    pub submitted_at: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockVote {
+    pub id: u64,
+    pub org_id: u64,
+    pub state: VoteState<u64, u32, ()>,
+    pub ballots: BTreeMap<String, VoterView>,
+}
+
+/// The simulated chain, see the module docs
+#[derive(Clone, Debug)]
+pub struct MockChain {
+    block: u32,
+    signer: String,
+    free: BTreeMap<String, u128>,
+    reserved: BTreeMap<String, u128>,
+    orgs: BTreeMap<u64, MockOrg>,
+    votes: BTreeMap<u64, MockVote>,
+    bounties: BTreeMap<u64, MockBounty>,
+    submissions: BTreeMap<u64, MockSubmission>,
+    contributions: BTreeMap<(u64, String), u128>,
+    /// The total paid out and the number of approved submissions by bounty
+    payouts: BTreeMap<u64, (u128, u32)>,
+    /// The issues claimed by bounties and submissions
+    issues: BTreeSet<(String, String, u64)>,
+    events: Vec<MockEvent>,
+    next_id: BTreeMap<&'static str, u64>,
+}
+
+fn rejected<T>(module: &str, error: &str) -> Result<T> {
+    Err(Error::Runtime(RuntimeError::Module(ModuleError {
+        module: module.to_string(),
+        error: error.to_string(),
+    }))
+    .into())
+}
+
+fn issue_key(issue: &GithubIssue) -> (String, String, u64) {
+    (
+        issue.repo_owner.clone(),
+        issue.repo_name.clone(),
+        issue.issue_number,
+    )
+}
+
+fn issue(repo_name: &str, issue_number: u64) -> GithubIssue {
+    GithubIssue {
+        issue_number,
+        repo_owner: "sunshine-protocol".to_string(),
+        repo_name: repo_name.to_string(),
+    }
+}
+
+impl MockChain {
+    /// An empty chain at block 1 on which `signer` signs the calls
+    pub fn new(signer: &str) -> Self {
+        Self {
+            block: 1,
+            signer: signer.to_string(),
+            free: BTreeMap::new(),
+            reserved: BTreeMap::new(),
+            orgs: BTreeMap::new(),
+            votes: BTreeMap::new(),
+            bounties: BTreeMap::new(),
+            submissions: BTreeMap::new(),
+            contributions: BTreeMap::new(),
+            payouts: BTreeMap::new(),
+            issues: BTreeSet::new(),
+            events: Vec::new(),
+            next_id: BTreeMap::new(),
+        }
+    }
+
+    /// The seed scenario, signed by Alice: two orgs, three open bounties
+    /// of which one has a submission awaiting review, and one open vote
+    /// with a ballot cast
+    pub fn seeded() -> Self {
+        let mut chain = Self::new(ALICE);
+        chain
+            .seed()
+            .expect("the seed scenario passes the checks of the pallets");
+        chain
+    }
+
+    fn seed(&mut self) -> Result<()> {
+        for account in &[ALICE, BOB, CHARLIE] {
+            self.endow(account, SEED_BALANCE);
+        }
+        let org = self
+            .register_org("sunshine", &[(ALICE, 10), (BOB, 5), (CHARLIE, 5)])?;
+        self.set_signer(BOB);
+        self.register_org("reviewers", &[(BOB, 1), (CHARLIE, 1)])?;
+        self.set_signer(ALICE);
+        self.post_bounty(issue("sunshine-node", 1), 1000, None)?;
+        let bounty = self.post_bounty(issue("sunshine-node", 2), 500, None)?;
+        self.set_signer(BOB);
+        self.post_bounty(issue("sunshine-core", 3), 250, None)?;
+        self.set_signer(CHARLIE);
+        self.contribute_to_bounty(bounty, 100)?;
+        self.submit_for_bounty(bounty, issue("sunshine-node", 4), 300)?;
+        self.set_signer(ALICE);
+        let threshold = Threshold::new(15, Some(10));
+        let vote = self.create_signal_vote(org, threshold, Some(100))?;
+        self.set_signer(BOB);
+        self.submit_vote(vote, VoterView::InFavor)?;
+        self.set_signer(ALICE);
+        Ok(())
+    }
+
+    pub fn signer(&self) -> &str {
+        &self.signer
+    }
+
+    /// Signs the following calls as `account`
+    pub fn set_signer(&mut self, account: &str) {
+        self.signer = account.to_string();
+    }
+
+    pub fn block(&self) -> u32 {
+        self.block
+    }
+
+    pub fn advance(&mut self, blocks: u32) {
+        self.block = self.block.saturating_add(blocks);
+    }
+
+    /// The time of `block` in milliseconds since the unix epoch
+    pub fn millis_at(&self, block: u32) -> u64 {
+        GENESIS_MS + u64::from(block) * BLOCK_TIME_MS
+    }
+
+    /// The events of all calls so far, oldest first
+    pub fn events(&self) -> &[MockEvent] {
+        &self.events
+    }
+
+    pub fn free_balance(&self, account: &str) -> u128 {
+        self.free.get(account).copied().unwrap_or_default()
+    }
+
+    pub fn reserved_balance(&self, account: &str) -> u128 {
+        self.reserved.get(account).copied().unwrap_or_default()
+    }
+
+    /// Mints `amount` to `account`, like a faucet
+    pub fn endow(&mut self, account: &str, amount: u128) {
+        *self.free.entry(account.to_string()).or_default() += amount;
+        self.deposit_event("Balances", "Endowed", (account, amount));
+    }
+
+    pub fn org(&self, org_id: u64) -> Option<&MockOrg> {
+        self.orgs.get(&org_id)
+    }
+
+    pub fn orgs(&self) -> impl Iterator<Item = &MockOrg> {
+        self.orgs.values()
+    }
+
+    /// Registers an org supervised by the signer
+    pub fn register_org(
+        &mut self,
+        name: &str,
+        members: &[(&str, u64)],
+    ) -> Result<u64> {
+        let id = self.next_id("org");
+        let org = MockOrg {
+            id,
+            name: name.to_string(),
+            supervisor: self.signer.clone(),
+            members: members
+                .iter()
+                .map(|(account, shares)| (account.to_string(), *shares))
+                .collect(),
+        };
+        let shares = org.total_shares();
+        self.orgs.insert(id, org);
+        let signer = self.signer.clone();
+        self.deposit_event("Org", "NewWeightedOrg", (signer, id, shares));
+        Ok(id)
+    }
+
+    pub fn bounty(&self, bounty_id: u64) -> Result<&MockBounty> {
+        match self.bounties.get(&bounty_id) {
+            Some(bounty) => Ok(bounty),
+            None => rejected("Bounty", "BountyDNE"),
+        }
+    }
+
+    pub fn open_bounties(&self, min: u128) -> Vec<&MockBounty> {
+        self.bounties
+            .values()
+            .filter(|bounty| !bounty.closed && bounty.total >= min)
+            .collect()
+    }
+
+    pub fn submission(&self, submission_id: u64) -> Result<&MockSubmission> {
+        match self.submissions.get(&submission_id) {
+            Some(submission) => Ok(submission),
+            None => rejected("Bounty", "SubmissionDNE"),
+        }
+    }
+
+    pub fn open_submissions(&self, bounty_id: u64) -> Vec<&MockSubmission> {
+        self.submissions
+            .values()
+            .filter(|submission| submission.bounty_id == bounty_id)
+            .collect()
+    }
+
+    /// The total `account` contributed to the bounty
+    pub fn contribution(&self, bounty_id: u64, account: &str) -> u128 {
+        self.contributions
+            .get(&(bounty_id, account.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Posts a bounty administered by the signer, returns its id. No asset
+    /// is accepted for bounties, they are funded in the native currency.
+    pub fn post_bounty(
+        &mut self,
+        issue: GithubIssue,
+        amount: u128,
+        asset_id: Option<u32>,
+    ) -> Result<u64> {
+        if self.issues.contains(&issue_key(&issue)) {
+            return rejected(
+                "Bounty",
+                "IssueAlreadyClaimedForBountyOrSubmission",
+            )
+        }
+        if amount < MIN_DEPOSIT {
+            return rejected("Bounty", "BountyPostMustExceedMinDeposit")
+        }
+        if asset_id.is_some() {
+            return rejected("Bounty", "AssetNotAcceptedForBounties")
+        }
+        let depositer = self.signer.clone();
+        self.withdraw(&depositer, amount, false)?;
+        let id = self.next_id("bounty");
+        self.issues.insert(issue_key(&issue));
+        self.bounties.insert(
+            id,
+            MockBounty {
+                id,
+                issue,
+                depositer: depositer.clone(),
+                admins: vec![depositer.clone()],
+                total: amount,
+                posted_at: self.block,
+                first_submission_at: None,
+                resolved_at: None,
+                closed: false,
+            },
+        );
+        self.contributions.insert((id, depositer.clone()), amount);
+        self.deposit_event("Bounty", "BountyPosted", (depositer, amount, id));
+        Ok(id)
+    }
+
+    /// Adds to the funds of a bounty, returns its new total
+    pub fn contribute_to_bounty(
+        &mut self,
+        bounty_id: u64,
+        amount: u128,
+    ) -> Result<u128> {
+        if amount < MIN_CONTRIBUTION {
+            return rejected("Bounty", "ContributionMustExceedModuleMin")
+        }
+        self.open_bounty(bounty_id)?;
+        let contributor = self.signer.clone();
+        self.withdraw(&contributor, amount, true)?;
+        *self
+            .contributions
+            .entry((bounty_id, contributor.clone()))
+            .or_default() += amount;
+        let bounty = self.bounties.get_mut(&bounty_id).expect("checked above");
+        bounty.total += amount;
+        let total = bounty.total;
+        self.deposit_event(
+            "Bounty",
+            "BountyRaiseContribution",
+            (contributor, amount, bounty_id, total),
+        );
+        Ok(total)
+    }
+
+    /// Submits for a bounty and reserves the submission deposit, returns
+    /// the submission id
+    pub fn submit_for_bounty(
+        &mut self,
+        bounty_id: u64,
+        issue: GithubIssue,
+        amount: u128,
+    ) -> Result<u64> {
+        if self.issues.contains(&issue_key(&issue)) {
+            return rejected(
+                "Bounty",
+                "IssueAlreadyClaimedForBountyOrSubmission",
+            )
+        }
+        let bounty = self.open_bounty(bounty_id)?;
+        let submitter = self.signer.clone();
+        if bounty.is_admin(&submitter) {
+            return rejected("Bounty", "AdminCannotSubmitForBounty")
+        }
+        if amount > bounty.total {
+            return rejected(
+                "Bounty",
+                "BountySubmissionExceedsTotalAvailableFunding",
+            )
+        }
+        self.withdraw(&submitter, SUBMISSION_DEPOSIT, false)?;
+        *self.reserved.entry(submitter.clone()).or_default() +=
+            SUBMISSION_DEPOSIT;
+        let id = self.next_id("submission");
+        let now = self.block;
+        self.issues.insert(issue_key(&issue));
+        self.submissions.insert(
+            id,
+            MockSubmission {
+                id,
+                bounty_id,
+                issue,
+                submitter: submitter.clone(),
+                amount,
+                submitted_at: now,
+            },
+        );
+        let bounty = self.bounties.get_mut(&bounty_id).expect("checked above");
+        bounty.first_submission_at.get_or_insert(now);
+        self.deposit_event(
+            "Bounty",
+            "BountySubmissionPosted",
+            (submitter, bounty_id, amount, id),
+        );
+        Ok(id)
+    }
+
+    /// Pays a submission from its bounty, returns what is left of the
+    /// bounty, which closes if that is too little for another submission
+    pub fn approve_bounty_submission(
+        &mut self,
+        submission_id: u64,
+    ) -> Result<u128> {
+        let submission = self.submission(submission_id)?.clone();
+        let bounty_id = submission.bounty_id;
+        let bounty = self.open_bounty(bounty_id)?;
+        if bounty.total < submission.amount {
+            return rejected(
+                "Bounty",
+                "CannotApproveSubmissionIfAmountExceedsTotalAvailable",
+            )
+        }
+        let approver = self.signer.clone();
+        if !bounty.is_admin(&approver) {
+            return rejected("Bounty", "NotAuthorizedToApproveBountySubmissions")
+        }
+        let submitter = submission.submitter;
+        *self.free.entry(submitter.clone()).or_default() +=
+            submission.amount + SUBMISSION_DEPOSIT;
+        *self.reserved.entry(submitter.clone()).or_default() -=
+            SUBMISSION_DEPOSIT;
+        self.submissions.remove(&submission_id);
+        let payouts = self.payouts.entry(bounty_id).or_default();
+        payouts.0 += submission.amount;
+        payouts.1 += 1;
+        let (total_paid, approved) = *payouts;
+        let now = self.block;
+        let bounty = self.bounties.get_mut(&bounty_id).expect("checked above");
+        bounty.total -= submission.amount;
+        bounty.resolved_at = Some(now);
+        let new_total = bounty.total;
+        let closing = new_total < MIN_CONTRIBUTION;
+        if closing {
+            bounty.closed = true;
+            bounty.total = 0;
+            let depositer = bounty.depositer.clone();
+            *self.free.entry(depositer).or_default() += new_total;
+        }
+        self.deposit_event(
+            "Bounty",
+            "BountyPaymentExecuted",
+            (bounty_id, new_total, submission_id, submission.amount),
+        );
+        if closing {
+            self.deposit_event(
+                "Bounty",
+                "BountyClosed",
+                (bounty_id, total_paid, approved),
+            );
+        }
+        Ok(new_total)
+    }
+
+    pub fn vote(&self, vote_id: u64) -> Result<&MockVote> {
+        match self.votes.get(&vote_id) {
+            Some(vote) => Ok(vote),
+            None => rejected("Vote", "NoVoteStateForOutcomeQuery"),
+        }
+    }
+
+    pub fn vote_outcome(&self, vote_id: u64) -> Result<VoteOutcome> {
+        Ok(self.vote(vote_id)?.state.outcome())
+    }
+
+    pub fn vote_progress(&self, vote_id: u64) -> Result<VoteProgress<u64>> {
+        Ok(self.vote(vote_id)?.state.progress())
+    }
+
+    /// Opens a vote of the members of an org, weighted by their shares,
+    /// returns its id
+    pub fn create_signal_vote(
+        &mut self,
+        org_id: u64,
+        threshold: Threshold<u64>,
+        duration: Option<u32>,
+    ) -> Result<u64> {
+        let creator = self.signer.clone();
+        let org = match self.orgs.get(&org_id) {
+            Some(org) if org.supervisor == creator => org,
+            _ => {
+                return rejected(
+                    "Vote",
+                    "NotAuthorizedToCreateVoteForOrganization",
+                )
+            }
+        };
+        let turnout = org.total_shares();
+        if turnout == 0 {
+            return rejected("Vote", "EmptyOrgCannotVote")
+        }
+        if threshold.in_favor() > turnout
+            || threshold.against().map_or(false, |t| t > turnout)
+        {
+            return rejected("Vote", "InputThresholdExceedsBounds")
+        }
+        let ends = duration.map(|d| self.block.saturating_add(d));
+        let state = VoteState::new(None, turnout, threshold, self.block, ends);
+        let id = self.next_id("vote");
+        self.votes.insert(
+            id,
+            MockVote {
+                id,
+                org_id,
+                state,
+                ballots: BTreeMap::new(),
+            },
+        );
+        self.deposit_event("Vote", "NewVoteStarted", (creator, id));
+        Ok(id)
+    }
+
+    /// Casts or changes the ballot of the signer with all of their shares
+    pub fn submit_vote(
+        &mut self,
+        vote_id: u64,
+        direction: VoterView,
+    ) -> Result<()> {
+        let voter = self.signer.clone();
+        let vote = match self.votes.get(&vote_id) {
+            Some(vote) => vote,
+            None => return rejected("Vote", "NoVoteStateForVoteRequest"),
+        };
+        if vote.state.outcome() == VoteOutcome::Cancelled {
+            return rejected("Vote", "VoteCancelled")
+        }
+        if vote.state.ends().map_or(false, |ends| ends < self.block) {
+            return rejected("Vote", "VotePastExpirationTimeSoVotesNotAccepted")
+        }
+        let signal = match self.orgs[&vote.org_id].members.get(&voter) {
+            Some(shares) => *shares,
+            None => return rejected("Vote", "SignalNotMintedForVoter"),
+        };
+        if signal == 0 {
+            return rejected("Vote", "ZeroSignalCannotVote")
+        }
+        let old = vote.ballots.get(&voter).copied().unwrap_or_default();
+        if old == direction {
+            return rejected(
+                "Vote",
+                "OldVoteDirectionEqualsNewVoteDirectionSoNoChange",
+            )
+        }
+        let state = match vote.state.apply(signal, old, direction) {
+            Some(state) => state,
+            None => return rejected("Vote", "VoteChangeNotSupported"),
+        };
+        let vote = self.votes.get_mut(&vote_id).expect("checked above");
+        vote.state = state;
+        vote.ballots.insert(voter.clone(), direction);
+        let block = self.block;
+        self.deposit_event(
+            "Vote",
+            "Voted",
+            (vote_id, voter, format!("{:?}", direction), block),
+        );
+        Ok(())
+    }
+
+    fn open_bounty(&self, bounty_id: u64) -> Result<&MockBounty> {
+        let bounty = self.bounty(bounty_id)?;
+        if bounty.closed {
+            return rejected("Bounty", "BountyClosed")
+        }
+        Ok(bounty)
+    }
+
+    /// Takes `amount` from the free balance of `account`, leaving at least
+    /// the existential deposit if `keep_alive`
+    fn withdraw(
+        &mut self,
+        account: &str,
+        amount: u128,
+        keep_alive: bool,
+    ) -> Result<()> {
+        let free = self.free_balance(account);
+        if free < amount {
+            return rejected("Balances", "InsufficientBalance")
+        }
+        if keep_alive && free - amount < EXISTENTIAL_DEPOSIT {
+            return rejected("Balances", "KeepAlive")
+        }
+        self.free.insert(account.to_string(), free - amount);
+        Ok(())
+    }
+
+    fn next_id(&mut self, kind: &'static str) -> u64 {
+        let id = self.next_id.entry(kind).or_default();
+        *id += 1;
+        *id
+    }
+
+    fn deposit_event(
+        &mut self,
+        module: &'static str,
+        variant: &'static str,
+        data: impl std::fmt::Debug,
+    ) {
+        self.events.push(MockEvent {
+            block: self.block,
+            module,
+            variant,
+            data: format!("{:?}", data),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_error<T: std::fmt::Debug>(result: Result<T>) -> String {
+        result
+            .unwrap_err()
+            .chain()
+            .find_map(crate::submit::module_error)
+            .unwrap()
+    }
+
+    #[test]
+    fn the_seed_scenario_is_repeatable() {
+        let chain = MockChain::seeded();
+        assert_eq!(chain.orgs().count(), 2);
+        assert_eq!(chain.open_bounties(0).len(), 3);
+        assert_eq!(chain.open_submissions(2).len(), 1);
+        assert_eq!(chain.vote_outcome(1).unwrap(), VoteOutcome::Voting);
+        assert_eq!(chain.vote_progress(1).unwrap().in_favor(), 5);
+        assert_eq!(chain.events(), MockChain::seeded().events());
+    }
+
+    #[test]
+    fn bounties_are_paid_out_by_their_admins() {
+        let mut chain = MockChain::seeded();
+        chain.set_signer(BOB);
+        assert_eq!(
+            module_error(chain.approve_bounty_submission(1)),
+            "Bounty::NotAuthorizedToApproveBountySubmissions"
+        );
+        chain.set_signer(ALICE);
+        assert_eq!(chain.approve_bounty_submission(1).unwrap(), 300);
+        assert_eq!(chain.free_balance(CHARLIE), SEED_BALANCE - 100 + 300);
+        assert_eq!(chain.reserved_balance(CHARLIE), 0);
+        assert_eq!(
+            module_error(chain.approve_bounty_submission(1)),
+            "Bounty::SubmissionDNE"
+        );
+    }
+
+    #[test]
+    fn calls_fail_like_on_chain() {
+        let mut chain = MockChain::seeded();
+        chain.set_signer(BOB);
+        assert_eq!(
+            module_error(chain.contribute_to_bounty(1, SEED_BALANCE)),
+            "Balances::InsufficientBalance"
+        );
+        assert_eq!(
+            module_error(chain.create_signal_vote(
+                1,
+                Threshold::new(1, None),
+                None
+            )),
+            "Vote::NotAuthorizedToCreateVoteForOrganization"
+        );
+        chain.set_signer("5Dave");
+        assert_eq!(
+            module_error(chain.submit_vote(1, VoterView::InFavor)),
+            "Vote::SignalNotMintedForVoter"
+        );
+        chain.set_signer(ALICE);
+        chain.submit_vote(1, VoterView::InFavor).unwrap();
+        assert_eq!(chain.vote_outcome(1).unwrap(), VoteOutcome::Approved);
+        chain.advance(101);
+        assert_eq!(
+            module_error(chain.submit_vote(1, VoterView::Against)),
+            "Vote::VotePastExpirationTimeSoVotesNotAccepted"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Local nonce tracking for concurrent extrinsic submission
+//!
+//! Without it every extrinsic reads the nonce of its account from chain
+//! state, so a second extrinsic sent before the first is included reuses
+//! the nonce and is rejected as stale. `nonced_signer` instead reserves the
+//! next nonce of the account locally while earlier extrinsics are in
+//! flight. When one of them is not included, e.g. because its nonce was
+//! stale or in the future or the pool dropped it, the local nonces are
+//! forgotten and the next reservation resynchronizes from the chain.
+//!
+//! Reservations are keyed by the signer of the client and its account, so
+//! clients shared behind a lock, like the ffi's, hand out sequential nonces
+//! and a new active key starts from its own on-chain nonce.
+use once_cell::sync::Lazy;
+use parity_scale_codec::Encode;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{
+            AtomicU8,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+};
+use substrate_subxt::{
+    sp_runtime::traits::{
+        UniqueSaturatedFrom,
+        UniqueSaturatedInto,
+    },
+    system::AccountStoreExt,
+    Error,
+    Runtime,
+    SignedPayload,
+    Signer,
+    UncheckedExtrinsic,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    Result,
+};
+
+/// Address of the signer and encoded account id
+type NonceKey = (usize, Vec<u8>);
+
+static NONCES: Lazy<Mutex<Nonces>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Nonces {
+    reservations: HashMap<NonceKey, Reservation>,
+    epochs: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Reservation {
+    /// The next nonce handed out
+    next: u64,
+    /// Extrinsics signed with a reserved nonce which are not settled yet
+    in_flight: u32,
+    /// Tells reservations apart after a resync reused their key
+    epoch: u64,
+}
+
+/// Reserves the next nonce of `key` given its current on-chain nonce,
+/// returning the nonce and the epoch of the reservation
+fn reserve(key: NonceKey, on_chain: u64) -> (u64, u64) {
+    let mut nonces = NONCES.lock().unwrap();
+    nonces.epochs += 1;
+    let epoch = nonces.epochs;
+    let r = nonces.reservations.entry(key).or_insert(Reservation {
+        next: on_chain,
+        in_flight: 0,
+        epoch,
+    });
+    r.next = r.next.max(on_chain);
+    r.in_flight += 1;
+    let nonce = r.next;
+    r.next += 1;
+    (nonce, r.epoch)
+}
+
+/// Settles a reservation, forgetting the local nonces of `key` once none
+/// are in flight or as soon as an extrinsic was not included
+fn release(key: &NonceKey, epoch: u64, included: bool) {
+    let mut nonces = NONCES.lock().unwrap();
+    let forget = match nonces.reservations.get_mut(key) {
+        Some(r) if r.epoch == epoch => {
+            r.in_flight -= 1;
+            !included || r.in_flight == 0
+        }
+        // already forgotten by another extrinsic
+        _ => false,
+    };
+    if forget {
+        nonces.reservations.remove(key);
+    }
+}
+
+const UNSETTLED: u8 = 0;
+const INCLUDED: u8 = 1;
+const NOT_INCLUDED: u8 = 2;
+
+/// The chain signer of a client with a nonce reserved for one extrinsic
+///
+/// The reservation is released when the signer is dropped. Unless the
+/// extrinsic was `settled` as included, the local nonces are forgotten.
+pub struct NoncedSigner<'a, T: Runtime> {
+    inner: &'a (dyn Signer<T> + Send + Sync),
+    nonce: T::Index,
+    key: NonceKey,
+    epoch: u64,
+    outcome: Arc<AtomicU8>,
+}
+
+impl<'a, T: Runtime> Drop for NoncedSigner<'a, T> {
+    fn drop(&mut self) {
+        let included = self.outcome.load(Ordering::SeqCst) == INCLUDED;
+        release(&self.key, self.epoch, included);
+    }
+}
+
+impl<'a, T: Runtime> Signer<T> for NoncedSigner<'a, T> {
+    fn account_id(&self) -> &T::AccountId {
+        self.inner.account_id()
+    }
+
+    fn nonce(&self) -> Option<T::Index> {
+        Some(self.nonce)
+    }
+
+    fn sign(
+        &self,
+        extrinsic: SignedPayload<T>,
+    ) -> Pin<
+        Box<
+            dyn Future<
+                    Output = std::result::Result<UncheckedExtrinsic<T>, String>,
+                > + Send
+                + Sync,
+        >,
+    > {
+        self.inner.sign(extrinsic)
+    }
+}
+
+#[async_trait]
+pub trait NonceExt<N: Node>: Client<N> {
+    /// The chain signer with the next free nonce of its account reserved
+    async fn nonced_signer(&self) -> Result<NoncedSigner<'_, N::Runtime>>;
+}
+
+#[async_trait]
+impl<N: Node, C: Client<N>> NonceExt<N> for C {
+    async fn nonced_signer(&self) -> Result<NoncedSigner<'_, N::Runtime>> {
+        let inner = self
+            .chain_signer()
+            .map_err(|_| crate::Error::KeystoreLocked)?;
+        let account = inner.account_id();
+        let on_chain = self.chain_client().account(account, None).await?.nonce;
+        let key = (inner as *const _ as *const () as usize, account.encode());
+        let (nonce, epoch) =
+            reserve(key.clone(), on_chain.unique_saturated_into());
+        Ok(NoncedSigner {
+            inner,
+            nonce: UniqueSaturatedFrom::unique_saturated_from(nonce),
+            key,
+            epoch,
+            outcome: Arc::new(AtomicU8::new(UNSETTLED)),
+        })
+    }
+}
+
+pub trait SettledExt<'a, T>:
+    Future<Output = std::result::Result<T, Error>> + Send + Sized + 'a
+where
+    T: 'a,
+{
+    /// Records whether the extrinsic signed by `signer` was included
+    fn settled<R: Runtime>(
+        self,
+        signer: &NoncedSigner<'_, R>,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<T, Error>> + Send + 'a>>
+    {
+        let outcome = signer.outcome.clone();
+        Box::pin(async move {
+            let result = self.await;
+            let included = match &result {
+                // a failed dispatch was included and used up its nonce
+                Ok(_) | Err(Error::Runtime(_)) => INCLUDED,
+                Err(_) => NOT_INCLUDED,
+            };
+            outcome.store(included, Ordering::SeqCst);
+            result
+        })
+    }
+}
+
+impl<'a, F, T> SettledExt<'a, T> for F
+where
+    F: Future<Output = std::result::Result<T, Error>> + Send + 'a,
+    T: 'a,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::task;
+    use substrate_subxt::balances::TransferCallExt;
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        DevClient as Client,
+        DevNode as Node,
+    };
+
+    #[test]
+    fn reservations_resync_from_the_chain() {
+        let key = (0, vec![1]);
+        let (first, epoch) = reserve(key.clone(), 5);
+        assert_eq!(first, 5);
+        assert_eq!(reserve(key.clone(), 5).0, 6);
+        // the chain went past the local nonces
+        assert_eq!(reserve(key.clone(), 10).0, 10);
+        // other accounts have their own nonces
+        assert_eq!(reserve((0, vec![2]), 0).0, 0);
+        // the first extrinsic was dropped, the next starts from the chain
+        release(&key, epoch, false);
+        let (next, new_epoch) = reserve(key.clone(), 6);
+        assert_eq!(next, 6);
+        // releases of the forgotten reservations do not touch the new one
+        release(&key, epoch, true);
+        release(&key, epoch, true);
+        assert_eq!(reserve(key.clone(), 6).0, 7);
+        release(&key, new_epoch, true);
+        release(&key, new_epoch, true);
+        // nothing in flight, back to the chain
+        assert_eq!(reserve(key, 7).0, 7);
+    }
+
+    #[async_std::test]
+    async fn concurrent_transfers_all_land() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let client = Arc::new(client);
+        let bob = AccountKeyring::Bob.to_account_id();
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let client = client.clone();
+                let bob = bob.clone();
+                task::spawn(async move {
+                    let signer = client.nonced_signer().await.unwrap();
+                    client
+                        .chain_client()
+                        .transfer_and_watch(&signer, &bob, 1_000)
+                        .settled(&signer)
+                        .await
+                        .map(|_| ())
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let alice = AccountKeyring::Alice.to_account_id();
+        let account =
+            client.chain_client().account(&alice, None).await.unwrap();
+        assert_eq!(account.nonce, 20);
+    }
+}
@@ -0,0 +1,418 @@
+//! Issuing voting shares to long lists of accounts, e.g. a spreadsheet of
+//! new members, in batches which survive a crash of the client.
+//!
+//! `airdrop_shares` splits the list in `batch_issue_shares` extrinsics of
+//! at most `chunk_size` accounts and submits them one after another. The
+//! outcome for each account is kept in a sled database below the client
+//! root, in a tree named after the content hash of the org and the list,
+//! so running the same airdrop again skips the accounts already issued
+//! their shares. A batch which fails to dispatch is retried account by
+//! account, so one bad row fails alone.
+//!
+//! Before each extrinsic is submitted its accounts are recorded as pending
+//! with its nonce. If the client stops before the outcome is known, the
+//! next run compares that nonce to the one of the signer on chain: a used
+//! nonce means the extrinsic may have issued the shares, so its accounts
+//! are reported `Unconfirmed` and skipped rather than issued twice.
+use crate::{
+    error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    org::{
+        BatchIssueSharesCall,
+        IssueSharesCall,
+        Org,
+        OrgClient,
+    },
+    submit::SubmitExt,
+};
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use std::{
+    collections::HashSet,
+    path::Path,
+};
+use substrate_subxt::{
+    sp_core::blake2_256,
+    sp_runtime::traits::UniqueSaturatedInto,
+    system::{
+        AccountStoreExt,
+        System,
+    },
+    Call,
+    Error as SubxtError,
+    ExtrinsicSuccess,
+    Signer,
+};
+use sunshine_bounty_utils::share::ShareClass;
+use sunshine_client_utils::{
+    async_trait,
+    Node,
+    Result,
+};
+
+/// The most accounts issued shares in one extrinsic
+pub const MAX_AIRDROP_CHUNK: usize = 100;
+
+/// The directory below the client root the progress of airdrops is kept in
+const AIRDROP_DB: &str = "airdrops";
+
+/// The key of the accounts in flight, account keys are encoded account ids
+const PENDING: &[u8] = b":pending";
+
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub enum AirdropStatus {
+    /// The issuance event was observed
+    Issued,
+    /// The issuance failed to dispatch, with the reason
+    Failed(String),
+    /// The extrinsic was submitted by a run which stopped before its
+    /// outcome was known, check the shares of the account on chain
+    Unconfirmed,
+}
+
+/// How many accounts of the list have an outcome so far
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AirdropProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+pub struct AirdropReport<T: Org> {
+    /// The content hash the progress of the airdrop is kept under
+    pub run: String,
+    pub accounts: Vec<(<T as System>::AccountId, T::Shares, AirdropStatus)>,
+}
+
+impl<T: Org> AirdropReport<T> {
+    pub fn issued(&self) -> usize {
+        self.count(|status| *status == AirdropStatus::Issued)
+    }
+    pub fn failed(&self) -> usize {
+        self.count(|status| matches!(status, AirdropStatus::Failed(_)))
+    }
+    pub fn unconfirmed(&self) -> usize {
+        self.count(|status| *status == AirdropStatus::Unconfirmed)
+    }
+    fn count(&self, f: impl Fn(&AirdropStatus) -> bool) -> usize {
+        self.accounts.iter().filter(|(_, _, s)| f(s)).count()
+    }
+}
+
+#[async_trait]
+pub trait AirdropClient<N: Node>: OrgClient<N>
+where
+    N::Runtime: Org,
+{
+    /// Issues voting shares in `org` to every account of `allocations`,
+    /// `chunk_size` accounts per extrinsic, keeping the progress below
+    /// `root` so an interrupted airdrop resumes where it stopped
+    ///
+    /// Orgs which require consent offer the shares to the accounts, which
+    /// are recorded as issued once offered.
+    async fn airdrop_shares<I>(
+        &self,
+        root: &Path,
+        org: <N::Runtime as Org>::OrgId,
+        allocations: I,
+        chunk_size: usize,
+        progress: &mut (dyn FnMut(AirdropProgress) + Send),
+    ) -> Result<AirdropReport<N::Runtime>>
+    where
+        I: IntoIterator<
+                Item = (
+                    <N::Runtime as System>::AccountId,
+                    <N::Runtime as Org>::Shares,
+                ),
+            > + Send;
+}
+
+#[async_trait]
+impl<N, C> AirdropClient<N> for C
+where
+    N: Node,
+    N::Runtime: Org,
+    C: OrgClient<N>,
+{
+    async fn airdrop_shares<I>(
+        &self,
+        root: &Path,
+        org: <N::Runtime as Org>::OrgId,
+        allocations: I,
+        chunk_size: usize,
+        progress: &mut (dyn FnMut(AirdropProgress) + Send),
+    ) -> Result<AirdropReport<N::Runtime>>
+    where
+        I: IntoIterator<
+                Item = (
+                    <N::Runtime as System>::AccountId,
+                    <N::Runtime as Org>::Shares,
+                ),
+            > + Send,
+    {
+        if chunk_size == 0 || chunk_size > MAX_AIRDROP_CHUNK {
+            return Err(Error::InvalidAirdropChunk {
+                max: MAX_AIRDROP_CHUNK,
+            }
+            .into())
+        }
+        let allocations = allocations.into_iter().collect::<Vec<_>>();
+        let mut seen = HashSet::with_capacity(allocations.len());
+        for (who, _) in &allocations {
+            if !seen.insert(who.encode()) {
+                return Err(Error::DuplicateAirdropAccount.into())
+            }
+        }
+        let run = blake2_256(&(org, &allocations).encode())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        let db = sled::open(root.join(AIRDROP_DB))?;
+        let tree = db.open_tree(&run)?;
+        resume_pending::<N, C>(self, &tree).await?;
+
+        let total = allocations.len();
+        let mut todo = Vec::new();
+        for (who, shares) in &allocations {
+            match status(&tree, who)? {
+                Some(AirdropStatus::Issued)
+                | Some(AirdropStatus::Unconfirmed) => {}
+                // failed accounts are tried again
+                _ => todo.push((who.clone(), *shares)),
+            }
+        }
+        let mut done = total - todo.len();
+        progress(AirdropProgress { done, total });
+        for chunk in todo.chunks(chunk_size) {
+            let call = BatchIssueSharesCall {
+                organization: org,
+                new_accounts: chunk,
+                class: ShareClass::Voting,
+            };
+            let accounts = chunk.iter().map(|(who, _)| who);
+            match submit_pending::<N, C, _>(self, &tree, accounts, call).await?
+            {
+                Ok(success) => {
+                    // orgs which require consent offer the shares instead
+                    let issued = success
+                        .decoded("shares_batch_issued", |r| {
+                            r.shares_batch_issued()
+                        })?
+                        .is_some()
+                        || success
+                            .decoded("shares_offered", |r| r.shares_offered())?
+                            .is_some();
+                    if !issued {
+                        return Err(Error::EventNotFound.into())
+                    }
+                    for (who, _) in chunk {
+                        record(&tree, who, &AirdropStatus::Issued)?;
+                    }
+                }
+                // some account of the chunk can't be issued shares
+                Err(SubxtError::Runtime(_)) => {
+                    for (who, shares) in chunk {
+                        let call = IssueSharesCall {
+                            organization: org,
+                            who,
+                            shares: *shares,
+                            class: ShareClass::Voting,
+                        };
+                        let status = match submit_pending::<N, C, _>(
+                            self,
+                            &tree,
+                            std::iter::once(who),
+                            call,
+                        )
+                        .await?
+                        {
+                            Ok(_) => AirdropStatus::Issued,
+                            Err(SubxtError::Runtime(err)) => {
+                                AirdropStatus::Failed(err.to_string())
+                            }
+                            // the pending accounts are resolved next run
+                            Err(err) => return Err(err.into()),
+                        };
+                        record(&tree, who, &status)?;
+                    }
+                }
+                Err(err) => return Err(err.into()),
+            }
+            tree.remove(PENDING)?;
+            tree.flush_async().await?;
+            done += chunk.len();
+            progress(AirdropProgress { done, total });
+        }
+
+        let mut accounts = Vec::with_capacity(total);
+        for (who, shares) in allocations {
+            let status = status(&tree, &who)?.unwrap_or_else(|| {
+                AirdropStatus::Failed("not submitted".to_string())
+            });
+            accounts.push((who, shares, status));
+        }
+        Ok(AirdropReport { run, accounts })
+    }
+}
+
+/// Signs `call` with the next nonce of the signer, records `accounts` as
+/// pending with it and submits it, returning the outcome of the submission
+async fn submit_pending<'a, N, C, X>(
+    client: &C,
+    tree: &sled::Tree,
+    accounts: impl Iterator<Item = &'a <N::Runtime as System>::AccountId>,
+    call: X,
+) -> Result<std::result::Result<ExtrinsicSuccess<N::Runtime>, SubxtError>>
+where
+    N: Node,
+    N::Runtime: Org,
+    C: OrgClient<N>,
+    X: Call<N::Runtime> + Clone + Send + Sync,
+{
+    let signer = client.nonced_signer().await?;
+    let nonce: u64 = signer.nonce().unwrap_or_default().unique_saturated_into();
+    let accounts = accounts.map(Encode::encode).collect::<Vec<_>>();
+    tree.insert(PENDING, (nonce, accounts).encode())?;
+    tree.flush_async().await?;
+    Ok(client
+        .watch(call, &signer)
+        .settled(&signer)
+        .traced(Op::Extrinsic, "airdrop_shares")
+        .await)
+}
+
+/// Resolves the accounts a previous run left pending, as `Unconfirmed` if
+/// their extrinsic used up its nonce and as not submitted otherwise
+async fn resume_pending<N, C>(client: &C, tree: &sled::Tree) -> Result<()>
+where
+    N: Node,
+    N::Runtime: Org,
+    C: OrgClient<N>,
+{
+    let pending = match tree.get(PENDING)? {
+        Some(pending) => pending,
+        None => return Ok(()),
+    };
+    let (nonce, accounts) = <(u64, Vec<Vec<u8>>)>::decode(&mut &pending[..])?;
+    let signer = client.chain_signer().map_err(|_| Error::KeystoreLocked)?;
+    let on_chain: u64 = client
+        .chain_client()
+        .account(signer.account_id(), None)
+        .await?
+        .nonce
+        .unique_saturated_into();
+    if on_chain > nonce {
+        for account in accounts {
+            if tree.get(&account)?.is_none() {
+                tree.insert(account, AirdropStatus::Unconfirmed.encode())?;
+            }
+        }
+    }
+    tree.remove(PENDING)?;
+    tree.flush_async().await?;
+    Ok(())
+}
+
+fn status<A: Encode>(
+    tree: &sled::Tree,
+    who: &A,
+) -> Result<Option<AirdropStatus>> {
+    Ok(match tree.get(who.encode())? {
+        Some(status) => Some(AirdropStatus::decode(&mut &status[..])?),
+        None => None,
+    })
+}
+
+fn record<A: Encode>(
+    tree: &sled::Tree,
+    who: &A,
+    status: &AirdropStatus,
+) -> Result<()> {
+    tree.insert(who.encode(), status.encode())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        DevClient as Client,
+        DevNode as Node,
+        TextBlock,
+    };
+
+    #[async_std::test]
+    async fn airdrops_resume_without_issuing_twice() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let root = TempDir::new("airdrop").unwrap();
+        let alice = AccountKeyring::Alice.to_account_id();
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                TextBlock {
+                    text: "onboarding".to_string(),
+                },
+                &[alice],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let members = (1u8..=5)
+            .map(|i| ([i; 32].into(), u64::from(i)))
+            .collect::<Vec<_>>();
+        let mut updates = Vec::new();
+        let report = client
+            .airdrop_shares(
+                root.path(),
+                org,
+                members.clone(),
+                2,
+                &mut |p: AirdropProgress| updates.push(p.done),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.issued(), 5);
+        assert_eq!(updates, vec![0, 2, 4, 5]);
+        // the same list again has nothing left to submit
+        let mut updates = Vec::new();
+        let again = client
+            .airdrop_shares(
+                root.path(),
+                org,
+                members.clone(),
+                2,
+                &mut |p: AirdropProgress| updates.push(p.done),
+            )
+            .await
+            .unwrap();
+        assert_eq!(again.run, report.run);
+        assert_eq!(again.issued(), 5);
+        assert_eq!(updates, vec![5]);
+        for (who, shares) in members {
+            let profile = client.share_profile(org, who).await.unwrap();
+            assert_eq!(profile.total(), shares);
+        }
+        assert!(client
+            .airdrop_shares(root.path(), org, Vec::new(), 0, &mut |_| {})
+            .await
+            .is_err());
+    }
+}
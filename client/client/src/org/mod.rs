@@ -1,19 +1,69 @@
+#[cfg(feature = "native")]
+mod airdrop;
 mod subxt;
 mod utils;
 
+#[cfg(feature = "native")]
+pub use airdrop::*;
 pub use subxt::*;
 pub use utils::AccountShare;
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    page::{
+        KeyHasher,
+        StoragePage,
+        DEFAULT_PAGE_SIZE,
+    },
+    submit::SubmitExt,
+    MemberProfile,
+};
+use futures::{
+    pin_mut,
+    TryStreamExt,
+};
 use libipld::{
     cache::Cache,
     cbor::DagCborCodec,
 };
+use parity_scale_codec::Encode;
+use sp_runtime::{
+    traits::AccountIdConversion,
+    ModuleId,
+};
+use std::marker::PhantomData;
 use substrate_subxt::{
+    sp_core::blake2_128,
+    sp_runtime,
     system::System,
     Runtime,
     SignedExtension,
     SignedExtra,
+    Store,
+};
+use sunshine_bounty_utils::{
+    merkle::{
+        membership_leaves,
+        merkle_proof,
+        merkle_root,
+        H256,
+    },
+    organization::{
+        org_handle,
+        OrgHandle,
+        OrgLifecycle,
+        PowerMask,
+    },
+    share::ShareClass,
 };
 use sunshine_client_utils::{
     async_trait,
@@ -23,6 +73,24 @@ use sunshine_client_utils::{
     Result,
 };
 
+/// The voting shares of a member with their proof against the membership
+/// root of the org, checked with `merkle::verify_membership_proof`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipProof<T: Org> {
+    pub root: H256,
+    pub shares: T::Shares,
+    pub proof: Vec<H256>,
+}
+
+/// Everyone listed on an org for display: the members with their share
+/// profiles, the supervisor and the observers with their role
+#[derive(Clone, Debug)]
+pub struct OrgRoster<T: Org> {
+    pub members: Vec<(<T as System>::AccountId, Prof<T>)>,
+    pub supervisor: Option<<T as System>::AccountId>,
+    pub observers: Vec<(<T as System>::AccountId, MemberProfile)>,
+}
+
 #[async_trait]
 pub trait OrgClient<N: Node>: Client<N>
 where
@@ -34,6 +102,7 @@ where
         parent_org: Option<<N::Runtime as Org>::OrgId>,
         constitution: <N::Runtime as Org>::Constitution,
         members: &[<N::Runtime as System>::AccountId],
+        handle: Option<OrgHandle>,
     ) -> Result<NewFlatOrgEvent<N::Runtime>>;
     async fn new_weighted_org(
         &self,
@@ -44,18 +113,21 @@ where
             <N::Runtime as System>::AccountId,
             <N::Runtime as Org>::Shares,
         )],
+        handle: Option<OrgHandle>,
     ) -> Result<NewWeightedOrgEvent<N::Runtime>>;
     async fn issue_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         who: <N::Runtime as System>::AccountId,
         shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
     ) -> Result<SharesIssuedEvent<N::Runtime>>;
     async fn burn_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         who: <N::Runtime as System>::AccountId,
         shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
     ) -> Result<SharesBurnedEvent<N::Runtime>>;
     async fn batch_issue_shares(
         &self,
@@ -64,6 +136,7 @@ where
             <N::Runtime as System>::AccountId,
             <N::Runtime as Org>::Shares,
         )],
+        class: ShareClass,
     ) -> Result<SharesBatchIssuedEvent<N::Runtime>>;
     async fn batch_burn_shares(
         &self,
@@ -72,7 +145,33 @@ where
             <N::Runtime as System>::AccountId,
             <N::Runtime as Org>::Shares,
         )],
+        class: ShareClass,
     ) -> Result<SharesBatchBurnedEvent<N::Runtime>>;
+    async fn convert_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        from: ShareClass,
+        shares: <N::Runtime as Org>::Shares,
+    ) -> Result<SharesConvertedEvent<N::Runtime>>;
+    async fn non_voting_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<<N::Runtime as Org>::Shares>;
+    async fn set_org_handle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        handle: Option<OrgHandle>,
+    ) -> Result<OrgHandleSetEvent<N::Runtime>>;
+    async fn org_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>>;
+    async fn org_handle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Option<OrgHandle>>;
     async fn org_parent_child(
         &self,
         parent: <N::Runtime as Org>::OrgId,
@@ -106,6 +205,232 @@ where
             )>,
         >,
     >;
+    async fn set_my_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MemberMetadataUpdatedEvent<N::Runtime>>;
+    async fn set_member_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MemberMetadataUpdatedEvent<N::Runtime>>;
+    async fn member_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<Option<MemberProfile>>;
+    async fn propose_spend(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        beneficiary: <N::Runtime as System>::AccountId,
+        amount: BalanceOf<N::Runtime>,
+        reason: <N::Runtime as Org>::Constitution,
+    ) -> Result<SpendProposedEvent<N::Runtime>>;
+    async fn execute_spend(
+        &self,
+        proposal_id: u32,
+    ) -> Result<SpendExecutedEvent<N::Runtime>>;
+    async fn spend_proposal(
+        &self,
+        proposal_id: u32,
+    ) -> Result<SpendProp<N::Runtime>>;
+    async fn org_account(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<<N::Runtime as System>::AccountId>;
+    async fn guard_org_actions(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        guards: u8,
+        issuance_limit: <N::Runtime as Org>::Shares,
+    ) -> Result<OrgActionsGuardedEvent<N::Runtime>>;
+    async fn set_action_threshold(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        threshold: <N::Runtime as Org>::SpendThresholdId,
+    ) -> Result<ActionThresholdSetEvent<N::Runtime>>;
+    async fn propose_org_action(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        action: OrgActionOf<N::Runtime>,
+    ) -> Result<OrgActionProposedEvent<N::Runtime>>;
+    async fn execute_org_action(
+        &self,
+        proposal_id: u32,
+        action: OrgActionOf<N::Runtime>,
+    ) -> Result<OrgActionExecutedEvent<N::Runtime>>;
+    async fn org_action_proposal(
+        &self,
+        proposal_id: u32,
+    ) -> Result<ActionProp<N::Runtime>>;
+    async fn grant_emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        power_mask: u8,
+        duration_blocks: u32,
+        proposal_id: u32,
+    ) -> Result<EmergencyPowerGrantedEvent<N::Runtime>>;
+    async fn revoke_emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+    ) -> Result<EmergencyPowerRevokedEvent<N::Runtime>>;
+    async fn emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<(PowerMask, <N::Runtime as System>::BlockNumber)>;
+    /// Emits the membership root of the org in an event, caching it on chain
+    async fn publish_membership_root(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<MembershipRootPublishedEvent<N::Runtime>>;
+    /// The proof of the voting shares of `account`, None if it is not a
+    /// member of the org
+    async fn membership_proof(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<MembershipProof<N::Runtime>>>;
+    async fn set_application_policy(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        deposit: BalanceOf<N::Runtime>,
+        cooldown: <N::Runtime as System>::BlockNumber,
+    ) -> Result<ApplicationPolicySetEvent<N::Runtime>>;
+    /// The deposit reserved from applicants to the org and the blocks an
+    /// account waits between its applications
+    async fn application_policy(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<(BalanceOf<N::Runtime>, <N::Runtime as System>::BlockNumber)>;
+    /// Applies to join the org, introducing the applicant with `profile`
+    async fn apply_for_membership(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MembershipAppliedEvent<N::Runtime>>;
+    async fn withdraw_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<ApplicationWithdrawnEvent<N::Runtime>>;
+    async fn approve_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: <N::Runtime as System>::AccountId,
+        shares: <N::Runtime as Org>::Shares,
+    ) -> Result<ApplicationApprovedEvent<N::Runtime>>;
+    /// Rejects the application, forfeiting its deposit to the org treasury
+    /// if it is `spam`
+    async fn reject_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: <N::Runtime as System>::AccountId,
+        spam: bool,
+    ) -> Result<ApplicationRejectedEvent<N::Runtime>>;
+    /// The deposit reserved for the pending application of `applicant`,
+    /// None if they have none
+    async fn application_deposit(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<BalanceOf<N::Runtime>>>;
+    async fn pending_applications(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<u32>;
+    /// Adds `who` as an observer of the org with the role in `profile`, or
+    /// updates its role. Observers hold no shares.
+    async fn add_observer(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+        profile: MemberProfile,
+    ) -> Result<ObserverAddedEvent<N::Runtime>>;
+    async fn remove_observer(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<ObserverRemovedEvent<N::Runtime>>;
+    async fn observers(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<(<N::Runtime as System>::AccountId, MemberProfile)>>;
+    async fn org_roster(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgRoster<N::Runtime>>;
+    /// Registers a child of `source_org` supervised by the signer with the
+    /// members kept by `filter`, holding their voting shares or one share
+    /// each if `equal_shares`
+    async fn fork_org(
+        &self,
+        source_org: <N::Runtime as Org>::OrgId,
+        constitution: <N::Runtime as Org>::Constitution,
+        filter: MemberFilterOf<N::Runtime>,
+        equal_shares: bool,
+    ) -> Result<OrgForkedEvent<N::Runtime>>;
+    /// Freezes the org, its open votes are cancelled and no new ones open
+    /// until it is unfrozen
+    async fn freeze_org(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgFrozenEvent<N::Runtime>>;
+    async fn unfreeze_org(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgUnfrozenEvent<N::Runtime>>;
+    async fn org_lifecycle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgLifecycle>;
+    /// Requires the recipients of shares issued by the org to accept them
+    /// before they hold them, or issues them at once again
+    async fn set_share_consent(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        required: bool,
+    ) -> Result<ShareConsentSetEvent<N::Runtime>>;
+    async fn requires_share_consent(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<bool>;
+    /// Issues shares in an org which requires consent, which offers them to
+    /// `who` as a grant until they accept it
+    async fn offer_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
+    ) -> Result<SharesOfferedEvent<N::Runtime>>;
+    async fn accept_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        grant_id: u32,
+    ) -> Result<ShareGrantAcceptedEvent<N::Runtime>>;
+    async fn decline_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        grant_id: u32,
+    ) -> Result<ShareGrantDeclinedEvent<N::Runtime>>;
+    /// Cancels the grant offered to `who`, which the supervisor may do at
+    /// any time and anyone once it expired
+    async fn cancel_share_grant(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+        grant_id: u32,
+    ) -> Result<ShareGrantCancelledEvent<N::Runtime>>;
+    /// The grants offered to `who` by any org by grant id, including the
+    /// expired ones nobody cancelled yet
+    async fn share_grants(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Vec<(u32, ShareGrantOf<N::Runtime>)>>;
 }
 
 #[async_trait]
@@ -115,13 +440,14 @@ where
     N::Runtime: Org,
     <<<N::Runtime as Runtime>::Extra as SignedExtra<N::Runtime>>::Extra as SignedExtension>::AdditionalSigned:
         Send + Sync,
-    <N::Runtime as Org>::Cid: From<libipld::cid::Cid>,
+    <N::Runtime as Org>::Cid:
+        From<libipld::cid::Cid> + Into<libipld::cid::Cid>,
     C: Client<N>,
     C::OffchainClient: libipld::cache::Cache<
         OffchainConfig<N>,
         DagCborCodec,
         <N::Runtime as Org>::Constitution,
-    >,
+    > + libipld::cache::Cache<OffchainConfig<N>, DagCborCodec, MemberProfile>,
 {
     async fn new_flat_org(
         &self,
@@ -129,20 +455,29 @@ where
         parent_org: Option<<N::Runtime as Org>::OrgId>,
         constitution: <N::Runtime as Org>::Constitution,
         members: &[<N::Runtime as System>::AccountId],
+        handle: Option<OrgHandle>,
     ) -> Result<NewFlatOrgEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        let constitution = self.offchain_client().insert(constitution).await?;
-        self.chain_client()
-            .new_flat_org_and_watch(
-                &signer,
+        let signer = self.nonced_signer().await?;
+        let constitution = self
+            .offchain_client()
+            .insert(constitution)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            NewFlatOrgCall {
                 sudo,
                 parent_org,
-                constitution.into(),
+                constitution: constitution.into(),
                 members,
-            )
-            .await?
-            .new_flat_org()?
-            .ok_or_else(|| Error::EventNotFound.into())
+                handle,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "new_flat_org")
+        .await?
+        .decoded("new_flat_org", |r| r.new_flat_org())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn new_weighted_org(
         &self,
@@ -150,70 +485,191 @@ where
         parent_org: Option<<N::Runtime as Org>::OrgId>,
         constitution: <N::Runtime as Org>::Constitution,
         weighted_members: &[(<N::Runtime as System>::AccountId, <N::Runtime as Org>::Shares)],
+        handle: Option<OrgHandle>,
     ) -> Result<NewWeightedOrgEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        let constitution = self.offchain_client().insert(constitution).await?;
-        self.chain_client()
-            .new_weighted_org_and_watch(
-                &signer,
+        let signer = self.nonced_signer().await?;
+        let constitution = self
+            .offchain_client()
+            .insert(constitution)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            NewWeightedOrgCall {
                 sudo,
                 parent_org,
-                constitution.into(),
+                constitution: constitution.into(),
                 weighted_members,
-            )
-            .await?
-            .new_weighted_org()?
-            .ok_or_else(|| Error::EventNotFound.into())
+                handle,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "new_weighted_org")
+        .await?
+        .decoded("new_weighted_org", |r| r.new_weighted_org())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn issue_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         who: <N::Runtime as System>::AccountId,
         shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
     ) -> Result<SharesIssuedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .issue_shares_and_watch(&signer, org, &who, shares)
-            .await?
-            .shares_issued()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            IssueSharesCall {
+                organization: org,
+                who: &who,
+                shares,
+                class,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "issue_shares")
+        .await?
+        .decoded("shares_issued", |r| r.shares_issued())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn burn_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         who: <N::Runtime as System>::AccountId,
         shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
     ) -> Result<SharesBurnedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .burn_shares_and_watch(&signer, org, &who, shares)
-            .await?
-            .shares_burned()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            BurnSharesCall {
+                organization: org,
+                who: &who,
+                shares,
+                class,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "burn_shares")
+        .await?
+        .decoded("shares_burned", |r| r.shares_burned())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn batch_issue_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         new_accounts: &[(<N::Runtime as System>::AccountId, <N::Runtime as Org>::Shares)],
+        class: ShareClass,
     ) -> Result<SharesBatchIssuedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .batch_issue_shares_and_watch(&signer, org, new_accounts)
-            .await?
-            .shares_batch_issued()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            BatchIssueSharesCall {
+                organization: org,
+                new_accounts,
+                class,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "batch_issue_shares")
+        .await?
+        .decoded("shares_batch_issued", |r| r.shares_batch_issued())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn batch_burn_shares(
         &self,
         org: <N::Runtime as Org>::OrgId,
         old_accounts: &[(<N::Runtime as System>::AccountId, <N::Runtime as Org>::Shares)],
+        class: ShareClass,
     ) -> Result<SharesBatchBurnedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
-        self.chain_client()
-            .batch_burn_shares_and_watch(&signer, org, old_accounts)
-            .await?
-            .shares_batch_burned()?
-            .ok_or_else(|| Error::EventNotFound.into())
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            BatchBurnSharesCall {
+                organization: org,
+                old_accounts,
+                class,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "batch_burn_shares")
+        .await?
+        .decoded("shares_batch_burned", |r| r.shares_batch_burned())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn convert_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        from: ShareClass,
+        shares: <N::Runtime as Org>::Shares,
+    ) -> Result<SharesConvertedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ConvertSharesCall {
+                organization: org,
+                who: &who,
+                from,
+                shares,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "convert_shares")
+        .await?
+        .decoded("shares_converted", |r| r.shares_converted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn non_voting_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<<N::Runtime as Org>::Shares> {
+        Ok(self
+            .chain_client()
+            .non_voting_shares(org, &account, None)
+            .traced(Op::Rpc, "non_voting_shares")
+            .await?)
+    }
+    async fn set_org_handle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        handle: Option<OrgHandle>,
+    ) -> Result<OrgHandleSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetOrgHandleCall {
+                organization: org,
+                handle,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_org_handle")
+        .await?
+        .decoded("org_handle_set", |r| r.org_handle_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn org_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<Option<<N::Runtime as Org>::OrgId>> {
+        let handle = org_handle(handle).ok_or(Error::InvalidOrgHandle)?;
+        Ok(self
+            .chain_client()
+            .handle_to_org(handle, None)
+            .traced(Op::Rpc, "handle_to_org")
+            .await?)
+    }
+    async fn org_handle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Option<OrgHandle>> {
+        Ok(self
+            .chain_client()
+            .org_handles(org, None)
+            .traced(Op::Rpc, "org_handles")
+            .await?)
     }
     async fn org_parent_child(
         &self,
@@ -226,17 +682,25 @@ where
             .is_ok()
     }
     async fn org(&self, org: <N::Runtime as Org>::OrgId) -> Result<OrgState<N::Runtime>> {
-        Ok(self.chain_client().orgs(org, None).await?)
+        Ok(self.chain_client().orgs(org, None).traced(Op::Rpc, "orgs").await?)
     }
     async fn share_profile(
         &self,
         org: <N::Runtime as Org>::OrgId,
         account: <N::Runtime as System>::AccountId,
     ) -> Result<Prof<N::Runtime>> {
-        Ok(self.chain_client().members(org, &account, None).await?)
+        Ok(self
+            .chain_client()
+            .members(org, &account, None)
+            .traced(Op::Rpc, "members")
+            .await?)
     }
     async fn org_relations(&self) -> Result<Vec<Relacion<N::Runtime>>> {
-        let mut relations = self.chain_client().org_tree_iter(None).await?;
+        let mut relations = self
+            .chain_client()
+            .org_tree_iter(None)
+            .traced(Op::Rpc, "org_tree_iter")
+            .await?;
         let mut org_relations = Vec::<Relacion<N::Runtime>>::new();
         while let Some((_, r)) = relations.next().await? {
             org_relations.push(r)
@@ -247,7 +711,11 @@ where
         &self,
         org: <N::Runtime as Org>::OrgId,
     ) -> Result<Option<Vec<(<N::Runtime as System>::AccountId, Prof<N::Runtime>)>>> {
-        let mut members = self.chain_client().members_iter(None).await?;
+        let mut members = self
+            .chain_client()
+            .members_iter(None)
+            .traced(Op::Rpc, "members_iter")
+            .await?;
         let mut members_for_org = Vec::new();
         while let Some((_, profile)) = members.next().await? {
             if profile.id().0 == org {
@@ -264,12 +732,19 @@ where
         &self,
         account: <N::Runtime as System>::AccountId,
     ) -> Result<Option<Vec<(<N::Runtime as Org>::OrgId, Prof<N::Runtime>, OrgState<N::Runtime>)>>> {
-        let mut members = self.chain_client().members_iter(None).await?;
+        let mut members = self
+            .chain_client()
+            .members_iter(None)
+            .traced(Op::Rpc, "members_iter")
+            .await?;
         let mut orgs_for_account = Vec::new();
         while let Some((_, profile)) = members.next().await? {
             if profile.id().1 == account {
-                let org_state =
-                    self.chain_client().orgs(profile.id().0, None).await?;
+                let org_state = self
+                    .chain_client()
+                    .orgs(profile.id().0, None)
+                    .traced(Op::Rpc, "orgs")
+                    .await?;
                 orgs_for_account.push((profile.id().0, profile, org_state));
             }
         }
@@ -279,6 +754,764 @@ where
             Ok(Some(orgs_for_account))
         }
     }
+    async fn set_my_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MemberMetadataUpdatedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let metadata = if let Some(p) = profile {
+            Some(
+                self.offchain_client()
+                    .insert(p)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            SetMyMetadataCall {
+                organization: org,
+                metadata,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_my_metadata")
+        .await?
+        .decoded("member_metadata_updated", |r| {
+                r.member_metadata_updated()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_member_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MemberMetadataUpdatedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let metadata = if let Some(p) = profile {
+            Some(
+                self.offchain_client()
+                    .insert(p)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            SetMemberMetadataCall {
+                organization: org,
+                who: &who,
+                metadata,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_member_metadata")
+        .await?
+        .decoded("member_metadata_updated", |r| {
+                r.member_metadata_updated()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn member_profile(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: <N::Runtime as System>::AccountId,
+    ) -> Result<Option<MemberProfile>> {
+        let metadata = self
+            .chain_client()
+            .member_metadata(org, &account, None)
+            .traced(Op::Rpc, "member_metadata")
+            .await?;
+        if let Some(cid) = metadata {
+            Ok(Some(
+                self.offchain_client()
+                    .get(&cid.into())
+                    .traced(Op::OffchainRead, "get")
+                    .await?,
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+    async fn propose_spend(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        beneficiary: <N::Runtime as System>::AccountId,
+        amount: BalanceOf<N::Runtime>,
+        reason: <N::Runtime as Org>::Constitution,
+    ) -> Result<SpendProposedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let reason = self
+            .offchain_client()
+            .insert(reason)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            ProposeSpendCall {
+                organization: org,
+                beneficiary: &beneficiary,
+                amount,
+                reason: reason.into(),
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "propose_spend")
+        .await?
+        .decoded("spend_proposed", |r| r.spend_proposed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn execute_spend(
+        &self,
+        proposal_id: u32,
+    ) -> Result<SpendExecutedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ExecuteSpendCall {
+                proposal_id,
+                _runtime: PhantomData,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "execute_spend")
+        .await?
+        .decoded("spend_executed", |r| r.spend_executed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn spend_proposal(
+        &self,
+        proposal_id: u32,
+    ) -> Result<SpendProp<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .spend_proposals(proposal_id, None)
+            .traced(Op::Rpc, "spend_proposals")
+            .await?)
+    }
+    async fn org_account(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<<N::Runtime as System>::AccountId> {
+        let treasury: ModuleId = self
+            .chain_client()
+            .metadata()
+            .module("Org")?
+            .constant("OrgTreasury")?
+            .value()?;
+        Ok(treasury.into_sub_account(org))
+    }
+    async fn guard_org_actions(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        guards: u8,
+        issuance_limit: <N::Runtime as Org>::Shares,
+    ) -> Result<OrgActionsGuardedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            GuardOrgActionsCall {
+                organization: org,
+                guards,
+                issuance_limit,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "guard_org_actions")
+        .await?
+        .decoded("org_actions_guarded", |r| r.org_actions_guarded())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_action_threshold(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        threshold: <N::Runtime as Org>::SpendThresholdId,
+    ) -> Result<ActionThresholdSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetActionThresholdCall {
+                organization: org,
+                threshold,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_action_threshold")
+        .await?
+        .decoded("action_threshold_set", |r| r.action_threshold_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn propose_org_action(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        action: OrgActionOf<N::Runtime>,
+    ) -> Result<OrgActionProposedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ProposeOrgActionCall {
+                organization: org,
+                action,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "propose_org_action")
+        .await?
+        .decoded("org_action_proposed", |r| r.org_action_proposed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn execute_org_action(
+        &self,
+        proposal_id: u32,
+        action: OrgActionOf<N::Runtime>,
+    ) -> Result<OrgActionExecutedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ExecuteOrgActionCall {
+                proposal_id,
+                action,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "execute_org_action")
+        .await?
+        .decoded("org_action_executed", |r| r.org_action_executed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn org_action_proposal(
+        &self,
+        proposal_id: u32,
+    ) -> Result<ActionProp<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .org_action_proposals(proposal_id, None)
+            .traced(Op::Rpc, "org_action_proposals")
+            .await?)
+    }
+    async fn grant_emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        power_mask: u8,
+        duration_blocks: u32,
+        proposal_id: u32,
+    ) -> Result<EmergencyPowerGrantedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            GrantEmergencyPowerCall {
+                organization: org,
+                who,
+                power_mask,
+                duration_blocks,
+                proposal_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "grant_emergency_power")
+        .await?
+        .decoded("emergency_power_granted", |r| r.emergency_power_granted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn revoke_emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+    ) -> Result<EmergencyPowerRevokedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(RevokeEmergencyPowerCall { organization: org, who }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "revoke_emergency_power")
+            .await?
+            .decoded("emergency_power_revoked", |r| {
+                r.emergency_power_revoked()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn emergency_power(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<(PowerMask, <N::Runtime as System>::BlockNumber)> {
+        Ok(self
+            .chain_client()
+            .emergency_powers(org, who, None)
+            .traced(Op::Rpc, "emergency_powers")
+            .await?)
+    }
+    async fn publish_membership_root(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<MembershipRootPublishedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(PublishMembershipRootCall { organization: org }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "publish_membership_root")
+            .await?
+            .decoded("membership_root_published", |r| {
+                r.membership_root_published()
+            })?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn membership_proof(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        account: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<MembershipProof<N::Runtime>>> {
+        let mut members = self
+            .org_members(org)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(who, profile)| (who, profile.total()))
+            .collect::<Vec<_>>();
+        // the leaves are ordered by account, like the pallet's
+        members.sort_by(|a, b| a.0.cmp(&b.0));
+        let index = match members.iter().position(|(who, _)| who == account) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let shares = members[index].1;
+        let leaves = membership_leaves(members);
+        Ok(merkle_proof(&leaves, index).map(|proof| {
+            MembershipProof {
+                root: merkle_root(&leaves),
+                shares,
+                proof,
+            }
+        }))
+    }
+    async fn set_application_policy(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        deposit: BalanceOf<N::Runtime>,
+        cooldown: <N::Runtime as System>::BlockNumber,
+    ) -> Result<ApplicationPolicySetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetApplicationPolicyCall {
+                organization: org,
+                deposit,
+                cooldown,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_application_policy")
+        .await?
+        .decoded("application_policy_set", |r| r.application_policy_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn application_policy(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<(BalanceOf<N::Runtime>, <N::Runtime as System>::BlockNumber)>
+    {
+        Ok(self
+            .chain_client()
+            .application_policies(org, None)
+            .traced(Op::Rpc, "application_policies")
+            .await?)
+    }
+    async fn apply_for_membership(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        profile: Option<MemberProfile>,
+    ) -> Result<MembershipAppliedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let application = if let Some(p) = profile {
+            Some(
+                self.offchain_client()
+                    .insert(p)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            ApplyForMembershipCall {
+                organization: org,
+                application,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "apply_for_membership")
+        .await?
+        .decoded("membership_applied", |r| r.membership_applied())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn withdraw_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<ApplicationWithdrawnEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(WithdrawApplicationCall { organization: org }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "withdraw_application")
+            .await?
+            .decoded("application_withdrawn", |r| r.application_withdrawn())?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn approve_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: <N::Runtime as System>::AccountId,
+        shares: <N::Runtime as Org>::Shares,
+    ) -> Result<ApplicationApprovedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ApproveApplicationCall {
+                organization: org,
+                applicant,
+                shares,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "approve_application")
+        .await?
+        .decoded("application_approved", |r| r.application_approved())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn reject_application(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: <N::Runtime as System>::AccountId,
+        spam: bool,
+    ) -> Result<ApplicationRejectedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            RejectApplicationCall {
+                organization: org,
+                applicant,
+                spam,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "reject_application")
+        .await?
+        .decoded("application_rejected", |r| r.application_rejected())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn application_deposit(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        applicant: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<BalanceOf<N::Runtime>>> {
+        let application = self
+            .chain_client()
+            .applications(org, applicant, None)
+            .traced(Op::Rpc, "applications")
+            .await?;
+        Ok(application.map(|(_, deposit)| deposit))
+    }
+    async fn pending_applications(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<u32> {
+        Ok(self
+            .chain_client()
+            .pending_applications(org, None)
+            .traced(Op::Rpc, "pending_applications")
+            .await?)
+    }
+    async fn add_observer(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+        profile: MemberProfile,
+    ) -> Result<ObserverAddedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let role = self
+            .offchain_client()
+            .insert(profile)
+            .traced(Op::OffchainWrite, "insert")
+            .await?
+            .into();
+        self.watch(
+            AddObserverCall {
+                organization: org,
+                who,
+                role,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "add_observer")
+        .await?
+        .decoded("observer_added", |r| r.observer_added())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn remove_observer(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<ObserverRemovedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            RemoveObserverCall {
+                organization: org,
+                who,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "remove_observer")
+        .await?
+        .decoded("observer_removed", |r| r.observer_removed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn observers(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<(<N::Runtime as System>::AccountId, MemberProfile)>> {
+        let chain = self.chain_client();
+        // the observers of the org are keyed by the blake2_128 hash and
+        // encoding of the org id followed by those of the observer
+        let mut prefix =
+            ObserversStore::<N::Runtime>::prefix(chain.metadata())?;
+        let org = org.encode();
+        prefix.0.extend(&blake2_128(&org));
+        prefix.0.extend(org);
+        let pages = StoragePage::<
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Org>::Cid,
+        >::new(prefix, KeyHasher::Blake2_128Concat, DEFAULT_PAGE_SIZE);
+        let roles = pages.stream(chain, None);
+        pin_mut!(roles);
+        let mut observers = Vec::new();
+        while let Some((who, role)) = roles.try_next().await? {
+            let profile = self
+                .offchain_client()
+                .get(&role.into())
+                .traced(Op::OffchainRead, "get")
+                .await?;
+            observers.push((who, profile));
+        }
+        Ok(observers)
+    }
+    async fn org_roster(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgRoster<N::Runtime>> {
+        let supervisor = self.org(org).await?.sudo();
+        Ok(OrgRoster {
+            members: self.org_members(org).await?.unwrap_or_default(),
+            supervisor,
+            observers: self.observers(org).await?,
+        })
+    }
+    async fn fork_org(
+        &self,
+        source_org: <N::Runtime as Org>::OrgId,
+        constitution: <N::Runtime as Org>::Constitution,
+        filter: MemberFilterOf<N::Runtime>,
+        equal_shares: bool,
+    ) -> Result<OrgForkedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let constitution = self
+            .offchain_client()
+            .insert(constitution)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            ForkOrgCall {
+                source_org,
+                constitution: constitution.into(),
+                filter,
+                equal_shares,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "fork_org")
+        .await?
+        .decoded("org_forked", |r| r.org_forked())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn freeze_org(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgFrozenEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(FreezeOrgCall { organization: org }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "freeze_org")
+            .await?
+            .decoded("org_frozen", |r| r.org_frozen())?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn unfreeze_org(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgUnfrozenEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(UnfreezeOrgCall { organization: org }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "unfreeze_org")
+            .await?
+            .decoded("org_unfrozen", |r| r.org_unfrozen())?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn org_lifecycle(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<OrgLifecycle> {
+        Ok(self
+            .chain_client()
+            .lifecycles(org, None)
+            .traced(Op::Rpc, "lifecycles")
+            .await?)
+    }
+    async fn set_share_consent(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        required: bool,
+    ) -> Result<ShareConsentSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetShareConsentCall {
+                organization: org,
+                required,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_share_consent")
+        .await?
+        .decoded("share_consent_set", |r| r.share_consent_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn requires_share_consent(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<bool> {
+        Ok(self
+            .chain_client()
+            .share_consent(org, None)
+            .traced(Op::Rpc, "share_consent")
+            .await?)
+    }
+    async fn offer_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: <N::Runtime as System>::AccountId,
+        shares: <N::Runtime as Org>::Shares,
+        class: ShareClass,
+    ) -> Result<SharesOfferedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            IssueSharesCall {
+                organization: org,
+                who: &who,
+                shares,
+                class,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "issue_shares")
+        .await?
+        .decoded("shares_offered", |r| r.shares_offered())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn accept_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        grant_id: u32,
+    ) -> Result<ShareGrantAcceptedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            AcceptSharesCall {
+                organization: org,
+                grant_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "accept_shares")
+        .await?
+        .decoded("share_grant_accepted", |r| r.share_grant_accepted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn decline_shares(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        grant_id: u32,
+    ) -> Result<ShareGrantDeclinedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            DeclineSharesCall {
+                organization: org,
+                grant_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "decline_shares")
+        .await?
+        .decoded("share_grant_declined", |r| r.share_grant_declined())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn cancel_share_grant(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+        who: &<N::Runtime as System>::AccountId,
+        grant_id: u32,
+    ) -> Result<ShareGrantCancelledEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            CancelShareGrantCall {
+                organization: org,
+                who,
+                grant_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "cancel_share_grant")
+        .await?
+        .decoded("share_grant_cancelled", |r| r.share_grant_cancelled())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn share_grants(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Vec<(u32, ShareGrantOf<N::Runtime>)>> {
+        let chain = self.chain_client();
+        // the grants are keyed by the blake2_128 hash and encoding of the
+        // recipient followed by the twox_64 hash and encoding of the grant id
+        let mut prefix =
+            ShareGrantsStore::<N::Runtime>::prefix(chain.metadata())?;
+        let who = who.encode();
+        prefix.0.extend(&blake2_128(&who));
+        prefix.0.extend(who);
+        let pages = StoragePage::<u32, ShareGrantOf<N::Runtime>>::new(
+            prefix,
+            KeyHasher::Twox64Concat,
+            DEFAULT_PAGE_SIZE,
+        );
+        let grants = pages.stream(chain, None);
+        pin_mut!(grants);
+        let mut all = Vec::new();
+        while let Some(grant) = grants.try_next().await? {
+            all.push(grant);
+        }
+        Ok(all)
+    }
 }
 
 #[cfg(test)]
@@ -295,10 +1528,19 @@ mod tests {
         },
         org::{
             NewFlatOrgEvent,
+            OrgActionsGuardedEvent,
             OrgClient,
         },
-        Client,
-        Node,
+        utils::{
+            organization::{
+                org_handle,
+                GuardedActions,
+            },
+            share::ShareClass,
+        },
+        DevClient as Client,
+        DevNode as Node,
+        MemberProfile,
         TextBlock,
     };
 
@@ -351,6 +1593,7 @@ mod tests {
                 None,
                 raw_const,
                 &members,
+                None,
             )
             .await
             .unwrap();
@@ -362,4 +1605,292 @@ mod tests {
         };
         assert_eq!(event, expected_event);
     }
+
+    #[async_std::test]
+    async fn guarded_issuance_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let raw_const = TextBlock {
+            text: "issuance needs a vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let event = client
+            .guard_org_actions(org, GuardedActions::ISSUE_SHARES, 5)
+            .await
+            .unwrap();
+        let expected_event = OrgActionsGuardedEvent {
+            organization: org,
+            guards: GuardedActions::ISSUE_SHARES,
+        };
+        assert_eq!(event, expected_event);
+        assert!(client
+            .issue_shares(org, alice_account_id.clone(), 3, ShareClass::Voting)
+            .await
+            .is_ok());
+        // more than the per block limit requires an approved vote
+        assert!(client
+            .issue_shares(org, alice_account_id, 10, ShareClass::Voting)
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn non_voting_shares_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let investor = random_account_id();
+        let raw_const = TextBlock {
+            text: "investors do not vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let event = client
+            .issue_shares(org, investor.clone(), 50, ShareClass::NonVoting)
+            .await
+            .unwrap();
+        assert_eq!(event.class, ShareClass::NonVoting);
+        let event = client
+            .convert_shares(org, investor.clone(), ShareClass::NonVoting, 10)
+            .await
+            .unwrap();
+        assert_eq!(event.shares, 10);
+        assert_eq!(
+            client
+                .share_profile(org, investor.clone())
+                .await
+                .unwrap()
+                .total(),
+            10
+        );
+        assert_eq!(client.non_voting_shares(org, investor).await.unwrap(), 40);
+    }
+
+    #[async_std::test]
+    async fn member_profile_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let raw_const = TextBlock {
+            text: "members introduce themselves".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        assert!(client
+            .member_profile(org, alice_account_id.clone())
+            .await
+            .unwrap()
+            .is_none());
+        let profile = MemberProfile {
+            name: "Alice".to_string(),
+            role: Some("maintainer".to_string()),
+            contact: None,
+        };
+        let event = client.set_my_profile(org, Some(profile)).await.unwrap();
+        assert_eq!(event.who, alice_account_id);
+        assert!(event.metadata.is_some());
+        let profile = client
+            .member_profile(org, alice_account_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.name, "Alice");
+        assert_eq!(profile.role.as_deref(), Some("maintainer"));
+    }
+    #[async_std::test]
+    async fn org_handle_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let raw_const = TextBlock {
+            text: "find us by name".to_string(),
+        };
+        let handle = org_handle("sunshine-devs").unwrap();
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id],
+                Some(handle),
+            )
+            .await
+            .unwrap()
+            .new_id;
+        assert_eq!(
+            client.org_by_handle("sunshine-devs").await.unwrap(),
+            Some(org)
+        );
+        assert_eq!(client.org_handle(org).await.unwrap(), Some(handle));
+        assert!(client.org_by_handle("Sunshine Devs").await.is_err());
+        let event = client.set_org_handle(org, None).await.unwrap();
+        assert_eq!(event.handle, None);
+        assert_eq!(client.org_by_handle("sunshine-devs").await.unwrap(), None);
+    }
+    #[async_std::test]
+    async fn membership_application_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (bob, _tmp_bob) = Client::mock(&node, AccountKeyring::Bob).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let bob_account_id = AccountKeyring::Bob.to_account_id();
+        let raw_const = TextBlock {
+            text: "apply to join".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        client.set_application_policy(org, 100, 0).await.unwrap();
+        assert_eq!(client.application_policy(org).await.unwrap(), (100, 0));
+        let profile = MemberProfile {
+            name: "Bob".to_string(),
+            role: None,
+            contact: None,
+        };
+        let event = bob.apply_for_membership(org, Some(profile)).await.unwrap();
+        assert_eq!(event.deposit, 100);
+        assert_eq!(client.pending_applications(org).await.unwrap(), 1);
+        assert_eq!(
+            client
+                .application_deposit(org, &bob_account_id)
+                .await
+                .unwrap(),
+            Some(100)
+        );
+        let event = client
+            .approve_application(org, bob_account_id.clone(), 1)
+            .await
+            .unwrap();
+        assert_eq!(event.applicant, bob_account_id);
+        assert_eq!(client.pending_applications(org).await.unwrap(), 0);
+        assert_eq!(
+            client
+                .application_deposit(org, &bob_account_id)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+    #[async_std::test]
+    async fn org_roster_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (bob, _tmp_bob) = Client::mock(&node, AccountKeyring::Bob).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let bob_account_id = AccountKeyring::Bob.to_account_id();
+        let raw_const = TextBlock {
+            text: "observed".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let profile = MemberProfile {
+            name: "Bob".to_string(),
+            role: Some("auditor".to_string()),
+            contact: None,
+        };
+        // members hold shares so they cannot observe
+        assert!(client
+            .add_observer(org, &alice_account_id, profile.clone())
+            .await
+            .is_err());
+        let event = client
+            .add_observer(org, &bob_account_id, profile)
+            .await
+            .unwrap();
+        assert_eq!(event.who, bob_account_id);
+        let roster = client.org_roster(org).await.unwrap();
+        assert_eq!(roster.supervisor, Some(alice_account_id.clone()));
+        assert_eq!(roster.members.len(), 1);
+        assert_eq!(roster.members[0].0, alice_account_id);
+        assert_eq!(roster.observers.len(), 1);
+        assert_eq!(roster.observers[0].0, bob_account_id);
+        assert_eq!(roster.observers[0].1.role.as_deref(), Some("auditor"));
+        bob.remove_observer(org, &bob_account_id).await.unwrap();
+        assert!(client.observers(org).await.unwrap().is_empty());
+    }
+    #[async_std::test]
+    async fn share_consent_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (bob, _tmp_bob) = Client::mock(&node, AccountKeyring::Bob).await;
+        let alice_account_id = AccountKeyring::Alice.to_account_id();
+        let bob_account_id = AccountKeyring::Bob.to_account_id();
+        let raw_const = TextBlock {
+            text: "by consent".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice_account_id.clone()),
+                None,
+                raw_const,
+                &[alice_account_id],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        client.set_share_consent(org, true).await.unwrap();
+        assert!(client.requires_share_consent(org).await.unwrap());
+        let event = client
+            .offer_shares(org, bob_account_id.clone(), 5, ShareClass::Voting)
+            .await
+            .unwrap();
+        // bob is not a member before accepting
+        let members = client.org_members(org).await.unwrap().unwrap();
+        assert_eq!(members.len(), 1);
+        let grants = bob.share_grants(&bob_account_id).await.unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].0, event.grant_id);
+        assert_eq!(grants[0].1.shares(), 5);
+        bob.accept_shares(org, event.grant_id).await.unwrap();
+        let members = client.org_members(org).await.unwrap().unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(bob.share_grants(&bob_account_id).await.unwrap().is_empty());
+    }
 }
@@ -17,8 +17,15 @@ use sp_runtime::traits::{
     Member,
     Zero,
 };
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+};
 use substrate_subxt::{
+    balances::{
+        Balances,
+        BalancesEventsDecoder,
+    },
     module,
     sp_runtime,
     system::{
@@ -30,19 +37,32 @@ use substrate_subxt::{
     Store,
 };
 use sunshine_bounty_utils::{
+    merkle::H256,
     organization::{
+        GuardedActions,
+        MemberFilter,
+        OrgAction,
+        OrgActionProposal,
+        OrgHandle,
+        OrgLifecycle,
+        OrgSpendProposal,
         Organization,
+        PowerMask,
         Relation,
     },
     share::{
         ProfileState,
+        ShareClass,
+        ShareGrant,
         ShareProfile,
     },
 };
 
 /// The subset of the org trait and its inherited traits that the client must inherit
+pub type BalanceOf<T> = <T as Balances>::Balance;
+
 #[module]
-pub trait Org: System {
+pub trait Org: System + Balances {
     /// Cid type
     type Cid: Parameter + Member + Default;
 
@@ -77,6 +97,12 @@ pub trait Org: System {
         + DagDecode<DagCborCodec>
         + Send
         + Sync;
+
+    /// Threshold identifier invoked for treasury spend votes
+    type SpendThresholdId: Parameter + Member + Copy + Default;
+
+    /// Vote identifier for treasury spend votes
+    type SpendVoteId: Parameter + Member + Copy + Default;
 }
 
 pub type OrgState<T> = Organization<
@@ -91,6 +117,27 @@ pub type Prof<T> = ShareProfile<
     ProfileState,
 >;
 pub type Relacion<T> = Relation<<T as Org>::OrgId>;
+pub type SpendProp<T> = OrgSpendProposal<
+    <T as Org>::OrgId,
+    <T as System>::AccountId,
+    BalanceOf<T>,
+    <T as Org>::Cid,
+    <T as Org>::SpendVoteId,
+>;
+pub type OrgActionOf<T> =
+    OrgAction<<T as System>::AccountId, <T as Org>::Shares, <T as Org>::Cid>;
+pub type MemberFilterOf<T> =
+    MemberFilter<<T as System>::AccountId, <T as Org>::Shares>;
+pub type ActionProp<T> = OrgActionProposal<
+    <T as Org>::OrgId,
+    <T as System>::Hash,
+    <T as Org>::SpendVoteId,
+>;
+pub type ShareGrantOf<T> = ShareGrant<
+    <T as Org>::OrgId,
+    <T as Org>::Shares,
+    <T as System>::BlockNumber,
+>;
 // ~~ Storage ~~
 
 #[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
@@ -113,6 +160,116 @@ pub struct MembersStore<'a, T: Org> {
     pub who: &'a <T as System>::AccountId,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct MembershipRootsStore<T: Org> {
+    #[store(returns = Option<H256>)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct NonVotingSharesStore<'a, T: Org> {
+    #[store(returns = T::Shares)]
+    pub org: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SnapshotSharesStore<'a, T: Org> {
+    #[store(returns = Option<T::Shares>)]
+    pub snapshot: (T::OrgId, u32),
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct MemberMetadataStore<'a, T: Org> {
+    #[store(returns = Option<T::Cid>)]
+    pub org: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SpendProposalsStore<T: Org> {
+    #[store(returns = SpendProp<T>)]
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct GuardedOrgActionsStore<T: Org> {
+    #[store(returns = GuardedActions)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct OrgActionProposalsStore<T: Org> {
+    #[store(returns = ActionProp<T>)]
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct EmergencyPowersStore<'a, T: Org> {
+    #[store(returns = (PowerMask, <T as System>::BlockNumber))]
+    pub org: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct HandleToOrgStore<T: Org> {
+    #[store(returns = Option<T::OrgId>)]
+    pub handle: OrgHandle,
+    pub _runtime: PhantomData<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct OrgHandlesStore<T: Org> {
+    #[store(returns = Option<OrgHandle>)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ApplicationPoliciesStore<T: Org> {
+    #[store(returns = (BalanceOf<T>, <T as System>::BlockNumber))]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ApplicationsStore<'a, T: Org> {
+    #[store(returns = Option<(Option<T::Cid>, BalanceOf<T>)>)]
+    pub org: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct PendingApplicationsStore<T: Org> {
+    #[store(returns = u32)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ObserversStore<'a, T: Org> {
+    #[store(returns = Option<T::Cid>)]
+    pub org: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct LifecyclesStore<T: Org> {
+    #[store(returns = OrgLifecycle)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ShareConsentStore<T: Org> {
+    #[store(returns = bool)]
+    pub org: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ShareGrantsStore<'a, T: Org> {
+    #[store(returns = Option<ShareGrantOf<T>>)]
+    pub who: &'a <T as System>::AccountId,
+    pub grant_id: u32,
+}
+
 // ~~ (Calls, Events) ~~
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -121,6 +278,7 @@ pub struct NewFlatOrgCall<'a, T: Org> {
     pub parent_org: Option<T::OrgId>,
     pub constitution: T::Cid,
     pub members: &'a [<T as System>::AccountId],
+    pub handle: Option<OrgHandle>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -137,6 +295,7 @@ pub struct NewWeightedOrgCall<'a, T: Org> {
     pub parent_org: Option<T::OrgId>,
     pub constitution: T::Cid,
     pub weighted_members: &'a [(<T as System>::AccountId, T::Shares)],
+    pub handle: Option<OrgHandle>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -152,6 +311,7 @@ pub struct IssueSharesCall<'a, T: Org> {
     pub organization: T::OrgId,
     pub who: &'a <T as System>::AccountId,
     pub shares: T::Shares,
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -159,6 +319,7 @@ pub struct SharesIssuedEvent<T: Org> {
     pub organization: T::OrgId,
     pub who: <T as System>::AccountId,
     pub shares: T::Shares,
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -166,6 +327,7 @@ pub struct BurnSharesCall<'a, T: Org> {
     pub organization: T::OrgId,
     pub who: &'a <T as System>::AccountId,
     pub shares: T::Shares,
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
@@ -173,28 +335,479 @@ pub struct SharesBurnedEvent<T: Org> {
     pub organization: T::OrgId,
     pub who: <T as System>::AccountId,
     pub shares: T::Shares,
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
 pub struct BatchIssueSharesCall<'a, T: Org> {
     pub organization: T::OrgId,
     pub new_accounts: &'a [(<T as System>::AccountId, T::Shares)],
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
 pub struct SharesBatchIssuedEvent<T: Org> {
     pub organization: T::OrgId,
     pub total_new_shares_minted: T::Shares,
+    pub total: T::Shares,
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
 pub struct BatchBurnSharesCall<'a, T: Org> {
     pub organization: T::OrgId,
     pub old_accounts: &'a [(<T as System>::AccountId, T::Shares)],
+    pub class: ShareClass,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
 pub struct SharesBatchBurnedEvent<T: Org> {
     pub organization: T::OrgId,
     pub total_new_shares_burned: T::Shares,
+    pub class: ShareClass,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ConvertSharesCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+    pub from: ShareClass,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SharesConvertedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub from: ShareClass,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct AddedOrgMemberEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RemovedOrgMemberEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SharesLockedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SharesUnlockedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SharesDecayedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub lost: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ShareBalanceChangedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub class: ShareClass,
+    pub balance: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetMyMetadataCall<T: Org> {
+    pub organization: T::OrgId,
+    pub metadata: Option<T::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetMemberMetadataCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+    pub metadata: Option<T::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct MemberMetadataUpdatedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub metadata: Option<T::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetOrgHandleCall<T: Org> {
+    pub organization: T::OrgId,
+    pub handle: Option<OrgHandle>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgHandleSetEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub handle: Option<OrgHandle>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ProposeSpendCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub beneficiary: &'a <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+    pub reason: T::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SpendProposedEvent<T: Org> {
+    pub proposer: <T as System>::AccountId,
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+    pub beneficiary: <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+    pub vote: T::SpendVoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SpendApprovedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SpendRejectedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ExecuteSpendCall<T: Org> {
+    pub proposal_id: u32,
+    pub _runtime: PhantomData<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SpendExecutedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+    pub beneficiary: <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ChangeConstitutionCall<T: Org> {
+    pub organization: T::OrgId,
+    pub constitution: T::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ConstitutionChangedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub constitution: T::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct GuardOrgActionsCall<T: Org> {
+    pub organization: T::OrgId,
+    pub guards: u8,
+    pub issuance_limit: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgActionsGuardedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub guards: u8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetActionThresholdCall<T: Org> {
+    pub organization: T::OrgId,
+    pub threshold: T::SpendThresholdId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ActionThresholdSetEvent<T: Org> {
+    pub setter: <T as System>::AccountId,
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ProposeOrgActionCall<T: Org> {
+    pub organization: T::OrgId,
+    pub action: OrgActionOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgActionProposedEvent<T: Org> {
+    pub proposer: <T as System>::AccountId,
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+    pub vote: T::SpendVoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ExecuteOrgActionCall<T: Org> {
+    pub proposal_id: u32,
+    pub action: OrgActionOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgActionExecutedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct GrantEmergencyPowerCall<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub power_mask: u8,
+    pub duration_blocks: u32,
+    pub proposal_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct EmergencyPowerGrantedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub power_mask: u8,
+    pub expiry: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct EmergencyPowerUsedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub power: u8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct EmergencyPowerExpiredEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct RevokeEmergencyPowerCall<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct EmergencyPowerRevokedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct PublishMembershipRootCall<T: Org> {
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct MembershipRootPublishedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub root: H256,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetApplicationPolicyCall<T: Org> {
+    pub organization: T::OrgId,
+    pub deposit: BalanceOf<T>,
+    pub cooldown: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ApplicationPolicySetEvent<T: Org> {
+    pub setter: <T as System>::AccountId,
+    pub organization: T::OrgId,
+    pub deposit: BalanceOf<T>,
+    pub cooldown: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ApplyForMembershipCall<T: Org> {
+    pub organization: T::OrgId,
+    pub application: Option<T::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct MembershipAppliedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+    pub deposit: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct WithdrawApplicationCall<T: Org> {
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ApplicationWithdrawnEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ApproveApplicationCall<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ApplicationApprovedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+    pub shares: T::Shares,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct RejectApplicationCall<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+    pub spam: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ApplicationRejectedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub applicant: <T as System>::AccountId,
+    pub spam: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct AddObserverCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+    pub role: T::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ObserverAddedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub role: T::Cid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct RemoveObserverCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ObserverRemovedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ForkOrgCall<T: Org> {
+    pub source_org: T::OrgId,
+    pub constitution: T::Cid,
+    pub filter: MemberFilterOf<T>,
+    pub equal_shares: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgForkedEvent<T: Org> {
+    pub source_org: T::OrgId,
+    pub new_org: T::OrgId,
+    pub members: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct FreezeOrgCall<T: Org> {
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgFrozenEvent<T: Org> {
+    pub supervisor: <T as System>::AccountId,
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct UnfreezeOrgCall<T: Org> {
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct OrgUnfrozenEvent<T: Org> {
+    pub supervisor: <T as System>::AccountId,
+    pub organization: T::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetShareConsentCall<T: Org> {
+    pub organization: T::OrgId,
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ShareConsentSetEvent<T: Org> {
+    pub setter: <T as System>::AccountId,
+    pub organization: T::OrgId,
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SharesOfferedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub grant_id: u32,
+    pub shares: T::Shares,
+    pub class: ShareClass,
+    pub expires_at: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct AcceptSharesCall<T: Org> {
+    pub organization: T::OrgId,
+    pub grant_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ShareGrantAcceptedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub grant_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct DeclineSharesCall<T: Org> {
+    pub organization: T::OrgId,
+    pub grant_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ShareGrantDeclinedEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub grant_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct CancelShareGrantCall<'a, T: Org> {
+    pub organization: T::OrgId,
+    pub who: &'a <T as System>::AccountId,
+    pub grant_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ShareGrantCancelledEvent<T: Org> {
+    pub organization: T::OrgId,
+    pub who: <T as System>::AccountId,
+    pub grant_id: u32,
+    pub expired: bool,
 }
@@ -0,0 +1,365 @@
+//! Cursor based reads of the entries under a storage prefix
+//!
+//! `StoragePage` lists the keys under a prefix with `state_getKeysPaged` and
+//! reads their values with one `state_queryStorageAt` per page, rather than
+//! one request per entry. A page ends with the token of its last key and the
+//! next page continues after it, so entries removed between two pages are
+//! skipped rather than shifting the pages. Each page is read at the best
+//! block when it is requested.
+use crate::metrics::{
+    Op,
+    TracedExt,
+};
+use futures::stream::{
+    self,
+    Stream,
+};
+use jsonrpsee::common::Params;
+use parity_scale_codec::Decode;
+use serde::Deserialize;
+use serde_json::to_value;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+};
+use substrate_subxt::{
+    sp_core::storage::{
+        StorageData,
+        StorageKey,
+    },
+    Client as ChainClient,
+    Metadata,
+    Runtime,
+    Store,
+};
+use sunshine_client_utils::Result;
+
+/// The number of keys read per page by the clients which list whole maps
+pub const DEFAULT_PAGE_SIZE: u32 = 256;
+
+/// The hasher of the key of a map, whose encoding follows its hash in the
+/// storage key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyHasher {
+    Blake2_128Concat,
+    Twox64Concat,
+    Identity,
+}
+
+impl KeyHasher {
+    fn hash_len(self) -> usize {
+        match self {
+            Self::Blake2_128Concat => 16,
+            Self::Twox64Concat => 8,
+            Self::Identity => 0,
+        }
+    }
+}
+
+/// The position after the last entry of a page. Any bytes are a valid
+/// token, apps hand back the bytes they were given.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PageToken(StorageKey);
+
+impl PageToken {
+    pub fn as_bytes(&self) -> &[u8] {
+        &(self.0).0
+    }
+}
+
+impl From<Vec<u8>> for PageToken {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(StorageKey(bytes))
+    }
+}
+
+/// The entries of a page and the token of the next, None after the last
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Page<K, V> {
+    pub entries: Vec<(K, V)>,
+    pub next: Option<PageToken>,
+}
+
+/// The response of `state_queryStorageAt`
+#[derive(Deserialize)]
+struct ChangeSet {
+    changes: Vec<(StorageKey, Option<StorageData>)>,
+}
+
+/// Reads the entries of a storage map a page at a time
+pub struct StoragePage<K, V> {
+    prefix: StorageKey,
+    hasher: KeyHasher,
+    size: u32,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K: Decode, V: Decode> StoragePage<K, V> {
+    /// The entries under `prefix` whose keys are hashed with `hasher`, in
+    /// pages of `size`
+    pub fn new(prefix: StorageKey, hasher: KeyHasher, size: u32) -> Self {
+        Self {
+            prefix,
+            hasher,
+            size: size.max(1),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The entries of the map read by `F`
+    pub fn of<T: Runtime, F: Store<T>>(
+        metadata: &Metadata,
+        hasher: KeyHasher,
+        size: u32,
+    ) -> Result<Self> {
+        Ok(Self::new(F::prefix(metadata)?, hasher, size))
+    }
+
+    /// The page after `token`, the first page without one
+    pub async fn page<T: Runtime>(
+        &self,
+        chain: &ChainClient<T>,
+        token: Option<&PageToken>,
+    ) -> Result<Page<K, V>> {
+        let keys: Vec<StorageKey> = chain
+            .rpc_client()
+            .request(
+                "state_getKeysPaged",
+                Params::Array(vec![
+                    to_value(&self.prefix)?,
+                    to_value(self.size)?,
+                    to_value(token.map(|token| &token.0))?,
+                ]),
+            )
+            .traced(Op::Rpc, "state_getKeysPaged")
+            .await?;
+        // a short page is the last, a full one may be followed by an empty
+        let next = if keys.len() < self.size as usize {
+            None
+        } else {
+            keys.last().cloned().map(PageToken)
+        };
+        if keys.is_empty() {
+            return Ok(Page {
+                entries: Vec::new(),
+                next,
+            })
+        }
+        let change_sets: Vec<ChangeSet> = chain
+            .rpc_client()
+            .request(
+                "state_queryStorageAt",
+                Params::Array(vec![to_value(&keys)?]),
+            )
+            .traced(Op::Rpc, "state_queryStorageAt")
+            .await?;
+        let mut values = change_sets
+            .into_iter()
+            .flat_map(|set| set.changes)
+            .filter_map(|(key, value)| Some((key, value?)))
+            .collect::<HashMap<_, _>>();
+        let mut entries = Vec::with_capacity(keys.len());
+        // keys removed since they were listed have no value
+        for key in keys {
+            if let Some(value) = values.remove(&key) {
+                let value = V::decode(&mut &value.0[..])?;
+                entries.push((self.decode_key(&key)?, value));
+            }
+        }
+        Ok(Page { entries, next })
+    }
+
+    /// The entries of every page after `token`, read a page at a time
+    pub fn stream<'a, T: Runtime>(
+        &'a self,
+        chain: &'a ChainClient<T>,
+        token: Option<PageToken>,
+    ) -> impl Stream<Item = Result<(K, V)>> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        // the token of the next page to read, None once the last was read
+        let next = Some(token);
+        stream::unfold(
+            (Vec::new().into_iter(), next),
+            move |(mut entries, mut next)| {
+                async move {
+                    loop {
+                        if let Some(entry) = entries.next() {
+                            return Some((Ok(entry), (entries, next)))
+                        }
+                        let token = next.take()?;
+                        match self.page(chain, token.as_ref()).await {
+                            Ok(page) => {
+                                entries = page.entries.into_iter();
+                                next = page.next.map(Some);
+                            }
+                            Err(err) => {
+                                return Some((Err(err), (entries, None)))
+                            }
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    fn decode_key(&self, key: &StorageKey) -> Result<K> {
+        let offset = self.prefix.0.len() + self.hasher.hash_len();
+        let mut encoded = key.0.get(offset..).unwrap_or_default();
+        Ok(K::decode(&mut encoded)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use test_client::{
+        bounty::{
+            BountiesStore,
+            BountyClient,
+            SubmissionsStore,
+        },
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        utils::bounty::SubmissionKind,
+        DevClient as Client,
+        DevNode as Node,
+        GithubIssue,
+        Runtime,
+    };
+
+    fn issue(issue_number: u64) -> GithubIssue {
+        GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number,
+        }
+    }
+
+    /// The pages of a map keyed by ids
+    fn page_of<F: Store<Runtime>>(
+        chain: &ChainClient<Runtime>,
+        size: u32,
+    ) -> StoragePage<u64, F::Returns> {
+        StoragePage::of::<_, F>(
+            chain.metadata(),
+            KeyHasher::Blake2_128Concat,
+            size,
+        )
+        .unwrap()
+    }
+
+    async fn post_bounties(client: &Client, n: u64) {
+        for i in 0..n {
+            client
+                .post_bounty(
+                    issue(200 + i),
+                    100u128,
+                    None,
+                    SubmissionKind::Any,
+                    vec![],
+                    vec![],
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[async_std::test]
+    async fn empty_prefix_has_one_empty_page() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let chain = client.chain_client();
+        let bounties = page_of::<BountiesStore<Runtime>>(chain, 2);
+        let page = bounties.page(chain, None).await.unwrap();
+        assert!(page.entries.is_empty());
+        assert_eq!(page.next, None);
+        let all: Vec<_> =
+            bounties.stream(chain, None).try_collect().await.unwrap();
+        assert!(all.is_empty());
+    }
+
+    #[async_std::test]
+    async fn pages_end_at_their_size() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        post_bounties(&client, 3).await;
+        let chain = client.chain_client();
+        let bounties = page_of::<BountiesStore<Runtime>>(chain, 2);
+        let first = bounties.page(chain, None).await.unwrap();
+        assert_eq!(first.entries.len(), 2);
+        let second = bounties.page(chain, first.next.as_ref()).await.unwrap();
+        assert_eq!(second.entries.len(), 1);
+        assert_eq!(second.next, None);
+        let mut ids: Vec<u64> = first
+            .entries
+            .iter()
+            .chain(&second.entries)
+            .map(|(id, bounty)| {
+                assert_eq!(*id, bounty.id());
+                *id
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3]);
+        // a full last page is followed by an empty one
+        let whole = page_of::<BountiesStore<Runtime>>(chain, 3);
+        let first = whole.page(chain, None).await.unwrap();
+        assert_eq!(first.entries.len(), 3);
+        let second = whole.page(chain, first.next.as_ref()).await.unwrap();
+        assert!(second.entries.is_empty());
+        assert_eq!(second.next, None);
+        // the stream reads the same entries in the same order
+        let streamed: Vec<_> =
+            bounties.stream(chain, None).try_collect().await.unwrap();
+        assert_eq!(streamed, first.entries);
+    }
+
+    #[async_std::test]
+    async fn removed_keys_are_skipped_between_pages() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let (charlie, _tmp_charlie) =
+            Client::mock(&node, AccountKeyring::Charlie).await;
+        post_bounties(&client, 3).await;
+        for bounty_id in 1..4 {
+            charlie
+                .submit_for_bounty(
+                    bounty_id,
+                    issue(300 + bounty_id).into(),
+                    10u128,
+                    vec![],
+                )
+                .await
+                .unwrap();
+        }
+        let chain = client.chain_client();
+        let submissions = page_of::<SubmissionsStore<Runtime>>(chain, 1);
+        let order: Vec<u64> = submissions
+            .stream(chain, None)
+            .map_ok(|(id, _)| id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(order.len(), 3);
+        let first = submissions.page(chain, None).await.unwrap();
+        assert_eq!(first.entries[0].0, order[0]);
+        // the key the token points at and the next one are removed
+        charlie.withdraw_bounty_submission(order[0]).await.unwrap();
+        charlie.withdraw_bounty_submission(order[1]).await.unwrap();
+        let second =
+            submissions.page(chain, first.next.as_ref()).await.unwrap();
+        assert_eq!(second.entries.len(), 1);
+        assert_eq!(second.entries[0].0, order[2]);
+        // a token handed back as bytes reads the same page
+        let token = PageToken::from(first.next.unwrap().as_bytes().to_vec());
+        let again = submissions.page(chain, Some(&token)).await.unwrap();
+        assert_eq!(again, second);
+    }
+}
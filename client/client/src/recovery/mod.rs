@@ -0,0 +1,324 @@
+mod subxt;
+
+pub use subxt::*;
+
+use crate::{
+    error::Error,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    submit::SubmitExt,
+};
+use substrate_subxt::{
+    system::System,
+    Call,
+    ExtrinsicSuccess,
+    Runtime,
+    SignedExtension,
+    SignedExtra,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    Result,
+};
+
+#[async_trait]
+pub trait RecoveryClient<N: Node>: Client<N>
+where
+    N::Runtime: Recovery,
+{
+    async fn add_recovery_friends(
+        &self,
+        friends: Vec<<N::Runtime as System>::AccountId>,
+        threshold: u16,
+    ) -> Result<RecoveryCreatedEvent<N::Runtime>>;
+    async fn initiate_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryInitiatedEvent<N::Runtime>>;
+    async fn vouch_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        rescuer: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryVouchedEvent<N::Runtime>>;
+    async fn claim_recovered(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<AccountRecoveredEvent<N::Runtime>>;
+    async fn as_recovered<R: Call<N::Runtime> + Send + Sync>(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        call: R,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>>;
+    async fn recovery_config(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryConf<N::Runtime>>;
+    async fn active_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        rescuer: <N::Runtime as System>::AccountId,
+    ) -> Result<Attempt<N::Runtime>>;
+}
+
+#[async_trait]
+impl<N, C> RecoveryClient<N> for C
+where
+    N: Node,
+    N::Runtime: Recovery,
+    <<<N::Runtime as Runtime>::Extra as SignedExtra<N::Runtime>>::Extra as SignedExtension>::AdditionalSigned:
+        Send + Sync,
+    <N::Runtime as System>::AccountId: Ord,
+    C: Client<N>,
+{
+    async fn add_recovery_friends(
+        &self,
+        mut friends: Vec<<N::Runtime as System>::AccountId>,
+        threshold: u16,
+    ) -> Result<RecoveryCreatedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        // the pallet rejects unsorted or duplicate friends
+        friends.sort();
+        friends.dedup();
+        // claimable as soon as enough friends vouch
+        let delay_period = Default::default();
+        self.watch(
+            CreateRecoveryCall {
+                friends,
+                threshold,
+                delay_period,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "create_recovery")
+        .await?
+        .decoded("recovery_created", |r| r.recovery_created())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn initiate_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryInitiatedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            InitiateRecoveryCall {
+                lost: &lost,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "initiate_recovery")
+        .await?
+        .decoded("recovery_initiated", |r| r.recovery_initiated())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn vouch_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        rescuer: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryVouchedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            VouchRecoveryCall {
+                lost: &lost,
+                rescuer: &rescuer,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "vouch_recovery")
+        .await?
+        .decoded("recovery_vouched", |r| r.recovery_vouched())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn claim_recovered(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<AccountRecoveredEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ClaimRecoveryCall {
+                lost: &lost,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "claim_recovery")
+        .await?
+        .decoded("account_recovered", |r| r.account_recovered())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn as_recovered<R: Call<N::Runtime> + Send + Sync>(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        call: R,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let call = self.chain_client().encode(call)?;
+        Ok(self
+            .watch(
+                AsRecoveredCall {
+                    lost: &lost,
+                    call: &call,
+                },
+                &signer,
+            )
+            .settled(&signer)
+            .traced(Op::Extrinsic, "as_recovered")
+            .await?)
+    }
+    async fn recovery_config(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+    ) -> Result<RecoveryConf<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .recoverable(&lost, None)
+            .traced(Op::Rpc, "recoverable")
+            .await?)
+    }
+    async fn active_recovery(
+        &self,
+        lost: <N::Runtime as System>::AccountId,
+        rescuer: <N::Runtime as System>::AccountId,
+    ) -> Result<Attempt<N::Runtime>> {
+        Ok(self
+            .chain_client()
+            .active_recoveries(&lost, &rescuer, None)
+            .traced(Op::Rpc, "active_recoveries")
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        org::{
+            BurnSharesCall,
+            IssueSharesCall,
+            OrgClient,
+        },
+        recovery::{
+            AccountRecoveredEvent,
+            RecoveryClient,
+        },
+        utils::share::ShareClass,
+        DevClient as Client,
+        DevNode as Node,
+        Runtime,
+        TextBlock,
+    };
+
+    #[async_std::test]
+    async fn friends_recover_org_shares() {
+        use substrate_subxt::balances::TransferCallExt;
+        let node = Node::new_mock();
+        let (alice_client, _tmp_alice) =
+            Client::mock(&node, AccountKeyring::Alice).await;
+        let (bob_client, _tmp_bob) =
+            Client::mock(&node, AccountKeyring::Bob).await;
+        let (charlie_client, _tmp_charlie) =
+            Client::mock(&node, AccountKeyring::Charlie).await;
+        let (dave_client, _tmp_dave) =
+            Client::mock(&node, AccountKeyring::Dave).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let dave = AccountKeyring::Dave.to_account_id();
+        let eve = AccountKeyring::Eve.to_account_id();
+        let constitution = TextBlock {
+            text: "alice runs this org".to_string(),
+        };
+        let org = alice_client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        // the vouching friends pay transaction fees
+        let alice_signer = alice_client.chain_signer().unwrap();
+        for friend in &[charlie.clone(), dave.clone()] {
+            alice_client
+                .chain_client()
+                .transfer(&alice_signer, friend, 10_000)
+                .await
+                .unwrap();
+        }
+        alice_client
+            .add_recovery_friends(vec![eve, dave.clone(), charlie.clone()], 2)
+            .await
+            .unwrap();
+        let config = alice_client.recovery_config(alice.clone()).await.unwrap();
+        assert_eq!(config.threshold, 2);
+        bob_client.initiate_recovery(alice.clone()).await.unwrap();
+        // the lost account cannot be claimed before enough friends vouch
+        charlie_client
+            .vouch_recovery(alice.clone(), bob.clone())
+            .await
+            .unwrap();
+        assert!(bob_client.claim_recovered(alice.clone()).await.is_err());
+        dave_client
+            .vouch_recovery(alice.clone(), bob.clone())
+            .await
+            .unwrap();
+        let attempt = bob_client
+            .active_recovery(alice.clone(), bob.clone())
+            .await
+            .unwrap();
+        let mut vouched = vec![charlie, dave];
+        vouched.sort();
+        assert_eq!(attempt.friends, vouched);
+        let event = bob_client.claim_recovered(alice.clone()).await.unwrap();
+        let expected_event = AccountRecoveredEvent {
+            lost: alice.clone(),
+            rescuer: bob.clone(),
+        };
+        assert_eq!(event, expected_event);
+        // bob acts as alice to move her share to himself
+        bob_client
+            .as_recovered(
+                alice.clone(),
+                IssueSharesCall::<Runtime> {
+                    organization: org,
+                    who: &bob,
+                    shares: 1,
+                    class: ShareClass::Voting,
+                },
+            )
+            .await
+            .unwrap();
+        bob_client
+            .as_recovered(
+                alice.clone(),
+                BurnSharesCall::<Runtime> {
+                    organization: org,
+                    who: &alice,
+                    shares: 1,
+                    class: ShareClass::Voting,
+                },
+            )
+            .await
+            .unwrap();
+        let profile = bob_client.share_profile(org, bob).await.unwrap();
+        assert_eq!(profile.total(), 1);
+    }
+}
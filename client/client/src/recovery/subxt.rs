@@ -0,0 +1,125 @@
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use std::fmt::Debug;
+use substrate_subxt::{
+    balances::{
+        Balances,
+        BalancesEventsDecoder,
+    },
+    module,
+    system::{
+        System,
+        SystemEventsDecoder,
+    },
+    Call,
+    Encoded,
+    Event,
+    Store,
+};
+
+/// The balance type
+pub type BalanceOf<T> = <T as Balances>::Balance;
+
+/// Social recovery through the substrate recovery pallet
+#[module]
+pub trait Recovery: System + Balances {}
+
+// ~~ Storage ~~
+
+/// The friends who may vouch for a lost account
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct RecoveryConfig<BlockNumber, Balance, AccountId> {
+    pub delay_period: BlockNumber,
+    pub deposit: Balance,
+    pub friends: Vec<AccountId>,
+    pub threshold: u16,
+}
+
+/// A recovery attempt and the friends who vouched for it so far
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct ActiveRecovery<BlockNumber, Balance, AccountId> {
+    pub created: BlockNumber,
+    pub deposit: Balance,
+    pub friends: Vec<AccountId>,
+}
+
+pub type RecoveryConf<T> = RecoveryConfig<
+    <T as System>::BlockNumber,
+    BalanceOf<T>,
+    <T as System>::AccountId,
+>;
+pub type Attempt<T> = ActiveRecovery<
+    <T as System>::BlockNumber,
+    BalanceOf<T>,
+    <T as System>::AccountId,
+>;
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct RecoverableStore<'a, T: Recovery> {
+    #[store(returns = RecoveryConf<T>)]
+    pub lost: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ActiveRecoveriesStore<'a, T: Recovery> {
+    #[store(returns = Attempt<T>)]
+    pub lost: &'a <T as System>::AccountId,
+    pub rescuer: &'a <T as System>::AccountId,
+}
+
+// ~~ Calls and Events ~~
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct CreateRecoveryCall<T: Recovery> {
+    pub friends: Vec<<T as System>::AccountId>,
+    pub threshold: u16,
+    pub delay_period: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RecoveryCreatedEvent<T: Recovery> {
+    pub lost: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct InitiateRecoveryCall<'a, T: Recovery> {
+    pub lost: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RecoveryInitiatedEvent<T: Recovery> {
+    pub lost: <T as System>::AccountId,
+    pub rescuer: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct VouchRecoveryCall<'a, T: Recovery> {
+    pub lost: &'a <T as System>::AccountId,
+    pub rescuer: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct RecoveryVouchedEvent<T: Recovery> {
+    pub lost: <T as System>::AccountId,
+    pub rescuer: <T as System>::AccountId,
+    pub friend: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ClaimRecoveryCall<'a, T: Recovery> {
+    pub lost: &'a <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct AccountRecoveredEvent<T: Recovery> {
+    pub lost: <T as System>::AccountId,
+    pub rescuer: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct AsRecoveredCall<'a, T: Recovery> {
+    pub lost: &'a <T as System>::AccountId,
+    pub call: &'a Encoded,
+}
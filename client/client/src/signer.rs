@@ -0,0 +1,237 @@
+use parity_scale_codec::Encode;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+use substrate_subxt::{
+    sp_runtime::traits::SignedExtension,
+    system::System,
+    Runtime,
+    SignedExtra,
+    SignedPayload,
+    Signer,
+    UncheckedExtrinsic,
+};
+
+/// Signs extrinsic payloads with a key that never enters the keystore,
+/// e.g. a hardware wallet or the host application of the ffi
+pub trait ExternalSign<T: Runtime>: Send + Sync {
+    /// Returns the signature over the scale encoded signing payload
+    fn sign(&self, payload: &[u8]) -> Result<T::Signature, String>;
+}
+
+impl<T, F> ExternalSign<T> for F
+where
+    T: Runtime,
+    F: Fn(&[u8]) -> Result<T::Signature, String> + Send + Sync,
+{
+    fn sign(&self, payload: &[u8]) -> Result<T::Signature, String> {
+        self(payload)
+    }
+}
+
+/// A subxt `Signer` that delegates signing to an `ExternalSign` backend
+pub struct ExternalSigner<T: Runtime> {
+    account_id: <T as System>::AccountId,
+    nonce: Option<<T as System>::Index>,
+    backend: Arc<dyn ExternalSign<T>>,
+}
+
+impl<T: Runtime> Clone for ExternalSigner<T> {
+    fn clone(&self) -> Self {
+        Self {
+            account_id: self.account_id.clone(),
+            nonce: self.nonce,
+            backend: self.backend.clone(),
+        }
+    }
+}
+
+impl<T: Runtime> ExternalSigner<T> {
+    pub fn new(
+        account_id: <T as System>::AccountId,
+        backend: Arc<dyn ExternalSign<T>>,
+    ) -> Self {
+        Self {
+            account_id,
+            nonce: None,
+            backend,
+        }
+    }
+
+    /// Sets the nonce to a new value
+    pub fn set_nonce(&mut self, nonce: <T as System>::Index) {
+        self.nonce = Some(nonce);
+    }
+
+    /// Increment the nonce
+    pub fn increment_nonce(&mut self) {
+        self.nonce = self.nonce.map(|nonce| nonce + 1.into());
+    }
+}
+
+impl<T> Signer<T> for ExternalSigner<T>
+where
+    T: Runtime,
+    T::AccountId: Into<T::Address> + 'static,
+    <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+        Send + Sync,
+{
+    fn account_id(&self) -> &T::AccountId {
+        &self.account_id
+    }
+
+    fn nonce(&self) -> Option<T::Index> {
+        self.nonce
+    }
+
+    fn sign(
+        &self,
+        extrinsic: SignedPayload<T>,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<UncheckedExtrinsic<T>, String>>
+                + Send
+                + Sync,
+        >,
+    > {
+        let signature =
+            extrinsic.using_encoded(|payload| self.backend.sign(payload));
+        let (call, extra, _) = extrinsic.deconstruct();
+        let account_id = self.account_id.clone();
+        Box::pin(async move {
+            Ok(UncheckedExtrinsic::<T>::new_signed(
+                call,
+                account_id.into(),
+                signature?,
+                extra,
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libipld::cache::Cache;
+    use substrate_subxt::{
+        balances::{
+            TransferCallExt,
+            TransferEventExt,
+        },
+        sp_core::{
+            ecdsa,
+            ed25519,
+            sr25519,
+            Pair,
+        },
+        sp_runtime::{
+            MultiSignature,
+            MultiSigner,
+        },
+        PairSigner,
+    };
+    use test_client::{
+        bounty::{
+            BountyPostedEvent,
+            BountyPostedEventExt,
+            PostBountyCallExt,
+        },
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        utils::bounty::SubmissionKind,
+        DevClient as Client,
+        DevNode as Node,
+        GithubIssue,
+        Runtime,
+    };
+
+    #[async_std::test]
+    async fn external_signer_submits_extrinsics() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        // stands in for a device that only ever returns signatures
+        let pair = AccountKeyring::Alice.pair();
+        let backend = move |payload: &[u8]| -> Result<MultiSignature, String> {
+            Ok(pair.sign(payload).into())
+        };
+        let signer = ExternalSigner::<Runtime>::new(
+            AccountKeyring::Alice.to_account_id(),
+            Arc::new(backend),
+        );
+        let bounty = GithubIssue {
+            repo_owner: "sunshine-protocol".to_string(),
+            repo_name: "sunshine-bounty".to_string(),
+            issue_number: 127,
+        };
+        let issue = Encode::encode(&bounty);
+        let info = client.offchain_client().insert(bounty).await.unwrap();
+        let event = client
+            .chain_client()
+            .post_bounty_and_watch(
+                &signer,
+                issue,
+                info.into(),
+                10u128,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap()
+            .bounty_posted()
+            .unwrap()
+            .unwrap();
+        let expected_event = BountyPostedEvent {
+            depositer: AccountKeyring::Alice.to_account_id(),
+            amount: 10,
+            id: 1,
+            description: event.description,
+        };
+        assert_eq!(event, expected_event);
+    }
+
+    /// Funds the account of `pair` and sends a transfer signed with it, which
+    /// the node only includes if it verifies the signature
+    async fn transfer_signed_with<P>(client: &Client, pair: P)
+    where
+        P: Pair + 'static,
+        MultiSignature: From<P::Signature>,
+        MultiSigner: From<P::Public>,
+    {
+        let signer = PairSigner::<Runtime, P>::new(pair);
+        client
+            .chain_client()
+            .transfer_and_watch(
+                client.chain_signer().unwrap(),
+                signer.account_id(),
+                1_000_000_000_000,
+            )
+            .await
+            .unwrap();
+        let alice = AccountKeyring::Alice.to_account_id();
+        let event = client
+            .chain_client()
+            .transfer_and_watch(&signer, &alice, 1_000)
+            .await
+            .unwrap()
+            .transfer()
+            .unwrap()
+            .unwrap();
+        assert_eq!(&event.from, signer.account_id());
+    }
+
+    #[async_std::test]
+    async fn every_key_scheme_signs_extrinsics() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        transfer_signed_with(&client, sr25519::Pair::generate().0).await;
+        transfer_signed_with(&client, ed25519::Pair::generate().0).await;
+        transfer_signed_with(&client, ecdsa::Pair::generate().0).await;
+    }
+}
@@ -0,0 +1,1377 @@
+//! Extrinsic submission which reports how far each extrinsic got
+//!
+//! subxt's `*_and_watch` calls return once the extrinsic is in a block and
+//! drop the statuses the node sends on the way. `SubmitExt::watch` submits
+//! the signed extrinsic itself instead, so that its hash and every status
+//! reach the progress observer as they arrive, and it waits as long as
+//! `set_wait_for` asks, but no longer than `set_timeout` or the timeout
+//! passed to `watch_for`. The settings apply to every client of the
+//! process.
+//!
+//! Extrinsics which are not waited for resolve to the error recognized by
+//! `is_not_waited`, there are no events to decode for them. Extrinsics
+//! which time out resolve to the error recognized by `timed_out`, they may
+//! still be included and can be looked up with `transaction_status`.
+//!
+//! `SubmitExt::dry_run` applies a signed extrinsic to the best block with
+//! `system_dryRun` without submitting it. With `set_validate_before_submit`
+//! every submission is dry run first and aborts with the error recognized
+//! by `dry_run_failed` if it would fail, with `set_dry_run` submissions
+//! stop after the dry run, resolving to the error recognized by
+//! `is_dry_run` if it would succeed.
+//!
+//! The pool may drop a waited for extrinsic, or reject it as invalid once
+//! its era ran out. Up to `set_resubmissions` times it is then signed again
+//! with a fresh era and submitted, with the same nonce unless the chain
+//! used it already. Before that the blocks since the first submission are
+//! searched for the extrinsics submitted so far, one which was included
+//! after all is the result. Calls which move funds, see `is_hazardous`, are
+//! also looked for by their call data, so a transfer or contribution that
+//! landed with another nonce is not sent twice. The submission resolves to
+//! a single result, the resubmissions are reported to the progress
+//! observer.
+//!
+//! Nothing is submitted to a runtime the typed calls were not checked
+//! against, see `compat`.
+use crate::{
+    compat,
+    era::{
+        self,
+        transaction_era,
+    },
+    nonce::NoncedSigner,
+    vote::events_key,
+};
+use futures::future::{
+    self,
+    Either,
+};
+use futures_timer::Delay;
+use jsonrpsee::{
+    client::Subscription,
+    common::{
+        to_value as to_json_value,
+        Params,
+    },
+};
+use once_cell::sync::Lazy;
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::{
+        Arc,
+        RwLock,
+    },
+    time::Duration,
+};
+use substrate_subxt::{
+    sp_core::{
+        storage::StorageData,
+        Bytes,
+    },
+    sp_runtime::{
+        traits::{
+            Hash,
+            Header as _,
+            UniqueSaturatedFrom,
+            UniqueSaturatedInto,
+        },
+        transaction_validity::{
+            InvalidTransaction,
+            TransactionValidityError,
+        },
+        ApplyExtrinsicResult,
+        DispatchError,
+    },
+    system::{
+        AccountStoreExt,
+        Phase,
+        System,
+    },
+    Call,
+    Error,
+    EventSubscription,
+    ExtrinsicSuccess,
+    Metadata,
+    Raw,
+    Runtime,
+    RuntimeError,
+    SignedPayload,
+    Signer,
+    UncheckedExtrinsic,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+};
+
+const NOT_WAITED: &str = "extrinsic was submitted without waiting for it";
+
+const DRY_RUN: &str = "extrinsic was dry run without submitting it";
+
+/// How long submissions wait unless `set_timeout` says otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a dropped extrinsic is resubmitted unless
+/// `set_resubmissions` says otherwise
+pub const DEFAULT_RESUBMISSIONS: u32 = 2;
+
+/// The module and function of the calls which would move funds twice if
+/// they landed twice
+const HAZARDOUS: &[(&str, &str)] = &[
+    ("Balances", "transfer"),
+    ("Balances", "transfer_keep_alive"),
+    ("Bounty", "contribute_to_bounty"),
+    ("Donate", "make_prop_donation"),
+    ("Donate", "make_equal_donation"),
+];
+
+type Observer = Arc<dyn Fn(&ExtrinsicProgress) + Send + Sync>;
+
+static WAIT_FOR: Lazy<RwLock<WaitFor>> = Lazy::new(Default::default);
+
+static OBSERVER: Lazy<RwLock<Option<Observer>>> = Lazy::new(Default::default);
+
+static TIMEOUT: Lazy<RwLock<Duration>> =
+    Lazy::new(|| RwLock::new(DEFAULT_TIMEOUT));
+
+static VALIDATE: Lazy<RwLock<bool>> = Lazy::new(Default::default);
+
+static DRY_RUN_ONLY: Lazy<RwLock<bool>> = Lazy::new(Default::default);
+
+static RESUBMISSIONS: Lazy<RwLock<u32>> =
+    Lazy::new(|| RwLock::new(DEFAULT_RESUBMISSIONS));
+
+/// How long submitting an extrinsic waits for it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WaitFor {
+    /// Return once the node accepted the extrinsic
+    None,
+    /// Return once the extrinsic is in a block
+    InBlock,
+    /// Return once the block with the extrinsic is finalized
+    Finalized,
+}
+
+impl Default for WaitFor {
+    fn default() -> Self {
+        Self::InBlock
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unknown wait-for {0}, expected one of none, in-block, finalized")]
+pub struct UnknownWaitFor(pub String);
+
+impl FromStr for WaitFor {
+    type Err = UnknownWaitFor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "in-block" => Ok(Self::InBlock),
+            "finalized" => Ok(Self::Finalized),
+            _ => Err(UnknownWaitFor(s.to_string())),
+        }
+    }
+}
+
+/// The statuses the node reports while it handles an extrinsic
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionStatus<Hash> {
+    Future,
+    Ready,
+    Broadcast(Vec<String>),
+    InBlock(Hash),
+    Retracted(Hash),
+    FinalityTimeout(Hash),
+    Finalized(Hash),
+    Usurped(Hash),
+    Dropped,
+    Invalid,
+}
+
+impl<H: fmt::Debug> TransactionStatus<H> {
+    /// The status with its hashes rendered for display
+    fn rendered(&self) -> TransactionStatus<String> {
+        let hash = |hash: &H| format!("{:?}", hash);
+        match self {
+            Self::Future => TransactionStatus::Future,
+            Self::Ready => TransactionStatus::Ready,
+            Self::Broadcast(peers) => {
+                TransactionStatus::Broadcast(peers.clone())
+            }
+            Self::InBlock(block) => TransactionStatus::InBlock(hash(block)),
+            Self::Retracted(block) => TransactionStatus::Retracted(hash(block)),
+            Self::FinalityTimeout(block) => {
+                TransactionStatus::FinalityTimeout(hash(block))
+            }
+            Self::Finalized(block) => TransactionStatus::Finalized(hash(block)),
+            Self::Usurped(other) => TransactionStatus::Usurped(hash(other)),
+            Self::Dropped => TransactionStatus::Dropped,
+            Self::Invalid => TransactionStatus::Invalid,
+        }
+    }
+}
+
+impl fmt::Display for TransactionStatus<String> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Future => write!(f, "Future"),
+            Self::Ready => write!(f, "Ready"),
+            Self::Broadcast(peers) => {
+                write!(f, "Broadcast({} peers)", peers.len())
+            }
+            Self::InBlock(block) => write!(f, "InBlock({})", block),
+            Self::Retracted(block) => write!(f, "Retracted({})", block),
+            Self::FinalityTimeout(block) => {
+                write!(f, "FinalityTimeout({})", block)
+            }
+            Self::Finalized(block) => write!(f, "Finalized({})", block),
+            Self::Usurped(other) => write!(f, "Usurped({})", other),
+            Self::Dropped => write!(f, "Dropped"),
+            Self::Invalid => write!(f, "Invalid"),
+        }
+    }
+}
+
+/// How far an extrinsic got, reported to the progress observer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExtrinsicProgress {
+    /// The hash of the extrinsic, reported before it is submitted
+    Submitted(String),
+    /// A status update sent by the node
+    Status(TransactionStatus<String>),
+    /// The outcome of the dry run before submitting
+    DryRun(DryRunOutcome),
+    /// The pool dropped the extrinsic, it is signed and submitted again for
+    /// the nth time
+    Resubmitted(u32),
+}
+
+impl fmt::Display for ExtrinsicProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Submitted(hash) => write!(f, "Submitted extrinsic {}", hash),
+            Self::Status(status) => write!(f, "Status {}", status),
+            Self::DryRun(outcome) => write!(f, "Dry run {}", outcome),
+            Self::Resubmitted(attempt) => {
+                write!(f, "Dropped, resubmitting ({})", attempt)
+            }
+        }
+    }
+}
+
+/// Sets how long every following submission waits for its extrinsic
+pub fn set_wait_for(wait_for: WaitFor) {
+    *WAIT_FOR.write().unwrap() = wait_for;
+}
+
+/// Sets how long every following submission waits for the node at most
+pub fn set_timeout(timeout: Duration) {
+    *TIMEOUT.write().unwrap() = timeout;
+}
+
+/// Sets whether every following submission is dry run first, aborting if
+/// it would fail
+pub fn set_validate_before_submit(validate: bool) {
+    *VALIDATE.write().unwrap() = validate;
+}
+
+/// Sets whether every following submission stops after its dry run
+pub fn set_dry_run(dry_run: bool) {
+    *DRY_RUN_ONLY.write().unwrap() = dry_run;
+}
+
+/// Sets how often every following submission resubmits its extrinsic if
+/// the pool drops it, zero never resubmits
+pub fn set_resubmissions(resubmissions: u32) {
+    *RESUBMISSIONS.write().unwrap() = resubmissions;
+}
+
+/// Whether the call moves funds, so that it is looked for by its call data
+/// before it is resubmitted
+pub fn is_hazardous(module: &str, function: &str) -> bool {
+    HAZARDOUS.contains(&(module, function))
+}
+
+/// Reports the progress of every following submission to `observer`
+pub fn observe_progress(
+    observer: impl Fn(&ExtrinsicProgress) + Send + Sync + 'static,
+) {
+    *OBSERVER.write().unwrap() = Some(Arc::new(observer));
+}
+
+/// Whether the submission was not waited for, which is not a failure
+pub fn is_not_waited(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<Error>(),
+        Some(Error::Other(msg)) if msg == NOT_WAITED
+    )
+}
+
+/// Waits `limit` at most for `fut`, `Err` if it did not resolve by then.
+/// Unlike async-std's timeout this needs no runtime.
+async fn timeout<F: Future>(limit: Duration, fut: F) -> Result<F::Output, ()> {
+    futures::pin_mut!(fut);
+    match future::select(fut, Delay::new(limit)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(()),
+    }
+}
+
+/// The error of an extrinsic which timed out, the subxt error carries it as
+/// its message
+fn timeout_error(extrinsic_hash: String) -> Error {
+    Error::Other(crate::Error::Timeout { extrinsic_hash }.to_string())
+}
+
+/// The `Error::Timeout` of a submission which timed out, whether its
+/// extrinsic was included is left to `transaction_status`
+pub fn timed_out(
+    err: &(dyn std::error::Error + 'static),
+) -> Option<crate::Error> {
+    match err.downcast_ref::<Error>()? {
+        Error::Other(msg) => {
+            let hash = msg.strip_prefix(crate::error::TIMED_OUT)?;
+            Some(crate::Error::Timeout {
+                extrinsic_hash: hash.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The name of the module error which failed an extrinsic, decoded with
+/// the metadata of the chain
+pub fn module_error(err: &(dyn std::error::Error + 'static)) -> Option<String> {
+    match err.downcast_ref::<Error>()? {
+        Error::Runtime(RuntimeError::Module(err)) => {
+            Some(format!("{}::{}", err.module, err.error))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the submission stopped after a dry run which would not fail
+pub fn is_dry_run(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<Error>(),
+        Some(Error::Other(msg)) if msg == DRY_RUN
+    )
+}
+
+/// The error of an extrinsic which failed its dry run, the subxt error
+/// carries it as its message
+fn dry_run_error(outcome: &DryRunOutcome) -> Error {
+    Error::Other(
+        crate::Error::DryRunFailed {
+            reason: outcome.to_string(),
+        }
+        .to_string(),
+    )
+}
+
+/// The `Error::DryRunFailed` of a submission which was not submitted
+/// because its dry run failed
+pub fn dry_run_failed(
+    err: &(dyn std::error::Error + 'static),
+) -> Option<crate::Error> {
+    match err.downcast_ref::<Error>()? {
+        Error::Other(msg) => {
+            let reason = msg.strip_prefix(crate::error::DRY_RUN_FAILED)?;
+            Some(crate::Error::DryRunFailed {
+                reason: reason.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// What applying an extrinsic to the best block would do
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DryRunOutcome {
+    /// It would be included and succeed
+    Ok,
+    /// It would be included, paying its fees, but fail with the module
+    /// error
+    Module { module: String, error: String },
+    /// It would be included, paying its fees, but fail with an error which
+    /// is not a module error, like a bad origin
+    Dispatch(String),
+    /// It would wait in the pool for the extrinsics with lower nonces which
+    /// are not in the best block yet, the dry run can't tell more
+    Future,
+    /// It would not be included at all, like with a stale nonce or without
+    /// the balance to pay its fees
+    Invalid(String),
+}
+
+impl DryRunOutcome {
+    /// Decodes the result of `system_dryRun`, the module errors with the
+    /// metadata of the chain
+    pub fn from_result(
+        metadata: &Metadata,
+        result: ApplyExtrinsicResult,
+    ) -> Result<Self, Error> {
+        Ok(match result {
+            Ok(Ok(())) => Self::Ok,
+            Ok(Err(err)) => Self::from_dispatch(metadata, err)?,
+            Err(err) => Self::from_validity(err),
+        })
+    }
+
+    fn from_dispatch(
+        metadata: &Metadata,
+        err: DispatchError,
+    ) -> Result<Self, Error> {
+        Ok(match RuntimeError::from_dispatch(metadata, err)? {
+            RuntimeError::Module(err) => {
+                Self::Module {
+                    module: err.module,
+                    error: err.error,
+                }
+            }
+            err => Self::Dispatch(err.to_string()),
+        })
+    }
+
+    fn from_validity(err: TransactionValidityError) -> Self {
+        match err {
+            TransactionValidityError::Invalid(InvalidTransaction::Future) => {
+                Self::Future
+            }
+            err => Self::Invalid(<&'static str>::from(err).to_string()),
+        }
+    }
+
+    /// Whether the extrinsic would succeed
+    pub fn is_ok(&self) -> bool {
+        *self == Self::Ok
+    }
+
+    /// Whether the extrinsic should not be submitted, an extrinsic which
+    /// would wait for lower nonces is left to the pool
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Self::Ok | Self::Future)
+    }
+}
+
+impl fmt::Display for DryRunOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "Ok"),
+            Self::Module { module, error } => {
+                write!(f, "{}::{}", module, error)
+            }
+            Self::Dispatch(err) => write!(f, "{}", err),
+            Self::Future => write!(f, "Future"),
+            Self::Invalid(reason) => write!(f, "Invalid {}", reason),
+        }
+    }
+}
+
+/// Applies the signed `extrinsic` to the best block without submitting it
+async fn dry_run_extrinsic<T: substrate_subxt::Runtime, E: Encode>(
+    chain: &substrate_subxt::Client<T>,
+    extrinsic: &E,
+) -> Result<DryRunOutcome, Error> {
+    let bytes = to_json_value(Bytes::from(extrinsic.encode()))
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let result: Bytes = chain
+        .rpc_client()
+        .request("system_dryRun", Params::Array(vec![bytes]))
+        .await?;
+    let result = ApplyExtrinsicResult::decode(&mut &result[..])?;
+    DryRunOutcome::from_result(chain.metadata(), result)
+}
+
+fn report(progress: ExtrinsicProgress) {
+    let observer = OBSERVER.read().unwrap().clone();
+    if let Some(observer) = observer {
+        observer(&progress);
+    }
+}
+
+/// The statuses the node sends for a submitted extrinsic
+#[async_trait]
+trait StatusStream<H> {
+    async fn next_status(&mut self) -> TransactionStatus<H>;
+}
+
+#[async_trait]
+impl<H: DeserializeOwned + Send> StatusStream<H>
+    for Subscription<TransactionStatus<H>>
+{
+    async fn next_status(&mut self) -> TransactionStatus<H> {
+        self.next().await
+    }
+}
+
+/// Reports the statuses until the extrinsic got as far as `wait_for`,
+/// returning the block it is in
+async fn included<H: fmt::Debug + Send, S: StatusStream<H> + Send>(
+    statuses: &mut S,
+    wait_for: WaitFor,
+) -> Result<H, Error> {
+    loop {
+        let status = statuses.next_status().await;
+        let rendered = status.rendered();
+        report(ExtrinsicProgress::Status(rendered.clone()));
+        match status {
+            TransactionStatus::InBlock(block)
+                if wait_for == WaitFor::InBlock =>
+            {
+                return Ok(block)
+            }
+            TransactionStatus::Finalized(block) => return Ok(block),
+            TransactionStatus::Future
+            | TransactionStatus::Ready
+            | TransactionStatus::Broadcast(_)
+            | TransactionStatus::InBlock(_)
+            | TransactionStatus::Retracted(_) => continue,
+            TransactionStatus::FinalityTimeout(_)
+            | TransactionStatus::Usurped(_)
+            | TransactionStatus::Dropped
+            | TransactionStatus::Invalid => {
+                return Err(Error::Other(format!("Extrinsic {}", rendered)))
+            }
+        }
+    }
+}
+
+/// Whether `included` failed because the pool dropped the extrinsic or
+/// found it invalid, like once its era ran out
+fn dropped(err: &Error) -> bool {
+    match err {
+        Error::Other(msg) => {
+            [TransactionStatus::Dropped, TransactionStatus::Invalid]
+                .iter()
+                .any(|status| *msg == format!("Extrinsic {}", status))
+        }
+        _ => false,
+    }
+}
+
+/// Whether the encoded `extrinsic` was signed by the encoded `account` for
+/// the encoded `call`, which signed extrinsics end with
+fn carries(extrinsic: &[u8], account: &[u8], call: &[u8]) -> bool {
+    extrinsic.ends_with(call)
+        && extrinsic
+            .windows(account.len().max(1))
+            .any(|window| window == account)
+}
+
+/// Where the extrinsic of a submission ended up
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Inclusion<H> {
+    /// The last extrinsic submitted got as far as awaited, its events are
+    /// sent to the subscription made before submitting it
+    Watched { block: H, extrinsic: H },
+    /// An extrinsic of the submission found in a block after the pool
+    /// dropped a later one, at `index` in the block
+    Found { block: H, extrinsic: H, index: u32 },
+}
+
+/// The chain as seen by a submission which resubmits dropped extrinsics
+#[async_trait]
+trait Submission<H> {
+    type Statuses: StatusStream<H> + Send;
+
+    /// Signs the call with `nonce`, or the nonce of the chain if none, and
+    /// submits it, returning the hash of the extrinsic and its statuses
+    async fn submit(
+        &mut self,
+        nonce: Option<u64>,
+    ) -> Result<(H, Self::Statuses), Error>;
+
+    /// The nonce of the signer on chain
+    async fn chain_nonce(&self) -> Result<u64, Error>;
+
+    /// The block, hash and index of an extrinsic included since the first
+    /// submission which is one of `extrinsics`, or with `by_call` any
+    /// extrinsic of the signer for the call
+    async fn find(
+        &self,
+        extrinsics: &[H],
+        by_call: bool,
+    ) -> Result<Option<(H, H, u32)>, Error>;
+}
+
+/// Submits until an extrinsic got as far as `wait_for`, resubmitting it up
+/// to `resubmissions` times when it is dropped, with the hashes of the
+/// extrinsics submitted collected in `extrinsics`
+async fn resubmitting<H, S>(
+    submission: &mut S,
+    wait_for: WaitFor,
+    nonce: u64,
+    hazardous: bool,
+    resubmissions: u32,
+    extrinsics: &mut Vec<H>,
+) -> Result<Inclusion<H>, Error>
+where
+    H: Clone + fmt::Debug + Send + Sync,
+    S: Submission<H> + Send,
+{
+    let mut next = Some(nonce);
+    let mut attempt = 0;
+    loop {
+        let (extrinsic, mut statuses) = submission.submit(next).await?;
+        extrinsics.push(extrinsic.clone());
+        match included(&mut statuses, wait_for).await {
+            Ok(block) => return Ok(Inclusion::Watched { block, extrinsic }),
+            Err(err) if attempt < resubmissions && dropped(&err) => {}
+            Err(err) => return Err(err),
+        }
+        // a block author may have included it before the pool dropped it
+        if let Some((block, extrinsic, index)) =
+            submission.find(extrinsics, hazardous).await?
+        {
+            return Ok(Inclusion::Found {
+                block,
+                extrinsic,
+                index,
+            })
+        }
+        // another extrinsic of the signer used up the nonce
+        if submission.chain_nonce().await? > nonce {
+            next = None;
+        }
+        attempt += 1;
+        report(ExtrinsicProgress::Resubmitted(attempt));
+    }
+}
+
+/// The nonced signer of a submission, signing its resubmissions with the
+/// nonce of the chain once the reserved one was used up
+struct Resigner<'a, 'b, T: Runtime> {
+    inner: &'a NoncedSigner<'b, T>,
+    nonce: Option<T::Index>,
+}
+
+impl<'a, 'b, T: Runtime> Signer<T> for Resigner<'a, 'b, T> {
+    fn account_id(&self) -> &T::AccountId {
+        self.inner.account_id()
+    }
+
+    fn nonce(&self) -> Option<T::Index> {
+        self.nonce
+    }
+
+    fn sign(
+        &self,
+        extrinsic: SignedPayload<T>,
+    ) -> Pin<
+        Box<
+            dyn Future<
+                    Output = std::result::Result<UncheckedExtrinsic<T>, String>,
+                > + Send
+                + Sync,
+        >,
+    > {
+        self.inner.sign(extrinsic)
+    }
+}
+
+/// A submission of `call` to the node of `chain`
+struct ChainSubmission<'a, 'b, T: Runtime, C> {
+    chain: &'a substrate_subxt::Client<T>,
+    call: C,
+    signer: &'a NoncedSigner<'b, T>,
+    wait_for: WaitFor,
+    /// The extrinsic signed before the dry run, submitted first
+    first: Option<UncheckedExtrinsic<T>>,
+    /// The best block before the first submission
+    since: u64,
+    /// The events of the blocks after the last submission
+    events: Option<EventSubscription<T>>,
+}
+
+#[async_trait]
+impl<'a, 'b, T, C> Submission<T::Hash> for ChainSubmission<'a, 'b, T, C>
+where
+    T: Runtime,
+    C: Call<T> + Clone + Send + Sync,
+{
+    type Statuses = Subscription<TransactionStatus<T::Hash>>;
+
+    async fn submit(
+        &mut self,
+        nonce: Option<u64>,
+    ) -> Result<(T::Hash, Self::Statuses), Error> {
+        let extrinsic = match self.first.take() {
+            Some(extrinsic) => extrinsic,
+            None => {
+                era::checkpoint(self.chain).await?;
+                let signer = Resigner {
+                    inner: self.signer,
+                    nonce: nonce.map(T::Index::unique_saturated_from),
+                };
+                self.chain.create_signed(self.call.clone(), &signer).await?
+            }
+        };
+        let hash = T::Hashing::hash_of(&extrinsic);
+        report(ExtrinsicProgress::Submitted(format!("{:?}", hash)));
+        // subscribe first so that no events of the block are missed
+        let events = if self.wait_for == WaitFor::Finalized {
+            self.chain.subscribe_finalized_events().await?
+        } else {
+            self.chain.subscribe_events().await?
+        };
+        self.events =
+            Some(EventSubscription::new(events, self.chain.events_decoder()));
+        let bytes = to_json_value(Bytes::from(extrinsic.encode()))
+            .map_err(|err| Error::Other(err.to_string()))?;
+        let statuses = self
+            .chain
+            .rpc_client()
+            .subscribe(
+                "author_submitAndWatchExtrinsic",
+                Params::Array(vec![bytes]),
+                "author_unwatchExtrinsic",
+            )
+            .await?;
+        Ok((hash, statuses))
+    }
+
+    async fn chain_nonce(&self) -> Result<u64, Error> {
+        let account = self.signer.account_id();
+        let info = self.chain.account(account, None).await?;
+        Ok(info.nonce.unique_saturated_into())
+    }
+
+    async fn find(
+        &self,
+        extrinsics: &[T::Hash],
+        by_call: bool,
+    ) -> Result<Option<(T::Hash, T::Hash, u32)>, Error> {
+        let account = self.signer.account_id().encode();
+        let call = if by_call {
+            Some(self.chain.encode(self.call.clone())?.0)
+        } else {
+            None
+        };
+        let mut next = self.chain.block_hash(None).await?;
+        while let Some(block) = next {
+            let signed = match self.chain.block(Some(block)).await? {
+                Some(signed) => signed.block,
+                None => break,
+            };
+            let number: u64 = (*signed.header.number()).unique_saturated_into();
+            if number <= self.since {
+                break
+            }
+            for (index, ext) in signed.extrinsics.iter().enumerate() {
+                let hash = T::Hashing::hash_of(ext);
+                let found = extrinsics.contains(&hash)
+                    || call.as_ref().map_or(false, |call| {
+                        carries(&ext.encode(), &account, call)
+                    });
+                if found {
+                    return Ok(Some((block, hash, index as u32)))
+                }
+            }
+            next = Some(*signed.header.parent_hash());
+        }
+        Ok(None)
+    }
+}
+
+/// The events of the extrinsic at `index` in `block`, read from the storage
+/// of the block since the subscription may have missed it
+async fn block_events<T: Runtime>(
+    chain: &substrate_subxt::Client<T>,
+    block: T::Hash,
+    index: u32,
+) -> Result<Vec<substrate_subxt::RawEvent>, Error> {
+    let key = to_json_value(events_key())
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let at =
+        to_json_value(block).map_err(|err| Error::Other(err.to_string()))?;
+    let events: Option<StorageData> = chain
+        .rpc_client()
+        .request("state_getStorage", Params::Array(vec![key, at]))
+        .await?;
+    let events = events.map(|events| events.0).unwrap_or_default();
+    let mut raw = vec![];
+    let decoded = chain.events_decoder().decode_events(&mut &events[..])?;
+    for (phase, event) in decoded {
+        match (phase, event) {
+            (Phase::ApplyExtrinsic(i), Raw::Event(event)) if i == index => {
+                raw.push(event)
+            }
+            (Phase::ApplyExtrinsic(i), Raw::Error(err)) if i == index => {
+                return Err(Error::Runtime(err))
+            }
+            _ => {}
+        }
+    }
+    Ok(raw)
+}
+
+#[async_trait]
+pub trait SubmitExt<N: Node>: Client<N> {
+    /// Signs and submits `call`, resolving once it got as far as the
+    /// configured `WaitFor` or the configured timeout passed, resubmitting
+    /// it if the pool drops it
+    async fn watch<C: Call<N::Runtime> + Clone + Send + Sync>(
+        &self,
+        call: C,
+        signer: &NoncedSigner<'_, N::Runtime>,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>, Error>;
+
+    /// `watch` with its own timeout
+    async fn watch_for<C: Call<N::Runtime> + Clone + Send + Sync>(
+        &self,
+        call: C,
+        signer: &NoncedSigner<'_, N::Runtime>,
+        timeout: Duration,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>, Error>;
+
+    /// Signs `call` with the nonce of the chain and applies it to the best
+    /// block without submitting it
+    async fn dry_run<C: Call<N::Runtime> + Send + Sync>(
+        &self,
+        call: C,
+    ) -> Result<DryRunOutcome, Error>;
+
+    /// Where the extrinsic with the hash `extrinsic` is, `Finalized` or
+    /// `InBlock` with its block or `None` if it is in none of the blocks it
+    /// could be in with the configured era
+    async fn transaction_status(
+        &self,
+        extrinsic: <N::Runtime as System>::Hash,
+    ) -> Result<Option<TransactionStatus<<N::Runtime as System>::Hash>>, Error>;
+}
+
+#[async_trait]
+impl<N: Node, T: Client<N>> SubmitExt<N> for T {
+    async fn watch<C: Call<N::Runtime> + Clone + Send + Sync>(
+        &self,
+        call: C,
+        signer: &NoncedSigner<'_, N::Runtime>,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>, Error> {
+        let limit = *TIMEOUT.read().unwrap();
+        self.watch_for(call, signer, limit).await
+    }
+
+    async fn watch_for<C: Call<N::Runtime> + Clone + Send + Sync>(
+        &self,
+        call: C,
+        signer: &NoncedSigner<'_, N::Runtime>,
+        limit: Duration,
+    ) -> Result<ExtrinsicSuccess<N::Runtime>, Error> {
+        let chain = self.chain_client();
+        compat::ensure_supported_runtime(chain, C::MODULE).await?;
+        era::checkpoint(chain).await?;
+        let extrinsic = chain.create_signed(call.clone(), signer).await?;
+        let dry_run = *DRY_RUN_ONLY.read().unwrap();
+        if dry_run || *VALIDATE.read().unwrap() {
+            let outcome = dry_run_extrinsic(chain, &extrinsic).await?;
+            report(ExtrinsicProgress::DryRun(outcome.clone()));
+            if outcome.is_failure() {
+                return Err(dry_run_error(&outcome))
+            }
+            if dry_run {
+                return Err(Error::Other(DRY_RUN.into()))
+            }
+        }
+        let hash = <N::Runtime as System>::Hashing::hash_of(&extrinsic);
+        let wait_for = *WAIT_FOR.read().unwrap();
+        if wait_for == WaitFor::None {
+            report(ExtrinsicProgress::Submitted(format!("{:?}", hash)));
+            chain.submit_extrinsic(extrinsic).await?;
+            return Err(Error::Other(NOT_WAITED.into()))
+        }
+        let since: u64 = chain
+            .header(None::<<N::Runtime as System>::Hash>)
+            .await?
+            .map(|header| (*header.number()).unique_saturated_into())
+            .unwrap_or_default();
+        let nonce = signer
+            .nonce()
+            .map(UniqueSaturatedInto::unique_saturated_into)
+            .unwrap_or_default();
+        let mut submission = ChainSubmission {
+            chain,
+            call,
+            signer,
+            wait_for,
+            first: Some(extrinsic),
+            since,
+            events: None,
+        };
+        let mut extrinsics = vec![];
+        // dropping the statuses on timeout unwatches the extrinsic
+        let inclusion = timeout(
+            limit,
+            resubmitting(
+                &mut submission,
+                wait_for,
+                nonce,
+                is_hazardous(C::MODULE, C::FUNCTION),
+                *RESUBMISSIONS.read().unwrap(),
+                &mut extrinsics,
+            ),
+        )
+        .await;
+        let last = extrinsics.last().unwrap_or(&hash);
+        let (block, hash) = match inclusion
+            .map_err(|_| timeout_error(format!("{:?}", last)))??
+        {
+            Inclusion::Watched { block, extrinsic } => (block, extrinsic),
+            Inclusion::Found {
+                block,
+                extrinsic,
+                index,
+            } => {
+                return Ok(ExtrinsicSuccess {
+                    block,
+                    extrinsic,
+                    events: block_events(chain, block, index).await?,
+                })
+            }
+        };
+        let extrinsics = chain
+            .block(Some(block))
+            .await?
+            .ok_or_else(|| {
+                Error::Other(format!("Block {:?} not found", block))
+            })?
+            .block
+            .extrinsics;
+        let index = extrinsics
+            .iter()
+            .position(|ext| {
+                <N::Runtime as System>::Hashing::hash_of(ext) == hash
+            })
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Extrinsic {:?} not found in block {:?}",
+                    hash, block
+                ))
+            })?;
+        let mut sub = submission.events.take().ok_or_else(|| {
+            Error::Other("Extrinsic submitted without events".into())
+        })?;
+        sub.filter_extrinsic(block, index);
+        let mut events = vec![];
+        while let Some(event) = sub.next().await {
+            events.push(event?);
+        }
+        Ok(ExtrinsicSuccess {
+            block,
+            extrinsic: hash,
+            events,
+        })
+    }
+
+    async fn dry_run<C: Call<N::Runtime> + Send + Sync>(
+        &self,
+        call: C,
+    ) -> Result<DryRunOutcome, Error> {
+        let chain = self.chain_client();
+        let signer = self
+            .chain_signer()
+            .map_err(|err| Error::Other(err.to_string()))?;
+        era::checkpoint(chain).await?;
+        let extrinsic = chain.create_signed(call, signer).await?;
+        dry_run_extrinsic(chain, &extrinsic).await
+    }
+
+    async fn transaction_status(
+        &self,
+        extrinsic: <N::Runtime as System>::Hash,
+    ) -> Result<Option<TransactionStatus<<N::Runtime as System>::Hash>>, Error>
+    {
+        let chain = self.chain_client();
+        let finalized = chain.finalized_head().await?;
+        let finalized: u64 = chain
+            .header(Some(finalized))
+            .await?
+            .map(|header| (*header.number()).unique_saturated_into())
+            .unwrap_or_default();
+        // walk back from the best block, which also covers the blocks
+        // which are not finalized yet
+        let mut next = chain.block_hash(None).await?;
+        for _ in 0..transaction_era().lookback() {
+            let block = match next {
+                Some(block) => block,
+                None => break,
+            };
+            let signed = match chain.block(Some(block)).await? {
+                Some(signed) => signed.block,
+                None => break,
+            };
+            let number: u64 = (*signed.header.number()).unique_saturated_into();
+            if signed.extrinsics.iter().any(|ext| {
+                <N::Runtime as System>::Hashing::hash_of(ext) == extrinsic
+            }) {
+                let status = if number <= finalized {
+                    TransactionStatus::Finalized(block)
+                } else {
+                    TransactionStatus::InBlock(block)
+                };
+                return Ok(Some(status))
+            }
+            if number == 0 {
+                break
+            }
+            next = Some(*signed.header.parent_hash());
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_metadata::{
+        DecodeDifferent,
+        ExtrinsicMetadata,
+        RuntimeMetadata,
+        RuntimeMetadataPrefixed,
+        RuntimeMetadataV12,
+        META_RESERVED,
+    };
+    use std::convert::TryFrom;
+
+    #[test]
+    fn wait_for_parses_the_cli_values() {
+        assert_eq!("none".parse::<WaitFor>().unwrap(), WaitFor::None);
+        assert_eq!("in-block".parse::<WaitFor>().unwrap(), WaitFor::InBlock);
+        assert_eq!("finalized".parse::<WaitFor>().unwrap(), WaitFor::Finalized);
+        assert!("inblock".parse::<WaitFor>().is_err());
+    }
+
+    #[test]
+    fn statuses_decode_from_the_rpc_layout() {
+        let status: TransactionStatus<String> =
+            serde_json::from_str(r#"{"inBlock":"0x01"}"#).unwrap();
+        assert_eq!(status, TransactionStatus::InBlock("0x01".into()));
+        let status: TransactionStatus<String> =
+            serde_json::from_str(r#""ready""#).unwrap();
+        assert_eq!(status.to_string(), "Ready");
+        assert!(is_not_waited(&Error::Other(NOT_WAITED.into())));
+        assert!(!is_not_waited(&Error::Other("Extrinsic Dropped".into())));
+    }
+
+    /// The metadata of a runtime without modules
+    fn empty_metadata() -> Metadata {
+        let metadata = RuntimeMetadataPrefixed(
+            META_RESERVED,
+            RuntimeMetadata::V12(RuntimeMetadataV12 {
+                modules: DecodeDifferent::Decoded(vec![]),
+                extrinsic: ExtrinsicMetadata {
+                    version: 4,
+                    signed_extensions: vec![],
+                },
+            }),
+        );
+        Metadata::try_from(metadata).unwrap()
+    }
+
+    #[test]
+    fn dry_run_results_decode_without_module_errors() {
+        let metadata = empty_metadata();
+        let outcome = DryRunOutcome::from_result(&metadata, Ok(Ok(())));
+        assert!(outcome.unwrap().is_ok());
+        let outcome = DryRunOutcome::from_result(
+            &metadata,
+            Ok(Err(DispatchError::BadOrigin)),
+        )
+        .unwrap();
+        assert!(matches!(outcome, DryRunOutcome::Dispatch(_)));
+        assert!(outcome.is_failure());
+        let payment = InvalidTransaction::Payment.into();
+        let outcome =
+            DryRunOutcome::from_result(&metadata, Err(payment)).unwrap();
+        assert_eq!(
+            outcome,
+            DryRunOutcome::Invalid(<&str>::from(payment).to_string())
+        );
+        let future = InvalidTransaction::Future.into();
+        let outcome =
+            DryRunOutcome::from_result(&metadata, Err(future)).unwrap();
+        assert!(!outcome.is_ok() && !outcome.is_failure());
+    }
+
+    #[test]
+    fn failed_dry_runs_are_recognized() {
+        let err = dry_run_error(&DryRunOutcome::Module {
+            module: "Bounty".into(),
+            error: "ContributionMustExceedModuleMin".into(),
+        });
+        assert!(matches!(
+            dry_run_failed(&err),
+            Some(crate::Error::DryRunFailed { reason })
+                if reason == "Bounty::ContributionMustExceedModuleMin"
+        ));
+        assert!(!is_dry_run(&err));
+        assert!(is_dry_run(&Error::Other(DRY_RUN.into())));
+        assert!(dry_run_failed(&Error::Other(DRY_RUN.into())).is_none());
+    }
+
+    /// A node which sends the statuses and then stops responding
+    struct MockStatuses(Vec<TransactionStatus<u8>>);
+
+    #[async_trait]
+    impl StatusStream<u8> for MockStatuses {
+        async fn next_status(&mut self) -> TransactionStatus<u8> {
+            if self.0.is_empty() {
+                async_std::future::pending().await
+            } else {
+                self.0.remove(0)
+            }
+        }
+    }
+
+    #[async_std::test]
+    async fn statuses_are_read_until_the_awaited_one() {
+        let mut statuses = MockStatuses(vec![
+            TransactionStatus::Ready,
+            TransactionStatus::InBlock(1),
+            TransactionStatus::Finalized(1),
+        ]);
+        let block = included(&mut statuses, WaitFor::Finalized).await;
+        assert_eq!(block.unwrap(), 1);
+        let mut statuses = MockStatuses(vec![
+            TransactionStatus::Ready,
+            TransactionStatus::Dropped,
+        ]);
+        assert!(included(&mut statuses, WaitFor::InBlock).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn unresponsive_nodes_time_out() {
+        let mut statuses = MockStatuses(vec![TransactionStatus::Ready]);
+        let limit = Duration::from_millis(50);
+        let result = timeout(limit, included(&mut statuses, WaitFor::InBlock))
+            .await
+            .map_err(|_| timeout_error("0x01".into()));
+        let err = result.unwrap_err();
+        assert!(matches!(
+            timed_out(&err),
+            Some(crate::Error::Timeout { extrinsic_hash }) if extrinsic_hash == "0x01"
+        ));
+        assert!(timed_out(&Error::Other(NOT_WAITED.into())).is_none());
+        assert!(!is_not_waited(&err));
+    }
+
+    /// A node which sends the statuses of each submission in turn, with
+    /// `landed` in a block once the pool dropped an extrinsic
+    struct MockSubmission {
+        statuses: Vec<Vec<TransactionStatus<u8>>>,
+        /// The nonces the extrinsics were signed with
+        submitted: Vec<Option<u64>>,
+        nonce: u64,
+        /// The block, hash and index of an included extrinsic and whether
+        /// it is only found by its call
+        landed: Option<(u8, u8, u32, bool)>,
+    }
+
+    impl MockSubmission {
+        fn new(statuses: Vec<Vec<TransactionStatus<u8>>>) -> Self {
+            Self {
+                statuses,
+                submitted: vec![],
+                nonce: 5,
+                landed: None,
+            }
+        }
+
+        async fn run(
+            &mut self,
+            hazardous: bool,
+            resubmissions: u32,
+        ) -> Result<Inclusion<u8>, Error> {
+            resubmitting(
+                self,
+                WaitFor::InBlock,
+                5,
+                hazardous,
+                resubmissions,
+                &mut vec![],
+            )
+            .await
+        }
+    }
+
+    #[async_trait]
+    impl Submission<u8> for MockSubmission {
+        type Statuses = MockStatuses;
+
+        async fn submit(
+            &mut self,
+            nonce: Option<u64>,
+        ) -> Result<(u8, MockStatuses), Error> {
+            self.submitted.push(nonce);
+            let hash = 10 + self.submitted.len() as u8;
+            Ok((hash, MockStatuses(self.statuses.remove(0))))
+        }
+
+        async fn chain_nonce(&self) -> Result<u64, Error> {
+            Ok(self.nonce)
+        }
+
+        async fn find(
+            &self,
+            extrinsics: &[u8],
+            by_call: bool,
+        ) -> Result<Option<(u8, u8, u32)>, Error> {
+            Ok(self.landed.and_then(|(block, hash, index, call_only)| {
+                let found = if call_only {
+                    by_call
+                } else {
+                    extrinsics.contains(&hash)
+                };
+                if found {
+                    Some((block, hash, index))
+                } else {
+                    None
+                }
+            }))
+        }
+    }
+
+    fn dropped_then(status: TransactionStatus<u8>) -> MockSubmission {
+        MockSubmission::new(vec![
+            vec![TransactionStatus::Ready, TransactionStatus::Dropped],
+            vec![TransactionStatus::Ready, status],
+        ])
+    }
+
+    #[async_std::test]
+    async fn dropped_extrinsics_are_resubmitted_with_their_nonce() {
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        let inclusion = chain.run(false, 2).await.unwrap();
+        assert_eq!(
+            inclusion,
+            Inclusion::Watched {
+                block: 7,
+                extrinsic: 12
+            }
+        );
+        assert_eq!(chain.submitted, vec![Some(5), Some(5)]);
+        // extrinsics invalid once their era ran out are signed again
+        let mut chain = MockSubmission::new(vec![
+            vec![TransactionStatus::Invalid],
+            vec![TransactionStatus::InBlock(7)],
+        ]);
+        assert!(chain.run(false, 2).await.is_ok());
+        assert_eq!(chain.submitted.len(), 2);
+    }
+
+    #[async_std::test]
+    async fn dropped_extrinsics_which_landed_are_not_resubmitted() {
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        chain.landed = Some((3, 11, 1, false));
+        let inclusion = chain.run(false, 2).await.unwrap();
+        assert_eq!(
+            inclusion,
+            Inclusion::Found {
+                block: 3,
+                extrinsic: 11,
+                index: 1
+            }
+        );
+        assert_eq!(chain.submitted, vec![Some(5)]);
+        // the resubmitted extrinsic is found once it was dropped as well
+        let mut chain = MockSubmission::new(vec![
+            vec![TransactionStatus::Dropped],
+            vec![TransactionStatus::Dropped],
+            vec![TransactionStatus::InBlock(7)],
+        ]);
+        chain.landed = Some((3, 12, 0, false));
+        let inclusion = chain.run(false, 2).await.unwrap();
+        assert!(matches!(inclusion, Inclusion::Found { extrinsic: 12, .. }));
+        assert_eq!(chain.submitted.len(), 2);
+    }
+
+    #[async_std::test]
+    async fn used_nonces_are_not_signed_again() {
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        chain.nonce = 6;
+        assert!(chain.run(false, 2).await.is_ok());
+        assert_eq!(chain.submitted, vec![Some(5), None]);
+    }
+
+    #[async_std::test]
+    async fn hazardous_calls_are_looked_for_by_their_call() {
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        chain.nonce = 6;
+        chain.landed = Some((3, 20, 0, true));
+        let inclusion = chain.run(false, 2).await.unwrap();
+        // other calls are only looked for by their extrinsics
+        assert!(matches!(inclusion, Inclusion::Watched { .. }));
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        chain.nonce = 6;
+        chain.landed = Some((3, 20, 0, true));
+        let inclusion = chain.run(true, 2).await.unwrap();
+        assert_eq!(
+            inclusion,
+            Inclusion::Found {
+                block: 3,
+                extrinsic: 20,
+                index: 0
+            }
+        );
+        assert_eq!(chain.submitted, vec![Some(5)]);
+        assert!(is_hazardous("Bounty", "contribute_to_bounty"));
+        assert!(is_hazardous("Balances", "transfer"));
+        assert!(!is_hazardous("Vote", "submit_vote"));
+    }
+
+    #[async_std::test]
+    async fn resubmissions_stop_at_the_configured_count() {
+        let mut chain = dropped_then(TransactionStatus::Dropped);
+        let err = chain.run(false, 1).await.unwrap_err();
+        assert!(dropped(&err));
+        assert_eq!(chain.submitted.len(), 2);
+        let mut chain = dropped_then(TransactionStatus::InBlock(7));
+        assert!(chain.run(false, 0).await.is_err());
+        assert_eq!(chain.submitted.len(), 1);
+    }
+
+    #[async_std::test]
+    async fn usurped_extrinsics_are_not_resubmitted() {
+        let mut chain = MockSubmission::new(vec![vec![
+            TransactionStatus::Ready,
+            TransactionStatus::Usurped(2),
+        ]]);
+        let err = chain.run(true, 2).await.unwrap_err();
+        assert!(!dropped(&err));
+        assert_eq!(chain.submitted.len(), 1);
+        let err = Error::Other("Extrinsic FinalityTimeout(0x01)".into());
+        assert!(!dropped(&err));
+    }
+
+    #[test]
+    fn extrinsics_carry_the_call_of_their_signer() {
+        let account = [1u8; 32];
+        let call = [9u8, 0, 42];
+        let mut extrinsic = vec![0x84, 0x00];
+        extrinsic.extend_from_slice(&account);
+        extrinsic.extend_from_slice(&[7; 64]);
+        extrinsic.extend_from_slice(&call);
+        assert!(carries(&extrinsic, &account, &call));
+        assert!(!carries(&extrinsic, &[2; 32], &call));
+        assert!(!carries(&extrinsic, &account, &[9, 0, 43]));
+        assert_eq!(
+            ExtrinsicProgress::Resubmitted(1).to_string(),
+            "Dropped, resubmitting (1)"
+        );
+    }
+}
@@ -1,24 +1,86 @@
+mod receipt;
 mod subxt;
+mod text;
+mod threshold;
 
+pub use receipt::*;
 pub use subxt::*;
+pub use text::*;
+pub use threshold::*;
 
 use crate::{
     error::Error,
-    org::Org,
+    metrics::{
+        DecodedExt,
+        Op,
+        TracedExt,
+    },
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    org::{
+        BalanceOf,
+        MembersStoreExt,
+        Org,
+        SnapshotSharesStoreExt,
+    },
+    page::{
+        KeyHasher,
+        StoragePage,
+        DEFAULT_PAGE_SIZE,
+    },
+    submit::SubmitExt,
 };
+use futures::{
+    pin_mut,
+    TryStreamExt,
+};
+use jsonrpsee::common::Params;
 use libipld::{
     cache::Cache,
     cbor::DagCborCodec,
 };
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use serde_json::to_value;
+use sp_runtime::traits::{
+    Header as _,
+    Zero,
+};
 use substrate_subxt::{
-    system::System,
+    sp_core::{
+        blake2_128,
+        storage::StorageData,
+    },
+    sp_runtime,
+    system::{
+        Phase,
+        System,
+    },
+    Raw,
     Runtime,
     SignedExtension,
     SignedExtra,
+    Store,
 };
 use sunshine_bounty_utils::{
     organization::OrgRep,
-    vote::Threshold,
+    traits::VoteVector as _,
+    vote::{
+        BallotStatus,
+        Composition,
+        LockReason,
+        PercentBase,
+        Threshold,
+        TieBreak,
+        VoteOutcome,
+        VoteProgress,
+        VoterView,
+        XorThreshold,
+    },
 };
 use sunshine_client_utils::{
     async_trait,
@@ -28,6 +90,26 @@ use sunshine_client_utils::{
     Result,
 };
 
+/// The current vote of a voter with their latest ballots, oldest first
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ballot<T: Vote> {
+    pub vote: Option<BallotOf<T>>,
+    /// The number of ballots cast, including those dropped from the history
+    pub count: u32,
+    pub history: Vec<BallotEntry<T>>,
+}
+
+/// An open vote in which an account was minted signal
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenVote<T: Vote> {
+    pub id: T::VoteId,
+    pub progress: VoteProgress<T::Signal>,
+    /// The block the vote ends at, if it has a deadline
+    pub ends: Option<<T as System>::BlockNumber>,
+    /// Whether the account voted already
+    pub voted: bool,
+}
+
 #[async_trait]
 pub trait VoteClient<N: Node>: Client<N>
 where
@@ -47,16 +129,203 @@ where
         threshold: Threshold<<N::Runtime as Vote>::Percent>,
         duration: Option<<N::Runtime as System>::BlockNumber>,
     ) -> Result<NewVoteStartedEvent<N::Runtime>>;
+    /// Opens a vote with a threshold from `VoteThresholdBuilder`, calling
+    /// `create_signal_vote` or `create_percent_vote` for its kind; percents
+    /// of the votes cast only open from a registered threshold
+    async fn create_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        threshold: VoteThreshold<N::Runtime>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<NewVoteStartedEvent<N::Runtime>>;
+    async fn open_custom_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: <N::Runtime as Org>::OrgId,
+        weights: Vec<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Vote>::Signal,
+        )>,
+        threshold: Threshold<<N::Runtime as Vote>::Signal>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<CustomWeightedVoteStartedEvent<N::Runtime>>;
+    /// Opens a vote for each leg whose outcomes decide the returned vote,
+    /// ballots on it are cast in every leg the voter holds signal in
+    async fn open_composite_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        composition: Composition,
+        legs: Vec<CompositeLeg<N::Runtime>>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<CompositeVoteStartedEvent<N::Runtime>>;
+    /// Opens a vote without end whose tally follows the current shares of
+    /// the voters and never decides an outcome
+    async fn open_standing_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: OrgRep<<N::Runtime as Org>::OrgId>,
+    ) -> Result<StandingVoteStartedEvent<N::Runtime>>;
     async fn submit_vote(
         &self,
         vote_id: <N::Runtime as Vote>::VoteId,
         direction: <N::Runtime as Vote>::VoterView,
         justification: Option<<N::Runtime as Vote>::VoteJustification>,
     ) -> Result<VotedEvent<N::Runtime>>;
+    async fn update_topic(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        new_topic: <N::Runtime as Vote>::VoteTopic,
+        reset_ballots: bool,
+    ) -> Result<VoteTopicChangedEvent<N::Runtime>>;
+    async fn set_tie_break(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        tie_break: TieBreak,
+    ) -> Result<TieBreakSetEvent<N::Runtime>>;
+    /// Closes the vote to new ballots for its final `window` blocks, only
+    /// before voting starts
+    async fn set_change_only_window(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        window: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<ChangeOnlyWindowSetEvent<N::Runtime>>;
+    /// Extends the vote by `window` blocks for each ballot cast in its
+    /// final `window` blocks, by at most `max_extension` blocks in total,
+    /// only before voting starts
+    async fn set_deadline_extension(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        extension: Option<(
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    ) -> Result<DeadlineExtensionSetEvent<N::Runtime>>;
+    /// Ends the vote now, reporting its outcome to the dependent pallets
+    async fn close_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteOutcomeReportedEvent<N::Runtime>>;
+    /// Ends the vote now as cancelled whatever its tally, only by the org
+    /// supervisor or the creator of the vote
+    async fn cancel_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        reason: Option<<N::Runtime as Vote>::VoteJustification>,
+    ) -> Result<VoteCancelledEvent<N::Runtime>>;
+    async fn open_bonded_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: OrgRep<<N::Runtime as Org>::OrgId>,
+        threshold: Threshold<<N::Runtime as Vote>::Signal>,
+        duration: <N::Runtime as System>::BlockNumber,
+        min_bond: BalanceOf<N::Runtime>,
+    ) -> Result<NewVoteStartedEvent<N::Runtime>>;
+    async fn bond_and_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        direction: <N::Runtime as Vote>::VoterView,
+        amount: BalanceOf<N::Runtime>,
+        justification: Option<<N::Runtime as Vote>::VoteJustification>,
+    ) -> Result<BondedVoteEvent<N::Runtime>>;
+    async fn release_bond(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<BondReleasedEvent<N::Runtime>>;
+    /// Registers a threshold votes of its org can be opened from, only by
+    /// the org supervisor
+    async fn link_accounts(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        accounts: Vec<<N::Runtime as System>::AccountId>,
+    ) -> Result<AccountsLinkedEvent<N::Runtime>>;
+    async fn confirm_link(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        set_id: u32,
+    ) -> Result<LinkConfirmedEvent<N::Runtime>>;
+    async fn unlink_accounts(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        set_id: u32,
+    ) -> Result<AccountsUnlinkedEvent<N::Runtime>>;
+    async fn set_threshold_default(
+        &self,
+        threshold: VoteThreshold<N::Runtime>,
+    ) -> Result<ThresholdSetEvent<N::Runtime>>;
     async fn vote_threshold(
         &self,
         threshold_id: <N::Runtime as Vote>::ThresholdId,
     ) -> Result<ThreshConfig<N::Runtime>>;
+    /// The thresholds registered for `org`, weighted or flat, by id
+    async fn vote_thresholds_of(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<ThreshConfig<N::Runtime>>>;
+    async fn vote_progress(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteProgress<<N::Runtime as Vote>::Signal>>;
+    async fn ballot_of(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Ballot<N::Runtime>>;
+    /// Whether `who` may still cast or change their ballot and until when
+    async fn can_change_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<BallotStatus<<N::Runtime as System>::BlockNumber>>;
+    /// The ballot of the signer
+    async fn my_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<Ballot<N::Runtime>>;
+    /// A receipt proving the signer's latest ballot in the vote
+    async fn vote_receipt(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteReceipt<N::Runtime>>;
+    /// Checks a receipt shared as base64 against the state root of its
+    /// block, or against `state_root` for offline verification
+    async fn verify_vote_receipt(
+        &self,
+        blob: &str,
+        state_root: Option<<N::Runtime as System>::Hash>,
+    ) -> Result<VoteReceipt<N::Runtime>>;
+    /// The open votes in which `who` was minted signal but did not vote yet
+    async fn pending_votes(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<
+        Vec<(
+            <N::Runtime as Vote>::VoteId,
+            VoteProgress<<N::Runtime as Vote>::Signal>,
+        )>,
+    >;
+    /// The open votes in which `who` was minted signal, voted or not
+    async fn open_votes(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Vec<OpenVote<N::Runtime>>>;
+    /// The voters who voted `direction`, or all who voted, with their
+    /// signal, justification and the blocks of their first and latest
+    /// ballots, `limit` of them after the first `offset`
+    async fn ballots(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        direction: Option<VoterView>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<
+        Vec<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Vote>::Signal,
+            Option<<N::Runtime as Org>::Cid>,
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    >;
 }
 
 #[async_trait]
@@ -85,23 +354,33 @@ where
         threshold: Threshold<<N::Runtime as Vote>::Signal>,
         duration: Option<<N::Runtime as System>::BlockNumber>,
     ) -> Result<NewVoteStartedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
+        let signer = self.nonced_signer().await?;
         let topic = if let Some(t) = topic {
-            Some(self.offchain_client().insert(t).await?.into())
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
         } else {
             None
         };
-        self.chain_client()
-            .create_signal_vote_and_watch(
-                &signer,
+        self.watch(
+            CreateSignalVoteCall {
                 topic,
                 organization,
                 threshold,
                 duration,
-            )
-            .await?
-            .new_vote_started()?
-            .ok_or_else(|| Error::EventNotFound.into())
+                per_voter_cap: None,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "create_signal_vote")
+        .await?
+        .decoded("new_vote_started", |r| r.new_vote_started())?
+        .ok_or_else(|| Error::EventNotFound.into())
     }
     async fn create_percent_vote(
         &self,
@@ -110,42 +389,474 @@ where
         threshold: Threshold<<N::Runtime as Vote>::Percent>,
         duration: Option<<N::Runtime as System>::BlockNumber>,
     ) -> Result<NewVoteStartedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
+        let signer = self.nonced_signer().await?;
         let topic = if let Some(t) = topic {
-            Some(self.offchain_client().insert(t).await?.into())
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
         } else {
             None
         };
-        self.chain_client()
-            .create_percent_vote_and_watch(
-                &signer,
+        self.watch(
+            CreatePercentVoteCall {
                 topic,
                 organization,
                 threshold,
                 duration,
+                per_voter_cap: None,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "create_percent_vote")
+        .await?
+        .decoded("new_vote_started", |r| r.new_vote_started())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn create_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        threshold: VoteThreshold<N::Runtime>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<NewVoteStartedEvent<N::Runtime>> {
+        match threshold.threshold() {
+            XorThreshold::Signal(t) => {
+                self.create_signal_vote(topic, threshold.org(), t, duration)
+                    .await
+            }
+            XorThreshold::Percent(t)
+            | XorThreshold::PercentOf(t, PercentBase::TotalIssuance) => {
+                self.create_percent_vote(topic, threshold.org(), t, duration)
+                    .await
+            }
+            XorThreshold::PercentOf(_, PercentBase::VotesCast { .. }) => {
+                Err(Error::VotesCastThresholdNotRegistered.into())
+            }
+        }
+    }
+    async fn open_custom_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: <N::Runtime as Org>::OrgId,
+        weights: Vec<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Vote>::Signal,
+        )>,
+        threshold: Threshold<<N::Runtime as Vote>::Signal>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<CustomWeightedVoteStartedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let topic = if let Some(t) = topic {
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
             )
-            .await?
-            .new_vote_started()?
+        } else {
+            None
+        };
+        self.watch(
+            OpenCustomVoteCall {
+                topic,
+                organization,
+                weights,
+                threshold,
+                duration,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "open_custom_vote")
+        .await?
+        .decoded("custom_weighted_vote_started", |r| {
+                r.custom_weighted_vote_started()
+            })?
             .ok_or_else(|| Error::EventNotFound.into())
     }
+    async fn open_composite_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        composition: Composition,
+        legs: Vec<CompositeLeg<N::Runtime>>,
+        duration: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<CompositeVoteStartedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let topic = if let Some(t) = topic {
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            OpenCompositeVoteCall {
+                topic,
+                composition,
+                legs,
+                duration,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "open_composite_vote")
+        .await?
+        .decoded("composite_vote_started", |r| r.composite_vote_started())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn open_standing_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: OrgRep<<N::Runtime as Org>::OrgId>,
+    ) -> Result<StandingVoteStartedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let topic = if let Some(t) = topic {
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            OpenStandingVoteCall {
+                topic,
+                organization,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "open_standing_vote")
+        .await?
+        .decoded("standing_vote_started", |r| r.standing_vote_started())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
     async fn submit_vote(
         &self,
         vote_id: <N::Runtime as Vote>::VoteId,
         direction: <N::Runtime as Vote>::VoterView,
         justification: Option<<N::Runtime as Vote>::VoteJustification>,
     ) -> Result<VotedEvent<N::Runtime>> {
-        let signer = self.chain_signer()?;
+        let signer = self.nonced_signer().await?;
         let justification = if let Some(j) = justification {
-            Some(self.offchain_client().insert(j).await?.into())
+            Some(
+                self.offchain_client()
+                    .insert(j)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
         } else {
             None
         };
-        self.chain_client()
-            .submit_vote_and_watch(&signer, vote_id, direction, justification)
+        self.watch(
+            SubmitVoteCall {
+                vote_id,
+                direction,
+                justification,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "submit_vote")
+        .await?
+        .decoded("voted", |r| r.voted())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn update_topic(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        new_topic: <N::Runtime as Vote>::VoteTopic,
+        reset_ballots: bool,
+    ) -> Result<VoteTopicChangedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let new_topic = self
+            .offchain_client()
+            .insert(new_topic)
+            .traced(Op::OffchainWrite, "insert")
+            .await?;
+        self.watch(
+            UpdateTopicCall {
+                vote_id,
+                new_topic: new_topic.into(),
+                reset_ballots,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "update_topic")
+        .await?
+        .decoded("vote_topic_changed", |r| r.vote_topic_changed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_tie_break(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        tie_break: TieBreak,
+    ) -> Result<TieBreakSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetTieBreakCall {
+                vote_id,
+                tie_break,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_tie_break")
+        .await?
+        .decoded("tie_break_set", |r| r.tie_break_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_change_only_window(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        window: Option<<N::Runtime as System>::BlockNumber>,
+    ) -> Result<ChangeOnlyWindowSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(SetChangeOnlyWindowCall { vote_id, window }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "set_change_only_window")
             .await?
-            .voted()?
+            .decoded("change_only_window_set", |r| r.change_only_window_set())?
             .ok_or_else(|| Error::EventNotFound.into())
     }
+    async fn set_deadline_extension(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        extension: Option<(
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    ) -> Result<DeadlineExtensionSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(SetDeadlineExtensionCall { vote_id, extension }, &signer)
+            .settled(&signer)
+            .traced(Op::Extrinsic, "set_deadline_extension")
+            .await?
+            .decoded("deadline_extension_set", |r| r.deadline_extension_set())?
+            .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn close_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteOutcomeReportedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            CloseVoteCall {
+                vote_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "close_vote")
+        .await?
+        .decoded("vote_outcome_reported", |r| r.vote_outcome_reported())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn cancel_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        reason: Option<<N::Runtime as Vote>::VoteJustification>,
+    ) -> Result<VoteCancelledEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let reason = if let Some(r) = reason {
+            Some(
+                self.offchain_client()
+                    .insert(r)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            CancelVoteCall {
+                vote_id,
+                reason,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "cancel_vote")
+        .await?
+        .decoded("vote_cancelled", |r| r.vote_cancelled())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn open_bonded_vote(
+        &self,
+        topic: Option<<N::Runtime as Vote>::VoteTopic>,
+        organization: OrgRep<<N::Runtime as Org>::OrgId>,
+        threshold: Threshold<<N::Runtime as Vote>::Signal>,
+        duration: <N::Runtime as System>::BlockNumber,
+        min_bond: BalanceOf<N::Runtime>,
+    ) -> Result<NewVoteStartedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let topic = if let Some(t) = topic {
+            Some(
+                self.offchain_client()
+                    .insert(t)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            OpenBondedVoteCall {
+                topic,
+                organization,
+                threshold,
+                duration,
+                min_bond,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "open_bonded_vote")
+        .await?
+        .decoded("new_vote_started", |r| r.new_vote_started())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn bond_and_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        direction: <N::Runtime as Vote>::VoterView,
+        amount: BalanceOf<N::Runtime>,
+        justification: Option<<N::Runtime as Vote>::VoteJustification>,
+    ) -> Result<BondedVoteEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        let justification = if let Some(j) = justification {
+            Some(
+                self.offchain_client()
+                    .insert(j)
+                    .traced(Op::OffchainWrite, "insert")
+                    .await?
+                    .into(),
+            )
+        } else {
+            None
+        };
+        self.watch(
+            BondAndVoteCall {
+                vote_id,
+                direction,
+                amount,
+                justification,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "bond_and_vote")
+        .await?
+        .decoded("bonded_vote", |r| r.bonded_vote())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn release_bond(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<BondReleasedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ReleaseBondCall {
+                vote_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "release_bond")
+        .await?
+        .decoded("bond_released", |r| r.bond_released())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn link_accounts(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        accounts: Vec<<N::Runtime as System>::AccountId>,
+    ) -> Result<AccountsLinkedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            LinkAccountsCall {
+                organization,
+                accounts,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "link_accounts")
+        .await?
+        .decoded("accounts_linked", |r| r.accounts_linked())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn confirm_link(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        set_id: u32,
+    ) -> Result<LinkConfirmedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            ConfirmLinkCall {
+                organization,
+                set_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "confirm_link")
+        .await?
+        .decoded("link_confirmed", |r| r.link_confirmed())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn unlink_accounts(
+        &self,
+        organization: <N::Runtime as Org>::OrgId,
+        set_id: u32,
+    ) -> Result<AccountsUnlinkedEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            UnlinkAccountsCall {
+                organization,
+                set_id,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "unlink_accounts")
+        .await?
+        .decoded("accounts_unlinked", |r| r.accounts_unlinked())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
+    async fn set_threshold_default(
+        &self,
+        threshold: VoteThreshold<N::Runtime>,
+    ) -> Result<ThresholdSetEvent<N::Runtime>> {
+        let signer = self.nonced_signer().await?;
+        self.watch(
+            SetThresholdDefaultCall {
+                threshold,
+            },
+            &signer,
+        )
+        .settled(&signer)
+        .traced(Op::Extrinsic, "set_threshold_default")
+        .await?
+        .decoded("threshold_set", |r| r.threshold_set())?
+        .ok_or_else(|| Error::EventNotFound.into())
+    }
     async fn vote_threshold(
         &self,
         threshold_id: <N::Runtime as Vote>::ThresholdId,
@@ -153,6 +864,958 @@ where
         Ok(self
             .chain_client()
             .vote_thresholds(threshold_id, None)
+            .traced(Op::Rpc, "vote_thresholds")
             .await?)
     }
+    async fn vote_thresholds_of(
+        &self,
+        org: <N::Runtime as Org>::OrgId,
+    ) -> Result<Vec<ThreshConfig<N::Runtime>>> {
+        let mut thresholds = self
+            .chain_client()
+            .vote_thresholds_iter(None)
+            .traced(Op::Rpc, "vote_thresholds_iter")
+            .await?;
+        let mut of_org = Vec::new();
+        while let Some((_, config)) = thresholds.next().await? {
+            if config.org().org() == org {
+                of_org.push(config);
+            }
+        }
+        of_org.sort_by_key(|config| config.id());
+        Ok(of_org)
+    }
+    async fn vote_progress(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteProgress<<N::Runtime as Vote>::Signal>> {
+        let state = self
+            .chain_client()
+            .vote_states(vote_id, None)
+            .traced(Op::Rpc, "vote_states")
+            .await?;
+        Ok(state.progress())
+    }
+    async fn ballot_of(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Ballot<N::Runtime>> {
+        let chain = self.chain_client();
+        Ok(Ballot {
+            vote: chain
+                .vote_logger(vote_id, who, None)
+                .traced(Op::Rpc, "vote_logger")
+                .await?,
+            count: chain
+                .ballot_counts(vote_id, who, None)
+                .traced(Op::Rpc, "ballot_counts")
+                .await?,
+            history: chain
+                .justification_history(vote_id, who, None)
+                .traced(Op::Rpc, "justification_history")
+                .await?,
+        })
+    }
+    async fn can_change_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<BallotStatus<<N::Runtime as System>::BlockNumber>> {
+        let chain = self.chain_client();
+        let state = chain
+            .vote_states(vote_id, None)
+            .traced(Op::Rpc, "vote_states")
+            .await?;
+        let now = chain
+            .header(None::<<N::Runtime as System>::Hash>)
+            .traced(Op::Rpc, "header")
+            .await?
+            .map(|header| *header.number())
+            .unwrap_or_default();
+        // the checks of the pallet's `ballot_status`, in the same order
+        if state.outcome() == VoteOutcome::Cancelled
+            || state.ends().map_or(false, |ends| ends < now)
+        {
+            let outcome = chain
+                .reported_outcomes(vote_id, None)
+                .traced(Op::Rpc, "reported_outcomes")
+                .await?
+                .unwrap_or_else(|| state.outcome());
+            return Ok(BallotStatus::Locked {
+                reason: LockReason::Ended(outcome),
+            })
+        }
+        let not_yet_voted = if state.accepts_new_ballots(now) {
+            BallotStatus::NotYetVoted
+        } else {
+            BallotStatus::Locked {
+                reason: LockReason::NewBallotsClosed,
+            }
+        };
+        let ballot = chain
+            .vote_logger(vote_id, who, None)
+            .traced(Op::Rpc, "vote_logger")
+            .await?;
+        if let Some(ballot) = ballot {
+            return Ok(if ballot.magnitude().is_zero() {
+                BallotStatus::NotAParticipant
+            } else if ballot.direction() == VoterView::Uninitialized {
+                not_yet_voted
+            } else {
+                BallotStatus::CanChange {
+                    until: state.ends(),
+                }
+            })
+        }
+        // lazily minted votes mint signal from the org's snapshot with the
+        // first ballot
+        let snapshot = chain
+            .lazy_snapshots(vote_id, None)
+            .traced(Op::Rpc, "lazy_snapshots")
+            .await?;
+        let organization = chain
+            .vote_orgs(vote_id, None)
+            .traced(Op::Rpc, "vote_orgs")
+            .await?;
+        let (snapshot, org) = match (snapshot, organization) {
+            (Some(snapshot), Some(organization)) => {
+                (snapshot, organization.org())
+            }
+            _ => return Ok(BallotStatus::NotAParticipant),
+        };
+        let shares = match chain
+            .snapshot_shares((org, snapshot), who, None)
+            .traced(Op::Rpc, "snapshot_shares")
+            .await?
+        {
+            Some(shares) => shares,
+            // members without a profile have no shares
+            None => {
+                chain
+                    .members(org, who, None)
+                    .traced(Op::Rpc, "members")
+                    .await
+                    .map(|profile| profile.total())
+                    .unwrap_or_default()
+            }
+        };
+        Ok(if shares.is_zero() {
+            BallotStatus::NotAParticipant
+        } else {
+            not_yet_voted
+        })
+    }
+    async fn my_vote(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<Ballot<N::Runtime>> {
+        let signer =
+            self.chain_signer().map_err(|_| Error::KeystoreLocked)?;
+        self.ballot_of(vote_id, signer.account_id()).await
+    }
+    async fn vote_receipt(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+    ) -> Result<VoteReceipt<N::Runtime>> {
+        let voter = self
+            .chain_signer()
+            .map_err(|_| Error::KeystoreLocked)?
+            .account_id()
+            .clone();
+        let chain = self.chain_client();
+        let history = chain
+            .justification_history(vote_id, &voter, None)
+            .traced(Op::Rpc, "justification_history")
+            .await?;
+        let (number, _, _) = history.last().ok_or(Error::NoBallotCast)?;
+        let block: Option<<N::Runtime as System>::Hash> = chain
+            .rpc_client()
+            .request(
+                "chain_getBlockHash",
+                Params::Array(vec![to_value(number)?]),
+            )
+            .traced(Op::Rpc, "chain_getBlockHash")
+            .await?;
+        let block = block.ok_or(Error::NoBallotCast)?;
+        // the ballot's extrinsic is the last to emit its `Voted` event in
+        // the block
+        let events: Option<StorageData> = chain
+            .rpc_client()
+            .request(
+                "state_getStorage",
+                Params::Array(vec![to_value(events_key())?, to_value(block)?]),
+            )
+            .traced(Op::Rpc, "state_getStorage")
+            .await?;
+        let events = events.ok_or(Error::EventNotFound)?;
+        let (extrinsic_index, voted) = chain
+            .events_decoder()
+            .decode_events(&mut &events.0[..])?
+            .into_iter()
+            .filter_map(|(phase, raw)| {
+                match (phase, raw) {
+                    (Phase::ApplyExtrinsic(index), Raw::Event(event))
+                        if event.module == "Vote"
+                            && event.variant == "Voted" =>
+                    {
+                        let voted = VotedEvent::<N::Runtime>::decode(
+                            &mut &event.data[..],
+                        )
+                        .ok()?;
+                        Some((index, voted))
+                    }
+                    _ => None,
+                }
+            })
+            .filter(|(_, voted)| {
+                voted.vote_id == vote_id && voted.voter == voter
+            })
+            .last()
+            .ok_or(Error::EventNotFound)?;
+        let key = VoteLoggerStore::<N::Runtime> {
+            vote: vote_id,
+            who: voter,
+        }
+        .key(chain.metadata())?;
+        let proof: ReadProof = chain
+            .rpc_client()
+            .request(
+                "state_getReadProof",
+                Params::Array(vec![to_value(vec![key])?, to_value(block)?]),
+            )
+            .traced(Op::Rpc, "state_getReadProof")
+            .await?;
+        Ok(VoteReceipt {
+            genesis: *chain.genesis(),
+            block,
+            number: *number,
+            extrinsic_index,
+            voted,
+            proof: proof.proof.into_iter().map(|node| node.0).collect(),
+        })
+    }
+    async fn verify_vote_receipt(
+        &self,
+        blob: &str,
+        state_root: Option<<N::Runtime as System>::Hash>,
+    ) -> Result<VoteReceipt<N::Runtime>> {
+        let chain = self.chain_client();
+        let receipt = VoteReceipt::<N::Runtime>::from_base64(blob)?;
+        if &receipt.genesis != chain.genesis() {
+            return Err(Error::VoteReceiptChainMismatch.into())
+        }
+        let state_root = match state_root {
+            Some(state_root) => state_root,
+            None => {
+                let header = chain
+                    .header(Some(receipt.block))
+                    .traced(Op::Rpc, "header")
+                    .await?
+                    .ok_or(Error::VoteReceiptNotProven)?;
+                if *header.number() != receipt.number {
+                    return Err(Error::VoteReceiptNotProven.into())
+                }
+                *header.state_root()
+            }
+        };
+        receipt.check(&receipt.key(chain.metadata())?, state_root)?;
+        Ok(receipt)
+    }
+    async fn pending_votes(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<
+        Vec<(
+            <N::Runtime as Vote>::VoteId,
+            VoteProgress<<N::Runtime as Vote>::Signal>,
+        )>,
+    > {
+        let chain = self.chain_client();
+        let mut votes = chain
+            .vote_states_iter(None)
+            .traced(Op::Rpc, "vote_states_iter")
+            .await?;
+        let mut pending = Vec::new();
+        // the keys are the two 16 byte prefixes followed by the blake2_128
+        // hash and encoding of the vote id
+        while let Some((key, state)) = votes.next().await? {
+            if state.outcome() != VoteOutcome::Voting {
+                continue
+            }
+            let vote_id = Decode::decode(&mut &key.0[48..])?;
+            let ballot = chain
+                .vote_logger(vote_id, who, None)
+                .traced(Op::Rpc, "vote_logger")
+                .await?;
+            if let Some(ballot) = ballot {
+                if ballot.direction() == VoterView::Uninitialized {
+                    pending.push((vote_id, state.progress()));
+                }
+            }
+        }
+        Ok(pending)
+    }
+
+    async fn open_votes(
+        &self,
+        who: &<N::Runtime as System>::AccountId,
+    ) -> Result<Vec<OpenVote<N::Runtime>>> {
+        let chain = self.chain_client();
+        let mut votes = chain
+            .vote_states_iter(None)
+            .traced(Op::Rpc, "vote_states_iter")
+            .await?;
+        let mut open = Vec::new();
+        // keyed like in `pending_votes`
+        while let Some((key, state)) = votes.next().await? {
+            if state.outcome() != VoteOutcome::Voting {
+                continue
+            }
+            let vote_id = Decode::decode(&mut &key.0[48..])?;
+            let ballot = chain
+                .vote_logger(vote_id, who, None)
+                .traced(Op::Rpc, "vote_logger")
+                .await?;
+            if let Some(ballot) = ballot {
+                open.push(OpenVote {
+                    id: vote_id,
+                    progress: state.progress(),
+                    ends: state.ends(),
+                    voted: ballot.direction() != VoterView::Uninitialized,
+                });
+            }
+        }
+        Ok(open)
+    }
+
+    async fn ballots(
+        &self,
+        vote_id: <N::Runtime as Vote>::VoteId,
+        direction: Option<VoterView>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<
+        Vec<(
+            <N::Runtime as System>::AccountId,
+            <N::Runtime as Vote>::Signal,
+            Option<<N::Runtime as Org>::Cid>,
+            <N::Runtime as System>::BlockNumber,
+            <N::Runtime as System>::BlockNumber,
+        )>,
+    > {
+        let chain = self.chain_client();
+        // the ballots of the vote are keyed by the blake2_128 hash and
+        // encoding of the vote id followed by those of the voter
+        let mut prefix =
+            VoteLoggerStore::<N::Runtime>::prefix(chain.metadata())?;
+        let vote_id = vote_id.encode();
+        prefix.0.extend(&blake2_128(&vote_id));
+        prefix.0.extend(vote_id);
+        let pages = StoragePage::<
+            <N::Runtime as System>::AccountId,
+            BallotOf<N::Runtime>,
+        >::new(prefix, KeyHasher::Blake2_128Concat, DEFAULT_PAGE_SIZE);
+        let ballots = pages.stream(chain, None);
+        pin_mut!(ballots);
+        let mut skipped = 0;
+        let mut listed = Vec::new();
+        while listed.len() < limit as usize {
+            let (voter, ballot) = match ballots.try_next().await? {
+                Some(entry) => entry,
+                None => break,
+            };
+            let listed_direction = match direction {
+                Some(direction) => ballot.direction() == direction,
+                None => ballot.direction() != VoterView::Uninitialized,
+            };
+            if !listed_direction {
+                continue
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue
+            }
+            listed.push((
+                voter,
+                ballot.magnitude(),
+                ballot.justification(),
+                ballot.cast_at(),
+                ballot.last_changed_at(),
+            ));
+        }
+        Ok(listed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_client::{
+        client::{
+            AccountKeyring,
+            Client as _,
+            Node as _,
+        },
+        org::OrgClient,
+        utils::{
+            organization::OrgRep,
+            vote::{
+                BallotStatus,
+                LockReason,
+                Threshold,
+                VoteOutcome,
+                VoterView,
+            },
+        },
+        vote::{
+            BondedVoteEvent,
+            CustomWeightedVoteStartedEvent,
+            NewVoteStartedEvent,
+            VoteClient,
+            VoteOutcomeReportedEvent,
+            VotedEvent,
+        },
+        DevClient as Client,
+        DevNode as Node,
+        Error,
+        TextBlock,
+    };
+
+    #[async_std::test]
+    async fn signal_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let event = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(1, None),
+                None,
+            )
+            .await
+            .unwrap();
+        let expected_event = NewVoteStartedEvent {
+            caller: alice.clone(),
+            new_vote_id: 1,
+        };
+        assert_eq!(event, expected_event);
+        let event = client
+            .submit_vote(1, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        let expected_event = VotedEvent {
+            vote_id: 1,
+            voter: alice,
+            view: VoterView::InFavor,
+            sequence: 1,
+        };
+        assert_eq!(event, expected_event);
+        let ballot = client.my_vote(1).await.unwrap();
+        assert_eq!(ballot.count, 1);
+        assert_eq!(ballot.history.len(), 1);
+        assert_eq!(ballot.history[0].1, VoterView::InFavor);
+        let progress = client.vote_progress(1).await.unwrap();
+        assert_eq!(progress.in_favor(), 1);
+        assert_eq!(progress.turnout(), 1);
+        assert_eq!(progress.outcome(), VoteOutcome::Approved);
+    }
+
+    #[async_std::test]
+    async fn close_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let event = client.close_vote(vote_id).await.unwrap();
+        let expected = VoteOutcomeReportedEvent {
+            vote_id,
+            outcome: VoteOutcome::Rejected,
+        };
+        assert_eq!(event, expected);
+        assert!(client.close_vote(vote_id).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn cancel_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let reason = TextBlock {
+            text: "opened with the wrong threshold".to_string(),
+        };
+        let event = client
+            .cancel_vote(vote_id, Some(reason.into()))
+            .await
+            .unwrap();
+        assert_eq!(event.vote_id, vote_id);
+        assert_eq!(event.who, alice.clone());
+        assert!(event.reason.is_some());
+        let progress = client.vote_progress(vote_id).await.unwrap();
+        assert_eq!(progress.outcome(), VoteOutcome::Cancelled);
+        assert_eq!(
+            client.can_change_vote(vote_id, &alice).await.unwrap(),
+            BallotStatus::Locked {
+                reason: LockReason::Ended(VoteOutcome::Cancelled),
+            }
+        );
+        assert!(client
+            .submit_vote(vote_id, VoterView::InFavor, None)
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn pending_votes_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let pending = client.pending_votes(&alice).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, vote_id);
+        client
+            .submit_vote(vote_id, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        assert!(client.pending_votes(&alice).await.unwrap().is_empty());
+        let pending = client.pending_votes(&bob).await.unwrap();
+        assert_eq!(pending[0].1.in_favor(), 1);
+        client.close_vote(vote_id).await.unwrap();
+        assert!(client.pending_votes(&bob).await.unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn open_votes_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                Some(10),
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let open = client.open_votes(&alice).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, vote_id);
+        assert!(open[0].ends.is_some());
+        assert!(!open[0].voted);
+        client
+            .submit_vote(vote_id, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        let open = client.open_votes(&alice).await.unwrap();
+        assert!(open[0].voted);
+        assert_eq!(open[0].progress.in_favor(), 1);
+        assert!(client.open_votes(&charlie).await.unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn ballots_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        assert!(client
+            .ballots(vote_id, None, 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        let voted = client
+            .submit_vote(vote_id, VoterView::Against, None)
+            .await
+            .unwrap();
+        let against = client
+            .ballots(vote_id, Some(VoterView::Against), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            against,
+            vec![(alice.clone(), 1, None, voted.block, voted.block)]
+        );
+        let in_favor = client
+            .ballots(vote_id, Some(VoterView::InFavor), 0, 10)
+            .await
+            .unwrap();
+        assert!(in_favor.is_empty());
+        // bob was minted signal but did not vote
+        let not_voted = client
+            .ballots(vote_id, Some(VoterView::Uninitialized), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(not_voted, vec![(bob, 1, None, 0, 0)]);
+        assert!(client
+            .ballots(vote_id, None, 1, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(client
+            .ballots(vote_id, None, 0, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[async_std::test]
+    async fn can_change_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let constitution = TextBlock {
+            text: "one member one vote".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let status = client.can_change_vote(vote_id, &charlie).await.unwrap();
+        assert_eq!(status, BallotStatus::NotAParticipant);
+        let status = client.can_change_vote(vote_id, &alice).await.unwrap();
+        assert_eq!(status, BallotStatus::NotYetVoted);
+        client
+            .submit_vote(vote_id, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        let status = client.can_change_vote(vote_id, &alice).await.unwrap();
+        assert_eq!(status, BallotStatus::CanChange { until: None });
+        client.close_vote(vote_id).await.unwrap();
+        let status = client.can_change_vote(vote_id, &bob).await.unwrap();
+        assert_eq!(
+            status,
+            BallotStatus::Locked {
+                reason: LockReason::Ended(VoteOutcome::Rejected),
+            }
+        );
+    }
+
+    #[async_std::test]
+    async fn custom_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let constitution = TextBlock {
+            text: "alice counts twice".to_string(),
+        };
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                constitution,
+                &[alice.clone(), bob.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let event = client
+            .open_custom_vote(
+                None,
+                org,
+                vec![(alice.clone(), 2), (bob, 1)],
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap();
+        let expected_event = CustomWeightedVoteStartedEvent {
+            caller: alice,
+            new_vote_id: 1,
+        };
+        assert_eq!(event, expected_event);
+        client
+            .submit_vote(1, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        let progress = client.vote_progress(1).await.unwrap();
+        assert_eq!(progress.in_favor(), 2);
+        assert_eq!(progress.outcome(), VoteOutcome::Approved);
+    }
+
+    #[async_std::test]
+    async fn bonded_vote_test() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                TextBlock {
+                    text: "bonded".to_string(),
+                },
+                &[alice.clone()],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let event = client
+            .open_bonded_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(100, None),
+                10,
+                10,
+            )
+            .await
+            .unwrap();
+        let vote_id = event.new_vote_id;
+        let event = client
+            .bond_and_vote(vote_id, VoterView::InFavor, 40, None)
+            .await
+            .unwrap();
+        let expected_event = BondedVoteEvent {
+            vote_id,
+            voter: alice,
+            view: VoterView::InFavor,
+            amount: 40,
+        };
+        assert_eq!(event, expected_event);
+        let progress = client.vote_progress(vote_id).await.unwrap();
+        assert_eq!(progress.in_favor(), 40);
+        assert_eq!(progress.remaining_to_pass(), Some(60));
+        // the vote is still open so the bond stays reserved
+        assert!(client.release_bond(vote_id).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn vote_receipts_are_proven_by_the_chain() {
+        let node = Node::new_mock();
+        let (client, _tmp) = Client::mock(&node, AccountKeyring::Alice).await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        let org = client
+            .new_flat_org(
+                Some(alice.clone()),
+                None,
+                TextBlock {
+                    text: "receipts".to_string(),
+                },
+                &[alice.clone(), bob],
+                None,
+            )
+            .await
+            .unwrap()
+            .new_id;
+        let vote_id = client
+            .create_signal_vote(
+                None,
+                OrgRep::Equal(org),
+                Threshold::new(2, None),
+                None,
+            )
+            .await
+            .unwrap()
+            .new_vote_id;
+        let err = client.vote_receipt(vote_id).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::NoBallotCast)
+        ));
+        client
+            .submit_vote(vote_id, VoterView::InFavor, None)
+            .await
+            .unwrap();
+        client
+            .submit_vote(vote_id, VoterView::Against, None)
+            .await
+            .unwrap();
+        // the receipt is of the latest ballot
+        let receipt = client.vote_receipt(vote_id).await.unwrap();
+        assert_eq!(
+            receipt.voted,
+            VotedEvent {
+                vote_id,
+                voter: alice,
+                view: VoterView::Against,
+                sequence: 2,
+            }
+        );
+        let blob = receipt.to_base64();
+        assert_eq!(
+            client.verify_vote_receipt(&blob, None).await.unwrap(),
+            receipt
+        );
+        assert!(client
+            .verify_vote_receipt(&blob, Some(Default::default()))
+            .await
+            .is_err());
+        let mut forged = receipt.clone();
+        forged.voted.view = VoterView::InFavor;
+        let err = client
+            .verify_vote_receipt(&forged.to_base64(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::VoteReceiptNotProven)
+        ));
+        let mut other_chain = receipt;
+        other_chain.genesis = Default::default();
+        let err = client
+            .verify_vote_receipt(&other_chain.to_base64(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::VoteReceiptChainMismatch)
+        ));
+        let err = client.verify_vote_receipt("not a receipt", None).await;
+        assert!(matches!(
+            err.unwrap_err().downcast_ref::<Error>(),
+            Some(Error::InvalidVoteReceipt)
+        ));
+    }
 }
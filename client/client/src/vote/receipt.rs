@@ -0,0 +1,116 @@
+//! Receipts proving how an account voted
+//!
+//! `VoteClient::vote_receipt` takes a receipt of the signer's latest ballot
+//! from the block it was cast in: the fields of its `Voted` event, the
+//! index of the extrinsic which cast it and a proof of the signer's
+//! `VoteLogger` entry read with `state_getReadProof`. Receipts are shared
+//! as the base64 of their SCALE encoding.
+//!
+//! `VoteClient::verify_vote_receipt` checks the proof against the state
+//! root of the block, read from the chain or given for offline
+//! verification. Receipts taken on another chain are rejected whatever
+//! their proof.
+use crate::{
+    error::Error,
+    vote::{
+        BallotOf,
+        Vote,
+        VoteLoggerStore,
+        VotedEvent,
+    },
+};
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use serde::Deserialize;
+use sp_state_machine::{
+    read_proof_check,
+    StorageProof,
+};
+use substrate_subxt::{
+    sp_core::{
+        storage::StorageKey,
+        twox_128,
+        Bytes,
+    },
+    system::System,
+    Metadata,
+    Store,
+};
+use sunshine_bounty_utils::traits::VoteVector as _;
+use sunshine_client_utils::Result;
+
+/// A proof of the latest ballot of a voter
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct VoteReceipt<T: Vote> {
+    /// The genesis hash of the chain the ballot was cast on
+    pub genesis: <T as System>::Hash,
+    /// The block the ballot was cast in
+    pub block: <T as System>::Hash,
+    pub number: <T as System>::BlockNumber,
+    /// The index of the extrinsic which cast the ballot in the block
+    pub extrinsic_index: u32,
+    pub voted: VotedEvent<T>,
+    /// The trie nodes proving the voter's `VoteLogger` entry at the block
+    pub proof: Vec<Vec<u8>>,
+}
+
+impl<T: Vote> VoteReceipt<T> {
+    /// The receipt as shareable text
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.encode())
+    }
+
+    /// Reads a receipt shared with `to_base64`
+    pub fn from_base64(blob: &str) -> Result<Self> {
+        let bytes = base64::decode(blob.trim())
+            .map_err(|_| Error::InvalidVoteReceipt)?;
+        Ok(Self::decode(&mut &bytes[..])
+            .map_err(|_| Error::InvalidVoteReceipt)?)
+    }
+
+    /// The storage key of the voter's `VoteLogger` entry
+    pub fn key(&self, metadata: &Metadata) -> Result<StorageKey> {
+        let store = VoteLoggerStore::<T> {
+            vote: self.voted.vote_id,
+            who: self.voted.voter.clone(),
+        };
+        Ok(store.key(metadata)?)
+    }
+
+    /// Checks that the proof holds the voter's ballot in the direction of
+    /// the event under `state_root`
+    pub fn check(&self, key: &StorageKey, state_root: T::Hash) -> Result<()> {
+        let proof = StorageProof::new(self.proof.clone());
+        let values = read_proof_check::<T::Hashing, _>(
+            state_root,
+            proof,
+            std::iter::once(&key.0),
+        )
+        .map_err(|_| Error::VoteReceiptNotProven)?;
+        let ballot = values
+            .get(&key.0)
+            .cloned()
+            .flatten()
+            .ok_or(Error::VoteReceiptNotProven)?;
+        let ballot: BallotOf<T> = Decode::decode(&mut &ballot[..])
+            .map_err(|_| Error::VoteReceiptNotProven)?;
+        // the pallet's and the runtime's views share their encoding
+        if ballot.direction().encode() != self.voted.view.encode() {
+            return Err(Error::VoteReceiptNotProven.into())
+        }
+        Ok(())
+    }
+}
+
+/// The response of `state_getReadProof`
+#[derive(Deserialize)]
+pub(crate) struct ReadProof {
+    pub proof: Vec<Bytes>,
+}
+
+/// The key of the events of a block
+pub(crate) fn events_key() -> StorageKey {
+    StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
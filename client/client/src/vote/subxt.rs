@@ -1,4 +1,6 @@
+use super::VoteThreshold;
 use crate::org::{
+    BalanceOf,
     Org,
     OrgEventsDecoder,
 };
@@ -37,11 +39,16 @@ use substrate_subxt::{
     Store,
 };
 use sunshine_bounty_utils::{
+    deadline::DeadlineExtension,
     organization::OrgRep,
     vote::{
+        CompositeVoteState,
+        Composition,
         Threshold,
         ThresholdConfig,
+        TieBreak,
         Vote as VoteVector,
+        VoteOutcome,
         VoteState,
         XorThreshold,
     },
@@ -53,6 +60,28 @@ pub type ThreshConfig<T> = ThresholdConfig<
     XorThreshold<<T as Vote>::Signal, <T as Vote>::Percent>,
 >;
 
+/// An org with the threshold it must meet, one leg of a composite vote
+pub type CompositeLeg<T> = (
+    OrgRep<<T as Org>::OrgId>,
+    XorThreshold<<T as Vote>::Signal, <T as Vote>::Percent>,
+);
+
+/// A ballot of a voter: the block it was cast in, its direction and
+/// justification
+pub type BallotEntry<T> = (
+    <T as System>::BlockNumber,
+    <T as Vote>::VoterView,
+    Option<<T as Org>::Cid>,
+);
+
+/// The ballot a voter holds in a vote, with the blocks of their first and
+/// latest ballots
+pub type BallotOf<T> = VoteVector<
+    <T as Vote>::Signal,
+    <T as Org>::Cid,
+    <T as System>::BlockNumber,
+>;
+
 /// The subset of the `vote::Trait` that a client must implement.
 #[module]
 pub trait Vote: System + Org {
@@ -138,14 +167,40 @@ pub struct OpenVoteCounterStore {
 // ~~ Maps ~~
 
 #[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
-pub struct VoteStateStore<T: Vote> {
+pub struct VoteStatesStore<T: Vote> {
     #[store(returns = VoteState<T::Signal, <T as System>::BlockNumber, <T as Org>::Cid>)]
     pub vote: T::VoteId,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct VoteCreatorsStore<T: Vote> {
+    #[store(returns = Option<<T as System>::AccountId>)]
+    pub vote: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct VoteOrgsStore<T: Vote> {
+    #[store(returns = Option<OrgRep<<T as Org>::OrgId>>)]
+    pub vote: T::VoteId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
 pub struct VoteLoggerStore<T: Vote> {
-    #[store(returns = VoteVector<T::Signal, <T as Org>::Cid>)]
+    #[store(returns = Option<BallotOf<T>>)]
+    pub vote: T::VoteId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct JustificationHistoryStore<T: Vote> {
+    #[store(returns = Vec<BallotEntry<T>>)]
+    pub vote: T::VoteId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BallotCountsStore<T: Vote> {
+    #[store(returns = u32)]
     pub vote: T::VoteId,
     pub who: <T as System>::AccountId,
 }
@@ -156,6 +211,37 @@ pub struct VoteThresholdsStore<T: Vote> {
     pub threshold: T::ThresholdId,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BondedVotesStore<T: Vote> {
+    #[store(returns = Option<BalanceOf<T>>)]
+    pub vote: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct VoteBondsStore<T: Vote> {
+    #[store(returns = Option<BalanceOf<T>>)]
+    pub vote: T::VoteId,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ReportedOutcomesStore<T: Vote> {
+    #[store(returns = Option<VoteOutcome>)]
+    pub vote: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct LazySnapshotsStore<T: Vote> {
+    #[store(returns = Option<u32>)]
+    pub vote: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct CompositeVotesStore<T: Vote> {
+    #[store(returns = Option<CompositeVoteState<T::VoteId>>)]
+    pub vote: T::VoteId,
+}
+
 // ~~ Calls ~~
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -164,6 +250,7 @@ pub struct CreateSignalVoteCall<T: Vote> {
     pub organization: OrgRep<T::OrgId>,
     pub threshold: Threshold<T::Signal>,
     pub duration: Option<<T as System>::BlockNumber>,
+    pub per_voter_cap: Option<T::Percent>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -172,6 +259,24 @@ pub struct CreatePercentVoteCall<T: Vote> {
     pub organization: OrgRep<T::OrgId>,
     pub threshold: Threshold<T::Percent>,
     pub duration: Option<<T as System>::BlockNumber>,
+    pub per_voter_cap: Option<T::Percent>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct OpenCustomVoteCall<T: Vote> {
+    pub topic: Option<<T as Org>::Cid>,
+    pub organization: T::OrgId,
+    pub weights: Vec<(<T as System>::AccountId, T::Signal)>,
+    pub threshold: Threshold<T::Signal>,
+    pub duration: Option<<T as System>::BlockNumber>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct OpenCompositeVoteCall<T: Vote> {
+    pub topic: Option<<T as Org>::Cid>,
+    pub composition: Composition,
+    pub legs: Vec<CompositeLeg<T>>,
+    pub duration: Option<<T as System>::BlockNumber>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
@@ -181,18 +286,244 @@ pub struct SubmitVoteCall<T: Vote> {
     pub justification: Option<<T as Org>::Cid>,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct OpenBondedVoteCall<T: Vote> {
+    pub topic: Option<<T as Org>::Cid>,
+    pub organization: OrgRep<<T as Org>::OrgId>,
+    pub threshold: Threshold<T::Signal>,
+    pub duration: <T as System>::BlockNumber,
+    pub min_bond: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct BondAndVoteCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub direction: <T as Vote>::VoterView,
+    pub amount: BalanceOf<T>,
+    pub justification: Option<<T as Org>::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ReleaseBondCall<T: Vote> {
+    pub vote_id: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct UpdateTopicCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub new_topic: <T as Org>::Cid,
+    pub reset_ballots: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetTieBreakCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub tie_break: TieBreak,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetChangeOnlyWindowCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub window: Option<<T as System>::BlockNumber>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetDeadlineExtensionCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub extension:
+        Option<(<T as System>::BlockNumber, <T as System>::BlockNumber)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct CloseVoteCall<T: Vote> {
+    pub vote_id: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct CancelVoteCall<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub reason: Option<<T as Org>::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct OpenStandingVoteCall<T: Vote> {
+    pub topic: Option<<T as Org>::Cid>,
+    pub organization: OrgRep<T::OrgId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct LinkAccountsCall<T: Vote> {
+    pub organization: T::OrgId,
+    pub accounts: Vec<<T as System>::AccountId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct ConfirmLinkCall<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct UnlinkAccountsCall<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SetThresholdDefaultCall<T: Vote> {
+    pub threshold: VoteThreshold<T>,
+}
+
 // ~~ Events ~~
 
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ThresholdSetEvent<T: Vote> {
+    pub threshold_id: T::ThresholdId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
 pub struct NewVoteStartedEvent<T: Vote> {
     pub caller: <T as System>::AccountId,
-    pub org: T::OrgId,
     pub new_vote_id: T::VoteId,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoterSignalCappedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub cap: T::Signal,
+    pub capped: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoteCancelledOrgFrozenEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub org: <T as Org>::OrgId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct CustomWeightedVoteStartedEvent<T: Vote> {
+    pub caller: <T as System>::AccountId,
+    pub new_vote_id: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode, Encode)]
 pub struct VotedEvent<T: Vote> {
     pub vote_id: T::VoteId,
     pub voter: <T as System>::AccountId,
     pub view: <T as Vote>::VoterView,
+    pub sequence: u32,
+    /// The block the ballot was cast in
+    pub block: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BondedVoteEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub voter: <T as System>::AccountId,
+    pub view: <T as Vote>::VoterView,
+    pub amount: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BondReleasedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub voter: <T as System>::AccountId,
+    pub amount: BalanceOf<T>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoteTopicChangedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub old_topic: Option<<T as Org>::Cid>,
+    pub new_topic: <T as Org>::Cid,
+    pub reset_ballots: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct TieBreakSetEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub tie_break: TieBreak,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ChangeOnlyWindowSetEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub window: Option<<T as System>::BlockNumber>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct DeadlineExtensionSetEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub extension: Option<DeadlineExtension<<T as System>::BlockNumber>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoteDeadlineExtendedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub ends: <T as System>::BlockNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoteOutcomeReportedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub outcome: VoteOutcome,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct VoteCancelledEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub who: <T as System>::AccountId,
+    pub reason: Option<<T as Org>::Cid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct CompositeVoteStartedEvent<T: Vote> {
+    pub caller: <T as System>::AccountId,
+    pub new_vote_id: T::VoteId,
+    pub legs: Vec<T::VoteId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct CompositeLegDecidedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub leg: u32,
+    pub outcome: VoteOutcome,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct StandingVoteStartedEvent<T: Vote> {
+    pub caller: <T as System>::AccountId,
+    pub new_vote_id: T::VoteId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct StandingBallotReweighedEvent<T: Vote> {
+    pub vote_id: T::VoteId,
+    pub voter: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct AccountsLinkedEvent<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
+    pub accounts: Vec<<T as System>::AccountId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct LinkConfirmedEvent<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
+    pub who: <T as System>::AccountId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct LinkActivatedEvent<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct AccountsUnlinkedEvent<T: Vote> {
+    pub organization: T::OrgId,
+    pub set_id: u32,
 }
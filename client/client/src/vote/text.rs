@@ -0,0 +1,138 @@
+//! Vote topics and justifications written as plain text
+//!
+//! `VoteTextClient::create_topic` and `create_justification` compose a
+//! `VoteText` from the text, signed by the signer at the current time, and
+//! pin it in the local store without reaching the chain, so that drafts are
+//! written offline and the vote is opened or the ballot cast later. Opening
+//! or casting with the same text pins nothing new, blocks are addressed by
+//! their content. `resolve_text` reads a topic or justification back for
+//! display, fetching it from the network if it is not stored locally.
+use crate::{
+    chain::ChainPropertiesExt,
+    error::Error,
+    metrics::{
+        Op,
+        TracedExt,
+    },
+    vote::Vote,
+    TextBlock,
+    VoteText,
+};
+use libipld::{
+    cache::Cache,
+    cbor::DagCborCodec,
+    cid::Cid,
+};
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+use substrate_subxt::{
+    sp_core::crypto::Ss58Codec,
+    system::System,
+};
+use sunshine_client_utils::{
+    async_trait,
+    Client,
+    Node,
+    OffchainConfig,
+    Result,
+};
+
+#[async_trait]
+pub trait VoteTextClient<N: Node>: Client<N>
+where
+    N::Runtime: Vote,
+{
+    /// The text written by the signer now, its first line the title
+    fn compose_text(&self, text: &str) -> Result<VoteText>;
+    /// Pins the text to open a vote about, returning its cid
+    async fn create_topic(&self, text: &str) -> Result<Cid>;
+    /// Pins the text to justify a ballot with, returning its cid
+    async fn create_justification(&self, text: &str) -> Result<Cid>;
+    /// The text of a topic or justification, composed or plain
+    async fn resolve_text(&self, cid: &Cid) -> Result<String>;
+}
+
+#[async_trait]
+impl<N, C> VoteTextClient<N> for C
+where
+    N: Node,
+    N::Runtime: Vote,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    C: Client<N>,
+    C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, VoteText>
+        + Cache<OffchainConfig<N>, DagCborCodec, TextBlock>,
+{
+    fn compose_text(&self, text: &str) -> Result<VoteText> {
+        let signer = self.chain_signer().map_err(|_| Error::KeystoreLocked)?;
+        let author = self.chain_properties().ss58(signer.account_id());
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_millis() as u64)
+            .unwrap_or_default();
+        Ok(VoteText::compose(text, author, created_at))
+    }
+
+    async fn create_topic(&self, text: &str) -> Result<Cid> {
+        let topic = self.compose_text(text)?;
+        Ok(self
+            .offchain_client()
+            .insert(topic)
+            .traced(Op::OffchainWrite, "insert")
+            .await?)
+    }
+
+    async fn create_justification(&self, text: &str) -> Result<Cid> {
+        let justification = self.compose_text(text)?;
+        Ok(self
+            .offchain_client()
+            .insert(justification)
+            .traced(Op::OffchainWrite, "insert")
+            .await?)
+    }
+
+    async fn resolve_text(&self, cid: &Cid) -> Result<String> {
+        let composed: std::result::Result<VoteText, _> =
+            self.offchain_client().get(cid).await;
+        let err = match composed {
+            Ok(text) => return Ok(text.text()),
+            Err(err) => err,
+        };
+        // texts pinned before `VoteText` are plain blocks
+        let plain: std::result::Result<TextBlock, _> =
+            self.offchain_client().get(cid).await;
+        match plain {
+            Ok(block) => Ok(block.text),
+            Err(_) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::VoteText;
+
+    #[test]
+    fn texts_split_into_a_title_and_a_body() {
+        let text = VoteText::compose(
+            "  Fund the docs sprint\nTwo weeks, four writers.\n\nBudget below.",
+            "alice".to_string(),
+            7,
+        );
+        assert_eq!(text.title, "Fund the docs sprint");
+        assert_eq!(text.body, "Two weeks, four writers.\n\nBudget below.");
+        assert_eq!(
+            text.text(),
+            "Fund the docs sprint\n\nTwo weeks, four writers.\n\nBudget below."
+        );
+        let line = VoteText::compose("Yes, ship it", String::new(), 0);
+        assert_eq!(line.body, "");
+        assert_eq!(line.text(), "Yes, ship it");
+        let plain: VoteText = crate::TextBlock {
+            text: "one member one vote".to_string(),
+        }
+        .into();
+        assert_eq!(plain.text(), "one member one vote");
+    }
+}
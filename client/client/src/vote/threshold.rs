@@ -0,0 +1,219 @@
+//! Builders for the thresholds votes open with
+//!
+//! A threshold is either an amount of signal or a percent of the signal
+//! minted for the vote, and the pallet opens each kind with its own call.
+//! `VoteThresholdBuilder` picks the org representation first and the kind
+//! of threshold last, so a percent cannot be submitted as signal. The
+//! values are checked before submission: percents are 1 to 100, the in
+//! favor threshold is not zero and the against threshold does not exceed
+//! it.
+use crate::{
+    error::Error,
+    org::Org,
+    vote::Vote,
+};
+use sp_runtime::{
+    traits::Zero,
+    PerThing,
+};
+use substrate_subxt::sp_runtime;
+use sunshine_bounty_utils::{
+    organization::OrgRep,
+    vote::{
+        Threshold,
+        ThresholdInput,
+        XorThreshold,
+    },
+};
+
+/// An org with the threshold its vote must meet
+pub type VoteThreshold<T> = ThresholdInput<
+    OrgRep<<T as Org>::OrgId>,
+    XorThreshold<<T as Vote>::Signal, <T as Vote>::Percent>,
+>;
+
+/// Builds the `VoteThreshold` of a vote of an org
+pub struct VoteThresholdBuilder<T: Vote> {
+    org: OrgRep<T::OrgId>,
+}
+
+impl<T: Vote> VoteThresholdBuilder<T> {
+    /// Members vote with signal in proportion to their shares
+    pub fn weighted(org: T::OrgId) -> Self {
+        Self {
+            org: OrgRep::Weighted(org),
+        }
+    }
+
+    /// Every member votes with the same signal
+    pub fn flat(org: T::OrgId) -> Self {
+        Self {
+            org: OrgRep::Equal(org),
+        }
+    }
+
+    /// Decided once more than half of the signal votes either way
+    pub fn percent_majority(self) -> VoteThreshold<T> {
+        let half = T::Percent::ACCURACY / 2u8.into();
+        let majority = T::Percent::from_parts(half + 1u8.into());
+        self.percent_threshold(majority, Some(majority))
+    }
+
+    /// Approved once all of the signal votes in favor, rejected by any
+    /// signal against
+    pub fn unanimous(self) -> VoteThreshold<T> {
+        self.percent_threshold(
+            T::Percent::one(),
+            Some(T::Percent::from_parts(1u8.into())),
+        )
+    }
+
+    /// Decided once the percents of the signal vote in favor or against
+    pub fn percent(
+        self,
+        in_favor: u8,
+        against: Option<u8>,
+    ) -> Result<VoteThreshold<T>, Error> {
+        let percent = |p: u8| {
+            if p > 0 && p <= 100 {
+                Ok(T::Percent::from_percent(p.into()))
+            } else {
+                Err(Error::InvalidPercentThreshold)
+            }
+        };
+        let in_favor = percent(in_favor)?;
+        let against = against.map(percent).transpose()?;
+        if against.map_or(false, |a| a > in_favor) {
+            return Err(Error::AgainstExceedsInFavorThreshold)
+        }
+        Ok(self.percent_threshold(in_favor, against))
+    }
+
+    /// Decided once the signal in favor or against reaches the amounts
+    pub fn signal(
+        self,
+        in_favor: T::Signal,
+        against: Option<T::Signal>,
+    ) -> Result<VoteThreshold<T>, Error> {
+        if in_favor.is_zero() || against.map_or(false, |a| a.is_zero()) {
+            return Err(Error::ZeroSignalThreshold)
+        }
+        if against.map_or(false, |a| a > in_favor) {
+            return Err(Error::AgainstExceedsInFavorThreshold)
+        }
+        Ok(ThresholdInput::new(
+            self.org,
+            XorThreshold::Signal(Threshold::new(in_favor, against)),
+        ))
+    }
+
+    fn percent_threshold(
+        self,
+        in_favor: T::Percent,
+        against: Option<T::Percent>,
+    ) -> VoteThreshold<T> {
+        ThresholdInput::new(
+            self.org,
+            XorThreshold::Percent(Threshold::new(in_favor, against)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use substrate_subxt::sp_runtime::{
+        PerThing,
+        Permill,
+    };
+    use test_client::{
+        utils::{
+            organization::OrgRep,
+            vote::{
+                Threshold,
+                XorThreshold,
+            },
+        },
+        vote::VoteThresholdBuilder,
+        Error,
+        Runtime,
+    };
+
+    type Builder = VoteThresholdBuilder<Runtime>;
+
+    #[test]
+    fn builders_encode_the_kind_of_threshold_they_are_named_for() {
+        let signal = Builder::weighted(1).signal(100, None).unwrap();
+        assert_eq!(signal.org(), OrgRep::Weighted(1));
+        assert_eq!(
+            signal.threshold(),
+            XorThreshold::Signal(Threshold::new(100, None))
+        );
+        let percent = Builder::flat(2).percent(60, Some(40)).unwrap();
+        assert_eq!(percent.org(), OrgRep::Equal(2));
+        assert_eq!(
+            percent.threshold(),
+            XorThreshold::Percent(Threshold::new(
+                Permill::from_percent(60),
+                Some(Permill::from_percent(40))
+            ))
+        );
+        assert_eq!(
+            Builder::flat(2)
+                .percent(100, Some(100))
+                .unwrap()
+                .threshold(),
+            XorThreshold::Percent(Threshold::new(
+                Permill::one(),
+                Some(Permill::one())
+            ))
+        );
+    }
+
+    #[test]
+    fn majority_and_unanimity_round_up_to_the_next_signal() {
+        let majority = Permill::from_parts(500_001);
+        assert_eq!(
+            Builder::weighted(1).percent_majority().threshold(),
+            XorThreshold::Percent(Threshold::new(majority, Some(majority)))
+        );
+        // the pallet rounds percents of the turnout up
+        assert_eq!(majority.mul_ceil(4u64), 3);
+        assert_eq!(majority.mul_ceil(5u64), 3);
+        let unanimous = Builder::flat(1).unanimous();
+        assert_eq!(unanimous.org(), OrgRep::Equal(1));
+        assert_eq!(
+            unanimous.threshold(),
+            XorThreshold::Percent(Threshold::new(
+                Permill::one(),
+                Some(Permill::from_parts(1))
+            ))
+        );
+        assert_eq!(Permill::from_parts(1).mul_ceil(1_000u64), 1);
+    }
+
+    #[test]
+    fn invalid_thresholds_are_rejected_before_submission() {
+        for (in_favor, against) in vec![(0, None), (101, None), (60, Some(0))] {
+            assert!(matches!(
+                Builder::weighted(1).percent(in_favor, against),
+                Err(Error::InvalidPercentThreshold)
+            ));
+        }
+        assert!(matches!(
+            Builder::weighted(1).percent(40, Some(60)),
+            Err(Error::AgainstExceedsInFavorThreshold)
+        ));
+        assert!(matches!(
+            Builder::weighted(1).signal(0, None),
+            Err(Error::ZeroSignalThreshold)
+        ));
+        assert!(matches!(
+            Builder::weighted(1).signal(10, Some(0)),
+            Err(Error::ZeroSignalThreshold)
+        ));
+        assert!(matches!(
+            Builder::flat(1).signal(10, Some(11)),
+            Err(Error::AgainstExceedsInFavorThreshold)
+        ));
+    }
+}
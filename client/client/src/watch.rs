@@ -0,0 +1,146 @@
+//! Accounts followed without their key
+//!
+//! A watch-only account is an address saved under a label next to the
+//! keystore, without a secret. Its balance, votes and contributions are read
+//! by passing it to the read paths instead of the signer, but it never signs:
+//! `WatchList::ensure_can_sign` fails with `Error::WatchOnlyCannotSign` for
+//! it, so a command run as a watch-only account stops before submitting.
+use crate::error::Error;
+use std::{
+    collections::BTreeMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+use sunshine_client_utils::Result;
+
+const WATCH_ONLY_FILE: &str = "watch_only.json";
+
+/// The watch-only accounts saved under a client root, by label
+#[derive(Clone, Debug, Default)]
+pub struct WatchList {
+    path: PathBuf,
+    entries: BTreeMap<String, String>,
+}
+
+impl WatchList {
+    /// Loads the accounts saved under the client root, none if none were
+    /// saved yet
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(WATCH_ONLY_FILE);
+        let entries = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.entries)?)?;
+        Ok(())
+    }
+
+    /// Watches the ss58 address `account` as `label`, callers check that it
+    /// is an address of their chain
+    pub fn add(&mut self, label: &str, account: &str) -> Result<()> {
+        if self.entries.contains_key(label) {
+            return Err(Error::WatchOnlyLabelTaken {
+                label: label.to_string(),
+            }
+            .into())
+        }
+        self.entries.insert(label.to_string(), account.to_string());
+        Ok(())
+    }
+
+    /// Returns the address that was watched as `label`
+    pub fn remove(&mut self, label: &str) -> Result<String> {
+        Ok(self.entries.remove(label).ok_or_else(|| {
+            Error::UnknownWatchOnly {
+                label: label.to_string(),
+            }
+        })?)
+    }
+
+    pub fn get(&self, label: &str) -> Option<&str> {
+        self.entries.get(label).map(|s| s.as_str())
+    }
+
+    /// The address watched as `account` if it is a label, otherwise
+    /// `account` itself
+    pub fn resolve<'a>(&'a self, account: &'a str) -> &'a str {
+        self.get(account).unwrap_or(account)
+    }
+
+    pub fn is_watch_only(&self, account: &str) -> bool {
+        self.entries.values().any(|watched| watched == account)
+    }
+
+    /// Fails if the label or address `account` is watch-only
+    pub fn ensure_can_sign(&self, account: &str) -> Result<()> {
+        let account = self.resolve(account);
+        if self.is_watch_only(account) {
+            return Err(Error::WatchOnlyCannotSign {
+                account: account.to_string(),
+            }
+            .into())
+        }
+        Ok(())
+    }
+
+    /// The labels and addresses, ordered by label
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    const TREASURY: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn watched_accounts_survive_reloading() {
+        let root = TempDir::new("watch").unwrap();
+        let mut list = WatchList::load(root.path()).unwrap();
+        list.add("treasury", TREASURY).unwrap();
+        list.save().unwrap();
+        let mut list = WatchList::load(root.path()).unwrap();
+        assert_eq!(list.get("treasury"), Some(TREASURY));
+        assert_eq!(list.resolve("treasury"), TREASURY);
+        assert_eq!(list.resolve(TREASURY), TREASURY);
+        let err = list.add("treasury", TREASURY).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::WatchOnlyLabelTaken { .. })
+        ));
+        assert_eq!(list.remove("treasury").unwrap(), TREASURY);
+        let err = list.remove("treasury").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::UnknownWatchOnly { .. })
+        ));
+    }
+
+    #[test]
+    fn watched_accounts_cannot_sign() {
+        let root = TempDir::new("watch").unwrap();
+        let mut list = WatchList::load(root.path()).unwrap();
+        list.add("treasury", TREASURY).unwrap();
+        for account in &["treasury", TREASURY] {
+            let err = list.ensure_can_sign(account).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<Error>(),
+                Some(Error::WatchOnlyCannotSign { .. })
+            ));
+        }
+        assert!(list.ensure_can_sign("bob").is_ok());
+    }
+}
@@ -0,0 +1,319 @@
+//! Bounties and submissions composed on the device but not yet posted.
+//!
+//! Drafts are kept in their own sled database below the client root, apart
+//! from the keystore and the block store, so they survive restarts and the
+//! client being recreated. A draft is deleted only once the extrinsic posting
+//! it was included; a post that fails or times out keeps the draft so it can
+//! be retried.
+use crate::error::{
+    ErrorCode,
+    FfiError,
+    ResultExt,
+};
+use once_cell::sync::Lazy;
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+    Serialize,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+    sync::Mutex,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+use sunshine_client_utils::Result;
+
+const DRAFTS_DIR: &str = "drafts";
+
+/// sled locks its directory, so every store is opened once per process
+static DATABASES: Lazy<Mutex<HashMap<PathBuf, sled::Db>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// What posting a draft submits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DraftKind {
+    /// A `BountyDraft`, posted as a new bounty
+    Bounty,
+    /// A `SubmissionDraft`, posted as a submission for a bounty
+    Submission,
+}
+
+impl DraftKind {
+    /// Fails unless `payload` is the JSON of this kind's draft
+    fn check(self, payload: &str) -> Result<()> {
+        match self {
+            Self::Bounty => parse::<BountyDraft>(payload).map(drop),
+            Self::Submission => parse::<SubmissionDraft>(payload).map(drop),
+        }
+    }
+}
+
+impl FromStr for DraftKind {
+    type Err = FfiError;
+
+    fn from_str(kind: &str) -> std::result::Result<Self, Self::Err> {
+        match kind {
+            "bounty" => Ok(Self::Bounty),
+            "submission" => Ok(Self::Submission),
+            _ => {
+                Err(FfiError::new(
+                    ErrorCode::InvalidDraft,
+                    format!("unknown draft kind {}", kind),
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for DraftKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bounty => f.write_str("bounty"),
+            Self::Submission => f.write_str("submission"),
+        }
+    }
+}
+
+/// The arguments of `Bounty::post`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BountyDraft {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u64,
+    pub amount: String,
+    #[serde(default)]
+    pub asset_id: Option<String>,
+}
+
+/// The arguments of `Bounty::submit` and the evidence attached to the
+/// submission
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmissionDraft {
+    pub bounty_id: String,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u64,
+    pub amount: String,
+    #[serde(default)]
+    pub attachments: Vec<DraftAttachment>,
+}
+
+/// An evidence file pinned by `Bounty::attach_evidence`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftAttachment {
+    pub cid: String,
+    pub mime: String,
+    pub size: u64,
+}
+
+/// A saved draft, `payload` is the JSON it was saved with
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Draft {
+    pub id: u64,
+    pub kind: DraftKind,
+    pub payload: String,
+    /// Seconds since the unix epoch
+    pub created_at: u64,
+}
+
+impl Draft {
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        parse(&self.payload)
+    }
+}
+
+/// The drafts saved below one client root
+pub struct Drafts {
+    db: sled::Db,
+}
+
+impl Drafts {
+    pub fn open(root: &Path) -> Result<Self> {
+        let path = root.join(DRAFTS_DIR);
+        let mut databases = DATABASES.lock().unwrap();
+        let db = match databases.get(&path) {
+            Some(db) => db.clone(),
+            None => {
+                let db = sled::open(&path).code(ErrorCode::IoError)?;
+                databases.insert(path, db.clone());
+                db
+            }
+        };
+        Ok(Self { db })
+    }
+
+    /// Saves a draft, failing if `payload` does not match `kind`
+    pub fn save(&self, kind: DraftKind, payload: &str) -> Result<u64> {
+        kind.check(payload)?;
+        let id = self.db.generate_id().code(ErrorCode::IoError)?;
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let draft = Draft {
+            id,
+            kind,
+            payload: payload.to_string(),
+            created_at,
+        };
+        self.db
+            .insert(id.to_be_bytes(), serde_json::to_vec(&draft)?)
+            .code(ErrorCode::IoError)?;
+        // the app may be killed right after the draft was saved
+        self.db.flush().code(ErrorCode::IoError)?;
+        Ok(id)
+    }
+
+    /// Replaces the payload of a saved draft, failing if it does not match
+    /// the kind of the draft
+    pub fn update(&self, id: u64, payload: &str) -> Result<Draft> {
+        let mut draft = self.get(id)?;
+        draft.kind.check(payload)?;
+        draft.payload = payload.to_string();
+        self.db
+            .insert(id.to_be_bytes(), serde_json::to_vec(&draft)?)
+            .code(ErrorCode::IoError)?;
+        self.db.flush().code(ErrorCode::IoError)?;
+        Ok(draft)
+    }
+
+    pub fn get(&self, id: u64) -> Result<Draft> {
+        match self.db.get(id.to_be_bytes()).code(ErrorCode::IoError)? {
+            Some(value) => Ok(serde_json::from_slice(&value)?),
+            None => {
+                Err(FfiError::new(
+                    ErrorCode::DraftNotFound,
+                    format!("no draft with id {}", id),
+                )
+                .into())
+            }
+        }
+    }
+
+    /// All drafts, oldest first
+    pub fn list(&self) -> Result<Vec<Draft>> {
+        self.db
+            .iter()
+            .values()
+            .map(|value| {
+                let value = value.code(ErrorCode::IoError)?;
+                Ok(serde_json::from_slice(&value)?)
+            })
+            .collect()
+    }
+
+    /// Returns `false` if there was no draft with `id`
+    pub fn delete(&self, id: u64) -> Result<bool> {
+        let removed = self
+            .db
+            .remove(id.to_be_bytes())
+            .code(ErrorCode::IoError)?
+            .is_some();
+        self.db.flush().code(ErrorCode::IoError)?;
+        Ok(removed)
+    }
+}
+
+fn parse<T: DeserializeOwned>(payload: &str) -> Result<T> {
+    serde_json::from_str(payload).code(ErrorCode::InvalidDraft)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    const BOUNTY: &str = r#"{"repo_owner":"sunshine-protocol","repo_name":"sunshine-node","issue_number":42,"amount":"1000"}"#;
+    const SUBMISSION: &str = r#"{"bounty_id":"1","repo_owner":"sunshine-protocol","repo_name":"sunshine-node","issue_number":43,"amount":"100"}"#;
+
+    #[test]
+    fn drafts_survive_reopening() {
+        let root = TempDir::new("drafts").unwrap();
+        let id = Drafts::open(root.path())
+            .unwrap()
+            .save(DraftKind::Bounty, BOUNTY)
+            .unwrap();
+        let drafts = Drafts::open(root.path()).unwrap();
+        let draft = drafts.get(id).unwrap();
+        assert_eq!(draft.kind, DraftKind::Bounty);
+        assert_eq!(
+            draft.payload::<BountyDraft>().unwrap(),
+            BountyDraft {
+                repo_owner: "sunshine-protocol".to_string(),
+                repo_name: "sunshine-node".to_string(),
+                issue_number: 42,
+                amount: "1000".to_string(),
+                asset_id: None,
+            }
+        );
+        assert_eq!(drafts.list().unwrap(), vec![draft]);
+    }
+
+    #[test]
+    fn drafts_are_listed_oldest_first_and_deleted_once() {
+        let root = TempDir::new("drafts").unwrap();
+        let drafts = Drafts::open(root.path()).unwrap();
+        let first = drafts.save(DraftKind::Bounty, BOUNTY).unwrap();
+        let second = drafts.save(DraftKind::Bounty, BOUNTY).unwrap();
+        let ids = |drafts: &Drafts| {
+            drafts
+                .list()
+                .unwrap()
+                .into_iter()
+                .map(|d| d.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&drafts), vec![first, second]);
+        assert!(drafts.delete(first).unwrap());
+        assert!(!drafts.delete(first).unwrap());
+        assert_eq!(ids(&drafts), vec![second]);
+        let err = FfiError::from(drafts.get(first).unwrap_err());
+        assert_eq!(err.code, ErrorCode::DraftNotFound);
+    }
+
+    #[test]
+    fn submission_drafts_keep_their_attachments() {
+        let root = TempDir::new("drafts").unwrap();
+        let drafts = Drafts::open(root.path()).unwrap();
+        // drafts saved before attachments have none
+        let id = drafts.save(DraftKind::Submission, SUBMISSION).unwrap();
+        let mut draft: SubmissionDraft =
+            drafts.get(id).unwrap().payload().unwrap();
+        assert!(draft.attachments.is_empty());
+        draft.attachments.push(DraftAttachment {
+            cid: "bafyreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy"
+                .to_string(),
+            mime: "image/png".to_string(),
+            size: 2048,
+        });
+        let payload = serde_json::to_string(&draft).unwrap();
+        drafts.update(id, &payload).unwrap();
+        let reopened = Drafts::open(root.path()).unwrap().get(id).unwrap();
+        assert_eq!(reopened.payload::<SubmissionDraft>().unwrap(), draft);
+        let err = FfiError::from(drafts.update(id, BOUNTY).unwrap_err());
+        assert_eq!(err.code, ErrorCode::InvalidDraft);
+    }
+
+    #[test]
+    fn payloads_must_match_their_kind() {
+        let root = TempDir::new("drafts").unwrap();
+        let drafts = Drafts::open(root.path()).unwrap();
+        let err = FfiError::from(
+            drafts.save(DraftKind::Submission, BOUNTY).unwrap_err(),
+        );
+        assert_eq!(err.code, ErrorCode::InvalidDraft);
+        let err = "vote".parse::<DraftKind>().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidDraft);
+        assert!(drafts.list().unwrap().is_empty());
+    }
+}
@@ -1,31 +1,415 @@
-use serde::Serialize;
+use serde::{
+    Serialize,
+    Serializer,
+};
+
+/// The version of the dto schema, bumped on every breaking change.
+///
+/// Within a version dtos may only gain fields; removing or retyping a field
+/// requires a new version and is caught by the snapshot test below.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A balance, encoded as a decimal string so Dart and JS do not lose
+/// precision above 2^53
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Balance(pub u128);
+
+impl Serialize for Balance {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// An amount of vote signal, encoded as a decimal string like `Balance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal(pub u128);
+
+impl Serialize for Signal {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// The name of a field's JSON type as it appears in `ffi_schema`
+pub trait WireType {
+    const WIRE_TYPE: &'static str;
+}
+
+impl WireType for String {
+    const WIRE_TYPE: &'static str = "string";
+}
+
+impl WireType for Option<String> {
+    const WIRE_TYPE: &'static str = "nullable_string";
+}
+
+impl WireType for Vec<String> {
+    const WIRE_TYPE: &'static str = "string_list";
+}
+
+impl WireType for Balance {
+    const WIRE_TYPE: &'static str = "balance_string";
+}
+
+impl WireType for Option<Balance> {
+    const WIRE_TYPE: &'static str = "nullable_balance_string";
+}
+
+impl WireType for Signal {
+    const WIRE_TYPE: &'static str = "signal_string";
+}
+
+impl WireType for Option<Signal> {
+    const WIRE_TYPE: &'static str = "nullable_signal_string";
+}
+
+impl WireType for u32 {
+    const WIRE_TYPE: &'static str = "u32";
+}
+
+impl WireType for u64 {
+    const WIRE_TYPE: &'static str = "u64";
+}
+
+impl WireType for Option<u64> {
+    const WIRE_TYPE: &'static str = "nullable_u64";
+}
+
+impl WireType for bool {
+    const WIRE_TYPE: &'static str = "bool";
+}
+
+impl WireType for Option<bool> {
+    const WIRE_TYPE: &'static str = "nullable_bool";
+}
 
 #[derive(Debug, Serialize)]
-pub struct BountyInformation {
-    pub id: String,
-    pub repo_owner: String,
-    pub repo_name: String,
-    pub issue_number: u64,
-    pub depositer: String,
-    pub total: u128,
+pub struct FieldSchema {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub ty: &'static str,
 }
 
 #[derive(Debug, Serialize)]
-pub struct BountySubmissionInformation {
-    pub id: String,
-    pub repo_owner: String,
-    pub repo_name: String,
-    pub issue_number: u64,
-    pub bounty_id: String,
-    pub submitter: String,
-    pub amount: u128,
-    pub awaiting_review: bool,
-    pub approved: bool,
+pub struct DtoSchema {
+    pub name: &'static str,
+    pub fields: Vec<FieldSchema>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ContributionInformation {
-    pub id: String,
-    pub account: String,
-    pub total: u128,
+pub struct Schema {
+    pub schema_version: u32,
+    pub dtos: Vec<DtoSchema>,
+}
+
+/// Declares a dto with a leading `schema_version` field and registers its
+/// field names and wire types for `ffi_schema`
+macro_rules! dto {
+    ($(
+        pub struct $name:ident {
+            $(pub $field:ident: $ty:ty,)*
+        }
+    )*) => {
+        $(
+            #[derive(Clone, Debug, Serialize)]
+            pub struct $name {
+                pub schema_version: u32,
+                $(pub $field: $ty,)*
+            }
+
+            impl $name {
+                pub fn schema() -> DtoSchema {
+                    DtoSchema {
+                        name: stringify!($name),
+                        fields: vec![
+                            FieldSchema {
+                                name: "schema_version",
+                                ty: <u32 as WireType>::WIRE_TYPE,
+                            },
+                            $(FieldSchema {
+                                name: stringify!($field),
+                                ty: <$ty as WireType>::WIRE_TYPE,
+                            },)*
+                        ],
+                    }
+                }
+            }
+
+            impl WireType for Vec<$name> {
+                const WIRE_TYPE: &'static str =
+                    concat!(stringify!($name), "_list");
+            }
+        )*
+
+        /// The schema of every dto returned over the ffi and the http api
+        pub fn schema() -> Schema {
+            Schema {
+                schema_version: SCHEMA_VERSION,
+                dtos: vec![$($name::schema(),)*],
+            }
+        }
+    };
+}
+
+dto! {
+    pub struct BountyInformation {
+        pub id: String,
+        pub repo_owner: String,
+        pub repo_name: String,
+        pub issue_number: u64,
+        pub depositer: String,
+        pub total: Balance,
+        pub admins: Vec<String>,
+        pub asset_id: Option<String>,
+        pub symbol: String,
+        pub decimals: u32,
+        pub tags: Vec<String>,
+        pub posted_at: u64,
+        pub first_submission_at: Option<u64>,
+        pub resolved_at: Option<u64>,
+        pub posted_at_ms: Option<u64>,
+        pub first_submission_at_ms: Option<u64>,
+        pub resolved_at_ms: Option<u64>,
+        pub timestamped: bool,
+        pub org: Option<String>,
+        pub matching_org: Option<String>,
+        pub matching_ratio_ppm: Option<u64>,
+        pub matching_remaining: Option<Balance>,
+    }
+
+    pub struct BountySubmissionInformation {
+        pub id: String,
+        pub repo_owner: String,
+        pub repo_name: String,
+        pub issue_number: u64,
+        pub bounty_id: String,
+        pub submitter: String,
+        pub amount: Balance,
+        pub awaiting_review: bool,
+        pub approved: bool,
+        pub reviewer: Option<String>,
+        pub submitted_at: u64,
+        pub submitted_at_ms: Option<u64>,
+        pub timestamped: bool,
+        pub submitter_orgs: Vec<String>,
+        pub handle_match: Option<bool>,
+        pub split: Vec<PayoutSplitInformation>,
+        pub attachments: Vec<AttachmentInformation>,
+        pub times_out_at: Option<u64>,
+        pub nearing_timeout: bool,
+        pub timed_out: bool,
+    }
+
+    pub struct PayoutSplitInformation {
+        pub account: String,
+        pub share_ppm: u32,
+    }
+
+    pub struct AttachmentInformation {
+        pub cid: String,
+        pub mime: String,
+        pub size: u64,
+    }
+
+    pub struct ContributionInformation {
+        pub id: String,
+        pub account: String,
+        pub total: Balance,
+    }
+
+    pub struct VoteProgressInformation {
+        pub id: String,
+        pub in_favor: Signal,
+        pub against: Signal,
+        pub abstain: Signal,
+        pub turnout: Signal,
+        pub threshold_in_favor: Signal,
+        pub threshold_against: Option<Signal>,
+        pub remaining_to_pass: Option<Signal>,
+        pub remaining_to_reject: Option<Signal>,
+        pub outcome: String,
+    }
+
+    pub struct MemberInformation {
+        pub org_id: String,
+        pub account: String,
+        pub shares: u64,
+        pub name: Option<String>,
+    }
+
+    pub struct PaymentRequestInformation {
+        pub account: String,
+        pub amount: Option<Balance>,
+        pub memo: Option<String>,
+    }
+
+    pub struct EventInformation {
+        pub module: String,
+        pub variant: String,
+        pub data: String,
+    }
+
+    pub struct SyncSnapshot {
+        pub account: String,
+        pub balance: Balance,
+        pub pending_votes: Vec<VoteProgressInformation>,
+        pub my_bounties: Vec<BountyInformation>,
+        pub my_submissions: Vec<BountySubmissionInformation>,
+        pub my_contributions: Vec<ContributionInformation>,
+        pub open_bounties: Vec<BountyInformation>,
+        pub last_synced: u64,
+    }
+
+    pub struct ChainPropertiesInformation {
+        pub ss58_format: u32,
+        pub token_decimals: u32,
+        pub token_symbol: String,
+    }
+
+    pub struct ChainConstantsInformation {
+        pub spec_version: u32,
+        pub existential_deposit: Balance,
+        pub block_time: Option<u64>,
+        pub max_org_members: u32,
+        pub free_ballots: u32,
+        pub max_composite_legs: u32,
+        pub max_justification_history: u32,
+        pub bounty_min_deposit: Balance,
+        pub bounty_min_contribution: Balance,
+        pub bounty_submission_deposit: Balance,
+        pub max_pending_submissions: u32,
+        pub max_bounty_admins: u32,
+        pub max_bounty_tags: u32,
+        pub max_payout_splits: u32,
+        pub bounty_dispute_deposit: Balance,
+        pub bounty_dispute_window: u32,
+        pub bounty_review_timeout: u32,
+    }
+
+    pub struct RuntimeVersionInformation {
+        pub spec_name: String,
+        pub impl_name: String,
+        pub spec_version: u32,
+        pub impl_version: u32,
+        pub transaction_version: u32,
+    }
+
+    pub struct FeatureSupportInformation {
+        pub feature: String,
+        pub pallet: String,
+        pub support: String,
+        pub reason: Option<String>,
+    }
+
+    pub struct CompatibilityInformation {
+        pub spec_version: u32,
+        pub transaction_version: u32,
+        pub features: Vec<FeatureSupportInformation>,
+    }
+
+    pub struct DryRunInformation {
+        pub would_succeed: bool,
+        pub outcome: String,
+        pub module: Option<String>,
+        pub error: Option<String>,
+        pub reason: Option<String>,
+    }
+
+    pub struct DraftInformation {
+        pub id: u64,
+        pub kind: String,
+        pub payload: String,
+        pub created_at: u64,
+    }
+
+    pub struct WatchOnlyInformation {
+        pub label: String,
+        pub account: String,
+    }
+
+    pub struct LinkInformation {
+        pub kind: String,
+        pub id: String,
+        pub exists: bool,
+        pub preview: Option<String>,
+    }
+
+    pub struct RejectionInformation {
+        pub submission_id: String,
+        pub bounty_id: String,
+        pub submitter: String,
+        pub amount: Balance,
+        pub rejected_at: u64,
+        pub arbitration_threshold: String,
+        pub disputed: bool,
+        pub argument: Option<String>,
+        pub dispute_deposit: Option<Balance>,
+        pub vote_id: Option<String>,
+    }
+}
+
+/// Returns a JSON description of the field names and types of all dtos
+pub fn ffi_schema() -> String {
+    serde_json::to_string_pretty(&schema())
+        .expect("schema only contains strings and numbers; qed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const SNAPSHOT: &str = include_str!("../schema.json");
+
+    #[test]
+    fn balances_serialize_as_strings() {
+        let info = ContributionInformation {
+            schema_version: SCHEMA_VERSION,
+            id: "1".to_string(),
+            account: "alice".to_string(),
+            total: Balance(u128::MAX),
+        };
+        let json: Value = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["total"], Value::String(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn schema_changes_are_additive() {
+        let snapshot: Value = serde_json::from_str(SNAPSHOT).unwrap();
+        let current: Value = serde_json::from_str(&ffi_schema()).unwrap();
+        if snapshot["schema_version"] != current["schema_version"] {
+            // a new major version may break the old snapshot, regenerate it
+            return
+        }
+        let find = |dtos: &Value, name: &Value| {
+            dtos.as_array()
+                .unwrap()
+                .iter()
+                .find(|d| &d["name"] == name)
+                .cloned()
+        };
+        for old in snapshot["dtos"].as_array().unwrap() {
+            let new =
+                find(&current["dtos"], &old["name"]).unwrap_or_else(|| {
+                    panic!(
+                        "dto {} was removed, bump SCHEMA_VERSION",
+                        old["name"]
+                    )
+                });
+            for field in old["fields"].as_array().unwrap() {
+                assert!(
+                    new["fields"].as_array().unwrap().contains(field),
+                    "field {} of {} was removed or retyped, bump SCHEMA_VERSION",
+                    field,
+                    old["name"],
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,429 @@
+use serde::Serialize;
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    fmt,
+    future::Future,
+    num::ParseIntError,
+};
+use substrate_subxt::{
+    Error as SubxtError,
+    RuntimeError,
+};
+use sunshine_bounty_client::{
+    compat,
+    submit,
+    Error as ClientError,
+};
+use sunshine_client_utils::Result;
+
+/// Stable, machine readable error codes returned over the ffi.
+///
+/// Codes are serialized in SCREAMING_SNAKE_CASE and are never renamed or
+/// reused, new failure modes get new codes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The password is shorter than 8 characters
+    PasswordTooShort,
+    /// The paperkey is not a valid mnemonic
+    InvalidPaperkey,
+    /// The suri could not be turned into a key
+    InvalidSuri,
+    /// The keystore is locked or has no key yet
+    KeystoreLocked,
+    /// The keystore could not be unlocked, usually a wrong password
+    KeystoreUnlockFailed,
+    /// Any other keystore failure
+    KeystoreError,
+    /// A numeric argument could not be parsed
+    InvalidNumber,
+    /// An account argument is not a valid ss58 address
+    InvalidAccountId,
+    /// The balances pallet rejected a transfer or reservation
+    InsufficientBalance,
+    /// A transfer was submitted but could not be confirmed
+    TransferFailed,
+    /// The bounty pallet rejected the call, see `details` for the reason
+    BountyRejected,
+    /// A submission references the bounty it is submitted for
+    SubmissionMustDifferFromBounty,
+    /// Offchain bounty or submission metadata could not be fetched
+    MetadataNotFound,
+    /// The backup was exported from another chain than the configured one
+    BackupChainMismatch,
+    /// The backup could not be decrypted, usually a wrong password
+    BackupDecryptionFailed,
+    /// The file is not a backup or was truncated or modified
+    InvalidBackup,
+    /// Reading or writing local files failed
+    IoError,
+    /// The vote pallet rejected the call, see `details` for the reason
+    VoteRejected,
+    /// The recovery pallet rejected the call, see `details` for the reason
+    RecoveryRejected,
+    /// Another pallet rejected the call, see `details` for the reason
+    RuntimeError,
+    /// The call was included but its event could not be found
+    EventNotFound,
+    /// The node could not be reached
+    ConnectionFailed,
+    /// A scanned payload is malformed or of an unsupported version
+    InvalidQrPayload,
+    /// A scanned payload was made for another chain than the configured one
+    QrChainMismatch,
+    /// A call or signature is not valid hex
+    InvalidHex,
+    /// A bounty tag is not up to 16 lowercase letters, digits or dashes
+    InvalidBountyTag,
+    /// An org handle is not up to 32 lowercase letters, digits or dashes
+    InvalidOrgHandle,
+    /// The bounty id is neither of an open nor of a closed bounty
+    BountyNotFound,
+    /// An argument is not a valid cid
+    InvalidCid,
+    /// A log level is not one of off, error, warn, info, debug or trace
+    InvalidLogLevel,
+    /// A vote threshold is out of range, like a percent above 100 or an
+    /// against threshold above the in favor one
+    InvalidVoteThreshold,
+    /// The node did not confirm an extrinsic in time, it may still be
+    /// included, `details` has its hash
+    Timeout,
+    /// The dry run before submitting failed, the extrinsic was not
+    /// submitted, `details` has the reason
+    DryRunFailed,
+    /// The user cast no ballot in the vote to take a receipt of
+    NoBallotCast,
+    /// A vote receipt is not valid base64 or was truncated or modified
+    InvalidVoteReceipt,
+    /// A vote receipt was taken on another chain than the configured one
+    VoteReceiptChainMismatch,
+    /// A vote receipt is not proven by the state of its block
+    VoteReceiptNotProven,
+    /// A draft kind is unknown or the draft does not match its kind
+    InvalidDraft,
+    /// There is no saved draft with the id
+    DraftNotFound,
+    /// The runtime of the node is not one the client supports, nothing was
+    /// submitted, `details` has its spec version
+    UnsupportedRuntime,
+    /// The account is watch-only, there is no key to sign for it with
+    WatchOnlyCannotSign,
+    /// Another account is already watched under the label
+    WatchOnlyLabelTaken,
+    /// No account is watched under the label
+    UnknownWatchOnly,
+    /// A link is malformed or opens something the app does not know
+    InvalidLink,
+    /// A link was made on another chain than the configured one
+    LinkChainMismatch,
+    /// An evidence file is larger than the limit, `message` has both sizes
+    EvidenceTooLarge,
+    /// An evidence file is not valid base64, has no valid mime type or
+    /// does not match its cid
+    InvalidEvidence,
+    /// An airdrop has a chunk size out of range or lists an account twice
+    InvalidAirdrop,
+    /// Anything not covered above
+    Unknown,
+}
+
+/// An error returned over the ffi.
+///
+/// Displays as the JSON envelope
+/// `{ "code": "...", "message": "...", "details": {...} }` or, with the
+/// `legacy-errors` feature, as the plain message.
+#[derive(Clone, Debug, Serialize)]
+pub struct FfiError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Value,
+}
+
+impl FfiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: Value::Object(Map::new()),
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Classifies an error that was not given a code where it was raised
+    fn classify(err: &anyhow::Error) -> Self {
+        let message = err.to_string();
+        if err.downcast_ref::<ParseIntError>().is_some() {
+            return Self::new(ErrorCode::InvalidNumber, message)
+        }
+        if let Some(err) = err.downcast_ref::<ClientError>() {
+            let code = match err {
+                ClientError::EventNotFound => ErrorCode::EventNotFound,
+                ClientError::ParseIntError => ErrorCode::InvalidNumber,
+                ClientError::SubmissionMustDifferFromBountyInfo => {
+                    ErrorCode::SubmissionMustDifferFromBounty
+                }
+                ClientError::BackupChainMismatch => {
+                    ErrorCode::BackupChainMismatch
+                }
+                ClientError::BackupDecryptionFailed => {
+                    ErrorCode::BackupDecryptionFailed
+                }
+                ClientError::InvalidBackup => ErrorCode::InvalidBackup,
+                ClientError::InvalidOrgHandle => ErrorCode::InvalidOrgHandle,
+                ClientError::BountyNotFound => ErrorCode::BountyNotFound,
+                ClientError::InvalidPercentThreshold
+                | ClientError::ZeroSignalThreshold
+                | ClientError::AgainstExceedsInFavorThreshold
+                | ClientError::VotesCastThresholdNotRegistered => {
+                    ErrorCode::InvalidVoteThreshold
+                }
+                ClientError::Timeout { .. } => ErrorCode::Timeout,
+                ClientError::DryRunFailed { .. } => ErrorCode::DryRunFailed,
+                ClientError::NoBallotCast => ErrorCode::NoBallotCast,
+                ClientError::InvalidVoteReceipt => {
+                    ErrorCode::InvalidVoteReceipt
+                }
+                ClientError::VoteReceiptChainMismatch => {
+                    ErrorCode::VoteReceiptChainMismatch
+                }
+                ClientError::VoteReceiptNotProven => {
+                    ErrorCode::VoteReceiptNotProven
+                }
+                ClientError::KeystoreLocked => ErrorCode::KeystoreLocked,
+                ClientError::UnsupportedRuntime { .. } => {
+                    ErrorCode::UnsupportedRuntime
+                }
+                ClientError::CallVectorMismatch { .. }
+                | ClientError::CallVectorUnavailable { .. } => {
+                    ErrorCode::Unknown
+                }
+                ClientError::WatchOnlyCannotSign { .. } => {
+                    ErrorCode::WatchOnlyCannotSign
+                }
+                ClientError::WatchOnlyLabelTaken { .. } => {
+                    ErrorCode::WatchOnlyLabelTaken
+                }
+                ClientError::UnknownWatchOnly { .. } => {
+                    ErrorCode::UnknownWatchOnly
+                }
+                ClientError::EvidenceTooLarge { .. } => {
+                    ErrorCode::EvidenceTooLarge
+                }
+                ClientError::InvalidMimeType { .. }
+                | ClientError::EvidenceCorrupt => ErrorCode::InvalidEvidence,
+                ClientError::EvidenceUnavailable { .. } => {
+                    ErrorCode::MetadataNotFound
+                }
+                ClientError::InvalidAirdropChunk { .. }
+                | ClientError::DuplicateAirdropAccount => {
+                    ErrorCode::InvalidAirdrop
+                }
+            };
+            return Self::new(code, message)
+        }
+        if let Some(ClientError::Timeout { extrinsic_hash }) =
+            err.chain().find_map(submit::timed_out)
+        {
+            return Self::new(ErrorCode::Timeout, message).with_details(
+                serde_json::json!({ "extrinsicHash": extrinsic_hash }),
+            )
+        }
+        if let Some(ClientError::DryRunFailed { reason }) =
+            err.chain().find_map(submit::dry_run_failed)
+        {
+            return Self::new(ErrorCode::DryRunFailed, message)
+                .with_details(serde_json::json!({ "reason": reason }))
+        }
+        if let Some(ClientError::UnsupportedRuntime { spec_version }) =
+            err.chain().find_map(compat::unsupported_runtime)
+        {
+            return Self::new(ErrorCode::UnsupportedRuntime, message)
+                .with_details(
+                    serde_json::json!({ "specVersion": spec_version }),
+                )
+        }
+        if err.downcast_ref::<std::io::Error>().is_some() {
+            return Self::new(ErrorCode::IoError, message)
+        }
+        match err.downcast_ref::<SubxtError>() {
+            Some(SubxtError::Rpc(_)) | Some(SubxtError::Io(_)) => {
+                Self::new(ErrorCode::ConnectionFailed, message)
+            }
+            Some(SubxtError::Runtime(RuntimeError::Module(err))) => {
+                let code = match err.module.as_str() {
+                    "Bounty" => ErrorCode::BountyRejected,
+                    "Vote" => ErrorCode::VoteRejected,
+                    "Recovery" => ErrorCode::RecoveryRejected,
+                    "Balances" => ErrorCode::InsufficientBalance,
+                    _ => ErrorCode::RuntimeError,
+                };
+                Self::new(code, message).with_details(serde_json::json!({
+                    "module": err.module,
+                    "error": err.error,
+                }))
+            }
+            Some(SubxtError::Runtime(_)) => {
+                Self::new(ErrorCode::RuntimeError, message)
+            }
+            _ => Self::new(ErrorCode::Unknown, message),
+        }
+    }
+}
+
+impl From<anyhow::Error> for FfiError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<FfiError>() {
+            Ok(err) => err,
+            Err(err) => Self::classify(&err),
+        }
+    }
+}
+
+impl fmt::Display for FfiError {
+    #[cfg(not(feature = "legacy-errors"))]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(json) => f.write_str(&json),
+            Err(_) => f.write_str(&self.message),
+        }
+    }
+
+    #[cfg(feature = "legacy-errors")]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FfiError {}
+
+/// Tags an error with the code it is returned under
+pub trait ResultExt<T> {
+    fn code(self, code: ErrorCode) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> ResultExt<T> for std::result::Result<T, E> {
+    fn code(self, code: ErrorCode) -> Result<T> {
+        self.map_err(|err| {
+            let err = err.into();
+            match err.downcast::<FfiError>() {
+                Ok(err) => err.into(),
+                Err(err) => FfiError::new(code, err.to_string()).into(),
+            }
+        })
+    }
+}
+
+/// Runs an ffi call, turning any failure into an `FfiError`
+pub async fn reply<T, F>(call: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    call.await.map_err(|err| FfiError::from(err).into())
+}
+
+/// Runs an ffi call and encodes its result as `{ "ok": ... }`, or as the
+/// bare JSON value with the `legacy-errors` feature
+pub async fn reply_json<T, F>(call: F) -> Result<String>
+where
+    T: Serialize,
+    F: Future<Output = Result<T>>,
+{
+    let value = reply(call).await?;
+    Ok(encode(&value)?)
+}
+
+#[cfg(not(feature = "legacy-errors"))]
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    ok: &'a T,
+}
+
+#[cfg(not(feature = "legacy-errors"))]
+fn encode<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string(&Envelope { ok: value })
+}
+
+#[cfg(feature = "legacy-errors")]
+fn encode<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    // missing lists used to be returned as an empty string
+    match serde_json::to_string(value)? {
+        json if json == "null" => Ok(String::new()),
+        json => Ok(json),
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "legacy-errors"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sunshine_ffi_utils::async_std::task::block_on;
+
+    #[test]
+    fn errors_display_as_envelope() {
+        let err =
+            FfiError::new(ErrorCode::PasswordTooShort, "Password Too Short");
+        let json: Value = serde_json::from_str(&err.to_string()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "code": "PASSWORD_TOO_SHORT",
+                "message": "Password Too Short",
+                "details": {},
+            })
+        );
+    }
+
+    #[test]
+    fn untagged_errors_are_classified() {
+        let err: anyhow::Error = "x".parse::<u64>().unwrap_err().into();
+        assert_eq!(FfiError::from(err).code, ErrorCode::InvalidNumber);
+        let err = anyhow::anyhow!("something else");
+        assert_eq!(FfiError::from(err).code, ErrorCode::Unknown);
+        let err: anyhow::Error =
+            SubxtError::Other("timed out waiting for extrinsic 0x01".into())
+                .into();
+        let err = FfiError::from(err);
+        assert_eq!(err.code, ErrorCode::Timeout);
+        assert_eq!(err.details, json!({ "extrinsicHash": "0x01" }));
+        let err: anyhow::Error =
+            SubxtError::Other("unsupported runtime spec version 7".into())
+                .into();
+        let err = FfiError::from(err);
+        assert_eq!(err.code, ErrorCode::UnsupportedRuntime);
+        assert_eq!(err.details, json!({ "specVersion": 7 }));
+    }
+
+    #[test]
+    fn airdrop_errors_are_classified() {
+        let err: anyhow::Error =
+            ClientError::InvalidAirdropChunk { max: 100 }.into();
+        assert_eq!(FfiError::from(err).code, ErrorCode::InvalidAirdrop);
+        let err: anyhow::Error = ClientError::DuplicateAirdropAccount.into();
+        assert_eq!(FfiError::from(err).code, ErrorCode::InvalidAirdrop);
+    }
+
+    #[test]
+    fn codes_survive_reraising() {
+        let tagged = Err::<(), _>(anyhow::anyhow!("bad words"))
+            .code(ErrorCode::InvalidPaperkey);
+        let retagged = tagged.code(ErrorCode::KeystoreError).unwrap_err();
+        assert_eq!(FfiError::from(retagged).code, ErrorCode::InvalidPaperkey);
+    }
+
+    #[test]
+    fn results_are_wrapped_in_ok() {
+        let json = block_on(reply_json(async { Ok(vec![1u32, 2]) })).unwrap();
+        assert_eq!(json, r#"{"ok":[1,2]}"#);
+        let none = block_on(reply_json(async { Ok(None::<Vec<u32>>) }));
+        assert_eq!(none.unwrap(), r#"{"ok":null}"#);
+    }
+}
@@ -1,38 +1,89 @@
+#[cfg(feature = "mock")]
+use crate::mock;
 use crate::{
+    draft::{
+        BountyDraft,
+        Draft,
+        DraftAttachment,
+        DraftKind,
+        Drafts,
+        SubmissionDraft,
+    },
     dto::{
+        AttachmentInformation,
+        Balance,
         BountyInformation,
         BountySubmissionInformation,
+        ChainConstantsInformation,
+        ChainPropertiesInformation,
+        CompatibilityInformation,
         ContributionInformation,
+        DraftInformation,
+        DryRunInformation,
+        FeatureSupportInformation,
+        LinkInformation,
+        MemberInformation,
+        PaymentRequestInformation,
+        PayoutSplitInformation,
+        RejectionInformation,
+        RuntimeVersionInformation,
+        Signal,
+        VoteProgressInformation,
+        WatchOnlyInformation,
+        SCHEMA_VERSION,
     },
-    ffi_utils::log::{
-        error,
-        info,
-        warn,
+    error::{
+        reply,
+        reply_json,
+        ErrorCode,
+        FfiError,
+        ResultExt,
+    },
+    link::{
+        Link,
+        LinkTarget,
+    },
+    logging::{
+        parse_level,
+        recent as recent_logs,
+        set_level as set_log_level,
+        set_sink as set_log_sink,
+    },
+    qr::{
+        decode_hex,
+        PaymentRequest,
+        SignedRequest,
+        SigningRequest,
     },
 };
-use anyhow::{
-    anyhow,
-    bail,
-};
+use allo_isolate::Isolate;
 use libipld::{
     cache::Cache,
     cbor::DagCborCodec,
+    cid::Cid,
+};
+use parity_scale_codec::{
+    Decode,
+    Encode,
 };
 use std::{
+    convert::TryFrom,
     fmt::{
         Debug,
         Display,
     },
     marker::PhantomData,
+    path::Path,
 };
 use substrate_subxt::{
     balances::{
         AccountData,
         Balances,
-        TransferCallExt,
+        TransferCall,
         TransferEventExt,
     },
     sp_core::crypto::Ss58Codec,
+    sp_runtime::traits::UniqueSaturatedInto,
     system::{
         AccountStoreExt,
         System,
@@ -42,13 +93,68 @@ use substrate_subxt::{
     SignedExtra,
 };
 use sunshine_bounty_client::{
+    backup::{
+        BackupClient,
+        Progress,
+    },
     bounty::{
+        ApproveBountySubmissionCall,
+        AssignReviewerCall,
+        BountiesStore,
         Bounty as BountyTrait,
         BountyClient,
         BountyState,
+        ClosedBountiesStore,
+        ContributeToBountyCall,
+        DisputeResolution,
+        EvidenceClient,
+        PostBountyCall,
         SubState,
+        SubmitForBountyCall,
+    },
+    chain::{
+        BlockClock,
+        BlockClockExt,
+        ChainConstantsExt,
+        ChainPropertiesExt,
+        PropertiesOverrides,
+    },
+    compat::CompatibilityExt,
+    metrics::metrics_snapshot,
+    nonce::{
+        NonceExt,
+        SettledExt,
+    },
+    org::{
+        Org as OrgTrait,
+        OrgClient,
+    },
+    recovery::{
+        Recovery as RecoveryTrait,
+        RecoveryClient,
+    },
+    submit::{
+        DryRunOutcome,
+        SubmitExt,
+    },
+    utils::{
+        bounty::{
+            bounty_tag,
+            tag_name,
+            SubmissionKind,
+        },
+        vote::VoteProgress,
+    },
+    vote::{
+        Vote as VoteTrait,
+        VoteClient,
+        VoteStatesStore,
+        VoteTextClient,
     },
+    watch::WatchList,
+    Attachment,
     GithubIssue,
+    GithubSubmission,
 };
 use sunshine_client_utils::{
     crypto::{
@@ -60,6 +166,7 @@ use sunshine_client_utils::{
         },
         ss58::Ss58,
     },
+    Client,
     Keystore,
     Node,
     OffchainConfig,
@@ -92,6 +199,211 @@ where
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct Vote<'a, C, N>
+where
+    C: VoteClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: VoteTrait,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Vote<'a, C, N>
+where
+    C: VoteClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: VoteTrait,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Org<'a, C, N>
+where
+    C: OrgClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: OrgTrait,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+fn draft_information(draft: Draft) -> DraftInformation {
+    DraftInformation {
+        schema_version: SCHEMA_VERSION,
+        id: draft.id,
+        kind: draft.kind.to_string(),
+        payload: draft.payload,
+        created_at: draft.created_at,
+    }
+}
+
+/// The dto of the outcome of a dry run
+fn dry_run_information(outcome: DryRunOutcome) -> DryRunInformation {
+    let (kind, module, error, reason) = match &outcome {
+        DryRunOutcome::Ok => ("Ok", None, None, None),
+        DryRunOutcome::Module { module, error } => {
+            ("Module", Some(module.clone()), Some(error.clone()), None)
+        }
+        DryRunOutcome::Dispatch(reason) => {
+            ("Dispatch", None, None, Some(reason.clone()))
+        }
+        DryRunOutcome::Future => ("Future", None, None, None),
+        DryRunOutcome::Invalid(reason) => {
+            ("Invalid", None, None, Some(reason.clone()))
+        }
+    };
+    DryRunInformation {
+        schema_version: SCHEMA_VERSION,
+        would_succeed: !outcome.is_failure(),
+        outcome: kind.to_string(),
+        module,
+        error,
+        reason,
+    }
+}
+
+impl<'a, C, N> Org<'a, C, N>
+where
+    C: OrgClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: OrgTrait,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Recovery<'a, C, N>
+where
+    C: RecoveryClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: RecoveryTrait,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Recovery<'a, C, N>
+where
+    C: RecoveryClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: RecoveryTrait,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Backup<'a, C, N>
+where
+    C: BackupClient<N> + Send + Sync,
+    N: Node,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Backup<'a, C, N>
+where
+    C: BackupClient<N> + Send + Sync,
+    N: Node,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Metrics<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    // the snapshot is process wide and needs no client
+    _client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Metrics<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            _client: client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Log<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    // the log settings are process wide and need no client
+    _client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Log<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            _client: client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Mock<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    // the simulated chain is process wide and needs no client
+    _client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Mock<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            _client: client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Key<'a, C, N>
 where
@@ -103,6 +415,31 @@ where
     _runtime: PhantomData<N>,
 }
 
+#[derive(Clone, Debug)]
+pub struct Links<'a, C, N>
+where
+    C: BountyClient<N> + VoteClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: BountyTrait + VoteTrait,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Links<'a, C, N>
+where
+    C: BountyClient<N> + VoteClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: BountyTrait + VoteTrait,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
 impl<'a, C, N> Key<'a, C, N>
 where
     C: BountyClient<N> + Send + Sync,
@@ -142,20 +479,55 @@ where
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct Chain<'a, C, N>
+where
+    C: Client<N> + Send + Sync,
+    N: Node,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Chain<'a, C, N>
+where
+    C: Client<N> + Send + Sync,
+    N: Node,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
 impl<'a, C, N> Key<'a, C, N>
 where
     C: BountyClient<N> + Send + Sync,
     N: Node,
     N::Runtime: BountyTrait,
+    <N::Runtime as System>::AccountId: Ss58Codec,
 {
     pub async fn exists(&self) -> Result<bool> {
-        self.client.read().await.keystore().is_initialized().await
+        reply(async move {
+            let client = self.client.read().await;
+            client
+                .keystore()
+                .is_initialized()
+                .await
+                .code(ErrorCode::KeystoreError)
+        })
+        .await
     }
 
     pub async fn uid(&self) -> Result<String> {
-        let client = self.client.read().await;
-        let signer = client.signer()?;
-        Ok(signer.account_id().to_string())
+        reply(async move {
+            let client = self.client.read().await;
+            let signer = client.signer().code(ErrorCode::KeystoreLocked)?;
+            Ok(client.chain_properties().ss58(signer.account_id()))
+        })
+        .await
     }
 
     pub async fn set(
@@ -164,44 +536,162 @@ where
         suri: Option<&str>,
         paperkey: Option<&str>,
     ) -> Result<String> {
-        let password = SecretString::new(password.to_string());
-        if password.expose_secret().len() < 8 {
-            bail!("Password Too Short");
-        }
-        let dk = if let Some(paperkey) = paperkey {
-            let mnemonic = Mnemonic::parse(paperkey)?;
-            TypedPair::<C::KeyType>::from_mnemonic(&mnemonic)?
-        } else if let Some(suri) = suri {
-            TypedPair::<C::KeyType>::from_suri(suri)?
-        } else {
-            TypedPair::<C::KeyType>::generate().await
-        };
+        reply(async move {
+            let password = SecretString::new(password.to_string());
+            if password.expose_secret().len() < 8 {
+                return Err(FfiError::new(
+                    ErrorCode::PasswordTooShort,
+                    "Password Too Short",
+                )
+                .into())
+            }
+            let dk = if let Some(paperkey) = paperkey {
+                let mnemonic = Mnemonic::parse(paperkey)
+                    .code(ErrorCode::InvalidPaperkey)?;
+                TypedPair::<C::KeyType>::from_mnemonic(&mnemonic)
+                    .code(ErrorCode::InvalidPaperkey)?
+            } else if let Some(suri) = suri {
+                TypedPair::<C::KeyType>::from_suri(suri)
+                    .code(ErrorCode::InvalidSuri)?
+            } else {
+                TypedPair::<C::KeyType>::generate().await
+            };
 
-        self.client
-            .write()
-            .await
-            .set_key(dk, &password, false)
-            .await?;
-        let account_id =
-            self.client.read().await.signer()?.account_id().to_string();
-        Ok(account_id)
+            self.client
+                .write()
+                .await
+                .set_key(dk, &password, false)
+                .await
+                .code(ErrorCode::KeystoreError)?;
+            let client = self.client.read().await;
+            let signer = client.signer().code(ErrorCode::KeystoreLocked)?;
+            Ok(client.chain_properties().ss58(signer.account_id()))
+        })
+        .await
     }
 
     pub async fn lock(&self) -> Result<bool> {
-        self.client.write().await.lock().await?;
-        Ok(true)
+        reply(async move {
+            self.client
+                .write()
+                .await
+                .lock()
+                .await
+                .code(ErrorCode::KeystoreError)?;
+            Ok(true)
+        })
+        .await
     }
 
     pub async fn unlock(&self, password: impl Into<&str>) -> Result<bool> {
         let password = SecretString::new(password.into().to_string());
-        self.client.write().await.unlock(&password).await?;
-        Ok(true)
+        reply(async move {
+            self.client
+                .write()
+                .await
+                .unlock(&password)
+                .await
+                .code(ErrorCode::KeystoreUnlockFailed)?;
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn signing_request(&self, call_hex: &str) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            let signer = client.signer().code(ErrorCode::KeystoreLocked)?;
+            let request = SigningRequest {
+                genesis: genesis::<N, C>(&*client),
+                account: client.chain_properties().ss58(signer.account_id()),
+                call: decode_hex(call_hex).code(ErrorCode::InvalidHex)?,
+            };
+            Ok(request.encode())
+        })
+        .await
+    }
+
+    pub async fn provide_signature(
+        &self,
+        payload: &str,
+        signature_hex: &str,
+    ) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            let request =
+                SigningRequest::decode(payload, &genesis::<N, C>(&*client))?;
+            let signature =
+                decode_hex(signature_hex).code(ErrorCode::InvalidHex)?;
+            if signature.is_empty() {
+                return Err(FfiError::new(
+                    ErrorCode::InvalidHex,
+                    "Signature is empty",
+                )
+                .into())
+            }
+            info!("Signature provided for {}", request.account);
+            Ok(SignedRequest { request, signature }.encode())
+        })
+        .await
+    }
+
+    /// Follows `account` as `label` without its key, returns the address in
+    /// the format of the chain
+    pub async fn add_watch_only(
+        &self,
+        root: &str,
+        account: &str,
+        label: &str,
+    ) -> Result<String> {
+        reply(async move {
+            let account = account
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            let account =
+                self.client.read().await.chain_properties().ss58(&account.0);
+            let mut list = WatchList::load(Path::new(root))?;
+            list.add(label, &account)?;
+            list.save()?;
+            Ok(account)
+        })
+        .await
+    }
+
+    pub async fn watch_only(&self, root: &str) -> Result<String> {
+        reply_json(async move {
+            let list = WatchList::load(Path::new(root))?;
+            Ok(list
+                .iter()
+                .map(|(label, account)| {
+                    WatchOnlyInformation {
+                        schema_version: SCHEMA_VERSION,
+                        label: label.clone(),
+                        account: account.clone(),
+                    }
+                })
+                .collect::<Vec<_>>())
+        })
+        .await
+    }
+
+    pub async fn remove_watch_only(
+        &self,
+        root: &str,
+        label: &str,
+    ) -> Result<bool> {
+        reply(async move {
+            let mut list = WatchList::load(Path::new(root))?;
+            list.remove(label)?;
+            list.save()?;
+            Ok(true)
+        })
+        .await
     }
 }
 
 impl<'a, C, N> Bounty<'a, C, N>
 where
-    C: BountyClient<N> + Send + Sync,
+    C: BountyClient<N> + OrgClient<N> + EvidenceClient<N> + Send + Sync,
     N: Node,
     N::Runtime: BountyTrait<IpfsReference = sunshine_codec::Cid> + Debug,
     C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
@@ -210,23 +700,36 @@ where
     <N::Runtime as BountyTrait>::BountyId: From<u64> + Into<u64> + Display,
     <N::Runtime as BountyTrait>::SubmissionId: From<u64> + Into<u64> + Display,
     <N::Runtime as BountyTrait>::BountyPost: From<GithubIssue> + Debug,
-    <N::Runtime as BountyTrait>::BountySubmission: From<GithubIssue> + Debug,
+    <N::Runtime as BountyTrait>::BountySubmission:
+        From<GithubIssue> + From<GithubSubmission> + Debug,
+    <N::Runtime as BountyTrait>::AssetId: From<u32> + Display,
+    <N::Runtime as OrgTrait>::OrgId: From<u64> + Display,
+    <N::Runtime as OrgTrait>::Cid: From<Cid> + Into<Cid>,
+    <N::Runtime as OrgTrait>::SpendThresholdId: From<u64> + Display,
+    <N::Runtime as OrgTrait>::SpendVoteId: Display,
     <N::Runtime as Balances>::Balance: Into<u128> + From<u64>,
 {
     pub async fn get(&self, bounty_id: &str) -> Result<String> {
-        info!("Getting Bounty with id: {}", bounty_id);
-        let bounty_state = self
-            .client
-            .read()
-            .await
-            .bounty(bounty_id.parse::<u64>()?.into())
-            .await?;
-        info!("Got bounty State for BountyId: {}", bounty_id);
-        let info = self
-            .get_bounty_info(bounty_id.parse::<u64>()?.into(), bounty_state)
-            .await?;
-        info!("Bounty Info: {:?}", info);
-        Ok(serde_json::to_string(&info)?)
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply_json(async move { mock::bounty(bounty_id) }).await
+        }
+        reply_json(async move {
+            info!("Getting Bounty with id: {}", bounty_id);
+            let bounty_state = self
+                .client
+                .read()
+                .await
+                .bounty(bounty_id.parse::<u64>()?.into())
+                .await?;
+            info!("Got bounty State for BountyId: {}", bounty_id);
+            let info = self
+                .get_bounty_info(bounty_id.parse::<u64>()?.into(), bounty_state)
+                .await?;
+            info!("Bounty Info: {:?}", info);
+            Ok(info)
+        })
+        .await
     }
 
     pub async fn post(
@@ -235,22 +738,92 @@ where
         repo_name: &str,
         issue_number: u64,
         amount: &str,
+        asset_id: Option<&str>,
     ) -> Result<u64> {
-        let bounty = GithubIssue {
-            repo_owner: repo_owner.to_string(),
-            repo_name: repo_name.to_string(),
-            issue_number,
-        }
-        .into();
-        info!("Posting Bounty: {:?}", bounty);
-        let event = self
-            .client
-            .read()
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply(async move {
+                mock::post(
+                    repo_owner,
+                    repo_name,
+                    issue_number,
+                    amount,
+                    asset_id,
+                )
+            })
             .await
-            .post_bounty(bounty, amount.parse::<u64>()?.into())
-            .await?;
-        info!("Bounty Created: {:?}", event);
-        Ok(event.id.into())
+        }
+        reply(async move {
+            let asset_id = match asset_id {
+                Some(id) => Some(id.parse::<u32>()?.into()),
+                None => None,
+            };
+            let bounty = GithubIssue {
+                repo_owner: repo_owner.to_string(),
+                repo_name: repo_name.to_string(),
+                issue_number,
+            }
+            .into();
+            info!("Posting Bounty: {:?}", bounty);
+            let event = self
+                .client
+                .read()
+                .await
+                .post_bounty(
+                    bounty,
+                    amount.parse::<u64>()?.into(),
+                    asset_id,
+                    SubmissionKind::Any,
+                    vec![],
+                    vec![],
+                )
+                .await?;
+            info!("Bounty Created: {:?}", event);
+            Ok(event.id.into())
+        })
+        .await
+    }
+
+    /// Dry runs `post` without submitting it
+    pub async fn post_preflight(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        issue_number: u64,
+        amount: &str,
+        asset_id: Option<&str>,
+    ) -> Result<String> {
+        reply_json(async move {
+            let asset_id = match asset_id {
+                Some(id) => Some(id.parse::<u32>()?.into()),
+                None => None,
+            };
+            let bounty = GithubIssue {
+                repo_owner: repo_owner.to_string(),
+                repo_name: repo_name.to_string(),
+                issue_number,
+            };
+            let issue = Encode::encode(
+                &<N::Runtime as BountyTrait>::BountyPost::from(bounty.clone()),
+            );
+            let client = self.client.read().await;
+            // the issue is content addressed, posting it later inserts the
+            // same cid again
+            let info = client.offchain_client().insert(bounty).await?;
+            let outcome = client
+                .dry_run(PostBountyCall::<N::Runtime> {
+                    issue,
+                    info: info.into(),
+                    amount: amount.parse::<u64>()?.into(),
+                    asset_id,
+                    submission_kind: SubmissionKind::Any,
+                    admins: vec![],
+                    tags: vec![],
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
     }
 
     pub async fn contribute(
@@ -258,18 +831,50 @@ where
         bounty_id: &str,
         amount: &str,
     ) -> Result<u128> {
-        info!("Contribute to BountyId: {}", bounty_id);
-        let event = self
-            .client
-            .read()
-            .await
-            .contribute_to_bounty(
-                bounty_id.parse::<u64>()?.into(),
-                amount.parse::<u64>()?.into(),
-            )
-            .await?;
-        info!("Contibution Added: {:?}", event);
-        Ok(event.total.into())
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply(async move { mock::contribute(bounty_id, amount) })
+                .await
+        }
+        reply(async move {
+            info!("Contribute to BountyId: {}", bounty_id);
+            let client = self.client.read().await;
+            let bounty_id = bounty_id.parse::<u64>()?.into();
+            // contributions are made in the asset the bounty was posted in
+            let asset_id = client.bounty(bounty_id).await?.asset();
+            let event = client
+                .contribute_to_bounty(
+                    bounty_id,
+                    amount.parse::<u64>()?.into(),
+                    asset_id,
+                )
+                .await?;
+            info!("Contibution Added: {:?}", event);
+            Ok(event.total.into())
+        })
+        .await
+    }
+
+    /// Dry runs `contribute` without submitting it
+    pub async fn contribute_preflight(
+        &self,
+        bounty_id: &str,
+        amount: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            let client = self.client.read().await;
+            let bounty_id = bounty_id.parse::<u64>()?.into();
+            let asset_id = client.bounty(bounty_id).await?.asset();
+            let outcome = client
+                .dry_run(ContributeToBountyCall::<N::Runtime> {
+                    bounty_id,
+                    amount: amount.parse::<u64>()?.into(),
+                    asset_id,
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
     }
 
     pub async fn submit(
@@ -280,56 +885,167 @@ where
         issue_number: u64,
         amount: &str,
     ) -> Result<u64> {
-        let bounty = GithubIssue {
-            repo_owner: repo_owner.to_string(),
-            repo_name: repo_name.to_string(),
-            issue_number,
-        }
-        .into();
-        info!("Submit for BountyId: {} with {:?}", bounty_id, bounty);
-        let event = self
+        reply(async move {
+            let submission = GithubIssue {
+                repo_owner: repo_owner.to_string(),
+                repo_name: repo_name.to_string(),
+                issue_number,
+            };
+            self.submit_body(bounty_id, submission.into(), amount).await
+        })
+        .await
+    }
+
+    async fn submit_body(
+        &self,
+        bounty_id: &str,
+        submission: GithubSubmission,
+        amount: &str,
+    ) -> Result<u64> {
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            // the simulated chain keeps no offchain bodies
+            return mock::submit(
+                bounty_id,
+                &submission.repo_owner,
+                &submission.repo_name,
+                submission.issue_number,
+                amount,
+            )
+        }
+        let submission: <N::Runtime as BountyTrait>::BountySubmission =
+            submission.into();
+        info!("Submit for BountyId: {} with {:?}", bounty_id, submission);
+        let event = self
             .client
             .read()
             .await
             .submit_for_bounty(
                 bounty_id.parse::<u64>()?.into(),
-                bounty,
+                submission,
                 amount.parse::<u64>()?.into(),
+                vec![],
             )
             .await?;
         info!("Submission Added: {:?}", event);
         Ok(event.id.into())
     }
 
+    /// Dry runs `submit` without submitting it
+    pub async fn submit_preflight(
+        &self,
+        bounty_id: &str,
+        repo_owner: &str,
+        repo_name: &str,
+        issue_number: u64,
+        amount: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            let submission = GithubIssue {
+                repo_owner: repo_owner.to_string(),
+                repo_name: repo_name.to_string(),
+                issue_number,
+            };
+            let issue = Encode::encode(
+                &<N::Runtime as BountyTrait>::BountySubmission::from(
+                    submission.clone(),
+                ),
+            );
+            let client = self.client.read().await;
+            let submission_ref =
+                client.offchain_client().insert(submission).await?;
+            let outcome = client
+                .dry_run(SubmitForBountyCall::<N::Runtime> {
+                    bounty_id: bounty_id.parse::<u64>()?.into(),
+                    issue,
+                    submission_ref: submission_ref.into(),
+                    amount: amount.parse::<u64>()?.into(),
+                    split: vec![],
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
+    }
+
     pub async fn approve(&self, submission_id: &str) -> Result<u128> {
-        info!("Approving SubmissionId: {}", submission_id);
-        let event = self
-            .client
-            .read()
-            .await
-            .approve_bounty_submission(submission_id.parse::<u64>()?.into())
-            .await?;
-        info!("Approved SubmissionId: {} with {:?}", submission_id, event);
-        Ok(event.new_total.into())
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply(async move { mock::approve(submission_id) }).await
+        }
+        reply(async move {
+            info!("Approving SubmissionId: {}", submission_id);
+            let event = self
+                .client
+                .read()
+                .await
+                .approve_bounty_submission(submission_id.parse::<u64>()?.into())
+                .await?;
+            info!("Approved SubmissionId: {} with {:?}", submission_id, event);
+            Ok(event.new_total.into())
+        })
+        .await
+    }
+
+    /// Dry runs `approve` without submitting it
+    pub async fn approve_preflight(
+        &self,
+        submission_id: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            let outcome = self
+                .client
+                .read()
+                .await
+                .dry_run(ApproveBountySubmissionCall::<N::Runtime> {
+                    submission_id: submission_id.parse::<u64>()?.into(),
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
+    }
+
+    pub async fn submission_deposit(&self) -> Result<u128> {
+        reply(async move {
+            let client = self.client.read().await;
+            Ok(client.submission_deposit().await?.into())
+        })
+        .await
     }
 
     pub async fn get_submission(&self, submission_id: &str) -> Result<String> {
-        info!("Getting SubmissionId: {}", submission_id);
-        let submission_state = self
-            .client
-            .read()
-            .await
-            .submission(submission_id.parse::<u64>()?.into())
-            .await?;
-        info!("Got Submission State: {:?}", submission_state);
-        let info = self
-            .get_submission_info(
-                submission_id.parse::<u64>()?.into(),
-                submission_state,
-            )
-            .await?;
-        info!("Submission: {:?}", info);
-        Ok(serde_json::to_string(&info)?)
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply_json(async move { mock::submission(submission_id) })
+                .await
+        }
+        reply_json(async move {
+            info!("Getting SubmissionId: {}", submission_id);
+            let submission_state = self
+                .client
+                .read()
+                .await
+                .submission(submission_id.parse::<u64>()?.into())
+                .await?;
+            info!("Got Submission State: {:?}", submission_state);
+            let reviewer = self
+                .client
+                .read()
+                .await
+                .submission_reviewer(submission_id.parse::<u64>()?.into())
+                .await?;
+            let info = self
+                .get_submission_info(
+                    submission_id.parse::<u64>()?.into(),
+                    submission_state,
+                    reviewer,
+                )
+                .await?;
+            info!("Submission: {:?}", info);
+            Ok(info)
+        })
+        .await
     }
 
     pub async fn get_contribution(
@@ -337,167 +1053,617 @@ where
         acc: &str,
         bounty_id: &str,
     ) -> Result<String> {
-        let account = acc.parse::<Ss58<N::Runtime>>()?;
-        info!(
-            "Getting the contribution for Account {} in Bounty {}",
-            account.0, bounty_id
-        );
-        let c = self
-            .client
-            .read()
-            .await
-            .contribution(bounty_id.parse::<u64>()?.into(), account.0)
-            .await?;
-        let info = ContributionInformation {
-            id: c.id().to_string(),
-            account: c.account().to_string(),
-            total: c.total().into(),
-        };
-        info!("Contribution: {:?}", info);
-        Ok(serde_json::to_string(&info)?)
+        reply_json(async move {
+            let account = acc
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            info!(
+                "Getting the contribution for Account {} in Bounty {}",
+                account.0, bounty_id
+            );
+            let client = self.client.read().await;
+            let c = client
+                .contribution(bounty_id.parse::<u64>()?.into(), account.0)
+                .await?;
+            let info = ContributionInformation {
+                schema_version: SCHEMA_VERSION,
+                id: c.id().to_string(),
+                account: client.chain_properties().ss58(&c.account()),
+                total: Balance(c.total().into()),
+            };
+            info!("Contribution: {:?}", info);
+            Ok(info)
+        })
+        .await
     }
 
-    pub async fn open_bounties(&self, min: &str) -> Result<String> {
-        info!("Getting Open Bounties with min: {}", min);
-        let open_bounties = self
-            .client
-            .read()
-            .await
-            .open_bounties(min.parse::<u64>()?.into())
-            .await?;
-        info!("is there any Open Bounties? {}", open_bounties.is_some());
-        match open_bounties {
-            Some(list) => {
-                let mut v = Vec::with_capacity(list.len());
-                for (id, state) in list {
-                    info!("Listing Bounty #{} with State: {:?}", id, state);
-                    match self.get_bounty_info(id, state).await {
-                        Ok(info) => {
-                            info!("Adding it to the list: {:?}", info);
-                            v.push(info);
+    pub async fn open_bounties(
+        &self,
+        min: &str,
+        tag: Option<&str>,
+    ) -> Result<String> {
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply_json(async move { mock::open_bounties(min, tag) })
+                .await
+        }
+        reply_json(async move {
+            info!("Getting Open Bounties with min: {} and tag: {:?}", min, tag);
+            let tagged = match tag {
+                Some(tag) => {
+                    let tag = bounty_tag(tag).ok_or_else(|| {
+                        FfiError::new(
+                            ErrorCode::InvalidBountyTag,
+                            format!("Invalid bounty tag {}", tag),
+                        )
+                    })?;
+                    Some(self.client.read().await.bounties_by_tag(tag).await?)
+                }
+                None => None,
+            };
+            let open_bounties = self
+                .client
+                .read()
+                .await
+                .open_bounties(min.parse::<u64>()?.into())
+                .await?;
+            info!("is there any Open Bounties? {}", open_bounties.is_some());
+            match open_bounties {
+                Some(list) => {
+                    let mut v = Vec::with_capacity(list.len());
+                    for (id, state) in list {
+                        if let Some(tagged) = &tagged {
+                            if !tagged.contains(&id) {
+                                continue
+                            }
                         }
-                        Err(e) => {
-                            warn!("I can't get the info of Bounty #{}. Skipping...", id);
-                            error!("{:?}", e);
+                        info!("Listing Bounty #{} with State: {:?}", id, state);
+                        match self.get_bounty_info(id, state).await {
+                            Ok(info) => {
+                                info!("Adding it to the list: {:?}", info);
+                                v.push(info);
+                            }
+                            Err(e) => {
+                                warn!("I can't get the info of Bounty #{}. Skipping...", id);
+                                error!("{:?}", e);
+                            }
                         }
                     }
+                    Ok(Some(v))
+                }
+                None => {
+                    info!("Empty, No Open Bounties");
+                    Ok(None)
                 }
-                Ok(serde_json::to_string(&v)?)
             }
-            None => {
-                info!("Empty, No Open Bounties");
-                Ok(String::new())
+        })
+        .await
+    }
+
+    pub async fn closed_bounties(&self) -> Result<String> {
+        reply_json(async move {
+            info!("Getting Closed Bounties");
+            let closed_bounties =
+                self.client.read().await.closed_bounties().await?;
+            let mut v = Vec::new();
+            for (id, state) in closed_bounties.unwrap_or_default() {
+                match self.get_bounty_info(id, state).await {
+                    Ok(info) => v.push(info),
+                    Err(e) => {
+                        warn!(
+                            "I can't get the info of Bounty #{}. Skipping...",
+                            id
+                        );
+                        error!("{:?}", e);
+                    }
+                }
             }
-        }
+            Ok(v)
+        })
+        .await
+    }
+
+    /// The open bounties on the board of the org, funded by its treasury
+    pub async fn board(&self, org_id: &str) -> Result<String> {
+        reply_json(async move {
+            info!("Getting the Bounty Board of Org {}", org_id);
+            let ids = self
+                .client
+                .read()
+                .await
+                .org_bounties(org_id.parse::<u64>()?.into())
+                .await?;
+            let mut v = Vec::with_capacity(ids.len());
+            for id in ids {
+                let state = self.client.read().await.bounty(id).await?;
+                match self.get_bounty_info(id, state).await {
+                    Ok(info) => v.push(info),
+                    Err(e) => {
+                        warn!(
+                            "I can't get the info of Bounty #{}. Skipping...",
+                            id
+                        );
+                        error!("{:?}", e);
+                    }
+                }
+            }
+            Ok(v)
+        })
+        .await
     }
 
     pub async fn open_bounty_submissions(
         &self,
         bounty_id: &str,
     ) -> Result<String> {
-        info!("Getting Open Submissions for BountyId: {}", bounty_id);
-        let open_submissions = self
-            .client
-            .read()
-            .await
-            .open_submissions(bounty_id.parse::<u64>()?.into())
-            .await?;
-        info!(
-            "is there any Open Submissions? {}",
-            open_submissions.is_some()
-        );
-        match open_submissions {
-            Some(list) => {
-                let mut v = Vec::with_capacity(list.len());
-                for (id, state) in list {
-                    info!("Listing Submission #{} with State: {:?}", id, state);
-                    match self.get_submission_info(id, state).await {
-                        Ok(info) => {
-                            info!("Adding it to the list: {:?}", info);
-                            v.push(info);
+        reply_json(async move {
+            info!("Getting Open Submissions for BountyId: {}", bounty_id);
+            let open_submissions = self
+                .client
+                .read()
+                .await
+                .open_submissions(bounty_id.parse::<u64>()?.into())
+                .await?;
+            info!(
+                "is there any Open Submissions? {}",
+                open_submissions.is_some()
+            );
+            match open_submissions {
+                Some(list) => {
+                    let mut v = Vec::with_capacity(list.len());
+                    for (id, state, reviewer, _) in list {
+                        info!("Listing Submission #{} with State: {:?}", id, state);
+                        match self.get_submission_info(id, state, reviewer).await {
+                            Ok(info) => {
+                                info!("Adding it to the list: {:?}", info);
+                                v.push(info);
+                            }
+                            Err(e) => {
+                                warn!("I can't get the info of Submission #{}. Skipping..", id);
+                                error!("{:?}", e);
+                            }
                         }
-                        Err(e) => {
-                            warn!("I can't get the info of Submission #{}. Skipping..", id);
-                            error!("{:?}", e);
+                    }
+                    Ok(Some(v))
+                }
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    pub async fn unassigned_submissions(
+        &self,
+        bounty_id: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            info!("Getting Unassigned Submissions for BountyId: {}", bounty_id);
+            let unassigned = self
+                .client
+                .read()
+                .await
+                .unassigned_submissions(bounty_id.parse::<u64>()?.into())
+                .await?;
+            match unassigned {
+                Some(list) => {
+                    let mut v = Vec::with_capacity(list.len());
+                    for (id, state) in list {
+                        match self.get_submission_info(id, state, None).await {
+                            Ok(info) => v.push(info),
+                            Err(e) => {
+                                warn!("I can't get the info of Submission #{}. Skipping..", id);
+                                error!("{:?}", e);
+                            }
                         }
                     }
+                    Ok(Some(v))
                 }
-                Ok(serde_json::to_string(&v)?)
+                None => Ok(None),
             }
-            None => Ok(String::new()),
-        }
+        })
+        .await
     }
 
-    pub async fn bounty_contributions(
+    pub async fn assign_reviewer(
+        &self,
+        submission_id: &str,
+        reviewer: &str,
+    ) -> Result<bool> {
+        reply(async move {
+            let reviewer = reviewer
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            info!(
+                "Assigning {} to review SubmissionId {}",
+                reviewer.0, submission_id
+            );
+            let event = self
+                .client
+                .read()
+                .await
+                .assign_reviewer(
+                    submission_id.parse::<u64>()?.into(),
+                    reviewer.0,
+                )
+                .await?;
+            info!("Reviewer Assigned: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Dry runs `assign_reviewer` without submitting it
+    pub async fn assign_reviewer_preflight(
+        &self,
+        submission_id: &str,
+        reviewer: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            let reviewer = reviewer
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            let outcome = self
+                .client
+                .read()
+                .await
+                .dry_run(AssignReviewerCall::<N::Runtime> {
+                    submission_id: submission_id.parse::<u64>()?.into(),
+                    reviewer: reviewer.0,
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
+    }
+
+    /// Has the org of `threshold_id` arbitrate disputes of the bounty's
+    /// rejections, or the default org if None
+    pub async fn set_arbitration(
         &self,
         bounty_id: &str,
+        threshold_id: Option<&str>,
+    ) -> Result<bool> {
+        reply(async move {
+            let threshold = match threshold_id {
+                Some(t) => Some(t.parse::<u64>()?.into()),
+                None => None,
+            };
+            let event = self
+                .client
+                .read()
+                .await
+                .set_bounty_arbitration(
+                    bounty_id.parse::<u64>()?.into(),
+                    threshold,
+                )
+                .await?;
+            info!("Bounty Arbitration Set: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Disputes the rejection of the submission with the Cid of the
+    /// submitter's argument, returns the id of the arbitration vote
+    pub async fn dispute_rejection(
+        &self,
+        submission_id: &str,
+        argument: &str,
     ) -> Result<String> {
-        info!("Getting Contributions to BountyId: {}", bounty_id);
-        let open_contributions = self
-            .client
-            .read()
-            .await
-            .bounty_contributions(bounty_id.parse::<u64>()?.into())
-            .await?;
-        info!(
-            "is there any Open Contributions? {}",
-            open_contributions.is_some()
-        );
-        match open_contributions {
-            Some(list) => {
-                let mut v: Vec<ContributionInformation> =
-                    Vec::with_capacity(list.len());
-                for c in list {
-                    info!("Listing Bounty #{} Contribution by Account {} of Amount {:?}", c.id(), c.account(), c.total());
-                    let info = ContributionInformation {
-                        id: c.id().to_string(),
-                        account: c.account().to_string(),
-                        total: c.total().into(),
+        reply(async move {
+            let argument =
+                Cid::try_from(argument).code(ErrorCode::InvalidCid)?;
+            info!("Disputing the rejection of SubmissionId {}", submission_id);
+            let event = self
+                .client
+                .read()
+                .await
+                .dispute_rejection(
+                    submission_id.parse::<u64>()?.into(),
+                    argument.into(),
+                )
+                .await?;
+            info!("Rejection Disputed: {:?}", event);
+            Ok(event.vote.to_string())
+        })
+        .await
+    }
+
+    /// Applies the decided arbitration vote, true if it overturned the
+    /// rejection and paid the submission
+    pub async fn resolve_dispute(&self, submission_id: &str) -> Result<bool> {
+        reply(async move {
+            let resolution = self
+                .client
+                .read()
+                .await
+                .resolve_dispute(submission_id.parse::<u64>()?.into())
+                .await?;
+            info!("Dispute Resolved: {:?}", resolution);
+            Ok(matches!(resolution, DisputeResolution::Overturned(_)))
+        })
+        .await
+    }
+
+    /// The rejection of the submission and its dispute, null unless its
+    /// bounty had an arbitrator and no dispute of it was resolved yet
+    pub async fn rejection(&self, submission_id: &str) -> Result<String> {
+        reply_json(async move {
+            let id = submission_id.parse::<u64>()?.into();
+            let client = self.client.read().await;
+            let (submission, _, rejected_at, threshold) =
+                match client.rejected_submission(id).await? {
+                    Some(rejection) => rejection,
+                    None => return Ok(None),
+                };
+            let dispute = client.dispute(id).await?;
+            let properties = client.chain_properties();
+            Ok(Some(RejectionInformation {
+                schema_version: SCHEMA_VERSION,
+                submission_id: id.to_string(),
+                bounty_id: submission.bounty_id().to_string(),
+                submitter: properties.ss58(&submission.submitter()),
+                amount: Balance(submission.amount().into()),
+                rejected_at: block_number::<N::Runtime>(rejected_at),
+                arbitration_threshold: threshold.to_string(),
+                disputed: dispute.is_some(),
+                argument: dispute
+                    .as_ref()
+                    .map(|d| Into::<Cid>::into(d.argument()).to_string()),
+                dispute_deposit: dispute
+                    .as_ref()
+                    .map(|d| Balance(d.deposit().into())),
+                vote_id: dispute.map(|d| d.vote().to_string()),
+            }))
+        })
+        .await
+    }
+
+    pub async fn archive(&self, bounty_id: &str) -> Result<String> {
+        reply(async move {
+            info!("Archiving BountyId: {}", bounty_id);
+            let cid = self
+                .client
+                .read()
+                .await
+                .archive_bounty(bounty_id.parse::<u64>()?.into())
+                .await?;
+            info!("Archived BountyId: {} as {}", bounty_id, cid);
+            Ok(cid.to_string())
+        })
+        .await
+    }
+
+    pub async fn verify_archive(&self, cid: &str) -> Result<bool> {
+        reply(async move {
+            let cid = Cid::try_from(cid).code(ErrorCode::InvalidCid)?;
+            info!("Verifying bounty archive {}", cid);
+            Ok(self.client.read().await.verify_archive(&cid).await?)
+        })
+        .await
+    }
+
+    pub async fn save_draft(
+        &self,
+        root: &str,
+        kind: &str,
+        draft: &str,
+    ) -> Result<u64> {
+        reply(async move {
+            let kind = kind.parse::<DraftKind>()?;
+            let id = Drafts::open(Path::new(root))?.save(kind, draft)?;
+            info!("Saved {} draft {}", kind, id);
+            Ok(id)
+        })
+        .await
+    }
+
+    pub async fn list_drafts(&self, root: &str) -> Result<String> {
+        reply_json(async move {
+            let drafts = Drafts::open(Path::new(root))?.list()?;
+            Ok(drafts
+                .into_iter()
+                .map(draft_information)
+                .collect::<Vec<_>>())
+        })
+        .await
+    }
+
+    pub async fn delete_draft(
+        &self,
+        root: &str,
+        draft_id: u64,
+    ) -> Result<bool> {
+        reply(async move {
+            info!("Deleting draft {}", draft_id);
+            Drafts::open(Path::new(root))?.delete(draft_id)
+        })
+        .await
+    }
+
+    pub async fn post_draft(&self, root: &str, draft_id: u64) -> Result<u64> {
+        reply(async move {
+            let drafts = Drafts::open(Path::new(root))?;
+            let draft = drafts.get(draft_id)?;
+            info!("Posting {} draft {}", draft.kind, draft_id);
+            // both calls return once the extrinsic was included
+            let id = match draft.kind {
+                DraftKind::Bounty => {
+                    let d: BountyDraft = draft.payload()?;
+                    self.post(
+                        &d.repo_owner,
+                        &d.repo_name,
+                        d.issue_number,
+                        &d.amount,
+                        d.asset_id.as_deref(),
+                    )
+                    .await?
+                }
+                DraftKind::Submission => {
+                    let d: SubmissionDraft = draft.payload()?;
+                    let attachments = d
+                        .attachments
+                        .iter()
+                        .map(|a| {
+                            Ok(Attachment {
+                                evidence: Cid::try_from(a.cid.as_str())
+                                    .code(ErrorCode::InvalidDraft)?,
+                                mime: a.mime.clone(),
+                                size: a.size,
+                            })
+                        })
+                        .collect::<Result<_>>()?;
+                    let submission = GithubSubmission {
+                        repo_owner: d.repo_owner,
+                        repo_name: d.repo_name,
+                        issue_number: d.issue_number,
+                        attachments,
                     };
-                    info!("Adding it to the list: {:?}", info);
-                    v.push(info);
+                    self.submit_body(&d.bounty_id, submission, &d.amount)
+                        .await?
                 }
-                Ok(serde_json::to_string(&v)?)
+            };
+            drafts.delete(draft_id)?;
+            Ok(id)
+        })
+        .await
+    }
+
+    /// Pins a file as evidence for the submission draft `draft_id`, listing
+    /// it in the draft, posting the draft attaches it
+    pub async fn attach_evidence(
+        &self,
+        root: &str,
+        draft_id: u64,
+        bytes: &str,
+        mime: &str,
+    ) -> Result<String> {
+        reply(async move {
+            let drafts = Drafts::open(Path::new(root))?;
+            let draft = drafts.get(draft_id)?;
+            if draft.kind != DraftKind::Submission {
+                return Err(FfiError::new(
+                    ErrorCode::InvalidDraft,
+                    format!("draft {} is not a submission", draft_id),
+                )
+                .into())
             }
-            None => Ok(String::new()),
-        }
+            let mut submission: SubmissionDraft = draft.payload()?;
+            let bytes =
+                base64::decode(bytes).code(ErrorCode::InvalidEvidence)?;
+            let attachment =
+                self.client.read().await.pin_evidence(&bytes, mime).await?;
+            let cid = attachment.evidence.to_string();
+            info!(
+                "Attached {} of {} bytes to draft {}",
+                cid, attachment.size, draft_id
+            );
+            submission.attachments.push(DraftAttachment {
+                cid: cid.clone(),
+                mime: attachment.mime,
+                size: attachment.size,
+            });
+            drafts.update(draft_id, &serde_json::to_string(&submission)?)?;
+            Ok(cid)
+        })
+        .await
+    }
+
+    /// The bytes of an evidence file as base64
+    pub async fn fetch_evidence(&self, cid: &str) -> Result<String> {
+        reply(async move {
+            let cid = Cid::try_from(cid).code(ErrorCode::InvalidCid)?;
+            let bytes = self.client.read().await.fetch_evidence(&cid).await?;
+            Ok(base64::encode(bytes))
+        })
+        .await
+    }
+
+    pub async fn bounty_contributions(
+        &self,
+        bounty_id: &str,
+    ) -> Result<String> {
+        reply_json(async move {
+            info!("Getting Contributions to BountyId: {}", bounty_id);
+            let open_contributions = self
+                .client
+                .read()
+                .await
+                .bounty_contributions(bounty_id.parse::<u64>()?.into())
+                .await?;
+            info!(
+                "is there any Open Contributions? {}",
+                open_contributions.is_some()
+            );
+            match open_contributions {
+                Some(list) => {
+                    let properties =
+                        self.client.read().await.chain_properties();
+                    let mut v: Vec<ContributionInformation> =
+                        Vec::with_capacity(list.len());
+                    for c in list {
+                        info!("Listing Bounty #{} Contribution by Account {} of Amount {:?}", c.id(), c.account(), c.total());
+                        let info = ContributionInformation {
+                            schema_version: SCHEMA_VERSION,
+                            id: c.id().to_string(),
+                            account: properties.ss58(&c.account()),
+                            total: Balance(c.total().into()),
+                        };
+                        info!("Adding it to the list: {:?}", info);
+                        v.push(info);
+                    }
+                    Ok(Some(v))
+                }
+                None => Ok(None),
+            }
+        })
+        .await
     }
 
     pub async fn account_contributions(
         &self,
         account_id: &str,
     ) -> Result<String> {
-        info!("Getting Contributions by {}", account_id);
-        let open_contributions = self
-            .client
-            .read()
-            .await
-            .account_contributions(account_id.parse::<Ss58<N::Runtime>>()?.0)
-            .await?;
-        info!(
-            "is there any Open Contributions? {}",
-            open_contributions.is_some()
-        );
-        match open_contributions {
-            Some(list) => {
-                let mut v = Vec::with_capacity(list.len());
-                for c in list {
-                    info!("Listing Bounty #{} Contribution by Account {} of Amount {:?}", c.id(), c.account(), c.total());
-                    let info = ContributionInformation {
-                        id: c.id().to_string(),
-                        account: c.account().to_string(),
-                        total: c.total().into(),
-                    };
-                    info!("Adding it to the list: {:?}", info);
-                    v.push(info);
+        reply_json(async move {
+            info!("Getting Contributions by {}", account_id);
+            let account = account_id
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            let open_contributions = self
+                .client
+                .read()
+                .await
+                .account_contributions(account.0)
+                .await?;
+            info!(
+                "is there any Open Contributions? {}",
+                open_contributions.is_some()
+            );
+            match open_contributions {
+                Some(list) => {
+                    let properties =
+                        self.client.read().await.chain_properties();
+                    let mut v = Vec::with_capacity(list.len());
+                    for c in list {
+                        info!("Listing Bounty #{} Contribution by Account {} of Amount {:?}", c.id(), c.account(), c.total());
+                        let info = ContributionInformation {
+                            schema_version: SCHEMA_VERSION,
+                            id: c.id().to_string(),
+                            account: properties.ss58(&c.account()),
+                            total: Balance(c.total().into()),
+                        };
+                        info!("Adding it to the list: {:?}", info);
+                        v.push(info);
+                    }
+                    Ok(Some(v))
                 }
-                Ok(serde_json::to_string(&v)?)
+                None => Ok(None),
             }
-            None => Ok(String::new()),
-        }
+        })
+        .await
     }
 
-    async fn get_bounty_info(
+    pub(crate) async fn get_bounty_info(
         &self,
         id: <N::Runtime as BountyTrait>::BountyId,
         state: BountyState<N::Runtime>,
@@ -510,48 +1676,674 @@ where
             .await
             .offchain_client()
             .get(&event_cid)
-            .await?;
-        info!("Bounty Body: {:?}", bounty_body);
+            .await
+            .code(ErrorCode::MetadataNotFound)?;
+        debug!("Bounty Body: {:?}", bounty_body);
+        let (symbol, decimals) = self.asset_metadata(state.asset()).await?;
+        let tags = self.client.read().await.bounty_tags(id).await?;
+        let org = self.client.read().await.bounty_org(id).await?;
+        let matching = self.client.read().await.bounty_matching(id).await?;
+        let properties = self.client.read().await.chain_properties();
+        let clock = self.client.read().await.block_clock().await?;
+        let timestamped = state.timestamped();
+        let posted_at = block_number::<N::Runtime>(state.posted_at());
+        let first_submission_at =
+            state.first_submission_at().map(block_number::<N::Runtime>);
+        let resolved_at = state.resolved_at().map(block_number::<N::Runtime>);
         let info = BountyInformation {
+            schema_version: SCHEMA_VERSION,
             id: id.to_string(),
             repo_owner: bounty_body.repo_owner,
             repo_name: bounty_body.repo_name,
             issue_number: bounty_body.issue_number,
-            depositer: state.depositer().to_string(),
-            total: state.total().into(),
+            depositer: properties.ss58(&state.depositer()),
+            total: Balance(state.total().into()),
+            admins: state
+                .admins()
+                .iter()
+                .map(|admin| properties.ss58(admin))
+                .collect(),
+            asset_id: state.asset().map(|id| id.to_string()),
+            symbol,
+            decimals,
+            tags: tags.iter().map(|tag| tag_name(tag).to_string()).collect(),
+            posted_at,
+            first_submission_at,
+            resolved_at,
+            posted_at_ms: block_millis(clock, timestamped, Some(posted_at)),
+            first_submission_at_ms: block_millis(
+                clock,
+                timestamped,
+                first_submission_at,
+            ),
+            resolved_at_ms: block_millis(clock, timestamped, resolved_at),
+            timestamped,
+            org: org.map(|org| org.to_string()),
+            matching_org: matching.as_ref().map(|m| m.org().to_string()),
+            matching_ratio_ppm: matching
+                .as_ref()
+                .map(|m| m.ratio().deconstruct().into()),
+            matching_remaining: matching.map(|m| Balance(m.remaining().into())),
         };
         Ok(info)
     }
 
-    async fn get_submission_info(
+    /// The symbol and decimals of `asset`, the native currency if None
+    async fn asset_metadata(
+        &self,
+        asset: Option<<N::Runtime as BountyTrait>::AssetId>,
+    ) -> Result<(String, u32)> {
+        let client = self.client.read().await;
+        let id = match asset {
+            Some(id) => id,
+            None => {
+                let properties = client.chain_properties();
+                return Ok((
+                    properties.token_symbol,
+                    properties.token_decimals.into(),
+                ))
+            }
+        };
+        // an asset no longer accepted for new bounties keeps its old ones
+        match client.bounty_asset(id).await? {
+            Some(metadata) => {
+                Ok((
+                    String::from_utf8_lossy(&metadata.symbol()).into_owned(),
+                    metadata.decimals().into(),
+                ))
+            }
+            None => Ok((id.to_string(), 0)),
+        }
+    }
+
+    pub(crate) async fn get_submission_info(
         &self,
         id: <N::Runtime as BountyTrait>::SubmissionId,
         state: SubState<N::Runtime>,
+        reviewer: Option<<N::Runtime as System>::AccountId>,
     ) -> Result<BountySubmissionInformation> {
         info!("Get submission info of id: {}", id);
         let event_cid = state.submission();
-        let submission_body: GithubIssue = self
+        let submission_body = self
             .client
             .read()
             .await
-            .offchain_client()
-            .get(&event_cid)
-            .await?;
-        info!("Submission Body: {:?}", submission_body);
+            .resolve_submission(&event_cid)
+            .await
+            .code(ErrorCode::MetadataNotFound)?;
+        debug!("Submission Body: {:?}", submission_body);
         let awaiting_review = state.state().awaiting_review();
+        let properties = self.client.read().await.chain_properties();
+        let clock = self.client.read().await.block_clock().await?;
+        let submitted_at = block_number::<N::Runtime>(state.submitted_at());
+        let submitter_orgs = self
+            .client
+            .read()
+            .await
+            .share_profiles(state.submitter())
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(org, _, _)| org.to_string())
+            .collect();
+        let handle_match = self
+            .handle_match(&submission_body.issue(), &state.submitter())
+            .await?;
+        let split = self
+            .client
+            .read()
+            .await
+            .submission_split(id)
+            .await?
+            .into_iter()
+            .map(|(account, share)| {
+                PayoutSplitInformation {
+                    schema_version: SCHEMA_VERSION,
+                    account: properties.ss58(&account),
+                    share_ppm: share.deconstruct(),
+                }
+            })
+            .collect();
+        let attachments = submission_body
+            .attachments
+            .iter()
+            .map(|attachment| {
+                AttachmentInformation {
+                    schema_version: SCHEMA_VERSION,
+                    cid: attachment.evidence.to_string(),
+                    mime: attachment.mime.clone(),
+                    size: attachment.size,
+                }
+            })
+            .collect();
+        let deadline = if awaiting_review {
+            Some(self.client.read().await.review_deadline(&state).await?)
+        } else {
+            None
+        };
         let info = BountySubmissionInformation {
+            schema_version: SCHEMA_VERSION,
             id: id.to_string(),
             repo_owner: submission_body.repo_owner,
             repo_name: submission_body.repo_name,
             issue_number: submission_body.issue_number,
             bounty_id: state.bounty_id().to_string(),
-            submitter: state.submitter().to_string(),
-            amount: state.amount().into(),
+            submitter: properties.ss58(&state.submitter()),
+            amount: Balance(state.amount().into()),
             awaiting_review,
             approved: !awaiting_review,
+            reviewer: reviewer.map(|r| properties.ss58(&r)),
+            submitted_at,
+            submitted_at_ms: block_millis(
+                clock,
+                state.timestamped(),
+                Some(submitted_at),
+            ),
+            timestamped: state.timestamped(),
+            submitter_orgs,
+            handle_match,
+            split,
+            attachments,
+            times_out_at: deadline
+                .as_ref()
+                .map(|d| block_number::<N::Runtime>(d.times_out_at)),
+            nearing_timeout: deadline.as_ref().map_or(false, |d| d.nearing),
+            timed_out: deadline.as_ref().map_or(false, |d| d.passed),
         };
         Ok(info)
     }
+
+    /// Whether the account mapped to the GitHub handle of the author of
+    /// `pr` is the submitter, false if the submitter claimed another handle
+    /// and None if neither is known
+    #[cfg(feature = "github")]
+    async fn handle_match(
+        &self,
+        pr: &GithubIssue,
+        submitter: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<bool>> {
+        use sunshine_bounty_client::utils::bounty::github_handle;
+        use sunshine_bounty_gbot::GBot;
+        // reviewers without GitHub access still see the submission
+        let author = match GBot::new() {
+            Ok(bot) => {
+                bot.issue_author(
+                    pr.repo_owner.clone(),
+                    pr.repo_name.clone(),
+                    pr.issue_number,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let handle = match author.map(|login| github_handle(&login)) {
+            Ok(Some(handle)) => handle,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                warn!("I can't get the author of {:?}: {}", pr, e);
+                return Ok(None)
+            }
+        };
+        let client = self.client.read().await;
+        if let Some((account, _)) = client.github_handle_account(handle).await?
+        {
+            return Ok(Some(&account == submitter))
+        }
+        let claimed = client.account_github_handle(submitter.clone()).await?;
+        Ok(claimed.map(|_| false))
+    }
+
+    #[cfg(not(feature = "github"))]
+    async fn handle_match(
+        &self,
+        _pr: &GithubIssue,
+        _submitter: &<N::Runtime as System>::AccountId,
+    ) -> Result<Option<bool>> {
+        Ok(None)
+    }
+}
+
+impl<'a, C, N> Vote<'a, C, N>
+where
+    C: VoteClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: VoteTrait,
+    <N::Runtime as VoteTrait>::VoteId: From<u64> + Display,
+    <N::Runtime as VoteTrait>::Signal: Into<u128>,
+{
+    pub async fn status(&self, vote_id: &str) -> Result<String> {
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply_json(async move { mock::vote_status(vote_id) }).await
+        }
+        reply_json(async move {
+            info!("Getting the progress of VoteId: {}", vote_id);
+            let id: <N::Runtime as VoteTrait>::VoteId =
+                vote_id.parse::<u64>()?.into();
+            let progress = self.client.read().await.vote_progress(id).await?;
+            let info = progress_information::<N::Runtime>(id, progress);
+            info!("Vote Progress: {:?}", info);
+            Ok(info)
+        })
+        .await
+    }
+
+    pub async fn receipt(&self, vote_id: &str) -> Result<String> {
+        reply(async move {
+            info!("Taking a receipt of the ballot in VoteId: {}", vote_id);
+            let receipt = self
+                .client
+                .read()
+                .await
+                .vote_receipt(vote_id.parse::<u64>()?.into())
+                .await?;
+            Ok(receipt.to_base64())
+        })
+        .await
+    }
+
+    pub async fn verify_receipt(
+        &self,
+        blob: &str,
+        state_root: Option<&str>,
+    ) -> Result<bool> {
+        reply(async move {
+            let state_root = match state_root {
+                Some(root) => {
+                    let root = decode_hex(root).code(ErrorCode::InvalidHex)?;
+                    Some(
+                        Decode::decode(&mut &root[..])
+                            .code(ErrorCode::InvalidHex)?,
+                    )
+                }
+                None => None,
+            };
+            let receipt = self
+                .client
+                .read()
+                .await
+                .verify_vote_receipt(blob, state_root)
+                .await?;
+            info!("Verified the receipt of VoteId: {}", receipt.voted.vote_id);
+            Ok(true)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Vote<'a, C, N>
+where
+    C: VoteClient<N> + VoteTextClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: VoteTrait,
+{
+    /// Pins a topic composed from `text` by the user, which works offline
+    pub async fn create_topic(&self, text: &str) -> Result<String> {
+        reply(async move {
+            let cid = self.client.read().await.create_topic(text).await?;
+            info!("Pinned vote topic {}", cid);
+            Ok(cid.to_string())
+        })
+        .await
+    }
+
+    /// Pins a justification composed from `text` by the user, which works
+    /// offline
+    pub async fn create_justification(&self, text: &str) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            let cid = client.create_justification(text).await?;
+            info!("Pinned vote justification {}", cid);
+            Ok(cid.to_string())
+        })
+        .await
+    }
+
+    pub async fn resolve_text(&self, cid: &str) -> Result<String> {
+        reply(async move {
+            let cid = Cid::try_from(cid).code(ErrorCode::InvalidCid)?;
+            Ok(self
+                .client
+                .read()
+                .await
+                .resolve_text(&cid)
+                .await
+                .code(ErrorCode::MetadataNotFound)?)
+        })
+        .await
+    }
+}
+
+fn block_number<R: System>(block: R::BlockNumber) -> u64 {
+    block.unique_saturated_into()
+}
+
+/// The approximate time of `block` in milliseconds since the unix epoch,
+/// none for values stored before their blocks were tracked
+fn block_millis(
+    clock: Option<BlockClock>,
+    timestamped: bool,
+    block: Option<u64>,
+) -> Option<u64> {
+    if !timestamped {
+        return None
+    }
+    Some(clock?.millis_at(block?))
+}
+
+/// The dto of the progress of the vote `id`
+pub(crate) fn progress_information<R>(
+    id: R::VoteId,
+    progress: VoteProgress<R::Signal>,
+) -> VoteProgressInformation
+where
+    R: VoteTrait,
+    R::VoteId: Display,
+    R::Signal: Into<u128>,
+{
+    let signal = |s: R::Signal| Signal(s.into());
+    VoteProgressInformation {
+        schema_version: SCHEMA_VERSION,
+        id: id.to_string(),
+        in_favor: signal(progress.in_favor()),
+        against: signal(progress.against()),
+        abstain: signal(progress.abstain()),
+        turnout: signal(progress.turnout()),
+        threshold_in_favor: signal(progress.threshold_in_favor()),
+        threshold_against: progress.threshold_against().map(signal),
+        remaining_to_pass: progress.remaining_to_pass().map(signal),
+        remaining_to_reject: progress.remaining_to_reject().map(signal),
+        outcome: format!("{:?}", progress.outcome()),
+    }
+}
+
+impl<'a, C, N> Org<'a, C, N>
+where
+    C: OrgClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: OrgTrait,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+    <N::Runtime as OrgTrait>::OrgId: From<u64> + Display,
+    <N::Runtime as OrgTrait>::Shares: Into<u64>,
+{
+    pub async fn members(&self, org_id: &str) -> Result<String> {
+        #[cfg(feature = "mock")]
+        if mock::enabled() {
+            return reply_json(async move { mock::org_members(org_id) }).await
+        }
+        reply_json(async move {
+            info!("Getting the members of OrgId: {}", org_id);
+            let org: <N::Runtime as OrgTrait>::OrgId =
+                org_id.parse::<u64>()?.into();
+            let client = self.client.read().await;
+            let members = client.org_members(org).await?.unwrap_or_default();
+            let properties = client.chain_properties();
+            let mut v = Vec::with_capacity(members.len());
+            for (account, profile) in members {
+                // the listing does not fail on members without a profile
+                let name =
+                    match client.member_profile(org, account.clone()).await {
+                        Ok(p) => p.map(|p| p.name),
+                        Err(e) => {
+                            warn!("I can't get the profile of {}", account);
+                            error!("{:?}", e);
+                            None
+                        }
+                    };
+                v.push(MemberInformation {
+                    schema_version: SCHEMA_VERSION,
+                    org_id: org.to_string(),
+                    account: properties.ss58(&account),
+                    shares: profile.total().into(),
+                    name,
+                });
+            }
+            Ok(v)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Recovery<'a, C, N>
+where
+    C: RecoveryClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: RecoveryTrait,
+    <N::Runtime as System>::AccountId: Ss58Codec,
+{
+    pub async fn add_friends(
+        &self,
+        friends: &str,
+        threshold: u64,
+    ) -> Result<bool> {
+        reply(async move {
+            let friends: Vec<String> = serde_json::from_str(friends)
+                .code(ErrorCode::InvalidAccountId)?;
+            let friends = friends
+                .iter()
+                .map(|f| Ok(f.parse::<Ss58<N::Runtime>>()?.0))
+                .collect::<Result<Vec<_>>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            let threshold =
+                u16::try_from(threshold).code(ErrorCode::InvalidNumber)?;
+            info!("Adding {} recovery friends", friends.len());
+            let event = self
+                .client
+                .read()
+                .await
+                .add_recovery_friends(friends, threshold)
+                .await?;
+            info!("Recovery Created: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn initiate(&self, lost: &str) -> Result<bool> {
+        reply(async move {
+            let lost = lost
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            info!("Initiating the recovery of {}", lost.0);
+            let event =
+                self.client.read().await.initiate_recovery(lost.0).await?;
+            info!("Recovery Initiated: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn vouch(&self, lost: &str, rescuer: &str) -> Result<bool> {
+        reply(async move {
+            let lost = lost
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            let rescuer = rescuer
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            info!("Vouching for {} to recover {}", rescuer.0, lost.0);
+            let event = self
+                .client
+                .read()
+                .await
+                .vouch_recovery(lost.0, rescuer.0)
+                .await?;
+            info!("Recovery Vouched: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn claim(&self, lost: &str) -> Result<bool> {
+        reply(async move {
+            let lost = lost
+                .parse::<Ss58<N::Runtime>>()
+                .code(ErrorCode::InvalidAccountId)?;
+            info!("Claiming the recovery of {}", lost.0);
+            let event =
+                self.client.read().await.claim_recovered(lost.0).await?;
+            info!("Account Recovered: {:?}", event);
+            Ok(true)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Backup<'a, C, N>
+where
+    C: BackupClient<N> + Send + Sync,
+    N: Node,
+{
+    pub async fn export(
+        &self,
+        root: &str,
+        path: &str,
+        password: &str,
+        progress_port: i64,
+    ) -> Result<bool> {
+        let password = SecretString::new(password.to_string());
+        reply(async move {
+            info!("Exporting the client state to {}", path);
+            self.client
+                .read()
+                .await
+                .export_state(
+                    Path::new(root),
+                    Path::new(path),
+                    &password,
+                    &mut progress_reporter(progress_port),
+                )
+                .await?;
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn import(
+        &self,
+        root: &str,
+        path: &str,
+        password: &str,
+        progress_port: i64,
+    ) -> Result<bool> {
+        let password = SecretString::new(password.to_string());
+        reply(async move {
+            info!("Importing the client state from {}", path);
+            // nothing else may use the client while its files are swapped
+            self.client
+                .write()
+                .await
+                .import_state(
+                    Path::new(root),
+                    Path::new(path),
+                    &password,
+                    &mut progress_reporter(progress_port),
+                )
+                .await?;
+            Ok(true)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Metrics<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub async fn snapshot(&self) -> Result<String> {
+        reply_json(async move {
+            let snapshot: serde_json::Value =
+                serde_json::from_str(&metrics_snapshot())?;
+            Ok(snapshot)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Log<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub async fn set_level(&self, level: &str) -> Result<bool> {
+        reply(async move {
+            let level = parse_level(level).code(ErrorCode::InvalidLogLevel)?;
+            set_log_level(level);
+            info!("Log level set to {}", level);
+            Ok(true)
+        })
+        .await
+    }
+
+    /// A port of 0 stops posting the lines
+    pub async fn set_sink(&self, port: i64) -> Result<bool> {
+        reply(async move {
+            set_log_sink(if port == 0 { None } else { Some(port) });
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn recent(&self, n: u32) -> Result<String> {
+        reply_json(async move { Ok(recent_logs(n as usize)) }).await
+    }
+}
+
+#[cfg(feature = "mock")]
+impl<'a, C, N> Mock<'a, C, N>
+where
+    C: Send + Sync,
+    N: Node,
+{
+    pub async fn enabled(&self) -> Result<bool> {
+        reply(async move { Ok(mock::enabled()) }).await
+    }
+
+    pub async fn set_signer(&self, account: &str) -> Result<bool> {
+        reply(async move {
+            mock::set_signer(account);
+            Ok(true)
+        })
+        .await
+    }
+
+    pub async fn advance(&self, blocks: u32) -> Result<u32> {
+        reply(async move { Ok(mock::advance(blocks)) }).await
+    }
+
+    pub async fn events(&self) -> Result<String> {
+        reply_json(async move { Ok(mock::events()) }).await
+    }
+
+    pub async fn reset(&self) -> Result<bool> {
+        reply(async move {
+            mock::reset();
+            Ok(true)
+        })
+        .await
+    }
+}
+
+/// Posts `{ "done": .., "total": .. }` to the port whenever another percent
+/// of the state was processed
+fn progress_reporter(port: i64) -> impl FnMut(Progress) + Send {
+    let isolate = Isolate::new(port);
+    let mut reported = None;
+    move |progress: Progress| {
+        let percent = match progress.total {
+            0 => 100,
+            total => progress.done * 100 / total,
+        };
+        if reported != Some(percent) {
+            reported = Some(percent);
+            let json = serde_json::json!({
+                "done": progress.done,
+                "total": progress.total,
+            });
+            isolate.post(json.to_string());
+        }
+    }
 }
 
 impl<'a, C, N> Wallet<'a, C, N>
@@ -567,14 +2359,18 @@ where
 
 {
     pub async fn balance(&self, identifier: Option<&str>) -> Result<<N::Runtime as Balances>::Balance> {
-        let client = self.client.read().await;
-        let account_id: Ss58<N::Runtime> = if let Some(identifier) = identifier {
-            identifier.parse()?
-        } else {
-            Ss58(client.signer()?.account_id().clone())
-        };
-        let account = client.chain_client().account(&account_id.0, None).await?;
-        Ok(account.data.free)
+        reply(async move {
+            let client = self.client.read().await;
+            let account_id: Ss58<N::Runtime> = if let Some(identifier) = identifier {
+                identifier.parse().code(ErrorCode::InvalidAccountId)?
+            } else {
+                let signer = client.signer().code(ErrorCode::KeystoreLocked)?;
+                Ss58(signer.account_id().clone())
+            };
+            let account = client.chain_client().account(&account_id.0, None).await?;
+            Ok(account.data.free)
+        })
+        .await
     }
 
     pub async fn transfer(
@@ -582,16 +2378,339 @@ where
         to: &str,
         amount: u64,
     ) -> Result<<N::Runtime as Balances>::Balance> {
+        reply(async move {
+            let client = self.client.read().await;
+            let account_id: Ss58<N::Runtime> =
+                to.parse().code(ErrorCode::InvalidAccountId)?;
+            client.chain_signer().code(ErrorCode::KeystoreLocked)?;
+            let signer = client.nonced_signer().await?;
+            client
+                .watch(
+                    TransferCall::<N::Runtime> {
+                        to: &account_id.0.into(),
+                        amount: amount.into(),
+                    },
+                    &signer,
+                )
+                .settled(&signer)
+                .await?
+                .transfer()
+                .map_err(|_| {
+                    FfiError::new(
+                        ErrorCode::TransferFailed,
+                        "Failed to decode transfer event",
+                    )
+                })?
+                .ok_or_else(|| {
+                    FfiError::new(
+                        ErrorCode::TransferFailed,
+                        "Failed to find transfer event",
+                    )
+                })?;
+            self.balance(None).await
+        })
+        .await
+    }
+
+    /// Dry runs `transfer` without submitting it
+    pub async fn transfer_preflight(
+        &self,
+        to: &str,
+        amount: u64,
+    ) -> Result<String> {
+        reply_json(async move {
+            let client = self.client.read().await;
+            let account_id: Ss58<N::Runtime> =
+                to.parse().code(ErrorCode::InvalidAccountId)?;
+            client.chain_signer().code(ErrorCode::KeystoreLocked)?;
+            let outcome = client
+                .dry_run(TransferCall::<N::Runtime> {
+                    to: &account_id.0.into(),
+                    amount: amount.into(),
+                })
+                .await?;
+            Ok(dry_run_information(outcome))
+        })
+        .await
+    }
+
+    pub async fn payment_request(
+        &self,
+        amount: Option<&str>,
+        memo: Option<&str>,
+    ) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            let signer = client.signer().code(ErrorCode::KeystoreLocked)?;
+            let amount = match amount {
+                Some(amount) => Some(amount.parse::<u128>()?),
+                None => None,
+            };
+            let request = PaymentRequest {
+                genesis: genesis::<N, C>(&*client),
+                account: client.chain_properties().ss58(signer.account_id()),
+                amount,
+                memo: memo.filter(|m| !m.is_empty()).map(str::to_string),
+            };
+            Ok(request.encode())
+        })
+        .await
+    }
+
+    pub async fn parse_payment_request(&self, payload: &str) -> Result<String> {
+        reply_json(async move {
+            let client = self.client.read().await;
+            let request =
+                PaymentRequest::decode(payload, &genesis::<N, C>(&*client))?;
+            let account: Ss58<N::Runtime> =
+                request.account.parse().code(ErrorCode::InvalidAccountId)?;
+            Ok(PaymentRequestInformation {
+                schema_version: SCHEMA_VERSION,
+                account: client.chain_properties().ss58(&account.0),
+                amount: request.amount.map(Balance),
+                memo: request.memo,
+            })
+        })
+        .await
+    }
+
+    pub async fn chain_properties(&self) -> Result<String> {
+        reply_json(async move {
+            let properties = self.client.read().await.chain_properties();
+            Ok(ChainPropertiesInformation {
+                schema_version: SCHEMA_VERSION,
+                ss58_format: properties.ss58_format.into(),
+                token_decimals: properties.token_decimals.into(),
+                token_symbol: properties.token_symbol,
+            })
+        })
+        .await
+    }
+
+    pub async fn override_chain_properties(
+        &self,
+        ss58_format: Option<&str>,
+        token_decimals: Option<&str>,
+        token_symbol: Option<&str>,
+    ) -> Result<bool> {
+        reply(async move {
+            let overrides = PropertiesOverrides {
+                ss58_format: ss58_format.map(str::parse).transpose()?,
+                token_decimals: token_decimals.map(str::parse).transpose()?,
+                token_symbol: token_symbol.map(str::to_string),
+            };
+            self.client
+                .read()
+                .await
+                .override_chain_properties(overrides);
+            Ok(true)
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Chain<'a, C, N>
+where
+    C: Client<N> + Send + Sync,
+    N: Node,
+    N::Runtime: Balances,
+    <N::Runtime as Balances>::Balance: Into<u128>,
+{
+    pub async fn constants(&self) -> Result<String> {
+        reply_json(async move {
+            let client = self.client.read().await;
+            // drops the cached constants if the runtime was upgraded
+            let version = client.runtime_version().await?;
+            let constants = client.chain_constants().await?;
+            Ok(ChainConstantsInformation {
+                schema_version: SCHEMA_VERSION,
+                spec_version: version.spec_version,
+                existential_deposit: Balance(constants.existential_deposit),
+                block_time: constants.block_time,
+                max_org_members: constants.max_org_members,
+                free_ballots: constants.free_ballots,
+                max_composite_legs: constants.max_composite_legs,
+                max_justification_history: constants.max_justification_history,
+                bounty_min_deposit: Balance(constants.bounty_min_deposit),
+                bounty_min_contribution: Balance(
+                    constants.bounty_min_contribution,
+                ),
+                bounty_submission_deposit: Balance(
+                    constants.bounty_submission_deposit,
+                ),
+                max_pending_submissions: constants.max_pending_submissions,
+                max_bounty_admins: constants.max_bounty_admins,
+                max_bounty_tags: constants.max_bounty_tags,
+                max_payout_splits: constants.max_payout_splits,
+                bounty_dispute_deposit: Balance(
+                    constants.bounty_dispute_deposit,
+                ),
+                bounty_dispute_window: constants.bounty_dispute_window,
+                bounty_review_timeout: constants.bounty_review_timeout,
+            })
+        })
+        .await
+    }
+
+    pub async fn runtime_version(&self) -> Result<String> {
+        reply_json(async move {
+            let version = self.client.read().await.runtime_version().await?;
+            Ok(RuntimeVersionInformation {
+                schema_version: SCHEMA_VERSION,
+                spec_name: version.spec_name,
+                impl_name: version.impl_name,
+                spec_version: version.spec_version,
+                impl_version: version.impl_version,
+                transaction_version: version.transaction_version,
+            })
+        })
+        .await
+    }
+
+    pub async fn compatibility(&self) -> Result<String> {
+        reply_json(async move {
+            let report = self.client.read().await.compatibility().await?;
+            let features = report
+                .features
+                .into_iter()
+                .map(|feature| {
+                    FeatureSupportInformation {
+                        schema_version: SCHEMA_VERSION,
+                        feature: feature.feature,
+                        pallet: feature.pallet,
+                        support: feature.support.to_string(),
+                        reason: feature.reason,
+                    }
+                })
+                .collect();
+            Ok(CompatibilityInformation {
+                schema_version: SCHEMA_VERSION,
+                spec_version: report.spec_version,
+                transaction_version: report.transaction_version,
+                features,
+            })
+        })
+        .await
+    }
+
+    pub async fn genesis_hash(&self) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            Ok(format!("0x{}", hex::encode(genesis::<N, C>(&*client))))
+        })
+        .await
+    }
+}
+
+impl<'a, C, N> Links<'a, C, N>
+where
+    C: BountyClient<N> + VoteClient<N> + VoteTextClient<N> + Send + Sync,
+    N: Node,
+    N::Runtime: BountyTrait<IpfsReference = sunshine_codec::Cid> + VoteTrait,
+    C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
+    <N::Runtime as BountyTrait>::BountyId: From<u64>,
+    <N::Runtime as VoteTrait>::VoteId: From<u64>,
+    <N::Runtime as OrgTrait>::Cid: Into<Cid>,
+{
+    pub async fn for_bounty(&self, bounty_id: &str) -> Result<String> {
+        self.link(LinkTarget::Bounty, bounty_id).await
+    }
+
+    pub async fn for_vote(&self, vote_id: &str) -> Result<String> {
+        self.link(LinkTarget::Vote, vote_id).await
+    }
+
+    async fn link(&self, target: LinkTarget, id: &str) -> Result<String> {
+        reply(async move {
+            let client = self.client.read().await;
+            let link = Link {
+                target,
+                id: id.parse()?,
+            };
+            Ok(link.encode(&genesis::<N, C>(&*client)))
+        })
+        .await
+    }
+
+    /// Looks up the target of a link on the connected chain. A target
+    /// which does not exist is not an error, the app shows it as gone
+    pub async fn resolve(&self, uri: &str) -> Result<String> {
+        reply_json(async move {
+            let genesis = genesis::<N, C>(&*self.client.read().await);
+            let link = Link::decode(uri, &genesis)?;
+            let (exists, preview) = match link.target {
+                LinkTarget::Bounty => {
+                    self.bounty_preview(link.id.into()).await?
+                }
+                LinkTarget::Vote => self.vote_preview(link.id.into()).await?,
+            };
+            Ok(LinkInformation {
+                schema_version: SCHEMA_VERSION,
+                kind: link.target.as_str().to_string(),
+                id: link.id.to_string(),
+                exists,
+                preview,
+            })
+        })
+        .await
+    }
+
+    /// Whether the bounty is open or closed and its issue, a preview whose
+    /// metadata cannot be fetched is left out
+    async fn bounty_preview(
+        &self,
+        id: <N::Runtime as BountyTrait>::BountyId,
+    ) -> Result<(bool, Option<String>)> {
         let client = self.client.read().await;
-        let account_id: Ss58<N::Runtime> = to.parse()?;
-        let signer = client.chain_signer()?;
-        client
+        let chain = client.chain_client();
+        let state = match chain.fetch(&BountiesStore { id }, None).await? {
+            Some(state) => Some(state),
+            None => {
+                chain
+                    .fetch(&ClosedBountiesStore { bounty_id: id }, None)
+                    .await?
+            }
+        };
+        let state = match state {
+            Some(state) => state,
+            None => return Ok((false, None)),
+        };
+        let issue: Option<GithubIssue> =
+            client.offchain_client().get(&state.info()).await.ok();
+        let preview = issue.map(|issue| {
+            format!(
+                "{}/{}#{}",
+                issue.repo_owner, issue.repo_name, issue.issue_number
+            )
+        });
+        Ok((true, preview))
+    }
+
+    /// Whether the vote exists and its topic, if it has one that can be
+    /// fetched
+    async fn vote_preview(
+        &self,
+        vote: <N::Runtime as VoteTrait>::VoteId,
+    ) -> Result<(bool, Option<String>)> {
+        let client = self.client.read().await;
+        let state = match client
             .chain_client()
-            .transfer_and_watch(&signer, &account_id.0.into(), amount.into())
+            .fetch(&VoteStatesStore { vote }, None)
             .await?
-            .transfer()
-            .map_err(|_| anyhow!("Failed to decode transfer event"))?
-            .ok_or_else(|| anyhow!("Failed to find transfer event"))?;
-        self.balance(None).await
+        {
+            Some(state) => state,
+            None => return Ok((false, None)),
+        };
+        let preview = match state.topic() {
+            Some(topic) => client.resolve_text(&topic.into()).await.ok(),
+            None => None,
+        };
+        Ok((true, preview))
     }
 }
+
+/// The genesis hash of the chain the client is connected to, which binds
+/// scanned payloads to it
+fn genesis<N: Node, C: Client<N>>(client: &C) -> Vec<u8> {
+    client.chain_client().genesis().as_ref().to_vec()
+}
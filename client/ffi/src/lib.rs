@@ -1,6 +1,17 @@
 pub use sunshine_ffi_utils as ffi_utils;
+pub mod draft;
 pub mod dto;
+pub mod error;
+// the log macros are in scope of the modules declared after it only
+#[macro_use]
+pub mod logging;
 pub mod ffi;
+pub mod link;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod qr;
+pub mod sync;
+pub use dto::ffi_schema;
 
 #[doc(hidden)]
 #[cfg(feature = "bounty-key")]
@@ -33,6 +44,37 @@ macro_rules! impl_bounty_key_ffi {
             /// Get current UID as string (if any)
             /// otherwise null returned
             Key::uid => fn client_key_uid() -> Option<String>;
+            /// Make a payload asking an air-gapped device to sign a call
+            /// `call_hex` is the SCALE encoded call, with or without `0x`
+            /// returns a string to be shown as a QR code
+            Key::signing_request => fn client_key_signing_request(
+                call_hex: *const raw::c_char = cstr!(call_hex)
+            ) -> String;
+            /// Attach the signature made by the air-gapped device to the
+            /// payload from `client_key_signing_request`
+            /// returns the signed payload, or an error if the payload is for another chain
+            Key::provide_signature => fn client_key_provide_signature(
+                payload: *const raw::c_char = cstr!(payload),
+                signature_hex: *const raw::c_char = cstr!(signature_hex)
+            ) -> String;
+            /// Follow an account without its key under `label`
+            /// `root` is the directory of the client, the one with the keystore
+            /// returns the address in the format of the chain
+            Key::add_watch_only => fn client_key_add_watch_only(
+                root: *const raw::c_char = cstr!(root),
+                account: *const raw::c_char = cstr!(account),
+                label: *const raw::c_char = cstr!(label)
+            ) -> String;
+            /// Get the accounts followed without their key
+            /// Returns JSON encoded `Vec<WatchOnlyInformation>` as string
+            Key::watch_only => fn client_key_watch_only(
+                root: *const raw::c_char = cstr!(root)
+            ) -> JSON<Vec<WatchOnlyInformation>>;
+            /// Stop following the account watched as `label`
+            Key::remove_watch_only => fn client_key_remove_watch_only(
+                root: *const raw::c_char = cstr!(root),
+                label: *const raw::c_char = cstr!(label)
+            ) -> bool;
         }
     }
 }
@@ -60,6 +102,36 @@ macro_rules! impl_bounty_wallet_ffi {
                 to: *const raw::c_char = cstr!(to),
                 amount: u64 = amount
             ) -> String;
+            /// Dry run a transfer without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Wallet::transfer_preflight => fn client_wallet_transfer_preflight(
+                to: *const raw::c_char = cstr!(to),
+                amount: u64 = amount
+            ) -> JSON<DryRunInformation>;
+            /// Make a payload asking the scanning wallet to pay the current account
+            /// `amount` and `memo` are optional
+            /// returns a string to be shown as a QR code
+            Wallet::payment_request => fn client_wallet_payment_request(
+                amount: *const raw::c_char = cstr!(amount, allow_null),
+                memo: *const raw::c_char = cstr!(memo, allow_null)
+            ) -> String;
+            /// Read a scanned payment request to pre-fill a transfer
+            /// Returns JSON encoded `PaymentRequestInformation` as string,
+            /// or an error if the payload is for another chain
+            Wallet::parse_payment_request => fn client_wallet_parse_payment_request(
+                payload: *const raw::c_char = cstr!(payload)
+            ) -> JSON<PaymentRequestInformation>;
+            /// Get the ss58 format and native token the client renders with
+            /// Returns JSON encoded `ChainPropertiesInformation` as string
+            Wallet::chain_properties => fn client_wallet_chain_properties() -> JSON<ChainPropertiesInformation>;
+            /// Override the chain properties for chains that do not publish them
+            /// null arguments keep the detected value
+            /// returns `true` once applied
+            Wallet::override_chain_properties => fn client_wallet_override_chain_properties(
+                ss58_format: *const raw::c_char = cstr!(ss58_format, allow_null),
+                token_decimals: *const raw::c_char = cstr!(token_decimals, allow_null),
+                token_symbol: *const raw::c_char = cstr!(token_symbol, allow_null)
+            ) -> bool;
         }
     };
 }
@@ -88,20 +160,37 @@ macro_rules! impl_bounty_ffi {
             Bounty::get_submission => fn client_bounty_get_submission(
                 submission_id: *const raw::c_char = cstr!(submission_id)
             ) -> JSON<BountySubmissionInformation>;
-            /// Create a new Bounty
+            /// Create a new Bounty, funded in `asset_id` or in the native
+            /// currency if it is null
             /// Returns the `BountyId` as `u64`
             Bounty::post => fn client_bounty_post(
                 repo_owner: *const raw::c_char = cstr!(repo_owner),
                 repo_name: *const raw::c_char = cstr!(repo_name),
                 issue_number: u64 = issue_number,
-                amount: *const raw::c_char = cstr!(amount)
+                amount: *const raw::c_char = cstr!(amount),
+                asset_id: *const raw::c_char = cstr!(asset_id, allow_null)
             ) -> u64;
+            /// Dry run posting a bounty without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Bounty::post_preflight => fn client_bounty_post_preflight(
+                repo_owner: *const raw::c_char = cstr!(repo_owner),
+                repo_name: *const raw::c_char = cstr!(repo_name),
+                issue_number: u64 = issue_number,
+                amount: *const raw::c_char = cstr!(amount),
+                asset_id: *const raw::c_char = cstr!(asset_id, allow_null)
+            ) -> JSON<DryRunInformation>;
             /// Contribute to a bounty.
             /// Returns the new total bounty amount
             Bounty::contribute => fn client_bounty_contribute(
                 bounty_id: *const raw::c_char = cstr!(bounty_id),
                 amount: *const raw::c_char = cstr!(amount)
             ) -> u128;
+            /// Dry run a contribution without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Bounty::contribute_preflight => fn client_bounty_contribute_preflight(
+                bounty_id: *const raw::c_char = cstr!(bounty_id),
+                amount: *const raw::c_char = cstr!(amount)
+            ) -> JSON<DryRunInformation>;
             /// Create a submission on a bounty
             /// Returns the `SubmissionId` as `u64`
             Bounty::submit => fn client_bounty_submit(
@@ -111,21 +200,146 @@ macro_rules! impl_bounty_ffi {
                 issue_number: u64 = issue_number,
                 amount: *const raw::c_char = cstr!(amount)
             ) -> u64;
+            /// Dry run a submission without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Bounty::submit_preflight => fn client_bounty_submit_preflight(
+                bounty_id: *const raw::c_char = cstr!(bounty_id),
+                repo_owner: *const raw::c_char = cstr!(repo_owner),
+                repo_name: *const raw::c_char = cstr!(repo_name),
+                issue_number: u64 = issue_number,
+                amount: *const raw::c_char = cstr!(amount)
+            ) -> JSON<DryRunInformation>;
             /// Approve a Submission using `SubmissionId`
             /// Returns the new total amount on that bounty after this operation
             Bounty::approve => fn client_bounty_approve(
                 submission_id: *const raw::c_char = cstr!(submission_id)
             ) -> u128;
-            /// Get a list of open bounties.
+            /// Dry run approving a submission without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Bounty::approve_preflight => fn client_bounty_approve_preflight(
+                submission_id: *const raw::c_char = cstr!(submission_id)
+            ) -> JSON<DryRunInformation>;
+            /// Get the deposit reserved from submitters for every submission
+            /// so the user can be warned before submitting
+            Bounty::submission_deposit => fn client_bounty_submission_deposit() -> u128;
+            /// Get a list of open bounties, only those with `tag` unless it is null.
             /// Returns a JSON encoded list of `BountyInformation` as string.
             Bounty::open_bounties => fn client_bounty_open_bounties(
-                min: *const raw::c_char = cstr!(min)
+                min: *const raw::c_char = cstr!(min),
+                tag: *const raw::c_char = cstr!(tag, allow_null)
+            ) -> JSON<Vec<BountyInformation>>;
+            /// Get the bounties closed after paying out all they could.
+            /// Returns a JSON encoded list of `BountyInformation` as string.
+            Bounty::closed_bounties => fn client_bounty_closed_bounties() -> JSON<Vec<BountyInformation>>;
+            /// Get the open bounties on the board of an org, funded by its treasury.
+            /// Returns a JSON encoded list of `BountyInformation` as string.
+            Bounty::board => fn client_bounty_board(
+                org_id: *const raw::c_char = cstr!(org_id)
             ) -> JSON<Vec<BountyInformation>>;
             /// Get a list of open submissions on a bounty.
             /// Returns a JSON encoded list of `BountySubmissionInformation` as string.
             Bounty::open_bounty_submissions => fn client_bounty_open_bounty_submissions(
                 bounty_id: *const raw::c_char = cstr!(bounty_id)
             ) -> JSON<Vec<BountySubmissionInformation>>;
+            /// Get the open submissions on a bounty nobody was assigned to review.
+            /// Returns a JSON encoded list of `BountySubmissionInformation` as string.
+            Bounty::unassigned_submissions => fn client_bounty_unassigned_submissions(
+                bounty_id: *const raw::c_char = cstr!(bounty_id)
+            ) -> JSON<Vec<BountySubmissionInformation>>;
+            /// Assign a bounty admin to review a submission
+            Bounty::assign_reviewer => fn client_bounty_assign_reviewer(
+                submission_id: *const raw::c_char = cstr!(submission_id),
+                reviewer: *const raw::c_char = cstr!(reviewer)
+            ) -> bool;
+            /// Dry run assigning a reviewer without submitting it
+            /// Returns JSON encoded `DryRunInformation` as string
+            Bounty::assign_reviewer_preflight => fn client_bounty_assign_reviewer_preflight(
+                submission_id: *const raw::c_char = cstr!(submission_id),
+                reviewer: *const raw::c_char = cstr!(reviewer)
+            ) -> JSON<DryRunInformation>;
+            /// Have the org of `threshold_id` arbitrate disputes of the
+            /// bounty's rejections, or the default org if it is null
+            Bounty::set_arbitration => fn client_bounty_set_arbitration(
+                bounty_id: *const raw::c_char = cstr!(bounty_id),
+                threshold_id: *const raw::c_char = cstr!(threshold_id, allow_null)
+            ) -> bool;
+            /// Dispute the rejection of a submission with the cid of the
+            /// submitter's argument, reserving the dispute deposit
+            /// Returns the id of the arbitration vote as string
+            Bounty::dispute_rejection => fn client_bounty_dispute_rejection(
+                submission_id: *const raw::c_char = cstr!(submission_id),
+                argument: *const raw::c_char = cstr!(argument)
+            ) -> String;
+            /// Apply the outcome of a decided arbitration vote
+            /// Returns true if the rejection was overturned and the
+            /// submission paid, false if the deposit was slashed
+            Bounty::resolve_dispute => fn client_bounty_resolve_dispute(
+                submission_id: *const raw::c_char = cstr!(submission_id)
+            ) -> bool;
+            /// Get the rejection of a submission and its dispute
+            /// Returns JSON encoded `RejectionInformation` as string, or
+            /// null if the rejection cannot be or is no longer disputed
+            Bounty::rejection => fn client_bounty_rejection(
+                submission_id: *const raw::c_char = cstr!(submission_id)
+            ) -> JSON<RejectionInformation>;
+            /// Pin a snapshot of the bounty with its contributions and
+            /// submissions to the offchain store.
+            /// Returns the cid of the snapshot.
+            Bounty::archive => fn client_bounty_archive(
+                bounty_id: *const raw::c_char = cstr!(bounty_id)
+            ) -> String;
+            /// Check a snapshot pinned by `client_bounty_archive` against
+            /// the chain state at the block it was taken at
+            Bounty::verify_archive => fn client_bounty_verify_archive(
+                cid: *const raw::c_char = cstr!(cid)
+            ) -> bool;
+            /// Save a bounty or submission locally to post it later, e.g.
+            /// once the device is back online. `kind` is `bounty` or
+            /// `submission` and `draft` the JSON of the arguments of
+            /// `client_bounty_post` or `client_bounty_submit`.
+            /// Returns the id of the draft as `u64`
+            Bounty::save_draft => fn client_bounty_save_draft(
+                root: *const raw::c_char = cstr!(root),
+                kind: *const raw::c_char = cstr!(kind),
+                draft: *const raw::c_char = cstr!(draft)
+            ) -> u64;
+            /// Get the drafts saved below `root`, oldest first.
+            /// Returns a JSON encoded list of `DraftInformation` as string.
+            Bounty::list_drafts => fn client_bounty_list_drafts(
+                root: *const raw::c_char = cstr!(root)
+            ) -> JSON<Vec<DraftInformation>>;
+            /// Delete a draft without posting it
+            /// returns `false` if there was no draft with the id
+            Bounty::delete_draft => fn client_bounty_delete_draft(
+                root: *const raw::c_char = cstr!(root),
+                draft_id: u64 = draft_id
+            ) -> bool;
+            /// Post a draft, deleting it once the extrinsic was included.
+            /// The draft is kept if posting fails or times out.
+            /// Returns the `BountyId` or `SubmissionId` as `u64`
+            Bounty::post_draft => fn client_bounty_post_draft(
+                root: *const raw::c_char = cstr!(root),
+                draft_id: u64 = draft_id
+            ) -> u64;
+            /// Pin a file of up to 5 MiB as evidence for the submission
+            /// draft `draft_id`, `bytes` encoded as base64 and `mime` its
+            /// type like `image/png`. Posting the draft attaches it.
+            /// Fails with `EVIDENCE_TOO_LARGE` above the limit.
+            /// Returns the cid of the evidence
+            Bounty::attach_evidence => fn client_bounty_attach_evidence(
+                root: *const raw::c_char = cstr!(root),
+                draft_id: u64 = draft_id,
+                bytes: *const raw::c_char = cstr!(bytes),
+                mime: *const raw::c_char = cstr!(mime)
+            ) -> String;
+            /// Get the bytes of an evidence file listed in the
+            /// `attachments` of a submission, fetched from the network or
+            /// the gateway in `SUNSHINE_IPFS_GATEWAY` if it is not pinned
+            /// locally. Fails with `EVIDENCE_TOO_LARGE` above the limit.
+            /// Returns the bytes encoded as base64
+            Bounty::fetch_evidence => fn client_bounty_fetch_evidence(
+                cid: *const raw::c_char = cstr!(cid)
+            ) -> String;
         }
     };
 }
@@ -137,8 +351,331 @@ macro_rules! impl_bounty_ffi {
     () => {};
 }
 
+#[doc(hidden)]
+#[cfg(feature = "vote-module")]
+#[macro_export]
+macro_rules! impl_vote_ffi {
+    () => {
+        use $crate::ffi::Vote;
+        gen_ffi! {
+            /// Get the tally of a vote and the signal remaining to decide it
+            /// Returns JSON encoded `VoteProgressInformation` as string
+            Vote::status => fn client_vote_status(
+                vote_id: *const raw::c_char = cstr!(vote_id)
+            ) -> JSON<VoteProgressInformation>;
+            /// Take a receipt proving the latest ballot of the user in a vote
+            /// Returns the receipt as a base64 string to share
+            Vote::receipt => fn client_vote_receipt(
+                vote_id: *const raw::c_char = cstr!(vote_id)
+            ) -> String;
+            /// Check a receipt against the state of the block it was taken
+            /// at, or against `state_root` if it is not null. Fails if the
+            /// receipt was taken on another chain
+            Vote::verify_receipt => fn client_vote_verify_receipt(
+                blob: *const raw::c_char = cstr!(blob),
+                state_root: *const raw::c_char = cstr!(state_root, allow_null)
+            ) -> bool;
+            /// Compose a vote topic from plain text, its first line the
+            /// title, and pin it locally without reaching the chain
+            /// Returns the cid of the topic
+            Vote::create_topic => fn client_vote_create_topic(
+                text: *const raw::c_char = cstr!(text)
+            ) -> String;
+            /// Compose a ballot justification from plain text and pin it
+            /// locally without reaching the chain
+            /// Returns the cid of the justification
+            Vote::create_justification => fn client_vote_create_justification(
+                text: *const raw::c_char = cstr!(text)
+            ) -> String;
+            /// Get the text of a vote topic or justification, fetched from
+            /// the network if it is not pinned locally
+            Vote::resolve_text => fn client_vote_resolve_text(
+                cid: *const raw::c_char = cstr!(cid)
+            ) -> String;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "vote-module"))]
+#[macro_export]
+macro_rules! impl_vote_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[cfg(feature = "org-module")]
+#[macro_export]
+macro_rules! impl_org_ffi {
+    () => {
+        use $crate::ffi::Org;
+        gen_ffi! {
+            /// Get the members of an org with their shares and, if they set
+            /// a profile, their display name
+            /// Returns a JSON encoded list of `MemberInformation` as string
+            Org::members => fn client_org_members(
+                org_id: *const raw::c_char = cstr!(org_id)
+            ) -> JSON<Vec<MemberInformation>>;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "org-module"))]
+#[macro_export]
+macro_rules! impl_org_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[cfg(feature = "recovery-module")]
+#[macro_export]
+macro_rules! impl_recovery_ffi {
+    () => {
+        use $crate::ffi::Recovery;
+        gen_ffi! {
+            /// Let friends recover this account if the device is lost
+            /// `friends` is a JSON encoded list of account ids
+            Recovery::add_friends => fn client_recovery_add_friends(
+                friends: *const raw::c_char = cstr!(friends),
+                threshold: u64 = threshold
+            ) -> bool;
+            /// Start recovering a lost account to the current account
+            Recovery::initiate => fn client_recovery_initiate(
+                lost: *const raw::c_char = cstr!(lost)
+            ) -> bool;
+            /// Vouch, as a friend of the lost account, for the rescuer
+            Recovery::vouch => fn client_recovery_vouch(
+                lost: *const raw::c_char = cstr!(lost),
+                rescuer: *const raw::c_char = cstr!(rescuer)
+            ) -> bool;
+            /// Take control of the lost account once enough friends vouched
+            Recovery::claim => fn client_recovery_claim(
+                lost: *const raw::c_char = cstr!(lost)
+            ) -> bool;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "recovery-module"))]
+#[macro_export]
+macro_rules! impl_recovery_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[cfg(feature = "backup")]
+#[macro_export]
+macro_rules! impl_backup_ffi {
+    () => {
+        use $crate::ffi::Backup;
+        gen_ffi! {
+            /// Package the keystore, block store and config found in `root`
+            /// into an archive at `path`, encrypted with `password`.
+            /// `{ "done": u64, "total": u64 }` is posted to `progress_port`
+            /// as the export advances.
+            Backup::export => fn client_backup_export(
+                root: *const raw::c_char = cstr!(root),
+                path: *const raw::c_char = cstr!(path),
+                password: *const raw::c_char = cstr!(password),
+                progress_port: i64 = progress_port
+            ) -> bool;
+            /// Restore an archive made by `client_backup_export` into `root`
+            /// if it belongs to the configured chain. Progress is posted like
+            /// for the export. The client must be initialized again afterwards.
+            Backup::import => fn client_backup_import(
+                root: *const raw::c_char = cstr!(root),
+                path: *const raw::c_char = cstr!(path),
+                password: *const raw::c_char = cstr!(password),
+                progress_port: i64 = progress_port
+            ) -> bool;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "backup"))]
+#[macro_export]
+macro_rules! impl_backup_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_log_ffi {
+    () => {
+        use $crate::ffi::Log;
+        gen_ffi! {
+            /// Set the verbosity of the logs at runtime, one of `off`, `error`,
+            /// `warn`, `info`, `debug` or `trace`. Account ids and cids are
+            /// redacted below `debug`.
+            Log::set_level => fn client_set_log_level(
+                level: *const raw::c_char = cstr!(level)
+            ) -> bool;
+            /// Post every following log line as a string to `port`, so the app
+            /// can route them to its own logger. A port of 0 stops posting.
+            Log::set_sink => fn client_set_log_sink(port: i64 = port) -> bool;
+            /// Get up to the last `n` log lines, oldest first, to attach to
+            /// support tickets
+            Log::recent => fn client_get_recent_logs(n: u32 = n) -> JSON<Vec<String>>;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_chain_ffi {
+    () => {
+        use $crate::ffi::Chain;
+        gen_ffi! {
+            /// Get the runtime constants apps check inputs against, like the
+            /// existential deposit, the block time in milliseconds and the
+            /// bounty minimums. Cached until the runtime is upgraded.
+            /// Returns JSON encoded `ChainConstantsInformation` as string.
+            Chain::constants => fn client_chain_constants() -> JSON<ChainConstantsInformation>;
+            /// Get the version of the runtime at the best block
+            /// Returns JSON encoded `RuntimeVersionInformation` as string.
+            Chain::runtime_version => fn client_chain_runtime_version() -> JSON<RuntimeVersionInformation>;
+            /// Get which features of the client the runtime at the best
+            /// block supports: `safe`, `degraded` when reads may be wrong
+            /// and extrinsics are refused, or `unsupported`
+            /// Returns JSON encoded `CompatibilityInformation` as string.
+            Chain::compatibility => fn client_chain_compatibility() -> JSON<CompatibilityInformation>;
+            /// Get the genesis hash of the chain as 0x prefixed hex
+            Chain::genesis_hash => fn client_chain_genesis_hash() -> String;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_link_ffi {
+    () => {
+        use $crate::ffi::Links;
+        gen_ffi! {
+            /// Get a `sunshine://bounty/<id>?chain=<genesis>` link to share
+            /// the bounty, which only resolves on this chain
+            Links::for_bounty => fn client_link_for_bounty(
+                bounty_id: *const raw::c_char = cstr!(bounty_id)
+            ) -> String;
+            /// Get a `sunshine://vote/<id>?chain=<genesis>` link to share the
+            /// vote, which only resolves on this chain
+            Links::for_vote => fn client_link_for_vote(
+                vote_id: *const raw::c_char = cstr!(vote_id)
+            ) -> String;
+            /// Look up what a shared link opens, failing with
+            /// `LINK_CHAIN_MISMATCH` for a link of another chain.
+            /// Returns JSON encoded `LinkInformation` as string.
+            Links::resolve => fn client_link_resolve(
+                uri: *const raw::c_char = cstr!(uri)
+            ) -> JSON<LinkInformation>;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "metrics")]
+#[macro_export]
+macro_rules! impl_metrics_ffi {
+    () => {
+        use $crate::ffi::Metrics;
+        gen_ffi! {
+            /// The counters and latency histograms collected by the client
+            /// since it was started, as JSON to attach to bug reports.
+            Metrics::snapshot => fn client_metrics_snapshot() -> String;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "metrics"))]
+#[macro_export]
+macro_rules! impl_metrics_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[cfg(feature = "mock")]
+#[macro_export]
+macro_rules! impl_mock_ffi {
+    () => {
+        use $crate::ffi::Mock;
+        gen_ffi! {
+            /// Whether `SUNSHINE_MOCK` switched the client to the simulated
+            /// chain, the bounty, vote and org calls then answer from it
+            Mock::enabled => fn client_mock_enabled() -> bool;
+            /// Sign the following simulated calls as `account`, to play
+            /// another role like a bounty admin
+            Mock::set_signer => fn client_mock_set_signer(
+                account: *const raw::c_char = cstr!(account)
+            ) -> bool;
+            /// Advance the simulated chain by `blocks`
+            /// returns the new block number
+            Mock::advance => fn client_mock_advance(blocks: u32 = blocks) -> u32;
+            /// Get the events of the simulated calls so far, oldest first
+            /// Returns JSON encoded `Vec<EventInformation>` as string
+            Mock::events => fn client_mock_events() -> JSON<Vec<EventInformation>>;
+            /// Play the seed scenario again from the start
+            Mock::reset => fn client_mock_reset() -> bool;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "mock"))]
+#[macro_export]
+macro_rules! impl_mock_ffi {
+    () => {};
+}
+
+#[doc(hidden)]
+#[cfg(feature = "sync")]
+#[macro_export]
+macro_rules! impl_sync_ffi {
+    () => {
+        // renamed so it does not shadow the `Sync` marker trait
+        use $crate::sync::Sync as BackgroundSync;
+        gen_ffi! {
+            /// Start refreshing the snapshot of the current account in the
+            /// background every `interval_secs`, listing the open bounties
+            /// of at least `min`. Restarts the sync if it is running.
+            /// The sync pauses while the keystore is locked.
+            BackgroundSync::start => fn client_sync_start(
+                interval_secs: u64 = interval_secs,
+                min: *const raw::c_char = cstr!(min)
+            ) -> bool;
+            /// Stop the background sync, the last snapshot is kept
+            BackgroundSync::stop => fn client_sync_stop() -> bool;
+            /// Get the last snapshot without waiting on the chain
+            /// Returns JSON encoded `SyncSnapshot` as string, null until
+            /// the first sync completed
+            BackgroundSync::snapshot => fn client_sync_snapshot() -> JSON<SyncSnapshot>;
+            /// Sync `account`, like a watch-only one, instead of the current
+            /// account, null syncs the current account again. A followed
+            /// account is synced while the keystore is locked.
+            BackgroundSync::follow => fn client_sync_follow(
+                account: *const raw::c_char = cstr!(account, allow_null)
+            ) -> bool;
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "sync"))]
+#[macro_export]
+macro_rules! impl_sync_ffi {
+    () => {};
+}
+
 /// Generate the FFI for the provided runtime
 ///
+/// Failures are returned as a JSON encoded `error::FfiError` envelope
+/// `{ "code": "...", "message": "...", "details": {...} }` and functions
+/// returning JSON wrap their result as `{ "ok": ... }`. Enable the
+/// `legacy-errors` feature to get the plain error messages and unwrapped
+/// results instead.
+///
 /// ### Example
 /// ```
 /// use test_client::Client;
@@ -150,6 +687,16 @@ macro_rules! impl_bounty_ffi {
 macro_rules! impl_ffi {
     () => {
         $crate::impl_bounty_ffi!();
+        $crate::impl_vote_ffi!();
+        $crate::impl_org_ffi!();
+        $crate::impl_recovery_ffi!();
+        $crate::impl_backup_ffi!();
+        $crate::impl_metrics_ffi!();
+        $crate::impl_log_ffi!();
+        $crate::impl_chain_ffi!();
+        $crate::impl_link_ffi!();
+        $crate::impl_sync_ffi!();
+        $crate::impl_mock_ffi!();
         $crate::impl_bounty_key_ffi!();
         $crate::impl_bounty_wallet_ffi!();
     };
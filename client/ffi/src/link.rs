@@ -0,0 +1,153 @@
+//! Shareable links opening a bounty or a vote in the app.
+//!
+//! A link reads `sunshine://<kind>/<id>?chain=<genesis>` with the first
+//! `GENESIS_PREFIX_LEN` bytes of the genesis hash hex encoded, so a link
+//! shared from another network is told apart before anything is looked up
+//! and the app can ask the user to switch networks.
+use crate::{
+    error::{
+        ErrorCode,
+        FfiError,
+    },
+    qr::decode_hex,
+};
+
+const SCHEME: &str = "sunshine://";
+const BOUNTY: &str = "bounty";
+const VOTE: &str = "vote";
+
+/// The bytes of the genesis hash a link carries, enough to tell the
+/// networks apart while keeping links short
+pub const GENESIS_PREFIX_LEN: usize = 8;
+
+/// What a link opens
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    Bounty,
+    Vote,
+}
+
+impl LinkTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkTarget::Bounty => BOUNTY,
+            LinkTarget::Vote => VOTE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub target: LinkTarget,
+    pub id: u64,
+}
+
+impl Link {
+    pub fn encode(&self, genesis: &[u8]) -> String {
+        format!(
+            "{}{}/{}?chain={}",
+            SCHEME,
+            self.target.as_str(),
+            self.id,
+            hex::encode(prefix(genesis)),
+        )
+    }
+
+    /// Parses a link, failing with `LinkChainMismatch` if it was made on
+    /// another chain than the one with `genesis`
+    pub fn decode(uri: &str, genesis: &[u8]) -> Result<Self, FfiError> {
+        let rest = uri
+            .trim()
+            .strip_prefix(SCHEME)
+            .ok_or_else(|| invalid("scheme"))?;
+        let mut halves = rest.splitn(2, '?');
+        let path = halves.next().unwrap_or_default();
+        let query = halves.next().ok_or_else(|| invalid("chain"))?;
+        let mut parts = path.trim_end_matches('/').splitn(2, '/');
+        let target = match parts.next() {
+            Some(BOUNTY) => LinkTarget::Bounty,
+            Some(VOTE) => LinkTarget::Vote,
+            _ => return Err(invalid("kind")),
+        };
+        let id = parts
+            .next()
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| invalid("id"))?;
+        let theirs = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("chain="))
+            .and_then(|chain| decode_hex(chain).ok())
+            .filter(|chain| !chain.is_empty())
+            .ok_or_else(|| invalid("chain"))?;
+        if theirs != prefix(genesis) {
+            return Err(FfiError::new(
+                ErrorCode::LinkChainMismatch,
+                format!(
+                    "Link is for chain 0x{} but the client is on 0x{}",
+                    hex::encode(&theirs),
+                    hex::encode(prefix(genesis)),
+                ),
+            ))
+        }
+        Ok(Self { target, id })
+    }
+}
+
+fn prefix(genesis: &[u8]) -> &[u8] {
+    &genesis[..genesis.len().min(GENESIS_PREFIX_LEN)]
+}
+
+fn invalid(what: &str) -> FfiError {
+    FfiError::new(ErrorCode::InvalidLink, format!("Invalid link {}", what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENESIS: [u8; 32] = [7; 32];
+
+    #[test]
+    fn links_round_trip() {
+        let bounty = Link {
+            target: LinkTarget::Bounty,
+            id: 42,
+        };
+        let uri = bounty.encode(&GENESIS);
+        assert_eq!(uri, "sunshine://bounty/42?chain=0707070707070707");
+        assert_eq!(Link::decode(&uri, &GENESIS).unwrap(), bounty);
+        let vote = Link {
+            target: LinkTarget::Vote,
+            id: 7,
+        };
+        let uri = vote.encode(&GENESIS);
+        assert_eq!(Link::decode(&uri, &GENESIS).unwrap(), vote);
+        // apps may append their own parameters
+        let uri = "sunshine://vote/7/?ref=chat&chain=0707070707070707";
+        assert_eq!(Link::decode(uri, &GENESIS).unwrap(), vote);
+    }
+
+    #[test]
+    fn links_of_other_chains_are_told_apart() {
+        let uri = "sunshine://bounty/42?chain=0808080808080808";
+        let err = Link::decode(uri, &GENESIS).unwrap_err();
+        assert_eq!(err.code, ErrorCode::LinkChainMismatch);
+    }
+
+    #[test]
+    fn malformed_links_are_rejected() {
+        for uri in &[
+            "",
+            "https://bounty/42?chain=0707070707070707",
+            "sunshine://bounty/42",
+            "sunshine://org/42?chain=0707070707070707",
+            "sunshine://bounty/forty?chain=0707070707070707",
+            "sunshine://bounty?chain=0707070707070707",
+            "sunshine://vote/7?chain=zz",
+            "sunshine://vote/7?chain=",
+        ] {
+            let err = Link::decode(uri, &GENESIS).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidLink, "{}", uri);
+        }
+    }
+}
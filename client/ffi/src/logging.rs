@@ -0,0 +1,232 @@
+//! Logging of the ffi, with a verbosity and a sink the host app controls
+//!
+//! The `error!`, `warn!`, `info!` and `debug!` macros of this module take
+//! the place of the `ffi_utils::log` ones. A line at or above the level set
+//! with `set_level` is kept in a ring buffer for `recent`, posted to the
+//! port set with `set_sink` and passed on to the device log. Unless the
+//! level is `debug` or `trace`, account ids, cids and hashes are cut to
+//! their first characters, so that the logs an app attaches to a support
+//! ticket do not identify its user.
+use crate::ffi_utils::log::{
+    self,
+    Level,
+    LevelFilter,
+};
+use allo_isolate::Isolate;
+use once_cell::sync::Lazy;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::Mutex,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+/// How many lines `recent` can return
+const RECENT_LOGS: usize = 1000;
+
+/// Ids shorter than this are left alone, ss58 addresses, cids and hex
+/// hashes are all longer
+const MIN_REDACTED_LEN: usize = 32;
+
+/// How much of a redacted id is kept
+const REDACTED_PREFIX_LEN: usize = 4;
+
+static STATE: Lazy<Mutex<LogState>> = Lazy::new(Default::default);
+
+struct LogState {
+    level: LevelFilter,
+    sink: Option<i64>,
+    recent: VecDeque<String>,
+}
+
+impl Default for LogState {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            sink: None,
+            recent: VecDeque::with_capacity(RECENT_LOGS),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownLogLevel(pub String);
+
+impl fmt::Display for UnknownLogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown log level {}, expected one of off, error, warn, info, debug, trace",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownLogLevel {}
+
+/// Parses a level name, case insensitive
+pub fn parse_level(level: &str) -> Result<LevelFilter, UnknownLogLevel> {
+    level
+        .parse()
+        .map_err(|_| UnknownLogLevel(level.to_string()))
+}
+
+/// Sets the level of every following line, the device log included
+pub fn set_level(level: LevelFilter) {
+    STATE.lock().unwrap().level = level;
+    log::set_max_level(level);
+}
+
+/// Posts every following line to the port, or stops posting with `None`
+pub fn set_sink(port: Option<i64>) {
+    STATE.lock().unwrap().sink = port;
+}
+
+/// The last `n` lines, oldest first
+pub fn recent(n: usize) -> Vec<String> {
+    let state = STATE.lock().unwrap();
+    let skip = state.recent.len().saturating_sub(n);
+    state.recent.iter().skip(skip).cloned().collect()
+}
+
+#[doc(hidden)]
+pub fn enabled(level: Level) -> bool {
+    level <= STATE.lock().unwrap().level
+}
+
+#[doc(hidden)]
+pub fn record(level: Level, target: &str, message: String) {
+    let (message, line, sink) = {
+        let mut state = STATE.lock().unwrap();
+        let message = if state.level < LevelFilter::Debug {
+            redact(&message)
+        } else {
+            message
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let line = format!(
+            "{}.{:03} {} {}: {}",
+            now.as_secs(),
+            now.subsec_millis(),
+            level,
+            target,
+            message
+        );
+        if state.recent.len() == RECENT_LOGS {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(line.clone());
+        (message, line, state.sink)
+    };
+    if let Some(port) = sink {
+        Isolate::new(port).post(line);
+    }
+    log::log!(target: target, level, "{}", message);
+}
+
+/// Cuts every word of at least `MIN_REDACTED_LEN` letters and digits to
+/// its first characters, which still tell an ss58 address (`5...`) from a
+/// cid (`Qm...`, `bafy...`) or a hash (`0x...`)
+pub fn redact(message: &str) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut word = String::new();
+    for c in message.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+        } else {
+            push_word(&mut redacted, &mut word);
+            redacted.push(c);
+        }
+    }
+    push_word(&mut redacted, &mut word);
+    redacted
+}
+
+fn push_word(redacted: &mut String, word: &mut String) {
+    if word.len() >= MIN_REDACTED_LEN {
+        redacted.push_str(&word[..REDACTED_PREFIX_LEN]);
+        redacted.push('…');
+    } else {
+        redacted.push_str(word);
+    }
+    word.clear();
+}
+
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::logging::enabled($level) {
+            $crate::logging::record($level, module_path!(), format!($($arg)+));
+        }
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => {
+        log_at!($crate::ffi_utils::log::Level::Error, $($arg)+)
+    };
+}
+
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        log_at!($crate::ffi_utils::log::Level::Warn, $($arg)+)
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => {
+        log_at!($crate::ffi_utils::log::Level::Info, $($arg)+)
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        log_at!($crate::ffi_utils::log::Level::Debug, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_and_cids_are_redacted() {
+        let message =
+            "Vouching for 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY \
+             with QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG in 0x8a1f";
+        assert_eq!(redact(message), "Vouching for 5Grw… with QmYw… in 0x8a1f");
+    }
+
+    #[test]
+    fn levels_parse_case_insensitive() {
+        assert_eq!(parse_level("DEBUG").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level("off").unwrap(), LevelFilter::Off);
+        assert!(parse_level("verbose").is_err());
+    }
+
+    #[test]
+    fn recent_logs_are_redacted_below_debug() {
+        set_level(LevelFilter::Info);
+        info!(
+            "Claiming {}",
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        debug!("not kept at info");
+        assert!(recent(1)[0].ends_with("Claiming 5Grw…"));
+        set_level(LevelFilter::Debug);
+        debug!(
+            "Claiming {}",
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        let lines = recent(2);
+        assert!(lines[0].ends_with("Claiming 5Grw…"));
+        assert!(lines[1].ends_with(
+            "Claiming 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        ));
+        set_level(LevelFilter::Info);
+    }
+}
@@ -0,0 +1,292 @@
+//! Answers from the simulated chain of the client, for apps developed
+//! without a node
+//!
+//! With the `mock` feature, setting `SUNSHINE_MOCK` to anything but `0`
+//! before the app starts makes the calls of the core flows answer from a
+//! `MockChain` playing the seed scenario instead of the node: getting,
+//! posting, funding, submitting for and approving bounties, the progress of
+//! votes and the members of orgs. Failures keep their codes, an approval by
+//! an account which is not an admin is still `BOUNTY_REJECTED` with the
+//! module error in its details.
+use crate::dto::{
+    Balance,
+    BountyInformation,
+    BountySubmissionInformation,
+    EventInformation,
+    MemberInformation,
+    Signal,
+    VoteProgressInformation,
+    SCHEMA_VERSION,
+};
+use once_cell::sync::Lazy;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+};
+use sunshine_bounty_client::{
+    mock::{
+        MockBounty,
+        MockChain,
+        MockSubmission,
+    },
+    GithubIssue,
+};
+use sunshine_client_utils::Result;
+
+/// The environment variable which switches the ffi to the simulated chain
+pub const MOCK_ENV: &str = "SUNSHINE_MOCK";
+
+static ENABLED: Lazy<bool> = Lazy::new(
+    || matches!(std::env::var(MOCK_ENV), Ok(v) if !v.is_empty() && v != "0"),
+);
+
+static CHAIN: Lazy<Mutex<MockChain>> =
+    Lazy::new(|| Mutex::new(MockChain::seeded()));
+
+/// Whether the calls answer from the simulated chain, read once
+pub fn enabled() -> bool {
+    *ENABLED
+}
+
+fn chain() -> MutexGuard<'static, MockChain> {
+    // the calls check before changing anything, a panic leaves it whole
+    CHAIN
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Plays the seed scenario again from the start
+pub fn reset() {
+    *chain() = MockChain::seeded();
+}
+
+pub fn set_signer(account: &str) {
+    chain().set_signer(account);
+}
+
+/// Returns the new block number
+pub fn advance(blocks: u32) -> u32 {
+    let mut chain = chain();
+    chain.advance(blocks);
+    chain.block()
+}
+
+pub fn events() -> Vec<EventInformation> {
+    chain()
+        .events()
+        .iter()
+        .map(|event| {
+            EventInformation {
+                schema_version: SCHEMA_VERSION,
+                module: event.module.to_string(),
+                variant: event.variant.to_string(),
+                data: event.data.clone(),
+            }
+        })
+        .collect()
+}
+
+fn github_issue(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u64,
+) -> GithubIssue {
+    GithubIssue {
+        issue_number,
+        repo_owner: repo_owner.to_string(),
+        repo_name: repo_name.to_string(),
+    }
+}
+
+fn bounty_information(
+    chain: &MockChain,
+    bounty: &MockBounty,
+) -> BountyInformation {
+    let millis = |block: Option<u32>| block.map(|b| chain.millis_at(b));
+    BountyInformation {
+        schema_version: SCHEMA_VERSION,
+        id: bounty.id.to_string(),
+        repo_owner: bounty.issue.repo_owner.clone(),
+        repo_name: bounty.issue.repo_name.clone(),
+        issue_number: bounty.issue.issue_number,
+        depositer: bounty.depositer.clone(),
+        total: Balance(bounty.total),
+        admins: bounty.admins.clone(),
+        asset_id: None,
+        symbol: String::new(),
+        decimals: 0,
+        tags: vec![],
+        posted_at: bounty.posted_at.into(),
+        first_submission_at: bounty.first_submission_at.map(Into::into),
+        resolved_at: bounty.resolved_at.map(Into::into),
+        posted_at_ms: millis(Some(bounty.posted_at)),
+        first_submission_at_ms: millis(bounty.first_submission_at),
+        resolved_at_ms: millis(bounty.resolved_at),
+        timestamped: true,
+        org: None,
+        matching_org: None,
+        matching_ratio_ppm: None,
+        matching_remaining: None,
+    }
+}
+
+fn submission_information(
+    chain: &MockChain,
+    submission: &MockSubmission,
+) -> BountySubmissionInformation {
+    let submitter_orgs = chain
+        .orgs()
+        .filter(|org| org.members.contains_key(&submission.submitter))
+        .map(|org| org.id.to_string())
+        .collect();
+    BountySubmissionInformation {
+        schema_version: SCHEMA_VERSION,
+        id: submission.id.to_string(),
+        repo_owner: submission.issue.repo_owner.clone(),
+        repo_name: submission.issue.repo_name.clone(),
+        issue_number: submission.issue.issue_number,
+        bounty_id: submission.bounty_id.to_string(),
+        submitter: submission.submitter.clone(),
+        amount: Balance(submission.amount),
+        // approved submissions are removed
+        awaiting_review: true,
+        approved: false,
+        reviewer: None,
+        submitted_at: submission.submitted_at.into(),
+        submitted_at_ms: Some(chain.millis_at(submission.submitted_at)),
+        timestamped: true,
+        submitter_orgs,
+        handle_match: None,
+        split: vec![],
+        attachments: vec![],
+        // the mock chain does not time submissions out
+        times_out_at: None,
+        nearing_timeout: false,
+        timed_out: false,
+    }
+}
+
+pub fn bounty(bounty_id: &str) -> Result<BountyInformation> {
+    let chain = chain();
+    let bounty = chain.bounty(bounty_id.parse()?)?;
+    Ok(bounty_information(&chain, bounty))
+}
+
+/// The mock has no tags, a tag lists no bounties
+pub fn open_bounties(
+    min: &str,
+    tag: Option<&str>,
+) -> Result<Vec<BountyInformation>> {
+    let chain = chain();
+    if tag.is_some() {
+        return Ok(vec![])
+    }
+    Ok(chain
+        .open_bounties(min.parse()?)
+        .into_iter()
+        .map(|bounty| bounty_information(&chain, bounty))
+        .collect())
+}
+
+pub fn post(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u64,
+    amount: &str,
+    asset_id: Option<&str>,
+) -> Result<u64> {
+    let issue = github_issue(repo_owner, repo_name, issue_number);
+    let asset_id = match asset_id {
+        Some(id) => Some(id.parse()?),
+        None => None,
+    };
+    chain().post_bounty(issue, amount.parse()?, asset_id)
+}
+
+pub fn contribute(bounty_id: &str, amount: &str) -> Result<u128> {
+    chain().contribute_to_bounty(bounty_id.parse()?, amount.parse()?)
+}
+
+pub fn submit(
+    bounty_id: &str,
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u64,
+    amount: &str,
+) -> Result<u64> {
+    let issue = github_issue(repo_owner, repo_name, issue_number);
+    chain().submit_for_bounty(bounty_id.parse()?, issue, amount.parse()?)
+}
+
+pub fn approve(submission_id: &str) -> Result<u128> {
+    chain().approve_bounty_submission(submission_id.parse()?)
+}
+
+pub fn submission(submission_id: &str) -> Result<BountySubmissionInformation> {
+    let chain = chain();
+    let submission = chain.submission(submission_id.parse()?)?;
+    Ok(submission_information(&chain, submission))
+}
+
+pub fn vote_status(vote_id: &str) -> Result<VoteProgressInformation> {
+    let progress = chain().vote_progress(vote_id.parse()?)?;
+    let signal = |s: u64| Signal(s.into());
+    Ok(VoteProgressInformation {
+        schema_version: SCHEMA_VERSION,
+        id: vote_id.to_string(),
+        in_favor: signal(progress.in_favor()),
+        against: signal(progress.against()),
+        abstain: signal(progress.abstain()),
+        turnout: signal(progress.turnout()),
+        threshold_in_favor: signal(progress.threshold_in_favor()),
+        threshold_against: progress.threshold_against().map(signal),
+        remaining_to_pass: progress.remaining_to_pass().map(signal),
+        remaining_to_reject: progress.remaining_to_reject().map(signal),
+        outcome: format!("{:?}", progress.outcome()),
+    })
+}
+
+/// Members of an org which does not exist are none, like on chain
+pub fn org_members(org_id: &str) -> Result<Vec<MemberInformation>> {
+    let chain = chain();
+    let org = match chain.org(org_id.parse()?) {
+        Some(org) => org,
+        None => return Ok(vec![]),
+    };
+    Ok(org
+        .members
+        .iter()
+        .map(|(account, shares)| {
+            MemberInformation {
+                schema_version: SCHEMA_VERSION,
+                org_id: org.id.to_string(),
+                account: account.clone(),
+                shares: *shares,
+                name: None,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{
+        ErrorCode,
+        FfiError,
+    };
+
+    #[test]
+    fn rejected_calls_keep_their_codes() {
+        reset();
+        set_signer(sunshine_bounty_client::mock::BOB);
+        let err = FfiError::from(approve("1").unwrap_err());
+        assert_eq!(err.code, ErrorCode::BountyRejected);
+        set_signer(sunshine_bounty_client::mock::ALICE);
+        assert_eq!(approve("1").unwrap(), 300);
+        assert_eq!(bounty("2").unwrap().resolved_at, Some(1));
+        assert_eq!(vote_status("1").unwrap().outcome, "Voting");
+        assert_eq!(org_members("1").unwrap().len(), 3);
+        assert_eq!(events().last().unwrap().variant, "BountyPaymentExecuted");
+    }
+}
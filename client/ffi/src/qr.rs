@@ -0,0 +1,266 @@
+//! Compact text payloads meant to be shown and scanned as QR codes.
+//!
+//! Every payload reads `sunshine:<version>:<kind>:<genesis>:...` with the
+//! genesis hash hex encoded, so a code made for another chain is rejected
+//! before anything is pre-filled or signed. Free text, like a memo, is
+//! always the last field and may itself contain `:`.
+use crate::error::{
+    ErrorCode,
+    FfiError,
+};
+
+/// The version of the payload format, bumped on every breaking change.
+pub const QR_VERSION: u32 = 1;
+
+const SCHEME: &str = "sunshine";
+const PAY: &str = "pay";
+const SIGN: &str = "sign";
+const SIGNED: &str = "signed";
+
+/// Asks the scanning wallet to pay `account`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub genesis: Vec<u8>,
+    pub account: String,
+    pub amount: Option<u128>,
+    pub memo: Option<String>,
+}
+
+/// Asks the air-gapped device holding the key of `account` to sign `call`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningRequest {
+    pub genesis: Vec<u8>,
+    pub account: String,
+    pub call: Vec<u8>,
+}
+
+/// A `SigningRequest` together with the signature made on the air-gapped
+/// device, ready to be submitted by the online one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedRequest {
+    pub request: SigningRequest,
+    pub signature: Vec<u8>,
+}
+
+impl PaymentRequest {
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            SCHEME,
+            QR_VERSION,
+            PAY,
+            hex::encode(&self.genesis),
+            self.account,
+            self.amount.map(|a| a.to_string()).unwrap_or_default(),
+            self.memo.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Parses a payload, failing if it was made for another chain than
+    /// the one with `genesis`
+    pub fn decode(payload: &str, genesis: &[u8]) -> Result<Self, FfiError> {
+        let fields = fields(payload, PAY, 3, genesis)?;
+        let amount = match fields[1] {
+            "" => None,
+            amount => Some(amount.parse().map_err(|_| invalid("amount"))?),
+        };
+        let memo = match fields[2] {
+            "" => None,
+            memo => Some(memo.to_string()),
+        };
+        Ok(Self {
+            genesis: genesis.to_vec(),
+            account: fields[0].to_string(),
+            amount,
+            memo,
+        })
+    }
+}
+
+impl SigningRequest {
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}",
+            SCHEME,
+            QR_VERSION,
+            SIGN,
+            hex::encode(&self.genesis),
+            self.account,
+            hex::encode(&self.call),
+        )
+    }
+
+    pub fn decode(payload: &str, genesis: &[u8]) -> Result<Self, FfiError> {
+        let fields = fields(payload, SIGN, 2, genesis)?;
+        Ok(Self {
+            genesis: genesis.to_vec(),
+            account: fields[0].to_string(),
+            call: decode_hex(fields[1]).map_err(|_| invalid("call"))?,
+        })
+    }
+}
+
+impl SignedRequest {
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            SCHEME,
+            QR_VERSION,
+            SIGNED,
+            hex::encode(&self.request.genesis),
+            self.request.account,
+            hex::encode(&self.request.call),
+            hex::encode(&self.signature),
+        )
+    }
+
+    pub fn decode(payload: &str, genesis: &[u8]) -> Result<Self, FfiError> {
+        let fields = fields(payload, SIGNED, 3, genesis)?;
+        Ok(Self {
+            request: SigningRequest {
+                genesis: genesis.to_vec(),
+                account: fields[0].to_string(),
+                call: decode_hex(fields[1]).map_err(|_| invalid("call"))?,
+            },
+            signature: decode_hex(fields[2])
+                .map_err(|_| invalid("signature"))?,
+        })
+    }
+}
+
+/// Decodes hex with or without a leading `0x`
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(hex.trim_start_matches("0x"))
+}
+
+/// Checks the header of a payload of `kind` and returns the `count` fields
+/// following the genesis hash, the last one taking the rest of the payload
+fn fields<'a>(
+    payload: &'a str,
+    kind: &str,
+    count: usize,
+    genesis: &[u8],
+) -> Result<Vec<&'a str>, FfiError> {
+    let mut parts = payload.trim().splitn(count + 4, ':');
+    if parts.next() != Some(SCHEME) {
+        return Err(invalid("scheme"))
+    }
+    match parts.next().map(str::parse::<u32>) {
+        Some(Ok(QR_VERSION)) => {}
+        Some(Ok(version)) => {
+            return Err(FfiError::new(
+                ErrorCode::InvalidQrPayload,
+                format!("Unsupported payload version {}", version),
+            ))
+        }
+        _ => return Err(invalid("version")),
+    }
+    if parts.next() != Some(kind) {
+        return Err(invalid("kind"))
+    }
+    let theirs = parts
+        .next()
+        .and_then(|g| decode_hex(g).ok())
+        .ok_or_else(|| invalid("genesis hash"))?;
+    if theirs != genesis {
+        return Err(FfiError::new(
+            ErrorCode::QrChainMismatch,
+            format!(
+                "Payload is for chain 0x{} but the client is on 0x{}",
+                hex::encode(&theirs),
+                hex::encode(genesis),
+            ),
+        ))
+    }
+    let fields: Vec<_> = parts.collect();
+    if fields.len() != count {
+        return Err(invalid("field count"))
+    }
+    Ok(fields)
+}
+
+fn invalid(what: &str) -> FfiError {
+    FfiError::new(
+        ErrorCode::InvalidQrPayload,
+        format!("Invalid payload {}", what),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENESIS: [u8; 4] = [1, 2, 3, 4];
+
+    #[test]
+    fn payment_requests_round_trip() {
+        let request = PaymentRequest {
+            genesis: GENESIS.to_vec(),
+            account: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+                .to_string(),
+            amount: Some(u128::MAX),
+            memo: Some("rent: march".to_string()),
+        };
+        let payload = request.encode();
+        assert_eq!(
+            PaymentRequest::decode(&payload, &GENESIS).unwrap(),
+            request
+        );
+
+        let bare = PaymentRequest {
+            genesis: GENESIS.to_vec(),
+            account: "alice".to_string(),
+            amount: None,
+            memo: None,
+        };
+        assert_eq!(bare.encode(), "sunshine:1:pay:01020304:alice::");
+        assert_eq!(
+            PaymentRequest::decode(&bare.encode(), &GENESIS).unwrap(),
+            bare
+        );
+    }
+
+    #[test]
+    fn signatures_round_trip() {
+        let request = SigningRequest {
+            genesis: GENESIS.to_vec(),
+            account: "alice".to_string(),
+            call: vec![6, 0, 42],
+        };
+        assert_eq!(request.encode(), "sunshine:1:sign:01020304:alice:06002a");
+        assert_eq!(
+            SigningRequest::decode(&request.encode(), &GENESIS).unwrap(),
+            request
+        );
+        let signed = SignedRequest {
+            request,
+            signature: vec![7; 64],
+        };
+        assert_eq!(
+            SignedRequest::decode(&signed.encode(), &GENESIS).unwrap(),
+            signed
+        );
+    }
+
+    #[test]
+    fn other_chains_are_rejected() {
+        let payload = "sunshine:1:pay:ffffffff:alice:10:";
+        let err = PaymentRequest::decode(payload, &GENESIS).unwrap_err();
+        assert_eq!(err.code, ErrorCode::QrChainMismatch);
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        for payload in &[
+            "",
+            "bitcoin:1:pay:01020304:alice::",
+            "sunshine:2:pay:01020304:alice::",
+            "sunshine:1:sign:01020304:alice::",
+            "sunshine:1:pay:01020304:alice:ten:",
+            "sunshine:1:pay:01020304:alice",
+        ] {
+            let err = PaymentRequest::decode(payload, &GENESIS).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidQrPayload, "{}", payload);
+        }
+    }
+}
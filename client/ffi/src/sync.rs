@@ -0,0 +1,524 @@
+//! Background sync of the signer's state
+//!
+//! `Sync::start` spawns a task on the ffi runtime which keeps a
+//! `SyncSnapshot` of the signer's balance, pending votes, bounties,
+//! submissions and contributions and of the open bounties up to date. The
+//! task follows the event subscription and, once per interval, refreshes
+//! only the sections touched by the events since the last refresh. It pauses
+//! while the keystore is locked and exits once `Sync::stop` is called, so
+//! `Sync::snapshot` answers from memory without waiting on the chain.
+//!
+//! `Sync::follow` makes the task keep the snapshot of another account, like
+//! a watch-only one, instead of the signer's. A followed account is synced
+//! even while the keystore is locked.
+use crate::{
+    dto::{
+        Balance,
+        ContributionInformation,
+        SyncSnapshot,
+        VoteProgressInformation,
+        SCHEMA_VERSION,
+    },
+    error::{
+        reply,
+        reply_json,
+        ErrorCode,
+        ResultExt,
+    },
+    ffi::{
+        progress_information,
+        Bounty,
+    },
+    ffi_utils::async_std::{
+        future::timeout,
+        sync::RwLock,
+        task,
+    },
+};
+use libipld::{
+    cache::Cache,
+    cbor::DagCborCodec,
+};
+use once_cell::sync::Lazy;
+use std::{
+    fmt::{
+        Debug,
+        Display,
+    },
+    marker::{
+        self,
+        PhantomData,
+    },
+    sync::Mutex,
+    time::{
+        Duration,
+        Instant,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+use substrate_subxt::{
+    balances::{
+        AccountData,
+        Balances,
+    },
+    sp_core::crypto::Ss58Codec,
+    system::{
+        AccountStoreExt,
+        System,
+    },
+    EventSubscription,
+};
+use sunshine_bounty_client::{
+    bounty::{
+        Bounty as BountyTrait,
+        BountyClient,
+        EvidenceClient,
+    },
+    chain::{
+        ChainConstantsExt,
+        ChainPropertiesExt,
+    },
+    org::{
+        Org as OrgTrait,
+        OrgClient,
+    },
+    vote::{
+        Vote as VoteTrait,
+        VoteClient,
+    },
+    GithubIssue,
+    GithubSubmission,
+};
+use sunshine_client_utils::{
+    crypto::ss58::Ss58,
+    Client,
+    Node,
+    OffchainConfig,
+    Result,
+};
+
+/// The sections of the snapshot to refresh, see `dirty_sections`
+const BALANCE: u8 = 1;
+const VOTES: u8 = 1 << 1;
+const BOUNTIES: u8 = 1 << 2;
+const ALL: u8 = BALANCE | VOTES | BOUNTIES;
+
+#[derive(Default)]
+struct SyncState {
+    /// Bumped by every start and stop, a task only writes while its
+    /// generation is the current one
+    generation: u64,
+    snapshot: Option<serde_json::Value>,
+    /// The ss58 address synced instead of the signer's
+    followed: Option<String>,
+}
+
+static SYNC: Lazy<Mutex<SyncState>> = Lazy::new(Default::default);
+
+#[derive(Clone, Debug)]
+pub struct Sync<'a, C, N>
+where
+    C: BountyClient<N> + VoteClient<N> + Send + marker::Sync,
+    N: Node,
+    N::Runtime: BountyTrait + VoteTrait,
+{
+    client: &'a RwLock<C>,
+    _runtime: PhantomData<N>,
+}
+
+impl<'a, C, N> Sync<'a, C, N>
+where
+    C: BountyClient<N> + VoteClient<N> + Send + marker::Sync,
+    N: Node,
+    N::Runtime: BountyTrait + VoteTrait,
+{
+    pub fn new(client: &'a RwLock<C>) -> Self {
+        Self {
+            client,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+impl<C, N> Sync<'static, C, N>
+where
+    C: BountyClient<N>
+        + OrgClient<N>
+        + VoteClient<N>
+        + EvidenceClient<N>
+        + Send
+        + marker::Sync
+        + 'static,
+    N: Node,
+    N::Runtime: BountyTrait<IpfsReference = sunshine_codec::Cid>
+        + VoteTrait
+        + Balances
+        + Debug,
+    N::Runtime:
+        System<AccountData = AccountData<<N::Runtime as Balances>::Balance>>,
+    C::OffchainClient: Cache<OffchainConfig<N>, DagCborCodec, GithubIssue>,
+    <N::Runtime as System>::AccountId:
+        Ss58Codec + Into<<N::Runtime as System>::Address>,
+    <N::Runtime as BountyTrait>::BountyId: From<u64> + Into<u64> + Display,
+    <N::Runtime as BountyTrait>::SubmissionId: From<u64> + Into<u64> + Display,
+    <N::Runtime as BountyTrait>::BountyPost: From<GithubIssue> + Debug,
+    <N::Runtime as BountyTrait>::BountySubmission:
+        From<GithubIssue> + From<GithubSubmission> + Debug,
+    <N::Runtime as BountyTrait>::AssetId: From<u32> + Display,
+    <N::Runtime as Balances>::Balance: Into<u128> + From<u64>,
+    <N::Runtime as OrgTrait>::OrgId: Display,
+    <N::Runtime as VoteTrait>::VoteId: Display,
+    <N::Runtime as VoteTrait>::Signal: Into<u128>,
+{
+    /// Restarts the task with the new interval if it is already running
+    pub async fn start(&self, interval_secs: u64, min: &str) -> Result<bool> {
+        reply(async move {
+            let min = min.parse::<u64>()?.into();
+            let interval = Duration::from_secs(interval_secs.max(1));
+            let generation = {
+                let mut state = SYNC.lock().unwrap();
+                state.generation += 1;
+                state.generation
+            };
+            info!("Starting the sync every {}s", interval.as_secs());
+            let sync = Self::new(self.client);
+            task::spawn(
+                async move { sync.run(generation, interval, min).await },
+            );
+            Ok(true)
+        })
+        .await
+    }
+
+    /// The last snapshot is kept, apps may still show it while stopped
+    pub async fn stop(&self) -> Result<bool> {
+        reply(async move {
+            SYNC.lock().unwrap().generation += 1;
+            info!("Stopped the sync");
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Syncs `account` instead of the signer from the next interval on,
+    /// None syncs the signer again
+    pub async fn follow(&self, account: Option<&str>) -> Result<bool> {
+        reply(async move {
+            let followed = match account {
+                Some(account) => {
+                    let account = account
+                        .parse::<Ss58<N::Runtime>>()
+                        .code(ErrorCode::InvalidAccountId)?;
+                    let properties =
+                        self.client.read().await.chain_properties();
+                    Some(properties.ss58(&account.0))
+                }
+                None => None,
+            };
+            info!("Syncing {}", followed.as_deref().unwrap_or("the signer"));
+            SYNC.lock().unwrap().followed = followed;
+            Ok(true)
+        })
+        .await
+    }
+
+    /// The last `SyncSnapshot`, null until the first sync completed
+    pub async fn snapshot(&self) -> Result<String> {
+        reply_json(async move { Ok(SYNC.lock().unwrap().snapshot.clone()) })
+            .await
+    }
+
+    async fn run(
+        &self,
+        generation: u64,
+        interval: Duration,
+        min: <N::Runtime as Balances>::Balance,
+    ) {
+        // the subscription only needs the chain client, holding the lock on
+        // the client while waiting for events would block other calls
+        let chain = self.client.read().await.chain_client().clone();
+        let mut events = None;
+        let mut snapshot: Option<SyncSnapshot> = None;
+        let mut dirty = ALL;
+        while is_current(generation) {
+            let deadline = Instant::now() + interval;
+            if events.is_none() {
+                match chain.subscribe_events().await {
+                    Ok(sub) => {
+                        let decoder = chain.events_decoder();
+                        events = Some(EventSubscription::new(sub, decoder));
+                        // events may have been missed while unsubscribed
+                        dirty = ALL;
+                    }
+                    Err(e) => {
+                        warn!("I can't subscribe to events. Retrying...");
+                        error!("{:?}", e);
+                    }
+                }
+            }
+            let followed = SYNC.lock().unwrap().followed.clone();
+            let synced = match followed {
+                // checked when it was followed
+                Some(followed) => {
+                    followed
+                        .parse::<Ss58<N::Runtime>>()
+                        .map(|account| account.0)
+                        .map_err(drop)
+                }
+                None => {
+                    self.client
+                        .read()
+                        .await
+                        .signer()
+                        .map(|s| s.account_id().clone())
+                        .map_err(drop)
+                }
+            };
+            match synced {
+                Ok(synced) => {
+                    let account = self
+                        .client
+                        .read()
+                        .await
+                        .chain_properties()
+                        .ss58(&synced);
+                    // the sections of another account are all stale
+                    if snapshot.as_ref().map(|s| &s.account) != Some(&account) {
+                        snapshot = Some(empty_snapshot(account));
+                        dirty = ALL;
+                    }
+                    let current = snapshot.as_mut().expect("set above; qed");
+                    if dirty != 0 {
+                        let failed =
+                            self.refresh(&synced, min, dirty, current).await;
+                        if failed != dirty {
+                            publish(generation, current);
+                        }
+                        dirty = failed;
+                    }
+                }
+                // paused until the client is unlocked
+                Err(_) => info!("The client is locked, skipping the sync"),
+            }
+            while let Some(subscription) = events.as_mut() {
+                let now = Instant::now();
+                if now >= deadline {
+                    break
+                }
+                match timeout(deadline - now, subscription.next()).await {
+                    Ok(Some(Ok(event))) => {
+                        if is_runtime_upgrade(&event.module, &event.variant) {
+                            self.client
+                                .read()
+                                .await
+                                .invalidate_chain_constants();
+                        }
+                        dirty |= dirty_sections(&event.module)
+                    }
+                    // the dispatch errors of failed extrinsics are not events
+                    Ok(Some(Err(substrate_subxt::Error::Runtime(_)))) => {}
+                    Ok(Some(Err(e))) => {
+                        warn!(
+                            "The event subscription failed. Resubscribing..."
+                        );
+                        error!("{:?}", e);
+                        events = None;
+                    }
+                    Ok(None) => events = None,
+                    Err(_) => break,
+                }
+            }
+            let now = Instant::now();
+            if events.is_none() && now < deadline {
+                task::sleep(deadline - now).await;
+            }
+        }
+        info!("The sync task exited");
+    }
+
+    /// Refreshes the dirty sections, returning those which failed so they
+    /// are retried on the next interval
+    async fn refresh(
+        &self,
+        account: &<N::Runtime as System>::AccountId,
+        min: <N::Runtime as Balances>::Balance,
+        dirty: u8,
+        snapshot: &mut SyncSnapshot,
+    ) -> u8 {
+        let mut failed = 0;
+        if dirty & BALANCE != 0 {
+            match self.balance(account).await {
+                Ok(balance) => snapshot.balance = balance,
+                Err(e) => {
+                    error!("{:?}", e);
+                    failed |= BALANCE;
+                }
+            }
+        }
+        if dirty & VOTES != 0 {
+            match self.pending_votes(account).await {
+                Ok(votes) => snapshot.pending_votes = votes,
+                Err(e) => {
+                    error!("{:?}", e);
+                    failed |= VOTES;
+                }
+            }
+        }
+        if dirty & BOUNTIES != 0 {
+            if let Err(e) = self.bounties(account, min, snapshot).await {
+                error!("{:?}", e);
+                failed |= BOUNTIES;
+            }
+        }
+        failed
+    }
+
+    async fn balance(
+        &self,
+        account: &<N::Runtime as System>::AccountId,
+    ) -> Result<Balance> {
+        let client = self.client.read().await;
+        let info = client.chain_client().account(account, None).await?;
+        Ok(Balance(info.data.free.into()))
+    }
+
+    async fn pending_votes(
+        &self,
+        account: &<N::Runtime as System>::AccountId,
+    ) -> Result<Vec<VoteProgressInformation>> {
+        let pending = self.client.read().await.pending_votes(account).await?;
+        Ok(pending
+            .into_iter()
+            .map(|(id, progress)| {
+                progress_information::<N::Runtime>(id, progress)
+            })
+            .collect())
+    }
+
+    /// Lists all bounties, submissions and contributions once and picks
+    /// both the signer's bounties and the open bounties from the same list
+    async fn bounties(
+        &self,
+        account: &<N::Runtime as System>::AccountId,
+        min: <N::Runtime as Balances>::Balance,
+        snapshot: &mut SyncSnapshot,
+    ) -> Result<()> {
+        let bounty = Bounty::new(self.client);
+        let (bounties, submissions, contributions, properties) = {
+            let client = self.client.read().await;
+            (
+                client.open_bounties(0u64.into()).await?.unwrap_or_default(),
+                client
+                    .account_submissions(account.clone())
+                    .await?
+                    .unwrap_or_default(),
+                client
+                    .account_contributions(account.clone())
+                    .await?
+                    .unwrap_or_default(),
+                client.chain_properties(),
+            )
+        };
+        let mut my_bounties = Vec::new();
+        let mut open_bounties = Vec::new();
+        for (id, state) in bounties {
+            let mine = &state.depositer() == account;
+            let open = state.total() >= min;
+            if !mine && !open {
+                continue
+            }
+            match bounty.get_bounty_info(id, state).await {
+                Ok(info) if mine && open => {
+                    open_bounties.push(info.clone());
+                    my_bounties.push(info);
+                }
+                Ok(info) if mine => my_bounties.push(info),
+                Ok(info) => open_bounties.push(info),
+                Err(e) => {
+                    warn!(
+                        "I can't get the info of Bounty #{}. Skipping...",
+                        id
+                    );
+                    error!("{:?}", e);
+                }
+            }
+        }
+        let mut my_submissions = Vec::with_capacity(submissions.len());
+        for (id, state, reviewer) in submissions {
+            match bounty.get_submission_info(id, state, reviewer).await {
+                Ok(info) => my_submissions.push(info),
+                Err(e) => {
+                    warn!(
+                        "I can't get the info of Submission #{}. Skipping..",
+                        id
+                    );
+                    error!("{:?}", e);
+                }
+            }
+        }
+        snapshot.my_bounties = my_bounties;
+        snapshot.open_bounties = open_bounties;
+        snapshot.my_submissions = my_submissions;
+        snapshot.my_contributions = contributions
+            .into_iter()
+            .map(|c| {
+                ContributionInformation {
+                    schema_version: SCHEMA_VERSION,
+                    id: c.id().to_string(),
+                    account: properties.ss58(&c.account()),
+                    total: Balance(c.total().into()),
+                }
+            })
+            .collect();
+        Ok(())
+    }
+}
+
+fn empty_snapshot(account: String) -> SyncSnapshot {
+    SyncSnapshot {
+        schema_version: SCHEMA_VERSION,
+        account,
+        balance: Balance(0),
+        pending_votes: Vec::new(),
+        my_bounties: Vec::new(),
+        my_submissions: Vec::new(),
+        my_contributions: Vec::new(),
+        open_bounties: Vec::new(),
+        last_synced: 0,
+    }
+}
+
+/// The sections which may be stale after an event of `module`
+fn dirty_sections(module: &str) -> u8 {
+    match module {
+        // fees are paid without an event of their own
+        "System" | "Balances" | "Donate" | "Bank" | "Drip" => BALANCE,
+        "Bounty" => BOUNTIES | BALANCE,
+        "Vote" | "Org" => VOTES,
+        _ => 0,
+    }
+}
+
+/// Whether the constants of the runtime may have changed with the event
+fn is_runtime_upgrade(module: &str, variant: &str) -> bool {
+    module == "System" && variant == "CodeUpdated"
+}
+
+fn is_current(generation: u64) -> bool {
+    SYNC.lock().unwrap().generation == generation
+}
+
+/// Replaces the snapshot unless the task was stopped or restarted meanwhile
+fn publish(generation: u64, snapshot: &mut SyncSnapshot) {
+    snapshot.last_synced = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    let json = serde_json::to_value(&*snapshot)
+        .expect("dtos only contain strings and numbers; qed");
+    let mut state = SYNC.lock().unwrap();
+    if state.generation == generation {
+        state.snapshot = Some(json);
+    }
+}
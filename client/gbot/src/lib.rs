@@ -62,6 +62,20 @@ impl GBot {
         }
         Ok(None)
     }
+    /// The login of whoever opened the issue or pull request
+    pub async fn issue_author(
+        &self,
+        repo_owner: String,
+        repo_name: String,
+        issue_number: u64,
+    ) -> Result<String> {
+        let issue = self
+            .crab
+            .issues(repo_owner, repo_name)
+            .get(issue_number)
+            .await?;
+        Ok(issue.user.login)
+    }
 }
 
 impl GBot {
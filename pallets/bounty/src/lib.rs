@@ -17,6 +17,7 @@
 //! [`Trait`]: ./trait.Trait.html
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod migrations;
 #[cfg(test)]
 mod tests;
 
@@ -26,50 +27,87 @@ use frame_support::{
     decl_module,
     decl_storage,
     ensure,
-    storage::IterableStorageMap,
+    storage::IterableStorageDoubleMap,
     traits::{
+        BalanceStatus,
         Currency,
         ExistenceRequirement,
         Get,
         ReservableCurrency,
-        WithdrawReason,
-        WithdrawReasons,
     },
+    weights::Weight,
     Parameter,
 };
-use frame_system::ensure_signed;
-use parity_scale_codec::Codec;
+use frame_system::{
+    ensure_root,
+    ensure_signed,
+};
+use parity_scale_codec::{
+    Codec,
+    Decode,
+    Encode,
+};
 use sp_runtime::{
     traits::{
         AccountIdConversion,
         AtLeast32Bit,
         MaybeSerializeDeserialize,
         Member,
+        Saturating,
         Zero,
     },
+    DispatchError,
     DispatchResult,
     ModuleId,
+    Permill,
+    RuntimeDebug,
 };
 use sp_std::{
     fmt::Debug,
     prelude::*,
 };
-use util::bounty::{
-    BountyInformation,
-    BountySubmission,
-    Contribution,
-    SubmissionState,
+use util::{
+    bounty::{
+        is_valid_github_handle,
+        is_valid_tag,
+        AssetMetadata,
+        BountyInformation,
+        BountySubmission,
+        BountyTag,
+        Contribution,
+        MatchingProgram,
+        RejectionDispute,
+        RejectionReason,
+        ReviewPolicy,
+        SubmissionKind,
+        SubmissionState,
+        TimeoutOutcome,
+    },
+    deadline::DeadlineExtension,
+    traits::{
+        GetGroup,
+        MultiCurrency,
+        OrganizationSupervisorPermissions,
+        ShareInformation,
+        ThresholdVote,
+    },
+    vote::VoteOutcome,
 };
 
 // type aliases
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<
     <T as frame_system::Trait>::AccountId,
 >>::Balance;
+type AssetIdOf<T> = <<T as Trait>::Assets as MultiCurrency<
+    <T as frame_system::Trait>::AccountId,
+>>::AssetId;
 type Bounty<T> = BountyInformation<
     <T as Trait>::BountyId,
     <T as Trait>::IpfsReference,
     <T as frame_system::Trait>::AccountId,
+    AssetIdOf<T>,
     BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
 >;
 type BountySub<T> = BountySubmission<
     <T as Trait>::BountyId,
@@ -78,15 +116,71 @@ type BountySub<T> = BountySubmission<
     <T as frame_system::Trait>::AccountId,
     BalanceOf<T>,
     SubmissionState,
+    <T as frame_system::Trait>::BlockNumber,
 >;
 type Contrib<T> = Contribution<
     <T as Trait>::BountyId,
     <T as frame_system::Trait>::AccountId,
     BalanceOf<T>,
 >;
+type Matching<T> = MatchingProgram<<T as org::Trait>::OrgId, BalanceOf<T>>;
+type Deadline<T> = (
+    <T as frame_system::Trait>::BlockNumber,
+    Option<DeadlineExtension<<T as frame_system::Trait>::BlockNumber>>,
+);
+type Review<T> =
+    ReviewPolicy<<T as frame_system::Trait>::BlockNumber, BalanceOf<T>>;
 type EncodedIssue = Vec<u8>;
+type ArbitrationThresholdId<T> =
+    <<T as org::Trait>::SpendVote as ThresholdVote<
+        <T as org::Trait>::OrgId,
+        <T as org::Trait>::Cid,
+        <T as frame_system::Trait>::BlockNumber,
+    >>::ThresholdId;
+type ArbitrationVoteId<T> = <<T as org::Trait>::SpendVote as ThresholdVote<
+    <T as org::Trait>::OrgId,
+    <T as org::Trait>::Cid,
+    <T as frame_system::Trait>::BlockNumber,
+>>::VoteId;
+/// A rejected submission, its split, the block it was rejected at and the
+/// threshold of the org arbitrating a dispute of the rejection
+type Rejection<T> = (
+    BountySub<T>,
+    Vec<(<T as frame_system::Trait>::AccountId, Permill)>,
+    <T as frame_system::Trait>::BlockNumber,
+    ArbitrationThresholdId<T>,
+);
+type Dispute<T> = RejectionDispute<
+    <T as org::Trait>::Cid,
+    BalanceOf<T>,
+    ArbitrationVoteId<T>,
+>;
+
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode, RuntimeDebug,
+)]
+/// The layout of the pallet storage
+pub enum Releases {
+    /// Before bounties and submissions tracked their blocks
+    V1,
+    /// Bounties and submissions track their blocks
+    V2,
+    /// Submissions awaiting review are indexed by bounty
+    V3,
+}
+
+impl Default for Releases {
+    fn default() -> Releases {
+        // chains started before the version was tracked
+        Releases::V1
+    }
+}
 
-pub trait Trait: frame_system::Trait {
+/// The layout written by this version of the pallet, bumped with every
+/// migration
+pub const STORAGE_VERSION: Releases = Releases::V3;
+
+pub trait Trait: frame_system::Trait + org::Trait {
     /// The overarching event type
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
@@ -97,6 +191,9 @@ pub trait Trait: frame_system::Trait {
     type Currency: Currency<Self::AccountId>
         + ReservableCurrency<Self::AccountId>;
 
+    /// The native currency and the other assets bounties may be funded in
+    type Assets: MultiCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+
     /// The bounty post identifier
     type BountyId: Parameter
         + Member
@@ -131,6 +228,31 @@ pub trait Trait: frame_system::Trait {
 
     /// Minimum contribution to posted bounty
     type MinContribution: Get<BalanceOf<Self>>;
+
+    /// Deposit reserved from the submitter for each submission
+    type SubmissionDeposit: Get<BalanceOf<Self>>;
+
+    /// Maximum submissions awaiting review per account per bounty
+    type MaxPendingSubmissions: Get<u32>;
+
+    /// Maximum admins per bounty, including the depositer
+    type MaxBountyAdmins: Get<u32>;
+
+    /// Maximum tags per bounty
+    type MaxBountyTags: Get<u32>;
+
+    /// Maximum co-authors a submission payout may be split with
+    type MaxPayoutSplits: Get<u32>;
+
+    /// Blocks after a rejection during which the submitter may dispute it
+    type DisputeWindow: Get<Self::BlockNumber>;
+
+    /// Deposit reserved from the submitter for each dispute
+    type DisputeDeposit: Get<BalanceOf<Self>>;
+
+    /// Blocks submissions await review before anyone may time them out,
+    /// unless their bounty set its own timeout
+    type ReviewTimeout: Get<Self::BlockNumber>;
 }
 
 decl_event!(
@@ -141,15 +263,78 @@ decl_event!(
         <T as Trait>::BountyId,
         <T as Trait>::SubmissionId,
         Balance = BalanceOf<T>,
+        AssetId = AssetIdOf<T>,
+        <T as frame_system::Trait>::BlockNumber,
+        <T as org::Trait>::OrgId,
+        <T as org::Trait>::Shares,
+        Cid = <T as org::Trait>::Cid,
+        ThresholdId = ArbitrationThresholdId<T>,
+        VoteId = ArbitrationVoteId<T>,
     {
         /// Poster, Initial Amount, Identifier, Bounty Metadata (i.e. github issue reference)
         BountyPosted(AccountId, Balance, BountyId, IpfsReference),
         /// Contributor, This Contribution Amount, Identifier, Full Amount After Contribution, Bounty Metadata
         BountyRaiseContribution(AccountId, Balance, BountyId, Balance, IpfsReference),
-        /// Submitter, Bounty Identifier, Amount Requested, Submission Identifier, Bounty Metadata, Submission Metadata
-        BountySubmissionPosted(AccountId, BountyId, Balance, SubmissionId, IpfsReference, IpfsReference),
-        /// Bounty Identifier, Full Amount Left After Payment, Submission Identifier, Amount Requested, Bounty Metadata, Submission Metadata
-        BountyPaymentExecuted(BountyId, Balance, SubmissionId, Balance, AccountId, IpfsReference, IpfsReference),
+        /// Submitter, Bounty Identifier, Amount Requested, Submission Identifier, Bounty Metadata, Submission Metadata, Submitter Shares In The Required Org If Any
+        BountySubmissionPosted(AccountId, BountyId, Balance, SubmissionId, IpfsReference, IpfsReference, Option<Shares>),
+        /// Bounty Identifier, Full Amount Left After Payment, Submission Identifier, Amount Requested, Submitter, Bounty Metadata, Submission Metadata, Approving Admin
+        BountyPaymentExecuted(BountyId, Balance, SubmissionId, Balance, AccountId, IpfsReference, IpfsReference, AccountId),
+        /// Bounty Identifier, Submission Identifier, Recipient, Slice Of The Amount Requested Paid To Them
+        SubmissionSlicePaid(BountyId, SubmissionId, AccountId, Balance),
+        /// Bounty Identifier, Submission Identifier, Submitter, Co-Authors And Their Shares Of The Payout
+        SubmissionSplitSet(BountyId, SubmissionId, AccountId, Vec<(AccountId, Permill)>),
+        /// Submitter, Bounty Identifier, Submission Identifier, Refunded Deposit
+        BountySubmissionWithdrawn(AccountId, BountyId, SubmissionId, Balance),
+        /// Bounty Identifier, Submission Identifier, Submitter, Reason, Deposit Refunded Or Slashed, Rejecting Admin
+        BountySubmissionRejected(BountyId, SubmissionId, AccountId, RejectionReason, Balance, AccountId),
+        /// Bounty Identifier, Admin Who Added, New Admin
+        BountyAdminAdded(BountyId, AccountId, AccountId),
+        /// Bounty Identifier, Admin Who Removed, Removed Admin
+        BountyAdminRemoved(BountyId, AccountId, AccountId),
+        /// Bounty Identifier, Submission Identifier, Admin Who Assigned, Reviewer
+        SubmissionReviewerAssigned(BountyId, SubmissionId, AccountId, AccountId),
+        /// Bounty Identifier, Admin Who Set It, Share Of Each Reviewed Submission Paid To The Reviewer
+        ReviewerRewardSet(BountyId, AccountId, Option<Permill>),
+        /// Bounty Identifier, Submission Identifier, Reviewer, Reward Paid
+        ReviewerRewarded(BountyId, SubmissionId, AccountId, Balance),
+        /// Asset Identifier, Metadata If Accepted For New Bounties Or None If No Longer Accepted
+        BountyAssetSet(AssetId, Option<AssetMetadata>),
+        /// Bounty Identifier, Admin Who Set Them, New Tags
+        BountyTagsSet(BountyId, AccountId, Vec<BountyTag>),
+        /// Bounty Identifier, Total Paid To Approved Submissions, Number Of Approved Submissions
+        BountyClosed(BountyId, Balance, u32),
+        /// Bounty Identifier, Admin Who Set It, Last Block Taking Submissions Or None If Removed
+        BountyDeadlineSet(BountyId, AccountId, Option<BlockNumber>),
+        /// Bounty Identifier, New Deadline After A Late Submission
+        BountyDeadlineExtended(BountyId, BlockNumber),
+        /// Bounty Identifier, Admin Who Set It, Org Submitters Must Be Members Of Or None If Removed
+        BountyRequiredOrgSet(BountyId, AccountId, Option<OrgId>),
+        /// Org, Poster, Identifier Of The Bounty Funded By The Org Treasury
+        OrgBountyPosted(OrgId, AccountId, BountyId),
+        /// Org, Poster, Whether They May Post Bounties For The Org
+        BountyPosterSet(OrgId, AccountId, bool),
+        /// Bounty Identifier, Matching Org, Share Of Each Contribution Matched, Cap Or Zero If Removed
+        BountyMatchingSet(BountyId, OrgId, Permill, Balance),
+        /// Bounty Identifier, Matching Org, Amount Added From Its Treasury, Cap Remaining
+        ContributionMatched(BountyId, OrgId, Balance, Balance),
+        /// Claimer, Lowercase GitHub Handle, Attestation Holding The Claimer's Signature
+        GithubHandleClaimed(AccountId, Vec<u8>, IpfsReference),
+        /// Claimer, Released GitHub Handle
+        GithubHandleReleased(AccountId, Vec<u8>),
+        /// Threshold Of The Org Arbitrating Disputes Of Every Bounty Or None If Removed
+        DefaultArbitrationSet(Option<ThresholdId>),
+        /// Bounty Identifier, Admin Who Set It, Threshold Of The Org Arbitrating Its Disputes Or None If Removed
+        BountyArbitrationSet(BountyId, AccountId, Option<ThresholdId>),
+        /// Bounty Identifier, Submission Identifier, Submitter, Argument, Reserved Deposit, Arbitration Vote Identifier
+        RejectionDisputed(BountyId, SubmissionId, AccountId, Cid, Balance, VoteId),
+        /// Bounty Identifier, Submission Identifier, Submitter, Amount Paid, Refunded Deposit
+        RejectionOverturned(BountyId, SubmissionId, AccountId, Balance, Balance),
+        /// Bounty Identifier, Submission Identifier, Submitter, Slashed Deposit
+        RejectionUpheld(BountyId, SubmissionId, AccountId, Balance),
+        /// Bounty Identifier, Admin Who Set It, Blocks Submissions Await Review, Most A Timed Out Submission Is Approved For Or None If They Are Rejected
+        ReviewPolicySet(BountyId, AccountId, BlockNumber, Option<Balance>),
+        /// Submission Identifier, Whether It Was Rejected Or Approved For Its Amount
+        SubmissionTimedOut(SubmissionId, TimeoutOutcome<Balance>),
     }
 );
 
@@ -160,12 +345,73 @@ decl_error! {
         SubmissionDNE,
         BountyPostMustExceedMinDeposit,
         ContributionMustExceedModuleMin,
-        DepositerCannotSubmitForBounty,
+        AdminCannotSubmitForBounty,
         BountySubmissionExceedsTotalAvailableFunding,
         SubmissionNotInValidStateToApprove,
         CannotApproveSubmissionIfAmountExceedsTotalAvailable,
         NotAuthorizedToApproveBountySubmissions,
         IssueAlreadyClaimedForBountyOrSubmission,
+        SubmissionMustDifferFromBountyInfo,
+        TooManyPendingSubmissions,
+        NotAuthorizedToWithdrawSubmission,
+        NotAuthorizedToRejectBountySubmissions,
+        SubmissionNotInValidStateToWithdrawOrReject,
+        TooManyBountyAdmins,
+        NotAuthorizedToManageBountyAdmins,
+        AccountIsAlreadyBountyAdmin,
+        AccountIsNotBountyAdmin,
+        CannotRemoveLastBountyAdmin,
+        NotAuthorizedToAssignReviewer,
+        ReviewerMustBeBountyAdmin,
+        NotAuthorizedToSetReviewerReward,
+        AssetNotAcceptedForBounties,
+        ContributionAssetMustMatchBounty,
+        TooManyBountyTags,
+        // tags must be lowercase ASCII letters, digits or dashes padded with zeros
+        InvalidBountyTag,
+        NotAuthorizedToSetBountyTags,
+        BountyClosed,
+        NotAuthorizedToSetBountyDeadline,
+        BountyDeadlineMustNotBePassed,
+        DeadlineExtensionNeedsDeadline,
+        BountyDeadlinePassed,
+        NotAuthorizedToSetRequiredOrg,
+        RequiredOrgDNE,
+        SubmitterNotInRequiredOrg,
+        BountyOrgDNE,
+        // only the supervisor and the posters they authorized post for an org
+        NotAuthorizedToPostForOrg,
+        NotAuthorizedToSetBountyPoster,
+        // only the org supervisor commits its treasury to matching
+        NotAuthorizedToSetMatching,
+        // one org matches the contributions to a bounty at a time
+        BountyMatchedByAnotherOrg,
+        // handles must be valid lowercase GitHub logins
+        InvalidGithubHandle,
+        GithubHandleClaimedByAnotherAccount,
+        NoGithubHandleClaimed,
+        TooManyPayoutSplits,
+        PayoutSplitExceedsWholeAmount,
+        // co-authors must be distinct from each other and the submitter and have a share
+        InvalidPayoutSplit,
+        NotAuthorizedToSetSubmissionSplit,
+        SubmissionNotInValidStateToSetSplit,
+        NotAuthorizedToSetBountyArbitration,
+        // the threshold must be registered for an org
+        ArbitrationThresholdDNE,
+        // the rejection is not disputable or was already resolved
+        RejectionDNE,
+        NotAuthorizedToDisputeRejection,
+        RejectionAlreadyDisputed,
+        DisputeWindowPassed,
+        DisputeDNE,
+        DisputeNotDecided,
+        NotAuthorizedToSetReviewPolicy,
+        // a zero timeout would time submissions out as soon as they are made
+        InvalidReviewTimeout,
+        // submitters may rely on the cap, which is only raised before the first submission
+        OptimisticApprovalAfterSubmissions,
+        ReviewTimeoutNotReached,
     }
 }
 
@@ -183,6 +429,9 @@ decl_storage! {
         /// Posted Bounties
         pub Bounties get(fn bounties): map
             hasher(blake2_128_concat) T::BountyId => Option<Bounty<T>>;
+        /// The kind of reference expected of submissions, if the depositer set one
+        pub SubmissionKinds get(fn submission_kinds): map
+            hasher(blake2_128_concat) T::BountyId => SubmissionKind;
         /// Tips for existing Bounties
         pub Contributions get(fn contributions): double_map
             hasher(blake2_128_concat) T::BountyId,
@@ -191,6 +440,89 @@ decl_storage! {
         /// Posted Submissions
         pub Submissions get(fn submissions): map
             hasher(blake2_128_concat) T::SubmissionId => Option<BountySub<T>>;
+        /// The deposit reserved for each submission awaiting review
+        pub SubmissionDeposits get(fn submission_deposits): map
+            hasher(blake2_128_concat) T::SubmissionId => BalanceOf<T>;
+        /// The issue claimed by each submission awaiting review
+        pub SubmissionIssues get(fn submission_issue): map
+            hasher(blake2_128_concat) T::SubmissionId => Option<EncodedIssue>;
+        /// The submissions awaiting review of each bounty
+        pub BountySubmissions get(fn bounty_submissions): double_map
+            hasher(blake2_128_concat) T::BountyId,
+            hasher(blake2_128_concat) T::SubmissionId => Option<()>;
+        /// Number of submissions awaiting review per bounty per submitter
+        pub PendingSubmissions get(fn pending_submissions): double_map
+            hasher(blake2_128_concat) T::BountyId,
+            hasher(blake2_128_concat) T::AccountId => u32;
+        /// The co-authors each submission payout is split with and their shares, the submitter is paid the rest
+        pub SubmissionSplits get(fn submission_splits): map
+            hasher(blake2_128_concat) T::SubmissionId => Vec<(T::AccountId, Permill)>;
+        /// The admin assigned to review each submission, if any
+        pub SubmissionReviewers get(fn submission_reviewers): map
+            hasher(blake2_128_concat) T::SubmissionId => Option<T::AccountId>;
+        /// Share of the requested amount paid to the assigned reviewer, if the bounty set one
+        pub ReviewerRewards get(fn reviewer_rewards): map
+            hasher(blake2_128_concat) T::BountyId => Option<Permill>;
+        /// Assets other than the native currency accepted for new bounties
+        pub BountyAssets get(fn bounty_assets): map
+            hasher(blake2_128_concat) AssetIdOf<T> => Option<AssetMetadata>;
+        /// The tags of each bounty, without duplicates
+        pub BountyTags get(fn bounty_tags): map
+            hasher(blake2_128_concat) T::BountyId => Vec<BountyTag>;
+        /// The bounties with each tag
+        pub BountiesByTag get(fn bounties_by_tag): double_map
+            hasher(blake2_128_concat) BountyTag,
+            hasher(blake2_128_concat) T::BountyId => Option<()>;
+        /// The amount paid to approved submissions and their number per bounty
+        pub BountyPayouts get(fn bounty_payouts): map
+            hasher(blake2_128_concat) T::BountyId => (BalanceOf<T>, u32);
+        /// The last block taking submissions and how late submissions extend it, if the admins set one
+        pub BountyDeadlines get(fn bounty_deadline): map
+            hasher(blake2_128_concat) T::BountyId => Option<Deadline<T>>;
+        /// The org whose members alone may submit, if the admins set one
+        pub RequiredOrgs get(fn required_org): map
+            hasher(blake2_128_concat) T::BountyId => Option<T::OrgId>;
+        /// The org whose treasury funded each bounty posted for its board
+        pub BountyOrgs get(fn bounty_org): map
+            hasher(blake2_128_concat) T::BountyId => Option<T::OrgId>;
+        /// The open bounties on the board of each org
+        pub BountiesByOrg get(fn bounties_by_org): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::BountyId => Option<()>;
+        /// The accounts the supervisor of each org authorized to post bounties for it
+        pub BountyPosters get(fn bounty_poster): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<()>;
+        /// The org matching the contributions to each bounty and what its treasury still adds, if its supervisor set a program
+        pub BountyMatching get(fn bounty_matching): map
+            hasher(blake2_128_concat) T::BountyId => Option<Matching<T>>;
+        /// The account and attestation of each claimed GitHub handle
+        pub GithubHandleToAccount get(fn github_handle_account): map
+            hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, T::IpfsReference)>;
+        /// The GitHub handle claimed by each account
+        pub AccountGithubHandles get(fn account_github_handle): map
+            hasher(blake2_128_concat) T::AccountId => Option<Vec<u8>>;
+        /// The threshold of the org arbitrating disputes of every bounty, if root set one
+        pub DefaultArbitration get(fn default_arbitration): Option<ArbitrationThresholdId<T>>;
+        /// The threshold of the org arbitrating disputes of each bounty instead of the default, if the admins set one
+        pub BountyArbitration get(fn bounty_arbitration): map
+            hasher(blake2_128_concat) T::BountyId => Option<ArbitrationThresholdId<T>>;
+        /// The submissions rejected by bounties with an arbitrator, removed once a dispute of the rejection is resolved
+        pub RejectedSubmissions get(fn rejected_submissions): map
+            hasher(blake2_128_concat) T::SubmissionId => Option<Rejection<T>>;
+        /// The disputed rejections awaiting the arbitration vote
+        pub Disputes get(fn disputes): map
+            hasher(blake2_128_concat) T::SubmissionId => Option<Dispute<T>>;
+        /// How long submissions to each bounty await review and whether those awaiting longer are approved, if the admins set it
+        pub ReviewPolicies get(fn review_policies): map
+            hasher(blake2_128_concat) T::BountyId => Option<Review<T>>;
+        /// Bounties which paid out all they could, kept for history
+        pub ClosedBounties get(fn closed_bounties): map
+            hasher(blake2_128_concat) T::BountyId => Option<Bounty<T>>;
+
+        /// The layout of the stored values, new chains start at the latest
+        pub StorageVersion get(fn storage_version)
+            build(|_: &GenesisConfig| STORAGE_VERSION): Releases;
     }
 }
 
@@ -199,29 +531,97 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        const MinDeposit: BalanceOf<T> = T::MinDeposit::get();
+        const MinContribution: BalanceOf<T> = T::MinContribution::get();
+        const SubmissionDeposit: BalanceOf<T> = T::SubmissionDeposit::get();
+        const MaxPendingSubmissions: u32 = T::MaxPendingSubmissions::get();
+        const MaxBountyAdmins: u32 = T::MaxBountyAdmins::get();
+        const MaxBountyTags: u32 = T::MaxBountyTags::get();
+        const MaxPayoutSplits: u32 = T::MaxPayoutSplits::get();
+        const DisputeWindow: T::BlockNumber = T::DisputeWindow::get();
+        const DisputeDeposit: BalanceOf<T> = T::DisputeDeposit::get();
+        const ReviewTimeout: T::BlockNumber = T::ReviewTimeout::get();
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
         #[weight = 0]
         fn post_bounty(
             origin,
             issue: EncodedIssue,
             info: T::IpfsReference,
             amount: BalanceOf<T>,
+            asset_id: Option<AssetIdOf<T>>,
+            submission_kind: SubmissionKind,
+            admins: Vec<T::AccountId>,
+            tags: Vec<BountyTag>,
         ) -> DispatchResult {
-            ensure!(<IssueHashSet>::get(issue.clone()).is_none(), Error::<T>::IssueAlreadyClaimedForBountyOrSubmission);
-            ensure!(amount >= T::MinDeposit::get(), Error::<T>::BountyPostMustExceedMinDeposit);
             let depositer = ensure_signed(origin)?;
-            let imb = T::Currency::withdraw(
-                &depositer,
-                amount,
-                WithdrawReasons::from(WithdrawReason::Transfer),
-                ExistenceRequirement::AllowDeath,
-            )?;
-            let id = Self::bounty_generate_uid();
-            let bounty = Bounty::<T>::new(id, info.clone(), depositer.clone(), amount);
-            T::Currency::resolve_creating(&Self::bounty_account_id(id), imb);
-            <IssueHashSet>::insert(issue, ());
-            <Bounties<T>>::insert(id, bounty);
-            <Contributions<T>>::insert(id, &depositer, Contrib::<T>::new(id, depositer.clone(), amount));
-            Self::deposit_event(RawEvent::BountyPosted(depositer, amount, id, info));
+            Self::post(depositer.clone(), depositer, issue, info, amount, asset_id, submission_kind, admins, tags)?;
+            Ok(())
+        }
+        #[weight = 0]
+        fn post_org_bounty(
+            origin,
+            org: T::OrgId,
+            issue: EncodedIssue,
+            info: T::IpfsReference,
+            amount: BalanceOf<T>,
+            asset_id: Option<AssetIdOf<T>>,
+            submission_kind: SubmissionKind,
+            admins: Vec<T::AccountId>,
+            tags: Vec<BountyTag>,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+            ensure!(<org::Module<T>>::get_group(org).is_some(), Error::<T>::BountyOrgDNE);
+            ensure!(Self::may_post_for_org(org, &poster), Error::<T>::NotAuthorizedToPostForOrg);
+            // the treasury funds the bounty and is refunded what it leaves
+            let treasury = <org::Module<T>>::org_account(org);
+            let id = Self::post(treasury, poster.clone(), issue, info, amount, asset_id, submission_kind, admins, tags)?;
+            <BountyOrgs<T>>::insert(id, org);
+            <BountiesByOrg<T>>::insert(org, id, ());
+            Self::deposit_event(RawEvent::OrgBountyPosted(org, poster, id));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_bounty_poster(
+            origin,
+            org: T::OrgId,
+            poster: T::AccountId,
+            authorized: bool,
+        ) -> DispatchResult {
+            let supervisor = ensure_signed(origin)?;
+            ensure!(<org::Module<T>>::is_organization_supervisor(org, &supervisor), Error::<T>::NotAuthorizedToSetBountyPoster);
+            if authorized {
+                <BountyPosters<T>>::insert(org, &poster, ());
+            } else {
+                <BountyPosters<T>>::remove(org, &poster);
+            }
+            Self::deposit_event(RawEvent::BountyPosterSet(org, poster, authorized));
+            Ok(())
+        }
+        /// A zero ratio or cap ends the program
+        #[weight = 0]
+        fn set_matching(
+            origin,
+            bounty_id: T::BountyId,
+            org: T::OrgId,
+            ratio: Permill,
+            cap: BalanceOf<T>,
+        ) -> DispatchResult {
+            let supervisor = ensure_signed(origin)?;
+            ensure!(<org::Module<T>>::is_organization_supervisor(org, &supervisor), Error::<T>::NotAuthorizedToSetMatching);
+            Self::open_bounty(bounty_id)?;
+            if let Some(program) = <BountyMatching<T>>::get(bounty_id) {
+                ensure!(program.org() == org, Error::<T>::BountyMatchedByAnotherOrg);
+            }
+            if ratio.is_zero() || cap.is_zero() {
+                <BountyMatching<T>>::remove(bounty_id);
+            } else {
+                <BountyMatching<T>>::insert(bounty_id, Matching::<T>::new(org, ratio, cap));
+            }
+            Self::deposit_event(RawEvent::BountyMatchingSet(bounty_id, org, ratio, cap));
             Ok(())
         }
         #[weight = 0]
@@ -229,11 +629,14 @@ decl_module! {
             origin,
             bounty_id: T::BountyId,
             amount: BalanceOf<T>,
+            asset_id: Option<AssetIdOf<T>>,
         ) -> DispatchResult {
             ensure!(amount >= T::MinContribution::get(), Error::<T>::ContributionMustExceedModuleMin);
             let contributor = ensure_signed(origin)?;
-            let bounty = <Bounties<T>>::get(bounty_id).ok_or(Error::<T>::BountyDNE)?;
-            T::Currency::transfer(
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(asset_id == bounty.asset(), Error::<T>::ContributionAssetMustMatchBounty);
+            T::Assets::transfer(
+                asset_id,
                 &contributor,
                 &Self::bounty_account_id(bounty_id),
                 amount,
@@ -249,6 +652,7 @@ decl_module! {
             <Contributions<T>>::insert(bounty_id, &contributor, new_contribution);
             <Bounties<T>>::insert(bounty_id, new_bounty);
             Self::deposit_event(RawEvent::BountyRaiseContribution(contributor, amount, bounty_id, total, bounty.info()));
+            Self::match_contribution(bounty_id, amount);
             Ok(())
         }
         #[weight = 0]
@@ -258,17 +662,49 @@ decl_module! {
             issue: EncodedIssue,
             submission_ref: T::IpfsReference,
             amount: BalanceOf<T>,
+            // the co-authors paid a share of the amount, the submitter is paid the rest
+            split: Vec<(T::AccountId, Permill)>,
         ) -> DispatchResult {
             ensure!(<IssueHashSet>::get(issue.clone()).is_none(), Error::<T>::IssueAlreadyClaimedForBountyOrSubmission);
-            let bounty = <Bounties<T>>::get(bounty_id).ok_or(Error::<T>::BountyDNE)?;
+            let bounty = Self::open_bounty(bounty_id)?;
             let submitter = ensure_signed(origin)?;
-            ensure!(submitter != bounty.depositer(), Error::<T>::DepositerCannotSubmitForBounty);
+            <org::Module<T>>::ensure_valid_cid(&submission_ref)?;
+            ensure!(!bounty.is_admin(&submitter), Error::<T>::AdminCannotSubmitForBounty);
             ensure!(amount <= bounty.total(), Error::<T>::BountySubmissionExceedsTotalAvailableFunding);
+            Self::check_split(&submitter, &split)?;
+            ensure!(submission_ref != bounty.info(), Error::<T>::SubmissionMustDifferFromBountyInfo);
+            let pending = <PendingSubmissions<T>>::get(bounty_id, &submitter);
+            ensure!(pending < T::MaxPendingSubmissions::get(), Error::<T>::TooManyPendingSubmissions);
+            let shares = Self::required_org_shares(bounty_id, &submitter)?;
+            let now = <frame_system::Module<T>>::block_number();
+            let deadline = <BountyDeadlines<T>>::get(bounty_id);
+            if let Some((ends, _)) = deadline {
+                ensure!(now <= ends, Error::<T>::BountyDeadlinePassed);
+            }
+            let deposit = T::SubmissionDeposit::get();
+            T::Currency::reserve(&submitter, deposit)?;
             let id = Self::submission_generate_uid();
-            let submission = BountySub::<T>::new(bounty_id, id, submission_ref.clone(), submitter.clone(), amount);
+            let submission = BountySub::<T>::new(bounty_id, id, submission_ref.clone(), submitter.clone(), amount, now);
             <Submissions<T>>::insert(id, submission);
-            <IssueHashSet>::insert(issue, ());
-            Self::deposit_event(RawEvent::BountySubmissionPosted(submitter, bounty_id, amount, id, bounty.info(), submission_ref));
+            <BountySubmissions<T>>::insert(bounty_id, id, ());
+            if !split.is_empty() {
+                <SubmissionSplits<T>>::insert(id, split);
+            }
+            if bounty.first_submission_at().is_none() {
+                <Bounties<T>>::insert(bounty_id, bounty.submitted(now));
+            }
+            <SubmissionDeposits<T>>::insert(id, deposit);
+            <PendingSubmissions<T>>::insert(bounty_id, &submitter, pending + 1);
+            <IssueHashSet>::insert(&issue, ());
+            <SubmissionIssues<T>>::insert(id, issue);
+            Self::deposit_event(RawEvent::BountySubmissionPosted(submitter, bounty_id, amount, id, bounty.info(), submission_ref, shares));
+            // late submissions give the others time to respond
+            if let Some((ends, Some(extension))) = deadline {
+                if let Some((new_ends, extension)) = extension.extend(ends, now) {
+                    <BountyDeadlines<T>>::insert(bounty_id, (new_ends, Some(extension)));
+                    Self::deposit_event(RawEvent::BountyDeadlineExtended(bounty_id, new_ends));
+                }
+            }
             Ok(())
         }
         #[weight = 0]
@@ -280,24 +716,414 @@ decl_module! {
             let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
             ensure!(submission.state().awaiting_review(), Error::<T>::SubmissionNotInValidStateToApprove);
             let bounty_id = submission.bounty_id();
-            let bounty = <Bounties<T>>::get(bounty_id).ok_or(Error::<T>::BountyDNE)?;
+            let bounty = Self::open_bounty(bounty_id)?;
             ensure!(bounty.total() >= submission.amount(), Error::<T>::CannotApproveSubmissionIfAmountExceedsTotalAvailable);
-            ensure!(bounty.depositer() == approver, Error::<T>::NotAuthorizedToApproveBountySubmissions);
+            ensure!(bounty.is_admin(&approver), Error::<T>::NotAuthorizedToApproveBountySubmissions);
+            let reward = Self::reviewer_reward(&bounty, &submission, &approver);
             // execute payment
-            T::Currency::transfer(
-                &Self::bounty_account_id(bounty_id),
-                &submission.submitter(),
-                submission.amount(),
-                ExistenceRequirement::KeepAlive,
-            )?;
-            let new_bounty = bounty.subtract_total(submission.amount());
+            let split = <SubmissionSplits<T>>::get(submission_id);
+            let slices = Self::payout_slices(submission.submitter(), submission.amount(), &split);
+            for (recipient, slice) in &slices {
+                T::Assets::transfer(
+                    bounty.asset(),
+                    &Self::bounty_account_id(bounty_id),
+                    recipient,
+                    *slice,
+                    ExistenceRequirement::KeepAlive,
+                )?;
+            }
+            let now = <frame_system::Module<T>>::block_number();
+            let new_bounty = bounty.subtract_total(submission.amount()).resolved(now);
+            let new_bounty = Self::pay_reviewer(new_bounty, submission_id, &approver, reward)?;
             let (bounty_info, new_total) = (new_bounty.info(), new_bounty.total());
+            // too little is left to fund another submission => the bounty closes
+            let closing = Self::payable(&new_bounty) < T::MinContribution::get();
+            let new_bounty = if closing {
+                Self::refund_depositer(new_bounty)?
+            } else {
+                new_bounty
+            };
+            let (paid, approved) = <BountyPayouts<T>>::get(bounty_id);
+            let (total_paid, approved) = (paid.saturating_add(submission.amount()), approved + 1);
             // submission approved and executed => can be removed
-            <Submissions<T>>::remove(submission_id);
+            Self::close_submission(bounty_id, submission_id, &submission.submitter());
+            T::Currency::unreserve(&submission.submitter(), <SubmissionDeposits<T>>::take(submission_id));
+            <BountyPayouts<T>>::insert(bounty_id, (total_paid, approved));
+            if !split.is_empty() {
+                for (recipient, slice) in slices {
+                    Self::deposit_event(RawEvent::SubmissionSlicePaid(bounty_id, submission_id, recipient, slice));
+                }
+            }
+            Self::deposit_event(RawEvent::BountyPaymentExecuted(bounty_id, new_total, submission_id, submission.amount(), submission.submitter(), bounty_info, submission.submission(), approver));
+            if closing {
+                Self::close_bounty(new_bounty);
+                Self::deposit_event(RawEvent::BountyClosed(bounty_id, total_paid, approved));
+            } else {
+                <Bounties<T>>::insert(bounty_id, new_bounty);
+            }
+            Ok(())
+        }
+        #[weight = 0]
+        fn withdraw_bounty_submission(
+            origin,
+            submission_id: T::SubmissionId,
+        ) -> DispatchResult {
+            let submitter = ensure_signed(origin)?;
+            let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
+            ensure!(submission.is_submitter(&submitter), Error::<T>::NotAuthorizedToWithdrawSubmission);
+            ensure!(submission.state().awaiting_review(), Error::<T>::SubmissionNotInValidStateToWithdrawOrReject);
+            let bounty_id = submission.bounty_id();
+            let deposit = <SubmissionDeposits<T>>::take(submission_id);
+            T::Currency::unreserve(&submitter, deposit);
+            Self::release_issue(submission_id);
+            Self::close_submission(bounty_id, submission_id, &submitter);
+            Self::deposit_event(RawEvent::BountySubmissionWithdrawn(submitter, bounty_id, submission_id, deposit));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_submission_split(
+            origin,
+            submission_id: T::SubmissionId,
+            split: Vec<(T::AccountId, Permill)>,
+        ) -> DispatchResult {
+            let submitter = ensure_signed(origin)?;
+            let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
+            ensure!(submission.is_submitter(&submitter), Error::<T>::NotAuthorizedToSetSubmissionSplit);
+            ensure!(submission.state().awaiting_review(), Error::<T>::SubmissionNotInValidStateToSetSplit);
+            Self::check_split(&submitter, &split)?;
+            if split.is_empty() {
+                <SubmissionSplits<T>>::remove(submission_id);
+            } else {
+                <SubmissionSplits<T>>::insert(submission_id, split.clone());
+            }
+            Self::deposit_event(RawEvent::SubmissionSplitSet(submission.bounty_id(), submission_id, submitter, split));
+            Ok(())
+        }
+        #[weight = 0]
+        fn reject_bounty_submission(
+            origin,
+            submission_id: T::SubmissionId,
+            reason: RejectionReason,
+        ) -> DispatchResult {
+            let rejecter = ensure_signed(origin)?;
+            let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
+            ensure!(submission.state().awaiting_review(), Error::<T>::SubmissionNotInValidStateToWithdrawOrReject);
+            let bounty_id = submission.bounty_id();
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&rejecter), Error::<T>::NotAuthorizedToRejectBountySubmissions);
+            let reward = Self::reviewer_reward(&bounty, &submission, &rejecter);
+            let bounty = Self::pay_reviewer(bounty, submission_id, &rejecter, reward)?;
+            let submitter = submission.submitter();
+            let deposit = <SubmissionDeposits<T>>::take(submission_id);
+            let bounty = match reason {
+                RejectionReason::Unsuitable => {
+                    T::Currency::unreserve(&submitter, deposit);
+                    bounty
+                }
+                RejectionReason::Spam => Self::slash_to_bounty(bounty, &submitter, deposit)?,
+            };
+            <Bounties<T>>::insert(bounty_id, bounty);
+            // the submitter may dispute the rejection if the bounty has an arbitrator
+            if let Some(threshold) = Self::arbitration_threshold(bounty_id) {
+                let now = <frame_system::Module<T>>::block_number();
+                let split = <SubmissionSplits<T>>::get(submission_id);
+                <RejectedSubmissions<T>>::insert(submission_id, (submission, split, now, threshold));
+            }
+            Self::release_issue(submission_id);
+            Self::close_submission(bounty_id, submission_id, &submitter);
+            Self::deposit_event(RawEvent::BountySubmissionRejected(bounty_id, submission_id, submitter, reason, deposit, rejecter));
+            Ok(())
+        }
+        #[weight = 0]
+        fn add_bounty_admin(
+            origin,
+            bounty_id: T::BountyId,
+            new_admin: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToManageBountyAdmins);
+            let new_bounty = bounty.add_admin(new_admin.clone()).ok_or(Error::<T>::AccountIsAlreadyBountyAdmin)?;
+            ensure!(new_bounty.admins().len() as u32 <= T::MaxBountyAdmins::get(), Error::<T>::TooManyBountyAdmins);
             <Bounties<T>>::insert(bounty_id, new_bounty);
-            Self::deposit_event(RawEvent::BountyPaymentExecuted(bounty_id, new_total, submission_id, submission.amount(), submission.submitter(), bounty_info, submission.submission()));
+            Self::deposit_event(RawEvent::BountyAdminAdded(bounty_id, admin, new_admin));
+            Ok(())
+        }
+        #[weight = 0]
+        fn remove_bounty_admin(
+            origin,
+            bounty_id: T::BountyId,
+            old_admin: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToManageBountyAdmins);
+            let new_bounty = bounty.remove_admin(&old_admin).ok_or(Error::<T>::AccountIsNotBountyAdmin)?;
+            ensure!(!new_bounty.admins().is_empty(), Error::<T>::CannotRemoveLastBountyAdmin);
+            <Bounties<T>>::insert(bounty_id, new_bounty);
+            Self::deposit_event(RawEvent::BountyAdminRemoved(bounty_id, admin, old_admin));
+            Ok(())
+        }
+        #[weight = 0]
+        fn assign_reviewer(
+            origin,
+            submission_id: T::SubmissionId,
+            reviewer: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
+            let bounty_id = submission.bounty_id();
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToAssignReviewer);
+            ensure!(bounty.is_admin(&reviewer), Error::<T>::ReviewerMustBeBountyAdmin);
+            <SubmissionReviewers<T>>::insert(submission_id, &reviewer);
+            Self::deposit_event(RawEvent::SubmissionReviewerAssigned(bounty_id, submission_id, admin, reviewer));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_reviewer_reward(
+            origin,
+            bounty_id: T::BountyId,
+            reward: Option<Permill>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetReviewerReward);
+            if let Some(r) = reward {
+                <ReviewerRewards<T>>::insert(bounty_id, r);
+            } else {
+                <ReviewerRewards<T>>::remove(bounty_id);
+            }
+            Self::deposit_event(RawEvent::ReviewerRewardSet(bounty_id, admin, reward));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_bounty_tags(
+            origin,
+            bounty_id: T::BountyId,
+            tags: Vec<BountyTag>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetBountyTags);
+            let tags = Self::checked_tags(tags)?;
+            Self::unindex_tags(bounty_id);
+            Self::index_tags(bounty_id, tags.clone());
+            Self::deposit_event(RawEvent::BountyTagsSet(bounty_id, admin, tags));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_bounty_deadline(
+            origin,
+            bounty_id: T::BountyId,
+            deadline: Option<T::BlockNumber>,
+            // the window and the maximum extension
+            extension: Option<(T::BlockNumber, T::BlockNumber)>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetBountyDeadline);
+            ensure!(deadline.is_some() || extension.is_none(), Error::<T>::DeadlineExtensionNeedsDeadline);
+            if let Some(ends) = deadline {
+                let now = <frame_system::Module<T>>::block_number();
+                ensure!(ends >= now, Error::<T>::BountyDeadlineMustNotBePassed);
+                let extension = extension.map(|(window, max)| DeadlineExtension::new(window, max));
+                <BountyDeadlines<T>>::insert(bounty_id, (ends, extension));
+            } else {
+                <BountyDeadlines<T>>::remove(bounty_id);
+            }
+            Self::deposit_event(RawEvent::BountyDeadlineSet(bounty_id, admin, deadline));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_required_org(
+            origin,
+            bounty_id: T::BountyId,
+            org: Option<T::OrgId>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetRequiredOrg);
+            if let Some(o) = org {
+                ensure!(<org::Module<T>>::get_group(o).is_some(), Error::<T>::RequiredOrgDNE);
+                <RequiredOrgs<T>>::insert(bounty_id, o);
+            } else {
+                <RequiredOrgs<T>>::remove(bounty_id);
+            }
+            Self::deposit_event(RawEvent::BountyRequiredOrgSet(bounty_id, admin, org));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_bounty_asset(
+            origin,
+            asset_id: AssetIdOf<T>,
+            metadata: Option<AssetMetadata>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(m) = metadata.clone() {
+                <BountyAssets<T>>::insert(asset_id, m);
+            } else {
+                <BountyAssets<T>>::remove(asset_id);
+            }
+            Self::deposit_event(RawEvent::BountyAssetSet(asset_id, metadata));
+            Ok(())
+        }
+        #[weight = 0]
+        fn claim_github_handle(
+            origin,
+            handle: Vec<u8>,
+            attestation: T::IpfsReference,
+        ) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
+            <org::Module<T>>::ensure_valid_cid(&attestation)?;
+            ensure!(is_valid_github_handle(&handle), Error::<T>::InvalidGithubHandle);
+            if let Some((owner, _)) = <GithubHandleToAccount<T>>::get(&handle) {
+                ensure!(owner == claimer, Error::<T>::GithubHandleClaimedByAnotherAccount);
+            }
+            // claiming another handle releases the old one
+            if let Some(old) = <AccountGithubHandles<T>>::get(&claimer) {
+                <GithubHandleToAccount<T>>::remove(old);
+            }
+            <GithubHandleToAccount<T>>::insert(&handle, (claimer.clone(), attestation.clone()));
+            <AccountGithubHandles<T>>::insert(&claimer, handle.clone());
+            Self::deposit_event(RawEvent::GithubHandleClaimed(claimer, handle, attestation));
+            Ok(())
+        }
+        #[weight = 0]
+        fn release_github_handle(origin) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
+            let handle = <AccountGithubHandles<T>>::take(&claimer)
+                .ok_or(Error::<T>::NoGithubHandleClaimed)?;
+            <GithubHandleToAccount<T>>::remove(&handle);
+            Self::deposit_event(RawEvent::GithubHandleReleased(claimer, handle));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_arbitration_threshold(
+            origin,
+            threshold: Option<ArbitrationThresholdId<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(t) = threshold {
+                ensure!(T::SpendVote::threshold_org(t).is_some(), Error::<T>::ArbitrationThresholdDNE);
+                <DefaultArbitration<T>>::put(t);
+            } else {
+                <DefaultArbitration<T>>::kill();
+            }
+            Self::deposit_event(RawEvent::DefaultArbitrationSet(threshold));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_bounty_arbitration(
+            origin,
+            bounty_id: T::BountyId,
+            threshold: Option<ArbitrationThresholdId<T>>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetBountyArbitration);
+            if let Some(t) = threshold {
+                ensure!(T::SpendVote::threshold_org(t).is_some(), Error::<T>::ArbitrationThresholdDNE);
+                <BountyArbitration<T>>::insert(bounty_id, t);
+            } else {
+                <BountyArbitration<T>>::remove(bounty_id);
+            }
+            Self::deposit_event(RawEvent::BountyArbitrationSet(bounty_id, admin, threshold));
+            Ok(())
+        }
+        #[weight = 0]
+        fn dispute_rejection(
+            origin,
+            submission_id: T::SubmissionId,
+            argument: T::Cid,
+        ) -> DispatchResult {
+            let submitter = ensure_signed(origin)?;
+            <org::Module<T>>::ensure_valid_cid(&argument)?;
+            let (submission, _, rejected_at, threshold) = <RejectedSubmissions<T>>::get(submission_id).ok_or(Error::<T>::RejectionDNE)?;
+            ensure!(submission.is_submitter(&submitter), Error::<T>::NotAuthorizedToDisputeRejection);
+            ensure!(<Disputes<T>>::get(submission_id).is_none(), Error::<T>::RejectionAlreadyDisputed);
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now <= rejected_at.saturating_add(T::DisputeWindow::get()), Error::<T>::DisputeWindowPassed);
+            let bounty_id = submission.bounty_id();
+            // a closed bounty could not pay the submission if the rejection were overturned
+            Self::open_bounty(bounty_id)?;
+            let deposit = T::DisputeDeposit::get();
+            T::Currency::reserve(&submitter, deposit)?;
+            let vote = T::SpendVote::open_threshold_vote(threshold, Some(argument.clone()), None).map_err(|e| {
+                T::Currency::unreserve(&submitter, deposit);
+                e
+            })?;
+            <Disputes<T>>::insert(submission_id, Dispute::<T>::new(argument.clone(), deposit, vote));
+            Self::deposit_event(RawEvent::RejectionDisputed(bounty_id, submission_id, submitter, argument, deposit, vote));
+            Ok(())
+        }
+        #[weight = 0]
+        fn resolve_dispute(
+            origin,
+            submission_id: T::SubmissionId,
+        ) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            let dispute = <Disputes<T>>::get(submission_id).ok_or(Error::<T>::DisputeDNE)?;
+            let (submission, split, _, _) = <RejectedSubmissions<T>>::get(submission_id).ok_or(Error::<T>::RejectionDNE)?;
+            match T::SpendVote::threshold_vote_outcome(dispute.vote())? {
+                VoteOutcome::NotStarted | VoteOutcome::Voting => return Err(Error::<T>::DisputeNotDecided.into()),
+                VoteOutcome::Approved => Self::overturn_rejection(submission_id, submission, split, dispute.deposit())?,
+                // rejected, expired and cancelled votes all leave the rejection standing
+                _ => Self::uphold_rejection(submission_id, submission, dispute.deposit())?,
+            }
+            <Disputes<T>>::remove(submission_id);
+            <RejectedSubmissions<T>>::remove(submission_id);
+            Ok(())
+        }
+        /// A cap of None rejects timed out submissions
+        #[weight = 0]
+        fn set_review_policy(
+            origin,
+            bounty_id: T::BountyId,
+            timeout: T::BlockNumber,
+            optimistic_cap: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+            let bounty = Self::open_bounty(bounty_id)?;
+            ensure!(bounty.is_admin(&admin), Error::<T>::NotAuthorizedToSetReviewPolicy);
+            ensure!(!timeout.is_zero(), Error::<T>::InvalidReviewTimeout);
+            if let Some(cap) = optimistic_cap {
+                let current = Self::review_policy(bounty_id).optimistic_cap();
+                // migrated bounties may have taken submissions they did not record
+                let before_submissions = bounty.timestamped() && bounty.first_submission_at().is_none();
+                ensure!(before_submissions || current.map_or(false, |current| cap <= current), Error::<T>::OptimisticApprovalAfterSubmissions);
+            }
+            <ReviewPolicies<T>>::insert(bounty_id, Review::<T>::new(timeout, optimistic_cap));
+            Self::deposit_event(RawEvent::ReviewPolicySet(bounty_id, admin, timeout, optimistic_cap));
             Ok(())
         }
+        #[weight = 0]
+        fn timeout_submission(
+            origin,
+            submission_id: T::SubmissionId,
+        ) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            let submission = <Submissions<T>>::get(submission_id).ok_or(Error::<T>::SubmissionDNE)?;
+            ensure!(submission.state().awaiting_review(), Error::<T>::SubmissionNotInValidStateToWithdrawOrReject);
+            let bounty_id = submission.bounty_id();
+            let bounty = Self::open_bounty(bounty_id)?;
+            let policy = Self::review_policy(bounty_id);
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now >= policy.times_out_at(submission.submitted_at()), Error::<T>::ReviewTimeoutNotReached);
+            let amount = submission.amount();
+            // submissions the bounty cannot pay in full are rejected even if it is optimistic
+            let approved = policy.optimistic_cap().map_or(false, |cap| amount <= cap) && bounty.total() >= amount;
+            if approved {
+                Self::approve_timed_out(bounty, submission_id, submission)
+            } else {
+                let submitter = submission.submitter();
+                T::Currency::unreserve(&submitter, <SubmissionDeposits<T>>::take(submission_id));
+                Self::release_issue(submission_id);
+                Self::close_submission(bounty_id, submission_id, &submitter);
+                Self::deposit_event(RawEvent::SubmissionTimedOut(submission_id, TimeoutOutcome::Rejected));
+                Ok(())
+            }
+        }
     }
 }
 
@@ -308,13 +1134,15 @@ impl<T: Trait> Module<T> {
     }
     fn bounty_id_is_available(id: T::BountyId) -> bool {
         <Bounties<T>>::get(id).is_none()
+            && <ClosedBounties<T>>::get(id).is_none()
     }
-    fn bounty_generate_uid() -> T::BountyId {
+    /// The identifier of the next bounty, which is only claimed by putting
+    /// it as the nonce once the bounty is funded
+    fn next_bounty_id() -> T::BountyId {
         let mut id_counter = <BountyNonce<T>>::get() + 1u32.into();
         while !Self::bounty_id_is_available(id_counter) {
             id_counter += 1u32.into();
         }
-        <BountyNonce<T>>::put(id_counter);
         id_counter
     }
     fn submission_id_is_available(id: T::SubmissionId) -> bool {
@@ -328,10 +1156,594 @@ impl<T: Trait> Module<T> {
         <SubmissionNonce<T>>::put(id_counter);
         id_counter
     }
+    /// Funds a new bounty from `depositer`, who is refunded what it leaves
+    /// once it closes, with `poster` and the other requested admins as its
+    /// admins
+    #[allow(clippy::too_many_arguments)]
+    fn post(
+        depositer: T::AccountId,
+        poster: T::AccountId,
+        issue: EncodedIssue,
+        info: T::IpfsReference,
+        amount: BalanceOf<T>,
+        asset_id: Option<AssetIdOf<T>>,
+        submission_kind: SubmissionKind,
+        admins: Vec<T::AccountId>,
+        tags: Vec<BountyTag>,
+    ) -> Result<T::BountyId, DispatchError> {
+        <org::Module<T>>::ensure_valid_cid(&info)?;
+        ensure!(
+            <IssueHashSet>::get(issue.clone()).is_none(),
+            Error::<T>::IssueAlreadyClaimedForBountyOrSubmission
+        );
+        let tags = Self::checked_tags(tags)?;
+        ensure!(
+            amount >= T::MinDeposit::get(),
+            Error::<T>::BountyPostMustExceedMinDeposit
+        );
+        if let Some(asset) = asset_id {
+            ensure!(
+                <BountyAssets<T>>::get(asset).is_some(),
+                Error::<T>::AssetNotAcceptedForBounties
+            );
+        }
+        let admins = Self::bounty_admins(&poster, admins);
+        ensure!(
+            admins.len() as u32 <= T::MaxBountyAdmins::get(),
+            Error::<T>::TooManyBountyAdmins
+        );
+        let id = Self::next_bounty_id();
+        T::Assets::transfer(
+            asset_id,
+            &depositer,
+            &Self::bounty_account_id(id),
+            amount,
+            ExistenceRequirement::AllowDeath,
+        )?;
+        <BountyNonce<T>>::put(id);
+        let now = <frame_system::Module<T>>::block_number();
+        let bounty = Bounty::<T>::new(
+            id,
+            info.clone(),
+            depositer.clone(),
+            admins,
+            asset_id,
+            amount,
+            now,
+        );
+        <IssueHashSet>::insert(issue, ());
+        <Bounties<T>>::insert(id, bounty);
+        if submission_kind != SubmissionKind::Any {
+            <SubmissionKinds<T>>::insert(id, submission_kind);
+        }
+        Self::index_tags(id, tags);
+        <Contributions<T>>::insert(
+            id,
+            &depositer,
+            Contrib::<T>::new(id, depositer.clone(), amount),
+        );
+        Self::deposit_event(RawEvent::BountyPosted(
+            depositer, amount, id, info,
+        ));
+        Ok(id)
+    }
+    /// Whether `who` supervises the org or was authorized by its supervisor
+    /// to post bounties funded by its treasury
+    pub fn may_post_for_org(org: T::OrgId, who: &T::AccountId) -> bool {
+        <org::Module<T>>::is_organization_supervisor(org, who)
+            || <BountyPosters<T>>::get(org, who).is_some()
+    }
+    /// Adds the match of a contribution of `amount` out of the treasury of
+    /// the org matching the bounty, nothing once its cap or treasury ran out
+    fn match_contribution(bounty_id: T::BountyId, amount: BalanceOf<T>) {
+        let (program, bounty) = match (
+            <BountyMatching<T>>::get(bounty_id),
+            <Bounties<T>>::get(bounty_id),
+        ) {
+            (Some(program), Some(bounty)) => (program, bounty),
+            _ => return,
+        };
+        let org = program.org();
+        let treasury = <org::Module<T>>::org_account(org);
+        let matched = (program.ratio() * amount)
+            .min(program.remaining())
+            .min(T::Assets::free_balance(bounty.asset(), &treasury));
+        if matched.is_zero() {
+            return
+        }
+        // the treasury may not be reaped to match, the contribution stands
+        if T::Assets::transfer(
+            bounty.asset(),
+            &treasury,
+            &Self::bounty_account_id(bounty_id),
+            matched,
+            ExistenceRequirement::KeepAlive,
+        )
+        .is_err()
+        {
+            return
+        }
+        let contribution = match <Contributions<T>>::get(bounty_id, &treasury) {
+            Some(c) => c.add_total(matched),
+            None => Contrib::<T>::new(bounty_id, treasury.clone(), matched),
+        };
+        <Contributions<T>>::insert(bounty_id, &treasury, contribution);
+        <Bounties<T>>::insert(bounty_id, bounty.add_total(matched));
+        let program = program.spend(matched);
+        let remaining = program.remaining();
+        if remaining.is_zero() {
+            <BountyMatching<T>>::remove(bounty_id);
+        } else {
+            <BountyMatching<T>>::insert(bounty_id, program);
+        }
+        Self::deposit_event(RawEvent::ContributionMatched(
+            bounty_id, org, matched, remaining,
+        ));
+    }
+    /// The poster followed by the other requested admins, without duplicates
+    fn bounty_admins(
+        poster: &T::AccountId,
+        requested: Vec<T::AccountId>,
+    ) -> Vec<T::AccountId> {
+        let mut admins = vec![poster.clone()];
+        for admin in requested {
+            if !admins.contains(&admin) {
+                admins.push(admin);
+            }
+        }
+        admins
+    }
+    /// The reward owed to `reviewer` for deciding on a submission, zero
+    /// unless they are the assigned reviewer and the bounty set a reward.
+    /// Capped at what remains in the bounty after paying the submission.
+    fn reviewer_reward(
+        bounty: &Bounty<T>,
+        submission: &BountySub<T>,
+        reviewer: &T::AccountId,
+    ) -> BalanceOf<T> {
+        let assigned =
+            <SubmissionReviewers<T>>::get(submission.submission_id());
+        match (assigned, <ReviewerRewards<T>>::get(bounty.id())) {
+            (Some(a), Some(share)) if &a == reviewer => {
+                let left = bounty.total().saturating_sub(submission.amount());
+                (share * submission.amount()).min(left)
+            }
+            _ => Zero::zero(),
+        }
+    }
+    /// Pays the reviewer reward out of the bounty
+    fn pay_reviewer(
+        bounty: Bounty<T>,
+        submission_id: T::SubmissionId,
+        reviewer: &T::AccountId,
+        reward: BalanceOf<T>,
+    ) -> Result<Bounty<T>, DispatchError> {
+        if reward.is_zero() {
+            return Ok(bounty)
+        }
+        T::Assets::transfer(
+            bounty.asset(),
+            &Self::bounty_account_id(bounty.id()),
+            reviewer,
+            reward,
+            ExistenceRequirement::KeepAlive,
+        )?;
+        Self::deposit_event(RawEvent::ReviewerRewarded(
+            bounty.id(),
+            submission_id,
+            reviewer.clone(),
+            reward,
+        ));
+        Ok(bounty.subtract_total(reward))
+    }
+    /// Fails unless the split names few enough distinct co-authors other
+    /// than the submitter, each with a share, and the shares add up to at
+    /// most the whole amount
+    fn check_split(
+        submitter: &T::AccountId,
+        split: &[(T::AccountId, Permill)],
+    ) -> DispatchResult {
+        ensure!(
+            split.len() as u32 <= T::MaxPayoutSplits::get(),
+            Error::<T>::TooManyPayoutSplits
+        );
+        let mut parts = 0u32;
+        for (i, (author, share)) in split.iter().enumerate() {
+            let distinct = author != submitter
+                && split[..i].iter().all(|(other, _)| other != author);
+            ensure!(
+                distinct && !share.is_zero(),
+                Error::<T>::InvalidPayoutSplit
+            );
+            parts = parts.saturating_add(share.deconstruct());
+        }
+        ensure!(
+            parts <= Permill::from_percent(100).deconstruct(),
+            Error::<T>::PayoutSplitExceedsWholeAmount
+        );
+        Ok(())
+    }
+    /// The amount paid to each recipient of an approved submission, the
+    /// submitter first with what the co-authors' shares leave. Shares
+    /// rounding to nothing are paid to the submitter instead of
+    /// transferring dust.
+    pub fn payout_slices(
+        submitter: T::AccountId,
+        amount: BalanceOf<T>,
+        split: &[(T::AccountId, Permill)],
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let mut left = amount;
+        let mut slices = vec![(submitter, Zero::zero())];
+        for (author, share) in split {
+            // rounding must not pay out more than the whole amount
+            let slice = (*share * amount).min(left);
+            if !slice.is_zero() {
+                left -= slice;
+                slices.push((author.clone(), slice));
+            }
+        }
+        slices[0].1 = left;
+        if left.is_zero() {
+            slices.remove(0);
+        }
+        slices
+    }
+    /// Slashes the native `deposit` reserved by `who` to the bounty, which
+    /// it funds unless the bounty is in another asset
+    fn slash_to_bounty(
+        bounty: Bounty<T>,
+        who: &T::AccountId,
+        deposit: BalanceOf<T>,
+    ) -> Result<Bounty<T>, DispatchError> {
+        if bounty.asset().is_some() {
+            // a native deposit cannot fund a bounty in another asset
+            T::Currency::slash_reserved(who, deposit);
+            return Ok(bounty)
+        }
+        let unslashed = T::Currency::repatriate_reserved(
+            who,
+            &Self::bounty_account_id(bounty.id()),
+            deposit,
+            BalanceStatus::Free,
+        )?;
+        Ok(bounty.add_total(deposit - unslashed))
+    }
+    /// The threshold of the org arbitrating disputes of the bounty's
+    /// rejections, its own or else the default, None if it has none
+    pub fn arbitration_threshold(
+        bounty_id: T::BountyId,
+    ) -> Option<ArbitrationThresholdId<T>> {
+        <BountyArbitration<T>>::get(bounty_id)
+            .or_else(Self::default_arbitration)
+    }
+    /// Pays a submission whose rejection the arbitration vote overturned as
+    /// if the admins had approved it, without a reviewer reward, and refunds
+    /// the dispute deposit. Fails while the bounty holds too little to pay
+    /// it, and pays nothing if the bounty closed meanwhile.
+    fn overturn_rejection(
+        submission_id: T::SubmissionId,
+        submission: BountySub<T>,
+        split: Vec<(T::AccountId, Permill)>,
+        deposit: BalanceOf<T>,
+    ) -> DispatchResult {
+        let bounty_id = submission.bounty_id();
+        let submitter = submission.submitter();
+        let bounty = match <Bounties<T>>::get(bounty_id) {
+            Some(bounty) => bounty,
+            None => {
+                T::Currency::unreserve(&submitter, deposit);
+                Self::deposit_event(RawEvent::RejectionOverturned(
+                    bounty_id,
+                    submission_id,
+                    submitter,
+                    Zero::zero(),
+                    deposit,
+                ));
+                return Ok(())
+            }
+        };
+        let amount = submission.amount();
+        ensure!(
+            bounty.total() >= amount,
+            Error::<T>::CannotApproveSubmissionIfAmountExceedsTotalAvailable
+        );
+        let slices = Self::payout_slices(submitter.clone(), amount, &split);
+        for (recipient, slice) in &slices {
+            T::Assets::transfer(
+                bounty.asset(),
+                &Self::bounty_account_id(bounty_id),
+                recipient,
+                *slice,
+                ExistenceRequirement::KeepAlive,
+            )?;
+        }
+        T::Currency::unreserve(&submitter, deposit);
+        let now = <frame_system::Module<T>>::block_number();
+        let new_bounty = bounty.subtract_total(amount).resolved(now);
+        let closing = Self::payable(&new_bounty) < T::MinContribution::get();
+        let new_bounty = if closing {
+            Self::refund_depositer(new_bounty)?
+        } else {
+            new_bounty
+        };
+        let (paid, approved) = <BountyPayouts<T>>::get(bounty_id);
+        let (total_paid, approved) =
+            (paid.saturating_add(amount), approved + 1);
+        <BountyPayouts<T>>::insert(bounty_id, (total_paid, approved));
+        if !split.is_empty() {
+            for (recipient, slice) in slices {
+                Self::deposit_event(RawEvent::SubmissionSlicePaid(
+                    bounty_id,
+                    submission_id,
+                    recipient,
+                    slice,
+                ));
+            }
+        }
+        Self::deposit_event(RawEvent::RejectionOverturned(
+            bounty_id,
+            submission_id,
+            submitter,
+            amount,
+            deposit,
+        ));
+        if closing {
+            Self::close_bounty(new_bounty);
+            Self::deposit_event(RawEvent::BountyClosed(
+                bounty_id, total_paid, approved,
+            ));
+        } else {
+            <Bounties<T>>::insert(bounty_id, new_bounty);
+        }
+        Ok(())
+    }
+    /// How long submissions to the bounty await review, the default timeout
+    /// without optimistic approval unless its admins set their own
+    pub fn review_policy(bounty_id: T::BountyId) -> Review<T> {
+        <ReviewPolicies<T>>::get(bounty_id)
+            .unwrap_or_else(|| Review::<T>::new(T::ReviewTimeout::get(), None))
+    }
+    /// Pays a submission which timed out awaiting review of an optimistic
+    /// bounty as if the admins had approved it, without a reviewer reward,
+    /// and refunds its deposit
+    fn approve_timed_out(
+        bounty: Bounty<T>,
+        submission_id: T::SubmissionId,
+        submission: BountySub<T>,
+    ) -> DispatchResult {
+        let bounty_id = bounty.id();
+        let submitter = submission.submitter();
+        let amount = submission.amount();
+        let split = <SubmissionSplits<T>>::get(submission_id);
+        let slices = Self::payout_slices(submitter.clone(), amount, &split);
+        for (recipient, slice) in &slices {
+            T::Assets::transfer(
+                bounty.asset(),
+                &Self::bounty_account_id(bounty_id),
+                recipient,
+                *slice,
+                ExistenceRequirement::KeepAlive,
+            )?;
+        }
+        let now = <frame_system::Module<T>>::block_number();
+        let new_bounty = bounty.subtract_total(amount).resolved(now);
+        let closing = Self::payable(&new_bounty) < T::MinContribution::get();
+        let new_bounty = if closing {
+            Self::refund_depositer(new_bounty)?
+        } else {
+            new_bounty
+        };
+        let (paid, approved) = <BountyPayouts<T>>::get(bounty_id);
+        let (total_paid, approved) =
+            (paid.saturating_add(amount), approved + 1);
+        // closed before the bounty, which refunds the deposits still pending
+        Self::close_submission(bounty_id, submission_id, &submitter);
+        T::Currency::unreserve(
+            &submitter,
+            <SubmissionDeposits<T>>::take(submission_id),
+        );
+        <BountyPayouts<T>>::insert(bounty_id, (total_paid, approved));
+        if !split.is_empty() {
+            for (recipient, slice) in slices {
+                Self::deposit_event(RawEvent::SubmissionSlicePaid(
+                    bounty_id,
+                    submission_id,
+                    recipient,
+                    slice,
+                ));
+            }
+        }
+        Self::deposit_event(RawEvent::SubmissionTimedOut(
+            submission_id,
+            TimeoutOutcome::Approved(amount),
+        ));
+        if closing {
+            Self::close_bounty(new_bounty);
+            Self::deposit_event(RawEvent::BountyClosed(
+                bounty_id, total_paid, approved,
+            ));
+        } else {
+            <Bounties<T>>::insert(bounty_id, new_bounty);
+        }
+        Ok(())
+    }
+    /// Slashes the dispute deposit of a rejection the arbitration vote
+    /// upheld to the bounty, or burns it if the bounty closed meanwhile
+    fn uphold_rejection(
+        submission_id: T::SubmissionId,
+        submission: BountySub<T>,
+        deposit: BalanceOf<T>,
+    ) -> DispatchResult {
+        let bounty_id = submission.bounty_id();
+        let submitter = submission.submitter();
+        if let Some(bounty) = <Bounties<T>>::get(bounty_id) {
+            let bounty = Self::slash_to_bounty(bounty, &submitter, deposit)?;
+            <Bounties<T>>::insert(bounty_id, bounty);
+        } else {
+            T::Currency::slash_reserved(&submitter, deposit);
+        }
+        Self::deposit_event(RawEvent::RejectionUpheld(
+            bounty_id,
+            submission_id,
+            submitter,
+            deposit,
+        ));
+        Ok(())
+    }
+    /// The bounty, unless it does not exist or was closed
+    fn open_bounty(id: T::BountyId) -> Result<Bounty<T>, DispatchError> {
+        ensure!(
+            <ClosedBounties<T>>::get(id).is_none(),
+            Error::<T>::BountyClosed
+        );
+        <Bounties<T>>::get(id).ok_or_else(|| Error::<T>::BountyDNE.into())
+    }
+    /// The voting shares of the submitter in the org the bounty requires
+    /// them to be a member of, None if it requires none
+    fn required_org_shares(
+        bounty_id: T::BountyId,
+        submitter: &T::AccountId,
+    ) -> Result<Option<T::Shares>, DispatchError> {
+        let org = match <RequiredOrgs<T>>::get(bounty_id) {
+            Some(org) => org,
+            None => return Ok(None),
+        };
+        // the members of the group are sorted
+        let is_member = <org::Module<T>>::get_group(org)
+            .map_or(false, |group| group.0.binary_search(submitter).is_ok());
+        ensure!(is_member, Error::<T>::SubmitterNotInRequiredOrg);
+        let shares = <org::Module<T>>::get_share_profile(org, submitter)
+            .map_or_else(Zero::zero, |profile| profile.total());
+        Ok(Some(shares))
+    }
+    /// What the bounty can still pay out, which excludes the existential
+    /// deposit keeping the account of a native bounty alive
+    fn payable(bounty: &Bounty<T>) -> BalanceOf<T> {
+        match bounty.asset() {
+            Some(_) => bounty.total(),
+            None => {
+                bounty
+                    .total()
+                    .saturating_sub(T::Currency::minimum_balance())
+            }
+        }
+    }
+    /// Refunds what is left in the bounty to the depositer, reaping the
+    /// bounty account
+    fn refund_depositer(bounty: Bounty<T>) -> Result<Bounty<T>, DispatchError> {
+        let left = bounty.total();
+        if left.is_zero() {
+            return Ok(bounty)
+        }
+        T::Assets::transfer(
+            bounty.asset(),
+            &Self::bounty_account_id(bounty.id()),
+            &bounty.depositer(),
+            left,
+            ExistenceRequirement::AllowDeath,
+        )?;
+        Ok(bounty.subtract_total(left))
+    }
+    /// Moves the bounty to `ClosedBounties`, refunding the deposits of the
+    /// submissions still awaiting review. Its tags and org are kept for
+    /// history but it is no longer indexed by them.
+    fn close_bounty(bounty: Bounty<T>) {
+        let id = bounty.id();
+        let pending = <BountySubmissions<T>>::iter_prefix(id)
+            .filter_map(|(submission_id, _)| {
+                <Submissions<T>>::get(submission_id)
+                    .map(|submission| (submission_id, submission))
+            })
+            .collect::<Vec<_>>();
+        for (submission_id, submission) in pending {
+            let submitter = submission.submitter();
+            T::Currency::unreserve(
+                &submitter,
+                <SubmissionDeposits<T>>::take(submission_id),
+            );
+            Self::release_issue(submission_id);
+            Self::close_submission(id, submission_id, &submitter);
+        }
+        for tag in <BountyTags<T>>::get(id) {
+            <BountiesByTag<T>>::remove(tag, id);
+        }
+        if let Some(org) = <BountyOrgs<T>>::get(id) {
+            <BountiesByOrg<T>>::remove(org, id);
+        }
+        <Bounties<T>>::remove(id);
+        <SubmissionKinds<T>>::remove(id);
+        <ReviewerRewards<T>>::remove(id);
+        <BountyDeadlines<T>>::remove(id);
+        <BountyArbitration<T>>::remove(id);
+        <BountyMatching<T>>::remove(id);
+        <ReviewPolicies<T>>::remove(id);
+        <ClosedBounties<T>>::insert(id, bounty);
+    }
+    /// The tags without duplicates, if they are valid and few enough
+    fn checked_tags(
+        tags: Vec<BountyTag>,
+    ) -> Result<Vec<BountyTag>, DispatchError> {
+        let mut unique = Vec::with_capacity(tags.len());
+        for tag in tags {
+            ensure!(is_valid_tag(&tag), Error::<T>::InvalidBountyTag);
+            if !unique.contains(&tag) {
+                unique.push(tag);
+            }
+        }
+        ensure!(
+            unique.len() as u32 <= T::MaxBountyTags::get(),
+            Error::<T>::TooManyBountyTags
+        );
+        Ok(unique)
+    }
+    fn index_tags(bounty_id: T::BountyId, tags: Vec<BountyTag>) {
+        if tags.is_empty() {
+            return
+        }
+        for tag in &tags {
+            <BountiesByTag<T>>::insert(tag, bounty_id, ());
+        }
+        <BountyTags<T>>::insert(bounty_id, tags);
+    }
+    fn unindex_tags(bounty_id: T::BountyId) {
+        for tag in <BountyTags<T>>::take(bounty_id) {
+            <BountiesByTag<T>>::remove(tag, bounty_id);
+        }
+    }
+    /// Removes a submission which is no longer awaiting review
+    fn close_submission(
+        bounty_id: T::BountyId,
+        submission_id: T::SubmissionId,
+        submitter: &T::AccountId,
+    ) {
+        <Submissions<T>>::remove(submission_id);
+        <BountySubmissions<T>>::remove(bounty_id, submission_id);
+        <SubmissionSplits<T>>::remove(submission_id);
+        <SubmissionReviewers<T>>::remove(submission_id);
+        // the issue of an approved submission stays claimed
+        <SubmissionIssues<T>>::remove(submission_id);
+        <PendingSubmissions<T>>::mutate(bounty_id, submitter, |pending| {
+            *pending = pending.saturating_sub(1)
+        });
+    }
+    /// Lets the issue claimed by a submission which was not approved be
+    /// claimed again, call before `close_submission`
+    fn release_issue(submission_id: T::SubmissionId) {
+        if let Some(issue) = <SubmissionIssues<T>>::take(submission_id) {
+            <IssueHashSet>::remove(issue);
+        }
+    }
     fn _recursive_remove_bounty(id: T::BountyId) {
         <Bounties<T>>::remove(id);
-        <Submissions<T>>::iter()
-            .filter(|(_, app)| app.bounty_id() == id)
+        <SubmissionKinds<T>>::remove(id);
+        <BountyDeadlines<T>>::remove(id);
+        <BountyMatching<T>>::remove(id);
+        <ReviewPolicies<T>>::remove(id);
+        Self::unindex_tags(id);
+        if let Some(org) = <BountyOrgs<T>>::take(id) {
+            <BountiesByOrg<T>>::remove(org, id);
+        }
+        <BountySubmissions<T>>::drain_prefix(id)
             .for_each(|(app_id, _)| <Submissions<T>>::remove(app_id));
     }
 }
@@ -0,0 +1,154 @@
+//! Storage migrations run by `on_runtime_upgrade`
+//!
+//! A change to the layout of stored values bumps `STORAGE_VERSION` and adds
+//! a step to `migrate` translating the values from their previous layout,
+//! which is kept in `util` next to the current one. The steps run in order
+//! from the stored version, and each translates the entries in storage key
+//! order, which is the same on every node.
+//!
+//! With the `try-runtime` feature the storage is checked before and after
+//! the migration and the upgrade panics if a value was lost.
+use crate::{
+    AssetIdOf,
+    BalanceOf,
+    Bounties,
+    BountySubmissions,
+    ClosedBounties,
+    Releases,
+    StorageVersion,
+    Submissions,
+    Trait,
+};
+use frame_support::{
+    storage::{
+        IterableStorageMap,
+        StorageDoubleMap,
+        StorageValue,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use frame_system::Trait as System;
+use sp_std::cell::Cell;
+use util::bounty::{
+    BountyInformationV1,
+    BountySubmissionV1,
+    SubmissionState,
+};
+
+type BountyV1<T> = BountyInformationV1<
+    <T as Trait>::BountyId,
+    <T as Trait>::IpfsReference,
+    <T as System>::AccountId,
+    AssetIdOf<T>,
+    BalanceOf<T>,
+>;
+type BountySubV1<T> = BountySubmissionV1<
+    <T as Trait>::BountyId,
+    <T as Trait>::SubmissionId,
+    <T as Trait>::IpfsReference,
+    <T as System>::AccountId,
+    BalanceOf<T>,
+    SubmissionState,
+>;
+
+/// Migrates the storage from the stored version to `STORAGE_VERSION`
+pub fn migrate<T: Trait>() -> Weight {
+    #[cfg(feature = "try-runtime")]
+    let counts = pre_migrate::<T>();
+    let mut weight = T::DbWeight::get().reads(1);
+    if StorageVersion::get() == Releases::V1 {
+        weight = weight.saturating_add(v1_to_v2::<T>());
+    }
+    if StorageVersion::get() == Releases::V2 {
+        weight = weight.saturating_add(v2_to_v3::<T>());
+    }
+    #[cfg(feature = "try-runtime")]
+    post_migrate::<T>(counts);
+    weight
+}
+
+/// Gives the bounties and submissions stored before their blocks were
+/// tracked zero blocks and marks them as not timestamped
+fn v1_to_v2<T: Trait>() -> Weight {
+    let values = Cell::new(0);
+    <Bounties<T>>::translate::<BountyV1<T>, _>(|_, old| {
+        values.set(values.get() + 1);
+        Some(old.into())
+    });
+    <ClosedBounties<T>>::translate::<BountyV1<T>, _>(|_, old| {
+        values.set(values.get() + 1);
+        Some(old.into())
+    });
+    <Submissions<T>>::translate::<BountySubV1<T>, _>(|_, old| {
+        values.set(values.get() + 1);
+        Some(old.into())
+    });
+    StorageVersion::put(Releases::V2);
+    T::DbWeight::get().reads_writes(values.get(), values.get() + 1)
+}
+
+/// Indexes the submissions awaiting review by their bounty
+fn v2_to_v3<T: Trait>() -> Weight {
+    let mut values = 0;
+    for (id, submission) in <Submissions<T>>::iter() {
+        <BountySubmissions<T>>::insert(submission.bounty_id(), id, ());
+        values += 1;
+    }
+    StorageVersion::put(Releases::V3);
+    T::DbWeight::get().reads_writes(values, values + 1)
+}
+
+/// The number of open bounties, closed bounties and submissions in the
+/// layout of the stored version
+#[cfg(feature = "try-runtime")]
+fn pre_migrate<T: Trait>() -> (usize, usize, usize) {
+    use frame_support::storage::migration::StorageIterator;
+    match StorageVersion::get() {
+        Releases::V1 => {
+            (
+                StorageIterator::<BountyV1<T>>::new(b"Bounty", b"Bounties")
+                    .count(),
+                StorageIterator::<BountyV1<T>>::new(
+                    b"Bounty",
+                    b"ClosedBounties",
+                )
+                .count(),
+                StorageIterator::<BountySubV1<T>>::new(
+                    b"Bounty",
+                    b"Submissions",
+                )
+                .count(),
+            )
+        }
+        _ => {
+            (
+                <Bounties<T>>::iter().count(),
+                <ClosedBounties<T>>::iter().count(),
+                <Submissions<T>>::iter().count(),
+            )
+        }
+    }
+}
+
+/// Panics unless the storage is at the latest version, every bounty and
+/// submission still decodes and every submission is indexed by its bounty
+#[cfg(feature = "try-runtime")]
+fn post_migrate<T: Trait>(counts: (usize, usize, usize)) {
+    assert_eq!(StorageVersion::get(), crate::STORAGE_VERSION);
+    assert_eq!(
+        (
+            <Bounties<T>>::iter().count(),
+            <ClosedBounties<T>>::iter().count(),
+            <Submissions<T>>::iter().count(),
+        ),
+        counts,
+        "bounties or submissions were lost in the migration"
+    );
+    assert!(
+        <Submissions<T>>::iter().all(|(id, submission)| {
+            <BountySubmissions<T>>::contains_key(submission.bounty_id(), id)
+        }),
+        "submissions are missing from the bounty index"
+    );
+}
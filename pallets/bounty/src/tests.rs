@@ -5,6 +5,12 @@ use frame_support::{
     impl_outer_event,
     impl_outer_origin,
     parameter_types,
+    storage::{
+        unhashed,
+        StorageDoubleMap,
+        StorageMap,
+    },
+    traits::OnRuntimeUpgrade,
     weights::Weight,
 };
 use frame_system::{self as system,};
@@ -18,6 +24,21 @@ use sp_runtime::{
     traits::IdentityLookup,
     Perbill,
 };
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+};
+use util::{
+    bounty::{
+        BountyInformationV1,
+        BountySubmissionV1,
+    },
+    organization::OrganizationSource,
+    traits::{
+        CidFormat,
+        RegisterOrganization,
+    },
+};
 
 // type aliases
 pub type AccountId = u64;
@@ -35,6 +56,7 @@ impl_outer_event! {
     pub enum TestEvent for Test {
         system<T>,
         pallet_balances<T>,
+        org<T>,
         bounty<T>,
     }
 }
@@ -87,20 +109,182 @@ impl pallet_balances::Trait for Test {
     type WeightInfo = ();
     type MaxLocks = MaxLocks;
 }
+thread_local! {
+    // (threshold id, org) pairs registered with the mock vote module
+    static THRESHOLDS: RefCell<Vec<(u64, u64)>> = RefCell::new(Vec::new());
+    // outcomes of the votes opened by the mock vote module, indexed by vote id
+    static OUTCOMES: RefCell<Vec<VoteOutcome>> = RefCell::new(Vec::new());
+}
+pub struct MockVote;
+impl MockVote {
+    fn register_threshold(id: u64, org: u64) {
+        THRESHOLDS.with(|t| t.borrow_mut().push((id, org)));
+    }
+    fn set_outcome(vote_id: u64, outcome: VoteOutcome) {
+        OUTCOMES.with(|o| o.borrow_mut()[vote_id as usize] = outcome);
+    }
+}
+impl ThresholdVote<u64, u32, u64> for MockVote {
+    type ThresholdId = u64;
+    type VoteId = u64;
+    fn threshold_org(id: u64) -> Option<u64> {
+        THRESHOLDS.with(|t| {
+            t.borrow().iter().find(|(i, _)| *i == id).map(|(_, o)| *o)
+        })
+    }
+    fn default_threshold(org: u64) -> Option<u64> {
+        THRESHOLDS.with(|t| {
+            t.borrow()
+                .iter()
+                .rev()
+                .find(|(_, o)| *o == org)
+                .map(|(i, _)| *i)
+        })
+    }
+    fn open_threshold_vote(
+        id: u64,
+        _topic: Option<u32>,
+        _duration: Option<u64>,
+    ) -> Result<u64, DispatchError> {
+        Self::threshold_org(id)
+            .ok_or(DispatchError::Other("threshold does not exist"))?;
+        Ok(OUTCOMES.with(|o| {
+            let mut o = o.borrow_mut();
+            o.push(VoteOutcome::Voting);
+            o.len() as u64 - 1
+        }))
+    }
+    fn threshold_vote_outcome(
+        vote_id: u64,
+    ) -> Result<VoteOutcome, DispatchError> {
+        OUTCOMES
+            .with(|o| o.borrow().get(vote_id as usize).cloned())
+            .ok_or(DispatchError::Other("vote does not exist"))
+    }
+}
+// mock cids are numbers, `BAD_CID` stands in for malformed bytes
+pub const BAD_CID: u32 = u32::MAX;
+pub struct MockCidFormat;
+impl CidFormat for MockCidFormat {
+    fn is_valid(encoded: &[u8]) -> bool {
+        encoded != BAD_CID.encode().as_slice()
+    }
+}
+parameter_types! {
+    pub const OrgTreasury: ModuleId = ModuleId(*b"org/trsy");
+    pub const SpendVoteDuration: BlockNumber = 10;
+    pub const MaxMembers: u32 = 100;
+    pub const HandleCooldown: BlockNumber = 10;
+    pub const MaxApplicationDeposit: u64 = 50;
+    pub const MaxApplicationCooldown: BlockNumber = 10;
+    pub const MaxPendingApplications: u32 = 2;
+    pub const ShareGrantExpiry: BlockNumber = 10;
+    pub const MaxPendingShareGrants: u32 = 3;
+}
+impl org::Trait for Test {
+    type Event = TestEvent;
+    type Cid = u32;
+    type CidFormat = MockCidFormat;
+    type OrgId = u64;
+    type Shares = u64;
+    type OrgCurrency = Balances;
+    type OrgTreasury = OrgTreasury;
+    type SpendVote = MockVote;
+    type SpendVoteDuration = SpendVoteDuration;
+    type MaxMembers = MaxMembers;
+    type HandleCooldown = HandleCooldown;
+    type MaxApplicationDeposit = MaxApplicationDeposit;
+    type MaxApplicationCooldown = MaxApplicationCooldown;
+    type MaxPendingApplications = MaxPendingApplications;
+    type ShareGrantExpiry = ShareGrantExpiry;
+    type MaxPendingShareGrants = MaxPendingShareGrants;
+    type ShareChangeHandler = ();
+    type LifecycleHandler = ();
+}
+thread_local! {
+    static ASSET_BALANCES: RefCell<BTreeMap<(u32, AccountId), u64>> =
+        RefCell::new(BTreeMap::new());
+}
+/// Balances for the native currency and an in memory ledger for the rest
+pub struct Assets;
+impl Assets {
+    fn mint(asset: u32, who: AccountId, amount: u64) {
+        ASSET_BALANCES.with(|b| {
+            *b.borrow_mut().entry((asset, who)).or_default() += amount
+        });
+    }
+}
+impl MultiCurrency<AccountId> for Assets {
+    type AssetId = u32;
+    type Balance = u64;
+
+    fn free_balance(asset: Option<u32>, who: &AccountId) -> u64 {
+        match asset {
+            None => Balances::free_balance(who),
+            Some(a) => {
+                ASSET_BALANCES
+                    .with(|b| b.borrow().get(&(a, *who)).copied().unwrap_or(0))
+            }
+        }
+    }
+    fn transfer(
+        asset: Option<u32>,
+        from: &AccountId,
+        to: &AccountId,
+        amount: u64,
+        existence: ExistenceRequirement,
+    ) -> DispatchResult {
+        let a = match asset {
+            None => {
+                return <Balances as Currency<AccountId>>::transfer(
+                    from, to, amount, existence,
+                )
+            }
+            Some(a) => a,
+        };
+        ensure!(
+            Self::free_balance(asset, from) >= amount,
+            DispatchError::Other("InsufficientAssetBalance")
+        );
+        ASSET_BALANCES.with(|b| {
+            let mut b = b.borrow_mut();
+            *b.entry((a, *from)).or_default() -= amount;
+            *b.entry((a, *to)).or_default() += amount;
+        });
+        Ok(())
+    }
+}
 parameter_types! {
     pub const Foundation: ModuleId = ModuleId(*b"fundacon");
     pub const MinDeposit: u64 = 10;
     pub const MinContribution: u64 = 5;
+    pub const SubmissionDeposit: u64 = 2;
+    pub const MaxPendingSubmissions: u32 = 1;
+    pub const MaxBountyAdmins: u32 = 3;
+    pub const MaxBountyTags: u32 = 2;
+    pub const MaxPayoutSplits: u32 = 2;
+    pub const DisputeWindow: BlockNumber = 10;
+    pub const DisputeDeposit: u64 = 5;
+    pub const ReviewTimeout: BlockNumber = 20;
 }
 impl Trait for Test {
     type Event = TestEvent;
     type IpfsReference = u32;
     type Currency = Balances;
+    type Assets = Assets;
     type BountyId = u64;
     type SubmissionId = u64;
     type Foundation = Foundation;
     type MinDeposit = MinDeposit;
     type MinContribution = MinContribution;
+    type SubmissionDeposit = SubmissionDeposit;
+    type MaxPendingSubmissions = MaxPendingSubmissions;
+    type MaxBountyAdmins = MaxBountyAdmins;
+    type MaxBountyTags = MaxBountyTags;
+    type MaxPayoutSplits = MaxPayoutSplits;
+    type DisputeWindow = DisputeWindow;
+    type DisputeDeposit = DisputeDeposit;
+    type ReviewTimeout = ReviewTimeout;
 }
 pub type System = system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
@@ -112,7 +296,8 @@ fn random(output_len: usize) -> Vec<u8> {
     buf
 }
 
-fn get_last_event() -> RawEvent<u64, u32, u64, u64, u64> {
+fn get_last_event(
+) -> RawEvent<u64, u32, u64, u64, u64, u32, u64, u64, u64, u32, u64, u64> {
     System::events()
         .into_iter()
         .map(|r| r.event)
@@ -136,7 +321,11 @@ fn new_test_ext() -> sp_io::TestExternalities {
     }
     .assimilate_storage(&mut t)
     .unwrap();
+    GenesisConfig {}.assimilate_storage::<Test>(&mut t).unwrap();
     let mut ext: sp_io::TestExternalities = t.into();
+    ASSET_BALANCES.with(|b| b.borrow_mut().clear());
+    THRESHOLDS.with(|t| t.borrow_mut().clear());
+    OUTCOMES.with(|o| o.borrow_mut().clear());
     ext.execute_with(|| System::set_block_number(1));
     ext
 }
@@ -157,6 +346,10 @@ fn post_bounty_works() {
                 random(10),
                 10u32, // cid
                 9,     // amount
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
             ),
             Error::<Test>::BountyPostMustExceedMinDeposit,
         );
@@ -166,6 +359,10 @@ fn post_bounty_works() {
                 random(10),
                 10u32, // cid
                 101,   // amount
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
             ),
             sp_runtime::DispatchError::Module {
                 index: 0,
@@ -179,6 +376,10 @@ fn post_bounty_works() {
             issue_hash.clone(),
             10u32, // constitution
             10,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
         ));
         assert_eq!(RawEvent::BountyPosted(1, 10, 1, 10), get_last_event());
         assert_noop!(
@@ -187,12 +388,108 @@ fn post_bounty_works() {
                 issue_hash,
                 10u32, // constitution
                 10,    // funding reserved
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
             ),
             Error::<Test>::IssueAlreadyClaimedForBountyOrSubmission
         );
     });
 }
 
+#[test]
+fn post_bounty_records_submission_kind() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32, // cid
+            10,    // amount
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_eq!(Bounty::submission_kinds(1), SubmissionKind::Any);
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            11u32, // cid
+            10,    // amount
+            None,
+            SubmissionKind::PullRequest,
+            vec![],
+            vec![],
+        ));
+        assert_eq!(Bounty::submission_kinds(2), SubmissionKind::PullRequest);
+    });
+}
+
+#[test]
+fn bounties_are_indexed_by_tag() {
+    new_test_ext().execute_with(|| {
+        let tag = |name| util::bounty::bounty_tag(name).unwrap();
+        let mut upper = tag("rust");
+        upper[0] = b'R';
+        assert_noop!(
+            Bounty::post_bounty(
+                Origin::signed(1),
+                random(10),
+                10u32, // cid
+                10,    // amount
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![upper],
+            ),
+            Error::<Test>::InvalidBountyTag
+        );
+        assert_noop!(
+            Bounty::post_bounty(
+                Origin::signed(1),
+                random(10),
+                10u32, // cid
+                10,    // amount
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![tag("rust"), tag("docs"), tag("security")],
+            ),
+            Error::<Test>::TooManyBountyTags
+        );
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32, // cid
+            10,    // amount
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![tag("rust"), tag("docs"), tag("rust")],
+        ));
+        assert_eq!(Bounty::bounty_tags(1), vec![tag("rust"), tag("docs")]);
+        assert!(Bounty::bounties_by_tag(tag("rust"), 1).is_some());
+        assert!(Bounty::bounties_by_tag(tag("docs"), 1).is_some());
+        assert_noop!(
+            Bounty::set_bounty_tags(Origin::signed(2), 1, vec![]),
+            Error::<Test>::NotAuthorizedToSetBountyTags
+        );
+        assert_ok!(Bounty::set_bounty_tags(
+            Origin::signed(1),
+            1,
+            vec![tag("frontend")]
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyTagsSet(1, 1, vec![tag("frontend")])
+        );
+        assert!(Bounty::bounties_by_tag(tag("rust"), 1).is_none());
+        assert!(Bounty::bounties_by_tag(tag("docs"), 1).is_none());
+        assert!(Bounty::bounties_by_tag(tag("frontend"), 1).is_some());
+    });
+}
+
 #[test]
 fn contribution_works() {
     new_test_ext().execute_with(|| {
@@ -201,24 +498,28 @@ fn contribution_works() {
             random(10),
             10u32, // constitution
             10,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
         ));
         assert_noop!(
-            Bounty::contribute_to_bounty(Origin::signed(2), 2, 5),
+            Bounty::contribute_to_bounty(Origin::signed(2), 2, 5, None),
             Error::<Test>::BountyDNE
         );
         assert_noop!(
-            Bounty::contribute_to_bounty(Origin::signed(2), 1, 4),
+            Bounty::contribute_to_bounty(Origin::signed(2), 1, 4, None),
             Error::<Test>::ContributionMustExceedModuleMin
         );
         assert_noop!(
-            Bounty::contribute_to_bounty(Origin::signed(2), 1, 99),
+            Bounty::contribute_to_bounty(Origin::signed(2), 1, 99, None),
             sp_runtime::DispatchError::Module {
                 index: 0,
                 error: 3,
                 message: Some("InsufficientBalance",),
             },
         );
-        assert_ok!(Bounty::contribute_to_bounty(Origin::signed(2), 1, 5));
+        assert_ok!(Bounty::contribute_to_bounty(Origin::signed(2), 1, 5, None));
         assert_eq!(
             RawEvent::BountyRaiseContribution(2, 5, 1, 15, 10),
             get_last_event()
@@ -236,6 +537,7 @@ fn submission_works() {
                 random(10),
                 10u32,
                 15u64,
+                vec![],
             ),
             Error::<Test>::BountyDNE
         );
@@ -244,6 +546,10 @@ fn submission_works() {
             random(10),
             10u32, // constitution
             21,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
         ));
         assert_noop!(
             Bounty::submit_for_bounty(
@@ -252,8 +558,9 @@ fn submission_works() {
                 random(10),
                 10u32,
                 15u64,
+                vec![],
             ),
-            Error::<Test>::DepositerCannotSubmitForBounty
+            Error::<Test>::AdminCannotSubmitForBounty
         );
         assert_noop!(
             Bounty::submit_for_bounty(
@@ -262,19 +569,32 @@ fn submission_works() {
                 random(10),
                 10u32,
                 22u64,
+                vec![],
             ),
             Error::<Test>::BountySubmissionExceedsTotalAvailableFunding,
         );
         let issue_hash = random(10);
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(2),
+                1,
+                random(10),
+                10u32,
+                10u64,
+                vec![],
+            ),
+            Error::<Test>::SubmissionMustDifferFromBountyInfo,
+        );
         assert_ok!(Bounty::submit_for_bounty(
             Origin::signed(2),
             1,
             issue_hash.clone(),
-            10u32,
+            11u32,
             10u64,
+            vec![],
         ));
         assert_eq!(
-            RawEvent::BountySubmissionPosted(2, 1, 10, 1, 10, 10),
+            RawEvent::BountySubmissionPosted(2, 1, 10, 1, 10, 11, None),
             get_last_event()
         );
         assert_noop!(
@@ -284,6 +604,7 @@ fn submission_works() {
                 issue_hash,
                 10u32, // constitution
                 10,    // funding reserved
+                vec![],
             ),
             Error::<Test>::IssueAlreadyClaimedForBountyOrSubmission
         );
@@ -302,6 +623,10 @@ fn submission_approval_works() {
             random(10),
             10u32, // constitution
             21,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
         ));
         assert_noop!(
             Bounty::approve_bounty_submission(Origin::signed(1), 1),
@@ -311,8 +636,9 @@ fn submission_approval_works() {
             Origin::signed(2),
             1,
             random(10),
-            10u32,
+            11u32,
             10u64,
+            vec![],
         ));
         assert_noop!(
             Bounty::approve_bounty_submission(Origin::signed(2), 1),
@@ -325,3 +651,1534 @@ fn submission_approval_works() {
         assert_eq!(Balances::total_balance(&1), 79);
     });
 }
+
+#[test]
+fn submissions_reserve_deposit_and_are_capped() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            21,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_eq!(Balances::reserved_balance(&2), 2);
+        assert_eq!(Bounty::pending_submissions(1, 2), 1);
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(2),
+                1,
+                random(10),
+                12u32,
+                10u64,
+                vec![],
+            ),
+            Error::<Test>::TooManyPendingSubmissions
+        );
+        assert_noop!(
+            Bounty::withdraw_bounty_submission(Origin::signed(3), 1),
+            Error::<Test>::NotAuthorizedToWithdrawSubmission
+        );
+        assert_ok!(Bounty::withdraw_bounty_submission(Origin::signed(2), 1));
+        assert_eq!(
+            RawEvent::BountySubmissionWithdrawn(2, 1, 1, 2),
+            get_last_event()
+        );
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Bounty::pending_submissions(1, 2), 0);
+        assert!(Bounty::submissions(1).is_none());
+        // the cap frees up once the pending submission is gone
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 2));
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 108);
+        assert_eq!(Bounty::pending_submissions(1, 2), 0);
+    });
+}
+
+#[test]
+fn only_approved_submissions_keep_their_issue_claimed() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            21,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let issue = random(10);
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            issue.clone(),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::withdraw_bounty_submission(Origin::signed(2), 1));
+        assert!(Bounty::submission_issue(1).is_none());
+        // the withdrawn submission's issue may be submitted again
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            issue.clone(),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::reject_bounty_submission(
+            Origin::signed(1),
+            2,
+            RejectionReason::Unsuitable
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            issue.clone(),
+            13u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 3));
+        assert!(Bounty::submission_issue(3).is_none());
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(3),
+                1,
+                issue,
+                14u32,
+                10u64,
+                vec![],
+            ),
+            Error::<Test>::IssueAlreadyClaimedForBountyOrSubmission
+        );
+    });
+}
+
+#[test]
+fn rejection_refunds_or_slashes_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            21,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::reject_bounty_submission(
+                Origin::signed(2),
+                1,
+                RejectionReason::Spam
+            ),
+            Error::<Test>::NotAuthorizedToRejectBountySubmissions
+        );
+        assert_ok!(Bounty::reject_bounty_submission(
+            Origin::signed(1),
+            1,
+            RejectionReason::Unsuitable
+        ));
+        assert_eq!(
+            RawEvent::BountySubmissionRejected(
+                1,
+                1,
+                2,
+                RejectionReason::Unsuitable,
+                2,
+                1
+            ),
+            get_last_event()
+        );
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 98);
+        assert_ok!(Bounty::reject_bounty_submission(
+            Origin::signed(1),
+            2,
+            RejectionReason::Spam
+        ));
+        assert_eq!(Balances::total_balance(&3), 198);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 23);
+        assert_eq!(Balances::free_balance(&Bounty::bounty_account_id(1)), 23);
+        assert_noop!(
+            Bounty::reject_bounty_submission(
+                Origin::signed(1),
+                2,
+                RejectionReason::Spam
+            ),
+            Error::<Test>::SubmissionDNE
+        );
+    });
+}
+
+#[test]
+fn bounty_admins_share_review_rights() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            20,
+            None,
+            SubmissionKind::Any,
+            vec![2, 1],
+            vec![],
+        ));
+        assert_eq!(Bounty::bounties(1).unwrap().admins(), vec![1, 2]);
+        assert_noop!(
+            Bounty::add_bounty_admin(Origin::signed(3), 1, 3),
+            Error::<Test>::NotAuthorizedToManageBountyAdmins
+        );
+        assert_noop!(
+            Bounty::add_bounty_admin(Origin::signed(2), 1, 1),
+            Error::<Test>::AccountIsAlreadyBountyAdmin
+        );
+        assert_ok!(Bounty::add_bounty_admin(Origin::signed(2), 1, 4));
+        assert_eq!(RawEvent::BountyAdminAdded(1, 2, 4), get_last_event());
+        assert_noop!(
+            Bounty::add_bounty_admin(Origin::signed(2), 1, 5),
+            Error::<Test>::TooManyBountyAdmins
+        );
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(4),
+                1,
+                random(10),
+                11u32,
+                10u64,
+                vec![],
+            ),
+            Error::<Test>::AdminCannotSubmitForBounty
+        );
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        // the co-depositer approves without the original depositer
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(2), 1));
+        assert_eq!(
+            RawEvent::BountyPaymentExecuted(1, 10, 1, 10, 3, 10, 11, 2),
+            get_last_event()
+        );
+        assert_ok!(Bounty::remove_bounty_admin(Origin::signed(4), 1, 1));
+        assert_eq!(RawEvent::BountyAdminRemoved(1, 4, 1), get_last_event());
+        assert_noop!(
+            Bounty::remove_bounty_admin(Origin::signed(1), 1, 2),
+            Error::<Test>::NotAuthorizedToManageBountyAdmins
+        );
+        assert_ok!(Bounty::remove_bounty_admin(Origin::signed(2), 1, 2));
+        assert_noop!(
+            Bounty::remove_bounty_admin(Origin::signed(4), 1, 2),
+            Error::<Test>::AccountIsNotBountyAdmin
+        );
+        assert_noop!(
+            Bounty::remove_bounty_admin(Origin::signed(4), 1, 4),
+            Error::<Test>::CannotRemoveLastBountyAdmin
+        );
+    });
+}
+
+#[test]
+fn assigned_reviewer_is_rewarded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            50,
+            None,
+            SubmissionKind::Any,
+            vec![2],
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            11u32,
+            20u64,
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::assign_reviewer(Origin::signed(4), 1, 2),
+            Error::<Test>::NotAuthorizedToAssignReviewer
+        );
+        assert_noop!(
+            Bounty::assign_reviewer(Origin::signed(1), 1, 5),
+            Error::<Test>::ReviewerMustBeBountyAdmin
+        );
+        assert_noop!(
+            Bounty::set_reviewer_reward(
+                Origin::signed(3),
+                1,
+                Some(Permill::from_percent(10))
+            ),
+            Error::<Test>::NotAuthorizedToSetReviewerReward
+        );
+        assert_ok!(Bounty::set_reviewer_reward(
+            Origin::signed(1),
+            1,
+            Some(Permill::from_percent(10))
+        ));
+        assert_ok!(Bounty::assign_reviewer(Origin::signed(1), 1, 2));
+        assert_eq!(
+            RawEvent::SubmissionReviewerAssigned(1, 1, 1, 2),
+            get_last_event()
+        );
+        assert_eq!(Bounty::submission_reviewers(1), Some(2));
+        // the assigned reviewer earns 10% of the 20 requested on top
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(2), 1));
+        assert_eq!(Balances::total_balance(&2), 100);
+        assert_eq!(Balances::total_balance(&3), 220);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 28);
+        assert_eq!(Bounty::submission_reviewers(1), None);
+        // approving while assigned to someone else pays no reward
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::assign_reviewer(Origin::signed(2), 2, 2));
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 2));
+        assert_eq!(Balances::total_balance(&2), 100);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 18);
+    });
+}
+
+#[test]
+fn bounties_are_funded_in_one_asset() {
+    new_test_ext().execute_with(|| {
+        Assets::mint(7, 1, 100);
+        Assets::mint(7, 2, 50);
+        assert_noop!(
+            Bounty::post_bounty(
+                Origin::signed(1),
+                random(10),
+                10u32,
+                40,
+                Some(7),
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            ),
+            Error::<Test>::AssetNotAcceptedForBounties
+        );
+        let usd = AssetMetadata::new(b"USD".to_vec(), 2);
+        assert_noop!(
+            Bounty::set_bounty_asset(Origin::signed(1), 7, Some(usd.clone())),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Bounty::set_bounty_asset(
+            Origin::root(),
+            7,
+            Some(usd.clone())
+        ));
+        assert_eq!(RawEvent::BountyAssetSet(7, Some(usd)), get_last_event());
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            40,
+            Some(7),
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_eq!(Bounty::bounties(1).unwrap().asset(), Some(7));
+        assert_eq!(Assets::free_balance(Some(7), &1), 60);
+        assert_eq!(Balances::free_balance(&1), 100);
+        // contributions must be made in the asset of the bounty
+        assert_noop!(
+            Bounty::contribute_to_bounty(Origin::signed(2), 1, 10, None),
+            Error::<Test>::ContributionAssetMustMatchBounty
+        );
+        assert_ok!(Bounty::contribute_to_bounty(
+            Origin::signed(2),
+            1,
+            10,
+            Some(7)
+        ));
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 50);
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            11u32,
+            30u64,
+            vec![],
+        ));
+        // the submission deposit stays in the native currency
+        assert_eq!(Balances::reserved_balance(&3), 2);
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 1));
+        assert_eq!(Assets::free_balance(Some(7), &3), 30);
+        assert_eq!(Balances::free_balance(&3), 200);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 20);
+        // a spam deposit is slashed rather than added to the bounty
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(4),
+            1,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::reject_bounty_submission(
+            Origin::signed(1),
+            2,
+            RejectionReason::Spam
+        ));
+        assert_eq!(Balances::total_balance(&4), 73);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 20);
+    });
+}
+
+#[test]
+fn drained_bounties_close() {
+    new_test_ext().execute_with(|| {
+        let rust = util::bounty::bounty_tag("rust").unwrap();
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            20,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![rust],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(4),
+            1,
+            random(10),
+            12u32,
+            5u64,
+            vec![],
+        ));
+        // 10 left is still enough to fund another submission
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 1));
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 10);
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            13u32,
+            6u64,
+            vec![],
+        ));
+        // 4 left is less than the minimum contribution
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 3));
+        assert_eq!(RawEvent::BountyClosed(1, 16, 2), get_last_event());
+        assert_eq!(Bounty::bounty_payouts(1), (16, 2));
+        assert!(Bounty::bounties(1).is_none());
+        assert_eq!(Bounty::closed_bounties(1).unwrap().total(), 0);
+        // the remainder is refunded to the depositer and the pending
+        // submission gets its deposit back
+        assert_eq!(Balances::total_balance(&1), 84);
+        assert_eq!(Balances::free_balance(&Bounty::bounty_account_id(1)), 0);
+        assert_eq!(Balances::reserved_balance(&4), 0);
+        assert!(Bounty::submissions(2).is_none());
+        assert!(Bounty::bounty_submissions(1, 2).is_none());
+        assert!(Bounty::bounties_by_tag(rust, 1).is_none());
+        assert_eq!(Bounty::bounty_tags(1), vec![rust]);
+        assert_noop!(
+            Bounty::contribute_to_bounty(Origin::signed(2), 1, 10, None),
+            Error::<Test>::BountyClosed
+        );
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(2),
+                1,
+                random(10),
+                14u32,
+                1u64,
+                vec![],
+            ),
+            Error::<Test>::BountyClosed
+        );
+    });
+}
+
+#[test]
+fn bounties_track_the_blocks_of_their_lifecycle() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32, // constitution
+            40,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let bounty = Bounty::bounties(1).unwrap();
+        assert!(bounty.timestamped());
+        assert_eq!(bounty.posted_at(), 1);
+        assert_eq!(bounty.first_submission_at(), None);
+        assert_eq!(bounty.resolved_at(), None);
+        System::set_block_number(3);
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        System::set_block_number(5);
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        assert_eq!(Bounty::submissions(1).unwrap().submitted_at(), 3);
+        assert_eq!(Bounty::submissions(2).unwrap().submitted_at(), 5);
+        assert_eq!(Bounty::bounties(1).unwrap().first_submission_at(), Some(3));
+        System::set_block_number(8);
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 2));
+        System::set_block_number(9);
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 1));
+        let bounty = Bounty::bounties(1).unwrap();
+        assert_eq!(bounty.posted_at(), 1);
+        assert_eq!(bounty.first_submission_at(), Some(3));
+        assert_eq!(bounty.resolved_at(), Some(8));
+    });
+}
+
+#[test]
+fn late_submissions_extend_the_deadline_up_to_the_cap() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            40,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::set_bounty_deadline(Origin::signed(2), 1, Some(20), None),
+            Error::<Test>::NotAuthorizedToSetBountyDeadline
+        );
+        assert_noop!(
+            Bounty::set_bounty_deadline(
+                Origin::signed(1),
+                1,
+                None,
+                Some((5, 8))
+            ),
+            Error::<Test>::DeadlineExtensionNeedsDeadline
+        );
+        System::set_block_number(5);
+        assert_noop!(
+            Bounty::set_bounty_deadline(Origin::signed(1), 1, Some(4), None),
+            Error::<Test>::BountyDeadlineMustNotBePassed
+        );
+        // each late submission adds the 5 block window, 8 blocks at most
+        assert_ok!(Bounty::set_bounty_deadline(
+            Origin::signed(1),
+            1,
+            Some(20),
+            Some((5, 8))
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyDeadlineSet(1, 1, Some(20))
+        );
+        let submit = |who, submission| {
+            Bounty::submit_for_bounty(
+                Origin::signed(who),
+                1,
+                random(10),
+                submission,
+                5u64,
+                vec![],
+            )
+        };
+        let deadline = || Bounty::bounty_deadline(1).unwrap().0;
+        System::set_block_number(15);
+        assert_ok!(submit(2, 11u32));
+        assert_eq!(deadline(), 20);
+        System::set_block_number(16);
+        assert_ok!(submit(3, 12u32));
+        assert_eq!(get_last_event(), RawEvent::BountyDeadlineExtended(1, 25));
+        // the last extension is cut short at the cap
+        System::set_block_number(25);
+        assert_ok!(submit(4, 13u32));
+        assert_eq!(get_last_event(), RawEvent::BountyDeadlineExtended(1, 28));
+        assert_eq!(
+            Bounty::bounty_deadline(1).unwrap().1.unwrap().extended(),
+            8
+        );
+        System::set_block_number(27);
+        assert_ok!(submit(6, 14u32));
+        assert_eq!(deadline(), 28);
+        System::set_block_number(29);
+        assert_noop!(submit(5, 15u32), Error::<Test>::BountyDeadlinePassed);
+        // removing the deadline reopens the bounty to submissions
+        assert_ok!(Bounty::set_bounty_deadline(
+            Origin::signed(1),
+            1,
+            None,
+            None
+        ));
+        assert_eq!(Bounty::bounty_deadline(1), None);
+        assert_ok!(submit(5, 15u32));
+    });
+}
+
+#[test]
+fn bounties_may_require_submitters_to_be_org_members() {
+    new_test_ext().execute_with(|| {
+        let org = <org::Module<Test>>::register_organization(
+            OrganizationSource::AccountsWeighted(vec![(2, 7), (4, 3)]),
+            Some(1),
+            1,
+        )
+        .unwrap();
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            40,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::set_required_org(Origin::signed(2), 1, Some(org)),
+            Error::<Test>::NotAuthorizedToSetRequiredOrg
+        );
+        assert_noop!(
+            Bounty::set_required_org(Origin::signed(1), 1, Some(org + 1)),
+            Error::<Test>::RequiredOrgDNE
+        );
+        assert_ok!(Bounty::set_required_org(Origin::signed(1), 1, Some(org)));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyRequiredOrgSet(1, 1, Some(org))
+        );
+        assert_eq!(Bounty::required_org(1), Some(org));
+        let submit = |who, submission| {
+            Bounty::submit_for_bounty(
+                Origin::signed(who),
+                1,
+                random(10),
+                submission,
+                5u64,
+                vec![],
+            )
+        };
+        assert_noop!(
+            submit(3, 11u32),
+            Error::<Test>::SubmitterNotInRequiredOrg
+        );
+        // members submit with their shares for the reviewers
+        assert_ok!(submit(2, 11u32));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountySubmissionPosted(2, 1, 5, 1, 10, 11, Some(7))
+        );
+        // removing the requirement opens the bounty to everyone
+        assert_ok!(Bounty::set_required_org(Origin::signed(1), 1, None));
+        assert_eq!(Bounty::required_org(1), None);
+        assert_ok!(submit(3, 12u32));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountySubmissionPosted(3, 1, 5, 2, 10, 12, None)
+        );
+    });
+}
+
+#[test]
+fn org_boards_are_funded_by_the_org_treasury() {
+    new_test_ext().execute_with(|| {
+        let org = <org::Module<Test>>::register_organization(
+            OrganizationSource::AccountsWeighted(vec![(2, 7), (4, 3)]),
+            Some(1),
+            1,
+        )
+        .unwrap();
+        let treasury = <org::Module<Test>>::org_account(org);
+        assert_ok!(<Balances as Currency<AccountId>>::transfer(
+            &3,
+            &treasury,
+            60,
+            ExistenceRequirement::AllowDeath,
+        ));
+        let post = |who, org| {
+            Bounty::post_org_bounty(
+                Origin::signed(who),
+                org,
+                random(10),
+                10u32,
+                40,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            )
+        };
+        assert_noop!(post(2, org), Error::<Test>::NotAuthorizedToPostForOrg);
+        assert_noop!(post(1, org + 1), Error::<Test>::BountyOrgDNE);
+        assert_noop!(
+            Bounty::set_bounty_poster(Origin::signed(2), org, 2, true),
+            Error::<Test>::NotAuthorizedToSetBountyPoster
+        );
+        assert_ok!(Bounty::set_bounty_poster(Origin::signed(1), org, 2, true));
+        assert_eq!(get_last_event(), RawEvent::BountyPosterSet(org, 2, true));
+        assert_ok!(post(2, org));
+        assert_eq!(get_last_event(), RawEvent::OrgBountyPosted(org, 2, 1));
+        // the treasury deposits while the poster reviews
+        let bounty = Bounty::bounties(1).unwrap();
+        assert_eq!(bounty.depositer(), treasury);
+        assert_eq!(bounty.admins(), vec![2]);
+        assert_eq!(Balances::free_balance(treasury), 20);
+        assert_eq!(Bounty::bounty_org(1), Some(org));
+        assert_eq!(Bounty::bounties_by_org(org, 1), Some(()));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            11u32,
+            36u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(2), 1));
+        // the closed bounty refunds the treasury and leaves the board
+        assert_eq!(Balances::free_balance(treasury), 24);
+        assert_eq!(Bounty::bounties_by_org(org, 1), None);
+        assert_eq!(Bounty::bounty_org(1), Some(org));
+        assert_ok!(Bounty::set_bounty_poster(Origin::signed(1), org, 2, false));
+        assert_noop!(post(2, org), Error::<Test>::NotAuthorizedToPostForOrg);
+    });
+}
+
+#[test]
+fn org_treasuries_match_contributions_up_to_the_cap() {
+    new_test_ext().execute_with(|| {
+        let org = <org::Module<Test>>::register_organization(
+            OrganizationSource::AccountsWeighted(vec![(2, 7), (4, 3)]),
+            Some(1),
+            1,
+        )
+        .unwrap();
+        let treasury = <org::Module<Test>>::org_account(org);
+        assert_ok!(<Balances as Currency<AccountId>>::transfer(
+            &3,
+            &treasury,
+            60,
+            ExistenceRequirement::AllowDeath,
+        ));
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(3),
+            random(10),
+            10u32,
+            20,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let half = Permill::from_percent(50);
+        assert_noop!(
+            Bounty::set_matching(Origin::signed(2), 1, org, half, 15),
+            Error::<Test>::NotAuthorizedToSetMatching
+        );
+        assert_noop!(
+            Bounty::set_matching(Origin::signed(1), 2, org, half, 15),
+            Error::<Test>::BountyDNE
+        );
+        assert_ok!(Bounty::set_matching(Origin::signed(1), 1, org, half, 15));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyMatchingSet(1, org, half, 15)
+        );
+        assert_ok!(Bounty::contribute_to_bounty(
+            Origin::signed(4),
+            1,
+            20,
+            None
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ContributionMatched(1, org, 10, 5)
+        );
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 50);
+        assert_eq!(Bounty::contributions(1, treasury).unwrap().total(), 10);
+        assert_eq!(Balances::free_balance(treasury), 50);
+        // the last match is cut to the cap, which ends the program
+        assert_ok!(Bounty::contribute_to_bounty(
+            Origin::signed(4),
+            1,
+            20,
+            None
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ContributionMatched(1, org, 5, 0)
+        );
+        assert_eq!(Bounty::bounty_matching(1), None);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 75);
+        assert_eq!(Balances::free_balance(treasury), 45);
+        // later contributions are taken without a match
+        assert_ok!(Bounty::contribute_to_bounty(
+            Origin::signed(4),
+            1,
+            10,
+            None
+        ));
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 85);
+        assert_eq!(Balances::free_balance(treasury), 45);
+    });
+}
+
+#[test]
+fn matching_stops_silently_when_the_treasury_runs_out() {
+    new_test_ext().execute_with(|| {
+        let org = <org::Module<Test>>::register_organization(
+            OrganizationSource::AccountsWeighted(vec![(2, 7), (4, 3)]),
+            Some(1),
+            1,
+        )
+        .unwrap();
+        let treasury = <org::Module<Test>>::org_account(org);
+        assert_ok!(<Balances as Currency<AccountId>>::transfer(
+            &3,
+            &treasury,
+            6,
+            ExistenceRequirement::AllowDeath,
+        ));
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(3),
+            random(10),
+            10u32,
+            20,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let whole = Permill::from_percent(100);
+        assert_ok!(Bounty::set_matching(Origin::signed(1), 1, org, whole, 50));
+        let other = <org::Module<Test>>::register_organization(
+            OrganizationSource::Accounts(vec![5, 6]),
+            Some(5),
+            5,
+        )
+        .unwrap();
+        assert_noop!(
+            Bounty::set_matching(Origin::signed(5), 1, other, whole, 50),
+            Error::<Test>::BountyMatchedByAnotherOrg
+        );
+        // the treasury stays alive and the contribution goes through
+        assert_ok!(Bounty::contribute_to_bounty(
+            Origin::signed(4),
+            1,
+            10,
+            None
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyRaiseContribution(4, 10, 1, 30, 10)
+        );
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 30);
+        assert_eq!(Balances::free_balance(treasury), 6);
+        assert_eq!(
+            Bounty::bounty_matching(1),
+            Some(MatchingProgram::new(org, whole, 50))
+        );
+        // a zero cap ends the program
+        assert_ok!(Bounty::set_matching(Origin::signed(1), 1, org, whole, 0));
+        assert_eq!(Bounty::bounty_matching(1), None);
+    });
+}
+
+#[test]
+fn payouts_are_split_between_co_authors() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            50,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let submit = |split| {
+            Bounty::submit_for_bounty(
+                Origin::signed(3),
+                1,
+                random(10),
+                11u32,
+                20u64,
+                split,
+            )
+        };
+        let share = Permill::from_percent;
+        assert_noop!(
+            submit(vec![(4, share(10)), (5, share(10)), (6, share(10))]),
+            Error::<Test>::TooManyPayoutSplits
+        );
+        assert_noop!(
+            submit(vec![(4, share(60)), (5, share(41))]),
+            Error::<Test>::PayoutSplitExceedsWholeAmount
+        );
+        assert_noop!(
+            submit(vec![(4, share(10)), (4, share(10))]),
+            Error::<Test>::InvalidPayoutSplit
+        );
+        assert_noop!(
+            submit(vec![(3, share(10))]),
+            Error::<Test>::InvalidPayoutSplit
+        );
+        assert_noop!(
+            submit(vec![(4, share(0))]),
+            Error::<Test>::InvalidPayoutSplit
+        );
+        assert_ok!(submit(vec![(4, share(50))]));
+        assert_eq!(Bounty::submission_splits(1), vec![(4, share(50))]);
+        assert_noop!(
+            Bounty::set_submission_split(Origin::signed(4), 1, vec![]),
+            Error::<Test>::NotAuthorizedToSetSubmissionSplit
+        );
+        // 1% of 20 rounds to nothing and is paid to the submitter
+        let split = vec![(4, share(25)), (6, Permill::from_parts(10_000))];
+        assert_ok!(Bounty::set_submission_split(
+            Origin::signed(3),
+            1,
+            split.clone()
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SubmissionSplitSet(1, 1, 3, split)
+        );
+        System::reset_events();
+        assert_ok!(Bounty::approve_bounty_submission(Origin::signed(1), 1));
+        let events = System::events()
+            .into_iter()
+            .filter_map(|r| {
+                match r.event {
+                    TestEvent::bounty(inner) => Some(inner),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                RawEvent::SubmissionSlicePaid(1, 1, 3, 15),
+                RawEvent::SubmissionSlicePaid(1, 1, 4, 5),
+                RawEvent::BountyPaymentExecuted(1, 30, 1, 20, 3, 10, 11, 1),
+            ]
+        );
+        assert_eq!(Balances::free_balance(&3), 215);
+        assert_eq!(Balances::free_balance(&4), 80);
+        assert_eq!(Balances::free_balance(&6), 69);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 30);
+        assert!(Bounty::submission_splits(1).is_empty());
+    });
+}
+
+#[test]
+fn rejections_may_be_disputed_before_the_arbitration_org() {
+    new_test_ext().execute_with(|| {
+        MockVote::register_threshold(7, 1);
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            30,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let submit = |who, submission| {
+            Bounty::submit_for_bounty(
+                Origin::signed(who),
+                1,
+                random(10),
+                submission,
+                10u64,
+                vec![],
+            )
+        };
+        let reject = |submission_id| {
+            Bounty::reject_bounty_submission(
+                Origin::signed(1),
+                submission_id,
+                RejectionReason::Unsuitable,
+            )
+        };
+        // rejections by bounties without an arbitrator are final
+        assert_ok!(submit(2, 11u32));
+        assert_ok!(reject(1));
+        assert_noop!(
+            Bounty::dispute_rejection(Origin::signed(2), 1, 40),
+            Error::<Test>::RejectionDNE
+        );
+        assert_noop!(
+            Bounty::set_bounty_arbitration(Origin::signed(2), 1, Some(7)),
+            Error::<Test>::NotAuthorizedToSetBountyArbitration
+        );
+        assert_noop!(
+            Bounty::set_bounty_arbitration(Origin::signed(1), 1, Some(8)),
+            Error::<Test>::ArbitrationThresholdDNE
+        );
+        assert_ok!(Bounty::set_bounty_arbitration(
+            Origin::signed(1),
+            1,
+            Some(7)
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::BountyArbitrationSet(1, 1, Some(7))
+        );
+        assert_ok!(submit(3, 12u32));
+        assert_ok!(submit(2, 13u32));
+        assert_ok!(reject(2));
+        assert_ok!(reject(3));
+        // removing the arbitrator does not stop disputes of past rejections
+        assert_ok!(Bounty::set_bounty_arbitration(Origin::signed(1), 1, None));
+        assert_noop!(
+            Bounty::dispute_rejection(Origin::signed(3), 3, 40),
+            Error::<Test>::NotAuthorizedToDisputeRejection
+        );
+        assert_ok!(Bounty::dispute_rejection(Origin::signed(2), 3, 40));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::RejectionDisputed(1, 3, 2, 40, 5, 0)
+        );
+        assert_eq!(Balances::reserved_balance(&2), 5);
+        assert_noop!(
+            Bounty::dispute_rejection(Origin::signed(2), 3, 41),
+            Error::<Test>::RejectionAlreadyDisputed
+        );
+        assert_noop!(
+            Bounty::resolve_dispute(Origin::signed(1), 3),
+            Error::<Test>::DisputeNotDecided
+        );
+        // the arbitration org overturns the rejection
+        MockVote::set_outcome(0, VoteOutcome::Approved);
+        assert_ok!(Bounty::resolve_dispute(Origin::signed(1), 3));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::RejectionOverturned(1, 3, 2, 10, 5)
+        );
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 108);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 20);
+        assert_eq!(Bounty::bounty_payouts(1), (10, 1));
+        assert_eq!(Bounty::rejected_submissions(3), None);
+        assert_noop!(
+            Bounty::resolve_dispute(Origin::signed(1), 3),
+            Error::<Test>::DisputeDNE
+        );
+        // the other rejection was not disputed in time
+        System::set_block_number(12);
+        assert_noop!(
+            Bounty::dispute_rejection(Origin::signed(3), 2, 41),
+            Error::<Test>::DisputeWindowPassed
+        );
+    });
+}
+
+#[test]
+fn upheld_rejections_slash_the_dispute_deposit() {
+    new_test_ext().execute_with(|| {
+        MockVote::register_threshold(7, 1);
+        assert_noop!(
+            Bounty::set_arbitration_threshold(Origin::root(), Some(8)),
+            Error::<Test>::ArbitrationThresholdDNE
+        );
+        assert_ok!(Bounty::set_arbitration_threshold(Origin::root(), Some(7)));
+        assert_eq!(get_last_event(), RawEvent::DefaultArbitrationSet(Some(7)));
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            30,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_eq!(Bounty::arbitration_threshold(1), Some(7));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::reject_bounty_submission(
+            Origin::signed(1),
+            1,
+            RejectionReason::Unsuitable
+        ));
+        assert_ok!(Bounty::dispute_rejection(Origin::signed(2), 1, 40));
+        MockVote::set_outcome(0, VoteOutcome::Rejected);
+        assert_ok!(Bounty::resolve_dispute(Origin::signed(3), 1));
+        assert_eq!(get_last_event(), RawEvent::RejectionUpheld(1, 1, 2, 5));
+        assert_eq!(Balances::total_balance(&2), 93);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 35);
+        assert_eq!(Balances::free_balance(&Bounty::bounty_account_id(1)), 35);
+        assert_eq!(Bounty::disputes(1), None);
+    });
+}
+
+#[test]
+fn github_handles_map_to_one_account_each() {
+    new_test_ext().execute_with(|| {
+        let claim = |who, handle: &str, attestation| {
+            Bounty::claim_github_handle(
+                Origin::signed(who),
+                handle.as_bytes().to_vec(),
+                attestation,
+            )
+        };
+        assert_noop!(
+            claim(2, "Amar", 20u32),
+            Error::<Test>::InvalidGithubHandle
+        );
+        assert_noop!(
+            claim(2, "-amar", 20u32),
+            Error::<Test>::InvalidGithubHandle
+        );
+        assert_ok!(claim(2, "amar", 20u32));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::GithubHandleClaimed(2, b"amar".to_vec(), 20)
+        );
+        assert_eq!(
+            Bounty::github_handle_account(b"amar".to_vec()),
+            Some((2, 20))
+        );
+        assert_noop!(
+            claim(3, "amar", 21u32),
+            Error::<Test>::GithubHandleClaimedByAnotherAccount
+        );
+        // claiming another handle releases the old one
+        assert_ok!(claim(2, "amar-singh", 22u32));
+        assert_eq!(Bounty::github_handle_account(b"amar".to_vec()), None);
+        assert_eq!(
+            Bounty::account_github_handle(2),
+            Some(b"amar-singh".to_vec())
+        );
+        assert_ok!(claim(3, "amar", 21u32));
+        assert_ok!(Bounty::release_github_handle(Origin::signed(3)));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::GithubHandleReleased(3, b"amar".to_vec())
+        );
+        assert_eq!(Bounty::github_handle_account(b"amar".to_vec()), None);
+        assert_noop!(
+            Bounty::release_github_handle(Origin::signed(3)),
+            Error::<Test>::NoGithubHandleClaimed
+        );
+    });
+}
+
+#[test]
+fn malformed_cids_are_rejected_at_every_ingress() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Bounty::post_bounty(
+                Origin::signed(1),
+                random(10),
+                BAD_CID,
+                10,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            ),
+            org::Error::<Test>::InvalidCidFormat
+        );
+        let org = <org::Module<Test>>::register_organization(
+            OrganizationSource::AccountsWeighted(vec![(2, 7), (4, 3)]),
+            Some(1),
+            1,
+        )
+        .unwrap();
+        assert_noop!(
+            Bounty::post_org_bounty(
+                Origin::signed(1),
+                org,
+                random(10),
+                BAD_CID,
+                10,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            ),
+            org::Error::<Test>::InvalidCidFormat
+        );
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            10,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::submit_for_bounty(
+                Origin::signed(2),
+                1,
+                random(11),
+                BAD_CID,
+                5,
+                vec![],
+            ),
+            org::Error::<Test>::InvalidCidFormat
+        );
+        assert_noop!(
+            Bounty::claim_github_handle(
+                Origin::signed(2),
+                b"amar".to_vec(),
+                BAD_CID,
+            ),
+            org::Error::<Test>::InvalidCidFormat
+        );
+        assert_noop!(
+            Bounty::dispute_rejection(Origin::signed(2), 1, BAD_CID),
+            org::Error::<Test>::InvalidCidFormat
+        );
+    });
+}
+
+#[test]
+fn stale_submissions_time_out_as_rejected_by_default() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            30,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_eq!(Bounty::review_policy(1), ReviewPolicy::new(20, None));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_eq!(Balances::reserved_balance(&2), 2);
+        // made at block 1, the submission awaits review until block 21
+        System::set_block_number(20);
+        assert_noop!(
+            Bounty::timeout_submission(Origin::signed(3), 1),
+            Error::<Test>::ReviewTimeoutNotReached
+        );
+        System::set_block_number(21);
+        assert_ok!(Bounty::timeout_submission(Origin::signed(3), 1));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SubmissionTimedOut(1, TimeoutOutcome::Rejected)
+        );
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 98);
+        assert_eq!(Bounty::submissions(1), None);
+        assert_eq!(Bounty::pending_submissions(1, 2), 0);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 30);
+        assert_noop!(
+            Bounty::timeout_submission(Origin::signed(3), 1),
+            Error::<Test>::SubmissionDNE
+        );
+    });
+}
+
+#[test]
+fn optimistic_bounties_approve_stale_submissions_up_to_the_cap() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32,
+            30,
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        assert_noop!(
+            Bounty::set_review_policy(Origin::signed(2), 1, 5, Some(10)),
+            Error::<Test>::NotAuthorizedToSetReviewPolicy
+        );
+        assert_noop!(
+            Bounty::set_review_policy(Origin::signed(1), 1, 0, Some(10)),
+            Error::<Test>::InvalidReviewTimeout
+        );
+        assert_ok!(Bounty::set_review_policy(
+            Origin::signed(1),
+            1,
+            5,
+            Some(10)
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ReviewPolicySet(1, 1, 5, Some(10))
+        );
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            1,
+            random(10),
+            12u32,
+            15u64,
+            vec![],
+        ));
+        // submitters relied on the cap, which may only be lowered now
+        assert_noop!(
+            Bounty::set_review_policy(Origin::signed(1), 1, 5, Some(11)),
+            Error::<Test>::OptimisticApprovalAfterSubmissions
+        );
+        assert_ok!(Bounty::set_review_policy(
+            Origin::signed(1),
+            1,
+            5,
+            Some(10)
+        ));
+        System::set_block_number(5);
+        assert_noop!(
+            Bounty::timeout_submission(Origin::signed(4), 1),
+            Error::<Test>::ReviewTimeoutNotReached
+        );
+        System::set_block_number(6);
+        assert_ok!(Bounty::timeout_submission(Origin::signed(4), 1));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SubmissionTimedOut(1, TimeoutOutcome::Approved(10))
+        );
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 108);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 20);
+        assert_eq!(Bounty::bounty_payouts(1), (10, 1));
+        // requesting more than the cap, the other submission is rejected
+        assert_ok!(Bounty::timeout_submission(Origin::signed(4), 2));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SubmissionTimedOut(2, TimeoutOutcome::Rejected)
+        );
+        assert_eq!(Balances::reserved_balance(&3), 0);
+        assert_eq!(Balances::free_balance(&3), 200);
+        assert_eq!(Bounty::bounties(1).unwrap().total(), 20);
+        assert_eq!(Bounty::bounty_payouts(1), (10, 1));
+    });
+}
+
+fn put_old_value<M, K, V>(key: K, old: &impl Encode)
+where
+    M: StorageMap<K, V>,
+    K: Encode,
+    V: Decode,
+{
+    unhashed::put(&M::hashed_key_for(key), old);
+}
+
+/// Runs the runtime upgrade of a chain whose storage is at `version`
+fn upgrade_from(version: Releases) -> Weight {
+    StorageVersion::put(version);
+    Bounty::on_runtime_upgrade()
+}
+
+#[test]
+fn new_chains_start_at_the_current_storage_version() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Bounty::storage_version(), STORAGE_VERSION);
+        assert_ok!(Bounty::post_bounty(
+            Origin::signed(1),
+            random(10),
+            10u32, // constitution
+            20,    // funding reserved
+            None,
+            SubmissionKind::Any,
+            vec![],
+            vec![],
+        ));
+        let bounty = Bounty::bounties(1);
+        upgrade_from(STORAGE_VERSION);
+        assert_eq!(Bounty::bounties(1), bounty);
+    });
+}
+
+#[test]
+fn v1_bounties_and_submissions_migrate_without_blocks() {
+    new_test_ext().execute_with(|| {
+        let open = BountyInformationV1::new(
+            1u64,
+            10u32,
+            1u64,
+            vec![1],
+            None::<u32>,
+            20u64,
+        );
+        let closed = BountyInformationV1::new(
+            2u64,
+            11u32,
+            3u64,
+            vec![3],
+            None::<u32>,
+            0u64,
+        );
+        let submission = BountySubmissionV1::new(
+            (1u64, 1u64),
+            12u32,
+            2u64,
+            10u64,
+            SubmissionState::SubmittedAwaitingResponse,
+        );
+        put_old_value::<Bounties<Test>, _, _>(1u64, &open);
+        put_old_value::<ClosedBounties<Test>, _, _>(2u64, &closed);
+        put_old_value::<Submissions<Test>, _, _>(1u64, &submission);
+        assert_eq!(Bounty::bounties(1), None);
+        assert_eq!(Bounty::submissions(1), None);
+
+        upgrade_from(Releases::V1);
+        assert_eq!(Bounty::storage_version(), STORAGE_VERSION);
+        let bounty = Bounty::bounties(1).unwrap();
+        assert!(!bounty.timestamped());
+        assert_eq!(bounty.admins(), vec![1]);
+        assert_eq!(bounty.total(), 20);
+        assert_eq!(bounty.posted_at(), 0);
+        assert_eq!(bounty.first_submission_at(), None);
+        assert_eq!(bounty.resolved_at(), None);
+        assert_eq!(Bounty::closed_bounties(2), Some(closed.into()));
+        let submission = Bounty::submissions(1).unwrap();
+        assert!(!submission.timestamped());
+        assert_eq!(submission.submitter(), 2);
+        assert_eq!(submission.amount(), 10);
+        assert_eq!(submission.submitted_at(), 0);
+        assert_eq!(Bounty::bounty_submissions(1, 1), Some(()));
+
+        // the migration only runs once
+        let migrated = Bounty::bounties(1);
+        upgrade_from(STORAGE_VERSION);
+        assert_eq!(Bounty::bounties(1), migrated);
+    });
+}
+
+#[test]
+fn v2_submissions_are_indexed_by_bounty() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..2 {
+            assert_ok!(Bounty::post_bounty(
+                Origin::signed(1),
+                random(10),
+                10u32,
+                20,
+                None,
+                SubmissionKind::Any,
+                vec![],
+                vec![],
+            ));
+        }
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(2),
+            1,
+            random(10),
+            11u32,
+            10u64,
+            vec![],
+        ));
+        assert_ok!(Bounty::submit_for_bounty(
+            Origin::signed(3),
+            2,
+            random(10),
+            12u32,
+            10u64,
+            vec![],
+        ));
+        // v2 chains have no index
+        <BountySubmissions<Test>>::remove(1, 1);
+        <BountySubmissions<Test>>::remove(2, 2);
+        assert_eq!(Bounty::bounty_submissions(1, 1), None);
+
+        upgrade_from(Releases::V2);
+        assert_eq!(Bounty::storage_version(), STORAGE_VERSION);
+        assert_eq!(Bounty::bounty_submissions(1, 1), Some(()));
+        assert_eq!(Bounty::bounty_submissions(2, 2), Some(()));
+        assert_eq!(Bounty::bounty_submissions(1, 2), None);
+    });
+}
@@ -89,6 +89,7 @@ type BountySub<T> = BountySubmission<
         <T as frame_system::Trait>::BlockNumber,
         <T as vote::Trait>::VoteId,
     >,
+    <T as frame_system::Trait>::BlockNumber,
 >;
 
 pub trait Trait: frame_system::Trait + vote::Trait {
@@ -284,7 +285,8 @@ decl_module! {
             ensure!(submitter != bounty.gov().leader(), Error::<T>::DepositerCannotSubmitForBounty);
             ensure!(amount <= bounty.total(), Error::<T>::BountySubmissionExceedsTotalAvailableFunding);
             let id = Self::submission_generate_uid();
-            let submission = BountySub::<T>::new(bounty_id, id, submission_ref, submitter.clone(), amount);
+            let now = <frame_system::Module<T>>::block_number();
+            let submission = BountySub::<T>::new(bounty_id, id, submission_ref, submitter.clone(), amount, now);
             <Submissions<T>>::insert(id, submission);
             Self::deposit_event(RawEvent::BountySubmissionPosted(submitter, bounty_id, amount, id, bounty.info(), submission_ref));
             Ok(())
@@ -336,7 +338,7 @@ decl_module! {
                                     <Submissions<T>>::remove(sub_id);
                                     Self::deposit_event(RawEvent::SpendChallengePassedAndSubmissionRejected(sub_id, sub.bounty_id(), sub.amount()));
                                 },
-                                VoteOutcome::Rejected => {
+                                VoteOutcome::Rejected | VoteOutcome::Cancelled => {
                                     // TODO: make this path infallible
                                     // => the submission is approved because this vote was a challenge to an approval by the depositer
                                     if !Self::approve_and_execute_payment(sub_id).is_ok() {
@@ -362,7 +364,7 @@ decl_module! {
                                     Self::deposit_event(RawEvent::BountyRefunded(bid, amt_to_contributors, amt_to_depositer));
                                 }
                             },
-                            VoteOutcome::Rejected => {
+                            VoteOutcome::Rejected | VoteOutcome::Cancelled => {
                                 // => the refund is not executed and the bty state is reset to NoPendingChallenges until next challenge
                                 let new_bty = bty.set_state(BountyState::NoPendingChallenges);
                                 let total = new_bty.total();
@@ -10,6 +10,7 @@ use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::IdentityLookup,
+    ModuleId,
     Perbill,
 };
 use util::{
@@ -86,11 +87,36 @@ impl pallet_balances::Trait for Test {
     type AccountStore = System;
     type WeightInfo = ();
 }
+parameter_types! {
+    pub const OrgTreasury: ModuleId = ModuleId(*b"org/trsy");
+    pub const SpendVoteDuration: BlockNumber = 10;
+    pub const MaxMembers: u32 = 100;
+    pub const HandleCooldown: BlockNumber = 10;
+    pub const MaxApplicationDeposit: u64 = 50;
+    pub const MaxApplicationCooldown: BlockNumber = 10;
+    pub const MaxPendingApplications: u32 = 2;
+    pub const ShareGrantExpiry: BlockNumber = 10;
+    pub const MaxPendingShareGrants: u32 = 3;
+}
 impl org::Trait for Test {
     type Event = TestEvent;
     type Cid = u32;
+    type CidFormat = ();
     type OrgId = u64;
     type Shares = u64;
+    type OrgCurrency = Balances;
+    type OrgTreasury = OrgTreasury;
+    type SpendVote = ();
+    type SpendVoteDuration = SpendVoteDuration;
+    type MaxMembers = MaxMembers;
+    type HandleCooldown = HandleCooldown;
+    type MaxApplicationDeposit = MaxApplicationDeposit;
+    type MaxApplicationCooldown = MaxApplicationCooldown;
+    type MaxPendingApplications = MaxPendingApplications;
+    type ShareGrantExpiry = ShareGrantExpiry;
+    type MaxPendingShareGrants = MaxPendingShareGrants;
+    type ShareChangeHandler = ();
+    type LifecycleHandler = ();
 }
 impl Trait for Test {
     type Event = TestEvent;
@@ -459,7 +459,7 @@ decl_module! {
                                         <Applications<T>>::insert(id, new_app);
                                         Self::deposit_event(RawEvent::ApplicationApproved(app.foundation_id(), id, app.submission_ref()));
                                     },
-                                    VoteOutcome::Rejected => {
+                                    VoteOutcome::Rejected | VoteOutcome::Cancelled => {
                                         <Applications<T>>::remove(id);
                                         Self::deposit_event(RawEvent::ApplicationRejected(app.foundation_id(), id));
                                     },
@@ -481,7 +481,7 @@ decl_module! {
                                         <Milestones<T>>::insert(aid, mid, new_mile);
                                         Self::deposit_event(RawEvent::MilestoneApproved(mile.base_foundation(), aid, mid, mile.submission()));
                                     },
-                                    VoteOutcome::Rejected => {
+                                    VoteOutcome::Rejected | VoteOutcome::Cancelled => {
                                         <Milestones<T>>::remove(aid, mid);
                                         Self::deposit_event(RawEvent::MilestoneRejected(mile.base_foundation(), aid, mid));
                                     },
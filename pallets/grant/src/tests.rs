@@ -99,17 +99,65 @@ impl pallet_balances::Trait for Test {
     type AccountStore = System;
     type WeightInfo = ();
 }
+parameter_types! {
+    pub const OrgTreasury: ModuleId = ModuleId(*b"org/trsy");
+    pub const SpendVoteDuration: BlockNumber = 10;
+    pub const MaxMembers: u32 = 100;
+    pub const HandleCooldown: BlockNumber = 10;
+    pub const MaxApplicationDeposit: u64 = 50;
+    pub const MaxApplicationCooldown: BlockNumber = 10;
+    pub const MaxPendingApplications: u32 = 2;
+    pub const ShareGrantExpiry: BlockNumber = 10;
+    pub const MaxPendingShareGrants: u32 = 3;
+}
 impl org::Trait for Test {
     type Event = TestEvent;
     type Cid = u32;
+    type CidFormat = ();
     type OrgId = u64;
     type Shares = u64;
+    type OrgCurrency = Balances;
+    type OrgTreasury = OrgTreasury;
+    type SpendVote = vote::Module<Test>;
+    type SpendVoteDuration = SpendVoteDuration;
+    type MaxMembers = MaxMembers;
+    type HandleCooldown = HandleCooldown;
+    type MaxApplicationDeposit = MaxApplicationDeposit;
+    type MaxApplicationCooldown = MaxApplicationCooldown;
+    type MaxPendingApplications = MaxPendingApplications;
+    type ShareGrantExpiry = ShareGrantExpiry;
+    type MaxPendingShareGrants = MaxPendingShareGrants;
+    type ShareChangeHandler = ();
+    type LifecycleHandler = ();
+}
+parameter_types! {
+    pub const MaxCustomWeights: u32 = 5;
+    pub const LazyMintThreshold: u32 = 100;
+    pub const MaxJustificationHistory: u32 = 3;
+    pub const FreeBallots: u32 = 2;
+    pub const MaxCompositeLegs: u32 = 4;
+    pub const MaxThresholdTweak: Permill = Permill::from_percent(10);
+    pub const MaxStandingBallots: u32 = 3;
+    pub const MinTurnoutForPercentVotes: u64 = 0;
+    pub const MaxPercentVoteFloor: u64 = 100;
 }
 impl vote::Trait for Test {
     type Event = TestEvent;
     type VoteId = u64;
     type Signal = u64;
     type ThresholdId = u64;
+    type BondCurrency = Balances;
+    type MaxCustomWeights = MaxCustomWeights;
+    type OutcomeHandler = ();
+    type LazyMintThreshold = LazyMintThreshold;
+    type MaxJustificationHistory = MaxJustificationHistory;
+    type FreeBallots = FreeBallots;
+    type MaxCompositeLegs = MaxCompositeLegs;
+    type MaxThresholdTweak = MaxThresholdTweak;
+    type MaxStandingBallots = MaxStandingBallots;
+    type Housekeeping = ();
+    type MinTurnoutForPercentVotes = MinTurnoutForPercentVotes;
+    type MaxPercentVoteFloor = MaxPercentVoteFloor;
 }
 impl donate::Trait for Test {
     type Event = TestEvent;
@@ -242,7 +242,7 @@ decl_module! {
                             // update dispute state
                             dispute.set_state(DisputeState::DisputeRaisedAndAccepted(live_vote_id))
                         }
-                        VoteOutcome::Rejected => {
+                        VoteOutcome::Rejected | VoteOutcome::Cancelled => {
                             // unreserve capital from locker
                             let _ = T::Currency::unreserve(&dispute.locker(), dispute.locked_funds());
                             // update dispute state
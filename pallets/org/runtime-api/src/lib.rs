@@ -0,0 +1,17 @@
+//! Runtime API for reading the membership root of an org without submitting
+//! an extrinsic
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use util::merkle::H256;
+
+sp_api::decl_runtime_apis! {
+    pub trait OrgApi<OrgId>
+    where
+        OrgId: Codec,
+    {
+        /// The Merkle root over the members of the org and their voting
+        /// shares, see `util::merkle`
+        fn membership_root(org: OrgId) -> H256;
+    }
+}
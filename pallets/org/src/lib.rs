@@ -25,6 +25,13 @@ use frame_support::{
     decl_storage,
     ensure,
     storage::IterableStorageDoubleMap,
+    traits::{
+        Currency,
+        ExistenceRequirement,
+        Get,
+        ReservableCurrency,
+    },
+    weights::Weight,
     Parameter,
 };
 use frame_system::{
@@ -32,51 +39,85 @@ use frame_system::{
     Trait as System,
 };
 use orml_utilities::OrderedSet;
-use parity_scale_codec::Codec;
+use parity_scale_codec::{
+    Codec,
+    Encode,
+};
 use sp_runtime::{
     traits::{
+        AccountIdConversion,
         AtLeast32Bit,
         AtLeast32BitUnsigned,
         CheckedAdd,
         CheckedSub,
+        Hash,
         MaybeSerializeDeserialize,
         Member,
+        Saturating,
+        UniqueSaturatedInto,
         Zero,
     },
     DispatchError,
     DispatchResult,
+    ModuleId,
     Permill,
 };
 use sp_std::{
+    collections::btree_map::BTreeMap,
     fmt::Debug,
     prelude::*,
 };
 use util::{
+    merkle::{
+        membership_leaves,
+        merkle_root,
+        H256,
+    },
     organization::{
+        is_valid_handle,
+        GuardedActions,
+        MemberFilter,
+        OrgAction,
+        OrgActionProposal,
+        OrgHandle,
+        OrgLifecycle,
+        OrgSpendProposal,
         Organization,
         OrganizationSource,
+        PowerMask,
         Relation,
+        SupervisorAuthority,
     },
     share::{
+        DecayPolicy,
         ProfileState,
+        ShareClass,
+        ShareGrant,
         SharePortion,
         ShareProfile,
         WeightedVector,
     },
     traits::{
         AccessGenesis,
+        CidFormat,
         GenerateUniqueID,
         GetGroup,
+        GetOrgLifecycle,
         GroupMembership,
         IDIsAvailable,
         LockProfile,
+        OrgLifecycleHandler,
         OrganizationSupervisorPermissions,
         RegisterOrganization,
         RemoveOrganization,
+        ShareChangeHandler,
         ShareInformation,
         ShareIssuance,
+        ThresholdVote,
         VerifyShape,
+        VoteOutcomeHandler,
     },
+    vote::VoteOutcome,
 };
 
 type Org<T> = Organization<
@@ -85,6 +126,41 @@ type Org<T> = Organization<
     <T as Trait>::Shares,
     <T as Trait>::Cid,
 >;
+type BalanceOf<T> =
+    <<T as Trait>::OrgCurrency as Currency<<T as System>::AccountId>>::Balance;
+type SpendThresholdId<T> = <<T as Trait>::SpendVote as ThresholdVote<
+    <T as Trait>::OrgId,
+    <T as Trait>::Cid,
+    <T as System>::BlockNumber,
+>>::ThresholdId;
+type SpendVoteId<T> = <<T as Trait>::SpendVote as ThresholdVote<
+    <T as Trait>::OrgId,
+    <T as Trait>::Cid,
+    <T as System>::BlockNumber,
+>>::VoteId;
+type SpendProp<T> = OrgSpendProposal<
+    <T as Trait>::OrgId,
+    <T as System>::AccountId,
+    BalanceOf<T>,
+    <T as Trait>::Cid,
+    SpendVoteId<T>,
+>;
+type OrgActionOf<T> = OrgAction<
+    <T as System>::AccountId,
+    <T as Trait>::Shares,
+    <T as Trait>::Cid,
+>;
+type ActionProp<T> =
+    OrgActionProposal<<T as Trait>::OrgId, <T as System>::Hash, SpendVoteId<T>>;
+type MemberFilterOf<T> =
+    MemberFilter<<T as System>::AccountId, <T as Trait>::Shares>;
+type DecayPolicyOf<T> =
+    DecayPolicy<<T as Trait>::Shares, <T as System>::BlockNumber>;
+type ShareGrantOf<T> = ShareGrant<
+    <T as Trait>::OrgId,
+    <T as Trait>::Shares,
+    <T as System>::BlockNumber,
+>;
 type Profile<T> = ShareProfile<
     (<T as Trait>::OrgId, <T as System>::AccountId),
     <T as Trait>::Shares,
@@ -98,6 +174,9 @@ pub trait Trait: System {
     /// Cid type
     type Cid: Parameter + Member + Default;
 
+    /// Checks the format of the cids accepted from extrinsics
+    type CidFormat: CidFormat;
+
     /// Organizational identifier
     type OrgId: Parameter
         + Member
@@ -124,6 +203,47 @@ pub trait Trait: System {
         + CheckedSub
         + Zero
         + AtLeast32BitUnsigned;
+
+    /// The currency held by org treasury accounts
+    type OrgCurrency: ReservableCurrency<Self::AccountId>;
+
+    /// The base account from which org treasury accounts are derived
+    type OrgTreasury: Get<ModuleId>;
+
+    /// Opens and polls the votes which authorize treasury spends
+    type SpendVote: ThresholdVote<Self::OrgId, Self::Cid, Self::BlockNumber>;
+
+    /// The number of blocks members vote on a spend from the org treasury
+    type SpendVoteDuration: Get<Self::BlockNumber>;
+
+    /// The maximum number of members of a single org
+    type MaxMembers: Get<u32>;
+
+    /// The number of blocks before a released handle can be claimed again
+    type HandleCooldown: Get<Self::BlockNumber>;
+
+    /// The maximum deposit a supervisor may require of membership applicants
+    type MaxApplicationDeposit: Get<BalanceOf<Self>>;
+
+    /// The maximum number of blocks a supervisor may make an account wait
+    /// between its applications to the org
+    type MaxApplicationCooldown: Get<Self::BlockNumber>;
+
+    /// The maximum number of pending membership applications to a single org
+    type MaxPendingApplications: Get<u32>;
+
+    /// The number of blocks a recipient has to accept the shares offered to
+    /// it by an org which requires consent
+    type ShareGrantExpiry: Get<Self::BlockNumber>;
+
+    /// The maximum number of pending share grants offered to one account
+    type MaxPendingShareGrants: Get<u32>;
+
+    /// Notified after the voting shares of a member changed
+    type ShareChangeHandler: ShareChangeHandler<Self::OrgId, Self::AccountId>;
+
+    /// Notified after an org was frozen or unfrozen
+    type LifecycleHandler: OrgLifecycleHandler<Self::OrgId>;
 }
 
 decl_event!(
@@ -133,6 +253,9 @@ decl_event!(
         OrgId = <T as Trait>::OrgId,
         <T as Trait>::Shares,
         <T as Trait>::Cid,
+        <T as System>::BlockNumber,
+        Balance = BalanceOf<T>,
+        VoteId = SpendVoteId<T>,
     {
         /// No shares issued but an organization was registered with flat membership with the last `u32` as the number of members
         NewFlatOrg(AccountId, OrgId, Cid, u32),
@@ -142,20 +265,90 @@ decl_event!(
         AddedOrgMember(OrgId, AccountId, Shares),
         /// Organization ID, Old Member Account Id, Amount Burned
         RemovedOrgMember(OrgId, AccountId, Shares),
-        /// Organization ID, Account Id
-        SharesLocked(OrgId, AccountId),
-        /// Organization ID, Account Id
-        SharesUnlocked(OrgId, AccountId),
-        /// Organization ID, Recipient AccountId, Issued Amount
-        SharesIssued(OrgId, AccountId, Shares),
-        /// Organization ID, Burned AccountId, Burned Amount
-        SharesBurned(OrgId, AccountId, Shares),
-        /// Organization ID, Total Shares Minted, Total Shares for Org
-        SharesBatchIssued(OrgId, Shares, Shares),
-        /// Organization ID, Total Shares Burned
-        SharesBatchBurned(OrgId, Shares),
+        /// Organization ID, Account Id, Shares Locked
+        SharesLocked(OrgId, AccountId, Shares),
+        /// Organization ID, Account Id, Shares Unlocked
+        SharesUnlocked(OrgId, AccountId, Shares),
+        /// Organization ID, Recipient AccountId, Issued Amount, Share Class
+        SharesIssued(OrgId, AccountId, Shares, ShareClass),
+        /// Organization ID, Burned AccountId, Burned Amount, Share Class
+        SharesBurned(OrgId, AccountId, Shares, ShareClass),
+        /// Organization ID, Total Shares Minted, Total Shares of the Class for Org, Share Class
+        SharesBatchIssued(OrgId, Shares, Shares, ShareClass),
+        /// Organization ID, Total Shares Burned, Share Class
+        SharesBatchBurned(OrgId, Shares, ShareClass),
         /// Organization ID Removed
         OrgRemoved(OrgId),
+        /// Proposer, Organization ID, Proposal ID, Beneficiary, Amount, Vote ID
+        SpendProposed(AccountId, OrgId, u32, AccountId, Balance, VoteId),
+        /// Organization ID, Proposal ID
+        SpendApproved(OrgId, u32),
+        /// Organization ID, Proposal ID
+        SpendRejected(OrgId, u32),
+        /// Organization ID, Proposal ID, Beneficiary, Amount
+        SpendExecuted(OrgId, u32, AccountId, Balance),
+        /// Organization ID, Bitmask of all guarded actions
+        OrgActionsGuarded(OrgId, u8),
+        /// Setter, Organization ID
+        ActionThresholdSet(AccountId, OrgId),
+        /// Proposer, Organization ID, Proposal ID, Vote ID
+        OrgActionProposed(AccountId, OrgId, u32, VoteId),
+        /// Organization ID, Proposal ID
+        OrgActionExecuted(OrgId, u32),
+        /// Organization ID, New Constitution
+        ConstitutionChanged(OrgId, Cid),
+        /// Organization ID, Member Account ID, New Metadata (None if cleared)
+        MemberMetadataUpdated(OrgId, AccountId, Option<Cid>),
+        /// Organization ID, New Handle (None if cleared)
+        OrgHandleSet(OrgId, Option<OrgHandle>),
+        /// Setter, Organization ID, Whether Shares Decay
+        DecayPolicySet(AccountId, OrgId, bool),
+        /// Organization ID, Inactive Account ID, Shares Lost
+        SharesDecayed(OrgId, AccountId, Shares),
+        /// Organization ID, Account ID, Class Converted From, Amount Converted
+        SharesConverted(OrgId, AccountId, ShareClass, Shares),
+        /// Organization ID, Account ID, Share Class, Balance of the Class after the Change
+        ShareBalanceChanged(OrgId, AccountId, ShareClass, Shares),
+        /// Organization ID, Account ID, Power Mask, Block the Grant Expires at
+        EmergencyPowerGranted(OrgId, AccountId, u8, BlockNumber),
+        /// Organization ID, Account ID, Power used by an Executed Action
+        EmergencyPowerUsed(OrgId, AccountId, u8),
+        /// Organization ID, Account ID
+        EmergencyPowerExpired(OrgId, AccountId),
+        /// Organization ID, Account ID
+        EmergencyPowerRevoked(OrgId, AccountId),
+        /// Organization ID, Merkle Root over its Members and their Voting Shares
+        MembershipRootPublished(OrgId, H256),
+        /// Setter, Organization ID, Application Deposit, Application Cooldown
+        ApplicationPolicySet(AccountId, OrgId, Balance, BlockNumber),
+        /// Organization ID, Applicant, Deposit Reserved
+        MembershipApplied(OrgId, AccountId, Balance),
+        /// Organization ID, Applicant
+        ApplicationWithdrawn(OrgId, AccountId),
+        /// Organization ID, Applicant, Shares Issued
+        ApplicationApproved(OrgId, AccountId, Shares),
+        /// Organization ID, Applicant, Whether the Deposit was Forfeited as Spam
+        ApplicationRejected(OrgId, AccountId, bool),
+        /// Organization ID, Observer, Role
+        ObserverAdded(OrgId, AccountId, Cid),
+        /// Organization ID, Observer
+        ObserverRemoved(OrgId, AccountId),
+        /// Source Organization ID, New Organization ID, Members Copied
+        OrgForked(OrgId, OrgId, u32),
+        /// Supervisor, Organization ID
+        OrgFrozen(AccountId, OrgId),
+        /// Supervisor, Organization ID
+        OrgUnfrozen(AccountId, OrgId),
+        /// Setter, Organization ID, Whether Recipients must Accept Issued Shares
+        ShareConsentSet(AccountId, OrgId, bool),
+        /// Organization ID, Recipient, Grant ID, Shares Offered, Share Class, Block the Grant Expires at
+        SharesOffered(OrgId, AccountId, u32, Shares, ShareClass, BlockNumber),
+        /// Organization ID, Recipient, Grant ID
+        ShareGrantAccepted(OrgId, AccountId, u32),
+        /// Organization ID, Recipient, Grant ID
+        ShareGrantDeclined(OrgId, AccountId, u32),
+        /// Organization ID, Recipient, Grant ID, Whether the Grant had Expired
+        ShareGrantCancelled(OrgId, AccountId, u32, bool),
     }
 );
 
@@ -174,6 +367,43 @@ decl_error! {
         CannotUnLockIfAlreadyUnLocked,
         OrganizationCannotBeRemovedIfInputIdIsAvailable,
         AccountHasNoOwnershipInOrg,
+        ThresholdNotRegisteredForOrg,
+        NoDefaultThresholdForOrg,
+        NotAuthorizedToProposeSpend,
+        SpendProposalDNE,
+        SpendNotApprovedByVote,
+        InsufficientOrgTreasuryBalance,
+        ActionGuardedByVote,
+        OrgActionNotGuarded,
+        NoActionThresholdSetForOrg,
+        OrgActionProposalDNE,
+        OrgActionDoesNotMatchProposal,
+        OrgActionNotApprovedByVote,
+        OrgHasMaxMembers,
+        InvalidOrgHandle,
+        HandleTaken,
+        HandleCoolingDown,
+        DecayPeriodCannotBeZero,
+        EmergencyPowerDurationCannotBeZero,
+        EmergencyPowerDNE,
+        AccountAlreadyOrgMember,
+        ApplicationAlreadyPending,
+        ApplicationDNE,
+        ApplicationCoolingDown,
+        ApplicationQueueFull,
+        ApplicationDepositAboveMax,
+        ApplicationCooldownAboveMax,
+        ObserverMustNotHoldShares,
+        AccountIsObserver,
+        ObserverDNE,
+        ForkListAboveMaxMembers,
+        ForkHasNoMembers,
+        InvalidCidFormat,
+        OrgNotActive,
+        OrgNotFrozen,
+        ShareGrantDNE,
+        ShareGrantExpired,
+        TooManyShareGrants,
     }
 }
 
@@ -198,6 +428,167 @@ decl_storage! {
         pub Members get(fn members): double_map
             hasher(blake2_128_concat) T::OrgId,
             hasher(blake2_128_concat) T::AccountId => Option<Profile<T>>;
+
+        /// The number of members of each org
+        pub MemberCount get(fn member_count): map
+            hasher(blake2_128_concat) T::OrgId => u32;
+
+        /// The members of each org by position, for paged reads of the group
+        pub MemberAt get(fn member_at): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(twox_64_concat) u32 => Option<T::AccountId>;
+
+        /// The position of each member in `MemberAt`
+        MemberPosition get(fn member_position): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<u32>;
+
+        /// Identity nonce for share snapshots
+        ShareSnapshotNonce get(fn share_snapshot_nonce): u32;
+
+        /// The snapshots of each org's shares which are still read
+        pub ShareSnapshots get(fn share_snapshots): map
+            hasher(blake2_128_concat) T::OrgId => Vec<u32>;
+
+        /// The shares of members before they first changed after a snapshot,
+        /// members without an entry still hold the shares of the snapshot
+        SnapshotShares get(fn snapshot_shares_changed): double_map
+            hasher(blake2_128_concat) (T::OrgId, u32),
+            hasher(blake2_128_concat) T::AccountId => Option<T::Shares>;
+
+        /// The profile attached to each membership, cleared when the member leaves
+        pub MemberMetadata get(fn member_metadata): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<T::Cid>;
+
+        /// Identity nonce for spend proposals
+        SpendProposalNonce get(fn spend_proposal_nonce): u32;
+
+        /// Open proposals to spend from org treasuries
+        pub SpendProposals get(fn spend_proposals): map
+            hasher(blake2_128_concat) u32 => Option<SpendProp<T>>;
+
+        /// The spend proposal decided by each vote without an outcome yet
+        pub SpendProposalVotes get(fn spend_proposal_of_vote): map
+            hasher(blake2_128_concat) SpendVoteId<T> => Option<u32>;
+
+        /// The supervisor actions which require an approved vote for each org
+        pub GuardedOrgActions get(fn guarded_org_actions): map
+            hasher(blake2_128_concat) T::OrgId => GuardedActions;
+
+        /// The shares the supervisor may issue per block once issuance is guarded
+        pub IssuanceLimits get(fn issuance_limits): map
+            hasher(blake2_128_concat) T::OrgId => T::Shares;
+
+        /// The shares issued by the supervisor in the last block with issuance
+        BlockIssuance get(fn block_issuance): map
+            hasher(blake2_128_concat) T::OrgId => (T::BlockNumber, T::Shares);
+
+        /// The threshold invoked to approve guarded actions
+        pub ActionThresholds get(fn action_thresholds): map
+            hasher(blake2_128_concat) T::OrgId => Option<SpendThresholdId<T>>;
+
+        /// Identity nonce for guarded action proposals
+        OrgActionNonce get(fn org_action_nonce): u32;
+
+        /// Open proposals to execute guarded actions
+        pub OrgActionProposals get(fn org_action_proposals): map
+            hasher(blake2_128_concat) u32 => Option<ActionProp<T>>;
+
+        /// The org which claimed each handle
+        pub HandleToOrg get(fn org_by_handle): map
+            hasher(blake2_128_concat) OrgHandle => Option<T::OrgId>;
+
+        /// The handle claimed by each org
+        pub OrgHandles get(fn org_handle): map
+            hasher(blake2_128_concat) T::OrgId => Option<OrgHandle>;
+
+        /// The block from which each released handle can be claimed again
+        pub ReleasedHandles get(fn released_handles): map
+            hasher(blake2_128_concat) OrgHandle => Option<T::BlockNumber>;
+
+        /// The decay applied to the shares of each org's inactive members
+        pub DecayPolicies get(fn decay_policy): map
+            hasher(blake2_128_concat) T::OrgId => Option<DecayPolicyOf<T>>;
+
+        /// The block from which each member is inactive with the shares they
+        /// held then, members without an entry are inactive since the policy
+        /// was set
+        pub LastActive get(fn last_active): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<(T::BlockNumber, T::Shares)>;
+
+        /// The non-voting shares of each holder, the voting shares are in
+        /// their share profile
+        pub NonVotingShares get(fn non_voting_shares): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => T::Shares;
+
+        /// The total non-voting shares issued by each org
+        pub NonVotingIssuance get(fn non_voting_issuance): map
+            hasher(blake2_128_concat) T::OrgId => T::Shares;
+
+        /// The emergency powers granted by vote and the block they expire at,
+        /// expired grants are removed when they are next read
+        pub EmergencyPowers get(fn emergency_power): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<(PowerMask, T::BlockNumber)>;
+
+        /// The membership root of each org, removed whenever the voting
+        /// shares of the org change and computed again when next read
+        MembershipRoots get(fn cached_membership_root): map
+            hasher(blake2_128_concat) T::OrgId => Option<H256>;
+
+        /// The deposit reserved from each org's applicants and the blocks an
+        /// account waits between its applications to the org
+        pub ApplicationPolicies get(fn application_policy): map
+            hasher(blake2_128_concat) T::OrgId => (BalanceOf<T>, T::BlockNumber);
+
+        /// The pending membership applications and the deposit reserved for
+        /// each, which is kept even if the policy changes
+        pub Applications get(fn application): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<(Option<T::Cid>, BalanceOf<T>)>;
+
+        /// The number of pending membership applications to each org
+        pub PendingApplications get(fn pending_applications): map
+            hasher(blake2_128_concat) T::OrgId => u32;
+
+        /// The block each account last applied to each org at
+        LastApplication get(fn last_application): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// The observers of each org and the role they were given, observers
+        /// hold no shares
+        pub Observers get(fn observer): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<T::Cid>;
+
+        /// The lifecycle of each org, orgs without an entry are active
+        pub Lifecycles get(fn lifecycle): map
+            hasher(blake2_128_concat) T::OrgId => OrgLifecycle;
+
+        /// The orgs whose share recipients must accept the shares issued to them
+        pub ShareConsent get(fn requires_share_consent): map
+            hasher(blake2_128_concat) T::OrgId => bool;
+
+        /// Identity nonce for share grants
+        ShareGrantNonce get(fn share_grant_nonce): u32;
+
+        /// The shares offered to each account and not yet accepted, declined
+        /// or cancelled, by grant id
+        pub ShareGrants get(fn share_grant): double_map
+            hasher(blake2_128_concat) T::AccountId,
+            hasher(twox_64_concat) u32 => Option<ShareGrantOf<T>>;
+
+        /// The number of pending share grants offered to each account
+        pub PendingShareGrants get(fn pending_share_grants): map
+            hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The grants expiring at each block, as recipient and grant id
+        ShareGrantExpiries get(fn share_grant_expiries): map
+            hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, u32)>;
     }
     add_extra_genesis {
         config(sudo): T::AccountId;
@@ -211,6 +602,7 @@ decl_storage! {
                 None,
                 config.doc.clone(),
                 config.mems.clone(),
+                None,
             ).expect("first organization config set up failed");
         })
     }
@@ -221,6 +613,37 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        const OrgTreasury: ModuleId = T::OrgTreasury::get();
+
+        const SpendVoteDuration: T::BlockNumber = T::SpendVoteDuration::get();
+
+        const MaxMembers: u32 = T::MaxMembers::get();
+
+        const HandleCooldown: T::BlockNumber = T::HandleCooldown::get();
+
+        const MaxApplicationDeposit: BalanceOf<T> = T::MaxApplicationDeposit::get();
+
+        const MaxApplicationCooldown: T::BlockNumber = T::MaxApplicationCooldown::get();
+
+        const MaxPendingApplications: u32 = T::MaxPendingApplications::get();
+
+        const ShareGrantExpiry: T::BlockNumber = T::ShareGrantExpiry::get();
+
+        const MaxPendingShareGrants: u32 = T::MaxPendingShareGrants::get();
+
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let due = <ShareGrantExpiries<T>>::take(n);
+            let swept = due.len() as Weight;
+            for (who, grant_id) in due {
+                // accepted, declined and cancelled grants are already gone
+                if let Some(grant) = Self::remove_grant(&who, grant_id) {
+                    Self::deposit_event(RawEvent::ShareGrantCancelled(grant.org(), who, grant_id, true));
+                }
+            }
+            let db = T::DbWeight::get();
+            db.reads_writes(1, 1).saturating_add(db.reads_writes(2, 2).saturating_mul(swept))
+        }
+
         #[weight = 0]
         fn new_flat_org(
             origin,
@@ -228,8 +651,13 @@ decl_module! {
             parent_org: Option<T::OrgId>,
             constitution: T::Cid,
             members: Vec<T::AccountId>,
+            handle: Option<OrgHandle>,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&constitution)?;
+            if let Some(handle) = &handle {
+                Self::check_handle(handle)?;
+            }
             let mut m = members;
             m.dedup();
             let total: u32 = m.len() as u32;
@@ -239,6 +667,9 @@ decl_module! {
                 Self::register_organization(OrganizationSource::Accounts(m), sudo, constitution.clone())?
             };
             Self::deposit_event(RawEvent::NewFlatOrg(caller, new_id, constitution, total));
+            if handle.is_some() {
+                Self::put_handle(new_id, handle);
+            }
             Ok(())
         }
         #[weight = 0]
@@ -248,8 +679,13 @@ decl_module! {
             parent_org: Option<T::OrgId>,
             constitution: T::Cid,
             weighted_members: Vec<(T::AccountId, T::Shares)>,
+            handle: Option<OrgHandle>,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&constitution)?;
+            if let Some(handle) = &handle {
+                Self::check_handle(handle)?;
+            }
             // auth will usually be specific to the module context in which this is used
             let wm_cpy: WeightedVector<T::AccountId, T::Shares> = weighted_members.clone().into();
             let new_id = if let Some(parent_id) = parent_org {
@@ -258,95 +694,1276 @@ decl_module! {
                 Self::register_organization(OrganizationSource::AccountsWeighted(weighted_members), sudo, constitution.clone())?
             };
             Self::deposit_event(RawEvent::NewWeightedOrg(caller, new_id, constitution, wm_cpy.total()));
+            if handle.is_some() {
+                Self::put_handle(new_id, handle);
+            }
             Ok(())
         }
         #[weight = 0]
-        fn issue_shares(origin, organization: T::OrgId, who: T::AccountId, shares: T::Shares) -> DispatchResult {
+        fn issue_shares(origin, organization: T::OrgId, who: T::AccountId, shares: T::Shares, class: ShareClass) -> DispatchResult {
             let issuer = ensure_signed(origin)?;
             // first check is that the organization exists
             ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
             // second check is that this is an authorized party for issuance (the supervisor or the module's sudo account)
-            let authentication: bool = Self::is_organization_supervisor(organization, &issuer);
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let authority = Self::supervisor_authority(organization, &issuer, PowerMask::ISSUE_SHARES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+            let block_issuance = Self::check_issuance_limit(organization, shares)?;
 
-            Self::issue(organization, who.clone(), shares, false)?;
-            Self::deposit_event(RawEvent::SharesIssued(organization, who, shares));
+            if who != issuer && <ShareConsent<T>>::get(organization) {
+                // the shares are only issued once the recipient accepts them
+                Self::ensure_can_offer(organization, &[(who.clone(), shares)])?;
+                Self::offer_shares(organization, who, shares, class);
+            } else {
+                Self::issue_class(organization, who, shares, class)?;
+            }
+            if let Some(issued) = block_issuance {
+                <BlockIssuance<T>>::insert(organization, issued);
+            }
+            Self::note_emergency_use(organization, &issuer, PowerMask::ISSUE_SHARES, authority);
             Ok(())
         }
         #[weight = 0]
-        fn burn_shares(origin, organization: T::OrgId, who: T::AccountId, shares: T::Shares) -> DispatchResult {
+        fn burn_shares(origin, organization: T::OrgId, who: T::AccountId, shares: T::Shares, class: ShareClass) -> DispatchResult {
             let burner = ensure_signed(origin)?;
             // first check is that the organization exists
             ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
             // second check is that this is an authorized party for burning
-            let authentication: bool = Self::is_organization_supervisor(organization, &burner);
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let authority = Self::supervisor_authority(organization, &burner, PowerMask::BURN_SHARES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                who == burner || !Self::is_guarded(organization, GuardedActions::BURN_SHARES),
+                Error::<T>::ActionGuardedByVote
+            );
 
-            Self::burn(organization, who.clone(), Some(shares), false)?;
-            Self::deposit_event(RawEvent::SharesBurned(organization, who, shares));
+            match class {
+                ShareClass::Voting => {
+                    Self::burn(organization, who.clone(), Some(shares), false)?;
+                }
+                ShareClass::NonVoting => Self::burn_non_voting(organization, &who, shares)?,
+            }
+            Self::deposit_event(RawEvent::SharesBurned(organization, who, shares, class));
+            Self::note_emergency_use(organization, &burner, PowerMask::BURN_SHARES, authority);
             Ok(())
         }
         #[weight = 0]
-        fn batch_issue_shares(origin, organization: T::OrgId, new_accounts: Vec<(T::AccountId, T::Shares)>) -> DispatchResult {
+        fn batch_issue_shares(origin, organization: T::OrgId, new_accounts: Vec<(T::AccountId, T::Shares)>, class: ShareClass) -> DispatchResult {
             let issuer = ensure_signed(origin)?;
             // first check is that the organization exists
             let org = <Orgs<T>>::get(organization).ok_or(Error::<T>::OrgDNE)?;
             // second check is that this is an authorized party for issuance
-            let authentication: bool = Self::is_organization_supervisor(organization, &issuer);
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let authority = Self::supervisor_authority(organization, &issuer, PowerMask::ISSUE_SHARES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
             let genesis: WeightedVector<T::AccountId, T::Shares> = new_accounts.into();
             let total_new_shares_minted = genesis.total();
-            let total = Self::batch_issue(organization, genesis)?;
-            <Orgs<T>>::insert(organization, org.set_shares(total));
-            Self::deposit_event(RawEvent::SharesBatchIssued(organization, total_new_shares_minted, total));
+            let block_issuance = Self::check_issuance_limit(organization, total_new_shares_minted)?;
+            if <ShareConsent<T>>::get(organization) {
+                ensure!(genesis.verify_shape(), Error::<T>::GenesisTotalMustEqualSumToUseBatchOps);
+                Self::ensure_no_observers(organization, &genesis)?;
+                // as in issue_shares, the issuer's own shares are issued at once
+                let (own, offers): (Vec<_>, Vec<_>) = genesis.vec().into_iter().partition(|(who, _)| *who == issuer);
+                Self::ensure_can_offer(organization, &offers)?;
+                let own = own.into_iter().fold(T::Shares::zero(), |sum, (_, shares)| sum.saturating_add(shares));
+                if !own.is_zero() {
+                    Self::issue_class(organization, issuer.clone(), own, class)?;
+                }
+                for (who, shares) in offers {
+                    Self::offer_shares(organization, who, shares, class);
+                }
+                if let Some(issued) = block_issuance {
+                    <BlockIssuance<T>>::insert(organization, issued);
+                }
+                Self::note_emergency_use(organization, &issuer, PowerMask::ISSUE_SHARES, authority);
+                return Ok(())
+            }
+            let total = match class {
+                ShareClass::Voting => {
+                    let total = Self::batch_issue(organization, genesis)?;
+                    <Orgs<T>>::insert(organization, org.set_shares(total));
+                    total
+                }
+                ShareClass::NonVoting => Self::batch_issue_non_voting(organization, genesis)?,
+            };
+            if let Some(issued) = block_issuance {
+                <BlockIssuance<T>>::insert(organization, issued);
+            }
+            Self::deposit_event(RawEvent::SharesBatchIssued(organization, total_new_shares_minted, total, class));
+            Self::note_emergency_use(organization, &issuer, PowerMask::ISSUE_SHARES, authority);
             Ok(())
         }
         #[weight = 0]
-        fn batch_burn_shares(origin, organization: T::OrgId, old_accounts: Vec<(T::AccountId, T::Shares)>) -> DispatchResult {
+        fn batch_burn_shares(origin, organization: T::OrgId, old_accounts: Vec<(T::AccountId, T::Shares)>, class: ShareClass) -> DispatchResult {
             let issuer = ensure_signed(origin)?;
             // first check is that the organization exists
             ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
             // second check is that this is an authorized party for burning
-            let authentication: bool = Self::is_organization_supervisor(organization, &issuer);
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let authority = Self::supervisor_authority(organization, &issuer, PowerMask::BURN_SHARES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                old_accounts.iter().all(|(who, _)| who == &issuer)
+                    || !Self::is_guarded(organization, GuardedActions::BURN_SHARES),
+                Error::<T>::ActionGuardedByVote
+            );
             let genesis: WeightedVector<T::AccountId, T::Shares> = old_accounts.into();
             let total_new_shares_burned = genesis.total();
-            Self::batch_burn(organization, genesis)?;
-            Self::deposit_event(RawEvent::SharesBatchBurned(organization, total_new_shares_burned));
+            match class {
+                ShareClass::Voting => Self::batch_burn(organization, genesis)?,
+                ShareClass::NonVoting => Self::batch_burn_non_voting(organization, genesis)?,
+            }
+            Self::deposit_event(RawEvent::SharesBatchBurned(organization, total_new_shares_burned, class));
+            Self::note_emergency_use(organization, &issuer, PowerMask::BURN_SHARES, authority);
+            Ok(())
+        }
+        #[weight = 0]
+        fn convert_shares(origin, organization: T::OrgId, who: T::AccountId, from: ShareClass, shares: T::Shares) -> DispatchResult {
+            let converter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &converter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            match from {
+                ShareClass::Voting => {
+                    // giving up the vote is a burn of voting shares
+                    ensure!(
+                        who == converter || !Self::is_guarded(organization, GuardedActions::BURN_SHARES),
+                        Error::<T>::ActionGuardedByVote
+                    );
+                    <NonVotingIssuance<T>>::get(organization)
+                        .checked_add(&shares)
+                        .ok_or(Error::<T>::IssuanceWouldOverflowShares)?;
+                    Self::burn(organization, who.clone(), Some(shares), false)?;
+                    Self::issue_non_voting(organization, &who, shares)?;
+                }
+                ShareClass::NonVoting => {
+                    // gaining the vote is an issuance of voting shares
+                    ensure!(
+                        <NonVotingShares<T>>::get(organization, &who) >= shares,
+                        Error::<T>::NotEnoughSharesToSatisfyBurnRequest
+                    );
+                    let block_issuance = Self::check_issuance_limit(organization, shares)?;
+                    Self::issue(organization, who.clone(), shares, false)?;
+                    Self::burn_non_voting(organization, &who, shares)?;
+                    if let Some(issued) = block_issuance {
+                        <BlockIssuance<T>>::insert(organization, issued);
+                    }
+                }
+            }
+            Self::deposit_event(RawEvent::SharesConverted(organization, who, from, shares));
+            Ok(())
+        }
+        #[weight = 0]
+        fn lock_shares(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
+            let locker = ensure_signed(origin)?;
+            // first check is that the organization exists
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            // second check is that this is an authorized party for locking shares
+            let authority = if locker == who {
+                Some(SupervisorAuthority::Supervisor)
+            } else {
+                Self::supervisor_authority(organization, &locker, PowerMask::LOCK_SHARES)
+            };
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+
+            Self::lock_profile(organization, &who)?;
+            let shares = <Members<T>>::get(organization, &who).map_or_else(Zero::zero, |p| p.total());
+            Self::deposit_event(RawEvent::SharesLocked(organization, who, shares));
+            Self::note_emergency_use(organization, &locker, PowerMask::LOCK_SHARES, authority);
+            Ok(())
+        }
+        #[weight = 0]
+        fn unlock_shares(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
+            let unlocker = ensure_signed(origin)?;
+            // first check is that the organization exists
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            // second check is that this is an authorized party for unlocking shares
+            let authority = if unlocker == who {
+                Some(SupervisorAuthority::Supervisor)
+            } else {
+                Self::supervisor_authority(organization, &unlocker, PowerMask::LOCK_SHARES)
+            };
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+
+            Self::unlock_profile(organization, &who)?;
+            let shares = <Members<T>>::get(organization, &who).map_or_else(Zero::zero, |p| p.total());
+            Self::deposit_event(RawEvent::SharesUnlocked(organization, who, shares));
+            Self::note_emergency_use(organization, &unlocker, PowerMask::LOCK_SHARES, authority);
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_my_metadata(origin, organization: T::OrgId, metadata: Option<T::Cid>) -> DispatchResult {
+            let member = ensure_signed(origin)?;
+            metadata.iter().try_for_each(Self::ensure_valid_cid)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            ensure!(
+                Self::is_member_of_group(organization, &member),
+                Error::<T>::AccountHasNoOwnershipInOrg
+            );
+            Self::set_metadata(organization, member, metadata);
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_member_metadata(origin, organization: T::OrgId, who: T::AccountId, metadata: Option<T::Cid>) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            metadata.iter().try_for_each(Self::ensure_valid_cid)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                Self::is_member_of_group(organization, &who),
+                Error::<T>::AccountHasNoOwnershipInOrg
+            );
+            Self::set_metadata(organization, who, metadata);
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_org_handle(origin, organization: T::OrgId, handle: Option<OrgHandle>) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            if let Some(handle) = &handle {
+                // renaming an org to its own handle is a noop
+                if <OrgHandles<T>>::get(organization).as_ref() == Some(handle) {
+                    return Ok(())
+                }
+                Self::check_handle(handle)?;
+            }
+            Self::put_handle(organization, handle);
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_decay_policy(
+            origin,
+            organization: T::OrgId,
+            policy: Option<(Permill, T::BlockNumber, T::Shares)>,
+        ) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            if let Some((_, period, _)) = policy {
+                ensure!(!period.is_zero(), Error::<T>::DecayPeriodCannotBeZero);
+            }
+            // members only decay under the new policy from the block it is set
+            <LastActive<T>>::remove_prefix(organization);
+            if let Some((decay, period, floor)) = policy {
+                let now = <frame_system::Module<T>>::block_number();
+                <DecayPolicies<T>>::insert(organization, DecayPolicy::new(decay, period, floor, now));
+            } else {
+                <DecayPolicies<T>>::remove(organization);
+            }
+            Self::deposit_event(RawEvent::DecayPolicySet(setter, organization, policy.is_some()));
+            Ok(())
+        }
+        #[weight = 0]
+        fn propose_spend(
+            origin,
+            organization: T::OrgId,
+            beneficiary: T::AccountId,
+            amount: BalanceOf<T>,
+            reason: T::Cid,
+        ) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&reason)?;
+            let authentication: bool = Self::is_member_of_group(organization, &proposer)
+                                    || Self::is_organization_supervisor(organization, &proposer);
+            ensure!(authentication, Error::<T>::NotAuthorizedToProposeSpend);
+            let threshold = T::SpendVote::default_threshold(organization).ok_or(Error::<T>::NoDefaultThresholdForOrg)?;
+            let duration = T::SpendVoteDuration::get();
+            let vote = T::SpendVote::open_threshold_vote(threshold, Some(reason.clone()), Some(duration))?;
+            let id = Self::generate_spend_proposal_uid();
+            let proposal = SpendProp::<T>::new(organization, beneficiary.clone(), amount, reason, vote);
+            <SpendProposals<T>>::insert(id, proposal);
+            <SpendProposalVotes<T>>::insert(vote, id);
+            Self::deposit_event(RawEvent::SpendProposed(proposer, organization, id, beneficiary, amount, vote));
+            Ok(())
+        }
+        #[weight = 0]
+        fn execute_spend(origin, proposal_id: u32) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            let proposal = <SpendProposals<T>>::get(proposal_id).ok_or(Error::<T>::SpendProposalDNE)?;
+            let outcome = T::SpendVote::threshold_vote_outcome(proposal.vote())?;
+            ensure!(outcome == VoteOutcome::Approved, Error::<T>::SpendNotApprovedByVote);
+            let org = proposal.org();
+            let treasury = Self::org_account(org);
+            ensure!(
+                T::OrgCurrency::free_balance(&treasury) >= proposal.amount(),
+                Error::<T>::InsufficientOrgTreasuryBalance
+            );
+            T::OrgCurrency::transfer(
+                &treasury,
+                &proposal.beneficiary(),
+                proposal.amount(),
+                ExistenceRequirement::AllowDeath,
+            )?;
+            <SpendProposals<T>>::remove(proposal_id);
+            <SpendProposalVotes<T>>::remove(proposal.vote());
+            Self::deposit_event(RawEvent::SpendExecuted(org, proposal_id, proposal.beneficiary(), proposal.amount()));
+            Ok(())
+        }
+        #[weight = 0]
+        fn change_constitution(origin, organization: T::OrgId, constitution: T::Cid) -> DispatchResult {
+            let changer = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&constitution)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &changer);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                !Self::is_guarded(organization, GuardedActions::CHANGE_CONSTITUTION),
+                Error::<T>::ActionGuardedByVote
+            );
+            Self::set_constitution(organization, constitution)
+        }
+        #[weight = 0]
+        fn guard_org_actions(origin, organization: T::OrgId, guards: u8, issuance_limit: T::Shares) -> DispatchResult {
+            let guarder = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &guarder);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let old_guards = <GuardedOrgActions<T>>::get(organization);
+            if guards & GuardedActions::ISSUE_SHARES != 0 {
+                // the supervisor may only tighten an existing issuance limit
+                let limit = if old_guards.contains(GuardedActions::ISSUE_SHARES) {
+                    issuance_limit.min(<IssuanceLimits<T>>::get(organization))
+                } else {
+                    issuance_limit
+                };
+                <IssuanceLimits<T>>::insert(organization, limit);
+            }
+            let new_guards = old_guards.add(guards);
+            <GuardedOrgActions<T>>::insert(organization, new_guards);
+            Self::deposit_event(RawEvent::OrgActionsGuarded(organization, new_guards.0));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_action_threshold(origin, organization: T::OrgId, threshold: SpendThresholdId<T>) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                T::SpendVote::threshold_org(threshold) == Some(organization),
+                Error::<T>::ThresholdNotRegisteredForOrg
+            );
+            <ActionThresholds<T>>::insert(organization, threshold);
+            Self::deposit_event(RawEvent::ActionThresholdSet(setter, organization));
+            Ok(())
+        }
+        #[weight = 0]
+        fn propose_org_action(origin, organization: T::OrgId, action: OrgActionOf<T>) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            let authentication: bool = Self::is_member_of_group(organization, &proposer)
+                                    || Self::is_organization_supervisor(organization, &proposer);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            if let OrgAction::ChangeConstitution(constitution) = &action {
+                Self::ensure_valid_cid(constitution)?;
+            }
+            let guarded = match action {
+                OrgAction::IssueShares(..) => Self::is_guarded(organization, GuardedActions::ISSUE_SHARES),
+                OrgAction::BurnShares(..) => Self::is_guarded(organization, GuardedActions::BURN_SHARES),
+                OrgAction::ChangeConstitution(_) => Self::is_guarded(organization, GuardedActions::CHANGE_CONSTITUTION),
+                OrgAction::RemoveGuards(_) | OrgAction::GrantEmergencyPower(..) => true,
+            };
+            ensure!(guarded, Error::<T>::OrgActionNotGuarded);
+            let threshold = <ActionThresholds<T>>::get(organization).ok_or(Error::<T>::NoActionThresholdSetForOrg)?;
+            let vote = T::SpendVote::open_threshold_vote(threshold, None, None)?;
+            let id = Self::generate_org_action_uid();
+            let proposal = ActionProp::<T>::new(organization, T::Hashing::hash_of(&action), vote);
+            <OrgActionProposals<T>>::insert(id, proposal);
+            Self::deposit_event(RawEvent::OrgActionProposed(proposer, organization, id, vote));
+            Ok(())
+        }
+        #[weight = 0]
+        fn execute_org_action(origin, proposal_id: u32, action: OrgActionOf<T>) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            Self::execute_action(proposal_id, action)
+        }
+        #[weight = 0]
+        fn grant_emergency_power(
+            origin,
+            organization: T::OrgId,
+            who: T::AccountId,
+            power_mask: u8,
+            duration_blocks: u32,
+            // the proposal of the grant whose vote must have approved it
+            proposal_id: u32,
+        ) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            let proposal = <OrgActionProposals<T>>::get(proposal_id).ok_or(Error::<T>::OrgActionProposalDNE)?;
+            ensure!(proposal.org() == organization, Error::<T>::OrgActionDoesNotMatchProposal);
+            Self::execute_action(
+                proposal_id,
+                OrgAction::GrantEmergencyPower(who, power_mask, duration_blocks),
+            )
+        }
+        #[weight = 0]
+        fn revoke_emergency_power(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
+            let revoker = ensure_signed(origin)?;
+            // only the supervisor itself, never another emergency grant
+            ensure!(
+                Self::is_organization_supervisor(organization, &revoker),
+                Error::<T>::NotAuthorizedForAccount
+            );
+            ensure!(
+                <EmergencyPowers<T>>::contains_key(organization, &who),
+                Error::<T>::EmergencyPowerDNE
+            );
+            <EmergencyPowers<T>>::remove(organization, &who);
+            Self::deposit_event(RawEvent::EmergencyPowerRevoked(organization, who));
+            Ok(())
+        }
+        #[weight = 0]
+        fn publish_membership_root(origin, organization: T::OrgId) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let root = Self::membership_root(organization);
+            Self::deposit_event(RawEvent::MembershipRootPublished(organization, root));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_application_policy(origin, organization: T::OrgId, deposit: BalanceOf<T>, cooldown: T::BlockNumber) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(deposit <= T::MaxApplicationDeposit::get(), Error::<T>::ApplicationDepositAboveMax);
+            ensure!(cooldown <= T::MaxApplicationCooldown::get(), Error::<T>::ApplicationCooldownAboveMax);
+            // pending applications keep the deposit reserved when they were made
+            <ApplicationPolicies<T>>::insert(organization, (deposit, cooldown));
+            Self::deposit_event(RawEvent::ApplicationPolicySet(setter, organization, deposit, cooldown));
+            Ok(())
+        }
+        #[weight = 0]
+        fn apply_for_membership(origin, organization: T::OrgId, application: Option<T::Cid>) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            application.iter().try_for_each(Self::ensure_valid_cid)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            ensure!(
+                !Self::is_member_of_group(organization, &applicant),
+                Error::<T>::AccountAlreadyOrgMember
+            );
+            ensure!(
+                !<Applications<T>>::contains_key(organization, &applicant),
+                Error::<T>::ApplicationAlreadyPending
+            );
+            let (deposit, cooldown) = <ApplicationPolicies<T>>::get(organization);
+            let now = <frame_system::Module<T>>::block_number();
+            if let Some(last) = <LastApplication<T>>::get(organization, &applicant) {
+                ensure!(now >= last.saturating_add(cooldown), Error::<T>::ApplicationCoolingDown);
+            }
+            let pending = <PendingApplications<T>>::get(organization);
+            ensure!(pending < T::MaxPendingApplications::get(), Error::<T>::ApplicationQueueFull);
+            T::OrgCurrency::reserve(&applicant, deposit)?;
+            <Applications<T>>::insert(organization, &applicant, (application, deposit));
+            <PendingApplications<T>>::insert(organization, pending + 1);
+            <LastApplication<T>>::insert(organization, &applicant, now);
+            Self::deposit_event(RawEvent::MembershipApplied(organization, applicant, deposit));
+            Ok(())
+        }
+        #[weight = 0]
+        fn withdraw_application(origin, organization: T::OrgId) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            let deposit = Self::close_application(organization, &applicant)?;
+            T::OrgCurrency::unreserve(&applicant, deposit);
+            Self::deposit_event(RawEvent::ApplicationWithdrawn(organization, applicant));
+            Ok(())
+        }
+        #[weight = 0]
+        fn approve_application(origin, organization: T::OrgId, applicant: T::AccountId, shares: T::Shares) -> DispatchResult {
+            let approver = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authority = Self::supervisor_authority(organization, &approver, PowerMask::ISSUE_SHARES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+            ensure!(
+                <Applications<T>>::contains_key(organization, &applicant),
+                Error::<T>::ApplicationDNE
+            );
+            let block_issuance = Self::check_issuance_limit(organization, shares)?;
+
+            Self::issue(organization, applicant.clone(), shares, false)?;
+            if let Some(issued) = block_issuance {
+                <BlockIssuance<T>>::insert(organization, issued);
+            }
+            let deposit = Self::close_application(organization, &applicant)?;
+            T::OrgCurrency::unreserve(&applicant, deposit);
+            Self::deposit_event(RawEvent::ApplicationApproved(organization, applicant, shares));
+            Self::note_emergency_use(organization, &approver, PowerMask::ISSUE_SHARES, authority);
+            Ok(())
+        }
+        #[weight = 0]
+        fn reject_application(origin, organization: T::OrgId, applicant: T::AccountId, spam: bool) -> DispatchResult {
+            let rejecter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &rejecter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let deposit = Self::close_application(organization, &applicant)?;
+            if spam {
+                // slashed and resolved rather than repatriated so that it
+                // also creates a treasury which holds nothing yet
+                let (forfeit, _) = T::OrgCurrency::slash_reserved(&applicant, deposit);
+                T::OrgCurrency::resolve_creating(&Self::org_account(organization), forfeit);
+            } else {
+                T::OrgCurrency::unreserve(&applicant, deposit);
+            }
+            Self::deposit_event(RawEvent::ApplicationRejected(organization, applicant, spam));
+            Ok(())
+        }
+        #[weight = 0]
+        fn add_observer(origin, organization: T::OrgId, who: T::AccountId, role: T::Cid) -> DispatchResult {
+            let adder = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&role)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &adder);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            // shares must be burned or converted away first, observers hold none
+            ensure!(!Self::is_member_of_group(organization, &who), Error::<T>::ObserverMustNotHoldShares);
+            ensure!(<NonVotingShares<T>>::get(organization, &who).is_zero(), Error::<T>::ObserverMustNotHoldShares);
+            // adding an observer again updates its role
+            <Observers<T>>::insert(organization, &who, role.clone());
+            Self::deposit_event(RawEvent::ObserverAdded(organization, who, role));
+            Ok(())
+        }
+        #[weight = 0]
+        fn remove_observer(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
+            let remover = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            // observers may leave on their own
+            let authentication: bool = remover == who || Self::is_organization_supervisor(organization, &remover);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(<Observers<T>>::contains_key(organization, &who), Error::<T>::ObserverDNE);
+            <Observers<T>>::remove(organization, &who);
+            Self::deposit_event(RawEvent::ObserverRemoved(organization, who));
+            Ok(())
+        }
+        #[weight = <Module<T>>::fork_weight(filter)]
+        fn fork_org(origin, source_org: T::OrgId, constitution: T::Cid, filter: MemberFilterOf<T>, equal_shares: bool) -> DispatchResult {
+            let forker = ensure_signed(origin)?;
+            Self::ensure_valid_cid(&constitution)?;
+            ensure!(!Self::id_is_available(source_org), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(source_org, &forker);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            let members = Self::fork_members(source_org, &filter)?;
+            let copied = members.len() as u32;
+            let source = if equal_shares {
+                OrganizationSource::Accounts(members.into_iter().map(|(who, _)| who).collect())
+            } else {
+                OrganizationSource::AccountsWeighted(members)
+            };
+            // the forker supervises the fork, which is a child of the source
+            let new_org = Self::register_sub_organization(source_org, source, Some(forker), constitution)?;
+            Self::deposit_event(RawEvent::OrgForked(source_org, new_org, copied));
+            Ok(())
+        }
+        #[weight = 0]
+        fn freeze_org(origin, organization: T::OrgId) -> DispatchResult {
+            let supervisor = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &supervisor);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(<Lifecycles<T>>::get(organization).is_active(), Error::<T>::OrgNotActive);
+            <Lifecycles<T>>::insert(organization, OrgLifecycle::Frozen);
+            T::LifecycleHandler::on_lifecycle_changed(organization, OrgLifecycle::Frozen);
+            Self::deposit_event(RawEvent::OrgFrozen(supervisor, organization));
+            Ok(())
+        }
+        #[weight = 0]
+        fn unfreeze_org(origin, organization: T::OrgId) -> DispatchResult {
+            let supervisor = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &supervisor);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            ensure!(<Lifecycles<T>>::get(organization) == OrgLifecycle::Frozen, Error::<T>::OrgNotFrozen);
+            <Lifecycles<T>>::remove(organization);
+            T::LifecycleHandler::on_lifecycle_changed(organization, OrgLifecycle::Active);
+            Self::deposit_event(RawEvent::OrgUnfrozen(supervisor, organization));
+            Ok(())
+        }
+        #[weight = 0]
+        fn set_share_consent(origin, organization: T::OrgId, required: bool) -> DispatchResult {
+            let setter = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let authentication: bool = Self::is_organization_supervisor(organization, &setter);
+            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
+            // grants offered before consent was dropped may still be accepted
+            if required {
+                <ShareConsent<T>>::insert(organization, true);
+            } else {
+                <ShareConsent<T>>::remove(organization);
+            }
+            Self::deposit_event(RawEvent::ShareConsentSet(setter, organization, required));
+            Ok(())
+        }
+        #[weight = 0]
+        fn accept_shares(origin, organization: T::OrgId, grant_id: u32) -> DispatchResult {
+            let recipient = ensure_signed(origin)?;
+            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
+            let grant = Self::pending_grant(organization, &recipient, grant_id)?;
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(!grant.is_expired(now), Error::<T>::ShareGrantExpired);
+            Self::issue_class(organization, recipient.clone(), grant.shares(), grant.class())?;
+            Self::remove_grant(&recipient, grant_id);
+            Self::deposit_event(RawEvent::ShareGrantAccepted(organization, recipient, grant_id));
             Ok(())
         }
         #[weight = 0]
-        fn lock_shares(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
-            let locker = ensure_signed(origin)?;
-            // first check is that the organization exists
-            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
-            // second check is that this is an authorized party for locking shares
-            let authentication: bool = Self::is_organization_supervisor(organization, &locker)
-                                    || locker == who;
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
-
-            Self::lock_profile(organization, &who)?;
-            Self::deposit_event(RawEvent::SharesLocked(organization, who));
+        fn decline_shares(origin, organization: T::OrgId, grant_id: u32) -> DispatchResult {
+            let recipient = ensure_signed(origin)?;
+            Self::pending_grant(organization, &recipient, grant_id)?;
+            Self::remove_grant(&recipient, grant_id);
+            Self::deposit_event(RawEvent::ShareGrantDeclined(organization, recipient, grant_id));
             Ok(())
         }
         #[weight = 0]
-        fn unlock_shares(origin, organization: T::OrgId, who: T::AccountId) -> DispatchResult {
-            let unlocker = ensure_signed(origin)?;
-            // first check is that the organization exists
-            ensure!(!Self::id_is_available(organization), Error::<T>::OrgDNE);
-            // second check is that this is an authorized party for unlocking shares
-            let authentication: bool = Self::is_organization_supervisor(organization, &unlocker)
-                                    || unlocker == who;
-            ensure!(authentication, Error::<T>::NotAuthorizedForAccount);
-
-            Self::unlock_profile(organization, &who)?;
-            Self::deposit_event(RawEvent::SharesUnlocked(organization, who));
+        fn cancel_share_grant(origin, organization: T::OrgId, who: T::AccountId, grant_id: u32) -> DispatchResult {
+            let canceller = ensure_signed(origin)?;
+            let grant = Self::pending_grant(organization, &who, grant_id)?;
+            // expired grants are left to be cleaned up by anyone
+            let expired = grant.is_expired(<frame_system::Module<T>>::block_number());
+            let authority = if expired {
+                None
+            } else {
+                let authority = Self::supervisor_authority(organization, &canceller, PowerMask::ISSUE_SHARES);
+                ensure!(authority.is_some(), Error::<T>::NotAuthorizedForAccount);
+                authority
+            };
+            Self::remove_grant(&who, grant_id);
+            Self::deposit_event(RawEvent::ShareGrantCancelled(organization, who, grant_id, expired));
+            Self::note_emergency_use(organization, &canceller, PowerMask::ISSUE_SHARES, authority);
             Ok(())
         }
     }
 }
 
 impl<T: Trait> Module<T> {
+    /// The weight of `fork_org`, which reads every member of the source and
+    /// copies at most `MaxMembers` of them
+    fn fork_weight(filter: &MemberFilterOf<T>) -> Weight {
+        let max = T::MaxMembers::get();
+        let copied = match filter {
+            MemberFilter::Accounts(accounts) => {
+                (accounts.len() as u32).min(max)
+            }
+            _ => max,
+        };
+        let db = T::DbWeight::get();
+        db.reads_writes(4, 4)
+            .saturating_add(db.reads(3).saturating_mul(max.into()))
+            .saturating_add(db.reads_writes(2, 5).saturating_mul(copied.into()))
+    }
+    /// The members of `org` kept by `filter` with the voting shares they hold
+    /// after decay, members left without shares are not copied
+    fn fork_members(
+        org: T::OrgId,
+        filter: &MemberFilterOf<T>,
+    ) -> Result<Vec<(T::AccountId, T::Shares)>, DispatchError> {
+        if let MemberFilter::Accounts(accounts) = filter {
+            ensure!(
+                accounts.len() as u32 <= T::MaxMembers::get(),
+                Error::<T>::ForkListAboveMaxMembers
+            );
+        }
+        let (members, _) =
+            Self::get_membership_with_shape_paged(org, 0, T::MaxMembers::get())
+                .ok_or(Error::<T>::OrgDNE)?;
+        let members = members
+            .into_iter()
+            .filter(|(who, shares)| {
+                !shares.is_zero() && filter.keeps(who, shares)
+            })
+            .collect::<Vec<_>>();
+        ensure!(!members.is_empty(), Error::<T>::ForkHasNoMembers);
+        Ok(members)
+    }
+    /// The treasury account controlled by the org, performs computation so don't call unnecessarily
+    pub fn org_account(org: T::OrgId) -> T::AccountId {
+        T::OrgTreasury::get().into_sub_account(org)
+    }
+    pub fn org_balance(org: T::OrgId) -> BalanceOf<T> {
+        T::OrgCurrency::free_balance(&Self::org_account(org))
+    }
+    /// Fails with `InvalidCidFormat` unless `T::CidFormat` accepts `cid`,
+    /// shared with the pallets which accept cids of other types
+    pub fn ensure_valid_cid<C: Encode>(cid: &C) -> DispatchResult {
+        ensure!(
+            T::CidFormat::is_valid(&cid.encode()),
+            Error::<T>::InvalidCidFormat
+        );
+        Ok(())
+    }
+    fn generate_spend_proposal_uid() -> u32 {
+        let mut id_counter = <SpendProposalNonce>::get() + 1u32;
+        while <SpendProposals<T>>::get(id_counter).is_some() {
+            id_counter += 1u32;
+        }
+        <SpendProposalNonce>::put(id_counter);
+        id_counter
+    }
+    fn is_guarded(org: T::OrgId, guard: u8) -> bool {
+        <GuardedOrgActions<T>>::get(org).contains(guard)
+    }
+    /// Removes the pending application and returns the deposit reserved for
+    /// it, which the caller returns or forfeits
+    fn close_application(
+        org: T::OrgId,
+        applicant: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let (_, deposit) = <Applications<T>>::take(org, applicant)
+            .ok_or(Error::<T>::ApplicationDNE)?;
+        <PendingApplications<T>>::mutate(org, |n| *n = n.saturating_sub(1));
+        Ok(deposit)
+    }
+    /// Issues `shares` of `class` to `who` at once and deposits the event
+    fn issue_class(
+        org: T::OrgId,
+        who: T::AccountId,
+        shares: T::Shares,
+        class: ShareClass,
+    ) -> DispatchResult {
+        match class {
+            ShareClass::Voting => Self::issue(org, who.clone(), shares, false)?,
+            ShareClass::NonVoting => Self::issue_non_voting(org, &who, shares)?,
+        }
+        Self::deposit_event(RawEvent::SharesIssued(org, who, shares, class));
+        Ok(())
+    }
+    /// Fails unless every offer may be made, so that offers are only
+    /// written once all of them were checked
+    fn ensure_can_offer(
+        org: T::OrgId,
+        offers: &[(T::AccountId, T::Shares)],
+    ) -> DispatchResult {
+        let mut pending = BTreeMap::<T::AccountId, u32>::new();
+        for (who, _) in offers {
+            ensure!(
+                !<Observers<T>>::contains_key(org, who),
+                Error::<T>::AccountIsObserver
+            );
+            let count = pending
+                .entry(who.clone())
+                .or_insert_with(|| <PendingShareGrants<T>>::get(who));
+            *count += 1;
+            ensure!(
+                *count <= T::MaxPendingShareGrants::get(),
+                Error::<T>::TooManyShareGrants
+            );
+        }
+        Ok(())
+    }
+    /// Offers `shares` of `class` to `who` until the grant expires, nothing
+    /// is issued before they accept it. Callers check `ensure_can_offer`
+    /// first.
+    fn offer_shares(
+        org: T::OrgId,
+        who: T::AccountId,
+        shares: T::Shares,
+        class: ShareClass,
+    ) {
+        let id = <ShareGrantNonce>::get().wrapping_add(1);
+        <ShareGrantNonce>::put(id);
+        let expires_at = <frame_system::Module<T>>::block_number()
+            .saturating_add(T::ShareGrantExpiry::get());
+        <ShareGrants<T>>::insert(
+            &who,
+            id,
+            ShareGrant::new(org, shares, class, expires_at),
+        );
+        <PendingShareGrants<T>>::mutate(&who, |count| *count += 1);
+        <ShareGrantExpiries<T>>::append(expires_at, (who.clone(), id));
+        Self::deposit_event(RawEvent::SharesOffered(
+            org, who, id, shares, class, expires_at,
+        ));
+    }
+    /// Removes the grant `id` offered to `who`, returning it if it was
+    /// still pending
+    fn remove_grant(who: &T::AccountId, id: u32) -> Option<ShareGrantOf<T>> {
+        let grant = <ShareGrants<T>>::take(who, id)?;
+        <PendingShareGrants<T>>::mutate_exists(who, |count| {
+            *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0)
+        });
+        Some(grant)
+    }
+    /// The grant `id` offered to `who` by `org`
+    fn pending_grant(
+        org: T::OrgId,
+        who: &T::AccountId,
+        id: u32,
+    ) -> Result<ShareGrantOf<T>, DispatchError> {
+        let grant =
+            <ShareGrants<T>>::get(who, id).ok_or(Error::<T>::ShareGrantDNE)?;
+        ensure!(grant.org() == org, Error::<T>::ShareGrantDNE);
+        Ok(grant)
+    }
+    /// The grants offered to `who` which may still be accepted
+    pub fn share_grants(who: &T::AccountId) -> Vec<(u32, ShareGrantOf<T>)> {
+        let now = <frame_system::Module<T>>::block_number();
+        <ShareGrants<T>>::iter_prefix(who)
+            .filter(|(_, grant)| !grant.is_expired(now))
+            .collect()
+    }
+    /// Executes the action of the proposal once its vote approved it
+    fn execute_action(
+        proposal_id: u32,
+        action: OrgActionOf<T>,
+    ) -> DispatchResult {
+        let proposal = <OrgActionProposals<T>>::get(proposal_id)
+            .ok_or(Error::<T>::OrgActionProposalDNE)?;
+        // only the exact action that was voted on may be executed
+        ensure!(
+            T::Hashing::hash_of(&action) == proposal.action_hash(),
+            Error::<T>::OrgActionDoesNotMatchProposal
+        );
+        let outcome = T::SpendVote::threshold_vote_outcome(proposal.vote())?;
+        ensure!(
+            outcome == VoteOutcome::Approved,
+            Error::<T>::OrgActionNotApprovedByVote
+        );
+        let org = proposal.org();
+        match action {
+            OrgAction::IssueShares(who, shares) => {
+                if <ShareConsent<T>>::get(org) {
+                    Self::ensure_can_offer(org, &[(who.clone(), shares)])?;
+                    Self::offer_shares(org, who, shares, ShareClass::Voting);
+                } else {
+                    Self::issue_class(org, who, shares, ShareClass::Voting)?;
+                }
+            }
+            OrgAction::BurnShares(who, shares) => {
+                Self::burn(org, who.clone(), Some(shares), false)?;
+                Self::deposit_event(RawEvent::SharesBurned(
+                    org,
+                    who,
+                    shares,
+                    ShareClass::Voting,
+                ));
+            }
+            OrgAction::ChangeConstitution(constitution) => {
+                Self::set_constitution(org, constitution)?;
+            }
+            OrgAction::RemoveGuards(guards) => {
+                let new_guards =
+                    <GuardedOrgActions<T>>::get(org).remove(guards);
+                <GuardedOrgActions<T>>::insert(org, new_guards);
+                Self::deposit_event(RawEvent::OrgActionsGuarded(
+                    org,
+                    new_guards.0,
+                ));
+            }
+            OrgAction::GrantEmergencyPower(who, powers, duration) => {
+                Self::grant_emergency(org, who, powers, duration)?;
+            }
+        }
+        <OrgActionProposals<T>>::remove(proposal_id);
+        Self::deposit_event(RawEvent::OrgActionExecuted(org, proposal_id));
+        Ok(())
+    }
+    /// Grants `who` the emergency `powers` in `org` for `duration` blocks,
+    /// replacing any grant it held
+    fn grant_emergency(
+        org: T::OrgId,
+        who: T::AccountId,
+        powers: u8,
+        duration: u32,
+    ) -> DispatchResult {
+        ensure!(duration > 0, Error::<T>::EmergencyPowerDurationCannotBeZero);
+        let expiry = <frame_system::Module<T>>::block_number()
+            .saturating_add(duration.into());
+        <EmergencyPowers<T>>::insert(org, &who, (PowerMask(powers), expiry));
+        Self::deposit_event(RawEvent::EmergencyPowerGranted(
+            org, who, powers, expiry,
+        ));
+        Ok(())
+    }
+    /// How `who` may use the supervisor `power` in `org`, if at all. The
+    /// supervisor always may, the holder of an emergency grant of the power
+    /// until it expires, expired grants are removed
+    pub fn supervisor_authority(
+        org: T::OrgId,
+        who: &T::AccountId,
+        power: u8,
+    ) -> Option<SupervisorAuthority> {
+        if Self::is_organization_supervisor(org, who) {
+            return Some(SupervisorAuthority::Supervisor)
+        }
+        let (powers, expiry) = <EmergencyPowers<T>>::get(org, who)?;
+        if <frame_system::Module<T>>::block_number() >= expiry {
+            <EmergencyPowers<T>>::remove(org, who);
+            Self::deposit_event(RawEvent::EmergencyPowerExpired(
+                org,
+                who.clone(),
+            ));
+            return None
+        }
+        if powers.contains(power) {
+            Some(SupervisorAuthority::Emergency)
+        } else {
+            None
+        }
+    }
+    /// Records the use of an emergency grant once the action it authorized
+    /// executed
+    pub fn note_emergency_use(
+        org: T::OrgId,
+        who: &T::AccountId,
+        power: u8,
+        authority: Option<SupervisorAuthority>,
+    ) {
+        if authority == Some(SupervisorAuthority::Emergency) {
+            Self::deposit_event(RawEvent::EmergencyPowerUsed(
+                org,
+                who.clone(),
+                power,
+            ));
+        }
+    }
+    /// Returns the shares issued this block including `shares` if issuance is
+    /// guarded, to be recorded once the issuance succeeds
+    fn check_issuance_limit(
+        org: T::OrgId,
+        shares: T::Shares,
+    ) -> Result<Option<(T::BlockNumber, T::Shares)>, DispatchError> {
+        if !Self::is_guarded(org, GuardedActions::ISSUE_SHARES) {
+            return Ok(None)
+        }
+        let now = <frame_system::Module<T>>::block_number();
+        let (block, issued) = <BlockIssuance<T>>::get(org);
+        let issued = if block == now { issued } else { Zero::zero() };
+        let total = issued
+            .checked_add(&shares)
+            .ok_or(Error::<T>::IssuanceWouldOverflowShares)?;
+        ensure!(
+            total <= <IssuanceLimits<T>>::get(org),
+            Error::<T>::ActionGuardedByVote
+        );
+        Ok(Some((now, total)))
+    }
+    fn set_constitution(org: T::OrgId, constitution: T::Cid) -> DispatchResult {
+        let old_org = <Orgs<T>>::get(org).ok_or(Error::<T>::OrgDNE)?;
+        <Orgs<T>>::insert(org, old_org.set_constitution(constitution.clone()));
+        Self::deposit_event(RawEvent::ConstitutionChanged(org, constitution));
+        Ok(())
+    }
+    /// Checks that the handle may be claimed now
+    fn check_handle(handle: &OrgHandle) -> DispatchResult {
+        ensure!(is_valid_handle(handle), Error::<T>::InvalidOrgHandle);
+        ensure!(
+            !<HandleToOrg<T>>::contains_key(handle),
+            Error::<T>::HandleTaken
+        );
+        if let Some(claimable) = <ReleasedHandles<T>>::get(handle) {
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now >= claimable, Error::<T>::HandleCoolingDown);
+        }
+        Ok(())
+    }
+    /// Replaces the handle of the org, the old one cools down before it can
+    /// be claimed again
+    fn put_handle(org: T::OrgId, handle: Option<OrgHandle>) {
+        Self::release_handle(org);
+        if let Some(new_handle) = handle {
+            <ReleasedHandles<T>>::remove(new_handle);
+            <HandleToOrg<T>>::insert(new_handle, org);
+            <OrgHandles<T>>::insert(org, new_handle);
+        }
+        Self::deposit_event(RawEvent::OrgHandleSet(org, handle));
+    }
+    fn release_handle(org: T::OrgId) {
+        if let Some(old_handle) = <OrgHandles<T>>::take(org) {
+            <HandleToOrg<T>>::remove(old_handle);
+            let claimable = <frame_system::Module<T>>::block_number()
+                .saturating_add(T::HandleCooldown::get());
+            <ReleasedHandles<T>>::insert(old_handle, claimable);
+        }
+    }
+    fn set_metadata(
+        org: T::OrgId,
+        who: T::AccountId,
+        metadata: Option<T::Cid>,
+    ) {
+        if let Some(cid) = metadata.clone() {
+            <MemberMetadata<T>>::insert(org, &who, cid);
+        } else {
+            <MemberMetadata<T>>::remove(org, &who);
+        }
+        Self::deposit_event(RawEvent::MemberMetadataUpdated(
+            org, who, metadata,
+        ));
+    }
+    /// Appends a new member to the positions read by the paged group getters
+    fn add_to_group(org: T::OrgId, who: T::AccountId) {
+        let position = <MemberCount<T>>::get(org);
+        <MemberAt<T>>::insert(org, position, who.clone());
+        <MemberPosition<T>>::insert(org, who, position);
+        <MemberCount<T>>::insert(org, position + 1);
+    }
+    /// Moves the last member into the position of the leaving one
+    fn remove_from_group(org: T::OrgId, who: &T::AccountId) {
+        if let Some(position) = <MemberPosition<T>>::take(org, who) {
+            let last = <MemberCount<T>>::get(org).saturating_sub(1);
+            if let Some(moved) = <MemberAt<T>>::take(org, last) {
+                if position != last {
+                    <MemberAt<T>>::insert(org, position, moved.clone());
+                    <MemberPosition<T>>::insert(org, moved, position);
+                }
+            }
+            <MemberCount<T>>::insert(org, last);
+        }
+    }
+    /// Keeps the shares `who` holds now for every open snapshot of the org
+    /// which did not see them change yet, called before the shares change
+    fn record_snapshots(org: T::OrgId, who: &T::AccountId) {
+        let snapshots = <ShareSnapshots<T>>::get(org);
+        if snapshots.is_empty() {
+            return
+        }
+        let shares =
+            <Members<T>>::get(org, who).map_or_else(Zero::zero, |p| p.total());
+        for id in snapshots {
+            if !<SnapshotShares<T>>::contains_key((org, id), who) {
+                <SnapshotShares<T>>::insert((org, id), who, shares);
+            }
+        }
+    }
+    /// Writes back the shares `who` lost to the org's decay policy and returns
+    /// the block their current period started, the loss is computed from
+    /// `LastActive` so it is the same however often the decay is applied
+    fn apply_decay(
+        org: T::OrgId,
+        who: &T::AccountId,
+    ) -> Option<T::BlockNumber> {
+        let policy = <DecayPolicies<T>>::get(org)?;
+        let profile = <Members<T>>::get(org, who)?;
+        let (since, active_shares) = <LastActive<T>>::get(org, who)
+            .unwrap_or_else(|| (policy.since(), profile.total()));
+        let now = <frame_system::Module<T>>::block_number();
+        let periods = (now - since) / policy.period();
+        let kept =
+            policy.decayed(active_shares, periods.unique_saturated_into());
+        if kept < profile.total() {
+            let lost = profile.total() - kept;
+            if let Some(org_state) = <Orgs<T>>::get(org) {
+                <Orgs<T>>::insert(org, org_state.subtract_shares(lost));
+            }
+            Self::record_snapshots(org, who);
+            <Members<T>>::insert(org, who, profile.subtract_shares(lost));
+            <LastActive<T>>::insert(org, who, (since, active_shares));
+            Self::deposit_share_balance(org, who, ShareClass::Voting);
+            Self::deposit_event(RawEvent::SharesDecayed(
+                org,
+                who.clone(),
+                lost,
+            ));
+        }
+        Some(since + periods * policy.period())
+    }
+    /// Decays the shares of every member of the org before they are read
+    fn apply_group_decay(org: T::OrgId) {
+        if !<DecayPolicies<T>>::contains_key(org) {
+            return
+        }
+        let members = <Members<T>>::iter_prefix(org)
+            .map(|(who, _)| who)
+            .collect::<Vec<_>>();
+        for who in members {
+            Self::apply_decay(org, &who);
+        }
+    }
+    /// Restarts the decay of `who` from `since` with the shares they hold now,
+    /// called after their shares changed
+    fn reset_decay(org: T::OrgId, who: &T::AccountId, since: T::BlockNumber) {
+        if !<DecayPolicies<T>>::contains_key(org) {
+            return
+        }
+        if let Some(profile) = <Members<T>>::get(org, who) {
+            <LastActive<T>>::insert(org, who, (since, profile.total()));
+        } else {
+            <LastActive<T>>::remove(org, who);
+        }
+    }
+    /// The Merkle root over the members of the org and their voting shares
+    /// as stored, without the decay they owe but were not charged yet
+    pub fn membership_root(org: T::OrgId) -> H256 {
+        if let Some(root) = <MembershipRoots<T>>::get(org) {
+            return root
+        }
+        let members = <Members<T>>::iter_prefix(org)
+            .map(|(who, profile)| (who, profile.total()))
+            .collect::<Vec<_>>();
+        let root = merkle_root(&membership_leaves(members));
+        if !Self::id_is_available(org) {
+            <MembershipRoots<T>>::insert(org, root);
+        }
+        root
+    }
+    /// Starts recording the shares of the org's members as they are now,
+    /// every share change costs a write per snapshot until it is released
+    pub fn take_share_snapshot(org: T::OrgId) -> u32 {
+        let id = <ShareSnapshotNonce>::get() + 1u32;
+        <ShareSnapshotNonce>::put(id);
+        <ShareSnapshots<T>>::append(org, id);
+        id
+    }
+    pub fn release_share_snapshot(org: T::OrgId, id: u32) {
+        <ShareSnapshots<T>>::mutate(org, |ids| ids.retain(|i| *i != id));
+        <SnapshotShares<T>>::remove_prefix((org, id));
+    }
+    /// The shares `who` held when the snapshot was taken
+    pub fn snapshot_shares(
+        org: T::OrgId,
+        id: u32,
+        who: &T::AccountId,
+    ) -> T::Shares {
+        <SnapshotShares<T>>::get((org, id), who).unwrap_or_else(|| {
+            <Members<T>>::get(org, who).map_or_else(Zero::zero, |p| p.total())
+        })
+    }
+    /// The non-voting shares of the org's holders, for splitting payouts by
+    /// economic ownership, fallible bc checks existence
+    pub fn economic_shape(
+        organization: T::OrgId,
+    ) -> Option<WeightedVector<T::AccountId, T::Shares>> {
+        if Self::id_is_available(organization) {
+            return None
+        }
+        Some(
+            <NonVotingShares<T>>::iter_prefix(organization)
+                .collect::<Vec<(T::AccountId, T::Shares)>>()
+                .into(),
+        )
+    }
+    fn issue_non_voting(
+        org: T::OrgId,
+        who: &T::AccountId,
+        amount: T::Shares,
+    ) -> DispatchResult {
+        ensure!(
+            !<Observers<T>>::contains_key(org, who),
+            Error::<T>::AccountIsObserver
+        );
+        let issuance = <NonVotingIssuance<T>>::get(org)
+            .checked_add(&amount)
+            .ok_or(Error::<T>::IssuanceWouldOverflowShares)?;
+        // no holder can overflow if the issuance does not
+        <NonVotingShares<T>>::mutate(org, who, |shares| {
+            *shares = shares.saturating_add(amount)
+        });
+        <NonVotingIssuance<T>>::insert(org, issuance);
+        Self::deposit_share_balance(org, who, ShareClass::NonVoting);
+        Ok(())
+    }
+    fn burn_non_voting(
+        org: T::OrgId,
+        who: &T::AccountId,
+        amount: T::Shares,
+    ) -> DispatchResult {
+        let shares = <NonVotingShares<T>>::get(org, who);
+        ensure!(
+            shares >= amount,
+            Error::<T>::NotEnoughSharesToSatisfyBurnRequest
+        );
+        if shares == amount {
+            <NonVotingShares<T>>::remove(org, who);
+        } else {
+            <NonVotingShares<T>>::insert(org, who, shares - amount);
+        }
+        <NonVotingIssuance<T>>::mutate(org, |issuance| {
+            *issuance = issuance.saturating_sub(amount)
+        });
+        Self::deposit_share_balance(org, who, ShareClass::NonVoting);
+        Ok(())
+    }
+    /// Emits the balance `who` holds of the class after it changed so
+    /// watchers can follow every holder without reading storage
+    fn deposit_share_balance(
+        org: T::OrgId,
+        who: &T::AccountId,
+        class: ShareClass,
+    ) {
+        let balance = match class {
+            ShareClass::Voting => {
+                <Members<T>>::get(org, who)
+                    .map_or_else(Zero::zero, |p| p.total())
+            }
+            ShareClass::NonVoting => <NonVotingShares<T>>::get(org, who),
+        };
+        Self::deposit_event(RawEvent::ShareBalanceChanged(
+            org,
+            who.clone(),
+            class,
+            balance,
+        ));
+        if class == ShareClass::Voting {
+            <MembershipRoots<T>>::remove(org);
+            T::ShareChangeHandler::on_shares_changed(org, who);
+        }
+    }
+    /// Fails if shares of a batch would go to an observer of the org
+    fn ensure_no_observers(
+        org: T::OrgId,
+        genesis: &WeightedVector<T::AccountId, T::Shares>,
+    ) -> DispatchResult {
+        ensure!(
+            genesis
+                .vec()
+                .iter()
+                .all(|(who, _)| !<Observers<T>>::contains_key(org, who)),
+            Error::<T>::AccountIsObserver
+        );
+        Ok(())
+    }
+    /// Returns the org's new non-voting issuance
+    fn batch_issue_non_voting(
+        org: T::OrgId,
+        genesis: WeightedVector<T::AccountId, T::Shares>,
+    ) -> Result<T::Shares, DispatchError> {
+        ensure!(
+            genesis.verify_shape(),
+            Error::<T>::GenesisTotalMustEqualSumToUseBatchOps
+        );
+        <NonVotingIssuance<T>>::get(org)
+            .checked_add(&genesis.total())
+            .ok_or(Error::<T>::IssuanceWouldOverflowShares)?;
+        Self::ensure_no_observers(org, &genesis)?;
+        genesis.vec().into_iter().for_each(|(holder, shares)| {
+            if let Ok(()) = Self::issue_non_voting(org, &holder, shares) {
+                Self::deposit_event(RawEvent::SharesIssued(
+                    org,
+                    holder,
+                    shares,
+                    ShareClass::NonVoting,
+                ));
+            }
+        });
+        Ok(<NonVotingIssuance<T>>::get(org))
+    }
+    fn batch_burn_non_voting(
+        org: T::OrgId,
+        genesis: WeightedVector<T::AccountId, T::Shares>,
+    ) -> DispatchResult {
+        ensure!(
+            genesis.verify_shape(),
+            Error::<T>::GenesisTotalMustEqualSumToUseBatchOps
+        );
+        <NonVotingIssuance<T>>::get(org)
+            .checked_sub(&genesis.total())
+            .ok_or(Error::<T>::IssuanceCannotGoNegative)?;
+        genesis.vec().into_iter().for_each(|(holder, shares)| {
+            if let Ok(()) = Self::burn_non_voting(org, &holder, shares) {
+                Self::deposit_event(RawEvent::SharesBurned(
+                    org,
+                    holder,
+                    shares,
+                    ShareClass::NonVoting,
+                ));
+            }
+        });
+        Ok(())
+    }
+    fn generate_org_action_uid() -> u32 {
+        let mut id_counter = <OrgActionNonce>::get() + 1u32;
+        while <OrgActionProposals<T>>::get(id_counter).is_some() {
+            id_counter += 1u32;
+        }
+        <OrgActionNonce>::put(id_counter);
+        id_counter
+    }
     pub fn is_immediate_child(parent: T::OrgId, child: T::OrgId) -> bool {
         <OrgTree<T>>::get(parent, child).is_some()
     }
@@ -368,6 +1985,12 @@ impl<T: Trait> GroupMembership<T::OrgId, T::AccountId> for Module<T> {
     }
 }
 
+impl<T: Trait> GetOrgLifecycle<T::OrgId> for Module<T> {
+    fn org_lifecycle(org: T::OrgId) -> OrgLifecycle {
+        <Lifecycles<T>>::get(org)
+    }
+}
+
 impl<T: Trait> IDIsAvailable<T::OrgId> for Module<T> {
     fn id_is_available(id: T::OrgId) -> bool {
         <Orgs<T>>::get(id).is_none()
@@ -502,6 +2125,23 @@ impl<T: Trait> RemoveOrganization<T::OrgId> for Module<T> {
             Error::<T>::OrganizationCannotBeRemovedIfInputIdIsAvailable
         );
         <Orgs<T>>::remove(id);
+        <DecayPolicies<T>>::remove(id);
+        <LastActive<T>>::remove_prefix(id);
+        <NonVotingShares<T>>::remove_prefix(id);
+        <NonVotingIssuance<T>>::remove(id);
+        <MembershipRoots<T>>::remove(id);
+        // the deposits of applications to a removed org are returned
+        for (applicant, (_, deposit)) in <Applications<T>>::drain_prefix(id) {
+            T::OrgCurrency::unreserve(&applicant, deposit);
+        }
+        <PendingApplications<T>>::remove(id);
+        <ApplicationPolicies<T>>::remove(id);
+        <LastApplication<T>>::remove_prefix(id);
+        <Observers<T>>::remove_prefix(id);
+        <Lifecycles<T>>::remove(id);
+        // its pending grants fail with `OrgDNE` and are left to expire
+        <ShareConsent<T>>::remove(id);
+        Self::release_handle(id);
         let new_org_count = <OrgCounter>::get().saturating_sub(1u32);
         <OrgCounter>::put(new_org_count);
         Ok(())
@@ -537,6 +2177,24 @@ impl<T: Trait> GetGroup<T::OrgId, T::AccountId> for Module<T> {
             None
         }
     }
+    /// Members leaving while the pages are read move the last member into
+    /// their position, which may then be skipped
+    fn get_group_paged(
+        organization: T::OrgId,
+        cursor: u32,
+        limit: u32,
+    ) -> Option<(Vec<T::AccountId>, Option<u32>)> {
+        if Self::id_is_available(organization) {
+            return None
+        }
+        let count = <MemberCount<T>>::get(organization);
+        let end = cursor.saturating_add(limit).min(count);
+        let page = (cursor..end)
+            .filter_map(|i| <MemberAt<T>>::get(organization, i))
+            .collect::<Vec<_>>();
+        let next = if end < count { Some(end) } else { None };
+        Some((page, next))
+    }
 }
 
 impl<T: Trait> ShareInformation<T::OrgId, T::AccountId, T::Shares>
@@ -559,11 +2217,13 @@ impl<T: Trait> ShareInformation<T::OrgId, T::AccountId, T::Shares>
     ) -> Option<Self::Profile> {
         <Members<T>>::get(organization, who)
     }
-    /// Returns the entire membership group associated with a share identifier, fallible bc checks existence
+    /// Returns the entire membership group associated with a share identifier
+    /// with their voting shares, fallible bc checks existence
     fn get_membership_with_shape(
         organization: T::OrgId,
     ) -> Option<Self::Genesis> {
         if !Self::id_is_available(organization) {
+            Self::apply_group_decay(organization);
             Some(
                 <Members<T>>::iter()
                     .filter(|(org, _, _)| *org == organization)
@@ -575,6 +2235,28 @@ impl<T: Trait> ShareInformation<T::OrgId, T::AccountId, T::Shares>
             None
         }
     }
+    fn get_membership_with_shape_paged(
+        organization: T::OrgId,
+        cursor: u32,
+        limit: u32,
+    ) -> Option<(Vec<(T::AccountId, T::Shares)>, Option<u32>)> {
+        let (group, next) = Self::get_group_paged(organization, cursor, limit)?;
+        let page = group
+            .into_iter()
+            .filter_map(|who| {
+                Self::apply_decay(organization, &who);
+                <Members<T>>::get(organization, &who)
+                    .map(|profile| (who, profile.total()))
+            })
+            .collect();
+        Some((page, next))
+    }
+    fn record_activity(organization: T::OrgId, who: &T::AccountId) {
+        if Self::apply_decay(organization, who).is_some() {
+            let now = <frame_system::Module<T>>::block_number();
+            Self::reset_decay(organization, who, now);
+        }
+    }
 }
 impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
     type Proportion = SharePortion<T::Shares, Permill>;
@@ -584,12 +2266,21 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
         amount: T::Shares,
         batch: bool,
     ) -> DispatchResult {
+        ensure!(
+            !<Observers<T>>::contains_key(organization, &new_owner),
+            Error::<T>::AccountIsObserver
+        );
+        let period_start = Self::apply_decay(organization, &new_owner);
         let mut new_member = false;
         let new_profile = if let Some(existing_profile) =
             <Members<T>>::get(organization, &new_owner)
         {
             existing_profile.add_shares(amount)
         } else {
+            ensure!(
+                <MemberCount<T>>::get(organization) < T::MaxMembers::get(),
+                Error::<T>::OrgHasMaxMembers
+            );
             new_member = true;
             ShareProfile::new_shares((organization, new_owner.clone()), amount)
         };
@@ -597,8 +2288,19 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
             let org = <Orgs<T>>::get(organization).ok_or(Error::<T>::OrgDNE)?;
             <Orgs<T>>::insert(organization, org.add_shares(amount));
         }
+        Self::record_snapshots(organization, &new_owner);
         <Members<T>>::insert(organization, new_owner.clone(), new_profile);
+        Self::deposit_share_balance(
+            organization,
+            &new_owner,
+            ShareClass::Voting,
+        );
+        // new members are active from the block they join
+        let since = period_start
+            .unwrap_or_else(<frame_system::Module<T>>::block_number);
+        Self::reset_decay(organization, &new_owner, since);
         if new_member {
+            Self::add_to_group(organization, new_owner.clone());
             Self::deposit_event(RawEvent::AddedOrgMember(
                 organization,
                 new_owner,
@@ -613,6 +2315,8 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
         amount: Option<T::Shares>,
         batch: bool,
     ) -> Result<Self::Proportion, DispatchError> {
+        // the decay is owed whether or not the burn succeeds
+        let period_start = Self::apply_decay(organization, &old_owner);
         let org = <Orgs<T>>::get(organization).ok_or(Error::<T>::OrgDNE)?;
         let old_profile = <Members<T>>::get(organization, &old_owner)
             .ok_or(Error::<T>::NotEnoughSharesToSatisfyBurnRequest)?;
@@ -636,17 +2340,33 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
         if !batch {
             <Orgs<T>>::insert(organization, org.subtract_shares(amt_to_burn));
         }
+        Self::record_snapshots(organization, &old_owner);
         let new_profile = old_profile.subtract_shares(amt_to_burn);
         if new_profile.is_zero() {
             // leave the group
             <Members<T>>::remove(organization, old_owner.clone());
+            <MemberMetadata<T>>::remove(organization, &old_owner);
+            Self::remove_from_group(organization, &old_owner);
+            Self::deposit_share_balance(
+                organization,
+                &old_owner,
+                ShareClass::Voting,
+            );
             Self::deposit_event(RawEvent::RemovedOrgMember(
                 organization,
                 old_owner,
                 amt_to_burn,
             ));
         } else {
-            <Members<T>>::insert(organization, old_owner, new_profile);
+            <Members<T>>::insert(organization, old_owner.clone(), new_profile);
+            Self::deposit_share_balance(
+                organization,
+                &old_owner,
+                ShareClass::Voting,
+            );
+        }
+        if let Some(since) = period_start {
+            Self::reset_decay(organization, &old_owner, since);
         }
         Ok(SharePortion::new(amt_to_burn, portion))
     }
@@ -660,9 +2380,22 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
         );
         let total_shares: T::Shares = <Orgs<T>>::get(organization)
             .map_or_else(Zero::zero, |o| o.total_shares());
-        let new_issuance = total_shares
+        total_shares
             .checked_add(&genesis.total())
             .ok_or(Error::<T>::IssuanceWouldOverflowShares)?;
+        // check the cap up front, members failing to join are skipped below
+        let joining = genesis
+            .vec()
+            .iter()
+            .filter(|(who, _)| !<Members<T>>::contains_key(organization, who))
+            .count() as u32;
+        ensure!(
+            <MemberCount<T>>::get(organization).saturating_add(joining)
+                <= T::MaxMembers::get(),
+            Error::<T>::OrgHasMaxMembers
+        );
+        // the returned total counts every share, so none may be skipped
+        Self::ensure_no_observers(organization, &genesis)?;
         genesis.vec().into_iter().for_each(|(member, shares)| {
             if let Ok(()) =
                 Self::issue(organization, member.clone(), shares, true)
@@ -671,10 +2404,14 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
                     organization,
                     member,
                     shares,
+                    ShareClass::Voting,
                 ));
             }
         });
-        Ok(new_issuance)
+        // read again because members may have lost shares to decay
+        let total_shares: T::Shares = <Orgs<T>>::get(organization)
+            .map_or_else(Zero::zero, |o| o.total_shares());
+        Ok(total_shares.saturating_add(genesis.total()))
     }
     fn batch_burn(
         organization: T::OrgId,
@@ -685,8 +2422,7 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
             Error::<T>::GenesisTotalMustEqualSumToUseBatchOps
         );
         let org = <Orgs<T>>::get(organization).ok_or(Error::<T>::OrgDNE)?;
-        let new_issuance = org
-            .total_shares()
+        org.total_shares()
             .checked_sub(&genesis.total())
             .ok_or(Error::<T>::IssuanceCannotGoNegative)?;
         genesis.vec().into_iter().for_each(|(member, shares)| {
@@ -697,9 +2433,13 @@ impl<T: Trait> ShareIssuance<T::OrgId, T::AccountId, T::Shares> for Module<T> {
                     organization,
                     member,
                     portion.total(),
+                    ShareClass::Voting,
                 ));
             }
         });
+        // read again because members may have lost shares to decay
+        let org = <Orgs<T>>::get(organization).ok_or(Error::<T>::OrgDNE)?;
+        let new_issuance = org.total_shares().saturating_sub(genesis.total());
         <Orgs<T>>::insert(organization, org.set_shares(new_issuance));
         Ok(())
     }
@@ -734,3 +2474,23 @@ impl<T: Trait> LockProfile<T::OrgId, T::AccountId> for Module<T> {
         Ok(())
     }
 }
+
+impl<T: Trait> VoteOutcomeHandler<SpendVoteId<T>> for Module<T> {
+    fn on_outcome(vote_id: SpendVoteId<T>, outcome: VoteOutcome) {
+        let proposal_id = match <SpendProposalVotes<T>>::take(vote_id) {
+            Some(id) => id,
+            None => return,
+        };
+        let org = match <SpendProposals<T>>::get(proposal_id) {
+            Some(proposal) => proposal.org(),
+            None => return,
+        };
+        if outcome == VoteOutcome::Approved {
+            Self::deposit_event(RawEvent::SpendApproved(org, proposal_id));
+        } else {
+            // rejected, expired and cancelled spends can never be executed
+            <SpendProposals<T>>::remove(proposal_id);
+            Self::deposit_event(RawEvent::SpendRejected(org, proposal_id));
+        }
+    }
+}
@@ -2,10 +2,12 @@
 
 use super::*;
 use frame_support::{
+    assert_noop,
     assert_ok,
     impl_outer_event,
     impl_outer_origin,
     parameter_types,
+    traits::OnInitialize,
     weights::Weight,
 };
 use sp_core::H256;
@@ -14,6 +16,7 @@ use sp_runtime::{
     traits::IdentityLookup,
     Perbill,
 };
+use std::cell::RefCell;
 
 pub type AccountId = u64;
 pub type BlockNumber = u64;
@@ -29,6 +32,7 @@ mod org {
 impl_outer_event! {
     pub enum TestEvent for TestRuntime {
         frame_system<T>,
+        pallet_balances<T>,
         org<T>,
     }
 }
@@ -68,16 +72,122 @@ impl frame_system::Trait for TestRuntime {
     type BaseCallFilter = ();
     type SystemWeightInfo = ();
 }
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+    pub const MaxLocks: u32 = 50;
+}
+impl pallet_balances::Trait for TestRuntime {
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type MaxLocks = MaxLocks;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+thread_local! {
+    // (threshold id, org) pairs registered with the mock vote module
+    static THRESHOLDS: RefCell<Vec<(u64, u64)>> = RefCell::new(Vec::new());
+    // outcomes of the votes opened by the mock vote module, indexed by vote id
+    static OUTCOMES: RefCell<Vec<VoteOutcome>> = RefCell::new(Vec::new());
+    // durations of the votes opened by the mock vote module
+    static DURATIONS: RefCell<Vec<Option<u64>>> = RefCell::new(Vec::new());
+}
+pub struct MockVote;
+impl MockVote {
+    fn register_threshold(id: u64, org: u64) {
+        THRESHOLDS.with(|t| t.borrow_mut().push((id, org)));
+    }
+    fn set_outcome(vote_id: u64, outcome: VoteOutcome) {
+        OUTCOMES.with(|o| o.borrow_mut()[vote_id as usize] = outcome);
+    }
+    fn duration(vote_id: u64) -> Option<u64> {
+        DURATIONS.with(|d| d.borrow()[vote_id as usize])
+    }
+}
+impl ThresholdVote<u64, u32, u64> for MockVote {
+    type ThresholdId = u64;
+    type VoteId = u64;
+    fn threshold_org(id: u64) -> Option<u64> {
+        THRESHOLDS.with(|t| {
+            t.borrow().iter().find(|(i, _)| *i == id).map(|(_, o)| *o)
+        })
+    }
+    fn default_threshold(org: u64) -> Option<u64> {
+        THRESHOLDS.with(|t| {
+            t.borrow()
+                .iter()
+                .rev()
+                .find(|(_, o)| *o == org)
+                .map(|(i, _)| *i)
+        })
+    }
+    fn open_threshold_vote(
+        id: u64,
+        _topic: Option<u32>,
+        duration: Option<u64>,
+    ) -> Result<u64, DispatchError> {
+        Self::threshold_org(id)
+            .ok_or(DispatchError::Other("threshold does not exist"))?;
+        DURATIONS.with(|d| d.borrow_mut().push(duration));
+        Ok(OUTCOMES.with(|o| {
+            let mut o = o.borrow_mut();
+            o.push(VoteOutcome::Voting);
+            o.len() as u64 - 1
+        }))
+    }
+    fn threshold_vote_outcome(
+        vote_id: u64,
+    ) -> Result<VoteOutcome, DispatchError> {
+        OUTCOMES
+            .with(|o| o.borrow().get(vote_id as usize).cloned())
+            .ok_or(DispatchError::Other("vote does not exist"))
+    }
+}
+// mock cids are numbers, `BAD_CID` stands in for malformed bytes
+pub const BAD_CID: u32 = u32::MAX;
+pub struct MockCidFormat;
+impl CidFormat for MockCidFormat {
+    fn is_valid(encoded: &[u8]) -> bool {
+        encoded != BAD_CID.encode().as_slice()
+    }
+}
+parameter_types! {
+    pub const OrgTreasury: ModuleId = ModuleId(*b"org/trsy");
+    pub const SpendVoteDuration: BlockNumber = 10;
+    pub const MaxMembers: u32 = 10;
+    pub const HandleCooldown: BlockNumber = 10;
+    pub const MaxApplicationDeposit: u64 = 50;
+    pub const MaxApplicationCooldown: BlockNumber = 10;
+    pub const MaxPendingApplications: u32 = 2;
+    pub const ShareGrantExpiry: BlockNumber = 10;
+    pub const MaxPendingShareGrants: u32 = 3;
+}
 impl Trait for TestRuntime {
     type Event = TestEvent;
     type Cid = u32;
+    type CidFormat = MockCidFormat;
     type OrgId = u64;
     type Shares = u64;
+    type OrgCurrency = Balances;
+    type OrgTreasury = OrgTreasury;
+    type SpendVote = MockVote;
+    type SpendVoteDuration = SpendVoteDuration;
+    type MaxMembers = MaxMembers;
+    type HandleCooldown = HandleCooldown;
+    type MaxApplicationDeposit = MaxApplicationDeposit;
+    type MaxApplicationCooldown = MaxApplicationCooldown;
+    type MaxPendingApplications = MaxPendingApplications;
+    type ShareGrantExpiry = ShareGrantExpiry;
+    type MaxPendingShareGrants = MaxPendingShareGrants;
+    type ShareChangeHandler = ();
+    type LifecycleHandler = ();
 }
 pub type System = frame_system::Module<TestRuntime>;
+pub type Balances = pallet_balances::Module<TestRuntime>;
 pub type Org = Module<TestRuntime>;
 
-fn get_last_event() -> RawEvent<u64, u64, u64, u32> {
+fn get_last_event() -> RawEvent<u64, u64, u64, u32, u64, u64, u64> {
     System::events()
         .into_iter()
         .map(|r| r.event)
@@ -96,6 +206,11 @@ fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<TestRuntime>()
         .unwrap();
+    pallet_balances::GenesisConfig::<TestRuntime> {
+        balances: vec![(1, 100), (2, 98), (3, 200)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
     GenesisConfig::<TestRuntime> {
         sudo: 1,
         doc: 1738,
@@ -136,6 +251,7 @@ fn organization_registration() {
             None,
             constitution,
             accounts,
+            None,
         ));
         assert_eq!(Org::org_counter(), 2);
         assert_eq!(
@@ -151,6 +267,7 @@ fn organization_registration() {
             None,
             third_org_constitution,
             third_org_accounts,
+            None,
         ));
         assert_eq!(Org::org_counter(), 3);
         assert_eq!(
@@ -171,6 +288,7 @@ fn share_lock() {
         let locked_profile = Org::members(1, 1).unwrap();
         let locked = !locked_profile.is_unlocked();
         assert_eq!(locked, true);
+        assert_eq!(get_last_event(), RawEvent::SharesLocked(1, 1, 1));
     });
 }
 
@@ -189,6 +307,7 @@ fn share_unlock() {
         let unlocked_profile = Org::members(1, 1).unwrap();
         let is_unlocked = unlocked_profile.is_unlocked();
         assert_eq!(is_unlocked, true);
+        assert_eq!(get_last_event(), RawEvent::SharesUnlocked(1, 1, 1));
     });
 }
 
@@ -201,7 +320,7 @@ fn share_issuance() {
 
         assert_eq!(pre_shares, 1);
         // issue 10 new shares to member 1
-        assert_ok!(Org::issue_shares(one, 1, 1, 10));
+        assert_ok!(Org::issue_shares(one, 1, 1, 10, ShareClass::Voting));
 
         let post_profile = Org::members(1, 1).unwrap();
         let post_shares = post_profile.total();
@@ -219,17 +338,1528 @@ fn share_burn() {
 
         assert_eq!(pre_shares, 1);
         // issue 10 new shares to 10
-        assert_ok!(Org::issue_shares(one.clone(), 1, 1, 10));
+        assert_ok!(Org::issue_shares(
+            one.clone(),
+            1,
+            1,
+            10,
+            ShareClass::Voting
+        ));
 
         let pre_pre_profile = Org::members(1, 1).unwrap();
         let pre_pre_shares = pre_pre_profile.total();
 
         assert_eq!(pre_pre_shares, 11);
         // burn 10 new shares for 10
-        assert_ok!(Org::burn_shares(one, 1, 1, 5));
+        assert_ok!(Org::burn_shares(one, 1, 1, 5, ShareClass::Voting));
         let post_profile = Org::members(1, 1).unwrap();
         let post_shares = post_profile.total();
 
         assert_eq!(post_shares, 6);
     });
 }
+
+#[test]
+fn share_balance_events_replay_to_the_balances_in_storage() {
+    new_test_ext().execute_with(|| {
+        let (voting, non_voting) = (ShareClass::Voting, ShareClass::NonVoting);
+        assert_ok!(Org::new_weighted_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![(1, 1000), (2, 500), (3, 10)],
+            None,
+        ));
+        assert_ok!(Org::set_decay_policy(
+            Origin::signed(1),
+            2,
+            Some((Permill::from_percent(10), 10, 100))
+        ));
+        assert_ok!(Org::issue_shares(Origin::signed(1), 2, 4, 40, voting));
+        assert_ok!(Org::burn_shares(Origin::signed(1), 2, 2, 100, voting));
+        assert_ok!(Org::burn_shares(Origin::signed(1), 2, 3, 10, voting));
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            2,
+            vec![(5, 20), (6, 30)],
+            non_voting
+        ));
+        assert_ok!(Org::batch_burn_shares(
+            Origin::signed(1),
+            2,
+            vec![(5, 5)],
+            non_voting
+        ));
+        assert_ok!(Org::convert_shares(
+            Origin::signed(1),
+            2,
+            6,
+            non_voting,
+            30
+        ));
+        assert_ok!(Org::convert_shares(Origin::signed(1), 2, 4, voting, 15));
+        assert_ok!(Org::lock_shares(Origin::signed(1), 2, 1));
+        System::set_block_number(21);
+        Org::get_membership_with_shape(2).unwrap();
+        let mut replayed = std::collections::BTreeMap::new();
+        for event in System::events().into_iter().map(|r| r.event) {
+            if let TestEvent::org(RawEvent::ShareBalanceChanged(
+                org,
+                who,
+                class,
+                balance,
+            )) = event
+            {
+                replayed.insert((org, who, class as u8), balance);
+            }
+        }
+        // every holder of either class is replayed, emptied ones at zero
+        for who in 1..7 {
+            let voting_shares = Org::members(2, who).map_or(0, |p| p.total());
+            let non_voting_shares = Org::non_voting_shares(2, who);
+            assert_eq!(
+                replayed.get(&(2, who, voting as u8)).copied().unwrap_or(0),
+                voting_shares
+            );
+            assert_eq!(
+                replayed
+                    .get(&(2, who, non_voting as u8))
+                    .copied()
+                    .unwrap_or(0),
+                non_voting_shares
+            );
+        }
+        assert_eq!(replayed.get(&(2, 1, voting as u8)), Some(&810));
+        assert_eq!(replayed.get(&(2, 3, voting as u8)), Some(&0));
+        assert_eq!(replayed.get(&(2, 6, non_voting as u8)), Some(&0));
+        assert_eq!(replayed.get(&(2, 4, non_voting as u8)), Some(&15));
+        assert_eq!(
+            replayed
+                .iter()
+                .filter(|((_, _, class), _)| *class == voting as u8)
+                .map(|(_, balance)| balance)
+                .sum::<u64>(),
+            Org::outstanding_shares(2)
+        );
+    });
+}
+
+#[test]
+fn non_voting_shares_are_not_read_for_votes() {
+    new_test_ext().execute_with(|| {
+        let non_voting = ShareClass::NonVoting;
+        assert_ok!(Org::issue_shares(Origin::signed(1), 1, 7, 50, non_voting));
+        assert_ok!(Org::issue_shares(Origin::signed(1), 1, 2, 10, non_voting));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SharesIssued(1, 2, 10, non_voting)
+        );
+        // investors do not join the group
+        assert!(!Org::is_member_of_group(1, &7));
+        assert_eq!(Org::outstanding_shares(1), 6);
+        assert_eq!(Org::non_voting_issuance(1), 60);
+        assert_eq!(Org::get_membership_with_shape(1).unwrap().total(), 6);
+        let economic = Org::economic_shape(1).unwrap();
+        assert_eq!(economic.total(), 60);
+        assert!(economic.vec().contains(&(7, 50)));
+        assert_noop!(
+            Org::burn_shares(Origin::signed(1), 1, 7, 51, non_voting),
+            Error::<TestRuntime>::NotEnoughSharesToSatisfyBurnRequest
+        );
+        assert_ok!(Org::burn_shares(Origin::signed(1), 1, 7, 20, non_voting));
+        assert_eq!(Org::non_voting_shares(1, 7), 30);
+        assert_eq!(Org::non_voting_issuance(1), 40);
+        // the voting shares of a member are untouched
+        assert_eq!(Org::members(1, 2).unwrap().total(), 1);
+    });
+}
+
+#[test]
+fn only_the_supervisor_converts_shares() {
+    new_test_ext().execute_with(|| {
+        let non_voting = ShareClass::NonVoting;
+        assert_ok!(Org::issue_shares(Origin::signed(1), 1, 7, 50, non_voting));
+        assert_noop!(
+            Org::convert_shares(Origin::signed(7), 1, 7, non_voting, 10),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::convert_shares(Origin::signed(1), 1, 7, non_voting, 51),
+            Error::<TestRuntime>::NotEnoughSharesToSatisfyBurnRequest
+        );
+        assert_ok!(Org::convert_shares(
+            Origin::signed(1),
+            1,
+            7,
+            non_voting,
+            10
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SharesConverted(1, 7, non_voting, 10)
+        );
+        assert!(Org::is_member_of_group(1, &7));
+        assert_eq!(Org::members(1, 7).unwrap().total(), 10);
+        assert_eq!(Org::non_voting_shares(1, 7), 40);
+        assert_eq!(Org::outstanding_shares(1), 16);
+        // giving up every vote leaves the group
+        assert_ok!(Org::convert_shares(
+            Origin::signed(1),
+            1,
+            7,
+            ShareClass::Voting,
+            10
+        ));
+        assert!(!Org::is_member_of_group(1, &7));
+        assert_eq!(Org::outstanding_shares(1), 6);
+        assert_eq!(Org::non_voting_shares(1, 7), 50);
+        assert_eq!(Org::non_voting_issuance(1), 50);
+    });
+}
+
+#[test]
+fn member_metadata_is_cleared_on_removal() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::set_my_metadata(Origin::signed(7), 1, Some(10)),
+            Error::<TestRuntime>::AccountHasNoOwnershipInOrg
+        );
+        assert_ok!(Org::set_my_metadata(Origin::signed(2), 1, Some(10)));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::MemberMetadataUpdated(1, 2, Some(10)),
+        );
+        assert_eq!(Org::member_metadata(1, 2), Some(10));
+        assert_noop!(
+            Org::set_member_metadata(Origin::signed(3), 1, 2, Some(11)),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::set_member_metadata(Origin::signed(1), 1, 7, Some(11)),
+            Error::<TestRuntime>::AccountHasNoOwnershipInOrg
+        );
+        assert_ok!(Org::set_member_metadata(Origin::signed(1), 1, 3, Some(11)));
+        assert_eq!(Org::member_metadata(1, 3), Some(11));
+        assert_ok!(Org::set_my_metadata(Origin::signed(3), 1, None));
+        assert_eq!(Org::member_metadata(1, 3), None);
+        // burning all shares of a member removes their metadata
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            1,
+            2,
+            1,
+            ShareClass::Voting
+        ));
+        assert!(!Org::is_member_of_group(1, &2));
+        assert_eq!(Org::member_metadata(1, 2), None);
+    });
+}
+
+#[test]
+fn org_account_is_distinct_per_org() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_flat_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![1, 2],
+            None,
+        ));
+        assert!(Org::org_account(1) != Org::org_account(2));
+        assert!(Org::org_account(1) != 1);
+        assert_eq!(Org::org_balance(1), 0);
+    });
+}
+
+#[test]
+fn spend_executes_after_vote_approval() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::propose_spend(Origin::signed(2), 1, 9, 10, 1),
+            Error::<TestRuntime>::NoDefaultThresholdForOrg
+        );
+        // another org's default is not used
+        MockVote::register_threshold(7, 2);
+        assert_noop!(
+            Org::propose_spend(Origin::signed(2), 1, 9, 10, 1),
+            Error::<TestRuntime>::NoDefaultThresholdForOrg
+        );
+        MockVote::register_threshold(8, 1);
+        assert_noop!(
+            Org::propose_spend(Origin::signed(9), 1, 9, 10, 1),
+            Error::<TestRuntime>::NotAuthorizedToProposeSpend
+        );
+        assert_ok!(Org::propose_spend(Origin::signed(2), 1, 9, 10, 1));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SpendProposed(2, 1, 1, 9, 10, 0)
+        );
+        assert_eq!(MockVote::duration(0), Some(SpendVoteDuration::get()));
+        assert_eq!(Org::spend_proposal_of_vote(0), Some(1));
+        assert_noop!(
+            Org::execute_spend(Origin::signed(2), 1),
+            Error::<TestRuntime>::SpendNotApprovedByVote
+        );
+        MockVote::set_outcome(0, VoteOutcome::Approved);
+        Org::on_outcome(0, VoteOutcome::Approved);
+        assert_eq!(get_last_event(), RawEvent::SpendApproved(1, 1));
+        assert_eq!(Org::spend_proposal_of_vote(0), None);
+        assert_noop!(
+            Org::execute_spend(Origin::signed(2), 1),
+            Error::<TestRuntime>::InsufficientOrgTreasuryBalance
+        );
+        assert_ok!(Balances::transfer(
+            Origin::signed(3),
+            Org::org_account(1),
+            50
+        ));
+        assert_ok!(Org::execute_spend(Origin::signed(2), 1));
+        assert_eq!(get_last_event(), RawEvent::SpendExecuted(1, 1, 9, 10));
+        assert_eq!(Balances::free_balance(9), 10);
+        assert_eq!(Org::org_balance(1), 40);
+        assert!(Org::spend_proposals(1).is_none());
+        assert_noop!(
+            Org::execute_spend(Origin::signed(2), 1),
+            Error::<TestRuntime>::SpendProposalDNE
+        );
+    });
+}
+
+#[test]
+fn rejected_spends_are_removed() {
+    new_test_ext().execute_with(|| {
+        MockVote::register_threshold(8, 1);
+        assert_ok!(Org::propose_spend(Origin::signed(2), 1, 9, 10, 1));
+        assert_ok!(Org::propose_spend(Origin::signed(2), 1, 9, 20, 1));
+        // expired votes are reported rejected
+        MockVote::set_outcome(0, VoteOutcome::Rejected);
+        Org::on_outcome(0, VoteOutcome::Rejected);
+        assert_eq!(get_last_event(), RawEvent::SpendRejected(1, 1));
+        assert!(Org::spend_proposals(1).is_none());
+        assert_eq!(Org::spend_proposal_of_vote(0), None);
+        MockVote::set_outcome(1, VoteOutcome::Cancelled);
+        Org::on_outcome(1, VoteOutcome::Cancelled);
+        assert_eq!(get_last_event(), RawEvent::SpendRejected(1, 2));
+        assert!(Org::spend_proposals(2).is_none());
+        assert_noop!(
+            Org::execute_spend(Origin::signed(2), 1),
+            Error::<TestRuntime>::SpendProposalDNE
+        );
+        // votes deciding no spend are ignored
+        Org::on_outcome(5, VoteOutcome::Rejected);
+        assert_eq!(get_last_event(), RawEvent::SpendRejected(1, 2));
+    });
+}
+
+#[test]
+fn guarded_issuance_is_limited_per_block() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::guard_org_actions(
+                Origin::signed(2),
+                1,
+                GuardedActions::ISSUE_SHARES,
+                5
+            ),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::guard_org_actions(
+            Origin::signed(1),
+            1,
+            GuardedActions::ISSUE_SHARES,
+            5
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::OrgActionsGuarded(1, GuardedActions::ISSUE_SHARES)
+        );
+        // the limit may be lowered but never raised
+        assert_ok!(Org::guard_org_actions(
+            Origin::signed(1),
+            1,
+            GuardedActions::ISSUE_SHARES,
+            50
+        ));
+        assert_eq!(Org::issuance_limits(1), 5);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            2,
+            3,
+            ShareClass::Voting
+        ));
+        assert_noop!(
+            Org::issue_shares(Origin::signed(1), 1, 2, 3, ShareClass::Voting),
+            Error::<TestRuntime>::ActionGuardedByVote
+        );
+        assert_noop!(
+            Org::batch_issue_shares(
+                Origin::signed(1),
+                1,
+                vec![(2, 3)],
+                ShareClass::Voting
+            ),
+            Error::<TestRuntime>::ActionGuardedByVote
+        );
+        System::set_block_number(2);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            2,
+            3,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::members(1, 2).unwrap().total(), 7);
+    });
+}
+
+#[test]
+fn guarded_burns_and_constitution_changes_are_rejected() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::change_constitution(Origin::signed(1), 1, 7));
+        assert_eq!(get_last_event(), RawEvent::ConstitutionChanged(1, 7));
+        assert_eq!(Org::orgs(1).unwrap().constitution(), 7);
+        assert_ok!(Org::guard_org_actions(
+            Origin::signed(1),
+            1,
+            GuardedActions::BURN_SHARES | GuardedActions::CHANGE_CONSTITUTION,
+            0
+        ));
+        assert_noop!(
+            Org::change_constitution(Origin::signed(1), 1, 8),
+            Error::<TestRuntime>::ActionGuardedByVote
+        );
+        assert_noop!(
+            Org::burn_shares(Origin::signed(1), 1, 2, 1, ShareClass::Voting),
+            Error::<TestRuntime>::ActionGuardedByVote
+        );
+        assert_noop!(
+            Org::batch_burn_shares(
+                Origin::signed(1),
+                1,
+                vec![(2, 1)],
+                ShareClass::Voting
+            ),
+            Error::<TestRuntime>::ActionGuardedByVote
+        );
+        // the supervisor may still burn their own shares
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            1,
+            2,
+            ShareClass::Voting
+        ));
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            1,
+            1,
+            1,
+            ShareClass::Voting
+        ));
+    });
+}
+
+#[test]
+fn guarded_actions_execute_after_vote_approval() {
+    new_test_ext().execute_with(|| {
+        let action = OrgAction::ChangeConstitution(8);
+        assert_noop!(
+            Org::propose_org_action(Origin::signed(2), 1, action.clone()),
+            Error::<TestRuntime>::OrgActionNotGuarded
+        );
+        assert_ok!(Org::guard_org_actions(
+            Origin::signed(1),
+            1,
+            GuardedActions::CHANGE_CONSTITUTION,
+            0
+        ));
+        assert_noop!(
+            Org::propose_org_action(Origin::signed(2), 1, action.clone()),
+            Error::<TestRuntime>::NoActionThresholdSetForOrg
+        );
+        MockVote::register_threshold(8, 1);
+        assert_ok!(Org::set_action_threshold(Origin::signed(1), 1, 8));
+        assert_noop!(
+            Org::propose_org_action(Origin::signed(9), 1, action.clone()),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::propose_org_action(
+            Origin::signed(2),
+            1,
+            action.clone()
+        ));
+        assert_eq!(get_last_event(), RawEvent::OrgActionProposed(2, 1, 1, 0));
+        assert_noop!(
+            Org::execute_org_action(Origin::signed(2), 1, action.clone()),
+            Error::<TestRuntime>::OrgActionNotApprovedByVote
+        );
+        MockVote::set_outcome(0, VoteOutcome::Approved);
+        assert_noop!(
+            Org::execute_org_action(
+                Origin::signed(2),
+                1,
+                OrgAction::ChangeConstitution(9)
+            ),
+            Error::<TestRuntime>::OrgActionDoesNotMatchProposal
+        );
+        assert_ok!(Org::execute_org_action(Origin::signed(2), 1, action));
+        assert_eq!(get_last_event(), RawEvent::OrgActionExecuted(1, 1));
+        assert_eq!(Org::orgs(1).unwrap().constitution(), 8);
+        assert!(Org::org_action_proposals(1).is_none());
+        // guards are only removed by vote
+        let remove =
+            OrgAction::RemoveGuards(GuardedActions::CHANGE_CONSTITUTION);
+        assert_ok!(Org::propose_org_action(
+            Origin::signed(1),
+            1,
+            remove.clone()
+        ));
+        MockVote::set_outcome(1, VoteOutcome::Approved);
+        assert_ok!(Org::execute_org_action(Origin::signed(1), 2, remove));
+        assert_eq!(Org::guarded_org_actions(1), GuardedActions(0));
+        assert_ok!(Org::change_constitution(Origin::signed(1), 1, 10));
+    });
+}
+
+#[test]
+fn emergency_powers_are_granted_by_vote_and_expire() {
+    new_test_ext().execute_with(|| {
+        MockVote::register_threshold(8, 1);
+        assert_ok!(Org::set_action_threshold(Origin::signed(1), 1, 8));
+        let grant =
+            OrgAction::GrantEmergencyPower(2, PowerMask::ISSUE_SHARES, 10);
+        assert_noop!(
+            Org::issue_shares(Origin::signed(2), 1, 7, 5, ShareClass::Voting),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::propose_org_action(Origin::signed(2), 1, grant));
+        assert_noop!(
+            Org::grant_emergency_power(
+                Origin::signed(3),
+                1,
+                2,
+                PowerMask::ISSUE_SHARES,
+                10,
+                1
+            ),
+            Error::<TestRuntime>::OrgActionNotApprovedByVote
+        );
+        MockVote::set_outcome(0, VoteOutcome::Approved);
+        // the grant executed must be the grant voted on
+        assert_noop!(
+            Org::grant_emergency_power(
+                Origin::signed(3),
+                1,
+                2,
+                PowerMask::BURN_SHARES,
+                10,
+                1
+            ),
+            Error::<TestRuntime>::OrgActionDoesNotMatchProposal
+        );
+        assert_ok!(Org::grant_emergency_power(
+            Origin::signed(3),
+            1,
+            2,
+            PowerMask::ISSUE_SHARES,
+            10,
+            1
+        ));
+        assert_eq!(
+            Org::emergency_power(1, 2),
+            Some((PowerMask(PowerMask::ISSUE_SHARES), 11))
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(2),
+            1,
+            7,
+            5,
+            ShareClass::Voting
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::EmergencyPowerUsed(1, 2, PowerMask::ISSUE_SHARES)
+        );
+        // only the granted powers
+        assert_noop!(
+            Org::burn_shares(Origin::signed(2), 1, 7, 5, ShareClass::Voting),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        System::set_block_number(11);
+        assert_eq!(
+            Org::issue_shares(Origin::signed(2), 1, 7, 5, ShareClass::Voting),
+            Err(Error::<TestRuntime>::NotAuthorizedForAccount.into())
+        );
+        assert_eq!(get_last_event(), RawEvent::EmergencyPowerExpired(1, 2));
+        assert!(Org::emergency_power(1, 2).is_none());
+    });
+}
+
+#[test]
+fn only_the_supervisor_revokes_emergency_powers() {
+    new_test_ext().execute_with(|| {
+        MockVote::register_threshold(8, 1);
+        assert_ok!(Org::set_action_threshold(Origin::signed(1), 1, 8));
+        let zero = OrgAction::GrantEmergencyPower(2, PowerMask::LOCK_SHARES, 0);
+        assert_ok!(Org::propose_org_action(Origin::signed(2), 1, zero));
+        MockVote::set_outcome(0, VoteOutcome::Approved);
+        assert_noop!(
+            Org::grant_emergency_power(
+                Origin::signed(2),
+                1,
+                2,
+                PowerMask::LOCK_SHARES,
+                0,
+                1
+            ),
+            Error::<TestRuntime>::EmergencyPowerDurationCannotBeZero
+        );
+        let grant =
+            OrgAction::GrantEmergencyPower(2, PowerMask::LOCK_SHARES, 5);
+        assert_ok!(Org::propose_org_action(Origin::signed(2), 1, grant));
+        MockVote::set_outcome(1, VoteOutcome::Approved);
+        assert_ok!(Org::grant_emergency_power(
+            Origin::signed(2),
+            1,
+            2,
+            PowerMask::LOCK_SHARES,
+            5,
+            2
+        ));
+        assert_ok!(Org::lock_shares(Origin::signed(2), 1, 3));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::EmergencyPowerUsed(1, 2, PowerMask::LOCK_SHARES)
+        );
+        // emergency powers never include revoking
+        assert_noop!(
+            Org::revoke_emergency_power(Origin::signed(2), 1, 2),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::revoke_emergency_power(Origin::signed(1), 1, 2));
+        assert_eq!(get_last_event(), RawEvent::EmergencyPowerRevoked(1, 2));
+        assert_noop!(
+            Org::unlock_shares(Origin::signed(2), 1, 3),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::revoke_emergency_power(Origin::signed(1), 1, 2),
+            Error::<TestRuntime>::EmergencyPowerDNE
+        );
+    });
+}
+
+#[test]
+fn membership_is_capped_at_max_members() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Org::member_count(1), 6);
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            1,
+            vec![(7, 1), (8, 1), (9, 1)],
+            ShareClass::Voting
+        ));
+        // existing members joining again do not count against the cap
+        assert_noop!(
+            Org::batch_issue_shares(
+                Origin::signed(1),
+                1,
+                vec![(2, 1), (10, 1), (11, 1)],
+                ShareClass::Voting
+            ),
+            Error::<TestRuntime>::OrgHasMaxMembers
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            10,
+            1,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::member_count(1), 10);
+        assert_noop!(
+            Org::issue_shares(Origin::signed(1), 1, 11, 1, ShareClass::Voting),
+            Error::<TestRuntime>::OrgHasMaxMembers
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            2,
+            5,
+            ShareClass::Voting
+        ));
+        // a leaving member makes room for a new one
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            1,
+            3,
+            1,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::member_count(1), 9);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            11,
+            1,
+            ShareClass::Voting
+        ));
+    });
+}
+
+#[test]
+fn paged_group_reads_cover_the_membership() {
+    new_test_ext().execute_with(|| {
+        assert!(Org::get_group_paged(2, 0, 4).is_none());
+        let (first, next) = Org::get_group_paged(1, 0, 4).unwrap();
+        assert_eq!(first, vec![1, 2, 3, 4]);
+        assert_eq!(next, Some(4));
+        let (second, next) = Org::get_group_paged(1, 4, 4).unwrap();
+        assert_eq!(second, vec![5, 6]);
+        assert_eq!(next, None);
+        // the last member takes the position of a leaving one
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            1,
+            2,
+            1,
+            ShareClass::Voting
+        ));
+        let (all, next) = Org::get_group_paged(1, 0, 10).unwrap();
+        assert_eq!(all, vec![1, 6, 3, 4, 5]);
+        assert_eq!(next, None);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            4,
+            2,
+            ShareClass::Voting
+        ));
+        let (shaped, _) =
+            Org::get_membership_with_shape_paged(1, 2, 2).unwrap();
+        assert_eq!(shaped, vec![(3, 1), (4, 3)]);
+    });
+}
+
+#[test]
+fn share_snapshots_keep_shares_until_released() {
+    new_test_ext().execute_with(|| {
+        let snapshot = Org::take_share_snapshot(1);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            2,
+            4,
+            ShareClass::Voting
+        ));
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            2,
+            4,
+            ShareClass::Voting
+        ));
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            1,
+            3,
+            1,
+            ShareClass::Voting
+        ));
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            7,
+            2,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::snapshot_shares(1, snapshot, &1), 1);
+        assert_eq!(Org::snapshot_shares(1, snapshot, &2), 1);
+        assert_eq!(Org::snapshot_shares(1, snapshot, &3), 1);
+        assert_eq!(Org::snapshot_shares(1, snapshot, &7), 0);
+        Org::release_share_snapshot(1, snapshot);
+        assert!(Org::share_snapshots(1).is_empty());
+        assert_eq!(Org::snapshot_shares(1, snapshot, &2), 9);
+        assert_eq!(Org::snapshot_shares(1, snapshot, &3), 0);
+    });
+}
+
+#[test]
+fn org_handles_are_unique_and_cool_down() {
+    new_test_ext().execute_with(|| {
+        let devs = util::organization::org_handle("sunshine-devs").unwrap();
+        assert_ok!(Org::new_flat_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![1, 2],
+            Some(devs),
+        ));
+        assert_eq!(Org::org_by_handle(devs), Some(2));
+        assert_eq!(Org::org_handle(2), Some(devs));
+        assert_eq!(get_last_event(), RawEvent::OrgHandleSet(2, Some(devs)));
+        assert_noop!(
+            Org::set_org_handle(Origin::signed(1), 1, Some(devs)),
+            Error::<TestRuntime>::HandleTaken
+        );
+        assert_noop!(
+            Org::set_org_handle(Origin::signed(2), 2, None),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        let mut invalid = devs;
+        invalid[0] = b'S';
+        assert_noop!(
+            Org::set_org_handle(Origin::signed(1), 1, Some(invalid)),
+            Error::<TestRuntime>::InvalidOrgHandle
+        );
+        // renaming releases the old handle after the cooldown
+        let core = util::organization::org_handle("sunshine-core").unwrap();
+        assert_ok!(Org::set_org_handle(Origin::signed(1), 2, Some(core)));
+        assert_eq!(Org::org_by_handle(devs), None);
+        assert_eq!(Org::org_by_handle(core), Some(2));
+        assert_noop!(
+            Org::set_org_handle(Origin::signed(1), 1, Some(devs)),
+            Error::<TestRuntime>::HandleCoolingDown
+        );
+        System::set_block_number(11);
+        assert_ok!(Org::set_org_handle(Origin::signed(1), 1, Some(devs)));
+        assert_eq!(Org::org_by_handle(devs), Some(1));
+        assert_eq!(Org::released_handles(devs), None);
+        // so does removing the org
+        assert_ok!(Org::recursive_remove_organization(2));
+        assert_eq!(Org::org_by_handle(core), None);
+        assert_eq!(Org::org_handle(2), None);
+        assert_eq!(Org::released_handles(core), Some(21));
+    });
+}
+
+#[test]
+fn set_decay_policy_requires_supervisor_and_period() {
+    new_test_ext().execute_with(|| {
+        let policy = Some((Permill::from_percent(10), 10, 100));
+        assert_noop!(
+            Org::set_decay_policy(Origin::signed(2), 1, policy),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::set_decay_policy(
+                Origin::signed(1),
+                1,
+                Some((Permill::from_percent(10), 0, 100))
+            ),
+            Error::<TestRuntime>::DecayPeriodCannotBeZero
+        );
+        assert_ok!(Org::set_decay_policy(Origin::signed(1), 1, policy));
+        assert_eq!(get_last_event(), RawEvent::DecayPolicySet(1, 1, true));
+        assert_eq!(
+            Org::decay_policy(1),
+            Some(DecayPolicy::new(Permill::from_percent(10), 10, 100, 1))
+        );
+        assert_ok!(Org::set_decay_policy(Origin::signed(1), 1, None));
+        assert_eq!(Org::decay_policy(1), None);
+    });
+}
+
+#[test]
+fn lazy_decay_equals_one_bulk_decay_and_stops_at_the_floor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_weighted_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![(1, 1000), (2, 1000), (3, 10), (4, 100), (5, 105)],
+            None,
+        ));
+        assert_ok!(Org::set_decay_policy(
+            Origin::signed(1),
+            2,
+            Some((Permill::from_percent(10), 10, 100))
+        ));
+        let shares = |who| Org::members(2, who).unwrap().total();
+        // nothing decays before a full period passed
+        System::set_block_number(10);
+        Org::get_membership_with_shape(2).unwrap();
+        assert_eq!(shares(1), 1000);
+        System::set_block_number(11);
+        Org::get_membership_with_shape_paged(2, 0, 1).unwrap();
+        assert_eq!(shares(1), 900);
+        assert_eq!(get_last_event(), RawEvent::SharesDecayed(2, 1, 100));
+        System::set_block_number(21);
+        Org::get_membership_with_shape_paged(2, 0, 1).unwrap();
+        assert_eq!(shares(2), 1000);
+        System::set_block_number(31);
+        let group = Org::get_membership_with_shape(2).unwrap();
+        // applied every period or once for all three periods
+        assert_eq!(shares(1), 729);
+        assert_eq!(shares(2), 729);
+        // members at or below the floor keep their shares
+        assert_eq!(shares(3), 10);
+        assert_eq!(shares(4), 100);
+        assert_eq!(shares(5), 100);
+        assert_eq!(group.total(), 1668);
+        assert_eq!(Org::outstanding_shares(2), 1668);
+        // voting restarts the decay from the shares held then
+        System::set_block_number(35);
+        Org::record_activity(2, &1);
+        assert_eq!(Org::last_active(2, 1), Some((35, 729)));
+        System::set_block_number(41);
+        Org::get_membership_with_shape(2).unwrap();
+        assert_eq!(shares(1), 729);
+        assert_eq!(shares(2), 656);
+        assert_eq!(shares(4), 100);
+        // issuance keeps the progress into the current period
+        System::set_block_number(46);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            2,
+            2,
+            344,
+            ShareClass::Voting
+        ));
+        assert_eq!(shares(2), 1000);
+        assert_eq!(Org::last_active(2, 2), Some((41, 1000)));
+        System::set_block_number(51);
+        Org::get_membership_with_shape(2).unwrap();
+        assert_eq!(shares(1), 656);
+        assert_eq!(shares(2), 900);
+        assert_eq!(Org::outstanding_shares(2), 1766);
+    });
+}
+
+/// The voting shares of `who` in the org with their proof against its
+/// membership root
+fn membership_proof_of(org: u64, who: AccountId) -> (u64, Vec<H256>) {
+    let mut members = <Members<TestRuntime>>::iter_prefix(org)
+        .map(|(account, profile)| (account, profile.total()))
+        .collect::<Vec<_>>();
+    members.sort();
+    let index = members.iter().position(|(a, _)| *a == who).unwrap();
+    let shares = members[index].1;
+    let leaves = util::merkle::membership_leaves(members);
+    (shares, util::merkle::merkle_proof(&leaves, index).unwrap())
+}
+
+#[test]
+fn membership_proofs_verify_against_the_root_after_share_changes() {
+    use util::merkle::verify_membership_proof;
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_weighted_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![(1, 10), (2, 20), (3, 30)],
+            None,
+        ));
+        let root = Org::membership_root(2);
+        assert_eq!(Org::cached_membership_root(2), Some(root));
+        let (shares, proof) = membership_proof_of(2, 2);
+        assert_eq!(shares, 20);
+        assert!(verify_membership_proof(root, &2u64, 20u64, &proof));
+        assert!(!verify_membership_proof(root, &2u64, 21u64, &proof));
+        assert!(!verify_membership_proof(root, &4u64, 20u64, &proof));
+        // a new member invalidates the cached root
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            2,
+            4,
+            40,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::cached_membership_root(2), None);
+        let new_root = Org::membership_root(2);
+        assert_ne!(new_root, root);
+        assert!(!verify_membership_proof(new_root, &2u64, 20u64, &proof));
+        let (shares, proof) = membership_proof_of(2, 2);
+        assert!(verify_membership_proof(new_root, &2u64, shares, &proof));
+        let (shares, proof) = membership_proof_of(2, 4);
+        assert_eq!(shares, 40);
+        assert!(verify_membership_proof(new_root, &4u64, 40u64, &proof));
+        // so does a member leaving
+        let (_, proof_of_1) = membership_proof_of(2, 1);
+        assert_ok!(Org::burn_shares(
+            Origin::signed(1),
+            2,
+            1,
+            10,
+            ShareClass::Voting
+        ));
+        let root = Org::membership_root(2);
+        assert_ne!(root, new_root);
+        assert!(!verify_membership_proof(root, &1u64, 10u64, &proof_of_1));
+        for who in 2..5 {
+            let (shares, proof) = membership_proof_of(2, who);
+            assert!(verify_membership_proof(root, &who, shares, &proof));
+        }
+        // non-voting shares do not change the root
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            2,
+            2,
+            5,
+            ShareClass::NonVoting
+        ));
+        assert_eq!(Org::cached_membership_root(2), Some(root));
+        assert_ok!(Org::publish_membership_root(Origin::signed(5), 2));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::MembershipRootPublished(2, root)
+        );
+        assert_noop!(
+            Org::publish_membership_root(Origin::signed(5), 99),
+            Error::<TestRuntime>::OrgDNE
+        );
+    });
+}
+
+#[test]
+fn application_deposits_are_returned_or_forfeited() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_flat_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![1],
+            None,
+        ));
+        assert_noop!(
+            Org::set_application_policy(Origin::signed(2), 2, 20, 5),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::set_application_policy(Origin::signed(1), 2, 51, 5),
+            Error::<TestRuntime>::ApplicationDepositAboveMax
+        );
+        assert_noop!(
+            Org::set_application_policy(Origin::signed(1), 2, 20, 11),
+            Error::<TestRuntime>::ApplicationCooldownAboveMax
+        );
+        assert_ok!(Org::set_application_policy(Origin::signed(1), 2, 20, 5));
+        assert_noop!(
+            Org::apply_for_membership(Origin::signed(1), 2, None),
+            Error::<TestRuntime>::AccountAlreadyOrgMember
+        );
+        // withdrawing returns the deposit
+        assert_ok!(Org::apply_for_membership(Origin::signed(2), 2, Some(7)));
+        assert_eq!(get_last_event(), RawEvent::MembershipApplied(2, 2, 20));
+        assert_eq!(Balances::reserved_balance(2), 20);
+        assert_eq!(Balances::free_balance(2), 78);
+        assert_eq!(Org::pending_applications(2), 1);
+        assert_noop!(
+            Org::apply_for_membership(Origin::signed(2), 2, None),
+            Error::<TestRuntime>::ApplicationAlreadyPending
+        );
+        assert_ok!(Org::withdraw_application(Origin::signed(2), 2));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 98);
+        assert_noop!(
+            Org::apply_for_membership(Origin::signed(2), 2, None),
+            Error::<TestRuntime>::ApplicationCoolingDown
+        );
+        // so does approving the application
+        System::set_block_number(6);
+        assert_ok!(Org::apply_for_membership(Origin::signed(2), 2, None));
+        assert_noop!(
+            Org::approve_application(Origin::signed(2), 2, 2, 10),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::approve_application(Origin::signed(1), 2, 2, 10));
+        assert_eq!(get_last_event(), RawEvent::ApplicationApproved(2, 2, 10));
+        assert!(Org::is_member_of_group(2, &2));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 98);
+        assert_eq!(Org::pending_applications(2), 0);
+        assert_noop!(
+            Org::approve_application(Origin::signed(1), 2, 3, 10),
+            Error::<TestRuntime>::ApplicationDNE
+        );
+        // and rejecting it unless it is spam
+        assert_ok!(Org::apply_for_membership(Origin::signed(3), 2, None));
+        assert_ok!(Org::reject_application(Origin::signed(1), 2, 3, false));
+        assert_eq!(Balances::free_balance(3), 200);
+        System::set_block_number(11);
+        assert_ok!(Org::apply_for_membership(Origin::signed(3), 2, None));
+        assert_ok!(Org::reject_application(Origin::signed(1), 2, 3, true));
+        assert_eq!(get_last_event(), RawEvent::ApplicationRejected(2, 3, true));
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(Balances::free_balance(3), 180);
+        assert_eq!(Org::org_balance(2), 20);
+        assert!(!Org::is_member_of_group(2, &3));
+    });
+}
+
+#[test]
+fn applications_are_capped_per_org() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_flat_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1739,
+            vec![1],
+            None,
+        ));
+        assert_ok!(Org::apply_for_membership(Origin::signed(2), 2, None));
+        assert_ok!(Org::apply_for_membership(Origin::signed(3), 2, None));
+        assert_noop!(
+            Org::apply_for_membership(Origin::signed(4), 2, None),
+            Error::<TestRuntime>::ApplicationQueueFull
+        );
+        // other orgs have their own queue
+        assert_ok!(Org::new_flat_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            1740,
+            vec![1],
+            None,
+        ));
+        assert_ok!(Org::apply_for_membership(Origin::signed(4), 3, None));
+        assert_ok!(Org::withdraw_application(Origin::signed(2), 2));
+        assert_eq!(Org::pending_applications(2), 1);
+        assert_ok!(Org::apply_for_membership(Origin::signed(4), 2, None));
+        assert_eq!(Org::pending_applications(2), 2);
+    });
+}
+
+#[test]
+fn observers_hold_no_shares() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::add_observer(Origin::signed(2), 1, 7, 42),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::add_observer(Origin::signed(1), 1, 2, 42),
+            Error::<TestRuntime>::ObserverMustNotHoldShares
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            8,
+            10,
+            ShareClass::NonVoting
+        ));
+        assert_noop!(
+            Org::add_observer(Origin::signed(1), 1, 8, 42),
+            Error::<TestRuntime>::ObserverMustNotHoldShares
+        );
+        assert_ok!(Org::add_observer(Origin::signed(1), 1, 7, 42));
+        assert_eq!(get_last_event(), RawEvent::ObserverAdded(1, 7, 42));
+        assert_eq!(Org::observer(1, 7), Some(42));
+        assert!(!Org::is_member_of_group(1, &7));
+        // shares of neither class are issued to observers
+        assert_noop!(
+            Org::issue_shares(Origin::signed(1), 1, 7, 10, ShareClass::Voting),
+            Error::<TestRuntime>::AccountIsObserver
+        );
+        assert_noop!(
+            Org::issue_shares(
+                Origin::signed(1),
+                1,
+                7,
+                10,
+                ShareClass::NonVoting
+            ),
+            Error::<TestRuntime>::AccountIsObserver
+        );
+        // batches naming an observer are refused as a whole
+        assert_noop!(
+            Org::batch_issue_shares(
+                Origin::signed(1),
+                1,
+                vec![(8, 10), (7, 10)],
+                ShareClass::Voting
+            ),
+            Error::<TestRuntime>::AccountIsObserver
+        );
+        assert_noop!(
+            Org::batch_issue_shares(
+                Origin::signed(1),
+                1,
+                vec![(8, 10), (7, 10)],
+                ShareClass::NonVoting
+            ),
+            Error::<TestRuntime>::AccountIsObserver
+        );
+        // adding the observer again updates its role
+        assert_ok!(Org::add_observer(Origin::signed(1), 1, 7, 43));
+        assert_eq!(Org::observer(1, 7), Some(43));
+        assert_noop!(
+            Org::remove_observer(Origin::signed(2), 1, 7),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        // observers may leave on their own
+        assert_ok!(Org::remove_observer(Origin::signed(7), 1, 7));
+        assert_eq!(get_last_event(), RawEvent::ObserverRemoved(1, 7));
+        assert_noop!(
+            Org::remove_observer(Origin::signed(1), 1, 7),
+            Error::<TestRuntime>::ObserverDNE
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            7,
+            10,
+            ShareClass::Voting
+        ));
+        assert!(Org::is_member_of_group(1, &7));
+    });
+}
+
+fn fork_shares(org: u64, who: AccountId) -> u64 {
+    Org::members(org, who).map_or(0, |p| p.total())
+}
+
+#[test]
+fn forks_copy_the_members_above_the_cutoff() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::new_weighted_org(
+            Origin::signed(1),
+            Some(1),
+            None,
+            5,
+            vec![(1, 10), (2, 5), (3, 1)],
+            None
+        ));
+        let cutoff = MemberFilter::MinShares(5);
+        assert_noop!(
+            Org::fork_org(Origin::signed(2), 2, 6, cutoff.clone(), false),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::fork_org(
+                Origin::signed(1),
+                2,
+                6,
+                MemberFilter::MinShares(11),
+                false
+            ),
+            Error::<TestRuntime>::ForkHasNoMembers
+        );
+        assert_ok!(Org::fork_org(
+            Origin::signed(1),
+            2,
+            6,
+            cutoff.clone(),
+            false
+        ));
+        assert_eq!(get_last_event(), RawEvent::OrgForked(2, 3, 2));
+        assert_eq!(fork_shares(3, 1), 10);
+        assert_eq!(fork_shares(3, 2), 5);
+        assert!(!Org::is_member_of_group(3, &3));
+        assert_eq!(Org::outstanding_shares(3), 15);
+        assert_eq!(Org::orgs(3).unwrap().constitution(), 6);
+        assert!(Org::is_organization_supervisor(3, &1));
+        assert!(Org::org_tree(2, 3).is_some());
+        // equal shares copy the same members with one share each
+        assert_ok!(Org::fork_org(Origin::signed(1), 2, 6, cutoff, true));
+        assert_eq!(fork_shares(4, 1), 1);
+        assert_eq!(fork_shares(4, 2), 1);
+        assert_eq!(Org::outstanding_shares(4), 2);
+    });
+}
+
+#[test]
+fn flat_orgs_fork_into_weighted_orgs() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::fork_org(
+                Origin::signed(1),
+                1,
+                6,
+                MemberFilter::Accounts((1..12).collect()),
+                false
+            ),
+            Error::<TestRuntime>::ForkListAboveMaxMembers
+        );
+        // listed accounts which are not members are skipped
+        let listed = MemberFilter::Accounts(vec![2, 3, 9]);
+        assert_ok!(Org::fork_org(Origin::signed(1), 1, 6, listed, false));
+        assert_eq!(get_last_event(), RawEvent::OrgForked(1, 2, 2));
+        assert!(!Org::is_member_of_group(2, &9));
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            2,
+            2,
+            4,
+            ShareClass::Voting
+        ));
+        assert_eq!(fork_shares(2, 2), 5);
+        assert_eq!(fork_shares(2, 3), 1);
+        assert_eq!(Org::outstanding_shares(2), 6);
+        // the source org keeps its flat membership
+        assert_eq!(fork_shares(1, 2), 1);
+        assert_eq!(Org::outstanding_shares(1), 6);
+    });
+}
+
+#[test]
+fn malformed_cids_are_rejected_at_every_ingress() {
+    new_test_ext().execute_with(|| {
+        let invalid = Error::<TestRuntime>::InvalidCidFormat;
+        let one = || Origin::signed(1);
+        assert_noop!(
+            Org::new_flat_org(one(), Some(1), None, BAD_CID, vec![1, 2], None),
+            invalid
+        );
+        assert_noop!(
+            Org::new_weighted_org(
+                one(),
+                Some(1),
+                None,
+                BAD_CID,
+                vec![(1, 10)],
+                None
+            ),
+            invalid
+        );
+        assert_noop!(
+            Org::set_my_metadata(Origin::signed(2), 1, Some(BAD_CID)),
+            invalid
+        );
+        assert_noop!(
+            Org::set_member_metadata(one(), 1, 2, Some(BAD_CID)),
+            invalid
+        );
+        assert_noop!(Org::propose_spend(one(), 1, 9, 10, BAD_CID), invalid);
+        assert_noop!(Org::change_constitution(one(), 1, BAD_CID), invalid);
+        assert_noop!(
+            Org::propose_org_action(
+                one(),
+                1,
+                OrgAction::ChangeConstitution(BAD_CID)
+            ),
+            invalid
+        );
+        assert_noop!(
+            Org::apply_for_membership(Origin::signed(7), 1, Some(BAD_CID)),
+            invalid
+        );
+        assert_noop!(Org::add_observer(one(), 1, 7, BAD_CID), invalid);
+        assert_noop!(
+            Org::fork_org(
+                one(),
+                1,
+                BAD_CID,
+                MemberFilter::Accounts(vec![2, 3]),
+                false
+            ),
+            invalid
+        );
+        // the same calls accept well formed cids
+        assert_ok!(Org::change_constitution(one(), 1, 7));
+        assert_ok!(Org::set_my_metadata(Origin::signed(2), 1, None));
+    });
+}
+
+#[test]
+fn supervisors_freeze_and_unfreeze_their_org() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Org::org_lifecycle(1), OrgLifecycle::Active);
+        assert_noop!(
+            Org::freeze_org(Origin::signed(2), 1),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_noop!(
+            Org::unfreeze_org(Origin::signed(1), 1),
+            Error::<TestRuntime>::OrgNotFrozen
+        );
+        assert_ok!(Org::freeze_org(Origin::signed(1), 1));
+        assert_eq!(get_last_event(), RawEvent::OrgFrozen(1, 1));
+        assert_eq!(Org::org_lifecycle(1), OrgLifecycle::Frozen);
+        assert_noop!(
+            Org::freeze_org(Origin::signed(1), 1),
+            Error::<TestRuntime>::OrgNotActive
+        );
+        assert_ok!(Org::unfreeze_org(Origin::signed(1), 1));
+        assert_eq!(get_last_event(), RawEvent::OrgUnfrozen(1, 1));
+        assert_eq!(Org::org_lifecycle(1), OrgLifecycle::Active);
+        assert!(!<Lifecycles<TestRuntime>>::contains_key(1));
+    });
+}
+
+#[test]
+fn shares_issued_with_consent_are_held_once_accepted() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Org::set_share_consent(Origin::signed(2), 1, true),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::set_share_consent(Origin::signed(1), 1, true));
+        assert_eq!(get_last_event(), RawEvent::ShareConsentSet(1, 1, true));
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            7,
+            10,
+            ShareClass::Voting
+        ));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::SharesOffered(1, 7, 1, 10, ShareClass::Voting, 11)
+        );
+        // nothing is issued before the recipient accepts
+        assert!(!Org::is_member_of_group(1, &7));
+        assert_eq!(Org::orgs(1).unwrap().total_shares(), 6);
+        assert_eq!(Org::share_grants(&7).len(), 1);
+        assert_noop!(
+            Org::accept_shares(Origin::signed(8), 1, 1),
+            Error::<TestRuntime>::ShareGrantDNE
+        );
+        assert_ok!(Org::accept_shares(Origin::signed(7), 1, 1));
+        assert_eq!(get_last_event(), RawEvent::ShareGrantAccepted(1, 7, 1));
+        assert!(Org::is_member_of_group(1, &7));
+        assert_eq!(Org::members(1, 7).unwrap().total(), 10);
+        assert!(Org::share_grants(&7).is_empty());
+        // the issuer consents to shares issued to itself
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            1,
+            5,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::members(1, 1).unwrap().total(), 6);
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            8,
+            10,
+            ShareClass::NonVoting
+        ));
+        assert_ok!(Org::decline_shares(Origin::signed(8), 1, 2));
+        assert_eq!(get_last_event(), RawEvent::ShareGrantDeclined(1, 8, 2));
+        assert_eq!(Org::non_voting_shares(1, 8), 0);
+        assert_noop!(
+            Org::accept_shares(Origin::signed(8), 1, 2),
+            Error::<TestRuntime>::ShareGrantDNE
+        );
+        // orgs without the setting issue at once again
+        assert_ok!(Org::set_share_consent(Origin::signed(1), 1, false));
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            9,
+            10,
+            ShareClass::Voting
+        ));
+        assert!(Org::is_member_of_group(1, &9));
+    });
+}
+
+#[test]
+fn share_grants_expire_and_are_cancelled() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::set_share_consent(Origin::signed(1), 1, true));
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            1,
+            vec![(7, 10), (8, 20)],
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::orgs(1).unwrap().total_shares(), 6);
+        assert_noop!(
+            Org::cancel_share_grant(Origin::signed(2), 1, 7, 1),
+            Error::<TestRuntime>::NotAuthorizedForAccount
+        );
+        assert_ok!(Org::cancel_share_grant(Origin::signed(1), 1, 7, 1));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ShareGrantCancelled(1, 7, 1, false)
+        );
+        System::set_block_number(11);
+        // expired grants are no longer listed nor accepted
+        assert!(Org::share_grants(&8).is_empty());
+        assert_noop!(
+            Org::accept_shares(Origin::signed(8), 1, 2),
+            Error::<TestRuntime>::ShareGrantExpired
+        );
+        assert_ok!(Org::cancel_share_grant(Origin::signed(2), 1, 8, 2));
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ShareGrantCancelled(1, 8, 2, true)
+        );
+        assert!(Org::share_grant(8, 2).is_none());
+        assert!(!Org::is_member_of_group(1, &8));
+    });
+}
+
+#[test]
+fn expired_share_grants_are_cancelled_on_initialize() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::set_share_consent(Origin::signed(1), 1, true));
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            1,
+            vec![(7, 10), (8, 20)],
+            ShareClass::Voting
+        ));
+        assert_ok!(Org::accept_shares(Origin::signed(8), 1, 2));
+        assert_eq!(Org::pending_share_grants(7), 1);
+        Org::on_initialize(10);
+        assert!(Org::share_grant(7, 1).is_some());
+        System::set_block_number(11);
+        Org::on_initialize(11);
+        assert_eq!(
+            get_last_event(),
+            RawEvent::ShareGrantCancelled(1, 7, 1, true)
+        );
+        assert!(Org::share_grant(7, 1).is_none());
+        assert_eq!(Org::pending_share_grants(7), 0);
+        assert!(Org::is_member_of_group(1, &8));
+    });
+}
+
+#[test]
+fn pending_share_grants_are_bounded_per_account() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Org::set_share_consent(Origin::signed(1), 1, true));
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            1,
+            vec![(7, 10), (7, 10)],
+            ShareClass::Voting
+        ));
+        // the batch is checked as a whole before any grant is offered
+        assert_noop!(
+            Org::batch_issue_shares(
+                Origin::signed(1),
+                1,
+                vec![(8, 10), (7, 10), (7, 10)],
+                ShareClass::Voting
+            ),
+            Error::<TestRuntime>::TooManyShareGrants
+        );
+        assert_ok!(Org::issue_shares(
+            Origin::signed(1),
+            1,
+            7,
+            10,
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::pending_share_grants(7), 3);
+        assert_noop!(
+            Org::issue_shares(Origin::signed(1), 1, 7, 10, ShareClass::Voting),
+            Error::<TestRuntime>::TooManyShareGrants
+        );
+        assert_ok!(Org::decline_shares(Origin::signed(7), 1, 1));
+        assert_eq!(Org::pending_share_grants(7), 2);
+        // the issuer's own shares in a batch are issued at once
+        assert_ok!(Org::batch_issue_shares(
+            Origin::signed(1),
+            1,
+            vec![(1, 5), (7, 10)],
+            ShareClass::Voting
+        ));
+        assert_eq!(Org::members(1, 1).unwrap().total(), 6);
+        assert_eq!(Org::pending_share_grants(1), 0);
+        assert_eq!(Org::pending_share_grants(7), 3);
+    });
+}
@@ -51,6 +51,7 @@ use sp_std::{
 };
 use util::{
     share::WeightedVector,
+    signal::MintedBallot,
     traits::{
         AccessGenesis,
         Apply,
@@ -79,7 +80,8 @@ type VoteSt<T> = VoteState<
     <T as System>::BlockNumber,
     <T as Trait>::Cid,
 >;
-type VoteVec<T> = Vote<<T as Trait>::Signal, <T as Trait>::Cid>;
+type VoteVec<T> =
+    Vote<<T as Trait>::Signal, <T as Trait>::Cid, <T as System>::BlockNumber>;
 
 pub trait Trait: System {
     /// The overarching event type
@@ -302,8 +304,7 @@ impl<T: Trait>
         let vote_id = Self::generate_unique_id();
         // iterate through src and mint the signal
         src.vec().iter().for_each(|(who, vote_power)| {
-            let new_vote =
-                Vote::new(*vote_power, VoterView::Uninitialized, None);
+            let new_vote = VoteVec::<T>::minted(*vote_power);
             <VoteLogger<T>>::insert(vote_id, who, new_vote);
         });
         <TotalSignalIssuance<T>>::insert(vote_id, src.total());
@@ -337,8 +338,7 @@ impl<T: Trait>
         let vote_id = Self::generate_unique_id();
         // iterate through src and mint the signal
         src.vec().iter().for_each(|(who, vote_power)| {
-            let new_vote =
-                Vote::new(*vote_power, VoterView::Uninitialized, None);
+            let new_vote = VoteVec::<T>::minted(*vote_power);
             <VoteLogger<T>>::insert(vote_id, who, new_vote);
         });
         <TotalSignalIssuance<T>>::insert(vote_id, src.total());
@@ -396,7 +396,7 @@ impl<T: Trait> UpdateVote<T::VoteId, T::Cid, T::BlockNumber> for Module<T> {
 impl<T: Trait> ApplyVote<T::Cid> for Module<T> {
     type Signal = T::Signal;
     type Direction = VoterView;
-    type Vote = Vote<T::Signal, T::Cid>;
+    type Vote = Vote<T::Signal, T::Cid, T::BlockNumber>;
     type State = VoteState<T::Signal, T::BlockNumber, T::Cid>;
 
     fn apply_vote(
@@ -438,9 +438,11 @@ impl<T: Trait> VoteOnProposal<T::AccountId, T::VoteId, T::Cid> for Module<T> {
         // get the organization associated with this vote_state
         let old_vote = <VoteLogger<T>>::get(vote_id, voter.clone())
             .ok_or(Error::<T>::SignalNotMintedForVoter)?;
-        let new_vote = old_vote.set_new_view(direction, justification).ok_or(
-            Error::<T>::OldVoteDirectionEqualsNewVoteDirectionSoNoChange,
-        )?;
+        let now = frame_system::Module::<T>::block_number();
+        let new_vote =
+            old_vote.set_new_view(direction, justification, now).ok_or(
+                Error::<T>::OldVoteDirectionEqualsNewVoteDirectionSoNoChange,
+            )?;
         let new_state = Self::apply_vote(
             vote_state,
             old_vote.magnitude(),
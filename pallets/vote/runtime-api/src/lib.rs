@@ -0,0 +1,39 @@
+//! Runtime API for reading vote progress without submitting an extrinsic
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_std::prelude::*;
+use util::vote::{
+    BallotStatus,
+    VoteProgress,
+    VoterView,
+};
+
+sp_api::decl_runtime_apis! {
+    #[api_version(4)]
+    pub trait VoteApi<VoteId, Signal, AccountId, BlockNumber, Cid>
+    where
+        VoteId: Codec,
+        Signal: Codec,
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Cid: Codec,
+    {
+        /// The tally of the vote and the signal remaining to decide it
+        fn vote_progress(vote_id: VoteId) -> Option<VoteProgress<Signal>>;
+        /// Whether `who` may still cast or change their ballot and until when
+        fn ballot_status(
+            vote_id: VoteId,
+            who: AccountId,
+        ) -> Option<BallotStatus<BlockNumber>>;
+        /// The voters who voted `direction`, or all who voted, with their
+        /// signal, justification and the blocks of their first and latest
+        /// ballots, a page of `limit` after `offset`
+        fn ballots(
+            vote_id: VoteId,
+            direction: Option<VoterView>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, Signal, Option<Cid>, BlockNumber, BlockNumber)>;
+    }
+}
@@ -16,6 +16,7 @@
 //! [`Trait`]: ./trait.Trait.html
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod migrations;
 #[cfg(test)]
 mod tests;
 
@@ -24,7 +25,18 @@ use frame_support::{
     decl_event,
     decl_module,
     decl_storage,
+    dispatch::DispatchResultWithPostInfo,
     ensure,
+    storage::IterableStorageDoubleMap,
+    traits::{
+        Currency,
+        Get,
+        ReservableCurrency,
+    },
+    weights::{
+        Pays,
+        Weight,
+    },
     Parameter,
 };
 use frame_system::{
@@ -32,51 +44,87 @@ use frame_system::{
     Trait as System,
 };
 use org::Trait as Org;
-use parity_scale_codec::Codec;
+use parity_scale_codec::{
+    Codec,
+    Decode,
+    Encode,
+};
 use sp_runtime::{
     traits::{
         AtLeast32BitUnsigned,
+        Bounded,
+        CheckedAdd,
         CheckedSub,
         MaybeSerializeDeserialize,
         Member,
+        Saturating,
         Zero,
     },
     DispatchError,
     DispatchResult,
     Permill,
+    RuntimeDebug,
+    SaturatedConversion,
 };
 use sp_std::{
+    convert::TryFrom,
     fmt::Debug,
+    marker::PhantomData,
     prelude::*,
 };
 use util::{
-    organization::OrgRep,
+    deadline::DeadlineExtension,
+    housekeeping::HousekeepingMeter,
+    organization::{
+        OrgLifecycle,
+        OrgRep,
+        PowerMask,
+        SupervisorAuthority,
+    },
+    signal::{
+        MintedBallot,
+        SignalBank,
+        SignalStore,
+    },
     traits::{
         AccessGenesis,
         Apply,
         ApplyVote,
         CheckVoteStatus,
         ConfigureThreshold,
-        GenerateUniqueID,
         GetGroup,
+        GetOrgLifecycle,
         GetVoteOutcome,
+        GroupMembership,
         IDIsAvailable,
         MintableSignal,
         OpenVote,
+        OrgLifecycleHandler,
         OrganizationSupervisorPermissions,
+        ShareChangeHandler,
         ShareInformation,
+        ThresholdVote,
         UpdateVote,
         VoteOnProposal,
+        VoteOutcomeHandler,
         VoteVector,
     },
     vote::{
+        BallotStatus,
+        CompositeVoteState,
+        Composition,
+        LockReason,
+        PercentBase,
         Threshold,
         ThresholdConfig,
         ThresholdInput,
+        TieBreak,
         Vote,
         VoteOutcome,
+        VoteProgress,
         VoteState,
         VoterView,
+        VotesCastThreshold,
         XorThreshold,
     },
 };
@@ -95,7 +143,70 @@ type VoteSt<T> = VoteState<
     <T as System>::BlockNumber,
     <T as Org>::Cid,
 >;
-type VoteVec<T> = Vote<<T as Trait>::Signal, <T as Org>::Cid>;
+type VoteVec<T> =
+    Vote<<T as Trait>::Signal, <T as Org>::Cid, <T as System>::BlockNumber>;
+type Ballot<T> = (
+    <T as System>::BlockNumber,
+    VoterView,
+    Option<<T as Org>::Cid>,
+);
+type Leg<T> = (
+    OrgRep<<T as Org>::OrgId>,
+    XorThreshold<<T as Trait>::Signal, Permill>,
+);
+type CompositeSt<T> = CompositeVoteState<<T as Trait>::VoteId>;
+type BalanceOf<T> =
+    <<T as Trait>::BondCurrency as Currency<<T as System>::AccountId>>::Balance;
+
+/// The signal minted in a vote capping each voter's signal
+struct CappedSignal<T: Trait> {
+    holders: Vec<(T::AccountId, T::Signal)>,
+    /// The turnout, the sum of the capped signal
+    total: T::Signal,
+    /// The most signal a voter holds
+    max: T::Signal,
+    /// The number of members holding more shares than `max`
+    capped: u32,
+}
+
+impl<T: Trait> CappedSignal<T> {
+    /// The signal of the capped voters and their number, if any was capped
+    fn binding(&self) -> Option<(T::Signal, u32)> {
+        if self.capped == 0 {
+            None
+        } else {
+            Some((self.max, self.capped))
+        }
+    }
+}
+
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode, RuntimeDebug,
+)]
+/// The layout of the pallet storage
+pub enum Releases {
+    /// Before `VoteState` had a tie break
+    V1,
+    /// `VoteState` has a tie break
+    V2,
+    /// `VoteState` has a change only window
+    V3,
+    /// Ballots record the blocks they were cast at
+    V4,
+    /// `VoteState` may follow the percents of the votes cast
+    V5,
+}
+
+impl Default for Releases {
+    fn default() -> Releases {
+        // chains started before the version was tracked
+        Releases::V1
+    }
+}
+
+/// The layout written by this version of the pallet, bumped with every
+/// migration
+pub const STORAGE_VERSION: Releases = Releases::V5;
 
 pub trait Trait: System + Org {
     /// The overarching event type
@@ -140,6 +251,49 @@ pub trait Trait: System + Org {
         + PartialOrd
         + PartialEq
         + Zero;
+
+    /// The currency reserved to vote in bonded votes
+    type BondCurrency: ReservableCurrency<Self::AccountId>;
+
+    /// The maximum number of voters in a custom weighted vote
+    type MaxCustomWeights: Get<u32>;
+
+    /// Called once with the outcome of every vote, a tuple of handlers may
+    /// be given
+    type OutcomeHandler: VoteOutcomeHandler<Self::VoteId>;
+
+    /// The member count above which signal is minted with each first ballot
+    /// instead of for all members when the vote opens
+    type LazyMintThreshold: Get<u32>;
+
+    /// The number of ballots kept in each voter's justification history
+    type MaxJustificationHistory: Get<u32>;
+
+    /// The number of accepted ballots per vote each voter casts without fees
+    type FreeBallots: Get<u32>;
+
+    /// The maximum number of legs of a composite vote
+    type MaxCompositeLegs: Get<u32>;
+
+    /// The most a supervisor may add to the percent in favor of a
+    /// registered threshold when opening a vote from it
+    type MaxThresholdTweak: Get<Permill>;
+
+    /// The maximum number of open standing votes each account holds a
+    /// ballot in, which bounds the ballots re-weighed per change of shares
+    type MaxStandingBallots: Get<u32>;
+
+    /// The per block housekeeping budget shared with other pallets, votes
+    /// expiring past it are swept in the next block
+    type Housekeeping: HousekeepingMeter;
+
+    /// The signal an org must be able to turn out to open percent votes,
+    /// unless its supervisor set its own floor
+    type MinTurnoutForPercentVotes: Get<Self::Signal>;
+
+    /// The highest floor a supervisor may set for the percent votes of
+    /// their org
+    type MaxPercentVoteFloor: Get<Self::Signal>;
 }
 
 decl_event!(
@@ -148,10 +302,58 @@ decl_event!(
         <T as System>::AccountId,
         <T as Trait>::VoteId,
         <T as Trait>::ThresholdId,
+        <T as System>::BlockNumber,
+        Balance = BalanceOf<T>,
+        <T as Org>::Cid,
+        <T as Org>::OrgId,
+        <T as Trait>::Signal,
     {
         ThresholdSet(ThresholdId),
         NewVoteStarted(AccountId, VoteId),
-        Voted(VoteId, AccountId, VoterView),
+        /// Vote creator, Vote ID, signal minted as set by the supervisor instead of by shares
+        CustomWeightedVoteStarted(AccountId, VoteId),
+        /// Vote ID, Voter, Direction, Number of ballots the voter has cast in the vote, Block of the ballot
+        Voted(VoteId, AccountId, VoterView, u32, BlockNumber),
+        BondedVote(VoteId, AccountId, VoterView, Balance),
+        BondReleased(VoteId, AccountId, Balance),
+        /// Vote ID, Old Topic, New Topic, Whether Ballots Were Reset
+        VoteTopicChanged(VoteId, Option<Cid>, Cid, bool),
+        /// Vote ID, outcome if both thresholds are crossed at once
+        TieBreakSet(VoteId, TieBreak),
+        /// Vote ID, final blocks of the vote in which only ballots can be changed
+        ChangeOnlyWindowSet(VoteId, Option<BlockNumber>),
+        /// Vote ID, how late ballots extend the vote
+        DeadlineExtensionSet(VoteId, Option<DeadlineExtension<BlockNumber>>),
+        /// Vote ID, new end of the vote after a late ballot
+        VoteDeadlineExtended(VoteId, BlockNumber),
+        /// Vote ID, outcome passed to the outcome handlers
+        VoteOutcomeReported(VoteId, VoteOutcome),
+        /// Vote ID, account which cancelled it, cid of the reason
+        VoteCancelled(VoteId, AccountId, Option<Cid>),
+        /// Vote creator, Vote ID, Vote ID of each leg
+        CompositeVoteStarted(AccountId, VoteId, Vec<VoteId>),
+        /// Composite Vote ID, index of the leg, outcome reported for the leg
+        CompositeLegDecided(VoteId, u32, VoteOutcome),
+        /// Threshold ID, Vote ID, percent added to the threshold in favor
+        ThresholdVoteStarted(ThresholdId, VoteId, Option<Permill>),
+        /// Vote creator, Vote ID of the standing vote
+        StandingVoteStarted(AccountId, VoteId),
+        /// Vote ID, Voter whose ballot follows their changed shares
+        StandingBallotReweighed(VoteId, AccountId),
+        /// Org ID, Link Set ID, accounts which must confirm the link
+        AccountsLinked(OrgId, u32, Vec<AccountId>),
+        /// Org ID, Link Set ID, account which confirmed the link
+        LinkConfirmed(OrgId, u32, AccountId),
+        /// Org ID, Link Set ID whose accounts now vote as one
+        LinkActivated(OrgId, u32),
+        /// Org ID, Link Set ID which was removed
+        AccountsUnlinked(OrgId, u32),
+        /// Org ID, turnout below which its percent votes do not open or None for the pallet default
+        PercentVoteFloorSet(OrgId, Option<Signal>),
+        /// Vote ID, signal of each capped voter, number of members whose signal was capped
+        VoterSignalCapped(VoteId, Signal, u32),
+        /// Vote ID, Org ID of the frozen org the vote was cancelled for
+        VoteCancelledOrgFrozen(VoteId, OrgId),
     }
 );
 
@@ -171,6 +373,62 @@ decl_error! {
         InputThresholdExceedsBounds,
         OnlySupervisorCanSetGenericThresholds,
         CannotInvokeThresholdThatDNE,
+        NotABondedVote,
+        BondBelowMinimumForVote,
+        BondExceedsSignalRange,
+        CannotBondForVoteThatIsNotOpen,
+        CannotReleaseBondBeforeVoteEnds,
+        NoBondToRelease,
+        CannotUpdateTopicOfVoteWithoutOrg,
+        NotAuthorizedToUpdateVoteTopic,
+        TooManyCustomWeights,
+        DuplicateVoterInCustomWeights,
+        CustomWeightForNonMember,
+        CannotSetTieBreakOfVoteWithoutOrg,
+        NotAuthorizedToSetTieBreak,
+        CannotSetTieBreakAfterVotingStarts,
+        CannotCloseVoteWithoutOrg,
+        NotAuthorizedToCloseVote,
+        VoteAlreadyClosed,
+        NotAuthorizedToCancelVote,
+        VoteCancelled,
+        EmptyOrgCannotVote,
+        ZeroSignalCannotVote,
+        CompositeVoteNeedsTwoLegs,
+        TooManyCompositeLegs,
+        NoCompositeLegAcceptsBallot,
+        ThresholdTweakExceedsMaximum,
+        CannotTweakSignalThreshold,
+        CannotSetChangeOnlyWindowOfVoteWithoutOrg,
+        NotAuthorizedToSetChangeOnlyWindow,
+        CannotSetChangeOnlyWindowAfterVotingStarts,
+        ChangeOnlyWindowNeedsVoteToEnd,
+        ChangeOnlyWindowExceedsVoteDuration,
+        NewBallotsClosedOnlyChangesAllowed,
+        CannotSetDeadlineExtensionOfVoteWithoutOrg,
+        NotAuthorizedToSetDeadlineExtension,
+        CannotSetDeadlineExtensionAfterVotingStarts,
+        DeadlineExtensionNeedsVoteToEnd,
+        TooManyStandingBallots,
+        NotAuthorizedToLinkAccounts,
+        LinkNeedsTwoAccounts,
+        DuplicateAccountInLink,
+        LinkedAccountNotAMember,
+        AccountAlreadyLinked,
+        LinkSetDNE,
+        NotInLinkSet,
+        LinkAlreadyConfirmed,
+        LinkedAccountAlreadyVoted,
+        DurationOverflow,
+        IdSpaceExhausted,
+        // the org could not turn out its percent vote floor, use a signal threshold
+        OrgTooSmallForPercentVote,
+        NotAuthorizedToSetPercentVoteFloor,
+        PercentVoteFloorExceedsMaximum,
+        // a cap of zero would leave no voter any signal
+        PerVoterCapIsZero,
+        OrgNotActive,
+        SignalOverflow,
     }
 }
 
@@ -189,10 +447,22 @@ decl_storage! {
         pub VoteStates get(fn vote_states): map
             hasher(blake2_128_concat) T::VoteId => Option<VoteSt<T>>;
 
+        /// The account which opened the vote, which may cancel it
+        pub VoteCreators get(fn vote_creator): map
+            hasher(blake2_128_concat) T::VoteId => Option<T::AccountId>;
+
+        /// The org whose members were minted signal for the vote
+        pub VoteOrgs get(fn vote_orgs): map
+            hasher(blake2_128_concat) T::VoteId => Option<OrgRep<T::OrgId>>;
+
         /// The set of configured thresholds for direct dispatch
         pub VoteThresholds get(fn vote_thresholds): map
             hasher(blake2_128_concat) T::ThresholdId => Option<Thresh<T>>;
 
+        /// The threshold each org set last with `set_threshold_default`
+        pub DefaultThresholds get(fn org_default_threshold): map
+            hasher(blake2_128_concat) T::OrgId => Option<T::ThresholdId>;
+
         /// Total signal minted for the vote; sum of all participant signal for the vote
         pub TotalSignalIssuance get(fn total_signal_issuance): map
             hasher(blake2_128_concat) T::VoteId => Option<T::Signal>;
@@ -201,6 +471,115 @@ decl_storage! {
         pub VoteLogger get(fn vote_logger): double_map
             hasher(blake2_128_concat) T::VoteId,
             hasher(blake2_128_concat) T::AccountId  => Option<VoteVec<T>>;
+
+        /// The latest ballots of each voter with their justifications, oldest first
+        pub JustificationHistory get(fn justification_history): double_map
+            hasher(blake2_128_concat) T::VoteId,
+            hasher(blake2_128_concat) T::AccountId => Vec<Ballot<T>>;
+
+        /// The number of ballots each voter has cast, including those dropped from the history
+        pub BallotCounts get(fn ballot_count): double_map
+            hasher(blake2_128_concat) T::VoteId,
+            hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The minimum bond for each vote in which signal is bonded while voting
+        pub BondedVotes get(fn bonded_votes): map
+            hasher(blake2_128_concat) T::VoteId => Option<BalanceOf<T>>;
+
+        /// The amount reserved by each voter in a bonded vote
+        pub VoteBonds get(fn vote_bonds): double_map
+            hasher(blake2_128_concat) T::VoteId,
+            hasher(blake2_128_concat) T::AccountId => Option<BalanceOf<T>>;
+
+        /// How late ballots extend each vote, if its supervisor set it
+        pub DeadlineExtensions get(fn deadline_extension): map
+            hasher(blake2_128_concat) T::VoteId => Option<DeadlineExtension<T::BlockNumber>>;
+
+        /// The votes ending at each block, swept in `on_finalize`
+        VoteExpiries get(fn vote_expiries): map
+            hasher(blake2_128_concat) T::BlockNumber => Vec<T::VoteId>;
+
+        /// The number of this block's expiries reserved from the
+        /// housekeeping budget in `on_initialize`
+        ExpirySweepAllowance: u32;
+
+        /// The outcome given to the outcome handlers, once per vote
+        pub ReportedOutcomes get(fn reported_outcome): map
+            hasher(blake2_128_concat) T::VoteId => Option<VoteOutcome>;
+
+        /// The org share snapshot signal is minted from for lazily minted votes
+        pub LazySnapshots get(fn lazy_snapshot): map
+            hasher(blake2_128_concat) T::VoteId => Option<u32>;
+
+        /// The composition and the leg votes of each composite vote
+        pub CompositeVotes get(fn composite_vote): map
+            hasher(blake2_128_concat) T::VoteId => Option<CompositeSt<T>>;
+
+        /// The composite vote and leg index of each leg vote
+        pub CompositeLegs get(fn composite_leg): map
+            hasher(blake2_128_concat) T::VoteId => Option<(T::VoteId, u32)>;
+
+        /// The votes whose tally follows the current shares of their voters
+        pub StandingVotes get(fn is_standing): map
+            hasher(blake2_128_concat) T::VoteId => bool;
+
+        /// The standing votes each account holds a ballot in
+        pub StandingBallots get(fn standing_ballots): map
+            hasher(blake2_128_concat) T::AccountId => Vec<T::VoteId>;
+
+        /// The nonce for unique link set id generation
+        LinkSetCounter get(fn link_set_counter): u32;
+
+        /// The accounts of each link set and whether each confirmed the link
+        pub LinkSets get(fn link_set): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) u32 => Option<Vec<(T::AccountId, bool)>>;
+
+        /// The link set of each linked account
+        pub AccountLinks get(fn account_link): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::AccountId => Option<u32>;
+
+        /// The members of each org sharing the signal of another member of
+        /// an active link set, which are not counted in the turnout
+        pub LinkedAccountCount get(fn linked_account_count): map
+            hasher(blake2_128_concat) T::OrgId => u32;
+
+        /// The turnout below which each org's percent votes do not open,
+        /// if its supervisor set one instead of the pallet default
+        pub PercentVoteFloors get(fn percent_vote_floor_of): map
+            hasher(blake2_128_concat) T::OrgId => Option<T::Signal>;
+
+        /// The share of the org's shares no voter holds more signal than in
+        /// each capped vote
+        pub PerVoterCaps get(fn per_voter_cap): map
+            hasher(blake2_128_concat) T::VoteId => Option<Permill>;
+
+        /// The cap of each voter's signal in the votes opened from each
+        /// registered threshold
+        pub ThresholdVoterCaps get(fn threshold_voter_cap): map
+            hasher(blake2_128_concat) T::ThresholdId => Option<Permill>;
+
+        /// The votes of each org whose outcome was not reported yet, votes
+        /// opened before the index was kept are missing from it
+        pub OrgOpenVotes get(fn org_open_vote): double_map
+            hasher(blake2_128_concat) T::OrgId,
+            hasher(blake2_128_concat) T::VoteId => bool;
+
+        /// The number of votes of each org in `OrgOpenVotes`
+        pub OrgOpenVoteCount get(fn org_open_vote_count): map
+            hasher(blake2_128_concat) T::OrgId => u32;
+
+        /// The frozen orgs whose open votes are still to be cancelled
+        FrozenOrgSweeps get(fn frozen_org_sweeps): Vec<T::OrgId>;
+
+        /// The number of votes of frozen orgs reserved from the
+        /// housekeeping budget in `on_initialize`
+        FrozenSweepAllowance: u32;
+
+        /// The layout of the stored values, new chains start at the latest
+        pub StorageVersion get(fn storage_version)
+            build(|_: &GenesisConfig| STORAGE_VERSION): Releases;
     }
 }
 
@@ -209,6 +588,84 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        const MaxCustomWeights: u32 = T::MaxCustomWeights::get();
+
+        const LazyMintThreshold: u32 = T::LazyMintThreshold::get();
+
+        const MaxJustificationHistory: u32 = T::MaxJustificationHistory::get();
+
+        const FreeBallots: u32 = T::FreeBallots::get();
+
+        const MaxCompositeLegs: u32 = T::MaxCompositeLegs::get();
+
+        const MaxThresholdTweak: Permill = T::MaxThresholdTweak::get();
+
+        const MaxStandingBallots: u32 = T::MaxStandingBallots::get();
+
+        const MinTurnoutForPercentVotes: T::Signal = T::MinTurnoutForPercentVotes::get();
+
+        const MaxPercentVoteFloor: T::Signal = T::MaxPercentVoteFloor::get();
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let due = <VoteExpiries<T>>::decode_len(n).unwrap_or_default();
+            let allowance = T::Housekeeping::reserve(
+                due.saturated_into(),
+                Self::expiry_weight(),
+            );
+            <ExpirySweepAllowance>::put(allowance);
+            let frozen = Self::frozen_votes_due();
+            let frozen_allowance = if frozen > 0 {
+                T::Housekeeping::reserve(frozen, Self::expiry_weight())
+            } else {
+                0
+            };
+            <FrozenSweepAllowance>::put(frozen_allowance);
+            let swept = allowance.saturating_add(frozen_allowance);
+            // the leftovers are written to the next block's queue in any case
+            let carried = if (allowance as usize) < due {
+                Self::carry_over_weight()
+            } else {
+                0
+            };
+            Self::expiry_weight()
+                .saturating_mul(swept.into())
+                .saturating_add(carried)
+        }
+
+        fn on_finalize(n: T::BlockNumber) {
+            let mut due = <VoteExpiries<T>>::take(n);
+            let allowance = <ExpirySweepAllowance>::take() as usize;
+            if allowance < due.len() {
+                // the oldest leftovers are swept first in the next block
+                let mut leftover = due.split_off(allowance);
+                let next = n.saturating_add(1u32.into());
+                <VoteExpiries<T>>::mutate(next, |queued| {
+                    leftover.append(queued);
+                    *queued = leftover;
+                });
+            }
+            for vote_id in due {
+                if let Some(vote_state) = <VoteStates<T>>::get(vote_id) {
+                    // extended votes are swept again at their new end,
+                    // carried over ones after it
+                    if vote_state.ends().map_or(false, |ends| ends <= n)
+                        && vote_state.outcome() != VoteOutcome::Cancelled
+                    {
+                        let settled = vote_state.settle();
+                        if settled != vote_state {
+                            <VoteStates<T>>::insert(vote_id, &settled);
+                        }
+                        Self::report_outcome(vote_id, settled.outcome());
+                    }
+                }
+            }
+            Self::sweep_frozen_orgs(n, <FrozenSweepAllowance>::take());
+        }
+
         #[weight = 0]
         pub fn create_signal_vote(
             origin,
@@ -216,19 +673,24 @@ decl_module! {
             organization: OrgRep<T::OrgId>,
             threshold: Threshold<T::Signal>,
             duration: Option<T::BlockNumber>,
+            per_voter_cap: Option<Permill>,
         ) -> DispatchResult {
             let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
             // default authentication is organization supervisor
-            let authentication: bool = <org::Module<T>>::is_organization_supervisor(organization.org(), &vote_creator);
-            ensure!(authentication, Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            let authority = <org::Module<T>>::supervisor_authority(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
             // call helper method
-            let new_vote_id = Self::open_vote(
+            let new_vote_id = Self::open_signal_vote_of(
                 topic,
                 organization,
                 threshold,
+                per_voter_cap,
                 duration,
             )?;
             // emit event
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES, authority);
             Self::deposit_event(RawEvent::NewVoteStarted(vote_creator, new_vote_id));
             Ok(())
         }
@@ -239,148 +701,1762 @@ decl_module! {
             organization: OrgRep<T::OrgId>,
             threshold: Threshold<Permill>,
             duration: Option<T::BlockNumber>,
+            per_voter_cap: Option<Permill>,
         ) -> DispatchResult {
             let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
             // default authentication is organization supervisor
-            let authentication: bool = <org::Module<T>>::is_organization_supervisor(organization.org(), &vote_creator);
-            ensure!(authentication, Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            let authority = <org::Module<T>>::supervisor_authority(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
             // call helper method
-            let new_vote_id = Self::open_percent_vote(
+            let new_vote_id = Self::open_percent_vote_of(
                 topic,
                 organization,
                 threshold,
-                duration
+                PercentBase::TotalIssuance,
+                per_voter_cap,
+                duration,
             )?;
             // emit event
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES, authority);
             Self::deposit_event(RawEvent::NewVoteStarted(vote_creator, new_vote_id));
             Ok(())
         }
         #[weight = 0]
+        pub fn open_custom_vote(
+            origin,
+            topic: Option<T::Cid>,
+            organization: T::OrgId,
+            weights: Vec<(T::AccountId, T::Signal)>,
+            threshold: Threshold<T::Signal>,
+            duration: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let authority = <org::Module<T>>::supervisor_authority(organization, &vote_creator, PowerMask::MANAGE_VOTES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            ensure!(
+                weights.len() as u32 <= T::MaxCustomWeights::get(),
+                Error::<T>::TooManyCustomWeights
+            );
+            let new_vote_id = Self::open_custom_weighted_vote(
+                topic,
+                organization,
+                weights,
+                threshold,
+                duration,
+            )?;
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(organization, &vote_creator, PowerMask::MANAGE_VOTES, authority);
+            Self::deposit_event(RawEvent::CustomWeightedVoteStarted(vote_creator, new_vote_id));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn open_composite_vote(
+            origin,
+            topic: Option<T::Cid>,
+            composition: Composition,
+            legs: Vec<Leg<T>>,
+            duration: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            ensure!(legs.len() >= 2, Error::<T>::CompositeVoteNeedsTwoLegs);
+            ensure!(
+                legs.len() as u32 <= T::MaxCompositeLegs::get(),
+                Error::<T>::TooManyCompositeLegs
+            );
+            for (organization, _) in legs.iter() {
+                ensure!(
+                    <org::Module<T>>::is_organization_supervisor(organization.org(), &vote_creator),
+                    Error::<T>::NotAuthorizedToCreateVoteForOrganization
+                );
+            }
+            let (new_vote_id, leg_votes) = Self::open_composite(
+                topic,
+                composition,
+                legs,
+                duration,
+            )?;
+            Self::deposit_event(RawEvent::CompositeVoteStarted(vote_creator, new_vote_id, leg_votes));
+            Ok(())
+        }
+        #[weight = 0]
         fn set_threshold_default(
             origin,
             threshold: ThreshInput<T>,
         ) -> DispatchResult {
             let setter = ensure_signed(origin)?;
+            let org = threshold.org().org();
             ensure!(
-                <org::Module<T>>::is_organization_supervisor(threshold.org().org(), &setter),
+                <org::Module<T>>::is_organization_supervisor(org, &setter),
                 Error::<T>::OnlySupervisorCanSetGenericThresholds
             );
             let id = Self::register_threshold(threshold)?;
+            <DefaultThresholds<T>>::insert(org, id);
             Self::deposit_event(RawEvent::ThresholdSet(id));
             Ok(())
         }
         #[weight = 0]
+        pub fn open_vote_from_threshold(
+            origin,
+            threshold_id: T::ThresholdId,
+            topic: Option<T::Cid>,
+            duration: Option<T::BlockNumber>,
+            tweak: Option<Permill>,
+        ) -> DispatchResult {
+            let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let config = <VoteThresholds<T>>::get(threshold_id)
+                .ok_or(Error::<T>::CannotInvokeThresholdThatDNE)?;
+            let authority = <org::Module<T>>::supervisor_authority(
+                config.org().org(),
+                &vote_creator,
+                PowerMask::MANAGE_VOTES,
+            );
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            let new_vote_id = if let Some(delta) = tweak {
+                ensure!(
+                    delta <= T::MaxThresholdTweak::get(),
+                    Error::<T>::ThresholdTweakExceedsMaximum
+                );
+                // only a percent can be raised without knowing the turnout
+                let (threshold, base) = match config.threshold() {
+                    XorThreshold::Percent(t) => (t, PercentBase::TotalIssuance),
+                    XorThreshold::PercentOf(t, base) => (t, base),
+                    XorThreshold::Signal(_) => {
+                        return Err(Error::<T>::CannotTweakSignalThreshold.into())
+                    }
+                };
+                let threshold = Threshold::new(
+                    threshold.in_favor().saturating_add(delta),
+                    threshold.against(),
+                );
+                let cap = <ThresholdVoterCaps<T>>::get(threshold_id);
+                Self::open_percent_vote_of(topic, config.org(), threshold, base, cap, duration)?
+            } else {
+                Self::invoke_threshold(threshold_id, topic, duration)?
+            };
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(config.org().org(), &vote_creator, PowerMask::MANAGE_VOTES, authority);
+            Self::deposit_event(RawEvent::NewVoteStarted(vote_creator, new_vote_id));
+            Self::deposit_event(RawEvent::ThresholdVoteStarted(threshold_id, new_vote_id, tweak));
+            Ok(())
+        }
+        #[weight = 0]
         pub fn submit_vote(
             origin,
             vote_id: T::VoteId,
             direction: VoterView,
             justification: Option<T::Cid>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let voter = ensure_signed(origin)?;
-            Self::vote_on_proposal(vote_id, voter.clone(), direction, justification)?;
-            Self::deposit_event(RawEvent::Voted(vote_id, voter, direction));
+            justification.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let votes = match <CompositeVotes<T>>::get(vote_id) {
+                Some(composite) => Self::ballot_legs(&composite, &voter, direction)?,
+                None => vec![vote_id],
+            };
+            let mut free = true;
+            for vote_id in votes {
+                Self::vote_on_proposal(vote_id, voter.clone(), direction, justification.clone())?;
+                let sequence = <BallotCounts<T>>::get(vote_id, &voter);
+                let now = <frame_system::Module<T>>::block_number();
+                Self::deposit_event(RawEvent::Voted(vote_id, voter.clone(), direction, sequence, now));
+                free = free && sequence <= T::FreeBallots::get();
+            }
+            // failed ballots returned above and pay the fee
+            if free {
+                Ok(Pays::No.into())
+            } else {
+                Ok(Pays::Yes.into())
+            }
+        }
+        #[weight = 0]
+        pub fn update_topic(
+            origin,
+            vote_id: T::VoteId,
+            new_topic: T::Cid,
+            reset_ballots: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            <org::Module<T>>::ensure_valid_cid(&new_topic)?;
+            let organization = <VoteOrgs<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotUpdateTopicOfVoteWithoutOrg)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization.org(), &caller),
+                Error::<T>::NotAuthorizedToUpdateVoteTopic
+            );
+            let old_topic = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotUpdateVoteIfVoteStateDNE)?
+                .topic();
+            Self::update_vote_topic(vote_id, new_topic.clone(), reset_ballots)?;
+            Self::deposit_event(RawEvent::VoteTopicChanged(vote_id, old_topic, new_topic, reset_ballots));
             Ok(())
         }
-    }
-}
-
-impl<T: Trait> Module<T> {
-    fn valid_signal_threshold(
-        threshold: &Threshold<T::Signal>,
-        all_possible_turnout: T::Signal,
-    ) -> bool {
-        threshold.in_favor() <= all_possible_turnout
-            && (if let Some(t) = threshold.against() {
-                t <= all_possible_turnout
-            } else {
-                true
-            })
-    }
-    fn from_permill_to_signal(
-        threshold: &Threshold<Permill>,
-        all_possible_turnout: T::Signal,
-    ) -> Threshold<T::Signal> {
-        let in_favor_t: T::Signal =
-            threshold.in_favor().mul_ceil(all_possible_turnout);
-        let against_t: Option<T::Signal> = if let Some(t) = threshold.against()
-        {
-            Some(t.mul_ceil(all_possible_turnout))
-        } else {
-            None
-        };
-        Threshold::new(in_favor_t, against_t)
-    }
-    fn generate_threshold_uid() -> T::ThresholdId {
-        let mut thresh_counter = <ThresholdIdCounter<T>>::get() + 1u32.into();
-        while <VoteThresholds<T>>::get(thresh_counter).is_some() {
-            thresh_counter += 1u32.into();
+        #[weight = 0]
+        pub fn set_tie_break(
+            origin,
+            vote_id: T::VoteId,
+            tie_break: TieBreak,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let organization = <VoteOrgs<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotSetTieBreakOfVoteWithoutOrg)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization.org(), &caller),
+                Error::<T>::NotAuthorizedToSetTieBreak
+            );
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            // the rule cannot change once ballots might be decided by it
+            ensure!(
+                vote_state.turnout() == Zero::zero(),
+                Error::<T>::CannotSetTieBreakAfterVotingStarts
+            );
+            <VoteStates<T>>::insert(vote_id, vote_state.set_tie_break(tie_break));
+            Self::deposit_event(RawEvent::TieBreakSet(vote_id, tie_break));
+            Ok(())
         }
-        <ThresholdIdCounter<T>>::put(thresh_counter);
-        thresh_counter
-    }
-}
-
-impl<T: Trait> IDIsAvailable<T::VoteId> for Module<T> {
-    fn id_is_available(id: T::VoteId) -> bool {
-        <VoteStates<T>>::get(id).is_none()
-    }
-}
-
-impl<T: Trait> GenerateUniqueID<T::VoteId> for Module<T> {
-    fn generate_unique_id() -> T::VoteId {
-        let mut id_counter = <VoteIdCounter<T>>::get() + 1u32.into();
-        while <VoteStates<T>>::get(id_counter).is_some() {
-            id_counter += 1u32.into();
+        #[weight = 0]
+        pub fn set_change_only_window(
+            origin,
+            vote_id: T::VoteId,
+            window: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let organization = <VoteOrgs<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotSetChangeOnlyWindowOfVoteWithoutOrg)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization.org(), &caller),
+                Error::<T>::NotAuthorizedToSetChangeOnlyWindow
+            );
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            // voters must know when new ballots close before casting theirs
+            ensure!(
+                vote_state.turnout() == Zero::zero(),
+                Error::<T>::CannotSetChangeOnlyWindowAfterVotingStarts
+            );
+            ensure!(
+                window.is_none() || vote_state.ends().is_some(),
+                Error::<T>::ChangeOnlyWindowNeedsVoteToEnd
+            );
+            let new_state = vote_state.set_change_only_window(window);
+            // some block must still accept the first ballots
+            let now = frame_system::Module::<T>::block_number();
+            ensure!(
+                new_state.accepts_new_ballots(now),
+                Error::<T>::ChangeOnlyWindowExceedsVoteDuration
+            );
+            <VoteStates<T>>::insert(vote_id, new_state);
+            Self::deposit_event(RawEvent::ChangeOnlyWindowSet(vote_id, window));
+            Ok(())
         }
-        <VoteIdCounter<T>>::put(id_counter);
-        id_counter
-    }
-}
-
-impl<T: Trait> GetVoteOutcome<T::VoteId> for Module<T> {
-    type Outcome = VoteOutcome;
-    fn get_vote_outcome(
-        vote_id: T::VoteId,
-    ) -> Result<Self::Outcome, DispatchError> {
-        let vote_state = <VoteStates<T>>::get(vote_id)
-            .ok_or(Error::<T>::NoVoteStateForOutcomeQuery)?;
-        Ok(vote_state.outcome())
-    }
-}
-
-impl<T: Trait> ConfigureThreshold<ThreshInput<T>, T::Cid, T::BlockNumber>
-    for Module<T>
-{
-    type ThresholdId = T::ThresholdId;
-    type VoteId = T::VoteId;
-    fn register_threshold(
-        t: ThreshInput<T>,
-    ) -> Result<T::ThresholdId, DispatchError> {
-        let id = Self::generate_threshold_uid();
-        let threshold = Thresh::<T>::new(id, t.org(), t.threshold());
-        <VoteThresholds<T>>::insert(id, threshold);
-        Ok(id)
-    }
-    fn invoke_threshold(
-        id: T::ThresholdId,
-        topic: Option<T::Cid>,
-        duration: Option<T::BlockNumber>,
-    ) -> Result<T::VoteId, DispatchError> {
-        let config = <VoteThresholds<T>>::get(id)
-            .ok_or(Error::<T>::CannotInvokeThresholdThatDNE)?;
-        match config.threshold() {
-            XorThreshold::Signal(t) => {
-                Self::open_vote(topic, config.org(), t, duration)
-            }
-            XorThreshold::Percent(t) => {
-                Self::open_percent_vote(topic, config.org(), t, duration)
+        #[weight = 0]
+        pub fn set_deadline_extension(
+            origin,
+            vote_id: T::VoteId,
+            // the window and the maximum extension
+            extension: Option<(T::BlockNumber, T::BlockNumber)>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let organization = <VoteOrgs<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotSetDeadlineExtensionOfVoteWithoutOrg)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization.org(), &caller),
+                Error::<T>::NotAuthorizedToSetDeadlineExtension
+            );
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            ensure!(
+                vote_state.turnout() == Zero::zero(),
+                Error::<T>::CannotSetDeadlineExtensionAfterVotingStarts
+            );
+            ensure!(
+                extension.is_none() || vote_state.ends().is_some(),
+                Error::<T>::DeadlineExtensionNeedsVoteToEnd
+            );
+            let extension = extension.map(|(window, max)| DeadlineExtension::new(window, max));
+            if let Some(e) = extension {
+                <DeadlineExtensions<T>>::insert(vote_id, e);
+            } else {
+                <DeadlineExtensions<T>>::remove(vote_id);
             }
+            Self::deposit_event(RawEvent::DeadlineExtensionSet(vote_id, extension));
+            Ok(())
         }
-    }
-}
-
-impl<T: Trait>
-    OpenVote<
+        #[weight = 0]
+        pub fn close_vote(
+            origin,
+            vote_id: T::VoteId,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let organization = <VoteOrgs<T>>::get(vote_id)
+                .ok_or(Error::<T>::CannotCloseVoteWithoutOrg)?;
+            let authority = <org::Module<T>>::supervisor_authority(
+                organization.org(),
+                &caller,
+                PowerMask::MANAGE_VOTES,
+            );
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCloseVote);
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            ensure!(
+                !<ReportedOutcomes<T>>::contains_key(vote_id)
+                    && !Self::check_vote_expired(&vote_state),
+                Error::<T>::VoteAlreadyClosed
+            );
+            // ending the vote in the previous block rejects further ballots
+            let now = frame_system::Module::<T>::block_number();
+            let ended = vote_state.settle().set_ends(now.saturating_sub(1u32.into()));
+            <VoteStates<T>>::insert(vote_id, &ended);
+            Self::report_outcome(vote_id, ended.outcome());
+            <org::Module<T>>::note_emergency_use(organization.org(), &caller, PowerMask::MANAGE_VOTES, authority);
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn cancel_vote(
+            origin,
+            vote_id: T::VoteId,
+            reason: Option<T::Cid>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            reason.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let organization = <VoteOrgs<T>>::get(vote_id).map(|o| o.org());
+            let authority = if <VoteCreators<T>>::get(vote_id).as_ref() == Some(&caller) {
+                Some(SupervisorAuthority::Supervisor)
+            } else {
+                organization.and_then(|org| {
+                    <org::Module<T>>::supervisor_authority(org, &caller, PowerMask::MANAGE_VOTES)
+                })
+            };
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCancelVote);
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            ensure!(
+                !<ReportedOutcomes<T>>::contains_key(vote_id)
+                    && !Self::check_vote_expired(&vote_state),
+                Error::<T>::VoteAlreadyClosed
+            );
+            <VoteStates<T>>::insert(vote_id, vote_state.cancel());
+            Self::report_outcome(vote_id, VoteOutcome::Cancelled);
+            if let Some(org) = organization {
+                <org::Module<T>>::note_emergency_use(org, &caller, PowerMask::MANAGE_VOTES, authority);
+            }
+            Self::deposit_event(RawEvent::VoteCancelled(vote_id, caller, reason));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn open_standing_vote(
+            origin,
+            topic: Option<T::Cid>,
+            organization: OrgRep<T::OrgId>,
+        ) -> DispatchResult {
+            let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let authority = <org::Module<T>>::supervisor_authority(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            Self::ensure_org_active(organization.org())?;
+            let now = frame_system::Module::<T>::block_number();
+            let new_vote_id = Self::generate_unique_id()?;
+            // turnout grows with each first ballot and no threshold is ever met
+            let new_vote_state = VoteState::new(
+                topic,
+                0u32.into(),
+                Threshold::new(T::Signal::max_value(), None),
+                now,
+                None,
+            );
+            <VoteStates<T>>::insert(new_vote_id, new_vote_state);
+            <StandingVotes<T>>::insert(new_vote_id, true);
+            Self::note_vote_org(new_vote_id, organization);
+            let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+            <OpenVoteCounter>::put(new_vote_count);
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES, authority);
+            Self::deposit_event(RawEvent::StandingVoteStarted(vote_creator, new_vote_id));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn open_bonded_vote(
+            origin,
+            topic: Option<T::Cid>,
+            organization: OrgRep<T::OrgId>,
+            threshold: Threshold<T::Signal>,
+            // required so that bonds cannot be locked by a vote that never ends
+            duration: T::BlockNumber,
+            min_bond: BalanceOf<T>,
+        ) -> DispatchResult {
+            let vote_creator = ensure_signed(origin)?;
+            topic.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            let authority = <org::Module<T>>::supervisor_authority(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES);
+            ensure!(authority.is_some(), Error::<T>::NotAuthorizedToCreateVoteForOrganization);
+            Self::ensure_org_active(organization.org())?;
+            let now = frame_system::Module::<T>::block_number();
+            let ends = Self::vote_end(now, duration)?;
+            let new_vote_id = Self::generate_unique_id()?;
+            // turnout starts at zero and grows as voters bond
+            let new_vote_state = VoteState::new(
+                topic,
+                0u32.into(),
+                threshold,
+                now,
+                Some(ends),
+            );
+            <VoteStates<T>>::insert(new_vote_id, new_vote_state);
+            Self::schedule_expiry(new_vote_id, Some(ends));
+            <BondedVotes<T>>::insert(new_vote_id, min_bond);
+            Self::note_vote_org(new_vote_id, organization);
+            let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+            <OpenVoteCounter>::put(new_vote_count);
+            <VoteCreators<T>>::insert(new_vote_id, &vote_creator);
+            <org::Module<T>>::note_emergency_use(organization.org(), &vote_creator, PowerMask::MANAGE_VOTES, authority);
+            Self::deposit_event(RawEvent::NewVoteStarted(vote_creator, new_vote_id));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn bond_and_vote(
+            origin,
+            vote_id: T::VoteId,
+            direction: VoterView,
+            amount: BalanceOf<T>,
+            justification: Option<T::Cid>,
+        ) -> DispatchResult {
+            let voter = ensure_signed(origin)?;
+            justification.iter().try_for_each(<org::Module<T>>::ensure_valid_cid)?;
+            Self::bond_vote(vote_id, &voter, direction, amount, justification)?;
+            Self::deposit_event(RawEvent::BondedVote(vote_id, voter, direction, amount));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn release_bond(
+            origin,
+            vote_id: T::VoteId,
+        ) -> DispatchResult {
+            let voter = ensure_signed(origin)?;
+            let vote_state = <VoteStates<T>>::get(vote_id)
+                .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+            ensure!(
+                <BondedVotes<T>>::get(vote_id).is_some(),
+                Error::<T>::NotABondedVote
+            );
+            ensure!(
+                vote_state.outcome() != VoteOutcome::Voting
+                    || Self::check_vote_expired(&vote_state),
+                Error::<T>::CannotReleaseBondBeforeVoteEnds
+            );
+            let bond = <VoteBonds<T>>::take(vote_id, &voter)
+                .ok_or(Error::<T>::NoBondToRelease)?;
+            T::BondCurrency::unreserve(&voter, bond);
+            Self::deposit_event(RawEvent::BondReleased(vote_id, voter, bond));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn link_accounts(
+            origin,
+            organization: T::OrgId,
+            accounts: Vec<T::AccountId>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization, &caller),
+                Error::<T>::NotAuthorizedToLinkAccounts
+            );
+            ensure!(accounts.len() >= 2, Error::<T>::LinkNeedsTwoAccounts);
+            for (i, who) in accounts.iter().enumerate() {
+                ensure!(!accounts[..i].contains(who), Error::<T>::DuplicateAccountInLink);
+                ensure!(
+                    <org::Module<T>>::is_member_of_group(organization, who),
+                    Error::<T>::LinkedAccountNotAMember
+                );
+                ensure!(
+                    !<AccountLinks<T>>::contains_key(organization, who),
+                    Error::<T>::AccountAlreadyLinked
+                );
+            }
+            let set_id = <LinkSetCounter>::get() + 1u32;
+            <LinkSetCounter>::put(set_id);
+            for who in accounts.iter() {
+                <AccountLinks<T>>::insert(organization, who, set_id);
+            }
+            let unconfirmed = accounts.iter().cloned().map(|who| (who, false)).collect::<Vec<_>>();
+            <LinkSets<T>>::insert(organization, set_id, unconfirmed);
+            Self::deposit_event(RawEvent::AccountsLinked(organization, set_id, accounts));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn confirm_link(
+            origin,
+            organization: T::OrgId,
+            set_id: u32,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let mut accounts = <LinkSets<T>>::get(organization, set_id)
+                .ok_or(Error::<T>::LinkSetDNE)?;
+            let confirmed = accounts
+                .iter_mut()
+                .find(|(who, _)| who == &caller)
+                .map(|(_, confirmed)| confirmed)
+                .ok_or(Error::<T>::NotInLinkSet)?;
+            ensure!(!*confirmed, Error::<T>::LinkAlreadyConfirmed);
+            *confirmed = true;
+            let active = accounts.iter().all(|(_, confirmed)| *confirmed);
+            if active {
+                <LinkedAccountCount<T>>::mutate(organization, |count| {
+                    *count += accounts.len() as u32 - 1
+                });
+            }
+            <LinkSets<T>>::insert(organization, set_id, accounts);
+            Self::deposit_event(RawEvent::LinkConfirmed(organization, set_id, caller));
+            if active {
+                Self::deposit_event(RawEvent::LinkActivated(organization, set_id));
+            }
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn unlink_accounts(
+            origin,
+            organization: T::OrgId,
+            set_id: u32,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization, &caller),
+                Error::<T>::NotAuthorizedToLinkAccounts
+            );
+            let accounts = <LinkSets<T>>::take(organization, set_id)
+                .ok_or(Error::<T>::LinkSetDNE)?;
+            if accounts.iter().all(|(_, confirmed)| *confirmed) {
+                <LinkedAccountCount<T>>::mutate(organization, |count| {
+                    *count = count.saturating_sub(accounts.len() as u32 - 1)
+                });
+            }
+            for (who, _) in accounts {
+                <AccountLinks<T>>::remove(organization, who);
+            }
+            Self::deposit_event(RawEvent::AccountsUnlinked(organization, set_id));
+            Ok(())
+        }
+        #[weight = 0]
+        pub fn set_percent_vote_floor(
+            origin,
+            organization: T::OrgId,
+            floor: Option<T::Signal>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                <org::Module<T>>::is_organization_supervisor(organization, &caller),
+                Error::<T>::NotAuthorizedToSetPercentVoteFloor
+            );
+            if let Some(f) = floor {
+                ensure!(f <= T::MaxPercentVoteFloor::get(), Error::<T>::PercentVoteFloorExceedsMaximum);
+                <PercentVoteFloors<T>>::insert(organization, f);
+            } else {
+                <PercentVoteFloors<T>>::remove(organization);
+            }
+            Self::deposit_event(RawEvent::PercentVoteFloorSet(organization, floor));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// The turnout below which the org's percent votes do not open, its own
+    /// floor or else the pallet default
+    pub fn percent_vote_floor(org: T::OrgId) -> T::Signal {
+        <PercentVoteFloors<T>>::get(org)
+            .unwrap_or_else(T::MinTurnoutForPercentVotes::get)
+    }
+    /// Fails if the turnout of the org is below the floor of its percent
+    /// votes, where one or two members would decide any percent alone
+    fn check_percent_vote_floor(
+        organization: OrgRep<T::OrgId>,
+        turnout: T::Signal,
+    ) -> DispatchResult {
+        ensure!(
+            turnout >= Self::percent_vote_floor(organization.org()),
+            Error::<T>::OrgTooSmallForPercentVote
+        );
+        Ok(())
+    }
+    /// The tally of the vote and the signal remaining to decide it
+    pub fn vote_progress(
+        vote_id: T::VoteId,
+    ) -> Option<VoteProgress<T::Signal>> {
+        <VoteStates<T>>::get(vote_id).map(|state| state.progress())
+    }
+    /// The outcome of a composite vote from the outcomes reported for its
+    /// legs, None if it is not a composite vote
+    pub fn composite_outcome(vote_id: T::VoteId) -> Option<VoteOutcome> {
+        let composite = <CompositeVotes<T>>::get(vote_id)?;
+        let legs = composite
+            .legs()
+            .into_iter()
+            .map(|leg| {
+                <ReportedOutcomes<T>>::get(leg).unwrap_or(VoteOutcome::Voting)
+            })
+            .collect::<Vec<_>>();
+        Some(composite.composition().outcome(&legs))
+    }
+    /// Whether `who` may still cast or change their ballot and until when,
+    /// None if the vote does not exist
+    pub fn ballot_status(
+        vote_id: T::VoteId,
+        who: &T::AccountId,
+    ) -> Option<BallotStatus<T::BlockNumber>> {
+        let vote_state = <VoteStates<T>>::get(vote_id)?;
+        let expired = DispatchError::from(
+            Error::<T>::VotePastExpirationTimeSoVotesNotAccepted,
+        );
+        let cancelled = DispatchError::from(Error::<T>::VoteCancelled);
+        let now = frame_system::Module::<T>::block_number();
+        let status = match Self::open_ballot(vote_id, who) {
+            Ok((_, ballot)) => {
+                match ballot.direction() {
+                    VoterView::Uninitialized
+                        if !vote_state.accepts_new_ballots(now) =>
+                    {
+                        BallotStatus::Locked {
+                            reason: LockReason::NewBallotsClosed,
+                        }
+                    }
+                    VoterView::Uninitialized => BallotStatus::NotYetVoted,
+                    _ => {
+                        BallotStatus::CanChange {
+                            until: vote_state.ends(),
+                        }
+                    }
+                }
+            }
+            Err(e) if e == expired || e == cancelled => {
+                let outcome = <ReportedOutcomes<T>>::get(vote_id)
+                    .unwrap_or_else(|| vote_state.outcome());
+                BallotStatus::Locked {
+                    reason: LockReason::Ended(outcome),
+                }
+            }
+            Err(_) => BallotStatus::NotAParticipant,
+        };
+        Some(status)
+    }
+    /// The voters of the vote with their signal, justification and the
+    /// blocks of their first and latest ballots, those who voted `direction`
+    /// or all who voted, skipping `offset` and at most `limit` of them; in
+    /// storage order, which is stable between reads of the same state
+    pub fn ballots(
+        vote_id: T::VoteId,
+        direction: Option<VoterView>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(
+        T::AccountId,
+        T::Signal,
+        Option<T::Cid>,
+        T::BlockNumber,
+        T::BlockNumber,
+    )> {
+        <VoteLogger<T>>::iter_prefix(vote_id)
+            .filter(|(_, ballot)| {
+                match direction {
+                    Some(direction) => ballot.direction() == direction,
+                    None => ballot.direction() != VoterView::Uninitialized,
+                }
+            })
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(voter, ballot)| {
+                (
+                    voter,
+                    ballot.magnitude(),
+                    ballot.justification(),
+                    ballot.cast_at(),
+                    ballot.last_changed_at(),
+                )
+            })
+            .collect()
+    }
+    /// The open vote and the ballot `voter` holds in it, minted on the spot
+    /// for lazily minted votes; every ballot passes these checks
+    fn open_ballot(
+        vote_id: T::VoteId,
+        voter: &T::AccountId,
+    ) -> Result<(VoteSt<T>, VoteVec<T>), DispatchError> {
+        let vote_state = <VoteStates<T>>::get(vote_id)
+            .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+        ensure!(
+            vote_state.outcome() != VoteOutcome::Cancelled,
+            Error::<T>::VoteCancelled
+        );
+        // TODO: add permissioned method for adding time to the vote state because of this restriction but this is a legitimate restriction
+        // -> every standard vote has a recognized end to establish when the decision must be made based on collected input
+        ensure!(
+            !Self::check_vote_expired(&vote_state),
+            Error::<T>::VotePastExpirationTimeSoVotesNotAccepted
+        );
+        let ballot = match <VoteLogger<T>>::get(vote_id, voter) {
+            Some(ballot) => Some(ballot),
+            None => Self::linked_ballot(vote_id, voter)?.map(|(_, b)| b),
+        }
+        .or_else(|| Self::lazy_mint(vote_id, voter))
+        .or_else(|| Self::standing_mint(vote_id, voter))
+        .ok_or(Error::<T>::SignalNotMintedForVoter)?;
+        ensure!(
+            !ballot.magnitude().is_zero(),
+            Error::<T>::ZeroSignalCannotVote
+        );
+        Ok((vote_state, ballot))
+    }
+    /// The legs of a composite vote a ballot of `voter` is cast in, those in
+    /// which they hold signal and have not voted in `direction` yet
+    fn ballot_legs(
+        composite: &CompositeSt<T>,
+        voter: &T::AccountId,
+        direction: VoterView,
+    ) -> Result<Vec<T::VoteId>, DispatchError> {
+        let legs = composite
+            .legs()
+            .into_iter()
+            .filter(|leg| {
+                matches!(
+                    Self::open_ballot(*leg, voter),
+                    Ok((_, ballot)) if ballot.direction() != direction
+                )
+            })
+            .collect::<Vec<_>>();
+        ensure!(!legs.is_empty(), Error::<T>::NoCompositeLegAcceptsBallot);
+        Ok(legs)
+    }
+    /// Opens the vote of each leg and the composite vote deciding on their
+    /// outcomes, once every leg is known to open
+    fn open_composite(
+        topic: Option<T::Cid>,
+        composition: Composition,
+        legs: Vec<Leg<T>>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<(T::VoteId, Vec<T::VoteId>), DispatchError> {
+        // no leg may fail to open once the first one opened
+        for (organization, threshold) in legs.iter() {
+            Self::ensure_org_active(organization.org())?;
+            let turnout = Self::possible_turnout(*organization)?;
+            let threshold = match threshold {
+                XorThreshold::Signal(t) => t.clone(),
+                XorThreshold::Percent(t) | XorThreshold::PercentOf(t, _) => {
+                    Self::check_percent_vote_floor(*organization, turnout)?;
+                    Self::from_permill_to_signal(t, turnout)
+                }
+            };
+            ensure!(
+                Self::valid_signal_threshold(&threshold, turnout),
+                Error::<T>::InputThresholdExceedsBounds
+            );
+        }
+        let mut leg_votes = Vec::with_capacity(legs.len());
+        for (organization, threshold) in legs {
+            let leg = match threshold {
+                XorThreshold::Signal(t) => {
+                    Self::open_vote(topic.clone(), organization, t, duration)?
+                }
+                XorThreshold::Percent(t) => {
+                    Self::open_percent_vote(
+                        topic.clone(),
+                        organization,
+                        t,
+                        duration,
+                    )?
+                }
+                XorThreshold::PercentOf(t, base) => {
+                    Self::open_percent_vote_of(
+                        topic.clone(),
+                        organization,
+                        t,
+                        base,
+                        None,
+                        duration,
+                    )?
+                }
+            };
+            leg_votes.push(leg);
+        }
+        let new_vote_id = Self::generate_unique_id()?;
+        for (index, leg) in leg_votes.iter().enumerate() {
+            <CompositeLegs<T>>::insert(leg, (new_vote_id, index as u32));
+        }
+        <CompositeVotes<T>>::insert(
+            new_vote_id,
+            CompositeVoteState::new(composition, leg_votes.clone()),
+        );
+        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+        <OpenVoteCounter>::put(new_vote_count);
+        Ok((new_vote_id, leg_votes))
+    }
+    /// The turnout a vote for the org opens with, without minting signal
+    fn possible_turnout(
+        organization: OrgRep<T::OrgId>,
+    ) -> Result<T::Signal, DispatchError> {
+        let turnout = match (Self::lazy_turnout(organization), organization) {
+            (Some(turnout), _) => turnout,
+            (None, OrgRep::Weighted(org_id)) => {
+                <org::Module<T>>::get_membership_with_shape(org_id)
+                    .ok_or(
+                        Error::<T>::CannotMintSignalBecauseMembershipShapeDNE,
+                    )?
+                    .total()
+                    .into()
+            }
+            (None, OrgRep::Equal(org_id)) => {
+                let group = <org::Module<T>>::get_group(org_id).ok_or(
+                    Error::<T>::CannotMintSignalBecauseGroupMembershipDNE,
+                )?;
+                (Self::equal_voters(org_id, group.0).len() as u32).into()
+            }
+        };
+        ensure!(!turnout.is_zero(), Error::<T>::EmptyOrgCannotVote);
+        Ok(turnout)
+    }
+    fn valid_signal_threshold(
+        threshold: &Threshold<T::Signal>,
+        all_possible_turnout: T::Signal,
+    ) -> bool {
+        threshold.in_favor() <= all_possible_turnout
+            && (if let Some(t) = threshold.against() {
+                t <= all_possible_turnout
+            } else {
+                true
+            })
+    }
+    /// Both thresholds round up so that each is only met once at least its
+    /// share of the turnout votes for it; complementary thresholds on an odd
+    /// turnout (e.g. 50% of 5 is 3 each way) can each be met but never both
+    fn from_permill_to_signal(
+        threshold: &Threshold<Permill>,
+        all_possible_turnout: T::Signal,
+    ) -> Threshold<T::Signal> {
+        let in_favor_t: T::Signal =
+            threshold.in_favor().mul_ceil(all_possible_turnout);
+        let against_t: Option<T::Signal> = if let Some(t) = threshold.against()
+        {
+            Some(t.mul_ceil(all_possible_turnout))
+        } else {
+            None
+        };
+        Threshold::new(in_favor_t, against_t)
+    }
+    /// The signal of each member of a weighted org in a vote in which no
+    /// voter holds more than `cap` of the org's shares, None without a cap
+    /// and in votes of equal signal, which no cap binds
+    fn capped_signal(
+        organization: OrgRep<T::OrgId>,
+        cap: Option<Permill>,
+    ) -> Result<Option<CappedSignal<T>>, DispatchError> {
+        let (org_id, cap) = match (organization, cap) {
+            (_, Some(cap)) if cap.is_zero() => {
+                return Err(Error::<T>::PerVoterCapIsZero.into())
+            }
+            (OrgRep::Weighted(org_id), Some(cap)) => (org_id, cap),
+            _ => return Ok(None),
+        };
+        let membership = <org::Module<T>>::get_membership_with_shape(org_id)
+            .ok_or(Error::<T>::CannotMintSignalBecauseMembershipShapeDNE)?;
+        let issuance: T::Signal = membership.total().into();
+        // rounded up so that every cap leaves each voter some signal
+        let max: T::Signal = cap.mul_ceil(issuance);
+        let mut capped = 0u32;
+        let mut total = T::Signal::zero();
+        let holders = membership
+            .vec()
+            .into_iter()
+            .map(|(who, shares)| {
+                let mut signal: T::Signal = shares.into();
+                if signal > max {
+                    signal = max;
+                    capped += 1;
+                }
+                total = total.saturating_add(signal);
+                (who, signal)
+            })
+            .collect();
+        Ok(Some(CappedSignal {
+            holders,
+            total,
+            max,
+            capped,
+        }))
+    }
+    /// Mints the signal of a new vote and returns its turnout, lazily
+    /// minted votes only record it
+    fn mint_vote_signal(
+        vote_id: T::VoteId,
+        organization: OrgRep<T::OrgId>,
+        lazy_turnout: Option<T::Signal>,
+        capped: Option<Vec<(T::AccountId, T::Signal)>>,
+    ) -> Result<T::Signal, DispatchError> {
+        Ok(match (capped, lazy_turnout, organization) {
+            (Some(holders), ..) => {
+                SignalBank::<VoteSignal<T>>::mint_batch(vote_id, holders)
+            }
+            (None, Some(turnout), _) => turnout,
+            (None, None, OrgRep::Weighted(org_id)) => {
+                Self::batch_mint_signal(vote_id, org_id)?
+            }
+            (None, None, OrgRep::Equal(org_id)) => {
+                Self::batch_mint_equal_signal(vote_id, org_id)?
+            }
+        })
+    }
+    /// Records the cap of a capped vote and tells if it bound
+    fn note_per_voter_cap(
+        vote_id: T::VoteId,
+        cap: Option<Permill>,
+        binding: Option<(T::Signal, u32)>,
+    ) {
+        if let Some(cap) = cap {
+            <PerVoterCaps<T>>::insert(vote_id, cap);
+        }
+        if let Some((max, capped)) = binding {
+            Self::deposit_event(RawEvent::VoterSignalCapped(
+                vote_id, max, capped,
+            ));
+        }
+    }
+    /// Opens a vote on a signal threshold, capping each voter's signal at
+    /// `per_voter_cap` of the org's shares if it is set
+    fn open_signal_vote_of(
+        topic: Option<T::Cid>,
+        organization: OrgRep<T::OrgId>,
+        threshold: Threshold<T::Signal>,
+        per_voter_cap: Option<Permill>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<T::VoteId, DispatchError> {
+        Self::ensure_org_active(organization.org())?;
+        let capped = Self::capped_signal(organization, per_voter_cap)?;
+        // the turnout of a capped vote is known before anything is written
+        if let Some(capped) = &capped {
+            ensure!(!capped.total.is_zero(), Error::<T>::EmptyOrgCannotVote);
+            ensure!(
+                Self::valid_signal_threshold(&threshold, capped.total),
+                Error::<T>::InputThresholdExceedsBounds
+            );
+        }
+        // calculate `initialized` and `expires` fields for vote state
+        let now = frame_system::Module::<T>::block_number();
+        let ends: Option<T::BlockNumber> = if let Some(time_to_add) = duration {
+            Some(Self::vote_end(now, time_to_add)?)
+        } else {
+            None
+        };
+        // generate new vote_id
+        let new_vote_id = Self::generate_unique_id()?;
+        // capped votes mint up front, each voter's cap depends on all shares
+        let lazy_turnout = match capped {
+            Some(_) => None,
+            None => Self::lazy_turnout(organization),
+        };
+        let binding = capped.as_ref().and_then(CappedSignal::binding);
+        let total_possible_turnout = Self::mint_vote_signal(
+            new_vote_id,
+            organization,
+            lazy_turnout,
+            capped.map(|capped| capped.holders),
+        )?;
+        // without signal no vote could ever be cast
+        ensure!(
+            !total_possible_turnout.is_zero(),
+            Error::<T>::EmptyOrgCannotVote
+        );
+        ensure!(
+            Self::valid_signal_threshold(&threshold, total_possible_turnout),
+            Error::<T>::InputThresholdExceedsBounds
+        );
+        // instantiate new VoteState with threshold and temporal metadata
+        let new_vote_state =
+            VoteState::new(topic, total_possible_turnout, threshold, now, ends);
+        if lazy_turnout.is_some() {
+            Self::snapshot_signal(
+                new_vote_id,
+                organization,
+                total_possible_turnout,
+            );
+        }
+        // insert the VoteState
+        <VoteStates<T>>::insert(new_vote_id, new_vote_state);
+        Self::schedule_expiry(new_vote_id, ends);
+        Self::note_vote_org(new_vote_id, organization);
+        Self::note_per_voter_cap(new_vote_id, per_voter_cap, binding);
+        // increment open vote count
+        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+        <OpenVoteCounter>::put(new_vote_count);
+        Ok(new_vote_id)
+    }
+    /// Opens a vote on percents of `base`, a vote on the votes cast
+    /// converts its minimum turnout to signal when it opens. The percents
+    /// of a capped vote are of the capped signal.
+    fn open_percent_vote_of(
+        topic: Option<T::Cid>,
+        organization: OrgRep<T::OrgId>,
+        threshold: Threshold<Permill>,
+        base: PercentBase<Permill>,
+        per_voter_cap: Option<Permill>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<T::VoteId, DispatchError> {
+        Self::ensure_org_active(organization.org())?;
+        let capped = Self::capped_signal(organization, per_voter_cap)?;
+        // checked before any signal is minted
+        if let Some(capped) = &capped {
+            ensure!(!capped.total.is_zero(), Error::<T>::EmptyOrgCannotVote);
+        }
+        if !Self::percent_vote_floor(organization.org()).is_zero() {
+            let turnout = match &capped {
+                Some(capped) => capped.total,
+                None => Self::possible_turnout(organization)?,
+            };
+            Self::check_percent_vote_floor(organization, turnout)?;
+        }
+        // calculate `initialized` and `expires` fields for vote state
+        let now = frame_system::Module::<T>::block_number();
+        let ends: Option<T::BlockNumber> = if let Some(time_to_add) = duration {
+            Some(Self::vote_end(now, time_to_add)?)
+        } else {
+            None
+        };
+        // generate new vote_id
+        let new_vote_id = Self::generate_unique_id()?;
+        // capped votes mint up front, each voter's cap depends on all shares
+        let lazy_turnout = match capped {
+            Some(_) => None,
+            None => Self::lazy_turnout(organization),
+        };
+        let binding = capped.as_ref().and_then(CappedSignal::binding);
+        let total_possible_turnout = Self::mint_vote_signal(
+            new_vote_id,
+            organization,
+            lazy_turnout,
+            capped.map(|capped| capped.holders),
+        )?;
+        // without signal no vote could ever be cast
+        ensure!(
+            !total_possible_turnout.is_zero(),
+            Error::<T>::EmptyOrgCannotVote
+        );
+        let signal_threshold =
+            Self::from_permill_to_signal(&threshold, total_possible_turnout);
+        ensure!(
+            Self::valid_signal_threshold(
+                &signal_threshold,
+                total_possible_turnout
+            ),
+            Error::<T>::InputThresholdExceedsBounds
+        );
+        // instantiate new VoteState with threshold and temporal metadata
+        let new_vote_state = match base {
+            PercentBase::TotalIssuance => {
+                VoteState::new(
+                    topic,
+                    total_possible_turnout,
+                    signal_threshold,
+                    now,
+                    ends,
+                )
+            }
+            PercentBase::VotesCast { min_turnout } => {
+                let min_turnout =
+                    min_turnout.map(|p| p.mul_ceil(total_possible_turnout));
+                VoteState::new_on_votes_cast(
+                    topic,
+                    total_possible_turnout,
+                    VotesCastThreshold::new(threshold, min_turnout),
+                    now,
+                    ends,
+                )
+            }
+        };
+        if lazy_turnout.is_some() {
+            Self::snapshot_signal(
+                new_vote_id,
+                organization,
+                total_possible_turnout,
+            );
+        }
+        // insert the VoteState
+        <VoteStates<T>>::insert(new_vote_id, new_vote_state);
+        Self::schedule_expiry(new_vote_id, ends);
+        Self::note_vote_org(new_vote_id, organization);
+        Self::note_per_voter_cap(new_vote_id, per_voter_cap, binding);
+        // increment open vote count
+        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+        <OpenVoteCounter>::put(new_vote_count);
+        Ok(new_vote_id)
+    }
+    /// Reserves the voter's bond and applies it to the tally, replacing any
+    /// previous bond and vote of the voter
+    fn bond_vote(
+        vote_id: T::VoteId,
+        voter: &T::AccountId,
+        direction: VoterView,
+        amount: BalanceOf<T>,
+        justification: Option<T::Cid>,
+    ) -> DispatchResult {
+        let min_bond =
+            <BondedVotes<T>>::get(vote_id).ok_or(Error::<T>::NotABondedVote)?;
+        ensure!(amount >= min_bond, Error::<T>::BondBelowMinimumForVote);
+        let vote_state = <VoteStates<T>>::get(vote_id)
+            .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
+        ensure!(
+            vote_state.outcome() == VoteOutcome::Voting,
+            Error::<T>::CannotBondForVoteThatIsNotOpen
+        );
+        ensure!(
+            !Self::check_vote_expired(&vote_state),
+            Error::<T>::VotePastExpirationTimeSoVotesNotAccepted
+        );
+        let magnitude = T::Signal::try_from(amount.saturated_into::<u128>())
+            .map_err(|_| Error::<T>::BondExceedsSignalRange)?;
+        let now = frame_system::Module::<T>::block_number();
+        // take the previous vote out of the tally before applying the new one
+        let (vote_state, cast_at) = match <VoteLogger<T>>::get(vote_id, voter) {
+            Some(old_vote) => {
+                (
+                    vote_state.remove_vote(
+                        old_vote.magnitude(),
+                        old_vote.direction(),
+                    ),
+                    old_vote.cast_at(),
+                )
+            }
+            None => (vote_state, now),
+        };
+        let new_state = Self::apply_vote(
+            vote_state.add_possible_turnout(magnitude),
+            magnitude,
+            VoterView::Uninitialized,
+            direction,
+        )
+        .ok_or(Error::<T>::VoteChangeNotSupported)?;
+        // only the difference to the previous bond is reserved or released
+        let old_bond = <VoteBonds<T>>::get(vote_id, voter).unwrap_or_default();
+        if amount > old_bond {
+            T::BondCurrency::reserve(voter, amount - old_bond)?;
+        } else {
+            T::BondCurrency::unreserve(voter, old_bond - amount);
+        }
+        <VoteBonds<T>>::insert(vote_id, voter, amount);
+        Self::record_ballot(vote_id, voter, direction, justification.clone());
+        <VoteLogger<T>>::insert(
+            vote_id,
+            voter,
+            Vote::new(magnitude, direction, justification, cast_at, now),
+        );
+        <VoteStates<T>>::insert(vote_id, new_state.clone());
+        Self::report_if_decided(vote_id, &new_state);
+        Ok(())
+    }
+    /// Appends a ballot to the voter's justification history, dropping the
+    /// oldest ballots past `MaxJustificationHistory`
+    fn record_ballot(
+        vote_id: T::VoteId,
+        voter: &T::AccountId,
+        direction: VoterView,
+        justification: Option<T::Cid>,
+    ) {
+        let now = frame_system::Module::<T>::block_number();
+        <JustificationHistory<T>>::mutate(vote_id, voter, |history| {
+            history.push((now, direction, justification));
+            let max = T::MaxJustificationHistory::get() as usize;
+            if history.len() > max {
+                history.drain(..history.len() - max);
+            }
+        });
+        <BallotCounts<T>>::mutate(vote_id, voter, |count| *count += 1);
+    }
+    /// Opens a vote in which each listed member gets exactly the given signal
+    fn open_custom_weighted_vote(
+        topic: Option<T::Cid>,
+        organization: T::OrgId,
+        weights: Vec<(T::AccountId, T::Signal)>,
+        threshold: Threshold<T::Signal>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<T::VoteId, DispatchError> {
+        Self::ensure_org_active(organization)?;
+        let mut voters = Vec::with_capacity(weights.len());
+        let mut total_possible_turnout = T::Signal::zero();
+        for (who, signal) in weights.iter() {
+            ensure!(
+                <org::Module<T>>::is_member_of_group(organization, who),
+                Error::<T>::CustomWeightForNonMember
+            );
+            ensure!(
+                !voters.contains(&who),
+                Error::<T>::DuplicateVoterInCustomWeights
+            );
+            voters.push(who);
+            total_possible_turnout = total_possible_turnout
+                .checked_add(signal)
+                .ok_or(Error::<T>::SignalOverflow)?;
+        }
+        ensure!(
+            !total_possible_turnout.is_zero(),
+            Error::<T>::EmptyOrgCannotVote
+        );
+        ensure!(
+            Self::valid_signal_threshold(&threshold, total_possible_turnout),
+            Error::<T>::InputThresholdExceedsBounds
+        );
+        let now = frame_system::Module::<T>::block_number();
+        let ends = duration
+            .map(|time_to_add| Self::vote_end(now, time_to_add))
+            .transpose()?;
+        let new_vote_id = Self::generate_unique_id()?;
+        for (who, signal) in weights.into_iter().filter(|(_, s)| !s.is_zero()) {
+            <VoteLogger<T>>::insert(
+                new_vote_id,
+                who,
+                VoteVec::<T>::minted(signal),
+            );
+        }
+        <TotalSignalIssuance<T>>::insert(new_vote_id, total_possible_turnout);
+        let new_vote_state =
+            VoteState::new(topic, total_possible_turnout, threshold, now, ends);
+        <VoteStates<T>>::insert(new_vote_id, new_vote_state);
+        Self::schedule_expiry(new_vote_id, ends);
+        // the supervisor of the org may still update the topic
+        Self::note_vote_org(new_vote_id, OrgRep::Weighted(organization));
+        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
+        <OpenVoteCounter>::put(new_vote_count);
+        Ok(new_vote_id)
+    }
+    /// Passes the outcome to the handlers unless it was reported before, a
+    /// vote still undecided when it ends is reported as rejected
+    fn report_outcome(vote_id: T::VoteId, outcome: VoteOutcome) {
+        if <ReportedOutcomes<T>>::contains_key(vote_id) {
+            return
+        }
+        let outcome = match outcome {
+            VoteOutcome::Approved => VoteOutcome::Approved,
+            VoteOutcome::Cancelled => VoteOutcome::Cancelled,
+            _ => VoteOutcome::Rejected,
+        };
+        <ReportedOutcomes<T>>::insert(vote_id, outcome);
+        <OpenVoteCounter>::mutate(|count| *count = count.saturating_sub(1));
+        if let Some(organization) = <VoteOrgs<T>>::get(vote_id) {
+            Self::unindex_open_vote(organization.org(), vote_id);
+        }
+        Self::release_snapshot(vote_id);
+        T::OutcomeHandler::on_outcome(vote_id, outcome);
+        Self::deposit_event(RawEvent::VoteOutcomeReported(vote_id, outcome));
+        Self::report_leg_outcome(vote_id, outcome);
+    }
+    /// Reports the outcome of the composite vote of a leg once the outcomes
+    /// of its legs decide it
+    fn report_leg_outcome(leg: T::VoteId, outcome: VoteOutcome) {
+        if let Some((vote_id, index)) = <CompositeLegs<T>>::get(leg) {
+            Self::deposit_event(RawEvent::CompositeLegDecided(
+                vote_id, index, outcome,
+            ));
+            match Self::composite_outcome(vote_id) {
+                Some(VoteOutcome::Approved) => {
+                    Self::report_outcome(vote_id, VoteOutcome::Approved)
+                }
+                Some(VoteOutcome::Rejected) => {
+                    Self::report_outcome(vote_id, VoteOutcome::Rejected)
+                }
+                _ => (),
+            }
+        }
+    }
+    /// Reports the outcome once a ballot meets either threshold
+    fn report_if_decided(vote_id: T::VoteId, vote_state: &VoteSt<T>) {
+        match vote_state.outcome() {
+            VoteOutcome::Approved | VoteOutcome::Rejected => {
+                Self::report_outcome(vote_id, vote_state.outcome())
+            }
+            _ => (),
+        }
+    }
+    /// Pushes the end of an undecided vote back if the ballot cast at `now`
+    /// is in its final blocks and the extension is not used up
+    fn extend_if_late(
+        vote_id: T::VoteId,
+        state: VoteSt<T>,
+        now: T::BlockNumber,
+    ) {
+        if <ReportedOutcomes<T>>::contains_key(vote_id) {
+            return
+        }
+        let extended =
+            match (state.ends(), <DeadlineExtensions<T>>::get(vote_id)) {
+                (Some(ends), Some(extension)) => extension.extend(ends, now),
+                _ => None,
+            };
+        if let Some((new_ends, extension)) = extended {
+            <DeadlineExtensions<T>>::insert(vote_id, extension);
+            <VoteStates<T>>::insert(vote_id, state.set_ends(new_ends));
+            Self::schedule_expiry(vote_id, Some(new_ends));
+            Self::deposit_event(RawEvent::VoteDeadlineExtended(
+                vote_id, new_ends,
+            ));
+        }
+    }
+    /// The turnout of a vote for the org if it is large enough for signal
+    /// to be minted lazily, read from the org's current shares
+    fn lazy_turnout(organization: OrgRep<T::OrgId>) -> Option<T::Signal> {
+        let members = <org::Module<T>>::member_count(organization.org());
+        if members <= T::LazyMintThreshold::get() {
+            return None
+        }
+        Some(match organization {
+            OrgRep::Weighted(org_id) => {
+                <org::Module<T>>::outstanding_shares(org_id).into()
+            }
+            OrgRep::Equal(org_id) => {
+                members
+                    .saturating_sub(<LinkedAccountCount<T>>::get(org_id))
+                    .into()
+            }
+        })
+    }
+    /// The link set `who` votes as one with in votes of equal signal, None
+    /// if they are not linked or some account did not confirm the link yet
+    fn active_link(org: T::OrgId, who: &T::AccountId) -> Option<u32> {
+        let set_id = <AccountLinks<T>>::get(org, who)?;
+        let accounts = <LinkSets<T>>::get(org, set_id)?;
+        if accounts.iter().all(|(_, confirmed)| *confirmed) {
+            Some(set_id)
+        } else {
+            None
+        }
+    }
+    /// The members minted signal in votes of equal signal, the first
+    /// account of each active link set stands in for the whole set
+    fn equal_voters(
+        org: T::OrgId,
+        members: Vec<T::AccountId>,
+    ) -> Vec<T::AccountId> {
+        let mut minted_sets = Vec::new();
+        members
+            .into_iter()
+            .filter(|who| {
+                match Self::active_link(org, who) {
+                    Some(set_id) if minted_sets.contains(&set_id) => false,
+                    Some(set_id) => {
+                        minted_sets.push(set_id);
+                        true
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+    /// The account holding the signal `voter` shares with their active link
+    /// set in a vote of equal signal and its ballot, None if they share no
+    /// signal or no account of the set holds it yet
+    fn linked_ballot(
+        vote_id: T::VoteId,
+        voter: &T::AccountId,
+    ) -> Result<Option<(T::AccountId, VoteVec<T>)>, DispatchError> {
+        let org = match <VoteOrgs<T>>::get(vote_id) {
+            Some(OrgRep::Equal(org)) => org,
+            _ => return Ok(None),
+        };
+        let accounts = match Self::active_link(org, voter)
+            .and_then(|set_id| <LinkSets<T>>::get(org, set_id))
+        {
+            Some(accounts) => accounts,
+            None => return Ok(None),
+        };
+        let others = accounts.into_iter().filter(|(who, _)| who != voter);
+        for (holder, _) in others {
+            if let Some(ballot) = <VoteLogger<T>>::get(vote_id, &holder) {
+                ensure!(
+                    ballot.direction() == VoterView::Uninitialized,
+                    Error::<T>::LinkedAccountAlreadyVoted
+                );
+                return Ok(Some((holder, ballot)))
+            }
+        }
+        Ok(None)
+    }
+    /// Snapshots the org's shares so that the signal minted by each first
+    /// ballot adds up to the recorded turnout
+    fn snapshot_signal(
+        vote_id: T::VoteId,
+        organization: OrgRep<T::OrgId>,
+        turnout: T::Signal,
+    ) {
+        let snapshot =
+            <org::Module<T>>::take_share_snapshot(organization.org());
+        <LazySnapshots<T>>::insert(vote_id, snapshot);
+        <TotalSignalIssuance<T>>::insert(vote_id, turnout);
+    }
+    /// Stops recording shares for the vote's snapshot once the vote ended,
+    /// members who did not vote yet cannot be minted signal afterwards
+    fn release_snapshot(vote_id: T::VoteId) {
+        if let Some(snapshot) = <LazySnapshots<T>>::take(vote_id) {
+            if let Some(organization) = <VoteOrgs<T>>::get(vote_id) {
+                <org::Module<T>>::release_share_snapshot(
+                    organization.org(),
+                    snapshot,
+                );
+            }
+        }
+    }
+    /// The ballot `who` starts with in a lazily minted vote, from the shares
+    /// they held when the vote opened
+    fn lazy_mint(vote_id: T::VoteId, who: &T::AccountId) -> Option<VoteVec<T>> {
+        let snapshot = <LazySnapshots<T>>::get(vote_id)?;
+        let organization = <VoteOrgs<T>>::get(vote_id)?;
+        let shares = <org::Module<T>>::snapshot_shares(
+            organization.org(),
+            snapshot,
+            who,
+        );
+        if shares.is_zero() {
+            return None
+        }
+        let signal: T::Signal = match organization {
+            OrgRep::Weighted(_) => shares.into(),
+            OrgRep::Equal(_) => 1u32.into(),
+        };
+        Some(VoteVec::<T>::minted(signal))
+    }
+    /// The ballot `who` starts with in a standing vote, from the shares they
+    /// hold now
+    fn standing_mint(
+        vote_id: T::VoteId,
+        who: &T::AccountId,
+    ) -> Option<VoteVec<T>> {
+        if !<StandingVotes<T>>::get(vote_id) {
+            return None
+        }
+        let organization = <VoteOrgs<T>>::get(vote_id)?;
+        let signal = Self::standing_signal(organization, who);
+        if signal.is_zero() {
+            return None
+        }
+        Some(VoteVec::<T>::minted(signal))
+    }
+    /// The signal of `who` in the standing votes of the org, from the voting
+    /// shares they hold now
+    fn standing_signal(
+        organization: OrgRep<T::OrgId>,
+        who: &T::AccountId,
+    ) -> T::Signal {
+        let shares: T::Shares =
+            <org::Module<T>>::get_share_profile(organization.org(), who)
+                .map_or_else(Zero::zero, |profile| profile.total());
+        match organization {
+            _ if shares.is_zero() => Zero::zero(),
+            OrgRep::Weighted(_) => shares.into(),
+            OrgRep::Equal(_) => 1u32.into(),
+        }
+    }
+    /// The standing votes `voter` holds a ballot in once their first ballot
+    /// in `vote_id` is cast, without those which ended
+    fn with_standing_ballot(
+        vote_id: T::VoteId,
+        voter: &T::AccountId,
+    ) -> Result<Vec<T::VoteId>, DispatchError> {
+        let mut ballots = <StandingBallots<T>>::get(voter);
+        ballots.retain(|v| {
+            <VoteStates<T>>::get(v)
+                .map_or(false, |state| Self::standing_vote_open(&state))
+        });
+        ensure!(
+            (ballots.len() as u32) < T::MaxStandingBallots::get(),
+            Error::<T>::TooManyStandingBallots
+        );
+        ballots.push(vote_id);
+        Ok(ballots)
+    }
+    /// Whether the tally of a standing vote still follows the shares of its
+    /// voters, it is frozen once the vote is closed or cancelled
+    fn standing_vote_open(vote_state: &VoteSt<T>) -> bool {
+        vote_state.outcome() != VoteOutcome::Cancelled
+            && !Self::check_vote_expired(vote_state)
+    }
+    /// Re-weighs the ballot of `who` in a standing vote of `org` to the
+    /// shares they hold now, false once the ballot needs no more tracking
+    fn reweigh_standing_ballot(
+        vote_id: T::VoteId,
+        org: T::OrgId,
+        who: &T::AccountId,
+    ) -> bool {
+        let (organization, vote_state, ballot) = match (
+            <VoteOrgs<T>>::get(vote_id),
+            <VoteStates<T>>::get(vote_id),
+            <VoteLogger<T>>::get(vote_id, who),
+        ) {
+            (Some(organization), Some(vote_state), Some(ballot)) => {
+                (organization, vote_state, ballot)
+            }
+            _ => return false,
+        };
+        if !Self::standing_vote_open(&vote_state) {
+            return false
+        }
+        if organization.org() != org {
+            return true
+        }
+        let signal = Self::standing_signal(organization, who);
+        if signal == ballot.magnitude() {
+            return true
+        }
+        let vote_state =
+            vote_state.remove_vote(ballot.magnitude(), ballot.direction());
+        let new_state = if signal.is_zero() {
+            // the voter rejoins the tally with their next ballot
+            <VoteLogger<T>>::remove(vote_id, who);
+            vote_state
+        } else {
+            let vote_state = vote_state.add_possible_turnout(signal);
+            <VoteLogger<T>>::insert(vote_id, who, ballot.reweigh(signal));
+            // ballots reset by a topic change only count in the electorate
+            Self::apply_vote(
+                vote_state.clone(),
+                signal,
+                VoterView::Uninitialized,
+                ballot.direction(),
+            )
+            .unwrap_or(vote_state)
+        };
+        <VoteStates<T>>::insert(vote_id, new_state);
+        Self::deposit_event(RawEvent::StandingBallotReweighed(
+            vote_id,
+            who.clone(),
+        ));
+        !signal.is_zero()
+    }
+    /// The weight of sweeping one expired vote, without the outcome handlers
+    fn expiry_weight() -> Weight {
+        T::DbWeight::get().reads_writes(4, 4)
+    }
+    /// The weight of carrying expiries past the budget over to the next
+    /// block's queue
+    fn carry_over_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+    /// Records the org of a new vote and indexes it among the org's open
+    /// votes
+    fn note_vote_org(vote_id: T::VoteId, organization: OrgRep<T::OrgId>) {
+        let org = organization.org();
+        <VoteOrgs<T>>::insert(vote_id, organization);
+        <OrgOpenVotes<T>>::insert(org, vote_id, true);
+        <OrgOpenVoteCount<T>>::mutate(org, |count| {
+            *count = count.saturating_add(1)
+        });
+    }
+    fn unindex_open_vote(org: T::OrgId, vote_id: T::VoteId) {
+        if <OrgOpenVotes<T>>::take(org, vote_id) {
+            <OrgOpenVoteCount<T>>::mutate(org, |count| {
+                *count = count.saturating_sub(1)
+            });
+        }
+    }
+    fn ensure_org_active(org: T::OrgId) -> DispatchResult {
+        ensure!(
+            <org::Module<T>>::org_lifecycle(org).is_active(),
+            Error::<T>::OrgNotActive
+        );
+        Ok(())
+    }
+    /// The open votes of the orgs queued for the frozen org sweep
+    fn frozen_votes_due() -> u32 {
+        <FrozenOrgSweeps<T>>::get()
+            .into_iter()
+            .map(<OrgOpenVoteCount<T>>::get)
+            .fold(0u32, |due, count| due.saturating_add(count))
+    }
+    /// Cancels up to `allowance` open votes of the queued frozen orgs,
+    /// dropping each org from the queue once none of its votes are left
+    fn sweep_frozen_orgs(now: T::BlockNumber, mut allowance: u32) {
+        let mut queue = <FrozenOrgSweeps<T>>::get();
+        if queue.is_empty() {
+            return
+        }
+        let mut swept = 0;
+        for org in queue.iter() {
+            // orgs unfrozen before the sweep reached them keep their votes
+            if <org::Module<T>>::org_lifecycle(*org).is_active()
+                || <OrgOpenVoteCount<T>>::get(org) == 0
+            {
+                swept += 1;
+                continue
+            }
+            if allowance == 0 {
+                break
+            }
+            let due = <OrgOpenVotes<T>>::iter_prefix(*org)
+                .map(|(vote_id, _)| vote_id)
+                .take(allowance as usize)
+                .collect::<Vec<_>>();
+            allowance -= due.len() as u32;
+            for vote_id in due {
+                Self::cancel_for_frozen_org(now, *org, vote_id);
+            }
+            if <OrgOpenVoteCount<T>>::get(org) > 0 {
+                break
+            }
+            swept += 1;
+        }
+        queue.drain(..swept);
+        <FrozenOrgSweeps<T>>::put(queue);
+    }
+    fn cancel_for_frozen_org(
+        now: T::BlockNumber,
+        org: T::OrgId,
+        vote_id: T::VoteId,
+    ) {
+        let vote_state = match <VoteStates<T>>::get(vote_id) {
+            Some(vote_state) => vote_state,
+            None => return Self::unindex_open_vote(org, vote_id),
+        };
+        // the expiry sweep reports votes past their end
+        if vote_state.ends().map_or(false, |ends| ends <= now) {
+            return Self::unindex_open_vote(org, vote_id)
+        }
+        <VoteStates<T>>::insert(vote_id, vote_state.cancel());
+        Self::report_outcome(vote_id, VoteOutcome::Cancelled);
+        Self::deposit_event(RawEvent::VoteCancelledOrgFrozen(vote_id, org));
+    }
+    fn schedule_expiry(vote_id: T::VoteId, ends: Option<T::BlockNumber>) {
+        if let Some(ends) = ends {
+            <VoteExpiries<T>>::append(ends, vote_id);
+        }
+    }
+    /// The block at which a vote opened at `now` for `duration` blocks ends
+    fn vote_end(
+        now: T::BlockNumber,
+        duration: T::BlockNumber,
+    ) -> Result<T::BlockNumber, DispatchError> {
+        // a wrapped end would lie in the past and expire the vote at once
+        now.checked_add(&duration)
+            .ok_or_else(|| Error::<T>::DurationOverflow.into())
+    }
+    fn generate_threshold_uid() -> Result<T::ThresholdId, DispatchError> {
+        let next = |id: T::ThresholdId| {
+            id.checked_add(&1u32.into())
+                .ok_or(Error::<T>::IdSpaceExhausted)
+        };
+        let mut thresh_counter = next(<ThresholdIdCounter<T>>::get())?;
+        while <VoteThresholds<T>>::get(thresh_counter).is_some() {
+            thresh_counter = next(thresh_counter)?;
+        }
+        <ThresholdIdCounter<T>>::put(thresh_counter);
+        Ok(thresh_counter)
+    }
+    /// Fails instead of wrapping once the id type has no unused id left
+    fn generate_unique_id() -> Result<T::VoteId, DispatchError> {
+        let next = |id: T::VoteId| {
+            id.checked_add(&1u32.into())
+                .ok_or(Error::<T>::IdSpaceExhausted)
+        };
+        let mut id_counter = next(<VoteIdCounter<T>>::get())?;
+        while !Self::id_is_available(id_counter) {
+            id_counter = next(id_counter)?;
+        }
+        <VoteIdCounter<T>>::put(id_counter);
+        Ok(id_counter)
+    }
+}
+
+impl<T: Trait> IDIsAvailable<T::VoteId> for Module<T> {
+    fn id_is_available(id: T::VoteId) -> bool {
+        <VoteStates<T>>::get(id).is_none()
+            && !<CompositeVotes<T>>::contains_key(id)
+    }
+}
+
+impl<T: Trait> GetVoteOutcome<T::VoteId> for Module<T> {
+    type Outcome = VoteOutcome;
+    fn get_vote_outcome(
+        vote_id: T::VoteId,
+    ) -> Result<Self::Outcome, DispatchError> {
+        if let Some(outcome) = Self::composite_outcome(vote_id) {
+            return Ok(outcome)
+        }
+        let vote_state = <VoteStates<T>>::get(vote_id)
+            .ok_or(Error::<T>::NoVoteStateForOutcomeQuery)?;
+        Ok(vote_state.outcome())
+    }
+}
+
+impl<T: Trait> ConfigureThreshold<ThreshInput<T>, T::Cid, T::BlockNumber>
+    for Module<T>
+{
+    type ThresholdId = T::ThresholdId;
+    type VoteId = T::VoteId;
+    fn register_threshold(
+        t: ThreshInput<T>,
+    ) -> Result<T::ThresholdId, DispatchError> {
+        let cap = t.per_voter_cap();
+        ensure!(
+            cap.map_or(true, |cap| !cap.is_zero()),
+            Error::<T>::PerVoterCapIsZero
+        );
+        let id = Self::generate_threshold_uid()?;
+        let threshold = Thresh::<T>::new(id, t.org(), t.threshold());
+        <VoteThresholds<T>>::insert(id, threshold);
+        if let Some(cap) = cap {
+            <ThresholdVoterCaps<T>>::insert(id, cap);
+        }
+        Ok(id)
+    }
+    fn invoke_threshold(
+        id: T::ThresholdId,
+        topic: Option<T::Cid>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<T::VoteId, DispatchError> {
+        let config = <VoteThresholds<T>>::get(id)
+            .ok_or(Error::<T>::CannotInvokeThresholdThatDNE)?;
+        let cap = <ThresholdVoterCaps<T>>::get(id);
+        let (threshold, base) = match config.threshold() {
+            XorThreshold::Signal(t) => {
+                return Self::open_signal_vote_of(
+                    topic,
+                    config.org(),
+                    t,
+                    cap,
+                    duration,
+                )
+            }
+            XorThreshold::Percent(t) => (t, PercentBase::TotalIssuance),
+            XorThreshold::PercentOf(t, base) => (t, base),
+        };
+        Self::open_percent_vote_of(
+            topic,
+            config.org(),
+            threshold,
+            base,
+            cap,
+            duration,
+        )
+    }
+}
+
+impl<T: Trait> ThresholdVote<T::OrgId, T::Cid, T::BlockNumber> for Module<T> {
+    type ThresholdId = T::ThresholdId;
+    type VoteId = T::VoteId;
+    fn threshold_org(id: T::ThresholdId) -> Option<T::OrgId> {
+        <VoteThresholds<T>>::get(id).map(|t| t.org().org())
+    }
+    fn default_threshold(org: T::OrgId) -> Option<T::ThresholdId> {
+        <DefaultThresholds<T>>::get(org)
+    }
+    fn open_threshold_vote(
+        id: T::ThresholdId,
+        topic: Option<T::Cid>,
+        duration: Option<T::BlockNumber>,
+    ) -> Result<T::VoteId, DispatchError> {
+        Self::invoke_threshold(id, topic, duration)
+    }
+    fn threshold_vote_outcome(
+        vote_id: T::VoteId,
+    ) -> Result<VoteOutcome, DispatchError> {
+        Self::get_vote_outcome(vote_id)
+    }
+}
+
+impl<T: Trait>
+    OpenVote<
         OrgRep<T::OrgId>,
         Threshold<T::Signal>,
         Threshold<Permill>,
@@ -395,37 +2471,13 @@ impl<T: Trait>
         threshold: Threshold<T::Signal>,
         duration: Option<T::BlockNumber>,
     ) -> Result<Self::VoteIdentifier, DispatchError> {
-        // calculate `initialized` and `expires` fields for vote state
-        let now = frame_system::Module::<T>::block_number();
-        let ends: Option<T::BlockNumber> = if let Some(time_to_add) = duration {
-            Some(now + time_to_add)
-        } else {
-            None
-        };
-        // generate new vote_id
-        let new_vote_id = Self::generate_unique_id();
-        // by default, this call mints signal based on weighted ownership in group
-        let total_possible_turnout = match organization {
-            OrgRep::Weighted(org_id) => {
-                Self::batch_mint_signal(new_vote_id, org_id)?
-            }
-            OrgRep::Equal(org_id) => {
-                Self::batch_mint_equal_signal(new_vote_id, org_id)?
-            }
-        };
-        ensure!(
-            Self::valid_signal_threshold(&threshold, total_possible_turnout),
-            Error::<T>::InputThresholdExceedsBounds
-        );
-        // instantiate new VoteState with threshold and temporal metadata
-        let new_vote_state =
-            VoteState::new(topic, total_possible_turnout, threshold, now, ends);
-        // insert the VoteState
-        <VoteStates<T>>::insert(new_vote_id, new_vote_state);
-        // increment open vote count
-        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
-        <OpenVoteCounter>::put(new_vote_count);
-        Ok(new_vote_id)
+        Self::open_signal_vote_of(
+            topic,
+            organization,
+            threshold,
+            None,
+            duration,
+        )
     }
     fn open_percent_vote(
         topic: Option<T::Cid>,
@@ -433,47 +2485,14 @@ impl<T: Trait>
         threshold: Threshold<Permill>,
         duration: Option<T::BlockNumber>,
     ) -> Result<Self::VoteIdentifier, DispatchError> {
-        // calculate `initialized` and `expires` fields for vote state
-        let now = frame_system::Module::<T>::block_number();
-        let ends: Option<T::BlockNumber> = if let Some(time_to_add) = duration {
-            Some(now + time_to_add)
-        } else {
-            None
-        };
-        // generate new vote_id
-        let new_vote_id = Self::generate_unique_id();
-        // by default, this call mints signal based on weighted ownership in group
-        let total_possible_turnout = match organization {
-            OrgRep::Weighted(org_id) => {
-                Self::batch_mint_signal(new_vote_id, org_id)?
-            }
-            OrgRep::Equal(org_id) => {
-                Self::batch_mint_equal_signal(new_vote_id, org_id)?
-            }
-        };
-        let signal_threshold =
-            Self::from_permill_to_signal(&threshold, total_possible_turnout);
-        ensure!(
-            Self::valid_signal_threshold(
-                &signal_threshold,
-                total_possible_turnout
-            ),
-            Error::<T>::InputThresholdExceedsBounds
-        );
-        // instantiate new VoteState with threshold and temporal metadata
-        let new_vote_state = VoteState::new(
+        Self::open_percent_vote_of(
             topic,
-            total_possible_turnout,
-            signal_threshold,
-            now,
-            ends,
-        );
-        // insert the VoteState
-        <VoteStates<T>>::insert(new_vote_id, new_vote_state);
-        // increment open vote count
-        let new_vote_count = <OpenVoteCounter>::get() + 1u32;
-        <OpenVoteCounter>::put(new_vote_count);
-        Ok(new_vote_id)
+            organization,
+            threshold,
+            PercentBase::TotalIssuance,
+            None,
+            duration,
+        )
     }
 }
 
@@ -485,7 +2504,30 @@ impl<T: Trait> UpdateVote<T::VoteId, T::Cid, T::BlockNumber> for Module<T> {
     ) -> DispatchResult {
         let old_vote_state = <VoteStates<T>>::get(vote_id)
             .ok_or(Error::<T>::CannotUpdateVoteIfVoteStateDNE)?;
+        // resetting the ballots would reopen the vote
+        ensure!(
+            old_vote_state.outcome() != VoteOutcome::Cancelled,
+            Error::<T>::VoteCancelled
+        );
+        ensure!(
+            !<ReportedOutcomes<T>>::contains_key(vote_id)
+                && !Self::check_vote_expired(&old_vote_state),
+            Error::<T>::VoteAlreadyClosed
+        );
         let new_vote_state = if clear_previous_vote_state {
+            // ballots cast for the old topic must not count for the new one
+            let ballots =
+                <VoteLogger<T>>::iter_prefix(vote_id)
+                    .collect::<Vec<(T::AccountId, VoteVec<T>)>>();
+            for (voter, ballot) in ballots {
+                <VoteLogger<T>>::insert(
+                    vote_id,
+                    voter,
+                    VoteVec::<T>::minted(ballot.magnitude()),
+                );
+            }
+            // voters get their free ballots for the new topic
+            <BallotCounts<T>>::remove_prefix(vote_id);
             old_vote_state.update_topic_and_clear_state(new_topic)
         } else {
             old_vote_state.update_topic_without_clearing_state(new_topic)
@@ -498,19 +2540,50 @@ impl<T: Trait> UpdateVote<T::VoteId, T::Cid, T::BlockNumber> for Module<T> {
         blocks_from_now: T::BlockNumber,
     ) -> DispatchResult {
         let now = <frame_system::Module<T>>::block_number();
-        let new_end_time = now + blocks_from_now;
+        let new_end_time = Self::vote_end(now, blocks_from_now)?;
         let pvs = <VoteStates<T>>::get(vote_id)
             .ok_or(Error::<T>::CannotUpdateVoteIfVoteStateDNE)?;
         if let Some(e) = pvs.ends() {
             if e < new_end_time {
                 let nvs = pvs.set_ends(new_end_time);
                 <VoteStates<T>>::insert(vote_id, nvs);
+                Self::schedule_expiry(vote_id, Some(new_end_time));
             }
         }
         Ok(())
     }
 }
 
+/// The vote pallet's storage behind its `SignalBank`, ballots are kept in
+/// `VoteLogger` and totals in `TotalSignalIssuance`
+pub struct VoteSignal<T>(PhantomData<T>);
+
+impl<T: Trait> SignalStore for VoteSignal<T> {
+    type VoteId = T::VoteId;
+    type AccountId = T::AccountId;
+    type Signal = T::Signal;
+    type Ballot = VoteVec<T>;
+
+    fn ballot(vote_id: T::VoteId, who: &T::AccountId) -> Option<VoteVec<T>> {
+        <VoteLogger<T>>::get(vote_id, who)
+    }
+    fn put_ballot(vote_id: T::VoteId, who: &T::AccountId, ballot: VoteVec<T>) {
+        <VoteLogger<T>>::insert(vote_id, who, ballot);
+    }
+    fn remove_ballots(vote_id: T::VoteId) {
+        <VoteLogger<T>>::remove_prefix(vote_id);
+    }
+    fn total(vote_id: T::VoteId) -> Option<T::Signal> {
+        <TotalSignalIssuance<T>>::get(vote_id)
+    }
+    fn put_total(vote_id: T::VoteId, total: T::Signal) {
+        <TotalSignalIssuance<T>>::insert(vote_id, total);
+    }
+    fn remove_total(vote_id: T::VoteId) {
+        <TotalSignalIssuance<T>>::remove(vote_id);
+    }
+}
+
 impl<T: Trait> MintableSignal<T::AccountId, T::OrgId, T::VoteId, T::Signal>
     for Module<T>
 {
@@ -522,16 +2595,11 @@ impl<T: Trait> MintableSignal<T::AccountId, T::OrgId, T::VoteId, T::Signal>
     ) -> Result<T::Signal, DispatchError> {
         let new_vote_group = <org::Module<T>>::get_group(organization)
             .ok_or(Error::<T>::CannotMintSignalBecauseGroupMembershipDNE)?;
+        ensure!(!new_vote_group.0.is_empty(), Error::<T>::EmptyOrgCannotVote);
         // 1 person 1 vote despite any weightings in org
-        let total_minted: T::Signal = (new_vote_group.0.len() as u32).into();
-        new_vote_group.0.into_iter().for_each(|who| {
-            let minted_signal: T::Signal = 1u32.into();
-            let new_vote =
-                Vote::new(minted_signal, VoterView::Uninitialized, None);
-            <VoteLogger<T>>::insert(vote_id, who, new_vote);
-        });
-        <TotalSignalIssuance<T>>::insert(vote_id, total_minted);
-        Ok(total_minted)
+        let voters = Self::equal_voters(organization, new_vote_group.0);
+        let holders = voters.into_iter().map(|who| (who, 1u32.into()));
+        Ok(SignalBank::<VoteSignal<T>>::mint_batch(vote_id, holders))
     }
     /// Mints signal based on weighted membership of the group
     fn batch_mint_signal(
@@ -543,21 +2611,21 @@ impl<T: Trait> MintableSignal<T::AccountId, T::OrgId, T::VoteId, T::Signal>
                 .ok_or(Error::<T>::CannotMintSignalBecauseMembershipShapeDNE)?;
         // total issuance
         let total_minted: T::Signal = new_vote_group.total().into();
-        new_vote_group.vec().into_iter().for_each(|(who, shares)| {
-            let minted_signal: T::Signal = shares.into();
-            let new_vote =
-                Vote::new(minted_signal, VoterView::Uninitialized, None);
-            <VoteLogger<T>>::insert(vote_id, who, new_vote);
-        });
-        <TotalSignalIssuance<T>>::insert(vote_id, total_minted);
-        Ok(total_minted)
+        ensure!(!total_minted.is_zero(), Error::<T>::EmptyOrgCannotVote);
+        // members without shares are minted nothing, they could only cast
+        // ballots of no weight
+        let holders = new_vote_group
+            .vec()
+            .into_iter()
+            .map(|(who, shares)| (who, shares.into()));
+        Ok(SignalBank::<VoteSignal<T>>::mint_batch(vote_id, holders))
     }
 }
 
 impl<T: Trait> ApplyVote<T::Cid> for Module<T> {
     type Signal = T::Signal;
     type Direction = VoterView;
-    type Vote = Vote<T::Signal, T::Cid>;
+    type Vote = Vote<T::Signal, T::Cid, T::BlockNumber>;
     type State = VoteState<T::Signal, T::BlockNumber, T::Cid>;
 
     fn apply_vote(
@@ -587,21 +2655,37 @@ impl<T: Trait> VoteOnProposal<T::AccountId, T::VoteId, T::Cid> for Module<T> {
         direction: Self::Direction,
         justification: Option<T::Cid>,
     ) -> DispatchResult {
-        // get the vote state
-        let vote_state = <VoteStates<T>>::get(vote_id)
-            .ok_or(Error::<T>::NoVoteStateForVoteRequest)?;
-        // TODO: add permissioned method for adding time to the vote state because of this restriction but this is a legitimate restriction
-        // -> every standard vote has a recognized end to establish when the decision must be made based on collected input
+        let (vote_state, old_vote) = Self::open_ballot(vote_id, &voter)?;
+        // the first ballot of a linked account takes over the set's signal
+        let linked_holder = if <VoteLogger<T>>::contains_key(vote_id, &voter) {
+            None
+        } else {
+            Self::linked_ballot(vote_id, &voter)?.map(|(holder, _)| holder)
+        };
+        let now = frame_system::Module::<T>::block_number();
         ensure!(
-            !Self::check_vote_expired(&vote_state),
-            Error::<T>::VotePastExpirationTimeSoVotesNotAccepted
+            old_vote.direction() != VoterView::Uninitialized
+                || vote_state.accepts_new_ballots(now),
+            Error::<T>::NewBallotsClosedOnlyChangesAllowed
         );
-        // get the organization associated with this vote_state
-        let old_vote = <VoteLogger<T>>::get(vote_id, voter.clone())
-            .ok_or(Error::<T>::SignalNotMintedForVoter)?;
-        let new_vote = old_vote.set_new_view(direction, justification).ok_or(
-            Error::<T>::OldVoteDirectionEqualsNewVoteDirectionSoNoChange,
-        )?;
+        // the first ballot in a standing vote adds the voter's signal to it
+        let standing_ballots = if <StandingVotes<T>>::get(vote_id)
+            && !<VoteLogger<T>>::contains_key(vote_id, &voter)
+        {
+            Some(Self::with_standing_ballot(vote_id, &voter)?)
+        } else {
+            None
+        };
+        let vote_state = if standing_ballots.is_some() {
+            vote_state.add_possible_turnout(old_vote.magnitude())
+        } else {
+            vote_state
+        };
+        let new_vote = old_vote
+            .set_new_view(direction, justification.clone(), now)
+            .ok_or(
+                Error::<T>::OldVoteDirectionEqualsNewVoteDirectionSoNoChange,
+            )?;
         let new_state = Self::apply_vote(
             vote_state,
             old_vote.magnitude(),
@@ -609,10 +2693,56 @@ impl<T: Trait> VoteOnProposal<T::AccountId, T::VoteId, T::Cid> for Module<T> {
             direction,
         )
         .ok_or(Error::<T>::VoteChangeNotSupported)?;
+        Self::record_ballot(vote_id, &voter, direction, justification);
+        if let Some(ballots) = standing_ballots {
+            <StandingBallots<T>>::insert(&voter, ballots);
+        }
+        if let Some(holder) = linked_holder {
+            <VoteLogger<T>>::remove(vote_id, holder);
+        }
         // set the new vote for the voter's profile
-        <VoteLogger<T>>::insert(vote_id, voter, new_vote);
+        <VoteLogger<T>>::insert(vote_id, &voter, new_vote);
         // commit new vote state to storage
-        <VoteStates<T>>::insert(vote_id, new_state);
+        <VoteStates<T>>::insert(vote_id, new_state.clone());
+        Self::report_if_decided(vote_id, &new_state);
+        Self::extend_if_late(vote_id, new_state, now);
+        // voting keeps the voter's shares from decaying, last because the
+        // decay re-weighs the standing ballots stored above
+        if let Some(organization) = <VoteOrgs<T>>::get(vote_id) {
+            <org::Module<T>>::record_activity(organization.org(), &voter);
+        }
         Ok(())
     }
 }
+
+impl<T: Trait> OrgLifecycleHandler<T::OrgId> for Module<T> {
+    fn on_lifecycle_changed(org: T::OrgId, lifecycle: OrgLifecycle) {
+        if lifecycle.is_active() {
+            return
+        }
+        <FrozenOrgSweeps<T>>::mutate(|queue| {
+            if !queue.contains(&org) {
+                queue.push(org);
+            }
+        });
+    }
+}
+
+impl<T: Trait> ShareChangeHandler<T::OrgId, T::AccountId> for Module<T> {
+    fn on_shares_changed(org: T::OrgId, who: &T::AccountId) {
+        let ballots = <StandingBallots<T>>::get(who);
+        if ballots.is_empty() {
+            return
+        }
+        let tracked = ballots
+            .iter()
+            .copied()
+            .filter(|vote_id| Self::reweigh_standing_ballot(*vote_id, org, who))
+            .collect::<Vec<_>>();
+        if tracked.is_empty() {
+            <StandingBallots<T>>::remove(who);
+        } else if tracked.len() != ballots.len() {
+            <StandingBallots<T>>::insert(who, tracked);
+        }
+    }
+}
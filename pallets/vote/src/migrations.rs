@@ -0,0 +1,158 @@
+//! Storage migrations run by `on_runtime_upgrade`
+//!
+//! A change to the layout of stored values bumps `STORAGE_VERSION` and
+//! keeps the previous layout in `util` next to the current one. A map can
+//! only be written in its current layout, so each map is translated once,
+//! from the layout of the stored version straight to the current one, and a
+//! chain several versions behind catches up in one upgrade. Each map is
+//! translated in storage key order, which is the same on every node.
+//!
+//! With the `try-runtime` feature the storage is checked before and after
+//! the migration and the upgrade panics if a value was lost.
+use crate::{
+    Releases,
+    StorageVersion,
+    Trait,
+    VoteLogger,
+    VoteStates,
+    STORAGE_VERSION,
+};
+use frame_support::{
+    storage::{
+        IterableStorageDoubleMap,
+        IterableStorageMap,
+        StorageValue,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use frame_system::Trait as System;
+use org::Trait as Org;
+use sp_std::cell::Cell;
+use util::vote::{
+    VoteStateV1,
+    VoteStateV2,
+    VoteStateV3,
+    VoteV1,
+};
+
+type VoteStV1<T> = VoteStateV1<
+    <T as Trait>::Signal,
+    <T as System>::BlockNumber,
+    <T as Org>::Cid,
+>;
+
+type VoteStV2<T> = VoteStateV2<
+    <T as Trait>::Signal,
+    <T as System>::BlockNumber,
+    <T as Org>::Cid,
+>;
+
+type VoteStV3<T> = VoteStateV3<
+    <T as Trait>::Signal,
+    <T as System>::BlockNumber,
+    <T as Org>::Cid,
+>;
+
+type VoteVecV1<T> = VoteV1<<T as Trait>::Signal, <T as Org>::Cid>;
+
+/// Migrates the storage from the stored version to `STORAGE_VERSION`
+pub fn migrate<T: Trait>() -> Weight {
+    #[cfg(feature = "try-runtime")]
+    let counts = pre_migrate::<T>();
+    let version = StorageVersion::get();
+    let mut weight = T::DbWeight::get().reads(1);
+    if version < STORAGE_VERSION {
+        weight = weight.saturating_add(migrate_votes::<T>(version));
+        if version < Releases::V4 {
+            weight = weight.saturating_add(migrate_ballots::<T>());
+        }
+        StorageVersion::put(STORAGE_VERSION);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    #[cfg(feature = "try-runtime")]
+    post_migrate::<T>(counts);
+    weight
+}
+
+/// Translates the votes from the layout of `version`. Votes stored before
+/// the tie break get the default one, votes stored before the change only
+/// window accept new ballots until they end and votes stored before the
+/// votes cast threshold are of the total issuance.
+fn migrate_votes<T: Trait>(version: Releases) -> Weight {
+    let votes = Cell::new(0);
+    let count = || votes.set(votes.get() + 1);
+    match version {
+        Releases::V1 => {
+            <VoteStates<T>>::translate::<VoteStV1<T>, _>(|_, old| {
+                count();
+                Some(old.into())
+            })
+        }
+        Releases::V2 => {
+            <VoteStates<T>>::translate::<VoteStV2<T>, _>(|_, old| {
+                count();
+                Some(old.into())
+            })
+        }
+        _ => {
+            <VoteStates<T>>::translate::<VoteStV3<T>, _>(|_, old| {
+                count();
+                Some(old.into())
+            })
+        }
+    }
+    T::DbWeight::get().reads_writes(votes.get(), votes.get())
+}
+
+/// Gives the ballots stored before their blocks were recorded block zero,
+/// the blocks they were cast at are not known
+fn migrate_ballots<T: Trait>() -> Weight {
+    let ballots = Cell::new(0);
+    <VoteLogger<T>>::translate::<VoteVecV1<T>, _>(|_, _, old| {
+        ballots.set(ballots.get() + 1);
+        Some(old.into())
+    });
+    T::DbWeight::get().reads_writes(ballots.get(), ballots.get())
+}
+
+/// The number of votes and of ballots in the layout of the stored version
+#[cfg(feature = "try-runtime")]
+fn pre_migrate<T: Trait>() -> (usize, usize) {
+    use frame_support::storage::migration::StorageIterator;
+    let votes = match StorageVersion::get() {
+        Releases::V1 => {
+            StorageIterator::<VoteStV1<T>>::new(b"Vote", b"VoteStates").count()
+        }
+        Releases::V2 => {
+            StorageIterator::<VoteStV2<T>>::new(b"Vote", b"VoteStates").count()
+        }
+        Releases::V3 | Releases::V4 => {
+            StorageIterator::<VoteStV3<T>>::new(b"Vote", b"VoteStates").count()
+        }
+        _ => <VoteStates<T>>::iter().count(),
+    };
+    let ballots = if StorageVersion::get() < Releases::V4 {
+        StorageIterator::<VoteVecV1<T>>::new(b"Vote", b"VoteLogger").count()
+    } else {
+        <VoteLogger<T>>::iter().count()
+    };
+    (votes, ballots)
+}
+
+/// Panics unless the storage is at the latest version and every vote and
+/// ballot still decodes
+#[cfg(feature = "try-runtime")]
+fn post_migrate<T: Trait>((votes, ballots): (usize, usize)) {
+    assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+    assert_eq!(
+        <VoteStates<T>>::iter().count(),
+        votes,
+        "votes were lost in the migration"
+    );
+    assert_eq!(
+        <VoteLogger<T>>::iter().count(),
+        ballots,
+        "ballots were lost in the migration"
+    );
+}
@@ -2,7 +2,11 @@ use parity_scale_codec::{
     Decode,
     Encode,
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::Saturating,
+    Permill,
+    RuntimeDebug,
+};
 use sp_std::prelude::*;
 
 #[derive(new, PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
@@ -80,36 +84,104 @@ impl<
 }
 
 #[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct BountyInformation<BountyId, IpfsReference, AccountId, Currency> {
+pub struct BountyInformation<
+    BountyId,
+    IpfsReference,
+    AccountId,
+    AssetId,
+    Currency,
+    BlockNumber,
+> {
     // Bounty identifier (pre-hash key for storage value)
     id: BountyId,
     // Storage cid
     info: IpfsReference,
     // Whoever posts the bounty
     depositer: AccountId,
+    // Accounts allowed to review submissions, always includes at least one
+    admins: Vec<AccountId>,
+    // The asset the bounty is funded in, None for the native currency
+    asset: Option<AssetId>,
     // Total amount
     total: Currency,
+    // The block the bounty was posted at
+    posted_at: BlockNumber,
+    // The block of the first submission
+    #[new(default)]
+    first_submission_at: Option<BlockNumber>,
+    // The block a submission was first approved and paid at
+    #[new(default)]
+    resolved_at: Option<BlockNumber>,
+    // False for bounties posted before the blocks were tracked, whose blocks
+    // are zero or missing
+    #[new(value = "true")]
+    timestamped: bool,
 }
 
 impl<
         BountyId: Copy,
         IpfsReference: Clone,
-        AccountId: Clone,
+        AccountId: Clone + PartialEq,
+        AssetId: Copy,
         Currency: Copy
             + PartialOrd
             + sp_std::ops::Sub<Output = Currency>
             + sp_std::ops::Add<Output = Currency>,
-    > BountyInformation<BountyId, IpfsReference, AccountId, Currency>
+        BlockNumber: Copy,
+    >
+    BountyInformation<
+        BountyId,
+        IpfsReference,
+        AccountId,
+        AssetId,
+        Currency,
+        BlockNumber,
+    >
 {
     pub fn id(&self) -> BountyId {
         self.id
     }
+    pub fn asset(&self) -> Option<AssetId> {
+        self.asset
+    }
     pub fn info(&self) -> IpfsReference {
         self.info.clone()
     }
     pub fn depositer(&self) -> AccountId {
         self.depositer.clone()
     }
+    pub fn admins(&self) -> Vec<AccountId> {
+        self.admins.clone()
+    }
+    pub fn is_admin(&self, who: &AccountId) -> bool {
+        self.admins.contains(who)
+    }
+    /// Returns None if `who` already is an admin
+    pub fn add_admin(&self, who: AccountId) -> Option<Self> {
+        if self.is_admin(&who) {
+            None
+        } else {
+            let mut admins = self.admins.clone();
+            admins.push(who);
+            Some(BountyInformation {
+                admins,
+                ..self.clone()
+            })
+        }
+    }
+    /// Returns None if `who` is not an admin
+    pub fn remove_admin(&self, who: &AccountId) -> Option<Self> {
+        if self.is_admin(who) {
+            let admins =
+                self.admins.iter().filter(|a| *a != who).cloned().collect();
+            Some(BountyInformation {
+                admins,
+                ..self.clone()
+            })
+        } else {
+            None
+        }
+    }
     pub fn total(&self) -> Currency {
         self.total
     }
@@ -125,6 +197,180 @@ impl<
             ..self.clone()
         }
     }
+    pub fn posted_at(&self) -> BlockNumber {
+        self.posted_at
+    }
+    pub fn first_submission_at(&self) -> Option<BlockNumber> {
+        self.first_submission_at
+    }
+    pub fn resolved_at(&self) -> Option<BlockNumber> {
+        self.resolved_at
+    }
+    /// Whether the blocks were tracked since the bounty was posted
+    pub fn timestamped(&self) -> bool {
+        self.timestamped
+    }
+    /// Records a submission at `at` unless there was one before
+    pub fn submitted(&self, at: BlockNumber) -> Self {
+        BountyInformation {
+            first_submission_at: self.first_submission_at.or(Some(at)),
+            ..self.clone()
+        }
+    }
+    /// Records a payout at `at` unless there was one before
+    pub fn resolved(&self, at: BlockNumber) -> Self {
+        BountyInformation {
+            resolved_at: self.resolved_at.or(Some(at)),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// The layout of `BountyInformation` before the blocks of its lifecycle were
+/// tracked, kept to migrate the bounties stored with it
+pub struct BountyInformationV1<
+    BountyId,
+    IpfsReference,
+    AccountId,
+    AssetId,
+    Currency,
+> {
+    id: BountyId,
+    info: IpfsReference,
+    depositer: AccountId,
+    admins: Vec<AccountId>,
+    asset: Option<AssetId>,
+    total: Currency,
+}
+
+impl<BountyId, IpfsReference, AccountId, AssetId, Currency, BlockNumber>
+    From<
+        BountyInformationV1<
+            BountyId,
+            IpfsReference,
+            AccountId,
+            AssetId,
+            Currency,
+        >,
+    >
+    for BountyInformation<
+        BountyId,
+        IpfsReference,
+        AccountId,
+        AssetId,
+        Currency,
+        BlockNumber,
+    >
+where
+    BlockNumber: Default,
+{
+    fn from(
+        old: BountyInformationV1<
+            BountyId,
+            IpfsReference,
+            AccountId,
+            AssetId,
+            Currency,
+        >,
+    ) -> Self {
+        BountyInformation {
+            id: old.id,
+            info: old.info,
+            depositer: old.depositer,
+            admins: old.admins,
+            asset: old.asset,
+            total: old.total,
+            posted_at: BlockNumber::default(),
+            first_submission_at: None,
+            resolved_at: None,
+            timestamped: false,
+        }
+    }
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// What clients need to display amounts of an asset accepted for bounties
+pub struct AssetMetadata {
+    /// Ticker, utf8 encoded
+    symbol: Vec<u8>,
+    /// Number of decimals of one unit of the asset
+    decimals: u8,
+}
+
+impl AssetMetadata {
+    pub fn symbol(&self) -> Vec<u8> {
+        self.symbol.clone()
+    }
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+}
+
+/// A category of bounties, lowercase ASCII padded with zeros to 16 bytes
+pub type BountyTag = [u8; 16];
+
+/// The tag named `name`, if it is 1 to 16 lowercase ASCII letters, digits
+/// or dashes
+pub fn bounty_tag(name: &str) -> Option<BountyTag> {
+    let mut tag = BountyTag::default();
+    if name.len() > tag.len() {
+        return None
+    }
+    tag[..name.len()].copy_from_slice(name.as_bytes());
+    if is_valid_tag(&tag) {
+        Some(tag)
+    } else {
+        None
+    }
+}
+
+/// Whether the tag is a name accepted by `bounty_tag` padded with zeros
+pub fn is_valid_tag(tag: &BountyTag) -> bool {
+    let len = tag_len(tag);
+    let valid =
+        |b: &u8| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-';
+    len > 0
+        && tag[..len].iter().all(valid)
+        && tag[len..].iter().all(|b| *b == 0)
+}
+
+/// The name of a valid tag
+pub fn tag_name(tag: &BountyTag) -> &str {
+    core::str::from_utf8(&tag[..tag_len(tag)]).unwrap_or_default()
+}
+
+fn tag_len(tag: &BountyTag) -> usize {
+    tag.iter()
+        .position(|b| *b == 0)
+        .unwrap_or_else(|| tag.len())
+}
+
+/// The longest GitHub login
+pub const MAX_GITHUB_HANDLE_LEN: usize = 39;
+
+/// The handle of the GitHub login `login`, lowercased because GitHub
+/// ignores case in logins, if it is a valid login
+pub fn github_handle(login: &str) -> Option<Vec<u8>> {
+    let handle = login.to_ascii_lowercase().into_bytes();
+    if is_valid_github_handle(&handle) {
+        Some(handle)
+    } else {
+        None
+    }
+}
+
+/// Whether the handle is 1 to 39 lowercase ASCII letters, digits or single
+/// dashes, neither starting nor ending with a dash
+pub fn is_valid_github_handle(handle: &[u8]) -> bool {
+    let valid =
+        |b: &u8| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-';
+    !handle.is_empty()
+        && handle.len() <= MAX_GITHUB_HANDLE_LEN
+        && handle.iter().all(valid)
+        && handle.first() != Some(&b'-')
+        && handle.last() != Some(&b'-')
+        && !handle.windows(2).any(|w| w == b"--")
 }
 
 #[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
@@ -188,6 +434,121 @@ impl SubmissionState {
     }
 }
 
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// The kind of reference a depositer expects submissions to link, a hint
+/// which is validated by clients rather than the runtime
+pub enum SubmissionKind {
+    Any,
+    Issue,
+    PullRequest,
+}
+
+impl Default for SubmissionKind {
+    fn default() -> SubmissionKind {
+        SubmissionKind::Any
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// Why the depositer rejected a submission, which decides the fate of its deposit
+pub enum RejectionReason {
+    /// Made in good faith but not accepted, the deposit is refunded
+    Unsuitable,
+    /// Junk submission, the deposit is slashed to the bounty
+    Spam,
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// A rejection disputed by its submitter, decided by a vote of the org
+/// arbitrating the bounty
+pub struct RejectionDispute<IpfsReference, Currency, VoteId> {
+    // Why the submitter thinks the rejection was wrong, the vote topic
+    argument: IpfsReference,
+    // Reserved from the submitter until the vote is decided
+    deposit: Currency,
+    vote: VoteId,
+}
+
+impl<IpfsReference: Clone, Currency: Copy, VoteId: Copy>
+    RejectionDispute<IpfsReference, Currency, VoteId>
+{
+    pub fn argument(&self) -> IpfsReference {
+        self.argument.clone()
+    }
+    pub fn deposit(&self) -> Currency {
+        self.deposit
+    }
+    pub fn vote(&self) -> VoteId {
+        self.vote
+    }
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// An org matching the contributions to a bounty out of its treasury
+pub struct MatchingProgram<OrgId, Currency> {
+    org: OrgId,
+    // Share of each contribution the treasury adds to the bounty
+    ratio: Permill,
+    // What the treasury still adds before the program ends
+    remaining: Currency,
+}
+
+impl<OrgId: Copy, Currency: Copy + sp_std::ops::Sub<Output = Currency>>
+    MatchingProgram<OrgId, Currency>
+{
+    pub fn org(&self) -> OrgId {
+        self.org
+    }
+    pub fn ratio(&self) -> Permill {
+        self.ratio
+    }
+    pub fn remaining(&self) -> Currency {
+        self.remaining
+    }
+    /// Spends `matched` of what remains, callers cap it at `remaining`
+    pub fn spend(&self, matched: Currency) -> Self {
+        Self {
+            remaining: self.remaining - matched,
+            ..*self
+        }
+    }
+}
+
+#[derive(new, PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// How long submissions to a bounty await review and what becomes of those
+/// awaiting it longer
+pub struct ReviewPolicy<BlockNumber, Currency> {
+    // Blocks after a submission from which anyone may time it out
+    timeout: BlockNumber,
+    // The most a timed out submission is approved for, none rejects them all
+    optimistic_cap: Option<Currency>,
+}
+
+impl<BlockNumber: Copy + Saturating, Currency: Copy>
+    ReviewPolicy<BlockNumber, Currency>
+{
+    pub fn timeout(&self) -> BlockNumber {
+        self.timeout
+    }
+    pub fn optimistic_cap(&self) -> Option<Currency> {
+        self.optimistic_cap
+    }
+    /// The first block a submission made at `submitted_at` may be timed out
+    pub fn times_out_at(&self, submitted_at: BlockNumber) -> BlockNumber {
+        submitted_at.saturating_add(self.timeout)
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// What became of a submission left awaiting review past its timeout
+pub enum TimeoutOutcome<Currency> {
+    /// Rejected, the deposit is refunded
+    Rejected,
+    /// Approved by a bounty which opted into optimistic approval, paying
+    /// the amount requested
+    Approved(Currency),
+}
+
 #[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
 /// Submission state for Bounty2
 pub enum SubmissionState2<BlockNumber, VoteId> {
@@ -230,6 +591,7 @@ pub struct BountySubmission<
     AccountId,
     Currency,
     State,
+    BlockNumber,
 > {
     /// The identifiers for the parent bounty and this storage item
     id: (BountyId, SubmissionId),
@@ -241,6 +603,11 @@ pub struct BountySubmission<
     amount: Currency,
     /// State of the application
     state: State,
+    /// The block the submission was made at
+    submitted_at: BlockNumber,
+    /// False for submissions made before the block was tracked, whose block
+    /// is zero
+    timestamped: bool,
 }
 
 impl<
@@ -250,6 +617,7 @@ impl<
         AccountId: Clone + PartialEq,
         Currency: Copy + PartialOrd + sp_std::ops::Sub<Output = Currency>,
         State: Copy + Default,
+        BlockNumber: Copy,
     >
     BountySubmission<
         BountyId,
@@ -258,6 +626,7 @@ impl<
         AccountId,
         Currency,
         State,
+        BlockNumber,
     >
 {
     pub fn new(
@@ -266,6 +635,7 @@ impl<
         submission_ref: IpfsReference,
         submitter: AccountId,
         amount: Currency,
+        submitted_at: BlockNumber,
     ) -> BountySubmission<
         BountyId,
         SubmissionId,
@@ -273,6 +643,7 @@ impl<
         AccountId,
         Currency,
         State,
+        BlockNumber,
     > {
         BountySubmission {
             id: (bounty, submission),
@@ -280,6 +651,8 @@ impl<
             submitter,
             amount,
             state: State::default(),
+            submitted_at,
+            timestamped: true,
         }
     }
     pub fn bounty_id(&self) -> BountyId {
@@ -310,4 +683,82 @@ impl<
     pub fn state(&self) -> State {
         self.state
     }
+    pub fn submitted_at(&self) -> BlockNumber {
+        self.submitted_at
+    }
+    /// Whether the block was tracked when the submission was made
+    pub fn timestamped(&self) -> bool {
+        self.timestamped
+    }
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// The layout of `BountySubmission` before the block of the submission was
+/// tracked, kept to migrate the submissions stored with it
+pub struct BountySubmissionV1<
+    BountyId,
+    SubmissionId,
+    IpfsReference,
+    AccountId,
+    Currency,
+    State,
+> {
+    id: (BountyId, SubmissionId),
+    submission_ref: IpfsReference,
+    submitter: AccountId,
+    amount: Currency,
+    state: State,
+}
+
+impl<
+        BountyId,
+        SubmissionId,
+        IpfsReference,
+        AccountId,
+        Currency,
+        State,
+        BlockNumber,
+    >
+    From<
+        BountySubmissionV1<
+            BountyId,
+            SubmissionId,
+            IpfsReference,
+            AccountId,
+            Currency,
+            State,
+        >,
+    >
+    for BountySubmission<
+        BountyId,
+        SubmissionId,
+        IpfsReference,
+        AccountId,
+        Currency,
+        State,
+        BlockNumber,
+    >
+where
+    BlockNumber: Default,
+{
+    fn from(
+        old: BountySubmissionV1<
+            BountyId,
+            SubmissionId,
+            IpfsReference,
+            AccountId,
+            Currency,
+            State,
+        >,
+    ) -> Self {
+        BountySubmission {
+            id: old.id,
+            submission_ref: old.submission_ref,
+            submitter: old.submitter,
+            amount: old.amount,
+            state: old.state,
+            submitted_at: BlockNumber::default(),
+            timestamped: false,
+        }
+    }
 }
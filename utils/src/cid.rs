@@ -0,0 +1,139 @@
+//! Checks of the content identifiers accepted from extrinsics
+//!
+//! A cid is either a CIDv0, the 34 bytes of a sha2-256 multihash, or a
+//! CIDv1: the varint version 1, the varint multicodec of the content and a
+//! multihash, which is the varint code of its hash function, the varint
+//! length of the digest and exactly that many digest bytes. Varints are
+//! unsigned LEB128 of at most 9 bytes without trailing zero bytes. Cids
+//! longer than `MAX_CID_LEN` are rejected so that they can't bloat storage.
+use crate::traits::CidFormat;
+use parity_scale_codec::Decode;
+use sp_std::prelude::*;
+
+/// The longest cid accepted, enough for a CIDv1 with a 64 byte digest
+pub const MAX_CID_LEN: usize = 96;
+
+const SHA2_256: u8 = 0x12;
+const CIDV0_LEN: usize = 34;
+const MAX_VARINT_LEN: usize = 9;
+
+/// Reads a varint off the front of `bytes`
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            // a trailing zero byte would encode the same value again
+            if i > 0 && *byte == 0 {
+                return None
+            }
+            *bytes = &bytes[i + 1..];
+            return Some(value)
+        }
+    }
+    None
+}
+
+/// Whether `bytes` are a CIDv0 or CIDv1 of at most `MAX_CID_LEN` bytes
+pub fn is_valid_cid(bytes: &[u8]) -> bool {
+    if bytes.len() > MAX_CID_LEN {
+        return false
+    }
+    if bytes.len() == CIDV0_LEN && bytes[0] == SHA2_256 {
+        return bytes[1] == 32
+    }
+    let mut rest = bytes;
+    let mut cidv1 = || {
+        if read_varint(&mut rest)? != 1 {
+            return None
+        }
+        let _codec = read_varint(&mut rest)?;
+        let _hash = read_varint(&mut rest)?;
+        let len = read_varint(&mut rest)?;
+        Some(rest.len() as u64 == len)
+    };
+    cidv1().unwrap_or(false)
+}
+
+impl CidFormat for () {
+    fn is_valid(_: &[u8]) -> bool {
+        true
+    }
+}
+
+/// Cids which encode as their bytes, like `sunshine_codec::Cid`
+pub struct ScaleBytesCid;
+
+impl CidFormat for ScaleBytesCid {
+    fn is_valid(encoded: &[u8]) -> bool {
+        let mut input = encoded;
+        match Vec::<u8>::decode(&mut input) {
+            Ok(bytes) => input.is_empty() && is_valid_cid(&bytes),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+
+    fn cidv1(codec: &[u8], hash: u8, digest: &[u8]) -> Vec<u8> {
+        let mut cid = vec![1];
+        cid.extend_from_slice(codec);
+        cid.push(hash);
+        cid.push(digest.len() as u8);
+        cid.extend_from_slice(digest);
+        cid
+    }
+
+    #[test]
+    fn well_formed_cids_are_valid() {
+        let mut cidv0 = vec![0x12, 0x20];
+        cidv0.extend_from_slice(&[7; 32]);
+        assert!(is_valid_cid(&cidv0));
+        // dag-cbor with a blake2b-256 digest, both codes take two bytes
+        let mut dag_cbor = vec![1, 0x71, 0xa0, 0xe4, 0x02, 0x20];
+        dag_cbor.extend_from_slice(&[7; 32]);
+        assert!(is_valid_cid(&dag_cbor));
+        assert!(is_valid_cid(&cidv1(&[0x55], 0x12, &[7; 32])));
+        // the identity hash may be empty
+        assert!(is_valid_cid(&cidv1(&[0x55], 0x00, &[])));
+        assert!(is_valid_cid(&cidv1(&[0x55], 0x13, &[7; 64])));
+    }
+
+    #[test]
+    fn malformed_cids_are_invalid() {
+        let valid = cidv1(&[0x71], 0x12, &[7; 32]);
+        for cid in vec![
+            vec![],
+            vec![0xff; 4],
+            // a CIDv0 with the wrong digest length
+            [&[0x12, 0x21][..], &[7; 32][..]].concat(),
+            // an unknown version
+            [&[2][..], &valid[1..]].concat(),
+            // digests shorter and longer than their length
+            valid[..valid.len() - 1].to_vec(),
+            [&valid[..], &[7][..]].concat(),
+            // a varint which does not end
+            vec![1, 0x80, 0x80],
+            // a varint with a trailing zero byte
+            [&[1, 0xf1, 0x00][..], &valid[2..]].concat(),
+            cidv1(&[0x55], 0x12, &[7; MAX_CID_LEN]),
+        ] {
+            assert!(!is_valid_cid(&cid), "{:?}", cid);
+        }
+    }
+
+    #[test]
+    fn scale_encoded_cids_are_checked_as_bytes() {
+        let valid = cidv1(&[0x71], 0x12, &[7; 32]);
+        assert!(ScaleBytesCid::is_valid(&valid.encode()));
+        assert!(!ScaleBytesCid::is_valid(&valid));
+        let trailing = [&valid.encode()[..], &[0][..]].concat();
+        assert!(!ScaleBytesCid::is_valid(&trailing));
+        assert!(!ScaleBytesCid::is_valid(&vec![0xffu8; 4].encode()));
+        assert!(<()>::is_valid(&[0xff; 4]));
+    }
+}
@@ -0,0 +1,81 @@
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use sp_runtime::traits::{
+    Saturating,
+    Zero,
+};
+
+#[derive(
+    PartialEq, Eq, Copy, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// Pushes a deadline back when something arrives in its final `window`
+/// blocks so others have as long to respond, by at most `max_extension`
+/// blocks in total
+pub struct DeadlineExtension<BlockNumber> {
+    /// The final blocks up to and including the deadline which extend it,
+    /// also the number of blocks each extension adds
+    window: BlockNumber,
+    /// The most all extensions add to the deadline together
+    max_extension: BlockNumber,
+    /// The blocks added to the deadline so far
+    extended: BlockNumber,
+}
+
+impl<BlockNumber: Copy> DeadlineExtension<BlockNumber> {
+    pub fn window(&self) -> BlockNumber {
+        self.window
+    }
+    pub fn max_extension(&self) -> BlockNumber {
+        self.max_extension
+    }
+    pub fn extended(&self) -> BlockNumber {
+        self.extended
+    }
+}
+
+impl<BlockNumber: Copy + PartialOrd + Saturating + Zero>
+    DeadlineExtension<BlockNumber>
+{
+    pub fn new(window: BlockNumber, max_extension: BlockNumber) -> Self {
+        Self {
+            window,
+            max_extension,
+            extended: Zero::zero(),
+        }
+    }
+    /// Whether something arriving at `now` is in the final `window` blocks
+    /// of `deadline`
+    pub fn is_late(&self, deadline: BlockNumber, now: BlockNumber) -> bool {
+        now <= deadline && now.saturating_add(self.window) > deadline
+    }
+    /// The new deadline and the extension recording it if something arrived
+    /// late at `now`, the last extension is cut short at `max_extension`
+    /// and None is returned once it is used up
+    pub fn extend(
+        self,
+        deadline: BlockNumber,
+        now: BlockNumber,
+    ) -> Option<(BlockNumber, Self)> {
+        if !self.is_late(deadline, now) {
+            return None
+        }
+        let left = self.max_extension.saturating_sub(self.extended);
+        let by = if left < self.window {
+            left
+        } else {
+            self.window
+        };
+        if by.is_zero() {
+            return None
+        }
+        Some((
+            deadline.saturating_add(by),
+            Self {
+                extended: self.extended.saturating_add(by),
+                ..self
+            },
+        ))
+    }
+}
@@ -0,0 +1,160 @@
+//! Per block housekeeping within a budget shared by the pallets
+//!
+//! Sweeping expired votes, processing bounty deadlines and releasing refund
+//! batches all want some of every block. Before running its due tasks a
+//! pallet reserves them from its `HousekeepingMeter`, which hands out what
+//! is left of the budget the runtime set aside for the block, and carries
+//! the tasks that did not fit over to the next block. Together the pallets
+//! never take more than the budget, however much falls due at once.
+use frame_support::{
+    storage::unhashed,
+    traits::Get,
+    weights::Weight,
+};
+use parity_scale_codec::{
+    Decode,
+    Encode,
+};
+use sp_std::marker::PhantomData;
+
+/// Hands out the housekeeping budget of the current block
+pub trait HousekeepingMeter {
+    /// Takes the weight of as many of `tasks`, each of `weight`, as fit in
+    /// what is left of the budget and returns how many that is
+    fn reserve(tasks: u32, weight: Weight) -> u32;
+}
+
+/// No budget, every due task runs at once
+impl HousekeepingMeter for () {
+    fn reserve(tasks: u32, _weight: Weight) -> u32 {
+        tasks
+    }
+}
+
+/// The block and the weight used in it, kept in unhashed storage so every
+/// pallet metered by a `BlockBudget` draws from the same budget
+const USED: &[u8] = b":sunshine:housekeeping:used";
+
+/// Meters `Budget` per block for all pallets using it, `Now` is the current
+/// block number
+///
+/// At least one task is run in a block nothing was reserved in yet, so a
+/// task weighing more than the whole budget cannot stall its queue.
+pub struct BlockBudget<Budget, Now, BlockNumber>(
+    PhantomData<(Budget, Now, BlockNumber)>,
+);
+
+impl<Budget, Now, BlockNumber> BlockBudget<Budget, Now, BlockNumber>
+where
+    Budget: Get<Weight>,
+    Now: Get<BlockNumber>,
+    BlockNumber: Encode + Decode + PartialEq,
+{
+    /// The weight reserved in the current block so far
+    pub fn used() -> Weight {
+        match unhashed::get::<(BlockNumber, Weight)>(USED) {
+            Some((block, used)) if block == Now::get() => used,
+            _ => 0,
+        }
+    }
+}
+
+impl<Budget, Now, BlockNumber> HousekeepingMeter
+    for BlockBudget<Budget, Now, BlockNumber>
+where
+    Budget: Get<Weight>,
+    Now: Get<BlockNumber>,
+    BlockNumber: Encode + Decode + PartialEq,
+{
+    fn reserve(tasks: u32, weight: Weight) -> u32 {
+        let used = Self::used();
+        let left = Budget::get().saturating_sub(used);
+        let fit = match left.checked_div(weight) {
+            Some(fit) => fit.min(tasks.into()) as u32,
+            None => tasks,
+        };
+        let fit = if fit == 0 && used == 0 {
+            tasks.min(1)
+        } else {
+            fit
+        };
+        if fit > 0 {
+            let used = used.saturating_add(weight.saturating_mul(fit.into()));
+            unhashed::put(USED, &(Now::get(), used));
+        }
+        fit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        static NOW: Cell<u32> = Cell::new(1);
+    }
+
+    struct Now;
+
+    impl Get<u32> for Now {
+        fn get() -> u32 {
+            NOW.with(|n| n.get())
+        }
+    }
+
+    struct Budget;
+
+    impl Get<Weight> for Budget {
+        fn get() -> Weight {
+            100
+        }
+    }
+
+    type Meter = BlockBudget<Budget, Now, u32>;
+
+    fn run(f: impl FnOnce()) {
+        NOW.with(|n| n.set(1));
+        sp_io::TestExternalities::default().execute_with(f)
+    }
+
+    #[test]
+    fn pallets_share_the_budget_of_a_block() {
+        run(|| {
+            assert_eq!(Meter::reserve(3, 20), 3);
+            assert_eq!(Meter::reserve(10, 15), 2);
+            assert_eq!(Meter::used(), 90);
+            assert_eq!(Meter::reserve(1, 15), 0);
+            // lighter tasks still fit in what is left
+            assert_eq!(Meter::reserve(4, 5), 2);
+            assert_eq!(Meter::used(), 100);
+        });
+    }
+
+    #[test]
+    fn the_budget_is_renewed_every_block() {
+        run(|| {
+            assert_eq!(Meter::reserve(200, 1), 100);
+            NOW.with(|n| n.set(2));
+            assert_eq!(Meter::used(), 0);
+            assert_eq!(Meter::reserve(200, 1), 100);
+        });
+    }
+
+    #[test]
+    fn one_oversized_task_runs_per_block() {
+        run(|| {
+            assert_eq!(Meter::reserve(2, 150), 1);
+            assert_eq!(Meter::reserve(1, 150), 0);
+            assert_eq!(Meter::reserve(0, 150), 0);
+        });
+    }
+
+    #[test]
+    fn weightless_tasks_all_run() {
+        run(|| {
+            assert_eq!(Meter::reserve(1000, 0), 1000);
+            assert_eq!(Meter::used(), 0);
+        });
+    }
+}
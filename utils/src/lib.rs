@@ -10,17 +10,22 @@ extern crate derive_new;
 
 pub mod bank;
 pub mod bounty;
+pub mod cid;
 pub mod court;
+pub mod deadline;
 pub mod drip;
 pub mod grant;
+pub mod housekeeping;
 pub mod insurance;
 pub mod kickback;
+pub mod merkle;
 pub mod meta;
 pub mod moloch;
 pub mod organization;
 pub mod rank;
 pub mod rfp;
 pub mod share;
+pub mod signal;
 pub mod sss;
 pub mod traits;
 pub mod vote;
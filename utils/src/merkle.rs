@@ -0,0 +1,96 @@
+//! Merkle roots over the members of an org and their shares
+//!
+//! A leaf is the hash of an encoded `(AccountId, Shares)` pair and the
+//! leaves are ordered by account. The two nodes of a pair are hashed in
+//! ascending order, so a proof is only the siblings from the leaf up, and
+//! the last node of a level with an odd length moves up unchanged. The
+//! root of an org without members is zero.
+use parity_scale_codec::Encode;
+pub use sp_core::H256;
+use sp_runtime::traits::{
+    BlakeTwo256,
+    Hash,
+};
+use sp_std::prelude::*;
+
+/// The leaf of a member holding `shares`
+pub fn membership_leaf<AccountId: Encode, Shares: Encode>(
+    who: &AccountId,
+    shares: Shares,
+) -> H256 {
+    BlakeTwo256::hash_of(&(who, shares))
+}
+
+/// The leaves of the members, in the order of their accounts
+pub fn membership_leaves<AccountId: Ord + Encode, Shares: Encode>(
+    mut members: Vec<(AccountId, Shares)>,
+) -> Vec<H256> {
+    members.sort_by(|a, b| a.0.cmp(&b.0));
+    members
+        .iter()
+        .map(|(who, shares)| membership_leaf(who, shares))
+        .collect()
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    let mut pair = [0u8; 64];
+    pair[..32].copy_from_slice(low.as_bytes());
+    pair[32..].copy_from_slice(high.as_bytes());
+    BlakeTwo256::hash(&pair)
+}
+
+fn next_level(level: &[H256]) -> Vec<H256> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            if let [a, b] = pair {
+                hash_pair(*a, *b)
+            } else {
+                pair[0]
+            }
+        })
+        .collect()
+}
+
+/// The root over the leaves, zero if there are none
+pub fn merkle_root(leaves: &[H256]) -> H256 {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// The siblings of the leaf at `index` from the bottom up, None if there is
+/// no such leaf
+pub fn merkle_proof(leaves: &[H256], mut index: usize) -> Option<Vec<H256>> {
+    if index >= leaves.len() {
+        return None
+    }
+    let mut level = leaves.to_vec();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(proof)
+}
+
+/// Whether `who` held `shares` in the org whose membership root is `root`
+pub fn verify_membership_proof<AccountId: Encode, Shares: Encode>(
+    root: H256,
+    who: &AccountId,
+    shares: Shares,
+    proof: &[H256],
+) -> bool {
+    let node = proof
+        .iter()
+        .fold(membership_leaf(who, shares), |node, sibling| {
+            hash_pair(node, *sibling)
+        });
+    node == root
+}
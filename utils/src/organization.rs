@@ -2,6 +2,11 @@ use parity_scale_codec::{
     Decode,
     Encode,
 };
+#[cfg(feature = "std")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
@@ -12,6 +17,7 @@ pub struct Relation<OrgId> {
 }
 
 #[derive(new, PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Used in `vote` and `donate` to distinguish between configurations that acknowledge ownership and don't
 pub enum OrgRep<OrgId> {
     // weighted by ownership
@@ -29,6 +35,47 @@ impl<OrgId: Copy> OrgRep<OrgId> {
     }
 }
 
+/// A unique name of an organization, lowercase ASCII padded with zeros to
+/// 32 bytes
+pub type OrgHandle = [u8; 32];
+
+/// The handle named `name`, if it is 1 to 32 lowercase ASCII letters, digits
+/// or dashes
+pub fn org_handle(name: &str) -> Option<OrgHandle> {
+    let mut handle = OrgHandle::default();
+    if name.len() > handle.len() {
+        return None
+    }
+    handle[..name.len()].copy_from_slice(name.as_bytes());
+    if is_valid_handle(&handle) {
+        Some(handle)
+    } else {
+        None
+    }
+}
+
+/// Whether the handle is a name accepted by `org_handle` padded with zeros
+pub fn is_valid_handle(handle: &OrgHandle) -> bool {
+    let len = handle_len(handle);
+    let valid =
+        |b: &u8| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-';
+    len > 0
+        && handle[..len].iter().all(valid)
+        && handle[len..].iter().all(|b| *b == 0)
+}
+
+/// The name of a valid handle
+pub fn handle_name(handle: &OrgHandle) -> &str {
+    core::str::from_utf8(&handle[..handle_len(handle)]).unwrap_or_default()
+}
+
+fn handle_len(handle: &OrgHandle) -> usize {
+    handle
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or_else(|| handle.len())
+}
+
 #[derive(new, PartialEq, Eq, Default, Clone, Encode, Decode, RuntimeDebug)]
 /// Tracks main organization state
 pub struct Organization<AccountId, OrgId, Shares, IpfsRef> {
@@ -78,6 +125,9 @@ impl<
             ..self.clone()
         }
     }
+    pub fn sudo(&self) -> Option<AccountId> {
+        self.sudo.clone()
+    }
     pub fn is_sudo(&self, cmp: &AccountId) -> bool {
         if let Some(unwrapped_sudo) = &self.sudo {
             unwrapped_sudo == cmp
@@ -97,6 +147,166 @@ impl<
             ..self.clone()
         }
     }
+    pub fn set_constitution(&self, constitution: IpfsRef) -> Self {
+        Organization {
+            constitution,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Default, Clone, Copy, Encode, Decode, RuntimeDebug)]
+/// Bitmask of the supervisor actions which require an approved member vote
+pub struct GuardedActions(pub u8);
+
+impl GuardedActions {
+    /// Issuing shares beyond the org's per block issuance limit
+    pub const ISSUE_SHARES: u8 = 1;
+    /// Burning the shares of any member other than the caller
+    pub const BURN_SHARES: u8 = 1 << 1;
+    /// Replacing the org constitution
+    pub const CHANGE_CONSTITUTION: u8 = 1 << 2;
+
+    pub fn contains(&self, guards: u8) -> bool {
+        self.0 & guards == guards
+    }
+    pub fn add(&self, guards: u8) -> Self {
+        GuardedActions(self.0 | guards)
+    }
+    pub fn remove(&self, guards: u8) -> Self {
+        GuardedActions(self.0 & !guards)
+    }
+}
+
+#[derive(PartialEq, Eq, Default, Clone, Copy, Encode, Decode, RuntimeDebug)]
+/// Bitmask of the supervisor powers an emergency grant confers, guards
+/// still apply to them
+pub struct PowerMask(pub u8);
+
+impl PowerMask {
+    /// Issuing shares to any account
+    pub const ISSUE_SHARES: u8 = 1;
+    /// Burning the shares of any member
+    pub const BURN_SHARES: u8 = 1 << 1;
+    /// Locking and unlocking the shares of any member
+    pub const LOCK_SHARES: u8 = 1 << 2;
+    /// Opening, closing and cancelling the org's votes
+    pub const MANAGE_VOTES: u8 = 1 << 3;
+
+    pub fn contains(&self, powers: u8) -> bool {
+        self.0 & powers == powers
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+/// The authority by which an account used a supervisor power
+pub enum SupervisorAuthority {
+    /// The org's supervisor
+    Supervisor,
+    /// The holder of an unexpired emergency grant
+    Emergency,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Whether an org may still open votes and change its governance
+pub enum OrgLifecycle {
+    /// The org governs itself as usual
+    Active,
+    /// The supervisor suspended the org, its open votes are cancelled
+    Frozen,
+    /// The org is being wound down and is removed once it is done
+    Dissolving,
+}
+
+impl Default for OrgLifecycle {
+    fn default() -> Self {
+        OrgLifecycle::Active
+    }
+}
+
+impl OrgLifecycle {
+    pub fn is_active(&self) -> bool {
+        *self == OrgLifecycle::Active
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// A supervisor action which may be executed once approved by a member vote
+pub enum OrgAction<AccountId, Shares, IpfsRef> {
+    IssueShares(AccountId, Shares),
+    BurnShares(AccountId, Shares),
+    ChangeConstitution(IpfsRef),
+    /// Guards can be added by the supervisor but only removed by vote
+    RemoveGuards(u8),
+    /// Grants the account the `PowerMask` for the number of blocks, always
+    /// requires a vote
+    GrantEmergencyPower(AccountId, u8, u32),
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// A guarded action awaiting the outcome of its vote
+pub struct OrgActionProposal<OrgId, Hash, VoteId> {
+    /// The org on which the action is executed
+    org: OrgId,
+    /// The hash of the scale encoded action
+    action_hash: Hash,
+    /// The vote which authorizes the action
+    vote: VoteId,
+}
+
+impl<OrgId: Copy, Hash: Clone, VoteId: Copy>
+    OrgActionProposal<OrgId, Hash, VoteId>
+{
+    pub fn org(&self) -> OrgId {
+        self.org
+    }
+    pub fn action_hash(&self) -> Hash {
+        self.action_hash.clone()
+    }
+    pub fn vote(&self) -> VoteId {
+        self.vote
+    }
+}
+
+#[derive(new, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// A proposal to spend from the org's sovereign account, approved by vote
+pub struct OrgSpendProposal<OrgId, AccountId, Currency, Hash, VoteId> {
+    /// The org whose account funds the spend
+    org: OrgId,
+    /// The recipient of the spend
+    beneficiary: AccountId,
+    /// The amount requested
+    amount: Currency,
+    /// The reason for the spend
+    reason: Hash,
+    /// The vote which authorizes the spend
+    vote: VoteId,
+}
+
+impl<
+        OrgId: Copy,
+        AccountId: Clone,
+        Currency: Copy,
+        Hash: Clone,
+        VoteId: Copy,
+    > OrgSpendProposal<OrgId, AccountId, Currency, Hash, VoteId>
+{
+    pub fn org(&self) -> OrgId {
+        self.org
+    }
+    pub fn beneficiary(&self) -> AccountId {
+        self.beneficiary.clone()
+    }
+    pub fn amount(&self) -> Currency {
+        self.amount
+    }
+    pub fn reason(&self) -> Hash {
+        self.reason.clone()
+    }
+    pub fn vote(&self) -> VoteId {
+        self.vote
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
@@ -124,6 +334,28 @@ impl<AccountId: PartialEq, Shares> Default
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// The members of an org copied into a fork of it
+pub enum MemberFilter<AccountId, Shares> {
+    /// Every member
+    All,
+    /// The members holding at least these voting shares
+    MinShares(Shares),
+    /// The listed accounts which are members, the others are skipped
+    Accounts(Vec<AccountId>),
+}
+
+impl<AccountId: PartialEq, Shares: PartialOrd> MemberFilter<AccountId, Shares> {
+    /// Whether a member holding `shares` is copied
+    pub fn keeps(&self, who: &AccountId, shares: &Shares) -> bool {
+        match self {
+            MemberFilter::All => true,
+            MemberFilter::MinShares(min) => shares >= min,
+            MemberFilter::Accounts(accounts) => accounts.contains(who),
+        }
+    }
+}
+
 #[derive(new, PartialEq, Eq, Default, Clone, Encode, Decode, RuntimeDebug)]
 /// Static terms of agreement, define how the enforced payout structure for grants
 pub struct TermsOfAgreement<AccountId, Shares, Hash> {
@@ -9,7 +9,12 @@ use parity_scale_codec::{
     Encode,
 };
 use sp_runtime::{
-    traits::Zero,
+    traits::{
+        AtLeast32BitUnsigned,
+        Saturating,
+        Zero,
+    },
+    Permill,
     RuntimeDebug,
 };
 use sp_std::prelude::*;
@@ -35,6 +40,15 @@ pub enum ProfileState {
     Unlocked,
 }
 
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// The class of shares an org issues, only voting shares mint signal
+pub enum ShareClass {
+    /// Held in the member's share profile, counts towards votes
+    Voting,
+    /// Economic ownership without a vote, read to split payouts
+    NonVoting,
+}
+
 #[derive(new, PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
 /// share profile reserves the total share amount every time but (might) have a limit on total reservations
 pub struct ShareProfile<Id, Shares, State> {
@@ -121,6 +135,81 @@ impl<Id: Clone, Shares: Copy + sp_std::ops::AddAssign + Zero>
     }
 }
 
+#[derive(new, PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// The rate at which the shares of inactive members decay towards a floor
+pub struct DecayPolicy<Shares, BlockNumber> {
+    /// The portion of their shares members lose every period of inactivity
+    decay: Permill,
+    /// The number of blocks in a period
+    period: BlockNumber,
+    /// Members never decay below this number of shares
+    floor: Shares,
+    /// The block from which members who were not active yet are inactive
+    since: BlockNumber,
+}
+
+impl<Shares: AtLeast32BitUnsigned + Copy, BlockNumber: Copy>
+    DecayPolicy<Shares, BlockNumber>
+{
+    pub fn decay(&self) -> Permill {
+        self.decay
+    }
+    pub fn period(&self) -> BlockNumber {
+        self.period
+    }
+    pub fn floor(&self) -> Shares {
+        self.floor
+    }
+    pub fn since(&self) -> BlockNumber {
+        self.since
+    }
+    /// The shares kept after `periods` of inactivity with `shares`, rounded
+    /// down so no share is kept that the decay would not leave
+    pub fn decayed(&self, shares: Shares, periods: u32) -> Shares {
+        if shares <= self.floor {
+            return shares
+        }
+        let kept = Permill::one()
+            .saturating_sub(self.decay)
+            .saturating_pow(periods as usize);
+        kept.mul_floor(shares).max(self.floor)
+    }
+}
+
+#[derive(new, PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+/// Shares issued by an org which requires consent, held by nobody until
+/// the recipient accepts them
+pub struct ShareGrant<OrgId, Shares, BlockNumber> {
+    /// The org issuing the shares
+    org: OrgId,
+    /// The number of shares issued
+    shares: Shares,
+    /// The class of the shares issued
+    class: ShareClass,
+    /// The block from which the grant can no longer be accepted
+    expires_at: BlockNumber,
+}
+
+impl<OrgId: Copy, Shares: Copy, BlockNumber: Copy + PartialOrd>
+    ShareGrant<OrgId, Shares, BlockNumber>
+{
+    pub fn org(&self) -> OrgId {
+        self.org
+    }
+    pub fn shares(&self) -> Shares {
+        self.shares
+    }
+    pub fn class(&self) -> ShareClass {
+        self.class
+    }
+    pub fn expires_at(&self) -> BlockNumber {
+        self.expires_at
+    }
+    pub fn is_expired(&self, now: BlockNumber) -> bool {
+        now >= self.expires_at
+    }
+}
+
 #[derive(PartialEq, Eq, Default, Clone, Encode, Decode, RuntimeDebug)]
 /// The account ownership for the share genesis
 pub struct WeightedVector<S, T> {
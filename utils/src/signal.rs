@@ -0,0 +1,242 @@
+//! Accounting of the signal minted for votes
+//!
+//! A `SignalBank` keeps the ballot each voter is minted signal in and the
+//! total signal issued for each vote over a `SignalStore`, so a mint policy
+//! only decides who gets how much signal. Whatever the policy, the total of
+//! a vote is the sum of the signal minted in it, no voter is minted twice
+//! in one vote and a ballot only changes while it holds the same signal.
+use sp_runtime::traits::{
+    Saturating,
+    Zero,
+};
+use sp_std::marker::PhantomData;
+
+/// A ballot which holds the signal of its voter
+pub trait MintedBallot<Signal> {
+    /// The ballot of a voter minted `signal` who did not vote yet
+    fn minted(signal: Signal) -> Self;
+    fn signal(&self) -> Signal;
+}
+
+/// The storage a `SignalBank` keeps its accounts in
+pub trait SignalStore {
+    type VoteId: Copy;
+    type AccountId;
+    type Signal: Copy + PartialEq + Zero + Saturating;
+    type Ballot: MintedBallot<Self::Signal>;
+
+    fn ballot(
+        vote_id: Self::VoteId,
+        who: &Self::AccountId,
+    ) -> Option<Self::Ballot>;
+    fn put_ballot(
+        vote_id: Self::VoteId,
+        who: &Self::AccountId,
+        ballot: Self::Ballot,
+    );
+    /// Removes the ballots of every voter in the vote
+    fn remove_ballots(vote_id: Self::VoteId);
+    fn total(vote_id: Self::VoteId) -> Option<Self::Signal>;
+    fn put_total(vote_id: Self::VoteId, total: Self::Signal);
+    fn remove_total(vote_id: Self::VoteId);
+}
+
+/// Mints, looks up, spends and prunes the signal of votes kept in `S`
+pub struct SignalBank<S>(PhantomData<S>);
+
+impl<S: SignalStore> SignalBank<S> {
+    /// The signal issued for the vote, zero if none was
+    pub fn total(vote_id: S::VoteId) -> S::Signal {
+        S::total(vote_id).unwrap_or_else(Zero::zero)
+    }
+
+    /// The signal held by `who` in the vote, None if they were minted none
+    pub fn signal(vote_id: S::VoteId, who: &S::AccountId) -> Option<S::Signal> {
+        S::ballot(vote_id, who).map(|ballot| ballot.signal())
+    }
+
+    /// Mints `signal` for `who` and adds it to the total, false without
+    /// minting for zero signal or if `who` already holds signal in the vote
+    pub fn mint(
+        vote_id: S::VoteId,
+        who: &S::AccountId,
+        signal: S::Signal,
+    ) -> bool {
+        if !Self::mint_ballot(vote_id, who, signal) {
+            return false
+        }
+        S::put_total(vote_id, Self::total(vote_id).saturating_add(signal));
+        true
+    }
+
+    /// Mints the signal of each holder and returns the new total of the
+    /// vote, which is recorded even if nothing was minted
+    pub fn mint_batch<I>(vote_id: S::VoteId, holders: I) -> S::Signal
+    where
+        I: IntoIterator<Item = (S::AccountId, S::Signal)>,
+    {
+        let mut total = Self::total(vote_id);
+        for (who, signal) in holders {
+            if Self::mint_ballot(vote_id, &who, signal) {
+                total = total.saturating_add(signal);
+            }
+        }
+        S::put_total(vote_id, total);
+        total
+    }
+
+    /// Replaces the ballot of `who` with `ballot`, false without changing
+    /// it if they hold no signal or `ballot` holds a different amount
+    pub fn spend(
+        vote_id: S::VoteId,
+        who: &S::AccountId,
+        ballot: S::Ballot,
+    ) -> bool {
+        match Self::signal(vote_id, who) {
+            Some(signal) if signal == ballot.signal() => {
+                S::put_ballot(vote_id, who, ballot);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Forgets the ballots and the total of the vote
+    pub fn prune(vote_id: S::VoteId) {
+        S::remove_ballots(vote_id);
+        S::remove_total(vote_id);
+    }
+
+    fn mint_ballot(
+        vote_id: S::VoteId,
+        who: &S::AccountId,
+        signal: S::Signal,
+    ) -> bool {
+        if signal.is_zero() || S::ballot(vote_id, who).is_some() {
+            return false
+        }
+        S::put_ballot(vote_id, who, S::Ballot::minted(signal));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        cell::RefCell,
+        collections::BTreeMap,
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct TestBallot {
+        signal: u64,
+        voted: bool,
+    }
+
+    impl MintedBallot<u64> for TestBallot {
+        fn minted(signal: u64) -> Self {
+            TestBallot {
+                signal,
+                voted: false,
+            }
+        }
+        fn signal(&self) -> u64 {
+            self.signal
+        }
+    }
+
+    thread_local! {
+        static BALLOTS: RefCell<BTreeMap<(u32, u64), TestBallot>> =
+            RefCell::new(BTreeMap::new());
+        static TOTALS: RefCell<BTreeMap<u32, u64>> =
+            RefCell::new(BTreeMap::new());
+    }
+
+    struct TestStore;
+
+    impl SignalStore for TestStore {
+        type VoteId = u32;
+        type AccountId = u64;
+        type Signal = u64;
+        type Ballot = TestBallot;
+
+        fn ballot(vote_id: u32, who: &u64) -> Option<TestBallot> {
+            BALLOTS.with(|b| b.borrow().get(&(vote_id, *who)).copied())
+        }
+        fn put_ballot(vote_id: u32, who: &u64, ballot: TestBallot) {
+            BALLOTS.with(|b| b.borrow_mut().insert((vote_id, *who), ballot));
+        }
+        fn remove_ballots(vote_id: u32) {
+            BALLOTS.with(|b| b.borrow_mut().retain(|(v, _), _| *v != vote_id));
+        }
+        fn total(vote_id: u32) -> Option<u64> {
+            TOTALS.with(|t| t.borrow().get(&vote_id).copied())
+        }
+        fn put_total(vote_id: u32, total: u64) {
+            TOTALS.with(|t| t.borrow_mut().insert(vote_id, total));
+        }
+        fn remove_total(vote_id: u32) {
+            TOTALS.with(|t| t.borrow_mut().remove(&vote_id));
+        }
+    }
+
+    type Bank = SignalBank<TestStore>;
+
+    /// The sum of the signal held in the vote
+    fn held(vote_id: u32) -> u64 {
+        BALLOTS.with(|b| {
+            b.borrow()
+                .iter()
+                .filter(|((v, _), _)| *v == vote_id)
+                .map(|(_, ballot)| ballot.signal)
+                .sum()
+        })
+    }
+
+    #[test]
+    fn the_total_is_the_signal_minted() {
+        assert_eq!(Bank::mint_batch(1, vec![(1, 10), (2, 0), (3, 5)]), 15);
+        assert_eq!(Bank::signal(1, &2), None);
+        // a voter is minted once per vote
+        assert!(!Bank::mint(1, &1, 7));
+        assert_eq!(Bank::signal(1, &1), Some(10));
+        assert!(Bank::mint(1, &4, 7));
+        assert_eq!(Bank::total(1), 22);
+        assert_eq!(held(1), Bank::total(1));
+        // votes are accounted apart
+        assert_eq!(Bank::total(2), 0);
+        assert_eq!(Bank::mint_batch(2, vec![(1, 1), (1, 1)]), 1);
+        assert_eq!(held(2), 1);
+    }
+
+    #[test]
+    fn spending_keeps_the_signal() {
+        Bank::mint(1, &1, 10);
+        let cast = TestBallot {
+            signal: 10,
+            voted: true,
+        };
+        assert!(Bank::spend(1, &1, cast));
+        assert_eq!(TestStore::ballot(1, &1), Some(cast));
+        let inflated = TestBallot {
+            signal: 11,
+            voted: true,
+        };
+        assert!(!Bank::spend(1, &1, inflated));
+        assert!(!Bank::spend(1, &2, cast));
+        assert_eq!(held(1), Bank::total(1));
+    }
+
+    #[test]
+    fn pruning_forgets_only_the_vote() {
+        Bank::mint_batch(1, vec![(1, 10), (2, 5)]);
+        Bank::mint_batch(2, vec![(1, 3)]);
+        Bank::prune(1);
+        assert_eq!(Bank::total(1), 0);
+        assert_eq!(Bank::signal(1, &1), None);
+        assert_eq!(held(1), 0);
+        assert_eq!(Bank::signal(2, &1), Some(3));
+        assert_eq!(Bank::total(2), 3);
+    }
+}
@@ -1,3 +1,11 @@
+use crate::{
+    organization::OrgLifecycle,
+    vote::VoteOutcome,
+};
+use frame_support::{
+    traits::ExistenceRequirement,
+    Parameter,
+};
 use sp_runtime::{
     DispatchError,
     DispatchResult,
@@ -42,6 +50,13 @@ pub trait GroupMembership<OrgId, AccountId> {
 use orml_utilities::OrderedSet;
 pub trait GetGroup<OrgId, AccountId> {
     fn get_group(organization: OrgId) -> Option<OrderedSet<AccountId>>;
+    /// Returns up to `limit` members from position `cursor` on and the cursor
+    /// of the next page, if there is one
+    fn get_group_paged(
+        organization: OrgId,
+        cursor: u32,
+        limit: u32,
+    ) -> Option<(Vec<AccountId>, Option<u32>)>;
 }
 /// Checks that the `total` field is correct by summing all assigned share quantities
 pub trait VerifyShape {
@@ -71,6 +86,14 @@ pub trait ShareInformation<OrgId, AccountId, Shares> {
     ) -> Option<Self::Profile>;
     /// Returns the entire membership group associated with a share identifier, fallible bc checks existence
     fn get_membership_with_shape(organization: OrgId) -> Option<Self::Genesis>;
+    /// Returns a page of the membership like `GetGroup::get_group_paged`
+    fn get_membership_with_shape_paged(
+        organization: OrgId,
+        cursor: u32,
+        limit: u32,
+    ) -> Option<(Vec<(AccountId, Shares)>, Option<u32>)>;
+    /// Restarts the decay of who's shares, called when they take part in the org
+    fn record_activity(organization: OrgId, who: &AccountId);
 }
 pub trait ShareIssuance<OrgId, AccountId, Shares>:
     ShareInformation<OrgId, AccountId, Shares>
@@ -129,6 +152,37 @@ pub trait RemoveOrganization<OrgId> {
     fn recursive_remove_organization(id: OrgId) -> DispatchResult;
 }
 
+/// Reads whether an org is active, frozen or dissolving
+pub trait GetOrgLifecycle<OrgId> {
+    fn org_lifecycle(org: OrgId) -> OrgLifecycle;
+}
+
+/// Notified whenever an org is frozen, unfrozen or starts dissolving, so
+/// that dependent pallets can wind down what they hold for it
+///
+/// Handlers run inside the extrinsic that changed the lifecycle and must
+/// queue any work proportional to the size of the org
+pub trait OrgLifecycleHandler<OrgId> {
+    fn on_lifecycle_changed(org: OrgId, lifecycle: OrgLifecycle);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<OrgId: Clone> OrgLifecycleHandler<OrgId> for Tuple {
+    // the arguments are unused by the implementation for `()`
+    #[allow(unused_variables)]
+    fn on_lifecycle_changed(org: OrgId, lifecycle: OrgLifecycle) {
+        for_tuples!(
+            #( Tuple::on_lifecycle_changed(org.clone(), lifecycle); )*
+        );
+    }
+}
+
+/// Checks the format of the cids accepted from extrinsics, given their scale
+/// encoding
+pub trait CidFormat {
+    fn is_valid(encoded: &[u8]) -> bool;
+}
+
 // ====== Vote Logic ======
 
 /// Retrieves the outcome of a vote associated with the vote identifier `vote_id`
@@ -138,6 +192,42 @@ pub trait GetVoteOutcome<VoteId> {
     fn get_vote_outcome(vote_id: VoteId) -> Result<Self::Outcome>;
 }
 
+/// Notified exactly once per vote when it is decided, expires, is closed or
+/// is cancelled, so that dependent pallets need not poll `GetVoteOutcome`
+///
+/// Handlers run inside the extrinsic or block hook that ended the vote, so
+/// they must not fail and should queue any heavy work instead of doing it
+pub trait VoteOutcomeHandler<VoteId> {
+    fn on_outcome(vote_id: VoteId, outcome: VoteOutcome);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<VoteId: Clone> VoteOutcomeHandler<VoteId> for Tuple {
+    // the arguments are unused by the implementation for `()`
+    #[allow(unused_variables)]
+    fn on_outcome(vote_id: VoteId, outcome: VoteOutcome) {
+        for_tuples!( #( Tuple::on_outcome(vote_id.clone(), outcome); )* );
+    }
+}
+
+/// Notified whenever the voting shares of a member change, so that tallies
+/// which follow current membership can be re-weighed
+///
+/// Handlers run inside the extrinsic that changed the shares and must bound
+/// the work they do per member
+pub trait ShareChangeHandler<OrgId, AccountId> {
+    fn on_shares_changed(org: OrgId, who: &AccountId);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<OrgId: Clone, AccountId> ShareChangeHandler<OrgId, AccountId> for Tuple {
+    // the arguments are unused by the implementation for `()`
+    #[allow(unused_variables)]
+    fn on_shares_changed(org: OrgId, who: &AccountId) {
+        for_tuples!( #( Tuple::on_shares_changed(org.clone(), who); )* );
+    }
+}
+
 /// Open a new vote for the organization, share_id and a custom threshold requirement
 pub trait OpenVote<OrgId, Signal, Percent, BlockNumber, Hash> {
     type VoteIdentifier;
@@ -166,6 +256,44 @@ pub trait ConfigureThreshold<Threshold, Hash, BlockNumber> {
     ) -> Result<Self::VoteId>;
 }
 
+/// Opens votes from thresholds registered with `ConfigureThreshold` and polls
+/// their outcome, for modules which cannot depend on the vote module directly
+pub trait ThresholdVote<OrgId, Hash, BlockNumber> {
+    type ThresholdId: Parameter + Copy;
+    type VoteId: Parameter + Copy;
+    /// Returns the org for which the threshold is registered
+    fn threshold_org(id: Self::ThresholdId) -> Option<OrgId>;
+    /// Returns the threshold the org registered last as its default
+    fn default_threshold(org: OrgId) -> Option<Self::ThresholdId>;
+    fn open_threshold_vote(
+        id: Self::ThresholdId,
+        topic: Option<Hash>,
+        duration: Option<BlockNumber>,
+    ) -> Result<Self::VoteId>;
+    fn threshold_vote_outcome(vote_id: Self::VoteId) -> Result<VoteOutcome>;
+}
+
+impl<OrgId, Hash, BlockNumber> ThresholdVote<OrgId, Hash, BlockNumber> for () {
+    type ThresholdId = ();
+    type VoteId = ();
+    fn threshold_org(_id: ()) -> Option<OrgId> {
+        None
+    }
+    fn default_threshold(_org: OrgId) -> Option<()> {
+        None
+    }
+    fn open_threshold_vote(
+        _id: (),
+        _topic: Option<Hash>,
+        _duration: Option<BlockNumber>,
+    ) -> Result<()> {
+        Err(DispatchError::Other("no vote module configured"))
+    }
+    fn threshold_vote_outcome(_vote_id: ()) -> Result<VoteOutcome> {
+        Err(DispatchError::Other("no vote module configured"))
+    }
+}
+
 pub trait UpdateVote<VoteId, Hash, BlockNumber> {
     fn update_vote_topic(
         vote_id: VoteId,
@@ -332,3 +460,24 @@ pub trait MolochMembership<AccountId, BankId, Currency, Shares, MProp> {
     fn poll_membership_proposal(prop: MProp) -> Result<Self::PropState>;
     fn _burn_shares(caller: AccountId, bank_id: BankId) -> DispatchResult;
 }
+
+// ====== Asset Logic ======
+
+/// Moves balances of the native currency, `None`, or of any registered
+/// asset, `Some(asset_id)`, behind a single interface
+pub trait MultiCurrency<AccountId> {
+    type AssetId: Parameter + Copy;
+    type Balance;
+
+    fn free_balance(
+        asset: Option<Self::AssetId>,
+        who: &AccountId,
+    ) -> Self::Balance;
+    fn transfer(
+        asset: Option<Self::AssetId>,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Self::Balance,
+        existence: ExistenceRequirement,
+    ) -> DispatchResult;
+}
@@ -1,14 +1,27 @@
-use crate::traits::{
-    Apply,
-    Approved,
-    Rejected,
-    VoteVector,
+use crate::{
+    signal::MintedBallot,
+    traits::{
+        Apply,
+        Approved,
+        Rejected,
+        VoteVector,
+    },
 };
 use frame_support::Parameter;
 use parity_scale_codec::{
     Decode,
     Encode,
 };
+#[cfg(feature = "std")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sp_runtime::{
+    traits::Saturating,
+    PerThing,
+    Permill,
+};
 use sp_std::prelude::*;
 
 #[derive(
@@ -37,33 +50,64 @@ impl Default for VoterView {
 )]
 /// Binary vote to express for/against with magnitude
 /// ~ vectors have direction and magnitude, not to be confused with `Vec`
-pub struct Vote<Signal, Hash> {
+pub struct Vote<Signal, Hash, BlockNumber> {
     magnitude: Signal,
     direction: VoterView,
     justification: Option<Hash>,
+    /// The block of the first ballot, zero until the voter voted
+    cast_at: BlockNumber,
+    /// The block of the latest ballot, zero until the voter voted
+    last_changed_at: BlockNumber,
 }
 
-impl<Signal: Copy, Hash: Clone> Vote<Signal, Hash> {
+impl<Signal: Copy, Hash: Clone, BlockNumber: Copy>
+    Vote<Signal, Hash, BlockNumber>
+{
+    /// The ballot after voting `new_direction` at block `now`, None if it
+    /// already is in that direction
     pub fn set_new_view(
         &self,
         new_direction: VoterView,
         new_justification: Option<Hash>,
+        now: BlockNumber,
     ) -> Option<Self> {
         if self.direction == new_direction {
             // new view not set because same object
             None
         } else {
+            // ballots reset by a topic change are cast anew
+            let cast_at = if self.direction == VoterView::Uninitialized {
+                now
+            } else {
+                self.cast_at
+            };
             Some(Vote {
                 magnitude: self.magnitude,
                 direction: new_direction,
                 justification: new_justification,
+                cast_at,
+                last_changed_at: now,
             })
         }
     }
+    /// The ballot with `magnitude` signal, cast at the same blocks
+    pub fn reweigh(&self, magnitude: Signal) -> Self {
+        Vote {
+            magnitude,
+            justification: self.justification.clone(),
+            ..*self
+        }
+    }
+    pub fn cast_at(&self) -> BlockNumber {
+        self.cast_at
+    }
+    pub fn last_changed_at(&self) -> BlockNumber {
+        self.last_changed_at
+    }
 }
 
-impl<Signal: Copy, Hash: Clone> VoteVector<Signal, VoterView, Hash>
-    for Vote<Signal, Hash>
+impl<Signal: Copy, Hash: Clone, BlockNumber> VoteVector<Signal, VoterView, Hash>
+    for Vote<Signal, Hash, BlockNumber>
 {
     fn magnitude(&self) -> Signal {
         self.magnitude
@@ -76,12 +120,60 @@ impl<Signal: Copy, Hash: Clone> VoteVector<Signal, VoterView, Hash>
     }
 }
 
+impl<Signal: Copy, Hash, BlockNumber: Default> MintedBallot<Signal>
+    for Vote<Signal, Hash, BlockNumber>
+{
+    fn minted(signal: Signal) -> Self {
+        Vote {
+            magnitude: signal,
+            direction: VoterView::Uninitialized,
+            justification: None,
+            cast_at: BlockNumber::default(),
+            last_changed_at: BlockNumber::default(),
+        }
+    }
+    fn signal(&self) -> Signal {
+        self.magnitude
+    }
+}
+
+#[derive(
+    new, Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// The layout of `Vote` before the blocks of the ballots, kept to migrate
+/// the ballots stored with it
+pub struct VoteV1<Signal, Hash> {
+    magnitude: Signal,
+    direction: VoterView,
+    justification: Option<Hash>,
+}
+
+impl<Signal, Hash, BlockNumber: Default> From<VoteV1<Signal, Hash>>
+    for Vote<Signal, Hash, BlockNumber>
+{
+    /// The blocks of ballots cast before they were recorded are zero
+    fn from(old: VoteV1<Signal, Hash>) -> Vote<Signal, Hash, BlockNumber> {
+        Vote {
+            magnitude: old.magnitude,
+            direction: old.direction,
+            justification: old.justification,
+            cast_at: BlockNumber::default(),
+            last_changed_at: BlockNumber::default(),
+        }
+    }
+}
+
 #[derive(
     new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
 )]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ThresholdInput<OrgId, Threshold> {
     org: OrgId,
     threshold: Threshold,
+    /// The share of the org's shares no voter holds more signal than in the
+    /// votes opened from the threshold
+    #[new(default)]
+    per_voter_cap: Option<Permill>,
 }
 
 impl<OrgId: Copy, Signal: Copy, Percent: Copy>
@@ -93,6 +185,14 @@ impl<OrgId: Copy, Signal: Copy, Percent: Copy>
     pub fn threshold(&self) -> XorThreshold<Signal, Percent> {
         self.threshold.clone()
     }
+    pub fn per_voter_cap(&self) -> Option<Permill> {
+        self.per_voter_cap
+    }
+    /// Caps each voter's signal at `cap` of the org's shares
+    pub fn with_per_voter_cap(mut self, cap: Permill) -> Self {
+        self.per_voter_cap = Some(cap);
+        self
+    }
 }
 
 #[derive(
@@ -119,14 +219,40 @@ impl<Id: Copy, OrgId: Copy, Signal: Copy, Percent: Copy>
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum XorThreshold<S, P> {
     Signal(Threshold<S>),
+    /// Percents of all signal that can vote
     Percent(Threshold<P>),
+    /// Percents of the given base
+    PercentOf(Threshold<P>, PercentBase<P>),
+}
+
+#[derive(
+    Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// What the percents of a percent threshold are of
+pub enum PercentBase<P> {
+    /// All signal that can vote, fixed when the vote opens
+    TotalIssuance,
+    /// The signal cast so far, abstentions included. The vote is decided
+    /// by the ballots cast when it ends, or as soon as a ballot crosses a
+    /// threshold once the turnout reaches `min_turnout` of all signal that
+    /// can vote
+    VotesCast { min_turnout: Option<P> },
+}
+
+impl<P> Default for PercentBase<P> {
+    fn default() -> PercentBase<P> {
+        PercentBase::TotalIssuance
+    }
 }
 
 #[derive(
     new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
 )]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Threshold<T> {
     in_favor: T,
     against: Option<T>,
@@ -144,6 +270,25 @@ impl<T: Copy + PartialOrd> Threshold<T> {
     }
 }
 
+#[derive(
+    Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// The outcome of a vote change which crosses both thresholds at once
+pub enum TieBreak {
+    /// The vote is approved
+    FavorWins,
+    /// The vote is rejected
+    AgainstWins,
+    /// Neither outcome is set and the vote remains open
+    StayOpen,
+}
+
+impl Default for TieBreak {
+    fn default() -> TieBreak {
+        TieBreak::AgainstWins
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug)]
 /// The state of an ongoing vote
 pub struct VoteState<Signal, BlockNumber, Hash> {
@@ -159,14 +304,177 @@ pub struct VoteState<Signal, BlockNumber, Hash> {
     all_possible_turnout: Signal,
     /// The threshold requirement for passage
     threshold: Threshold<Signal>,
+    /// The outcome if both thresholds are crossed at once
+    tie_break: TieBreak,
     /// The time at which this vote state is initialized
     initialized: BlockNumber,
     /// The time at which this vote state expires
     ends: Option<BlockNumber>,
+    /// The final blocks before `ends` in which only voters who already
+    /// voted may change their ballot
+    change_only_window: Option<BlockNumber>,
+    /// The percents of the votes cast `threshold` follows, for votes on
+    /// the votes cast
+    votes_cast: Option<VotesCastThreshold<Signal>>,
     /// The vote outcome
     outcome: VoteOutcome,
 }
 
+#[derive(
+    new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// A percent threshold of the votes cast
+pub struct VotesCastThreshold<Signal> {
+    threshold: Threshold<Permill>,
+    /// The turnout from which a ballot crossing a threshold decides the
+    /// vote before it ends
+    min_turnout: Option<Signal>,
+}
+
+impl<Signal: Copy> VotesCastThreshold<Signal> {
+    pub fn threshold(&self) -> Threshold<Permill> {
+        self.threshold.clone()
+    }
+    pub fn min_turnout(&self) -> Option<Signal> {
+        self.min_turnout
+    }
+}
+
+#[derive(
+    new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// The layout of `VoteState` before the votes cast threshold, kept to
+/// migrate the votes stored with it
+pub struct VoteStateV3<Signal, BlockNumber, Hash> {
+    topic: Option<Hash>,
+    in_favor: Signal,
+    against: Signal,
+    turnout: Signal,
+    all_possible_turnout: Signal,
+    threshold: Threshold<Signal>,
+    tie_break: TieBreak,
+    initialized: BlockNumber,
+    ends: Option<BlockNumber>,
+    change_only_window: Option<BlockNumber>,
+    outcome: VoteOutcome,
+}
+
+impl<Signal, BlockNumber, Hash> From<VoteStateV3<Signal, BlockNumber, Hash>>
+    for VoteState<Signal, BlockNumber, Hash>
+{
+    fn from(
+        old: VoteStateV3<Signal, BlockNumber, Hash>,
+    ) -> VoteState<Signal, BlockNumber, Hash> {
+        VoteState {
+            topic: old.topic,
+            in_favor: old.in_favor,
+            against: old.against,
+            turnout: old.turnout,
+            all_possible_turnout: old.all_possible_turnout,
+            threshold: old.threshold,
+            tie_break: old.tie_break,
+            initialized: old.initialized,
+            ends: old.ends,
+            change_only_window: old.change_only_window,
+            votes_cast: None,
+            outcome: old.outcome,
+        }
+    }
+}
+
+#[derive(
+    new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// The layout of `VoteState` before the change only window, kept to migrate
+/// the votes stored with it
+pub struct VoteStateV2<Signal, BlockNumber, Hash> {
+    topic: Option<Hash>,
+    in_favor: Signal,
+    against: Signal,
+    turnout: Signal,
+    all_possible_turnout: Signal,
+    threshold: Threshold<Signal>,
+    tie_break: TieBreak,
+    initialized: BlockNumber,
+    ends: Option<BlockNumber>,
+    outcome: VoteOutcome,
+}
+
+impl<Signal, BlockNumber, Hash> From<VoteStateV2<Signal, BlockNumber, Hash>>
+    for VoteState<Signal, BlockNumber, Hash>
+{
+    fn from(
+        old: VoteStateV2<Signal, BlockNumber, Hash>,
+    ) -> VoteState<Signal, BlockNumber, Hash> {
+        VoteState {
+            topic: old.topic,
+            in_favor: old.in_favor,
+            against: old.against,
+            turnout: old.turnout,
+            all_possible_turnout: old.all_possible_turnout,
+            threshold: old.threshold,
+            tie_break: old.tie_break,
+            initialized: old.initialized,
+            ends: old.ends,
+            change_only_window: None,
+            votes_cast: None,
+            outcome: old.outcome,
+        }
+    }
+}
+
+#[derive(
+    new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// The layout of `VoteState` before the tie break, kept to migrate the votes
+/// stored with it
+pub struct VoteStateV1<Signal, BlockNumber, Hash> {
+    topic: Option<Hash>,
+    in_favor: Signal,
+    against: Signal,
+    turnout: Signal,
+    all_possible_turnout: Signal,
+    threshold: Threshold<Signal>,
+    initialized: BlockNumber,
+    ends: Option<BlockNumber>,
+    outcome: VoteOutcome,
+}
+
+impl<Signal, BlockNumber, Hash> From<VoteStateV1<Signal, BlockNumber, Hash>>
+    for VoteState<Signal, BlockNumber, Hash>
+{
+    fn from(
+        old: VoteStateV1<Signal, BlockNumber, Hash>,
+    ) -> VoteState<Signal, BlockNumber, Hash> {
+        VoteState {
+            topic: old.topic,
+            in_favor: old.in_favor,
+            against: old.against,
+            turnout: old.turnout,
+            all_possible_turnout: old.all_possible_turnout,
+            threshold: old.threshold,
+            tie_break: TieBreak::default(),
+            initialized: old.initialized,
+            ends: old.ends,
+            change_only_window: None,
+            votes_cast: None,
+            outcome: old.outcome,
+        }
+    }
+}
+
+/// `a - b`, or zero if `b` exceeds `a`
+fn saturating_sub<Signal>(a: Signal, b: Signal) -> Signal
+where
+    Signal: From<u32> + sp_std::ops::Sub<Output = Signal> + PartialOrd,
+{
+    if a > b {
+        a - b
+    } else {
+        0u32.into()
+    }
+}
+
 impl<
         Signal: Parameter
             + From<u32>
@@ -174,6 +482,9 @@ impl<
             + Default
             + sp_std::ops::Add<Output = Signal>
             + sp_std::ops::Sub<Output = Signal>
+            + sp_std::ops::Mul<Output = Signal>
+            + sp_std::ops::Div<Output = Signal>
+            + sp_std::ops::Rem<Output = Signal>
             + PartialOrd,
         BlockNumber: Parameter + Copy + Default,
         Hash: Clone,
@@ -193,8 +504,11 @@ impl<
             turnout: 0u32.into(),
             all_possible_turnout,
             threshold,
+            tie_break: TieBreak::default(),
             initialized,
             ends,
+            change_only_window: None,
+            votes_cast: None,
             outcome: VoteOutcome::Voting,
         }
     }
@@ -211,10 +525,38 @@ impl<
             turnout: 0u32.into(),
             all_possible_turnout,
             threshold: Threshold::new(all_possible_turnout, None),
+            tie_break: TieBreak::default(),
+            initialized,
+            ends,
+            change_only_window: None,
+            votes_cast: None,
+            outcome: VoteOutcome::Voting,
+        }
+    }
+    /// A vote on the percents of the votes cast, its signal threshold
+    /// follows the turnout
+    pub fn new_on_votes_cast(
+        topic: Option<Hash>,
+        all_possible_turnout: Signal,
+        votes_cast: VotesCastThreshold<Signal>,
+        initialized: BlockNumber,
+        ends: Option<BlockNumber>,
+    ) -> VoteState<Signal, BlockNumber, Hash> {
+        VoteState {
+            topic,
+            in_favor: 0u32.into(),
+            against: 0u32.into(),
+            turnout: 0u32.into(),
+            all_possible_turnout,
+            threshold: Threshold::new(0u32.into(), None),
+            tie_break: TieBreak::default(),
             initialized,
             ends,
+            change_only_window: None,
+            votes_cast: Some(votes_cast),
             outcome: VoteOutcome::Voting,
         }
+        .rebase()
     }
     pub fn topic(&self) -> Option<Hash> {
         self.topic.clone()
@@ -243,17 +585,61 @@ impl<
     pub fn threshold(&self) -> Threshold<Signal> {
         self.threshold.clone()
     }
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+    pub fn set_tie_break(&self, tie_break: TieBreak) -> Self {
+        Self {
+            tie_break,
+            ..self.clone()
+        }
+    }
+    pub fn change_only_window(&self) -> Option<BlockNumber> {
+        self.change_only_window
+    }
+    pub fn set_change_only_window(&self, window: Option<BlockNumber>) -> Self {
+        Self {
+            change_only_window: window,
+            ..self.clone()
+        }
+    }
+    pub fn votes_cast(&self) -> Option<VotesCastThreshold<Signal>> {
+        self.votes_cast.clone()
+    }
     pub fn outcome(&self) -> VoteOutcome {
         self.outcome
     }
+    /// The state once the vote ended, a vote on the votes cast which was
+    /// not decided early is decided by the ballots cast by then
+    pub fn settle(&self) -> Self {
+        let zero: Signal = 0u32.into();
+        match self.votes_cast {
+            // without ballots a percent of nothing is not an approval
+            Some(_)
+                if self.outcome == VoteOutcome::Voting
+                    && self.turnout > zero =>
+            {
+                self.latch()
+            }
+            _ => self.clone(),
+        }
+    }
+    pub fn cancel(&self) -> Self {
+        Self {
+            outcome: VoteOutcome::Cancelled,
+            ..self.clone()
+        }
+    }
     pub fn update_topic_and_clear_state(&self, new_topic: Hash) -> Self {
         VoteState {
             in_favor: 0u32.into(),
             against: 0u32.into(),
             turnout: 0u32.into(),
             topic: Some(new_topic),
+            outcome: VoteOutcome::Voting,
             ..self.clone()
         }
+        .rebase()
     }
     pub fn update_topic_without_clearing_state(&self, new_topic: Hash) -> Self {
         VoteState {
@@ -261,24 +647,156 @@ impl<
             ..self.clone()
         }
     }
-    fn set_outcome(&self) -> Self {
-        let rejected = if let Some(rejection_outcome) = self.rejected() {
-            rejection_outcome
-        } else {
-            false
+    /// Adds signal to the electorate, for votes in which signal is bonded while voting
+    pub fn add_possible_turnout(&self, magnitude: Signal) -> Self {
+        VoteState {
+            all_possible_turnout: self.all_possible_turnout + magnitude,
+            ..self.clone()
+        }
+    }
+    /// Removes a vote from the tally and its signal from the electorate so that
+    /// it can be reapplied with a different magnitude
+    pub fn remove_vote(&self, magnitude: Signal, direction: VoterView) -> Self {
+        let (in_favor, against) = match direction {
+            VoterView::InFavor => {
+                (saturating_sub(self.in_favor, magnitude), self.against)
+            }
+            VoterView::Against => {
+                (self.in_favor, saturating_sub(self.against, magnitude))
+            }
+            _ => (self.in_favor, self.against),
+        };
+        let turnout = match direction {
+            VoterView::Uninitialized => self.turnout,
+            _ => saturating_sub(self.turnout, magnitude),
+        };
+        VoteState {
+            in_favor,
+            against,
+            turnout,
+            all_possible_turnout: saturating_sub(
+                self.all_possible_turnout,
+                magnitude,
+            ),
+            ..self.clone()
+        }
+    }
+    /// The tally and the signal still required to pass, derived from the stored state
+    pub fn progress(&self) -> VoteProgress<Signal> {
+        let abstain =
+            saturating_sub(self.turnout, self.in_favor + self.against);
+        // a vote which has met its rejection threshold can no longer pass
+        let remaining_to_pass = match self.outcome {
+            VoteOutcome::Rejected => None,
+            _ => Some(saturating_sub(self.threshold.in_favor(), self.in_favor)),
         };
-        if self.approved() {
-            VoteState {
-                outcome: VoteOutcome::Approved,
-                ..self.clone()
+        let remaining_to_reject = match self.outcome {
+            VoteOutcome::Approved => None,
+            _ => {
+                self.threshold
+                    .against()
+                    .map(|t| saturating_sub(t, self.against))
             }
-        } else if rejected {
-            VoteState {
-                outcome: VoteOutcome::Rejected,
-                ..self.clone()
+        };
+        VoteProgress {
+            in_favor: self.in_favor,
+            against: self.against,
+            abstain,
+            turnout: self.turnout,
+            threshold_in_favor: self.threshold.in_favor(),
+            threshold_against: self.threshold.against(),
+            remaining_to_pass,
+            remaining_to_reject,
+            outcome: self.outcome,
+        }
+    }
+    /// Sets the outcome after a change of the tally. A vote on the votes
+    /// cast is only decided before it ends once its turnout reaches the
+    /// minimum turnout
+    fn set_outcome(&self) -> Self {
+        let state = self.rebase();
+        match state.votes_cast.as_ref().map(|v| v.min_turnout) {
+            None => state.latch(),
+            Some(Some(min_turnout)) if state.turnout >= min_turnout => {
+                state.latch()
             }
-        } else {
-            self.clone()
+            Some(_) => state,
+        }
+    }
+    /// Derives the signal threshold of a vote on the votes cast from its
+    /// turnout
+    fn rebase(&self) -> Self {
+        match &self.votes_cast {
+            Some(votes_cast) => {
+                let t = &votes_cast.threshold;
+                VoteState {
+                    threshold: Threshold::new(
+                        percent_of(t.in_favor(), self.turnout),
+                        t.against().map(|p| percent_of(p, self.turnout)),
+                    ),
+                    ..self.clone()
+                }
+            }
+            None => self.clone(),
+        }
+    }
+    /// Sets the outcome from the thresholds crossed by the tally, resolving
+    /// a tally which crosses both thresholds by the `tie_break`; the outcome
+    /// is unchanged if neither threshold is crossed
+    fn latch(&self) -> Self {
+        let rejected = self.rejected().unwrap_or(false);
+        let outcome = match (self.approved(), rejected) {
+            (true, true) => {
+                match self.tie_break {
+                    TieBreak::FavorWins => VoteOutcome::Approved,
+                    TieBreak::AgainstWins => VoteOutcome::Rejected,
+                    TieBreak::StayOpen => VoteOutcome::Voting,
+                }
+            }
+            (true, false) => VoteOutcome::Approved,
+            (false, true) => VoteOutcome::Rejected,
+            (false, false) => return self.clone(),
+        };
+        VoteState {
+            outcome,
+            ..self.clone()
+        }
+    }
+}
+
+/// `percent` of `signal` rounded up, like `Permill::mul_ceil` without its
+/// `Unsigned` bound
+fn percent_of<Signal>(percent: Permill, signal: Signal) -> Signal
+where
+    Signal: Copy
+        + From<u32>
+        + PartialOrd
+        + sp_std::ops::Add<Output = Signal>
+        + sp_std::ops::Mul<Output = Signal>
+        + sp_std::ops::Div<Output = Signal>
+        + sp_std::ops::Rem<Output = Signal>,
+{
+    let parts: Signal = percent.deconstruct().into();
+    let accuracy: Signal = Permill::ACCURACY.into();
+    let rest = signal % accuracy * parts;
+    let rounded = if rest % accuracy > 0u32.into() {
+        rest / accuracy + 1u32.into()
+    } else {
+        rest / accuracy
+    };
+    signal / accuracy * parts + rounded
+}
+
+impl<Signal, BlockNumber: Copy + PartialOrd + Saturating, Hash>
+    VoteState<Signal, BlockNumber, Hash>
+{
+    /// Whether a ballot from an account which did not vote yet is accepted
+    /// at block `now`, it is not in the final `change_only_window` blocks up
+    /// to and including `ends`
+    pub fn accepts_new_ballots(&self, now: BlockNumber) -> bool {
+        match (self.ends, self.change_only_window) {
+            (Some(ends), Some(window)) => now.saturating_add(window) <= ends,
+            _ => true,
         }
     }
 }
@@ -290,7 +808,10 @@ impl<
             + Default
             + PartialOrd
             + sp_std::ops::Add<Output = Signal>
-            + sp_std::ops::Sub<Output = Signal>,
+            + sp_std::ops::Sub<Output = Signal>
+            + sp_std::ops::Mul<Output = Signal>
+            + sp_std::ops::Div<Output = Signal>
+            + sp_std::ops::Rem<Output = Signal>,
         BlockNumber: Parameter + Copy + Default,
         Hash: Clone,
     > Approved for VoteState<Signal, BlockNumber, Hash>
@@ -307,7 +828,10 @@ impl<
             + Default
             + PartialOrd
             + sp_std::ops::Add<Output = Signal>
-            + sp_std::ops::Sub<Output = Signal>,
+            + sp_std::ops::Sub<Output = Signal>
+            + sp_std::ops::Mul<Output = Signal>
+            + sp_std::ops::Div<Output = Signal>
+            + sp_std::ops::Rem<Output = Signal>,
         BlockNumber: Parameter + Copy + Default,
         Hash: Clone,
     > Rejected for VoteState<Signal, BlockNumber, Hash>
@@ -329,6 +853,9 @@ impl<
             + Default
             + sp_std::ops::Add<Output = Signal>
             + sp_std::ops::Sub<Output = Signal>
+            + sp_std::ops::Mul<Output = Signal>
+            + sp_std::ops::Div<Output = Signal>
+            + sp_std::ops::Rem<Output = Signal>
             + PartialOrd,
         Hash: Clone,
         BlockNumber: Parameter + Copy + Default,
@@ -427,6 +954,61 @@ impl<
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug)]
+/// A read-only summary of a vote's tally against its thresholds
+pub struct VoteProgress<Signal> {
+    /// All signal in favor
+    in_favor: Signal,
+    /// All signal against
+    against: Signal,
+    /// All signal that voted but abstained
+    abstain: Signal,
+    /// All signal that votes at all
+    turnout: Signal,
+    /// The signal in favor required for passage
+    threshold_in_favor: Signal,
+    /// The signal against required for rejection, if any
+    threshold_against: Option<Signal>,
+    /// The signal in favor still required for passage, saturating at zero;
+    /// `None` once the vote is rejected and can no longer pass
+    remaining_to_pass: Option<Signal>,
+    /// The signal against still required for rejection, saturating at zero;
+    /// `None` without a rejection threshold or once the vote is approved
+    remaining_to_reject: Option<Signal>,
+    /// The vote outcome
+    outcome: VoteOutcome,
+}
+
+impl<Signal: Copy> VoteProgress<Signal> {
+    pub fn in_favor(&self) -> Signal {
+        self.in_favor
+    }
+    pub fn against(&self) -> Signal {
+        self.against
+    }
+    pub fn abstain(&self) -> Signal {
+        self.abstain
+    }
+    pub fn turnout(&self) -> Signal {
+        self.turnout
+    }
+    pub fn threshold_in_favor(&self) -> Signal {
+        self.threshold_in_favor
+    }
+    pub fn threshold_against(&self) -> Option<Signal> {
+        self.threshold_against
+    }
+    pub fn remaining_to_pass(&self) -> Option<Signal> {
+        self.remaining_to_pass
+    }
+    pub fn remaining_to_reject(&self) -> Option<Signal> {
+        self.remaining_to_reject
+    }
+    pub fn outcome(&self) -> VoteOutcome {
+        self.outcome
+    }
+}
+
 #[derive(
     PartialEq, Eq, Copy, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
 )]
@@ -441,6 +1023,9 @@ pub enum VoteOutcome {
     Approved,
     /// The VoteState is rejected
     Rejected,
+    /// The vote was cancelled by its org supervisor or creator before it was
+    /// decided, it is never approved
+    Cancelled,
 }
 
 impl Default for VoteOutcome {
@@ -448,3 +1033,89 @@ impl Default for VoteOutcome {
         VoteOutcome::NotStarted
     }
 }
+
+#[derive(
+    PartialEq, Eq, Copy, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+#[non_exhaustive]
+/// Why a ballot can no longer be cast or changed
+pub enum LockReason {
+    /// The vote expired, was closed or was cancelled, with the outcome
+    /// reported for it
+    Ended(VoteOutcome),
+    /// The vote is in its change only window and the account did not vote
+    /// before it
+    NewBallotsClosed,
+}
+
+#[derive(
+    PartialEq, Eq, Copy, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// Whether an account may still cast or change its ballot in a vote
+pub enum BallotStatus<BlockNumber> {
+    /// The account holds no signal in the vote
+    NotAParticipant,
+    /// The account holds signal but did not vote yet
+    NotYetVoted,
+    /// The account voted and may change its ballot until the given block,
+    /// forever if the vote does not end
+    CanChange { until: Option<BlockNumber> },
+    /// The ballot can no longer be cast or changed
+    Locked { reason: LockReason },
+}
+
+#[derive(
+    Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// How the outcomes of the legs of a composite vote combine
+pub enum Composition {
+    /// Approved once every leg is approved, rejected once any leg is
+    And,
+    /// Approved once any leg is approved, rejected once every leg is
+    Or,
+}
+
+impl Composition {
+    /// The outcome of a composite vote from the outcomes of its legs, still
+    /// `Voting` until the legs decide it, a cancelled leg counts as rejected
+    pub fn outcome(&self, legs: &[VoteOutcome]) -> VoteOutcome {
+        let approved = |o: &VoteOutcome| *o == VoteOutcome::Approved;
+        let rejected = |o: &VoteOutcome| {
+            *o == VoteOutcome::Rejected || *o == VoteOutcome::Cancelled
+        };
+        match self {
+            Composition::And if legs.iter().any(rejected) => {
+                VoteOutcome::Rejected
+            }
+            Composition::And if legs.iter().all(approved) => {
+                VoteOutcome::Approved
+            }
+            Composition::Or if legs.iter().any(approved) => {
+                VoteOutcome::Approved
+            }
+            Composition::Or if legs.iter().all(rejected) => {
+                VoteOutcome::Rejected
+            }
+            _ => VoteOutcome::Voting,
+        }
+    }
+}
+
+#[derive(
+    new, PartialEq, Eq, Clone, Encode, Decode, sp_runtime::RuntimeDebug,
+)]
+/// A vote decided by the outcomes of a few votes of other orgs, its legs
+pub struct CompositeVoteState<VoteId> {
+    composition: Composition,
+    /// The vote of each leg, in the order the legs were given
+    legs: Vec<VoteId>,
+}
+
+impl<VoteId: Copy> CompositeVoteState<VoteId> {
+    pub fn composition(&self) -> Composition {
+        self.composition
+    }
+    pub fn legs(&self) -> Vec<VoteId> {
+        self.legs.clone()
+    }
+}